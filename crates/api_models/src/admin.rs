@@ -102,6 +102,9 @@ pub struct MerchantAccountCreate {
     pub organization_id: Option<String>,
 
     pub payment_link_config: Option<PaymentLinkConfig>,
+
+    /// The branding and events of the transactional emails sent to the customers of the merchant
+    pub email_config: Option<MerchantEmailConfig>,
 }
 
 #[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
@@ -193,6 +196,9 @@ pub struct MerchantAccountUpdate {
     pub default_profile: Option<String>,
 
     pub payment_link_config: Option<serde_json::Value>,
+
+    /// The branding and events of the transactional emails sent to the customers of the merchant
+    pub email_config: Option<MerchantEmailConfig>,
 }
 
 #[derive(Clone, Debug, ToSchema, Serialize)]
@@ -288,6 +294,10 @@ pub struct MerchantAccountResponse {
     pub recon_status: enums::ReconStatus,
 
     pub payment_link_config: Option<serde_json::Value>,
+
+    /// The branding and events of the transactional emails sent to the customers of the merchant
+    #[schema(value_type = Option<MerchantEmailConfig>)]
+    pub email_config: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -518,11 +528,32 @@ pub struct PrimaryBusinessDetails {
     pub business: String,
 }
 
-#[derive(Clone, Debug, Deserialize, ToSchema, Serialize, PartialEq)]
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize, PartialEq)]
 #[serde(deny_unknown_fields)]
 pub struct PaymentLinkConfig {
     pub merchant_logo: Option<String>,
     pub color_scheme: Option<PaymentLinkColorSchema>,
+
+    /// The name shown to the customer on the payment link, defaults to the merchant name
+    #[schema(max_length = 64, example = "Ekart Retail")]
+    pub display_name: Option<String>,
+
+    /// Additional CSS applied to the payment link and its status page
+    #[schema(
+        max_length = 10000,
+        example = ".hyper-checkout-payment-price { font-weight: 700; }"
+    )]
+    pub custom_css: Option<String>,
+
+    /// The locale that the payment link is shown in, such as `fr` or `fr-CA`. Falls back to
+    /// English when the locale is not supported
+    #[schema(example = "en")]
+    pub locale: Option<String>,
+
+    /// The number of seconds after its creation that a payment link expires, when no
+    /// `link_expiry` is provided while creating it
+    #[schema(minimum = 60, example = 86400)]
+    pub expiry_in_seconds: Option<u32>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize, PartialEq)]
@@ -534,6 +565,63 @@ pub struct PaymentLinkColorSchema {
     pub secondary_color: Option<String>,
 }
 
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize, PartialEq)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantEmailConfig {
+    /// The name shown as the sender of the emails, defaults to the merchant name
+    #[schema(max_length = 255, example = "Ekart Retail")]
+    pub sender_name: Option<String>,
+
+    /// The URL of the logo shown in the header of the emails
+    #[schema(example = "https://example.com/logo.png")]
+    pub logo_url: Option<String>,
+
+    /// The colour of the header and buttons of the emails
+    #[schema(example = "#0099FF")]
+    pub primary_color: Option<String>,
+
+    /// The colour of the background of the emails
+    #[schema(example = "#F5F5F5")]
+    pub background_color: Option<String>,
+
+    /// The locale the emails are rendered in, such as `en` or `fr-FR`. Defaults to `en`
+    #[schema(max_length = 16, example = "en")]
+    pub locale: Option<String>,
+
+    /// The events for which emails are sent to customers. No emails are sent if this is empty
+    #[serde(default)]
+    pub enabled_events: Vec<TransactionalEmailEvent>,
+}
+
+/// The events for which transactional emails can be sent to customers
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Deserialize,
+    Eq,
+    Hash,
+    PartialEq,
+    Serialize,
+    ToSchema,
+    strum::Display,
+    strum::EnumString,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum TransactionalEmailEvent {
+    /// A receipt sent when a payment succeeds
+    PaymentSucceeded,
+    /// A notification sent when a refund is processed
+    RefundProcessed,
+    /// An email containing the payment link, sent when a payment link is created
+    PaymentLinkCreated,
+    /// An email containing the invoice, sent when an invoice is finalized
+    InvoiceIssued,
+    /// A reminder sent to customers of invoices that have not been paid
+    InvoiceReminder,
+}
+
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
 #[serde(deny_unknown_fields)]
 pub struct WebhookDetails {
@@ -955,6 +1043,9 @@ pub struct ToggleKVResponse {
     /// Status of KV for the specific merchant
     #[schema(example = true)]
     pub kv_enabled: bool,
+    /// Status of the latest migration between storage schemes for the merchant, if any
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub migration_status: Option<KvMigrationStatus>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
@@ -964,6 +1055,144 @@ pub struct ToggleKVRequest {
     pub kv_enabled: bool,
 }
 
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum KvMigrationStatus {
+    /// Entries written while KV was enabled are still being drained to the database. New
+    /// entries are written to the database, while reads consult both Redis and the database
+    /// until the migration completes.
+    Draining,
+    /// The merchant has switched to the target storage scheme
+    Completed,
+    /// The migration was cancelled before it completed
+    Cancelled,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct KvMigrationStatusResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// Status of KV for the specific merchant
+    #[schema(example = true)]
+    pub kv_enabled: bool,
+    /// Status of KV for the specific merchant once the migration completes
+    #[schema(example = false)]
+    pub target_kv_enabled: bool,
+    /// Status of the migration
+    pub status: KvMigrationStatus,
+    /// Number of entries of the merchant yet to be drained to the database
+    #[schema(example = 0)]
+    pub pending_entries: usize,
+    /// The time at which the migration was started
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub started_at: time::PrimitiveDateTime,
+    /// The time at which the migration completed or was cancelled
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub completed_at: Option<time::PrimitiveDateTime>,
+}
+
+/// The entities holding data encrypted with the data encryption key of the merchant
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ToSchema, strum::Display)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum EncryptedEntity {
+    MerchantAccount,
+    Customer,
+    Address,
+    MerchantConnectorAccount,
+    PaymentMethod,
+}
+
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(rename_all = "snake_case")]
+pub enum MerchantKeyRotationStatus {
+    /// Data encrypted with the previous keys is being re-encrypted with the new key
+    InProgress,
+    /// All data of the merchant is encrypted with the new key
+    Completed,
+    /// Re-encrypting the data failed. Rotating the key again resumes re-encrypting the data with
+    /// the same key
+    Failed,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct EncryptedEntityProgress {
+    /// The entity holding the encrypted data
+    pub entity: EncryptedEntity,
+    /// Number of records of the entity processed so far
+    #[schema(example = 100)]
+    pub processed: u64,
+    /// Number of records of the entity re-encrypted with the new key so far
+    #[schema(example = 80)]
+    pub rewrapped: u64,
+    /// Whether all records of the entity have been processed
+    #[schema(example = false)]
+    pub completed: bool,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, ToSchema)]
+pub struct MerchantKeyRotationResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    /// Version of the key that data of the merchant is encrypted with
+    #[schema(example = 2)]
+    pub key_version: i32,
+    /// Status of the rotation
+    pub status: MerchantKeyRotationStatus,
+    /// Progress of re-encrypting the data of each entity with the new key
+    pub progress: Vec<EncryptedEntityProgress>,
+    /// The time at which the rotation was started
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub started_at: time::PrimitiveDateTime,
+    /// The time at which all data was re-encrypted with the new key
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub completed_at: Option<time::PrimitiveDateTime>,
+}
+
+/// A limit on the number of requests accepted within a sliding window
+#[derive(Debug, Clone, Copy, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct RateLimit {
+    /// The maximum number of requests accepted within the window
+    #[schema(example = 100)]
+    pub max_requests: u32,
+    /// The duration of the window, in seconds
+    #[schema(example = 60)]
+    pub window_in_seconds: u32,
+}
+
+#[derive(Debug, Clone, Default, Eq, PartialEq, Serialize, Deserialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct MerchantRateLimits {
+    /// The limit on all requests of the merchant. The default limit of the server applies if
+    /// this is not set.
+    pub merchant: Option<RateLimit>,
+    /// The limits on the requests of the merchant to each API, keyed by the API, such as
+    /// `payments`, `refunds` or `customers`
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, RateLimit>)]
+    pub apis: std::collections::HashMap<String, RateLimit>,
+    /// The limits on the requests made with each API key of the merchant, keyed by the key ID
+    #[serde(default)]
+    #[schema(value_type = HashMap<String, RateLimit>)]
+    pub api_keys: std::collections::HashMap<String, RateLimit>,
+}
+
+#[derive(Debug, Clone, Serialize, ToSchema)]
+pub struct MerchantRateLimitsResponse {
+    /// The identifier for the Merchant Account
+    #[schema(max_length = 255, example = "y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub merchant_id: String,
+    #[serde(flatten)]
+    pub rate_limits: MerchantRateLimits,
+}
+
 #[derive(Debug, Clone, Default, Eq, PartialEq, serde::Deserialize, serde::Serialize, ToSchema)]
 pub struct MerchantConnectorDetailsWrap {
     /// Creds Identifier is to uniquely identify the credentials. Do not send any sensitive info in this field. And do not send the string "null".
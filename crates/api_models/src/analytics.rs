@@ -0,0 +1,127 @@
+use std::collections::HashSet;
+
+use time::PrimitiveDateTime;
+
+use crate::{enums, payments::TimeRange};
+
+/// The request body for computing payment metrics over a time range.
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct GetPaymentMetricRequest {
+    /// The time range of the payment attempts the metrics are computed over, by their creation time.
+    pub time_range: TimeRange,
+    /// Splits the time range into buckets of the granularity, the metrics being computed for each
+    /// bucket. The metrics are computed over the whole time range when unset.
+    pub time_series: Option<TimeSeries>,
+    /// The dimensions the metrics are grouped by.
+    #[serde(default)]
+    pub group_by_names: Vec<PaymentDimension>,
+    /// Restricts the payment attempts the metrics are computed over.
+    #[serde(default)]
+    pub filters: PaymentFilters,
+    /// The metrics to compute. Metrics of amounts require the payments to be grouped by
+    /// currency or filtered by a single currency.
+    pub metrics: HashSet<PaymentMetric>,
+}
+
+#[derive(Clone, Copy, Debug, serde::Deserialize, serde::Serialize)]
+pub struct TimeSeries {
+    pub granularity: Granularity,
+}
+
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, serde::Deserialize, serde::Serialize, strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Granularity {
+    Minute,
+    Hour,
+    Day,
+    Week,
+    Month,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentDimension {
+    Connector,
+    PaymentMethod,
+    PaymentMethodType,
+    Currency,
+    Status,
+    AuthenticationType,
+}
+
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Deserialize, serde::Serialize, strum::Display,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentMetric {
+    /// The number of payment attempts.
+    PaymentCount,
+    /// The percentage of payment attempts that were charged.
+    PaymentSuccessRate,
+    /// The average amount of payment attempts, in the lowest denomination of their currency.
+    AvgTicketSize,
+    /// The total amount charged, in the lowest denomination of the currency of the payments.
+    PaymentProcessedAmount,
+    /// The average time (in seconds) between the creation and the last update of payment
+    /// attempts.
+    AvgLatency,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentFilters {
+    #[serde(default)]
+    pub connector: Vec<enums::Connector>,
+    #[serde(default)]
+    pub payment_method: Vec<enums::PaymentMethod>,
+    #[serde(default)]
+    pub payment_method_type: Vec<enums::PaymentMethodType>,
+    #[serde(default)]
+    pub currency: Vec<enums::Currency>,
+    #[serde(default)]
+    pub status: Vec<enums::AttemptStatus>,
+    #[serde(default)]
+    pub authentication_type: Vec<enums::AuthenticationType>,
+}
+
+/// The metrics of the payment attempts in a time bucket sharing the values of the grouped
+/// dimensions. Dimensions that are not grouped by, and metrics that were not requested, are unset.
+#[derive(Clone, Debug, Default, PartialEq, serde::Serialize)]
+pub struct PaymentMetricsBucketResponse {
+    pub connector: Option<String>,
+    pub payment_method: Option<String>,
+    pub payment_method_type: Option<String>,
+    pub currency: Option<enums::Currency>,
+    pub status: Option<enums::AttemptStatus>,
+    pub authentication_type: Option<enums::AuthenticationType>,
+    /// The start of the time bucket, unset when no time series was requested.
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub time_bucket: Option<PrimitiveDateTime>,
+    pub payment_count: Option<u64>,
+    pub payment_success_rate: Option<f64>,
+    pub avg_ticket_size: Option<f64>,
+    pub payment_processed_amount: Option<i64>,
+    pub avg_latency: Option<f64>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct MetricsResponse<T> {
+    pub query_data: Vec<T>,
+}
@@ -23,6 +23,18 @@ pub struct CreateApiKeyRequest {
     /// rotating your keys once every 6 months.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: ApiKeyExpiration,
+
+    /// The permission scopes of the API Key, each written as `<resource>:<access>`, where the
+    /// access is one of `none`, `read` or `write`. Resources without a scope cannot be accessed
+    /// with the API Key. The API Key has full access to all resources of the merchant if no
+    /// scopes are specified.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["payments:read", "refunds:write"]))]
+    pub scopes: Option<Vec<ApiKeyScope>>,
+
+    /// The IP addresses or CIDR blocks that requests made with the API Key must originate from.
+    /// Requests may originate from any IP address if this is not specified.
+    #[schema(example = json!(["10.0.0.0/8", "203.0.113.7"]))]
+    pub allowed_ips: Option<Vec<String>>,
 }
 
 /// The response body for creating an API Key.
@@ -60,6 +72,15 @@ pub struct CreateApiKeyResponse {
     /// The expiration date for the API Key.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: ApiKeyExpiration,
+
+    /// The permission scopes of the API Key. The API Key has full access to all resources of the
+    /// merchant if no scopes are specified.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["payments:read", "refunds:write"]))]
+    pub scopes: Option<Vec<ApiKeyScope>>,
+
+    /// The IP addresses or CIDR blocks that requests made with the API Key must originate from.
+    #[schema(example = json!(["10.0.0.0/8", "203.0.113.7"]))]
+    pub allowed_ips: Option<Vec<String>>,
     /*
     /// The date and time indicating when the API Key was last used.
     #[schema(example = "2022-09-10T10:11:12Z")]
@@ -102,12 +123,20 @@ pub struct RetrieveApiKeyResponse {
     /// The expiration date for the API Key.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: ApiKeyExpiration,
-    /*
+
+    /// The permission scopes of the API Key. The API Key has full access to all resources of the
+    /// merchant if no scopes are specified.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["payments:read", "refunds:write"]))]
+    pub scopes: Option<Vec<ApiKeyScope>>,
+
+    /// The IP addresses or CIDR blocks that requests made with the API Key must originate from.
+    #[schema(example = json!(["10.0.0.0/8", "203.0.113.7"]))]
+    pub allowed_ips: Option<Vec<String>>,
+
     /// The date and time indicating when the API Key was last used.
     #[schema(example = "2022-09-10T10:11:12Z")]
     #[serde(with = "common_utils::custom_serde::iso8601::option")]
     pub last_used: Option<PrimitiveDateTime>,
-    */
 }
 
 /// The request body for updating an API Key.
@@ -129,6 +158,15 @@ pub struct UpdateApiKeyRequest {
     /// rotating your keys once every 6 months.
     #[schema(example = "2022-09-10T10:11:12Z")]
     pub expiration: Option<ApiKeyExpiration>,
+
+    /// The permission scopes of the API Key, replacing the existing scopes.
+    #[schema(value_type = Option<Vec<String>>, example = json!(["payments:read", "refunds:write"]))]
+    pub scopes: Option<Vec<ApiKeyScope>>,
+
+    /// The IP addresses or CIDR blocks that requests made with the API Key must originate from,
+    /// replacing the existing ones. An empty list allows requests from any IP address.
+    #[schema(example = json!(["10.0.0.0/8", "203.0.113.7"]))]
+    pub allowed_ips: Option<Vec<String>>,
 }
 
 /// The response body for revoking an API Key.
@@ -146,6 +184,26 @@ pub struct RevokeApiKeyResponse {
     pub revoked: bool,
 }
 
+/// The request body for rotating an API Key.
+#[derive(Debug, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RotateApiKeyRequest {
+    /// The number of seconds for which the rotated API Key remains valid alongside the new API
+    /// Key, after which it is revoked. Defaults to the grace period configured for the server.
+    #[schema(example = 86400)]
+    pub grace_period_in_seconds: Option<u32>,
+}
+
+/// The response body for rotating an API Key.
+#[derive(Debug, Serialize, ToSchema)]
+pub struct RotateApiKeyResponse {
+    /// The API Key issued to replace the rotated API Key.
+    pub api_key: CreateApiKeyResponse,
+
+    /// The rotated API Key, which expires and is revoked at the end of the grace period.
+    pub rotated_api_key: RetrieveApiKeyResponse,
+}
+
 /// The constraints that are applicable when listing API Keys associated with a merchant account.
 #[derive(Clone, Debug, Deserialize)]
 #[serde(deny_unknown_fields)]
@@ -157,6 +215,55 @@ pub struct ListApiKeyConstraints {
     pub skip: Option<i64>,
 }
 
+/// The access an API Key grants to a resource. Write access includes read access.
+#[derive(
+    Clone, Copy, Debug, Eq, PartialEq, PartialOrd, Ord, strum::Display, strum::EnumString, ToSchema,
+)]
+#[strum(serialize_all = "snake_case")]
+pub enum ApiKeyAccess {
+    None,
+    Read,
+    Write,
+}
+
+/// A permission scope of an API Key, written as `<resource>:<access>`, such as `payments:read`.
+#[derive(
+    Clone, Debug, Eq, PartialEq, serde_with::SerializeDisplay, serde_with::DeserializeFromStr,
+)]
+pub struct ApiKeyScope {
+    /// The resource the scope applies to, such as `payments`, `refunds` or `customers`
+    pub resource: String,
+    /// The access granted to the resource
+    pub access: ApiKeyAccess,
+}
+
+impl std::fmt::Display for ApiKeyScope {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}:{}", self.resource, self.access)
+    }
+}
+
+impl std::str::FromStr for ApiKeyScope {
+    type Err = String;
+
+    fn from_str(scope: &str) -> Result<Self, Self::Err> {
+        let (resource, access) = scope
+            .split_once(':')
+            .filter(|(resource, _)| !resource.is_empty())
+            .ok_or_else(|| format!("invalid scope `{scope}`, expected `<resource>:<access>`"))?;
+        let access = access.parse().map_err(|_| {
+            format!(
+                "invalid access `{access}` in scope `{scope}`, expected `none`, `read` or `write`"
+            )
+        })?;
+
+        Ok(Self {
+            resource: resource.to_string(),
+            access,
+        })
+    }
+}
+
 /// The expiration date and time for an API Key.
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 #[serde(untagged)]
@@ -296,3 +403,29 @@ mod api_key_expiration_tests {
         assert_eq!(result, None);
     }
 }
+
+#[cfg(test)]
+mod api_key_scope_tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_scope_serialization() {
+        let scope = ApiKeyScope {
+            resource: "refunds".to_string(),
+            access: ApiKeyAccess::Write,
+        };
+        assert_eq!(serde_json::to_string(&scope).unwrap(), r#""refunds:write""#);
+        assert_eq!(
+            serde_json::from_str::<ApiKeyScope>(r#""refunds:write""#).unwrap(),
+            scope
+        );
+    }
+
+    #[test]
+    fn test_invalid_scope_deserialization() {
+        assert!(serde_json::from_str::<ApiKeyScope>(r#""refunds""#).is_err());
+        assert!(serde_json::from_str::<ApiKeyScope>(r#""refunds:delete""#).is_err());
+        assert!(serde_json::from_str::<ApiKeyScope>(r#"":read""#).is_err());
+    }
+}
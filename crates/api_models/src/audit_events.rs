@@ -0,0 +1,54 @@
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct AuditEventListConstraints {
+    /// The merchant whose resources were changed
+    pub merchant_id: Option<String>,
+    /// The type of the resource that was changed
+    pub resource_type: Option<enums::AuditResourceType>,
+    /// The identifier of the resource that was changed
+    pub resource_id: Option<String>,
+    /// Time less than the time at which the change was made
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(rename = "created.lt")]
+    pub created_lt: Option<PrimitiveDateTime>,
+    /// Time greater than or equals to the time at which the change was made
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(rename = "created.gte")]
+    pub created_gte: Option<PrimitiveDateTime>,
+    /// limit on the number of objects to return
+    pub limit: Option<i64>,
+}
+
+/// A change made to a resource, as recorded in the audit log.
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct AuditEventResponse {
+    /// The identifier of the audit event
+    pub audit_event_id: String,
+    /// The merchant whose resource was changed, unset for changes not specific to a merchant
+    pub merchant_id: Option<String>,
+    /// The type of the resource that was changed
+    pub resource_type: enums::AuditResourceType,
+    /// The identifier of the resource that was changed
+    pub resource_id: String,
+    /// The change made to the resource
+    pub action: enums::AuditAction,
+    /// How the request that made the change was authenticated
+    #[schema(example = "api_key")]
+    pub auth_type: String,
+    /// The API key, organization or user that made the change
+    pub actor_id: Option<String>,
+    /// The identifier of the request that made the change
+    pub request_id: Option<String>,
+    /// The fields that were changed, with their masked values before and after the change
+    #[schema(value_type = Object, example = r#"{ "disabled": { "before": false, "after": true } }"#)]
+    pub diff: serde_json::Value,
+    /// The time at which the change was made
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
@@ -97,6 +97,9 @@ pub struct DisputeEvidenceBlock {
     pub evidence_type: EvidenceType,
     /// File metadata
     pub file_metadata_response: files::FileMetadataResponse,
+    /// Pre-signed URL the evidence file can be downloaded from until it expires, when the file is
+    /// stored by the router in a backend supporting pre-signed URLs
+    pub download_url: Option<String>,
 }
 
 #[derive(Clone, Debug, Deserialize, ToSchema, Serialize)]
@@ -1,3 +1,4 @@
+use time::PrimitiveDateTime;
 use utoipa::ToSchema;
 
 #[derive(Debug, serde::Serialize, ToSchema)]
@@ -19,3 +20,14 @@ pub struct FileMetadataResponse {
     /// File availability
     pub available: bool,
 }
+
+#[derive(Debug, serde::Serialize, ToSchema)]
+pub struct FileDownloadUrlResponse {
+    /// ID of the file
+    pub file_id: String,
+    /// Pre-signed URL the file can be downloaded from until it expires
+    pub url: String,
+    /// Time at which the URL expires
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub expires_at: PrimitiveDateTime,
+}
@@ -0,0 +1,217 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvoiceCreateRequest {
+    /// The identifier of the customer that the invoice is billed to
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+
+    /// The three letter ISO currency code in which the invoice is billed
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The products or services that the invoice is billed for
+    #[schema(min_items = 1)]
+    pub line_items: Vec<InvoiceLineItemRequest>,
+
+    /// The taxes applied to the subtotal of the line items
+    pub tax_lines: Option<Vec<InvoiceTaxLineRequest>>,
+
+    /// A description of the invoice, which is shown to the customer
+    #[schema(max_length = 255, example = "Consulting services for October")]
+    pub description: Option<String>,
+
+    /// The time by which the customer is expected to pay the invoice
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub due_date: PrimitiveDateTime,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "purchase_order": "PO-1042" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvoiceLineItemRequest {
+    /// The product or service that is billed
+    #[schema(max_length = 255, example = "Consulting hours")]
+    pub description: String,
+
+    /// The number of units that are billed
+    #[schema(example = 4, minimum = 1)]
+    pub quantity: u16,
+
+    /// The price of a single unit, in the lowest denomination of the currency
+    #[schema(example = 15000, minimum = 1)]
+    pub unit_amount: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct InvoiceTaxLineRequest {
+    /// The name of the tax, which is shown to the customer
+    #[schema(max_length = 64, example = "VAT")]
+    pub name: String,
+
+    /// The rate of the tax in basis points, 2000 being a rate of 20%
+    #[schema(example = 2000, maximum = 10000)]
+    pub rate_basis_points: u16,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct InvoiceLineItem {
+    /// The product or service that is billed
+    #[schema(example = "Consulting hours")]
+    pub description: String,
+
+    /// The number of units that are billed
+    #[schema(example = 4)]
+    pub quantity: u16,
+
+    /// The price of a single unit, in the lowest denomination of the currency
+    #[schema(example = 15000)]
+    pub unit_amount: i64,
+
+    /// The amount of the line item, which is its quantity multiplied by its unit amount
+    #[schema(example = 60000)]
+    pub amount: i64,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+pub struct InvoiceTaxLine {
+    /// The name of the tax
+    #[schema(example = "VAT")]
+    pub name: String,
+
+    /// The rate of the tax in basis points
+    #[schema(example = 2000)]
+    pub rate_basis_points: u16,
+
+    /// The amount of the tax applied to the subtotal of the invoice, rounded half up to the
+    /// lowest denomination of the currency
+    #[schema(example = 12000)]
+    pub amount: i64,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct InvoiceResponse {
+    /// The identifier of the invoice
+    #[schema(example = "inv_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub invoice_id: String,
+
+    /// The identifier of the merchant that issued the invoice
+    #[schema(example = "merchant_1668273825")]
+    pub merchant_id: String,
+
+    /// The identifier of the customer that the invoice is billed to
+    #[schema(example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+
+    /// The status of the invoice
+    #[schema(value_type = InvoiceStatus, example = "open")]
+    pub status: enums::InvoiceStatus,
+
+    /// The three letter ISO currency code in which the invoice is billed
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The products or services that the invoice is billed for
+    pub line_items: Vec<InvoiceLineItem>,
+
+    /// The taxes applied to the subtotal of the line items
+    pub tax_lines: Vec<InvoiceTaxLine>,
+
+    /// The sum of the amounts of the line items
+    #[schema(example = 60000)]
+    pub subtotal: i64,
+
+    /// The sum of the amounts of the tax lines
+    #[schema(example = 12000)]
+    pub tax_amount: i64,
+
+    /// The amount that the customer has to pay, which is the subtotal along with the taxes
+    #[schema(example = 72000)]
+    pub total_amount: i64,
+
+    /// A description of the invoice
+    #[schema(example = "Consulting services for October")]
+    pub description: Option<String>,
+
+    /// The time by which the customer is expected to pay the invoice
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub due_date: PrimitiveDateTime,
+
+    /// The payment that collects the invoice, created when the invoice is finalized
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_id: Option<String>,
+
+    /// The payment link through which the customer pays the invoice
+    #[schema(example = "plink_mbabizu24mvu3mela5njyhpit4")]
+    pub payment_link_id: Option<String>,
+
+    /// The page on which the customer can view and pay the invoice, available once the invoice
+    /// is finalized
+    pub hosted_invoice_url: Option<String>,
+
+    /// The URL of the PDF of the invoice, available once the invoice is finalized
+    pub invoice_pdf_url: Option<String>,
+
+    /// The metadata attached to the invoice
+    #[schema(value_type = Option<Object>, example = r#"{ "purchase_order": "PO-1042" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the invoice was finalized
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub finalized_at: Option<PrimitiveDateTime>,
+
+    /// The time at which the invoice was paid
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub paid_at: Option<PrimitiveDateTime>,
+
+    /// The time at which the invoice was created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+/// The details of an invoice shown on its hosted page, with amounts formatted in the base unit
+/// of the currency
+#[derive(Clone, Debug, Serialize)]
+pub struct InvoicePageDetails {
+    pub invoice_id: String,
+    pub merchant_name: String,
+    pub status: enums::InvoiceStatus,
+    pub currency: enums::Currency,
+    pub description: Option<String>,
+    pub line_items: Vec<InvoicePageLineItem>,
+    pub tax_lines: Vec<InvoicePageTaxLine>,
+    pub subtotal: String,
+    pub tax_amount: String,
+    pub total_amount: String,
+    pub due_date: String,
+    pub is_overdue: bool,
+    pub link_to_pay: Option<String>,
+    pub pdf_url: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InvoicePageLineItem {
+    pub description: String,
+    pub quantity: u16,
+    pub unit_amount: String,
+    pub amount: String,
+}
+
+#[derive(Clone, Debug, Serialize)]
+pub struct InvoicePageTaxLine {
+    pub name: String,
+    pub rate: String,
+    pub amount: String,
+}
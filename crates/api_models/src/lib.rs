@@ -1,6 +1,8 @@
 #![forbid(unsafe_code)]
 pub mod admin;
+pub mod analytics;
 pub mod api_keys;
+pub mod audit_events;
 pub mod bank_accounts;
 pub mod cards_info;
 pub mod customers;
@@ -10,6 +12,7 @@ pub mod ephemeral_key;
 #[cfg(feature = "errors")]
 pub mod errors;
 pub mod files;
+pub mod invoices;
 pub mod mandates;
 pub mod organization;
 pub mod payment_methods;
@@ -17,5 +20,7 @@ pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod refunds;
+pub mod subscriptions;
+pub mod user;
 pub mod verifications;
 pub mod webhooks;
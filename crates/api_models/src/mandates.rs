@@ -35,6 +35,11 @@ pub struct MandateResponse {
     /// Details about the customer’s acceptance
     #[schema(value_type = Option<CustomerAcceptance>)]
     pub customer_acceptance: Option<payments::CustomerAcceptance>,
+    /// The maximum number of payments that can be made against the mandate within each `frequency_interval`
+    pub frequency_limit: Option<u16>,
+    /// The interval over which the payments made against the mandate are limited by `frequency_limit`
+    #[schema(value_type = Option<MandateFrequencyInterval>)]
+    pub frequency_interval: Option<api_enums::MandateFrequencyInterval>,
 }
 
 #[derive(Default, Debug, Deserialize, Serialize, ToSchema, Clone)]
@@ -91,3 +96,28 @@ pub struct MandateListConstraints {
     #[serde(rename = "created_time.gte")]
     pub created_time_gte: Option<PrimitiveDateTime>,
 }
+
+#[derive(Clone, Debug, Default, Deserialize, ToSchema, Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct MandatePaymentListConstraints {
+    /// limit on the number of objects to return
+    pub limit: Option<i64>,
+}
+
+#[derive(Debug, Deserialize, Serialize, ToSchema, Clone)]
+pub struct MandatePaymentResponse {
+    /// The identifier for the payment
+    pub payment_id: String,
+    /// The identifier for the payment attempt that was charged against the mandate
+    pub attempt_id: String,
+    /// The amount charged against the mandate
+    pub amount: i64,
+    /// The currency of the payment
+    #[schema(value_type = Currency)]
+    pub currency: api_enums::Currency,
+    /// The connector through which the payment was processed
+    pub connector: String,
+    /// The time at which the payment was made
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
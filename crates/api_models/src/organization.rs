@@ -1,3 +1,7 @@
+use masking::StrongSecret;
+
+use crate::{admin, enums};
+
 pub struct OrganizationNew {
     pub org_id: String,
     pub org_name: Option<String>,
@@ -11,3 +15,96 @@ impl OrganizationNew {
         }
     }
 }
+
+/// The request body for creating or updating an organization.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct OrganizationRequest {
+    /// The name of the organization
+    pub organization_name: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct OrganizationResponse {
+    /// The identifier for the organization
+    pub organization_id: String,
+    /// The name of the organization
+    pub organization_name: Option<String>,
+    /// The first few characters of the organization's API key, if one has been generated
+    pub api_key_prefix: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct OrganizationId {
+    pub organization_id: String,
+}
+
+/// The response body for generating the API key of an organization.
+#[derive(Debug, serde::Serialize)]
+pub struct OrganizationApiKeyResponse {
+    /// The identifier for the organization
+    pub organization_id: String,
+    /// The plaintext API key used to access the reports of the organization. Ensure you store the
+    /// API key securely as you will not be able to see it again.
+    pub api_key: StrongSecret<String>,
+    /// The first few characters of the API key
+    pub prefix: String,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct OrganizationMerchantsResponse {
+    /// The identifier for the organization
+    pub organization_id: String,
+    /// The merchant accounts belonging to the organization
+    pub merchants: Vec<admin::MerchantAccountResponse>,
+}
+
+/// The payments of a status and currency, aggregated over a time range.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct PaymentsAggregate {
+    pub status: enums::IntentStatus,
+    pub currency: Option<enums::Currency>,
+    /// The number of payments
+    pub count: i64,
+    /// The sum of the amounts of the payments, in the lowest denomination of the currency
+    pub total_amount: i64,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct MerchantPaymentsAggregate {
+    pub merchant_id: String,
+    pub aggregates: Vec<PaymentsAggregate>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct OrganizationPaymentsAggregateResponse {
+    /// The aggregates across all merchants of the organization
+    pub total: Vec<PaymentsAggregate>,
+    /// The aggregates of each merchant of the organization
+    pub merchants: Vec<MerchantPaymentsAggregate>,
+}
+
+/// The refunds of a status and currency, aggregated over a time range.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct RefundsAggregate {
+    pub status: enums::RefundStatus,
+    pub currency: enums::Currency,
+    /// The number of refunds
+    pub count: i64,
+    /// The sum of the refund amounts, in the lowest denomination of the currency
+    pub total_amount: i64,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct MerchantRefundsAggregate {
+    pub merchant_id: String,
+    pub aggregates: Vec<RefundsAggregate>,
+}
+
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct OrganizationRefundsAggregateResponse {
+    /// The aggregates across all merchants of the organization
+    pub total: Vec<RefundsAggregate>,
+    /// The aggregates of each merchant of the organization
+    pub merchants: Vec<MerchantRefundsAggregate>,
+}
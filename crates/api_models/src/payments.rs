@@ -588,6 +588,12 @@ pub struct MandateAmountData {
         "frequency": "DAILY"
     }"#)]
     pub metadata: Option<pii::SecretSerdeValue>,
+    /// The maximum number of payments that can be made against the mandate within each `frequency_interval`
+    #[schema(example = 1)]
+    pub frequency_limit: Option<u16>,
+    /// The interval over which the payments made against the mandate are limited by `frequency_limit`
+    #[schema(value_type = Option<MandateFrequencyInterval>, example = "month")]
+    pub frequency_interval: Option<api_enums::MandateFrequencyInterval>,
 }
 
 #[derive(Eq, PartialEq, Debug, serde::Deserialize, serde::Serialize, Clone, ToSchema)]
@@ -3098,6 +3104,17 @@ pub struct PaymentLinkObject {
     #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
     pub link_expiry: Option<PrimitiveDateTime>,
     pub merchant_custom_domain_name: Option<String>,
+    /// The theming, locale and expiry of this payment link, overriding the payment link
+    /// configuration of the merchant
+    pub payment_link_config: Option<admin::PaymentLinkConfig>,
+    /// The reusable payment link that this payment link was created from, which is only set
+    /// internally when a customer visits a reusable payment link
+    #[serde(skip)]
+    pub reusable_payment_link_id: Option<String>,
+    /// The invoice that this payment link collects the payment of, which is only set internally
+    /// when an invoice is finalized
+    #[serde(skip)]
+    pub invoice_id: Option<String>,
 }
 
 #[derive(Default, Debug, serde::Deserialize, Clone, ToSchema, serde::Serialize)]
@@ -3126,6 +3143,53 @@ pub struct RetrievePaymentLinkResponse {
     pub last_modified_at: PrimitiveDateTime,
     #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
     pub link_expiry: Option<PrimitiveDateTime>,
+    #[schema(value_type = PaymentLinkStatus, example = "active")]
+    pub status: api_enums::PaymentLinkStatus,
+    pub payment_link_config: Option<admin::PaymentLinkConfig>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, ToSchema, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct PaymentLinkListConstraints {
+    /// Only list the payment links of this status
+    #[schema(value_type = Option<PaymentLinkStatus>, example = "active")]
+    pub status: Option<api_enums::PaymentLinkStatus>,
+
+    /// Limit on the number of objects to return
+    #[schema(default = 10, maximum = 100)]
+    #[serde(default = "default_limit")]
+    pub limit: u32,
+
+    /// The number of objects to skip, for use in pagination
+    #[schema(default = 0)]
+    #[serde(default)]
+    pub offset: u32,
+
+    /// Time greater than or equals to the payment link created time
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(
+        default,
+        with = "common_utils::custom_serde::iso8601::option",
+        rename = "created.gte"
+    )]
+    pub created_gte: Option<PrimitiveDateTime>,
+
+    /// Time less than or equals to the payment link created time
+    #[schema(example = "2022-09-10T10:11:12Z")]
+    #[serde(
+        default,
+        with = "common_utils::custom_serde::iso8601::option",
+        rename = "created.lte"
+    )]
+    pub created_lte: Option<PrimitiveDateTime>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+pub struct PaymentLinkListResponse {
+    /// The number of payment links included in the list
+    pub size: usize,
+    // The list of payment links
+    pub data: Vec<RetrievePaymentLinkResponse>,
 }
 
 #[derive(Clone, Debug, serde::Deserialize, ToSchema, serde::Serialize)]
@@ -3145,7 +3209,164 @@ pub struct PaymentLinkDetails {
     pub expiry: PrimitiveDateTime,
     pub merchant_logo: String,
     pub return_url: String,
-    pub merchant_name: crypto::OptionalEncryptableName,
+    pub merchant_name: String,
     pub order_details: Vec<pii::SecretSerdeValue>,
     pub max_items_visible_after_collapse: i8,
+    pub locale: String,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct PaymentLinkStatusDetails {
+    pub amount: String,
+    pub currency: api_enums::Currency,
+    pub payment_id: String,
+    pub status: api_enums::IntentStatus,
+    pub is_link_expired: bool,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created: PrimitiveDateTime,
+    pub merchant_logo: String,
+    pub merchant_name: String,
+    pub return_url: Option<String>,
+    pub locale: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReusablePaymentLinkCreateRequest {
+    /// The three letter ISO currency code in which the payments of the link are made
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+
+    /// The fixed amount of every payment made through the link, in the lowest denomination of the
+    /// currency. When not provided, customers choose the amount they pay.
+    #[schema(example = 6540, minimum = 1)]
+    pub amount: Option<i64>,
+
+    /// The minimum amount that customers can choose to pay, in the lowest denomination of the
+    /// currency. Defaults to 1 when customers choose the amount.
+    #[schema(example = 100, minimum = 1)]
+    pub min_amount: Option<i64>,
+
+    /// The maximum amount that customers can choose to pay, in the lowest denomination of the
+    /// currency
+    #[schema(example = 100000)]
+    pub max_amount: Option<i64>,
+
+    /// The name of the product that is paid for, which is shown to customers
+    #[schema(max_length = 255, example = "Donation")]
+    pub product_name: String,
+
+    /// The image URL of the product
+    #[schema(example = "https://example.com/donation.png")]
+    pub product_img_link: Option<String>,
+
+    /// The description of the payments made through the link
+    #[schema(max_length = 255, example = "Donation to the animal shelter")]
+    pub description: Option<String>,
+
+    /// The URL that customers are returned to after paying
+    #[schema(value_type = Option<String>, example = "https://hyperswitch.io")]
+    pub return_url: Option<Url>,
+
+    /// The number of times the link can be paid, after which it is completed. The link can be
+    /// paid any number of times when not provided.
+    #[schema(example = 100, minimum = 1)]
+    pub max_uses: Option<i32>,
+
+    /// The time after which the link can no longer be used
+    #[schema(example = "2023-12-31T23:59:59Z")]
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub link_expiry: Option<PrimitiveDateTime>,
+
+    /// The custom domain that the link is served on
+    pub merchant_custom_domain_name: Option<String>,
+
+    /// The theming, locale and expiry of the payment links created for each payment, overriding
+    /// the payment link configuration of the merchant
+    pub payment_link_config: Option<admin::PaymentLinkConfig>,
+}
+
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct ReusablePaymentLinkUpdateRequest {
+    /// Whether the link can be used to pay. Deactivated links can be activated again unless
+    /// they have expired or have been used as many times as allowed.
+    #[schema(example = false)]
+    pub active: Option<bool>,
+
+    /// The number of times the link can be paid, after which it is completed
+    #[schema(example = 200, minimum = 1)]
+    pub max_uses: Option<i32>,
+}
+
+#[derive(Clone, Debug, serde::Serialize, ToSchema)]
+pub struct ReusablePaymentLinkResponse {
+    /// The identifier of the reusable payment link
+    #[schema(example = "rplink_Bz2FgXaNBoLYGn2VGHpr")]
+    pub reusable_payment_link_id: String,
+    pub merchant_id: String,
+    /// The link that customers visit to pay, each visit creating a new payment
+    pub link_to_pay: String,
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: api_enums::Currency,
+    pub amount: Option<i64>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub product_name: String,
+    pub product_img_link: Option<String>,
+    pub description: Option<String>,
+    pub return_url: Option<String>,
+    pub max_uses: Option<i32>,
+    /// The number of payments made through the link that have succeeded
+    pub times_used: i32,
+    /// The sum of the amounts of the payments made through the link that have succeeded
+    pub amount_collected: i64,
+    #[schema(value_type = PaymentLinkStatus, example = "active")]
+    pub status: api_enums::PaymentLinkStatus,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub link_expiry: Option<PrimitiveDateTime>,
+    pub payment_link_config: Option<admin::PaymentLinkConfig>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+/// The form that customers submit to pay through a reusable payment link.
+#[derive(Clone, Debug, Default, serde::Deserialize, serde::Serialize)]
+pub struct ReusablePaymentLinkPaymentForm {
+    /// The amount chosen by the customer, in the base denomination of the currency
+    pub amount: Option<String>,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct ReusablePaymentLinkInitiateRequest {
+    pub merchant_id: String,
+    pub reusable_payment_link_id: String,
+}
+
+#[derive(Clone, Debug, serde::Deserialize, serde::Serialize)]
+pub struct ReusablePaymentLinkPaymentRequest {
+    pub merchant_id: String,
+    pub reusable_payment_link_id: String,
+    /// The amount chosen by the customer, in the base denomination of the currency
+    pub amount: Option<String>,
+}
+
+#[derive(Debug, Clone, Eq, PartialEq, serde::Serialize, serde::Deserialize)]
+pub struct ReusablePaymentLinkDetails {
+    pub product_name: String,
+    pub product_img_link: Option<String>,
+    pub description: Option<String>,
+    pub currency: api_enums::Currency,
+    pub min_amount: String,
+    pub max_amount: Option<String>,
+    /// The amount that customers pay on links with a fixed amount
+    pub fixed_amount: Option<String>,
+    pub amount: Option<String>,
+    pub is_available: bool,
+    pub is_amount_invalid: bool,
+    pub merchant_logo: String,
+    pub merchant_name: String,
+    pub locale: String,
 }
@@ -0,0 +1,224 @@
+use common_utils::pii;
+use serde::{Deserialize, Serialize};
+use time::PrimitiveDateTime;
+use utoipa::ToSchema;
+
+use crate::enums;
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionPlanCreateRequest {
+    /// The name of the plan, which is shown to customers
+    #[schema(max_length = 255, example = "Premium")]
+    pub name: String,
+
+    /// A description of the plan
+    #[schema(max_length = 255, example = "Unlimited access to all features")]
+    pub description: Option<String>,
+
+    /// The amount charged for each billing period, in the lowest denomination of the currency
+    #[schema(example = 2000, minimum = 1)]
+    pub amount: i64,
+
+    /// The three letter ISO currency code in which subscribers are charged
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The unit of the billing period
+    #[schema(value_type = SubscriptionInterval, example = "month")]
+    pub interval: enums::SubscriptionInterval,
+
+    /// The number of intervals in a billing period, 3 months being a quarterly plan
+    #[schema(example = 1, minimum = 1, default = 1)]
+    pub interval_count: Option<u16>,
+
+    /// The number of days for which new subscribers are not charged
+    #[schema(example = 14)]
+    pub trial_period_days: Option<u16>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "tier": "premium" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionPlanUpdateRequest {
+    /// The name of the plan, which is shown to customers
+    #[schema(max_length = 255, example = "Premium")]
+    pub name: Option<String>,
+
+    /// A description of the plan
+    #[schema(max_length = 255, example = "Unlimited access to all features")]
+    pub description: Option<String>,
+
+    /// Whether new subscriptions can be created for the plan. Existing subscriptions are not
+    /// affected by deactivating their plan
+    #[schema(example = false)]
+    pub active: Option<bool>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "tier": "premium" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct SubscriptionPlanResponse {
+    /// The identifier of the plan
+    #[schema(example = "plan_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub plan_id: String,
+
+    /// The identifier of the merchant that the plan belongs to
+    #[schema(example = "merchant_1668273825")]
+    pub merchant_id: String,
+
+    /// The name of the plan
+    #[schema(example = "Premium")]
+    pub name: String,
+
+    /// A description of the plan
+    #[schema(example = "Unlimited access to all features")]
+    pub description: Option<String>,
+
+    /// The amount charged for each billing period
+    #[schema(example = 2000)]
+    pub amount: i64,
+
+    /// The three letter ISO currency code in which subscribers are charged
+    #[schema(value_type = Currency, example = "USD")]
+    pub currency: enums::Currency,
+
+    /// The unit of the billing period
+    #[schema(value_type = SubscriptionInterval, example = "month")]
+    pub interval: enums::SubscriptionInterval,
+
+    /// The number of intervals in a billing period
+    #[schema(example = 1)]
+    pub interval_count: i32,
+
+    /// The number of days for which new subscribers are not charged
+    #[schema(example = 14)]
+    pub trial_period_days: Option<i32>,
+
+    /// Whether new subscriptions can be created for the plan
+    #[schema(example = true)]
+    pub active: bool,
+
+    /// The metadata attached to the plan
+    #[schema(value_type = Option<Object>, example = r#"{ "tier": "premium" }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the plan was created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionCreateRequest {
+    /// The identifier of the customer that subscribes to the plan
+    #[schema(max_length = 64, example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+
+    /// The identifier of the plan that the customer subscribes to
+    #[schema(max_length = 64, example = "plan_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub plan_id: String,
+
+    /// The active mandate of the customer through which the subscription is charged
+    #[schema(max_length = 64, example = "man_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub mandate_id: String,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "seats": 5 }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+#[derive(Clone, Debug, Default, Deserialize, Serialize, ToSchema)]
+#[serde(deny_unknown_fields)]
+pub struct SubscriptionUpdateRequest {
+    /// The plan to move the subscription to. The difference between the prices of the plans for
+    /// the rest of the current billing period is added to the next payment of the subscription
+    #[schema(max_length = 64, example = "plan_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub plan_id: Option<String>,
+
+    /// The active mandate of the customer through which the subscription is charged
+    #[schema(max_length = 64, example = "man_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub mandate_id: Option<String>,
+
+    /// Whether the subscription is cancelled at the end of its current billing period, instead of
+    /// being renewed
+    #[schema(example = true)]
+    pub cancel_at_period_end: Option<bool>,
+
+    /// You can specify up to 50 keys, with key names up to 40 characters long and values up to 500 characters long. Metadata is useful for storing additional, structured information on an object.
+    #[schema(value_type = Option<Object>, example = r#"{ "seats": 5 }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+}
+
+#[derive(Clone, Debug, Serialize, ToSchema)]
+pub struct SubscriptionResponse {
+    /// The identifier of the subscription
+    #[schema(example = "sub_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub subscription_id: String,
+
+    /// The identifier of the merchant that the subscription belongs to
+    #[schema(example = "merchant_1668273825")]
+    pub merchant_id: String,
+
+    /// The identifier of the subscribed customer
+    #[schema(example = "cus_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub customer_id: String,
+
+    /// The identifier of the plan that the customer is subscribed to
+    #[schema(example = "plan_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub plan_id: String,
+
+    /// The mandate through which the subscription is charged
+    #[schema(example = "man_y3oqhf46pyzuxjbcn2giaqnb44")]
+    pub mandate_id: String,
+
+    /// The status of the subscription
+    #[schema(value_type = SubscriptionStatus, example = "active")]
+    pub status: enums::SubscriptionStatus,
+
+    /// The start of the current billing period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub current_period_start: PrimitiveDateTime,
+
+    /// The end of the current billing period, at which the subscription is charged for the next
+    /// period
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub current_period_end: PrimitiveDateTime,
+
+    /// The end of the trial period of the subscription, if it had one
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub trial_end: Option<PrimitiveDateTime>,
+
+    /// Whether the subscription is cancelled at the end of its current billing period
+    pub cancel_at_period_end: bool,
+
+    /// The time at which the subscription was cancelled
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub cancelled_at: Option<PrimitiveDateTime>,
+
+    /// The latest payment made to charge the subscription
+    #[schema(example = "pay_mbabizu24mvu3mela5njyhpit4")]
+    pub latest_payment_id: Option<String>,
+
+    /// The number of times charging the subscription for its current billing period has failed
+    #[schema(example = 0)]
+    pub failed_payment_attempts: i32,
+
+    /// The amount added to the next payment of the subscription after its plan was changed, which
+    /// is negative when the customer is credited
+    #[schema(example = 0)]
+    pub proration_amount: i64,
+
+    /// The metadata attached to the subscription
+    #[schema(value_type = Option<Object>, example = r#"{ "seats": 5 }"#)]
+    pub metadata: Option<pii::SecretSerdeValue>,
+
+    /// The time at which the subscription was created
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+}
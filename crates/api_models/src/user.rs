@@ -0,0 +1,175 @@
+use common_utils::pii;
+use masking::Secret;
+use time::PrimitiveDateTime;
+
+use crate::enums;
+
+/// The permissions that the roles of dashboard users are made up of.
+#[derive(
+    Clone, Copy, Debug, Eq, Hash, PartialEq, serde::Serialize, strum::Display, strum::EnumIter,
+)]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Permission {
+    PaymentRead,
+    PaymentWrite,
+    RefundRead,
+    RefundWrite,
+    DisputeRead,
+    DisputeWrite,
+    MandateRead,
+    MandateWrite,
+    CustomerRead,
+    CustomerWrite,
+    FileRead,
+    FileWrite,
+    ApiKeyRead,
+    ApiKeyWrite,
+    MerchantAccountRead,
+    MerchantAccountWrite,
+    MerchantConnectorAccountRead,
+    MerchantConnectorAccountWrite,
+    UsersRead,
+    UsersWrite,
+    AnalyticsRead,
+}
+
+/// The request body for creating a user in a merchant account with an admin API key.
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct CreateUserRequest {
+    /// The email of the user, which identifies the user when signing in.
+    pub email: pii::Email,
+    /// The name of the user.
+    pub name: Secret<String>,
+    /// The password of the user, required unless the user already exists.
+    pub password: Option<Secret<String>>,
+    /// The merchant account the user is added to.
+    pub merchant_id: String,
+    /// The role of the user in the merchant account.
+    pub role: enums::Role,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SignInRequest {
+    pub email: pii::Email,
+    pub password: Secret<String>,
+    /// The merchant account to sign in to, defaulting to the first merchant account the user has
+    /// an active role in.
+    pub merchant_id: Option<String>,
+}
+
+/// The tokens issued to a signed in user.
+#[derive(Debug, serde::Serialize)]
+pub struct AuthTokenResponse {
+    pub user_id: String,
+    pub merchant_id: String,
+    pub role: enums::Role,
+    /// The token to authenticate requests with, sent as a bearer token.
+    pub token: Secret<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub token_expires_at: PrimitiveDateTime,
+    /// The token to issue new tokens with once the token expires.
+    pub refresh_token: Secret<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub refresh_token_expires_at: PrimitiveDateTime,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct RefreshTokenRequest {
+    pub refresh_token: Secret<String>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ChangePasswordRequest {
+    pub old_password: Secret<String>,
+    pub new_password: Secret<String>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct SwitchMerchantRequest {
+    pub merchant_id: String,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct InviteUserRequest {
+    pub email: pii::Email,
+    pub name: Secret<String>,
+    pub role: enums::Role,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct AcceptInvitationRequest {
+    /// The token sent to the invited user by email.
+    pub token: Secret<String>,
+    /// The password to set, required if the user does not have a password yet.
+    pub password: Option<Secret<String>>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ForgotPasswordRequest {
+    pub email: pii::Email,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct ResetPasswordRequest {
+    /// The token sent to the user by email.
+    pub token: Secret<String>,
+    pub password: Secret<String>,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+#[serde(deny_unknown_fields)]
+pub struct UpdateUserRoleRequest {
+    pub user_id: String,
+    pub role: enums::Role,
+}
+
+#[derive(Debug, serde::Deserialize, serde::Serialize)]
+pub struct UserId {
+    pub user_id: String,
+}
+
+/// A user of a merchant account, along with their role in it.
+#[derive(Debug, serde::Serialize)]
+pub struct UserRoleResponse {
+    pub user_id: String,
+    pub email: pii::Email,
+    pub name: Secret<String>,
+    pub merchant_id: String,
+    pub role: enums::Role,
+    pub status: enums::UserStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+/// The details of the signed in user.
+#[derive(Debug, serde::Serialize)]
+pub struct GetUserResponse {
+    pub user_id: String,
+    pub email: pii::Email,
+    pub name: Secret<String>,
+    pub is_verified: bool,
+    /// The merchant account the user is signed in to.
+    pub merchant_id: String,
+    pub org_id: String,
+    pub role: enums::Role,
+    pub permissions: Vec<Permission>,
+    /// The merchant accounts the user has a role in.
+    pub merchants: Vec<UserMerchantResponse>,
+}
+
+#[derive(Debug, serde::Serialize)]
+pub struct UserMerchantResponse {
+    pub merchant_id: String,
+    pub role: enums::Role,
+    pub status: enums::UserStatus,
+}
@@ -807,6 +807,12 @@ pub enum EventType {
     DisputeLost,
     MandateActive,
     MandateRevoked,
+    MandateExpired,
+    SubscriptionCreated,
+    SubscriptionRenewed,
+    SubscriptionPaymentFailed,
+    SubscriptionUpdated,
+    SubscriptionCancelled,
 }
 
 // TODO: This decision about using KV mode or not,
@@ -830,6 +836,9 @@ pub enum MerchantStorageScheme {
     #[default]
     PostgresOnly,
     RedisKv,
+    /// KV is being disabled for the merchant, new entries are written to the database while the
+    /// entries in Redis are drained to it
+    RedisKvDraining,
 }
 
 #[derive(
@@ -1162,6 +1171,32 @@ pub enum MandateStatus {
     Inactive,
     Pending,
     Revoked,
+    /// The end date of the mandate has passed
+    Expired,
+}
+
+/// The interval over which the number of payments made against a mandate is limited
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum MandateFrequencyInterval {
+    Day,
+    Week,
+    Month,
+    Year,
 }
 
 #[derive(
@@ -1853,3 +1888,221 @@ pub enum ApplePayFlow {
     Simplified,
     Manual,
 }
+
+/// The role of a dashboard user in a merchant account, which determines the permissions of the
+/// user.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumIter,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum Role {
+    /// Has full access to the merchant account, including managing its users.
+    Admin,
+    /// Manages the integration of the merchant account: API keys, connectors and webhooks.
+    Developer,
+    /// Operates on payments, refunds and disputes.
+    Operator,
+    /// Has read-only access to the merchant account.
+    Viewer,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum UserStatus {
+    Active,
+    InvitationSent,
+}
+
+/// The type of a resource whose changes are recorded in the audit log.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditResourceType {
+    MerchantAccount,
+    MerchantConnectorAccount,
+    /// The storage scheme (KV or Postgres only) of a merchant account.
+    MerchantStorageScheme,
+    ApiKey,
+    Config,
+}
+
+/// The change made to a resource recorded in the audit log.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuditAction {
+    Create,
+    Update,
+    Delete,
+    Rotate,
+    Revoke,
+}
+
+/// The status of a payment link.
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum PaymentLinkStatus {
+    /// The payment link can be used to pay.
+    #[default]
+    Active,
+    /// The payment of the payment link has succeeded, or a reusable payment link has been paid
+    /// as many times as it may be used.
+    Completed,
+    /// The payment link has expired before its payment succeeded.
+    Expired,
+    /// The payment link has been deactivated by the merchant and can no longer be used to pay.
+    Inactive,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum InvoiceStatus {
+    /// The invoice is being prepared and has not been sent to the customer yet.
+    #[default]
+    Draft,
+    /// The invoice has been finalized and is awaiting payment.
+    Open,
+    /// A payment for the invoice has been initiated and is being processed.
+    Processing,
+    /// The invoice has been paid.
+    Paid,
+    /// The invoice has been voided and can no longer be paid.
+    Void,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SubscriptionInterval {
+    Day,
+    Week,
+    #[default]
+    Month,
+    Year,
+}
+
+#[derive(
+    Clone,
+    Copy,
+    Debug,
+    Default,
+    Eq,
+    Hash,
+    PartialEq,
+    serde::Deserialize,
+    serde::Serialize,
+    strum::Display,
+    strum::EnumString,
+    utoipa::ToSchema,
+)]
+#[router_derive::diesel_enum(storage_type = "text")]
+#[serde(rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum SubscriptionStatus {
+    /// The subscription is in its trial period, the customer is charged once the trial ends.
+    Trialing,
+    /// The customer has paid for the current period of the subscription.
+    #[default]
+    Active,
+    /// The payment for the current period has failed and is being retried.
+    PastDue,
+    /// The subscription has been cancelled and the customer is no longer charged.
+    Cancelled,
+}
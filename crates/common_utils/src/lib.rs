@@ -59,6 +59,22 @@ pub mod date_time {
         OffsetDateTime::now_utc().unix_timestamp()
     }
 
+    /// Add the given number of months to a date and time, which may be negative. The day of the
+    /// month is kept, and is clamped to the last day of shorter months.
+    pub fn add_months(date_time: PrimitiveDateTime, months: i64) -> Option<PrimitiveDateTime> {
+        let date = date_time.date();
+        let months = (i64::from(date.year()) * 12 + i64::from(u8::from(date.month())) - 1)
+            .checked_add(months)?;
+        let year = i32::try_from(months.div_euclid(12)).ok()?;
+        let month = time::Month::try_from(u8::try_from(months.rem_euclid(12) + 1).ok()?).ok()?;
+        let day = date.day().min(time::util::days_in_year_month(year, month));
+
+        Some(PrimitiveDateTime::new(
+            time::Date::from_calendar_date(year, month, day).ok()?,
+            date_time.time(),
+        ))
+    }
+
     /// Calculate execution time for a async block in milliseconds
     #[cfg(feature = "async_ext")]
     pub async fn time_it<T, Fut: futures::Future<Output = T>, F: FnOnce() -> Fut>(
@@ -2,6 +2,19 @@ pub mod errors;
 pub mod mandates;
 pub mod payments;
 
+/// The consistency required of a read from the database.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum ReadConsistency {
+    /// The read is served by the master database and observes every committed write. OLTP flows
+    /// read with this consistency.
+    #[default]
+    Strong,
+    /// The read is served by the replica database, as long as its replication lag is within the
+    /// configured staleness tolerance, and by the master database otherwise. OLAP flows such as
+    /// lists, filters and exports read with this consistency.
+    Eventual,
+}
+
 #[derive(Clone, Debug, Eq, PartialEq)]
 pub enum RemoteStorageObject<T: ForeignIDRef> {
     ForeignID(String),
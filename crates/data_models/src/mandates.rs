@@ -3,7 +3,7 @@ use api_models::payments::{
     MandateAmountData as ApiMandateAmountData, MandateData as ApiMandateData, MandateType,
     OnlineMandate as ApiOnlineMandate,
 };
-use common_enums::Currency;
+use common_enums::{Currency, MandateFrequencyInterval};
 use common_utils::{date_time, errors::ParsingError, pii};
 use error_stack::{IntoReport, ResultExt};
 use masking::{PeekInterface, Secret};
@@ -23,6 +23,8 @@ pub struct MandateAmountData {
     pub start_date: Option<PrimitiveDateTime>,
     pub end_date: Option<PrimitiveDateTime>,
     pub metadata: Option<pii::SecretSerdeValue>,
+    pub frequency_limit: Option<u16>,
+    pub frequency_interval: Option<MandateFrequencyInterval>,
 }
 
 // The fields on this struct are optional, as we want to allow the merchant to provide partial
@@ -81,6 +83,8 @@ impl From<ApiMandateAmountData> for MandateAmountData {
             start_date: value.start_date,
             end_date: value.end_date,
             metadata: value.metadata,
+            frequency_limit: value.frequency_limit,
+            frequency_interval: value.frequency_interval,
         }
     }
 }
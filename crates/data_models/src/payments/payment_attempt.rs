@@ -4,7 +4,7 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
 use super::PaymentIntent;
-use crate::{errors, mandates::MandateDataType, ForeignIDRef};
+use crate::{errors, mandates::MandateDataType, ForeignIDRef, ReadConsistency};
 
 #[async_trait::async_trait]
 pub trait PaymentAttemptInterface {
@@ -77,18 +77,20 @@ pub trait PaymentAttemptInterface {
         pi: &[PaymentIntent],
         merchant_id: &str,
         storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<PaymentListFilters, errors::StorageError>;
 
     #[allow(clippy::too_many_arguments)]
     async fn get_total_count_of_filtered_payment_attempts(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         active_attempt_ids: &[String],
         connector: Option<Vec<Connector>>,
         payment_method: Option<Vec<storage_enums::PaymentMethod>>,
         payment_method_type: Option<Vec<storage_enums::PaymentMethodType>>,
         authentication_type: Option<Vec<storage_enums::AuthenticationType>>,
         storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<i64, errors::StorageError>;
 }
 
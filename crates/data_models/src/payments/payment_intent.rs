@@ -7,7 +7,7 @@ use serde::{Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
 use super::{payment_attempt::PaymentAttempt, PaymentIntent};
-use crate::{errors, RemoteStorageObject};
+use crate::{errors, ReadConsistency, RemoteStorageObject};
 #[async_trait::async_trait]
 pub trait PaymentIntentInterface {
     async fn update_payment_intent(
@@ -42,6 +42,7 @@ pub trait PaymentIntentInterface {
         merchant_id: &str,
         filters: &PaymentIntentFetchConstraints,
         storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<PaymentIntent>, errors::StorageError>;
 
     #[cfg(feature = "olap")]
@@ -50,23 +51,46 @@ pub trait PaymentIntentInterface {
         merchant_id: &str,
         time_range: &api_models::payments::TimeRange,
         storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<PaymentIntent>, errors::StorageError>;
 
     #[cfg(feature = "olap")]
     async fn get_filtered_payment_intents_attempt(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         constraints: &PaymentIntentFetchConstraints,
         storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<(PaymentIntent, PaymentAttempt)>, errors::StorageError>;
 
     #[cfg(feature = "olap")]
     async fn get_filtered_active_attempt_ids_for_total_count(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         constraints: &PaymentIntentFetchConstraints,
         storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<String>, errors::StorageError>;
+
+    #[cfg(feature = "olap")]
+    async fn get_payment_intent_aggregates(
+        &self,
+        merchant_ids: &[String],
+        time_range: &api_models::payments::TimeRange,
+        storage_scheme: storage_enums::MerchantStorageScheme,
+        consistency: ReadConsistency,
+    ) -> error_stack::Result<Vec<PaymentIntentAggregate>, errors::StorageError>;
+}
+
+/// The number and total amount of the payment intents of a merchant with the same status and
+/// currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PaymentIntentAggregate {
+    pub merchant_id: String,
+    pub status: storage_enums::IntentStatus,
+    pub currency: Option<storage_enums::Currency>,
+    pub count: i64,
+    pub total_amount: i64,
 }
 
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -16,6 +16,10 @@ pub struct ApiKey {
     pub created_at: PrimitiveDateTime,
     pub expires_at: Option<PrimitiveDateTime>,
     pub last_used: Option<PrimitiveDateTime>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub scopes: Option<Vec<String>>,
+    #[diesel(deserialize_as = super::OptionalDieselArray<String>)]
+    pub allowed_ips: Option<Vec<String>>,
 }
 
 #[derive(Debug, Insertable)]
@@ -30,6 +34,8 @@ pub struct ApiKeyNew {
     pub created_at: PrimitiveDateTime,
     pub expires_at: Option<PrimitiveDateTime>,
     pub last_used: Option<PrimitiveDateTime>,
+    pub scopes: Option<Vec<String>>,
+    pub allowed_ips: Option<Vec<String>>,
 }
 
 #[derive(Debug)]
@@ -39,6 +45,8 @@ pub enum ApiKeyUpdate {
         description: Option<String>,
         expires_at: Option<Option<PrimitiveDateTime>>,
         last_used: Option<PrimitiveDateTime>,
+        scopes: Option<Vec<String>>,
+        allowed_ips: Option<Option<Vec<String>>>,
     },
     LastUsedUpdate {
         last_used: PrimitiveDateTime,
@@ -52,6 +60,8 @@ pub(crate) struct ApiKeyUpdateInternal {
     pub description: Option<String>,
     pub expires_at: Option<Option<PrimitiveDateTime>>,
     pub last_used: Option<PrimitiveDateTime>,
+    pub scopes: Option<Vec<String>>,
+    pub allowed_ips: Option<Option<Vec<String>>>,
 }
 
 impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
@@ -62,17 +72,23 @@ impl From<ApiKeyUpdate> for ApiKeyUpdateInternal {
                 description,
                 expires_at,
                 last_used,
+                scopes,
+                allowed_ips,
             } => Self {
                 name,
                 description,
                 expires_at,
                 last_used,
+                scopes,
+                allowed_ips,
             },
             ApiKeyUpdate::LastUsedUpdate { last_used } => Self {
                 last_used: Some(last_used),
                 name: None,
                 description: None,
                 expires_at: None,
+                scopes: None,
+                allowed_ips: None,
             },
         }
     }
@@ -145,3 +161,10 @@ pub struct ApiKeyExpiryWorkflow {
     // Days on which email reminder about api_key expiry has to be sent, prior to it's expiry.
     pub expiry_reminder_days: Vec<u8>,
 }
+
+// Tracking data by process_tracker for revoking an API key at the end of its rotation grace period
+#[derive(Debug, Deserialize, Serialize, Clone)]
+pub struct ApiKeyRevocationWorkflow {
+    pub key_id: String,
+    pub merchant_id: String,
+}
@@ -0,0 +1,37 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::{enums, schema::audit_events};
+
+/// A change made to a resource, recorded in the audit log. Audit events are never updated or
+/// deleted once recorded.
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = audit_events)]
+pub struct AuditEvent {
+    pub id: i32,
+    pub audit_event_id: String,
+    pub merchant_id: Option<String>,
+    pub resource_type: enums::AuditResourceType,
+    pub resource_id: String,
+    pub action: enums::AuditAction,
+    pub auth_type: String,
+    pub actor_id: Option<String>,
+    pub request_id: Option<String>,
+    pub diff: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = audit_events)]
+pub struct AuditEventNew {
+    pub audit_event_id: String,
+    pub merchant_id: Option<String>,
+    pub resource_type: enums::AuditResourceType,
+    pub resource_id: String,
+    pub action: enums::AuditAction,
+    pub auth_type: String,
+    pub actor_id: Option<String>,
+    pub request_id: Option<String>,
+    pub diff: serde_json::Value,
+    pub created_at: PrimitiveDateTime,
+}
@@ -40,6 +40,7 @@ pub enum EventClass {
     Refunds,
     Disputes,
     Mandates,
+    Subscriptions,
 }
 
 #[derive(
@@ -61,6 +62,7 @@ pub enum EventObjectType {
     RefundDetails,
     DisputeDetails,
     MandateDetails,
+    SubscriptionDetails,
 }
 
 #[derive(
@@ -180,6 +182,8 @@ pub struct MandateAmountData {
     pub start_date: Option<PrimitiveDateTime>,
     pub end_date: Option<PrimitiveDateTime>,
     pub metadata: Option<pii::SecretSerdeValue>,
+    pub frequency_limit: Option<u16>,
+    pub frequency_interval: Option<MandateFrequencyInterval>,
 }
 
 #[derive(
@@ -0,0 +1,114 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{self, Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::invoice};
+
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[diesel(table_name = invoice)]
+#[diesel(primary_key(invoice_id))]
+pub struct Invoice {
+    pub invoice_id: String,
+    pub merchant_id: String,
+    pub customer_id: String,
+    pub status: storage_enums::InvoiceStatus,
+    pub currency: storage_enums::Currency,
+    pub line_items: serde_json::Value,
+    pub tax_lines: serde_json::Value,
+    pub subtotal: i64,
+    pub tax_amount: i64,
+    pub total_amount: i64,
+    pub description: Option<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub due_date: PrimitiveDateTime,
+    pub payment_id: Option<String>,
+    pub payment_link_id: Option<String>,
+    pub metadata: Option<serde_json::Value>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub finalized_at: Option<PrimitiveDateTime>,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub paid_at: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Insertable,
+    serde::Serialize,
+    serde::Deserialize,
+    router_derive::DebugAsDisplay,
+)]
+#[diesel(table_name = invoice)]
+pub struct InvoiceNew {
+    pub invoice_id: String,
+    pub merchant_id: String,
+    pub customer_id: String,
+    pub status: storage_enums::InvoiceStatus,
+    pub currency: storage_enums::Currency,
+    pub line_items: serde_json::Value,
+    pub tax_lines: serde_json::Value,
+    pub subtotal: i64,
+    pub tax_amount: i64,
+    pub total_amount: i64,
+    pub description: Option<String>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub due_date: PrimitiveDateTime,
+    pub metadata: Option<serde_json::Value>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub created_at: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_modified_at: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug)]
+pub enum InvoiceUpdate {
+    Finalize {
+        payment_id: String,
+        payment_link_id: String,
+    },
+    StatusUpdate {
+        status: storage_enums::InvoiceStatus,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = invoice)]
+pub struct InvoiceUpdateInternal {
+    status: Option<storage_enums::InvoiceStatus>,
+    payment_id: Option<String>,
+    payment_link_id: Option<String>,
+    finalized_at: Option<PrimitiveDateTime>,
+    paid_at: Option<PrimitiveDateTime>,
+    last_modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<InvoiceUpdate> for InvoiceUpdateInternal {
+    fn from(invoice_update: InvoiceUpdate) -> Self {
+        let now = common_utils::date_time::now();
+        match invoice_update {
+            InvoiceUpdate::Finalize {
+                payment_id,
+                payment_link_id,
+            } => Self {
+                status: Some(storage_enums::InvoiceStatus::Open),
+                payment_id: Some(payment_id),
+                payment_link_id: Some(payment_link_id),
+                finalized_at: Some(now),
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            InvoiceUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                paid_at: (status == storage_enums::InvoiceStatus::Paid).then_some(now),
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+        }
+    }
+}
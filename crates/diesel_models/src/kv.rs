@@ -12,7 +12,7 @@ use crate::{
     PaymentIntent,
 };
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "db_op", content = "data")]
 pub enum DBOperation {
     Insert { insertable: Insertable },
@@ -20,6 +20,36 @@ pub enum DBOperation {
     Delete,
 }
 
+impl DBOperation {
+    /// Returns the identifier of the merchant whose data is affected by the operation, if any.
+    pub fn merchant_id(&self) -> Option<&str> {
+        match self {
+            Self::Insert { insertable } => match insertable {
+                Insertable::PaymentIntent(a) => Some(&a.merchant_id),
+                Insertable::PaymentAttempt(a) => Some(&a.merchant_id),
+                Insertable::Refund(a) => Some(&a.merchant_id),
+                Insertable::ConnectorResponse(a) => Some(&a.merchant_id),
+                Insertable::Address(a) => Some(&a.merchant_id),
+                Insertable::ReverseLookUp(_) => None,
+            },
+            Self::Update { updatable } => match updatable {
+                Updateable::PaymentIntentUpdate(a) => Some(&a.orig.merchant_id),
+                Updateable::PaymentAttemptUpdate(a) => Some(&a.orig.merchant_id),
+                Updateable::RefundUpdate(a) => Some(&a.orig.merchant_id),
+                Updateable::ConnectorResponseUpdate(a) => Some(&a.orig.merchant_id),
+                Updateable::AddressUpdate(a) => Some(&a.orig.merchant_id),
+            },
+            Self::Delete => None,
+        }
+    }
+}
+
+/// Returns the Redis key holding the number of operations on the merchant's data which are yet to
+/// be written to the database by the drainer.
+pub fn get_pending_entries_key(merchant_id: &str) -> String {
+    format!("drainer_pending_entries_{merchant_id}")
+}
+
 #[derive(Debug, Serialize, Deserialize)]
 pub struct TypedSql {
     #[serde(flatten)]
@@ -37,7 +67,7 @@ impl TypedSql {
     }
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "table", content = "data")]
 pub enum Insertable {
     PaymentIntent(PaymentIntentNew),
@@ -48,7 +78,7 @@ pub enum Insertable {
     ReverseLookUp(ReverseLookupNew),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 #[serde(rename_all = "snake_case", tag = "table", content = "data")]
 pub enum Updateable {
     PaymentIntentUpdate(PaymentIntentUpdateMems),
@@ -58,31 +88,31 @@ pub enum Updateable {
     AddressUpdate(Box<AddressUpdateMems>),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct ConnectorResponseUpdateMems {
     pub orig: ConnectorResponse,
     pub update_data: ConnectorResponseUpdate,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct AddressUpdateMems {
     pub orig: Address,
     pub update_data: AddressUpdateInternal,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PaymentIntentUpdateMems {
     pub orig: PaymentIntent,
     pub update_data: PaymentIntentUpdate,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct PaymentAttemptUpdateMems {
     pub orig: PaymentAttempt,
     pub update_data: PaymentAttemptUpdate,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, Serialize, Deserialize)]
 pub struct RefundUpdateMems {
     pub orig: Refund,
     pub update_data: RefundUpdate,
@@ -1,5 +1,6 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_event;
 pub mod business_profile;
 pub mod capture;
 pub mod cards_info;
@@ -15,11 +16,13 @@ pub mod events;
 pub mod file;
 #[allow(unused)]
 pub mod fraud_check;
+pub mod invoice;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
 pub mod macros;
 pub mod mandate;
+pub mod mandate_usage;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
@@ -33,9 +36,14 @@ pub mod payouts;
 pub mod process_tracker;
 pub mod query;
 pub mod refund;
+pub mod reusable_payment_link;
 pub mod reverse_lookup;
 #[allow(unused_qualifications)]
 pub mod schema;
+pub mod subscription;
+pub mod subscription_plan;
+pub mod user;
+pub mod user_role;
 
 use diesel_impl::{DieselArray, OptionalDieselArray};
 
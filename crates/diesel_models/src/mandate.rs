@@ -32,6 +32,8 @@ pub struct Mandate {
     pub connector_mandate_ids: Option<pii::SecretSerdeValue>,
     pub original_payment_id: Option<String>,
     pub merchant_connector_id: Option<String>,
+    pub frequency_limit: Option<i32>,
+    pub frequency_interval: Option<storage_enums::MandateFrequencyInterval>,
 }
 
 #[derive(
@@ -62,6 +64,8 @@ pub struct MandateNew {
     pub connector_mandate_ids: Option<pii::SecretSerdeValue>,
     pub original_payment_id: Option<String>,
     pub merchant_connector_id: Option<String>,
+    pub frequency_limit: Option<i32>,
+    pub frequency_interval: Option<storage_enums::MandateFrequencyInterval>,
 }
 
 #[derive(Debug)]
@@ -0,0 +1,40 @@
+use diesel::{Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::mandate_usage};
+
+/// A payment made against a mandate
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable)]
+#[diesel(table_name = mandate_usage)]
+pub struct MandateUsage {
+    pub id: i32,
+    pub merchant_id: String,
+    pub mandate_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub connector: String,
+    pub created_at: PrimitiveDateTime,
+}
+
+impl MandateUsage {
+    /// Whether a mandate that has been used the given number of times within its frequency
+    /// interval has reached its frequency limit
+    pub fn is_frequency_limit_reached(usages: i64, frequency_limit: i32) -> bool {
+        usages >= i64::from(frequency_limit)
+    }
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = mandate_usage)]
+pub struct MandateUsageNew {
+    pub merchant_id: String,
+    pub mandate_id: String,
+    pub payment_id: String,
+    pub attempt_id: String,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub connector: String,
+    pub created_at: Option<PrimitiveDateTime>,
+}
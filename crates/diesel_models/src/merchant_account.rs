@@ -41,6 +41,7 @@ pub struct MerchantAccount {
     pub default_profile: Option<String>,
     pub recon_status: storage_enums::ReconStatus,
     pub payment_link_config: Option<serde_json::Value>,
+    pub email_config: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
@@ -71,6 +72,7 @@ pub struct MerchantAccountNew {
     pub default_profile: Option<String>,
     pub recon_status: storage_enums::ReconStatus,
     pub payment_link_config: Option<serde_json::Value>,
+    pub email_config: Option<serde_json::Value>,
 }
 
 #[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
@@ -100,4 +102,5 @@ pub struct MerchantAccountUpdateInternal {
     pub default_profile: Option<Option<String>>,
     pub recon_status: storage_enums::ReconStatus,
     pub payment_link_config: Option<serde_json::Value>,
+    pub email_config: Option<serde_json::Value>,
 }
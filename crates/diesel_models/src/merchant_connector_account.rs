@@ -72,7 +72,7 @@ pub struct MerchantConnectorAccountNew {
     pub pm_auth_config: Option<serde_json::Value>,
 }
 
-#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
 #[diesel(table_name = merchant_connector_account)]
 pub struct MerchantConnectorAccountUpdateInternal {
     pub merchant_id: Option<String>,
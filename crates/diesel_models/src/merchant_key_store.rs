@@ -20,6 +20,8 @@ pub struct MerchantKeyStore {
     pub key: Encryption,
     #[serde(with = "custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
+    pub key_version: i32,
+    pub retired_keys: Option<serde_json::Value>,
 }
 
 #[derive(
@@ -30,13 +32,32 @@ pub struct MerchantKeyStoreNew {
     pub merchant_id: String,
     pub key: Encryption,
     pub created_at: PrimitiveDateTime,
+    pub key_version: i32,
+    pub retired_keys: Option<serde_json::Value>,
 }
 
 #[derive(
     Clone, Debug, serde::Serialize, serde::Deserialize, AsChangeset, router_derive::DebugAsDisplay,
 )]
-#[diesel(table_name = merchant_key_store)]
+#[diesel(table_name = merchant_key_store, treat_none_as_null = true)]
 pub struct MerchantKeyStoreUpdateInternal {
-    pub merchant_id: String,
     pub key: Encryption,
+    pub key_version: i32,
+    /// Replaces the retired keys of the key store, clearing them when `None`.
+    pub retired_keys: Option<serde_json::Value>,
+}
+
+/// A key of a merchant which data is no longer encrypted with, but which is kept for decrypting
+/// data encrypted before the key was rotated. Stored in `retired_keys`.
+#[derive(Clone, Debug, serde::Serialize, serde::Deserialize)]
+pub struct RetiredKey {
+    pub key_version: i32,
+    pub key: Encryption,
+}
+
+// Tracking data by process_tracker for re-encrypting the data of a merchant with a rotated key
+#[derive(Debug, serde::Deserialize, serde::Serialize, Clone)]
+pub struct MerchantKeyRotationWorkflow {
+    pub merchant_id: String,
+    pub key_version: i32,
 }
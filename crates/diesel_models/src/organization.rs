@@ -7,6 +7,8 @@ use crate::schema::organization;
 pub struct Organization {
     pub org_id: String,
     pub org_name: Option<String>,
+    pub hashed_api_key: Option<String>,
+    pub api_key_prefix: Option<String>,
 }
 
 #[derive(Clone, Debug, Insertable)]
@@ -16,20 +18,57 @@ pub struct OrganizationNew {
     pub org_name: Option<String>,
 }
 
-#[derive(Clone, Debug, AsChangeset)]
+#[derive(Clone, Debug, Default, AsChangeset)]
 #[diesel(table_name = organization)]
 pub struct OrganizationUpdateInternal {
     org_name: Option<String>,
+    hashed_api_key: Option<String>,
+    api_key_prefix: Option<String>,
 }
 
+#[derive(Debug)]
 pub enum OrganizationUpdate {
-    Update { org_name: Option<String> },
+    Update {
+        org_name: Option<String>,
+    },
+    ApiKeyUpdate {
+        hashed_api_key: String,
+        api_key_prefix: String,
+    },
 }
 
 impl From<OrganizationUpdate> for OrganizationUpdateInternal {
     fn from(value: OrganizationUpdate) -> Self {
         match value {
-            OrganizationUpdate::Update { org_name } => Self { org_name },
+            OrganizationUpdate::Update { org_name } => Self {
+                org_name,
+                ..Default::default()
+            },
+            OrganizationUpdate::ApiKeyUpdate {
+                hashed_api_key,
+                api_key_prefix,
+            } => Self {
+                hashed_api_key: Some(hashed_api_key),
+                api_key_prefix: Some(api_key_prefix),
+                ..Default::default()
+            },
+        }
+    }
+}
+
+impl OrganizationUpdate {
+    pub fn apply_changeset(self, source: Organization) -> Organization {
+        let OrganizationUpdateInternal {
+            org_name,
+            hashed_api_key,
+            api_key_prefix,
+        } = self.into();
+
+        Organization {
+            org_name: org_name.or(source.org_name),
+            hashed_api_key: hashed_api_key.or(source.hashed_api_key),
+            api_key_prefix: api_key_prefix.or(source.api_key_prefix),
+            ..source
         }
     }
 }
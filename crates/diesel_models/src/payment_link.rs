@@ -1,4 +1,4 @@
-use diesel::{Identifiable, Insertable, Queryable};
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
 use serde::{self, Deserialize, Serialize};
 use time::PrimitiveDateTime;
 
@@ -20,6 +20,9 @@ pub struct PaymentLink {
     pub last_modified_at: PrimitiveDateTime,
     #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
     pub fulfilment_time: Option<PrimitiveDateTime>,
+    pub payment_link_config: Option<serde_json::Value>,
+    pub status: storage_enums::PaymentLinkStatus,
+    pub reusable_payment_link_id: Option<String>,
 }
 
 #[derive(
@@ -47,4 +50,32 @@ pub struct PaymentLinkNew {
     pub last_modified_at: Option<PrimitiveDateTime>,
     #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
     pub fulfilment_time: Option<PrimitiveDateTime>,
+    pub payment_link_config: Option<serde_json::Value>,
+    pub status: storage_enums::PaymentLinkStatus,
+    pub reusable_payment_link_id: Option<String>,
+}
+
+#[derive(Debug)]
+pub enum PaymentLinkUpdate {
+    StatusUpdate {
+        status: storage_enums::PaymentLinkStatus,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = payment_link)]
+pub struct PaymentLinkUpdateInternal {
+    status: Option<storage_enums::PaymentLinkStatus>,
+    last_modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<PaymentLinkUpdate> for PaymentLinkUpdateInternal {
+    fn from(payment_link_update: PaymentLinkUpdate) -> Self {
+        match payment_link_update {
+            PaymentLinkUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                last_modified_at: Some(common_utils::date_time::now()),
+            },
+        }
+    }
 }
@@ -1,5 +1,6 @@
 pub mod address;
 pub mod api_keys;
+pub mod audit_event;
 pub mod business_profile;
 mod capture;
 pub mod cards_info;
@@ -11,8 +12,10 @@ pub mod events;
 pub mod file;
 pub mod fraud_check;
 pub mod generics;
+pub mod invoice;
 pub mod locker_mock_up;
 pub mod mandate;
+pub mod mandate_usage;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
@@ -25,4 +28,9 @@ pub mod payout_attempt;
 pub mod payouts;
 pub mod process_tracker;
 pub mod refund;
+pub mod reusable_payment_link;
 pub mod reverse_lookup;
+pub mod subscription;
+pub mod subscription_plan;
+pub mod user;
+pub mod user_role;
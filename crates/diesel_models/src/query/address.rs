@@ -1,4 +1,6 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, PgExpressionMethods,
+};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -14,6 +16,12 @@ impl AddressNew {
     pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<Address> {
         generics::generic_insert(conn, self).await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn batch_insert(addresses: Vec<Self>, conn: &PgPooledConn) -> StorageResult<()> {
+        generics::generic_insert::<_, _, Address>(conn, addresses).await?;
+        Ok(())
+    }
 }
 
 impl Address {
@@ -151,4 +159,55 @@ impl Address {
         )
         .await
     }
+
+    /// Lists the addresses of the merchant whose identifiers follow `address_id`, in the order of
+    /// their identifiers.
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id_after_address_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        address_id: &str,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::address_id.gt(address_id.to_owned())),
+            Some(limit),
+            None,
+            Some(dsl::address_id),
+        )
+        .await
+    }
+
+    /// Updates the encrypted fields of the address, only if they are unchanged since the address
+    /// was fetched, so that data updated concurrently is not overwritten with stale values.
+    #[instrument(skip(conn))]
+    pub async fn update_encrypted_fields_if_unchanged(
+        self,
+        conn: &PgPooledConn,
+        address_update_internal: AddressUpdateInternal,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::address_id
+                .eq(self.address_id)
+                .and(dsl::line1.is_not_distinct_from(self.line1))
+                .and(dsl::line2.is_not_distinct_from(self.line2))
+                .and(dsl::line3.is_not_distinct_from(self.line3))
+                .and(dsl::state.is_not_distinct_from(self.state))
+                .and(dsl::zip.is_not_distinct_from(self.zip))
+                .and(dsl::first_name.is_not_distinct_from(self.first_name))
+                .and(dsl::last_name.is_not_distinct_from(self.last_name))
+                .and(dsl::phone_number.is_not_distinct_from(self.phone_number)),
+            address_update_internal,
+        )
+        .await
+    }
 }
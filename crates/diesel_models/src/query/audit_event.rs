@@ -0,0 +1,14 @@
+use router_env::tracing::{self, instrument};
+
+use crate::{
+    audit_event::{AuditEvent, AuditEventNew},
+    query::generics,
+    PgPooledConn, StorageResult,
+};
+
+impl AuditEventNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<AuditEvent> {
+        generics::generic_insert(conn, self).await
+    }
+}
@@ -1,4 +1,6 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, PgExpressionMethods,
+};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -88,6 +90,53 @@ impl Customer {
         .await
     }
 
+    /// Lists the customers of the merchant whose identifiers follow `customer_id`, in the order of
+    /// their identifiers.
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id_after_customer_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        customer_id: &str,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::customer_id.gt(customer_id.to_owned())),
+            Some(limit),
+            None,
+            Some(dsl::customer_id),
+        )
+        .await
+    }
+
+    /// Updates the encrypted fields of the customer, only if they are unchanged since the customer
+    /// was fetched, so that data updated concurrently is not overwritten with stale values.
+    #[instrument(skip(conn))]
+    pub async fn update_encrypted_fields_if_unchanged(
+        self,
+        conn: &PgPooledConn,
+        customer: CustomerUpdateInternal,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::customer_id
+                .eq(self.customer_id)
+                .and(dsl::merchant_id.eq(self.merchant_id))
+                .and(dsl::name.is_not_distinct_from(self.name))
+                .and(dsl::email.is_not_distinct_from(self.email))
+                .and(dsl::phone.is_not_distinct_from(self.phone)),
+            customer,
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn find_optional_by_customer_id_merchant_id(
         conn: &PgPooledConn,
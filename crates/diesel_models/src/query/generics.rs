@@ -69,6 +69,18 @@ pub mod db_metrics {
     }
 }
 
+/// Converts the error of a query run in a transaction, where the generic functions cannot be used.
+pub(super) fn to_database_error(
+    error: DieselError,
+    message: &'static str,
+) -> error_stack::Report<errors::DatabaseError> {
+    match error {
+        DieselError::NotFound => report!(errors::DatabaseError::NotFound),
+        _ => report!(errors::DatabaseError::Others),
+    }
+    .attach_printable(message)
+}
+
 use db_metrics::*;
 
 #[instrument(level = "DEBUG", skip_all)]
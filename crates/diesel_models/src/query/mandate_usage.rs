@@ -0,0 +1,120 @@
+use async_bb8_diesel::{AsyncConnection, AsyncRunQueryDsl};
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, OptionalExtension, QueryDsl,
+};
+use router_env::{instrument, tracing};
+use time::PrimitiveDateTime;
+
+use super::generics;
+use crate::{
+    mandate_usage::{MandateUsage, MandateUsageNew},
+    schema::{mandate::dsl as mandate_dsl, mandate_usage::dsl},
+    PgPooledConn, StorageResult,
+};
+
+impl MandateUsageNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<MandateUsage> {
+        generics::generic_insert(conn, self).await
+    }
+
+    /// Records the payment against the mandate unless the mandate has already been used as many
+    /// times as allowed since the start of its frequency interval. The mandate is locked until the
+    /// transaction ends, so that the usages of concurrent payments are counted one after another.
+    /// A payment attempt that was recorded already is returned as is. Returns `None` when the
+    /// frequency limit has been reached.
+    #[instrument(skip(conn))]
+    pub async fn insert_within_frequency_limit(
+        self,
+        conn: &PgPooledConn,
+        frequency_limit: i32,
+        interval_start: PrimitiveDateTime,
+    ) -> StorageResult<Option<MandateUsage>> {
+        conn.transaction_async(|conn| async move {
+            mandate_dsl::mandate
+                .filter(
+                    mandate_dsl::merchant_id
+                        .eq(self.merchant_id.clone())
+                        .and(mandate_dsl::mandate_id.eq(self.mandate_id.clone())),
+                )
+                .select(mandate_dsl::id)
+                .for_update()
+                .get_result_async::<i32>(&conn)
+                .await?;
+
+            let recorded_usage = dsl::mandate_usage
+                .filter(
+                    dsl::merchant_id
+                        .eq(self.merchant_id.clone())
+                        .and(dsl::attempt_id.eq(self.attempt_id.clone())),
+                )
+                .first_async::<MandateUsage>(&conn)
+                .await
+                .optional()?;
+            if recorded_usage.is_some() {
+                return Ok(recorded_usage);
+            }
+
+            let usages: i64 = dsl::mandate_usage
+                .filter(
+                    dsl::merchant_id
+                        .eq(self.merchant_id.clone())
+                        .and(dsl::mandate_id.eq(self.mandate_id.clone()))
+                        .and(dsl::created_at.ge(interval_start)),
+                )
+                .count()
+                .get_result_async(&conn)
+                .await?;
+            if MandateUsage::is_frequency_limit_reached(usages, frequency_limit) {
+                return Ok(None);
+            }
+
+            diesel::insert_into(dsl::mandate_usage)
+                .values(self)
+                .get_result_async(&conn)
+                .await
+                .map(Some)
+        })
+        .await
+        .map_err(|error| generics::to_database_error(error, "Error while inserting mandate usage"))
+    }
+}
+
+impl MandateUsage {
+    /// Finds the payments made against a mandate since the given time, most recent first
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_mandate_id_created_after(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        mandate_id: &str,
+        created_after: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::mandate_id.eq(mandate_id.to_owned()))
+                .and(dsl::created_at.ge(created_after)),
+            limit,
+            None,
+            Some(dsl::created_at.desc()),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn delete_by_merchant_id_attempt_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        attempt_id: &str,
+    ) -> StorageResult<bool> {
+        generics::generic_delete::<<Self as HasTable>::Table, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::attempt_id.eq(attempt_id.to_owned())),
+        )
+        .await
+    }
+}
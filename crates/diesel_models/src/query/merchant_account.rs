@@ -1,4 +1,6 @@
-use diesel::{associations::HasTable, ExpressionMethods, Table};
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, PgExpressionMethods, Table,
+};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -79,6 +81,31 @@ impl MerchantAccount {
         .await
     }
 
+    /// Updates the encrypted fields of the merchant account, only if they are unchanged since the
+    /// merchant account was fetched, so that data updated concurrently is not overwritten with
+    /// stale values.
+    #[instrument(skip(conn))]
+    pub async fn update_encrypted_fields_if_unchanged(
+        self,
+        conn: &PgPooledConn,
+        merchant_account: MerchantAccountUpdateInternal,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(self.merchant_id)
+                .and(dsl::merchant_name.is_not_distinct_from(self.merchant_name))
+                .and(dsl::merchant_details.is_not_distinct_from(self.merchant_details)),
+            merchant_account,
+        )
+        .await
+    }
+
     #[instrument(skip_all)]
     pub async fn find_by_publishable_key(
         conn: &PgPooledConn,
@@ -156,4 +156,49 @@ impl MerchantConnectorAccount {
             .await
         }
     }
+
+    /// Lists the merchant connector accounts of the merchant whose identifiers follow `merchant_connector_id`, in the order of
+    /// their identifiers.
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id_after_merchant_connector_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        merchant_connector_id: &str,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::merchant_connector_id.gt(merchant_connector_id.to_owned())),
+            Some(limit),
+            None,
+            Some(dsl::merchant_connector_id),
+        )
+        .await
+    }
+
+    /// Updates the encrypted connector account details, only if they are unchanged since the
+    /// merchant connector account was fetched, so that details updated concurrently are not
+    /// overwritten with stale values.
+    #[instrument(skip(conn))]
+    pub async fn update_encrypted_fields_if_unchanged(
+        self,
+        conn: &PgPooledConn,
+        merchant_connector_account: MerchantConnectorAccountUpdateInternal,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_connector_id
+                .eq(self.merchant_connector_id)
+                .and(dsl::connector_account_details.eq(self.connector_account_details)),
+            merchant_connector_account,
+        )
+        .await
+    }
 }
@@ -1,9 +1,9 @@
-use diesel::{associations::HasTable, ExpressionMethods};
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
 use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    merchant_key_store::{MerchantKeyStore, MerchantKeyStoreNew},
+    merchant_key_store::{MerchantKeyStore, MerchantKeyStoreNew, MerchantKeyStoreUpdateInternal},
     schema::merchant_key_store::dsl,
     PgPooledConn, StorageResult,
 };
@@ -28,6 +28,48 @@ impl MerchantKeyStore {
         .await
     }
 
+    /// Lists the key stores of the merchants whose identifiers follow `merchant_id`, in the order
+    /// of their identifiers.
+    #[instrument(skip(conn))]
+    pub async fn list_after_merchant_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id.gt(merchant_id.to_owned()),
+            Some(limit),
+            None,
+            Some(dsl::merchant_id),
+        )
+        .await
+    }
+
+    /// Updates the key store only if its key is still of the provided version, so that concurrent
+    /// key rotations cannot overwrite each other.
+    #[instrument(skip(conn))]
+    pub async fn update_by_merchant_id_key_version(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        key_version: i32,
+        merchant_key_store: MerchantKeyStoreUpdateInternal,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::key_version.eq(key_version)),
+            merchant_key_store,
+        )
+        .await
+    }
+
     #[instrument(skip(conn))]
     pub async fn delete_by_merchant_id(
         conn: &PgPooledConn,
@@ -18,6 +18,17 @@ impl Organization {
             .await
     }
 
+    pub async fn find_optional_by_hashed_api_key(
+        conn: &PgPooledConn,
+        hashed_api_key: String,
+    ) -> StorageResult<Option<Self>> {
+        generics::generic_find_one_optional::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::hashed_api_key.eq(hashed_api_key),
+        )
+        .await
+    }
+
     pub async fn update_by_org_id(
         conn: &PgPooledConn,
         org_id: String,
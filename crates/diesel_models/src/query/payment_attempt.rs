@@ -25,6 +25,15 @@ impl PaymentAttemptNew {
     pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<PaymentAttempt> {
         generics::generic_insert(conn, self).await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn batch_insert(
+        payment_attempts: Vec<Self>,
+        conn: &PgPooledConn,
+    ) -> StorageResult<()> {
+        generics::generic_insert::<_, _, PaymentAttempt>(conn, payment_attempts).await?;
+        Ok(())
+    }
 }
 
 impl PaymentAttempt {
@@ -310,7 +319,7 @@ impl PaymentAttempt {
     }
     pub async fn get_total_count_of_attempts(
         conn: &PgPooledConn,
-        merchant_id: &str,
+        merchant_ids: &[String],
         active_attempt_ids: &[String],
         connector: Option<Vec<String>>,
         payment_method: Option<Vec<enums::PaymentMethod>>,
@@ -319,7 +328,7 @@ impl PaymentAttempt {
     ) -> StorageResult<i64> {
         let mut filter = <Self as HasTable>::table()
             .count()
-            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::merchant_id.eq_any(merchant_ids.to_owned()))
             .filter(dsl::attempt_id.eq_any(active_attempt_ids.to_owned()))
             .into_boxed();
 
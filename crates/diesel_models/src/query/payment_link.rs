@@ -1,10 +1,13 @@
-use diesel::{associations::HasTable, ExpressionMethods};
+use async_bb8_diesel::{AsyncConnection, AsyncRunQueryDsl};
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, QueryDsl};
 use router_env::{instrument, tracing};
 
 use super::generics;
 use crate::{
-    payment_link::{PaymentLink, PaymentLinkNew},
-    schema::payment_link::dsl,
+    enums,
+    payment_link::{PaymentLink, PaymentLinkNew, PaymentLinkUpdate, PaymentLinkUpdateInternal},
+    reusable_payment_link::ReusablePaymentLink,
+    schema::{payment_link::dsl, reusable_payment_link::dsl as reusable_payment_link_dsl},
     PgPooledConn, StorageResult,
 };
 
@@ -26,4 +29,140 @@ impl PaymentLink {
         )
         .await
     }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_payment_link_id(
+        self,
+        conn: &PgPooledConn,
+        payment_link_update: PaymentLinkUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_by_id::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            self.payment_link_id.clone(),
+            PaymentLinkUpdateInternal::from(payment_link_update),
+        )
+        .await
+    }
+
+    /// Marks the payment link as completed unless it already is. The payment is recorded on the
+    /// reusable payment link that the payment link was created from in the same transaction, and
+    /// reusable payment links that have been paid as many times as allowed are completed as well.
+    /// Payment links that have already been completed are not found.
+    #[instrument(skip(conn))]
+    pub async fn complete(conn: &PgPooledConn, payment_link_id: &str) -> StorageResult<Self> {
+        let payment_link_id = payment_link_id.to_owned();
+        conn.transaction_async(|conn| async move {
+            let previous_status: enums::PaymentLinkStatus = dsl::payment_link
+                .filter(dsl::payment_link_id.eq(payment_link_id.clone()))
+                .select(dsl::status)
+                .for_update()
+                .get_result_async(&conn)
+                .await?;
+
+            let payment_link: Self = diesel::update(
+                dsl::payment_link.filter(
+                    dsl::payment_link_id
+                        .eq(payment_link_id)
+                        .and(dsl::status.ne(enums::PaymentLinkStatus::Completed)),
+                ),
+            )
+            .set((
+                dsl::status.eq(enums::PaymentLinkStatus::Completed),
+                dsl::last_modified_at.eq(common_utils::date_time::now()),
+            ))
+            .get_result_async(&conn)
+            .await?;
+
+            let Some(reusable_payment_link_id) = payment_link.reusable_payment_link_id.clone()
+            else {
+                return Ok(payment_link);
+            };
+
+            // The use reserved for payment links that expired is released, so it is reserved
+            // again for payments that succeed after all
+            let reserved_again = i32::from(previous_status == enums::PaymentLinkStatus::Expired);
+            let reusable_payment_link: ReusablePaymentLink = diesel::update(
+                reusable_payment_link_dsl::reusable_payment_link.filter(
+                    reusable_payment_link_dsl::reusable_payment_link_id
+                        .eq(reusable_payment_link_id.clone()),
+                ),
+            )
+            .set((
+                reusable_payment_link_dsl::times_used.eq(reusable_payment_link_dsl::times_used + 1),
+                reusable_payment_link_dsl::times_reserved
+                    .eq(reusable_payment_link_dsl::times_reserved + reserved_again),
+                reusable_payment_link_dsl::amount_collected
+                    .eq(reusable_payment_link_dsl::amount_collected + payment_link.amount),
+                reusable_payment_link_dsl::last_modified_at.eq(common_utils::date_time::now()),
+            ))
+            .get_result_async(&conn)
+            .await?;
+
+            let is_exhausted = reusable_payment_link.max_uses.map_or(false, |max_uses| {
+                reusable_payment_link.times_used >= max_uses
+            });
+            if reusable_payment_link.status == enums::PaymentLinkStatus::Active && is_exhausted {
+                diesel::update(
+                    reusable_payment_link_dsl::reusable_payment_link.filter(
+                        reusable_payment_link_dsl::reusable_payment_link_id
+                            .eq(reusable_payment_link_id),
+                    ),
+                )
+                .set((
+                    reusable_payment_link_dsl::status.eq(enums::PaymentLinkStatus::Completed),
+                    reusable_payment_link_dsl::last_modified_at.eq(common_utils::date_time::now()),
+                ))
+                .execute_async(&conn)
+                .await?;
+            }
+
+            Ok(payment_link)
+        })
+        .await
+        .map_err(|error| generics::to_database_error(error, "Error while completing payment link"))
+    }
+
+    /// Marks the active payment link as expired, releasing the use reserved on the reusable
+    /// payment link that it was created from in the same transaction. Payment links that are no
+    /// longer active are not found.
+    #[instrument(skip(conn))]
+    pub async fn expire(conn: &PgPooledConn, payment_link_id: &str) -> StorageResult<Self> {
+        let payment_link_id = payment_link_id.to_owned();
+        conn.transaction_async(|conn| async move {
+            let payment_link: Self = diesel::update(
+                dsl::payment_link.filter(
+                    dsl::payment_link_id
+                        .eq(payment_link_id)
+                        .and(dsl::status.eq(enums::PaymentLinkStatus::Active)),
+                ),
+            )
+            .set((
+                dsl::status.eq(enums::PaymentLinkStatus::Expired),
+                dsl::last_modified_at.eq(common_utils::date_time::now()),
+            ))
+            .get_result_async(&conn)
+            .await?;
+
+            if let Some(reusable_payment_link_id) = payment_link.reusable_payment_link_id.clone() {
+                diesel::update(
+                    reusable_payment_link_dsl::reusable_payment_link.filter(
+                        reusable_payment_link_dsl::reusable_payment_link_id
+                            .eq(reusable_payment_link_id)
+                            .and(reusable_payment_link_dsl::times_reserved.gt(0)),
+                    ),
+                )
+                .set((
+                    reusable_payment_link_dsl::times_reserved
+                        .eq(reusable_payment_link_dsl::times_reserved - 1),
+                    reusable_payment_link_dsl::last_modified_at.eq(common_utils::date_time::now()),
+                ))
+                .execute_async(&conn)
+                .await?;
+            }
+
+            Ok(payment_link)
+        })
+        .await
+        .map_err(|error| generics::to_database_error(error, "Error while expiring payment link"))
+    }
 }
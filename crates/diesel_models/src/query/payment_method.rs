@@ -1,4 +1,6 @@
-use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods, Table};
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, PgExpressionMethods, Table,
+};
 use router_env::{instrument, tracing};
 
 use super::generics;
@@ -123,4 +125,48 @@ impl PaymentMethod {
             result => result,
         }
     }
+
+    /// Lists the payment methods of the merchant whose identifiers follow `payment_method_id`, in the order of
+    /// their identifiers.
+    #[instrument(skip(conn))]
+    pub async fn list_by_merchant_id_after_payment_method_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_method_id: &str,
+        limit: i64,
+    ) -> StorageResult<Vec<Self>> {
+        generics::generic_filter::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::payment_method_id.gt(payment_method_id.to_owned())),
+            Some(limit),
+            None,
+            Some(dsl::payment_method_id),
+        )
+        .await
+    }
+
+    /// Updates the encrypted payment method data, only if it is unchanged since the payment method
+    /// was fetched, so that data updated concurrently is not overwritten with stale values.
+    #[instrument(skip(conn))]
+    pub async fn update_encrypted_fields_if_unchanged(
+        self,
+        conn: &PgPooledConn,
+        payment_method: payment_method::PaymentMethodUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::payment_method_id
+                .eq(self.payment_method_id)
+                .and(dsl::payment_method_data.is_not_distinct_from(self.payment_method_data)),
+            payment_method::PaymentMethodUpdateInternal::from(payment_method),
+        )
+        .await
+    }
 }
@@ -0,0 +1,108 @@
+use diesel::{
+    associations::HasTable, BoolExpressionMethods, ExpressionMethods, NullableExpressionMethods,
+};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    enums,
+    reusable_payment_link::{
+        ReusablePaymentLink, ReusablePaymentLinkNew, ReusablePaymentLinkUpdate,
+        ReusablePaymentLinkUpdateInternal,
+    },
+    schema::reusable_payment_link::dsl,
+    PgPooledConn, StorageResult,
+};
+
+impl ReusablePaymentLinkNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<ReusablePaymentLink> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl ReusablePaymentLink {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_reusable_payment_link_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        reusable_payment_link_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::reusable_payment_link_id.eq(reusable_payment_link_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_reusable_payment_link_id(
+        self,
+        conn: &PgPooledConn,
+        reusable_payment_link_update: ReusablePaymentLinkUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_by_id::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            self.reusable_payment_link_id.clone(),
+            ReusablePaymentLinkUpdateInternal::from(reusable_payment_link_update),
+        )
+        .await
+    }
+
+    /// Reserves a use of the reusable payment link for a payment created from it. The counter is
+    /// incremented only while the link is active and has uses left, so that links visited
+    /// concurrently are not paid more times than allowed. Links without uses left are not found.
+    #[instrument(skip(conn))]
+    pub async fn reserve_usage(
+        conn: &PgPooledConn,
+        reusable_payment_link_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::reusable_payment_link_id
+                .eq(reusable_payment_link_id.to_owned())
+                .and(dsl::status.eq(enums::PaymentLinkStatus::Active))
+                .and(
+                    dsl::max_uses
+                        .is_null()
+                        .or(dsl::max_uses.gt(dsl::times_reserved.nullable())),
+                ),
+            (
+                dsl::times_reserved.eq(dsl::times_reserved + 1),
+                dsl::last_modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
+
+    /// Releases a use reserved for a payment that will not be paid.
+    #[instrument(skip(conn))]
+    pub async fn release_usage(
+        conn: &PgPooledConn,
+        reusable_payment_link_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::reusable_payment_link_id
+                .eq(reusable_payment_link_id.to_owned())
+                .and(dsl::times_reserved.gt(0)),
+            (
+                dsl::times_reserved.eq(dsl::times_reserved - 1),
+                dsl::last_modified_at.eq(common_utils::date_time::now()),
+            ),
+        )
+        .await
+    }
+}
@@ -0,0 +1,50 @@
+use diesel::{associations::HasTable, BoolExpressionMethods, ExpressionMethods};
+use router_env::{instrument, tracing};
+
+use super::generics;
+use crate::{
+    schema::subscription_plan::dsl,
+    subscription_plan::{
+        SubscriptionPlan, SubscriptionPlanNew, SubscriptionPlanUpdate,
+        SubscriptionPlanUpdateInternal,
+    },
+    PgPooledConn, StorageResult,
+};
+
+impl SubscriptionPlanNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<SubscriptionPlan> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl SubscriptionPlan {
+    #[instrument(skip(conn))]
+    pub async fn find_by_merchant_id_plan_id(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        plan_id: &str,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::merchant_id
+                .eq(merchant_id.to_owned())
+                .and(dsl::plan_id.eq(plan_id.to_owned())),
+        )
+        .await
+    }
+
+    #[instrument(skip(conn))]
+    pub async fn update_by_plan_id(
+        self,
+        conn: &PgPooledConn,
+        subscription_plan_update: SubscriptionPlanUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_by_id::<<Self as HasTable>::Table, _, _, _>(
+            conn,
+            self.plan_id.clone(),
+            SubscriptionPlanUpdateInternal::from(subscription_plan_update),
+        )
+        .await
+    }
+}
@@ -0,0 +1,56 @@
+use common_utils::pii;
+use diesel::{associations::HasTable, ExpressionMethods};
+use router_env::tracing::{self, instrument};
+
+use crate::{
+    query::generics,
+    schema::users::dsl,
+    user::{User, UserNew, UserUpdate, UserUpdateInternal},
+    PgPooledConn, StorageResult,
+};
+
+impl UserNew {
+    #[instrument(skip(conn))]
+    pub async fn insert(self, conn: &PgPooledConn) -> StorageResult<User> {
+        generics::generic_insert(conn, self).await
+    }
+}
+
+impl User {
+    pub async fn find_by_user_email(
+        conn: &PgPooledConn,
+        email: &pii::Email,
+    ) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::email.eq(email.to_owned()),
+        )
+        .await
+    }
+
+    pub async fn find_by_user_id(conn: &PgPooledConn, user_id: &str) -> StorageResult<Self> {
+        generics::generic_find_one::<<Self as HasTable>::Table, _, _>(
+            conn,
+            dsl::user_id.eq(user_id.to_owned()),
+        )
+        .await
+    }
+
+    pub async fn update_by_user_id(
+        conn: &PgPooledConn,
+        user_id: &str,
+        user: UserUpdate,
+    ) -> StorageResult<Self> {
+        generics::generic_update_with_unique_predicate_get_result::<
+            <Self as HasTable>::Table,
+            _,
+            _,
+            _,
+        >(
+            conn,
+            dsl::user_id.eq(user_id.to_owned()),
+            UserUpdateInternal::from(user),
+        )
+        .await
+    }
+}
@@ -0,0 +1,106 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{self, Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::reusable_payment_link};
+
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[diesel(table_name = reusable_payment_link)]
+#[diesel(primary_key(reusable_payment_link_id))]
+pub struct ReusablePaymentLink {
+    pub reusable_payment_link_id: String,
+    pub merchant_id: String,
+    pub link_to_pay: String,
+    pub currency: storage_enums::Currency,
+    pub amount: Option<i64>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub product_name: String,
+    pub product_img_link: Option<String>,
+    pub description: Option<String>,
+    pub return_url: Option<String>,
+    pub merchant_custom_domain_name: Option<String>,
+    pub payment_link_config: Option<serde_json::Value>,
+    pub max_uses: Option<i32>,
+    pub times_used: i32,
+    pub times_reserved: i32,
+    pub amount_collected: i64,
+    pub status: storage_enums::PaymentLinkStatus,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub link_expiry: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Insertable,
+    serde::Serialize,
+    serde::Deserialize,
+    router_derive::DebugAsDisplay,
+)]
+#[diesel(table_name = reusable_payment_link)]
+pub struct ReusablePaymentLinkNew {
+    pub reusable_payment_link_id: String,
+    pub merchant_id: String,
+    pub link_to_pay: String,
+    pub currency: storage_enums::Currency,
+    pub amount: Option<i64>,
+    pub min_amount: Option<i64>,
+    pub max_amount: Option<i64>,
+    pub product_name: String,
+    pub product_img_link: Option<String>,
+    pub description: Option<String>,
+    pub return_url: Option<String>,
+    pub merchant_custom_domain_name: Option<String>,
+    pub payment_link_config: Option<serde_json::Value>,
+    pub max_uses: Option<i32>,
+    pub status: storage_enums::PaymentLinkStatus,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub link_expiry: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub created_at: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_modified_at: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug)]
+pub enum ReusablePaymentLinkUpdate {
+    Update {
+        status: Option<storage_enums::PaymentLinkStatus>,
+        max_uses: Option<i32>,
+    },
+    StatusUpdate {
+        status: storage_enums::PaymentLinkStatus,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = reusable_payment_link)]
+pub struct ReusablePaymentLinkUpdateInternal {
+    status: Option<storage_enums::PaymentLinkStatus>,
+    max_uses: Option<i32>,
+    last_modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<ReusablePaymentLinkUpdate> for ReusablePaymentLinkUpdateInternal {
+    fn from(reusable_payment_link_update: ReusablePaymentLinkUpdate) -> Self {
+        match reusable_payment_link_update {
+            ReusablePaymentLinkUpdate::Update { status, max_uses } => Self {
+                status,
+                max_uses,
+                last_modified_at: Some(common_utils::date_time::now()),
+            },
+            ReusablePaymentLinkUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                last_modified_at: Some(common_utils::date_time::now()),
+                ..Default::default()
+            },
+        }
+    }
+}
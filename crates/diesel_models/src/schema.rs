@@ -54,6 +54,35 @@ diesel::table! {
         created_at -> Timestamp,
         expires_at -> Nullable<Timestamp>,
         last_used -> Nullable<Timestamp>,
+        scopes -> Nullable<Array<Nullable<Text>>>,
+        allowed_ips -> Nullable<Array<Nullable<Text>>>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    audit_events (id) {
+        id -> Int4,
+        #[max_length = 64]
+        audit_event_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        resource_type -> Varchar,
+        #[max_length = 255]
+        resource_id -> Varchar,
+        #[max_length = 64]
+        action -> Varchar,
+        #[max_length = 64]
+        auth_type -> Varchar,
+        #[max_length = 255]
+        actor_id -> Nullable<Varchar>,
+        #[max_length = 64]
+        request_id -> Nullable<Varchar>,
+        diff -> Jsonb,
+        created_at -> Timestamp,
     }
 }
 
@@ -332,6 +361,40 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    invoice (invoice_id) {
+        #[max_length = 64]
+        invoice_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        customer_id -> Varchar,
+        #[max_length = 64]
+        status -> Varchar,
+        currency -> Currency,
+        line_items -> Jsonb,
+        tax_lines -> Jsonb,
+        subtotal -> Int8,
+        tax_amount -> Int8,
+        total_amount -> Int8,
+        #[max_length = 255]
+        description -> Nullable<Varchar>,
+        due_date -> Timestamp,
+        #[max_length = 64]
+        payment_id -> Nullable<Varchar>,
+        #[max_length = 255]
+        payment_link_id -> Nullable<Varchar>,
+        metadata -> Nullable<Jsonb>,
+        finalized_at -> Nullable<Timestamp>,
+        paid_at -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        last_modified_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -410,6 +473,31 @@ diesel::table! {
         original_payment_id -> Nullable<Varchar>,
         #[max_length = 32]
         merchant_connector_id -> Nullable<Varchar>,
+        frequency_limit -> Nullable<Int4>,
+        #[max_length = 64]
+        frequency_interval -> Nullable<Varchar>,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    mandate_usage (id) {
+        id -> Int4,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        mandate_id -> Varchar,
+        #[max_length = 64]
+        payment_id -> Varchar,
+        #[max_length = 64]
+        attempt_id -> Varchar,
+        amount -> Int8,
+        currency -> Currency,
+        #[max_length = 64]
+        connector -> Varchar,
+        created_at -> Timestamp,
     }
 }
 
@@ -453,6 +541,7 @@ diesel::table! {
         default_profile -> Nullable<Varchar>,
         recon_status -> ReconStatus,
         payment_link_config -> Nullable<Jsonb>,
+        email_config -> Nullable<Jsonb>,
     }
 }
 
@@ -502,6 +591,8 @@ diesel::table! {
         merchant_id -> Varchar,
         key -> Bytea,
         created_at -> Timestamp,
+        key_version -> Int4,
+        retired_keys -> Nullable<Jsonb>,
     }
 }
 
@@ -513,6 +604,10 @@ diesel::table! {
         #[max_length = 32]
         org_id -> Varchar,
         org_name -> Nullable<Text>,
+        #[max_length = 128]
+        hashed_api_key -> Nullable<Varchar>,
+        #[max_length = 16]
+        api_key_prefix -> Nullable<Varchar>,
     }
 }
 
@@ -664,6 +759,11 @@ diesel::table! {
         created_at -> Timestamp,
         last_modified_at -> Timestamp,
         fulfilment_time -> Nullable<Timestamp>,
+        payment_link_config -> Nullable<Jsonb>,
+        #[max_length = 64]
+        status -> Varchar,
+        #[max_length = 64]
+        reusable_payment_link_id -> Nullable<Varchar>,
     }
 }
 
@@ -856,6 +956,42 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    reusable_payment_link (reusable_payment_link_id) {
+        #[max_length = 64]
+        reusable_payment_link_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 255]
+        link_to_pay -> Varchar,
+        currency -> Currency,
+        amount -> Nullable<Int8>,
+        min_amount -> Nullable<Int8>,
+        max_amount -> Nullable<Int8>,
+        #[max_length = 255]
+        product_name -> Varchar,
+        product_img_link -> Nullable<Text>,
+        #[max_length = 255]
+        description -> Nullable<Varchar>,
+        return_url -> Nullable<Text>,
+        #[max_length = 255]
+        merchant_custom_domain_name -> Nullable<Varchar>,
+        payment_link_config -> Nullable<Jsonb>,
+        max_uses -> Nullable<Int4>,
+        times_used -> Int4,
+        times_reserved -> Int4,
+        amount_collected -> Int8,
+        #[max_length = 64]
+        status -> Varchar,
+        link_expiry -> Nullable<Timestamp>,
+        created_at -> Timestamp,
+        last_modified_at -> Timestamp,
+    }
+}
+
 diesel::table! {
     use diesel::sql_types::*;
     use crate::enums::diesel_exports::*;
@@ -874,9 +1010,114 @@ diesel::table! {
     }
 }
 
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    subscription (subscription_id) {
+        #[max_length = 64]
+        subscription_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        customer_id -> Varchar,
+        #[max_length = 64]
+        plan_id -> Varchar,
+        #[max_length = 64]
+        mandate_id -> Varchar,
+        #[max_length = 64]
+        status -> Varchar,
+        current_period_start -> Timestamp,
+        current_period_end -> Timestamp,
+        billing_anchor -> Timestamp,
+        trial_end -> Nullable<Timestamp>,
+        cancel_at_period_end -> Bool,
+        cancelled_at -> Nullable<Timestamp>,
+        #[max_length = 64]
+        latest_payment_id -> Nullable<Varchar>,
+        failed_payment_attempts -> Int4,
+        proration_amount -> Int8,
+        metadata -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+        last_modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    subscription_plan (plan_id) {
+        #[max_length = 64]
+        plan_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        description -> Nullable<Varchar>,
+        amount -> Int8,
+        currency -> Currency,
+        #[max_length = 64]
+        interval -> Varchar,
+        interval_count -> Int4,
+        trial_period_days -> Nullable<Int4>,
+        active -> Bool,
+        metadata -> Nullable<Jsonb>,
+        created_at -> Timestamp,
+        last_modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    user_roles (id) {
+        id -> Int4,
+        #[max_length = 64]
+        user_id -> Varchar,
+        #[max_length = 64]
+        merchant_id -> Varchar,
+        #[max_length = 64]
+        org_id -> Varchar,
+        #[max_length = 64]
+        role -> Varchar,
+        #[max_length = 64]
+        status -> Varchar,
+        #[max_length = 64]
+        created_by -> Varchar,
+        #[max_length = 64]
+        last_modified_by -> Varchar,
+        created_at -> Timestamp,
+        last_modified_at -> Timestamp,
+    }
+}
+
+diesel::table! {
+    use diesel::sql_types::*;
+    use crate::enums::diesel_exports::*;
+
+    users (id) {
+        id -> Int4,
+        #[max_length = 64]
+        user_id -> Varchar,
+        #[max_length = 255]
+        email -> Varchar,
+        #[max_length = 255]
+        name -> Varchar,
+        #[max_length = 255]
+        password -> Nullable<Varchar>,
+        is_verified -> Bool,
+        created_at -> Timestamp,
+        last_modified_at -> Timestamp,
+    }
+}
+
 diesel::allow_tables_to_appear_in_same_query!(
     address,
     api_keys,
+    audit_events,
     business_profile,
     captures,
     cards_info,
@@ -887,8 +1128,10 @@ diesel::allow_tables_to_appear_in_same_query!(
     events,
     file_metadata,
     fraud_check,
+    invoice,
     locker_mock_up,
     mandate,
+    mandate_usage,
     merchant_account,
     merchant_connector_account,
     merchant_key_store,
@@ -901,5 +1144,10 @@ diesel::allow_tables_to_appear_in_same_query!(
     payouts,
     process_tracker,
     refund,
+    reusable_payment_link,
     reverse_lookup,
+    subscription,
+    subscription_plan,
+    user_roles,
+    users,
 );
@@ -0,0 +1,194 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{self, Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::subscription};
+
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[diesel(table_name = subscription)]
+#[diesel(primary_key(subscription_id))]
+pub struct Subscription {
+    pub subscription_id: String,
+    pub merchant_id: String,
+    pub customer_id: String,
+    pub plan_id: String,
+    pub mandate_id: String,
+    pub status: storage_enums::SubscriptionStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub current_period_start: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub current_period_end: PrimitiveDateTime,
+    /// The time that billing periods are counted from, so that they end on the same day of the
+    /// month even after shorter months.
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub billing_anchor: PrimitiveDateTime,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub trial_end: Option<PrimitiveDateTime>,
+    pub cancel_at_period_end: bool,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    pub cancelled_at: Option<PrimitiveDateTime>,
+    pub latest_payment_id: Option<String>,
+    pub failed_payment_attempts: i32,
+    pub proration_amount: i64,
+    pub metadata: Option<serde_json::Value>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Insertable,
+    serde::Serialize,
+    serde::Deserialize,
+    router_derive::DebugAsDisplay,
+)]
+#[diesel(table_name = subscription)]
+pub struct SubscriptionNew {
+    pub subscription_id: String,
+    pub merchant_id: String,
+    pub customer_id: String,
+    pub plan_id: String,
+    pub mandate_id: String,
+    pub status: storage_enums::SubscriptionStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub current_period_start: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub current_period_end: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub billing_anchor: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub trial_end: Option<PrimitiveDateTime>,
+    pub metadata: Option<serde_json::Value>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub created_at: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_modified_at: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug)]
+pub enum SubscriptionUpdate {
+    /// The subscription has been paid for a new billing period
+    Renewal {
+        latest_payment_id: Option<String>,
+        current_period_start: PrimitiveDateTime,
+        current_period_end: PrimitiveDateTime,
+        proration_amount: i64,
+    },
+    PaymentFailure {
+        latest_payment_id: Option<String>,
+        failed_payment_attempts: i32,
+        status: storage_enums::SubscriptionStatus,
+    },
+    /// The payment for the new billing period is about to be made
+    PaymentInitiation { latest_payment_id: Option<String> },
+    /// The payment for the new billing period has not reached its final status yet
+    PaymentPending { latest_payment_id: Option<String> },
+    PlanUpdate {
+        plan_id: String,
+        proration_amount: i64,
+    },
+    Update {
+        mandate_id: Option<String>,
+        cancel_at_period_end: Option<bool>,
+        metadata: Option<serde_json::Value>,
+    },
+    StatusUpdate {
+        status: storage_enums::SubscriptionStatus,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = subscription)]
+pub struct SubscriptionUpdateInternal {
+    plan_id: Option<String>,
+    mandate_id: Option<String>,
+    status: Option<storage_enums::SubscriptionStatus>,
+    current_period_start: Option<PrimitiveDateTime>,
+    current_period_end: Option<PrimitiveDateTime>,
+    cancel_at_period_end: Option<bool>,
+    cancelled_at: Option<PrimitiveDateTime>,
+    latest_payment_id: Option<String>,
+    failed_payment_attempts: Option<i32>,
+    proration_amount: Option<i64>,
+    metadata: Option<serde_json::Value>,
+    last_modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<SubscriptionUpdate> for SubscriptionUpdateInternal {
+    fn from(subscription_update: SubscriptionUpdate) -> Self {
+        let now = common_utils::date_time::now();
+        let cancelled_at =
+            |status| (status == storage_enums::SubscriptionStatus::Cancelled).then_some(now);
+        match subscription_update {
+            SubscriptionUpdate::Renewal {
+                latest_payment_id,
+                current_period_start,
+                current_period_end,
+                proration_amount,
+            } => Self {
+                status: Some(storage_enums::SubscriptionStatus::Active),
+                latest_payment_id,
+                current_period_start: Some(current_period_start),
+                current_period_end: Some(current_period_end),
+                failed_payment_attempts: Some(0),
+                proration_amount: Some(proration_amount),
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            SubscriptionUpdate::PaymentFailure {
+                latest_payment_id,
+                failed_payment_attempts,
+                status,
+            } => Self {
+                status: Some(status),
+                cancelled_at: cancelled_at(status),
+                latest_payment_id,
+                failed_payment_attempts: Some(failed_payment_attempts),
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            SubscriptionUpdate::PaymentInitiation { latest_payment_id } => Self {
+                latest_payment_id,
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            SubscriptionUpdate::PaymentPending { latest_payment_id } => Self {
+                status: Some(storage_enums::SubscriptionStatus::PastDue),
+                latest_payment_id,
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            SubscriptionUpdate::PlanUpdate {
+                plan_id,
+                proration_amount,
+            } => Self {
+                plan_id: Some(plan_id),
+                proration_amount: Some(proration_amount),
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            SubscriptionUpdate::Update {
+                mandate_id,
+                cancel_at_period_end,
+                metadata,
+            } => Self {
+                mandate_id,
+                cancel_at_period_end,
+                metadata,
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+            SubscriptionUpdate::StatusUpdate { status } => Self {
+                status: Some(status),
+                cancelled_at: cancelled_at(status),
+                last_modified_at: Some(now),
+                ..Default::default()
+            },
+        }
+    }
+}
@@ -0,0 +1,94 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use serde::{self, Deserialize, Serialize};
+use time::PrimitiveDateTime;
+
+use crate::{enums as storage_enums, schema::subscription_plan};
+
+#[derive(Clone, Debug, Eq, PartialEq, Identifiable, Queryable, Serialize, Deserialize)]
+#[diesel(table_name = subscription_plan)]
+#[diesel(primary_key(plan_id))]
+pub struct SubscriptionPlan {
+    pub plan_id: String,
+    pub merchant_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub interval: storage_enums::SubscriptionInterval,
+    pub interval_count: i32,
+    pub trial_period_days: Option<i32>,
+    pub active: bool,
+    pub metadata: Option<serde_json::Value>,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub created_at: PrimitiveDateTime,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(
+    Clone,
+    Debug,
+    Eq,
+    PartialEq,
+    Insertable,
+    serde::Serialize,
+    serde::Deserialize,
+    router_derive::DebugAsDisplay,
+)]
+#[diesel(table_name = subscription_plan)]
+pub struct SubscriptionPlanNew {
+    pub plan_id: String,
+    pub merchant_id: String,
+    pub name: String,
+    pub description: Option<String>,
+    pub amount: i64,
+    pub currency: storage_enums::Currency,
+    pub interval: storage_enums::SubscriptionInterval,
+    pub interval_count: i32,
+    pub trial_period_days: Option<i32>,
+    pub active: bool,
+    pub metadata: Option<serde_json::Value>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub created_at: Option<PrimitiveDateTime>,
+    #[serde(with = "common_utils::custom_serde::iso8601::option")]
+    pub last_modified_at: Option<PrimitiveDateTime>,
+}
+
+#[derive(Debug)]
+pub enum SubscriptionPlanUpdate {
+    Update {
+        name: Option<String>,
+        description: Option<String>,
+        active: Option<bool>,
+        metadata: Option<serde_json::Value>,
+    },
+}
+
+#[derive(Clone, Debug, Default, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = subscription_plan)]
+pub struct SubscriptionPlanUpdateInternal {
+    name: Option<String>,
+    description: Option<String>,
+    active: Option<bool>,
+    metadata: Option<serde_json::Value>,
+    last_modified_at: Option<PrimitiveDateTime>,
+}
+
+impl From<SubscriptionPlanUpdate> for SubscriptionPlanUpdateInternal {
+    fn from(subscription_plan_update: SubscriptionPlanUpdate) -> Self {
+        match subscription_plan_update {
+            SubscriptionPlanUpdate::Update {
+                name,
+                description,
+                active,
+                metadata,
+            } => Self {
+                name,
+                description,
+                active,
+                metadata,
+                last_modified_at: Some(common_utils::date_time::now()),
+            },
+        }
+    }
+}
@@ -0,0 +1,100 @@
+use common_utils::pii;
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use masking::Secret;
+use time::PrimitiveDateTime;
+
+use crate::schema::users;
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = users)]
+pub struct User {
+    pub id: i32,
+    pub user_id: String,
+    pub email: pii::Email,
+    pub name: Secret<String>,
+    /// The hash of the password of the user, absent until the user sets a password.
+    pub password: Option<Secret<String>>,
+    pub is_verified: bool,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = users)]
+pub struct UserNew {
+    pub user_id: String,
+    pub email: pii::Email,
+    pub name: Secret<String>,
+    pub password: Option<Secret<String>>,
+    pub is_verified: bool,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = users)]
+pub struct UserUpdateInternal {
+    name: Option<Secret<String>>,
+    password: Option<Secret<String>>,
+    is_verified: Option<bool>,
+    last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum UserUpdate {
+    NameUpdate {
+        name: Secret<String>,
+    },
+    PasswordUpdate {
+        password: Secret<String>,
+    },
+    /// Marks the email of the user as verified, setting their password if given.
+    VerifyUser {
+        password: Option<Secret<String>>,
+    },
+}
+
+impl From<UserUpdate> for UserUpdateInternal {
+    fn from(user_update: UserUpdate) -> Self {
+        let last_modified_at = common_utils::date_time::now();
+        match user_update {
+            UserUpdate::NameUpdate { name } => Self {
+                name: Some(name),
+                password: None,
+                is_verified: None,
+                last_modified_at,
+            },
+            UserUpdate::PasswordUpdate { password } => Self {
+                name: None,
+                password: Some(password),
+                is_verified: None,
+                last_modified_at,
+            },
+            UserUpdate::VerifyUser { password } => Self {
+                name: None,
+                password,
+                is_verified: Some(true),
+                last_modified_at,
+            },
+        }
+    }
+}
+
+impl UserUpdate {
+    pub fn apply_changeset(self, source: User) -> User {
+        let UserUpdateInternal {
+            name,
+            password,
+            is_verified,
+            last_modified_at,
+        } = self.into();
+
+        User {
+            name: name.unwrap_or(source.name),
+            password: password.or(source.password),
+            is_verified: is_verified.unwrap_or(source.is_verified),
+            last_modified_at,
+            ..source
+        }
+    }
+}
@@ -0,0 +1,96 @@
+use diesel::{AsChangeset, Identifiable, Insertable, Queryable};
+use time::PrimitiveDateTime;
+
+use crate::{enums, schema::user_roles};
+
+#[derive(Clone, Debug, Identifiable, Queryable)]
+#[diesel(table_name = user_roles)]
+pub struct UserRole {
+    pub id: i32,
+    pub user_id: String,
+    pub merchant_id: String,
+    pub org_id: String,
+    pub role: enums::Role,
+    pub status: enums::UserStatus,
+    pub created_by: String,
+    pub last_modified_by: String,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, Insertable, router_derive::DebugAsDisplay)]
+#[diesel(table_name = user_roles)]
+pub struct UserRoleNew {
+    pub user_id: String,
+    pub merchant_id: String,
+    pub org_id: String,
+    pub role: enums::Role,
+    pub status: enums::UserStatus,
+    pub created_by: String,
+    pub last_modified_by: String,
+    pub created_at: PrimitiveDateTime,
+    pub last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Clone, Debug, AsChangeset, router_derive::DebugAsDisplay)]
+#[diesel(table_name = user_roles)]
+pub struct UserRoleUpdateInternal {
+    role: Option<enums::Role>,
+    status: Option<enums::UserStatus>,
+    last_modified_by: String,
+    last_modified_at: PrimitiveDateTime,
+}
+
+#[derive(Debug)]
+pub enum UserRoleUpdate {
+    RoleUpdate {
+        role: enums::Role,
+        modified_by: String,
+    },
+    StatusUpdate {
+        status: enums::UserStatus,
+        modified_by: String,
+    },
+}
+
+impl From<UserRoleUpdate> for UserRoleUpdateInternal {
+    fn from(user_role_update: UserRoleUpdate) -> Self {
+        let last_modified_at = common_utils::date_time::now();
+        match user_role_update {
+            UserRoleUpdate::RoleUpdate { role, modified_by } => Self {
+                role: Some(role),
+                status: None,
+                last_modified_by: modified_by,
+                last_modified_at,
+            },
+            UserRoleUpdate::StatusUpdate {
+                status,
+                modified_by,
+            } => Self {
+                role: None,
+                status: Some(status),
+                last_modified_by: modified_by,
+                last_modified_at,
+            },
+        }
+    }
+}
+
+impl UserRoleUpdate {
+    pub fn apply_changeset(self, source: UserRole) -> UserRole {
+        let UserRoleUpdateInternal {
+            role,
+            status,
+            last_modified_by,
+            last_modified_at,
+        } = self.into();
+
+        UserRole {
+            role: role.unwrap_or(source.role),
+            status: status.unwrap_or(source.status),
+            last_modified_by,
+            last_modified_at,
+            ..source
+        }
+    }
+}
@@ -1,9 +1,6 @@
 use bb8::PooledConnection;
 use diesel::PgConnection;
-#[cfg(feature = "kms")]
-use external_services::kms::{self, decrypt::KmsDecrypt};
-#[cfg(not(feature = "kms"))]
-use masking::PeekInterface;
+use external_services::secrets_management::{SecretManagementInterface, SecretsDecrypt};
 
 use crate::settings::Database;
 
@@ -25,23 +22,23 @@ pub async fn redis_connection(
 #[allow(clippy::expect_used)]
 pub async fn diesel_make_pg_pool(
     database: &Database,
+    schema: Option<&str>,
     _test_transaction: bool,
-    #[cfg(feature = "kms")] kms_client: &'static kms::KmsClient,
+    secrets_manager: &'static dyn SecretManagementInterface,
 ) -> PgPool {
-    #[cfg(feature = "kms")]
     let password = database
         .password
-        .decrypt_inner(kms_client)
+        .decrypt_inner(secrets_manager)
         .await
         .expect("Failed to decrypt password");
 
-    #[cfg(not(feature = "kms"))]
-    let password = &database.password.peek();
-
-    let database_url = format!(
+    let mut database_url = format!(
         "postgres://{}:{}@{}:{}/{}",
         database.username, password, database.host, database.port, database.dbname
     );
+    if let Some(schema) = schema {
+        database_url.push_str(&format!("?options=-c%20search_path%3D{schema}"));
+    }
     let manager = async_bb8_diesel::ConnectionManager::<PgConnection>::new(database_url);
     let pool = bb8::Pool::builder()
         .max_size(database.pool_size)
@@ -2,6 +2,7 @@ mod connection;
 pub mod errors;
 pub mod logger;
 pub(crate) mod metrics;
+mod query;
 pub mod services;
 pub mod settings;
 mod utils;
@@ -15,9 +16,10 @@ use tokio::sync::{mpsc, oneshot};
 use crate::{connection::pg_connection, services::Store};
 
 pub async fn start_drainer(
-    store: Arc<Store>,
+    stores: Vec<Arc<Store>>,
     number_of_streams: u8,
     max_read_count: u64,
+    batch_size: usize,
     shutdown_interval: u32,
     loop_interval: u32,
 ) -> errors::DrainerResult<()> {
@@ -41,7 +43,15 @@ pub async fn start_drainer(
     let handle = signal.handle();
     let task_handle = tokio::spawn(common_utils::signals::signal_handler(signal, tx.clone()));
 
-    let redis_conn_clone = store.redis_conn.clone();
+    // The Redis connection is shared by the stores of all tenants
+    let redis_conn_clone = stores
+        .first()
+        .ok_or(errors::DrainerError::UnexpectedError(
+            "No store configured for the drainer".to_string(),
+        ))
+        .into_report()?
+        .redis_conn
+        .clone();
 
     // Spawn a task to monitor if redis is down or not
     tokio::spawn(async move { redis_conn_clone.on_error(redis_error_tx).await });
@@ -54,14 +64,17 @@ pub async fn start_drainer(
         metrics::DRAINER_HEALTH.add(&metrics::CONTEXT, 1, &[]);
         match rx.try_recv() {
             Err(mpsc::error::TryRecvError::Empty) => {
-                if utils::is_stream_available(stream_index, store.clone()).await {
-                    tokio::spawn(drainer_handler(
-                        store.clone(),
-                        stream_index,
-                        max_read_count,
-                        active_tasks.clone(),
-                    ));
-                    jobs_picked += 1;
+                for store in stores.iter() {
+                    if utils::is_stream_available(stream_index, store.clone()).await {
+                        tokio::spawn(drainer_handler(
+                            store.clone(),
+                            stream_index,
+                            max_read_count,
+                            batch_size,
+                            active_tasks.clone(),
+                        ));
+                        jobs_picked = jobs_picked.saturating_add(1);
+                    }
                 }
                 (stream_index, jobs_picked) = utils::increment_stream_index(
                     (stream_index, jobs_picked),
@@ -117,13 +130,19 @@ async fn drainer_handler(
     store: Arc<Store>,
     stream_index: u8,
     max_read_count: u64,
+    batch_size: usize,
     active_tasks: Arc<atomic::AtomicU64>,
 ) -> errors::DrainerResult<()> {
     active_tasks.fetch_add(1, atomic::Ordering::Release);
 
     let stream_name = utils::get_drainer_stream_name(store.clone(), stream_index);
-    let drainer_result =
-        Box::pin(drainer(store.clone(), max_read_count, stream_name.as_str())).await;
+    let drainer_result = Box::pin(drainer(
+        store.clone(),
+        max_read_count,
+        batch_size,
+        stream_name.as_str(),
+    ))
+    .await;
 
     if let Err(error) = drainer_result {
         logger::error!(?error)
@@ -140,6 +159,7 @@ async fn drainer_handler(
 async fn drainer(
     store: Arc<Store>,
     max_read_count: u64,
+    batch_size: usize,
     stream_name: &str,
 ) -> errors::DrainerResult<()> {
     let stream_read =
@@ -174,130 +194,24 @@ async fn drainer(
         }],
     );
 
-    // TODO: Handle errors when deserialization fails and when DB error occurs
-    for entry in entries {
-        let typed_sql = entry.1.get("typed_sql").map_or(String::new(), Clone::clone);
-        let result = serde_json::from_str::<kv::DBOperation>(&typed_sql);
-        let db_op = match result {
-            Ok(f) => f,
-            Err(_err) => continue, // TODO: handle error
-        };
-
-        let conn = pg_connection(&store.master_pool).await;
-        let insert_op = "insert";
-        let update_op = "update";
-        let payment_intent = "payment_intent";
-        let payment_attempt = "payment_attempt";
-        let refund = "refund";
-        let reverse_lookup = "reverse_lookup";
-        let connector_response = "connector_response";
-        let address = "address";
-        match db_op {
-            // TODO: Handle errors
-            kv::DBOperation::Insert { insertable } => {
-                let (_, execution_time) = common_utils::date_time::time_it(|| async {
-                    match insertable {
-                        kv::Insertable::PaymentIntent(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                payment_intent
-                            )
-                        }
-                        kv::Insertable::PaymentAttempt(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                payment_attempt
-                            )
-                        }
-                        kv::Insertable::Refund(a) => {
-                            macro_util::handle_resp!(a.insert(&conn).await, insert_op, refund)
-                        }
-                        kv::Insertable::ConnectorResponse(a) => {
-                            macro_util::handle_resp!(
-                                a.insert(&conn).await,
-                                insert_op,
-                                connector_response
-                            )
-                        }
-                        kv::Insertable::Address(addr) => {
-                            macro_util::handle_resp!(addr.insert(&conn).await, insert_op, address)
-                        }
-                        kv::Insertable::ReverseLookUp(rev) => {
-                            macro_util::handle_resp!(
-                                rev.insert(&conn).await,
-                                insert_op,
-                                reverse_lookup
-                            )
-                        }
-                    }
-                })
-                .await;
-                metrics::QUERY_EXECUTION_TIME.record(
-                    &metrics::CONTEXT,
-                    execution_time,
-                    &[metrics::KeyValue {
-                        key: "operation".into(),
-                        value: insert_op.into(),
-                    }],
-                );
-            }
-            kv::DBOperation::Update { updatable } => {
-                let (_, execution_time) = common_utils::date_time::time_it(|| async {
-                    match updatable {
-                        kv::Updateable::PaymentIntentUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update(&conn, a.update_data).await,
-                                update_op,
-                                payment_intent
-                            )
-                        }
-                        kv::Updateable::PaymentAttemptUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update_with_attempt_id(&conn, a.update_data).await,
-                                update_op,
-                                payment_attempt
-                            )
-                        }
-                        kv::Updateable::RefundUpdate(a) => {
-                            macro_util::handle_resp!(
-                                a.orig.update(&conn, a.update_data).await,
-                                update_op,
-                                refund
-                            )
-                        }
-                        kv::Updateable::ConnectorResponseUpdate(a) => macro_util::handle_resp!(
-                            a.orig.update(&conn, a.update_data).await,
-                            update_op,
-                            connector_response
-                        ),
-                        kv::Updateable::AddressUpdate(a) => macro_util::handle_resp!(
-                            a.orig.update(&conn, a.update_data).await,
-                            update_op,
-                            address
-                        ),
-                    }
-                })
-                .await;
-                metrics::QUERY_EXECUTION_TIME.record(
-                    &metrics::CONTEXT,
-                    execution_time,
-                    &[metrics::KeyValue {
-                        key: "operation".into(),
-                        value: update_op.into(),
-                    }],
-                );
-            }
-            kv::DBOperation::Delete => {
-                // [#224]: Implement this
-                logger::error!("Not implemented!");
-            }
-        };
+    // TODO: Handle errors when deserialization fails
+    let operations: Vec<_> = entries
+        .iter()
+        .filter_map(|entry| {
+            let typed_sql = entry.1.get("typed_sql").map_or(String::new(), Clone::clone);
+            serde_json::from_str::<kv::DBOperation>(&typed_sql).ok()
+        })
+        .collect();
+    let drained_entries_by_merchant = query::count_operations_by_merchant(&operations);
+
+    let conn = pg_connection(&store.master_pool).await;
+    for batch in query::group_into_batches(operations, batch_size) {
+        query::execute_batch(&conn, batch).await;
     }
 
     let entries_trimmed =
         utils::trim_from_stream(stream_name, last_entry_id.as_str(), &store.redis_conn).await?;
+    utils::decrement_pending_entries(&store.redis_conn, drained_entries_by_merchant).await;
 
     if read_count != entries_trimmed {
         logger::error!(
@@ -310,32 +224,3 @@ async fn drainer(
 
     Ok(())
 }
-
-mod macro_util {
-
-    macro_rules! handle_resp {
-        ($result:expr,$op_type:expr, $table:expr) => {
-            match $result {
-                Ok(inner_result) => {
-                    logger::info!(operation = %$op_type, table = %$table, ?inner_result);
-                    metrics::SUCCESSFUL_QUERY_EXECUTION.add(&metrics::CONTEXT, 1, &[
-                        metrics::KeyValue {
-                            key: "operation".into(),
-                            value: $table.into(),
-                        }
-                    ]);
-                }
-                Err(err) => {
-                    logger::error!(operation = %$op_type, table = %$table, ?err);
-                    metrics::ERRORS_WHILE_QUERY_EXECUTION.add(&metrics::CONTEXT, 1, &[
-                        metrics::KeyValue {
-                            key: "operation".into(),
-                            value: $table.into(),
-                        }
-                    ]);
-                }
-            }
-        };
-    }
-    pub(crate) use handle_resp;
-}
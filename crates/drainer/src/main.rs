@@ -13,10 +13,20 @@ async fn main() -> DrainerResult<()> {
         .expect("Failed to validate drainer configuration");
 
     let store = services::Store::new(&conf, false).await;
-    let store = std::sync::Arc::new(store);
-
-    let number_of_streams = store.config.drainer_num_partitions;
+    // Each tenant has its own database schema and Redis key prefix, and hence its own streams
+    let stores = if conf.multitenancy.enabled {
+        let mut stores = Vec::with_capacity(conf.multitenancy.tenants.len());
+        for tenant in conf.multitenancy.tenants.values() {
+            stores.push(std::sync::Arc::new(store.for_tenant(&conf, tenant).await));
+        }
+        stores
+    } else {
+        vec![std::sync::Arc::new(store)]
+    };
+
+    let number_of_streams = conf.drainer.num_partitions;
     let max_read_count = conf.drainer.max_read_count;
+    let batch_size = conf.drainer.batch_size;
     let shutdown_intervals = conf.drainer.shutdown_interval;
     let loop_interval = conf.drainer.loop_interval;
 
@@ -30,9 +40,10 @@ async fn main() -> DrainerResult<()> {
     logger::info!("Drainer started [{:?}] [{:?}]", conf.drainer, conf.log);
 
     start_drainer(
-        store.clone(),
+        stores,
         number_of_streams,
         max_read_count,
+        batch_size,
         shutdown_intervals,
         loop_interval,
     )
@@ -0,0 +1,660 @@
+use std::collections::HashMap;
+
+use async_bb8_diesel::AsyncSimpleConnection;
+use diesel_models::{
+    address::AddressNew, errors::DatabaseError, kv, payment_attempt::PaymentAttemptNew,
+    payment_intent::PaymentIntentNew, refund::RefundNew, reverse_lookup::ReverseLookupNew,
+    PgPooledConn, StorageResult,
+};
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{logger, metrics};
+
+const INSERT_OP: &str = "insert";
+const UPDATE_OP: &str = "update";
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Table {
+    PaymentIntent,
+    PaymentAttempt,
+    Refund,
+    ConnectorResponse,
+    Address,
+    ReverseLookup,
+}
+
+impl Table {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::PaymentIntent => "payment_intent",
+            Self::PaymentAttempt => "payment_attempt",
+            Self::Refund => "refund",
+            Self::ConnectorResponse => "connector_response",
+            Self::Address => "address",
+            Self::ReverseLookup => "reverse_lookup",
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+pub(crate) enum Operation {
+    Insert,
+    Update,
+}
+
+impl Operation {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Self::Insert => INSERT_OP,
+            Self::Update => UPDATE_OP,
+        }
+    }
+}
+
+/// Operations on the same table and of the same kind, which are applied to the database
+/// together in a single transaction.
+#[derive(Debug)]
+pub(crate) struct Batch {
+    pub table: Table,
+    pub operation: Operation,
+    pub entries: Vec<kv::DBOperation>,
+}
+
+/// Returns the table and kind of the operation, along with the keys of the rows it affects.
+fn describe_operation(db_op: &kv::DBOperation) -> Option<(Table, Operation, Vec<String>)> {
+    let payment_attempt_key =
+        |merchant_id: &str, attempt_id: &str| format!("payment_attempt_{merchant_id}_{attempt_id}");
+
+    match db_op {
+        kv::DBOperation::Insert { insertable } => {
+            let (table, keys) = match insertable {
+                kv::Insertable::PaymentIntent(a) => (
+                    Table::PaymentIntent,
+                    vec![format!("payment_intent_{}_{}", a.merchant_id, a.payment_id)],
+                ),
+                kv::Insertable::PaymentAttempt(a) => (
+                    Table::PaymentAttempt,
+                    vec![payment_attempt_key(&a.merchant_id, &a.attempt_id)],
+                ),
+                kv::Insertable::Refund(a) => (
+                    Table::Refund,
+                    vec![format!("refund_{}_{}", a.merchant_id, a.refund_id)],
+                ),
+                // Inserting a connector response also updates the corresponding payment attempt
+                kv::Insertable::ConnectorResponse(a) => (
+                    Table::ConnectorResponse,
+                    vec![
+                        format!(
+                            "connector_response_{}_{}_{}",
+                            a.merchant_id, a.payment_id, a.attempt_id
+                        ),
+                        payment_attempt_key(&a.merchant_id, &a.attempt_id),
+                    ],
+                ),
+                kv::Insertable::Address(a) => {
+                    (Table::Address, vec![format!("address_{}", a.address_id)])
+                }
+                kv::Insertable::ReverseLookUp(a) => (
+                    Table::ReverseLookup,
+                    vec![format!("reverse_lookup_{}", a.lookup_id)],
+                ),
+            };
+            Some((table, Operation::Insert, keys))
+        }
+        kv::DBOperation::Update { updatable } => {
+            let (table, keys) = match updatable {
+                kv::Updateable::PaymentIntentUpdate(a) => (
+                    Table::PaymentIntent,
+                    vec![format!(
+                        "payment_intent_{}_{}",
+                        a.orig.merchant_id, a.orig.payment_id
+                    )],
+                ),
+                kv::Updateable::PaymentAttemptUpdate(a) => (
+                    Table::PaymentAttempt,
+                    vec![payment_attempt_key(&a.orig.merchant_id, &a.orig.attempt_id)],
+                ),
+                kv::Updateable::RefundUpdate(a) => (
+                    Table::Refund,
+                    vec![format!(
+                        "refund_{}_{}",
+                        a.orig.merchant_id, a.orig.refund_id
+                    )],
+                ),
+                // Updating a connector response also updates the corresponding payment attempt
+                kv::Updateable::ConnectorResponseUpdate(a) => (
+                    Table::ConnectorResponse,
+                    vec![
+                        format!(
+                            "connector_response_{}_{}_{}",
+                            a.orig.merchant_id, a.orig.payment_id, a.orig.attempt_id
+                        ),
+                        payment_attempt_key(&a.orig.merchant_id, &a.orig.attempt_id),
+                    ],
+                ),
+                kv::Updateable::AddressUpdate(a) => (
+                    Table::Address,
+                    vec![format!("address_{}", a.orig.address_id)],
+                ),
+            };
+            Some((table, Operation::Update, keys))
+        }
+        kv::DBOperation::Delete => None,
+    }
+}
+
+/// Groups the operations read from a stream into batches of at most `batch_size` operations on
+/// the same table and of the same kind.
+///
+/// Operations are first assigned to stages, such that an operation is placed in a later stage
+/// than every preceding operation affecting the same row. Batches are formed within a stage and
+/// returned in stage order, which preserves the order of operations on any given row.
+pub(crate) fn group_into_batches(
+    operations: Vec<kv::DBOperation>,
+    batch_size: usize,
+) -> Vec<Batch> {
+    let batch_size = batch_size.max(1);
+    let mut last_stage_for_key: HashMap<String, usize> = HashMap::new();
+    let mut stages: Vec<Vec<Batch>> = Vec::new();
+
+    for db_op in operations {
+        let Some((table, operation, keys)) = describe_operation(&db_op) else {
+            // [#224]: Implement this
+            logger::error!("Not implemented!");
+            continue;
+        };
+
+        let stage = keys
+            .iter()
+            .filter_map(|key| last_stage_for_key.get(key))
+            .max()
+            .map_or(0, |stage| stage + 1);
+        for key in keys {
+            last_stage_for_key.insert(key, stage);
+        }
+
+        if stages.len() <= stage {
+            stages.resize_with(stage + 1, Vec::new);
+        }
+        let batches = &mut stages[stage];
+
+        match batches.iter_mut().rev().find(|batch| {
+            batch.table == table && batch.operation == operation && batch.entries.len() < batch_size
+        }) {
+            Some(batch) => batch.entries.push(db_op),
+            None => batches.push(Batch {
+                table,
+                operation,
+                entries: vec![db_op],
+            }),
+        }
+    }
+
+    stages.into_iter().flatten().collect()
+}
+
+/// Counts the operations read from a stream by the merchant whose data they affect.
+pub(crate) fn count_operations_by_merchant(operations: &[kv::DBOperation]) -> HashMap<String, i64> {
+    let mut counts = HashMap::new();
+    for merchant_id in operations.iter().filter_map(kv::DBOperation::merchant_id) {
+        *counts.entry(merchant_id.to_string()).or_insert(0) += 1;
+    }
+    counts
+}
+
+/// Applies the batch to the database within a single transaction, falling back to executing the
+/// operations one at a time if the batch could not be applied.
+pub(crate) async fn execute_batch(conn: &PgPooledConn, batch: Batch) {
+    let table = batch.table.as_str();
+    let operation = batch.operation.as_str();
+
+    if batch.entries.len() > 1 {
+        let entries = batch.entries.clone();
+        let (result, execution_time) = common_utils::date_time::time_it(|| async {
+            execute_in_transaction(conn, batch.table, batch.operation, entries).await
+        })
+        .await;
+
+        match result {
+            Ok(()) => {
+                logger::info!(operation = %operation, table = %table, batch_size = batch.entries.len());
+                metrics::SUCCESSFUL_QUERY_EXECUTION.add(
+                    &metrics::CONTEXT,
+                    u64::try_from(batch.entries.len()).unwrap_or(u64::MIN),
+                    &[metrics::KeyValue {
+                        key: "operation".into(),
+                        value: table.into(),
+                    }],
+                );
+                metrics::BATCH_EXECUTION_TIME.record(
+                    &metrics::CONTEXT,
+                    execution_time,
+                    &[metrics::KeyValue {
+                        key: "operation".into(),
+                        value: operation.into(),
+                    }],
+                );
+                return;
+            }
+            Err(error) => {
+                logger::error!(
+                    operation = %operation,
+                    table = %table,
+                    ?error,
+                    "Failed to execute batch, falling back to executing operations individually"
+                );
+                metrics::BATCH_EXECUTION_FAILURES.add(
+                    &metrics::CONTEXT,
+                    1,
+                    &[metrics::KeyValue {
+                        key: "operation".into(),
+                        value: table.into(),
+                    }],
+                );
+            }
+        }
+    }
+
+    for db_op in batch.entries {
+        execute_operation(conn, db_op).await;
+    }
+}
+
+async fn execute_in_transaction(
+    conn: &PgPooledConn,
+    table: Table,
+    operation: Operation,
+    entries: Vec<kv::DBOperation>,
+) -> StorageResult<()> {
+    conn.batch_execute_async("BEGIN")
+        .await
+        .into_report()
+        .change_context(DatabaseError::Others)
+        .attach_printable("Failed to begin transaction")?;
+
+    let result = match operation {
+        Operation::Insert => execute_batch_insert(conn, table, entries).await,
+        Operation::Update => execute_batch_update(conn, entries).await,
+    };
+
+    let statement = if result.is_ok() { "COMMIT" } else { "ROLLBACK" };
+    conn.batch_execute_async(statement)
+        .await
+        .into_report()
+        .change_context(DatabaseError::Others)
+        .attach_printable_lazy(|| format!("Failed to execute {statement}"))?;
+
+    result
+}
+
+macro_rules! collect_insertables {
+    ($entries:expr, $variant:path) => {
+        $entries
+            .into_iter()
+            .filter_map(|db_op| match db_op {
+                kv::DBOperation::Insert {
+                    insertable: $variant(insertable),
+                } => Some(insertable),
+                _ => None,
+            })
+            .collect::<Vec<_>>()
+    };
+}
+
+async fn execute_batch_insert(
+    conn: &PgPooledConn,
+    table: Table,
+    entries: Vec<kv::DBOperation>,
+) -> StorageResult<()> {
+    match table {
+        Table::PaymentIntent => {
+            PaymentIntentNew::batch_insert(
+                collect_insertables!(entries, kv::Insertable::PaymentIntent),
+                conn,
+            )
+            .await
+        }
+        Table::PaymentAttempt => {
+            PaymentAttemptNew::batch_insert(
+                collect_insertables!(entries, kv::Insertable::PaymentAttempt),
+                conn,
+            )
+            .await
+        }
+        Table::Refund => {
+            RefundNew::batch_insert(collect_insertables!(entries, kv::Insertable::Refund), conn)
+                .await
+        }
+        Table::Address => {
+            let addresses = collect_insertables!(entries, kv::Insertable::Address)
+                .into_iter()
+                .map(|address| *address)
+                .collect();
+            AddressNew::batch_insert(addresses, conn).await
+        }
+        Table::ReverseLookup => {
+            ReverseLookupNew::batch_insert(
+                collect_insertables!(entries, kv::Insertable::ReverseLookUp),
+                conn,
+            )
+            .await
+        }
+        // Connector response inserts update the payment attempt as well, and cannot be expressed
+        // as a single multi-row insert
+        Table::ConnectorResponse => {
+            for connector_response in
+                collect_insertables!(entries, kv::Insertable::ConnectorResponse)
+            {
+                connector_response.insert(conn).await?;
+            }
+            Ok(())
+        }
+    }
+}
+
+async fn execute_batch_update(
+    conn: &PgPooledConn,
+    entries: Vec<kv::DBOperation>,
+) -> StorageResult<()> {
+    for db_op in entries {
+        let kv::DBOperation::Update { updatable } = db_op else {
+            continue;
+        };
+
+        match updatable {
+            kv::Updateable::PaymentIntentUpdate(a) => {
+                a.orig.update(conn, a.update_data).await?;
+            }
+            kv::Updateable::PaymentAttemptUpdate(a) => {
+                a.orig.update_with_attempt_id(conn, a.update_data).await?;
+            }
+            kv::Updateable::RefundUpdate(a) => {
+                a.orig.update(conn, a.update_data).await?;
+            }
+            kv::Updateable::ConnectorResponseUpdate(a) => {
+                a.orig.update(conn, a.update_data).await?;
+            }
+            kv::Updateable::AddressUpdate(a) => {
+                a.orig.update(conn, a.update_data).await?;
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Executes a single operation, logging the outcome.
+pub(crate) async fn execute_operation(conn: &PgPooledConn, db_op: kv::DBOperation) {
+    let insert_op = INSERT_OP;
+    let update_op = UPDATE_OP;
+    let payment_intent = Table::PaymentIntent.as_str();
+    let payment_attempt = Table::PaymentAttempt.as_str();
+    let refund = Table::Refund.as_str();
+    let reverse_lookup = Table::ReverseLookup.as_str();
+    let connector_response = Table::ConnectorResponse.as_str();
+    let address = Table::Address.as_str();
+    match db_op {
+        // TODO: Handle errors
+        kv::DBOperation::Insert { insertable } => {
+            let (_, execution_time) = common_utils::date_time::time_it(|| async {
+                match insertable {
+                    kv::Insertable::PaymentIntent(a) => {
+                        macro_util::handle_resp!(a.insert(conn).await, insert_op, payment_intent)
+                    }
+                    kv::Insertable::PaymentAttempt(a) => {
+                        macro_util::handle_resp!(a.insert(conn).await, insert_op, payment_attempt)
+                    }
+                    kv::Insertable::Refund(a) => {
+                        macro_util::handle_resp!(a.insert(conn).await, insert_op, refund)
+                    }
+                    kv::Insertable::ConnectorResponse(a) => {
+                        macro_util::handle_resp!(
+                            a.insert(conn).await,
+                            insert_op,
+                            connector_response
+                        )
+                    }
+                    kv::Insertable::Address(addr) => {
+                        macro_util::handle_resp!(addr.insert(conn).await, insert_op, address)
+                    }
+                    kv::Insertable::ReverseLookUp(rev) => {
+                        macro_util::handle_resp!(rev.insert(conn).await, insert_op, reverse_lookup)
+                    }
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: insert_op.into(),
+                }],
+            );
+        }
+        kv::DBOperation::Update { updatable } => {
+            let (_, execution_time) = common_utils::date_time::time_it(|| async {
+                match updatable {
+                    kv::Updateable::PaymentIntentUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(conn, a.update_data).await,
+                            update_op,
+                            payment_intent
+                        )
+                    }
+                    kv::Updateable::PaymentAttemptUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update_with_attempt_id(conn, a.update_data).await,
+                            update_op,
+                            payment_attempt
+                        )
+                    }
+                    kv::Updateable::RefundUpdate(a) => {
+                        macro_util::handle_resp!(
+                            a.orig.update(conn, a.update_data).await,
+                            update_op,
+                            refund
+                        )
+                    }
+                    kv::Updateable::ConnectorResponseUpdate(a) => macro_util::handle_resp!(
+                        a.orig.update(conn, a.update_data).await,
+                        update_op,
+                        connector_response
+                    ),
+                    kv::Updateable::AddressUpdate(a) => macro_util::handle_resp!(
+                        a.orig.update(conn, a.update_data).await,
+                        update_op,
+                        address
+                    ),
+                }
+            })
+            .await;
+            metrics::QUERY_EXECUTION_TIME.record(
+                &metrics::CONTEXT,
+                execution_time,
+                &[metrics::KeyValue {
+                    key: "operation".into(),
+                    value: update_op.into(),
+                }],
+            );
+        }
+        kv::DBOperation::Delete => {
+            // [#224]: Implement this
+            logger::error!("Not implemented!");
+        }
+    };
+}
+
+mod macro_util {
+
+    macro_rules! handle_resp {
+        ($result:expr,$op_type:expr, $table:expr) => {
+            match $result {
+                Ok(inner_result) => {
+                    logger::info!(operation = %$op_type, table = %$table, ?inner_result);
+                    metrics::SUCCESSFUL_QUERY_EXECUTION.add(&metrics::CONTEXT, 1, &[
+                        metrics::KeyValue {
+                            key: "operation".into(),
+                            value: $table.into(),
+                        }
+                    ]);
+                }
+                Err(err) => {
+                    logger::error!(operation = %$op_type, table = %$table, ?err);
+                    metrics::ERRORS_WHILE_QUERY_EXECUTION.add(&metrics::CONTEXT, 1, &[
+                        metrics::KeyValue {
+                            key: "operation".into(),
+                            value: $table.into(),
+                        }
+                    ]);
+                }
+            }
+        };
+    }
+    pub(crate) use handle_resp;
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use diesel_models::{
+        connector_response::ConnectorResponseNew, kv, payment_attempt::PaymentAttemptNew,
+        payment_intent::PaymentIntentNew, reverse_lookup::ReverseLookupNew,
+    };
+
+    use super::{count_operations_by_merchant, group_into_batches, Operation, Table};
+
+    fn payment_intent_insert(payment_id: &str) -> kv::DBOperation {
+        kv::DBOperation::Insert {
+            insertable: kv::Insertable::PaymentIntent(PaymentIntentNew {
+                payment_id: payment_id.to_string(),
+                merchant_id: "merchant".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn payment_attempt_insert(attempt_id: &str) -> kv::DBOperation {
+        kv::DBOperation::Insert {
+            insertable: kv::Insertable::PaymentAttempt(PaymentAttemptNew {
+                attempt_id: attempt_id.to_string(),
+                merchant_id: "merchant".to_string(),
+                ..Default::default()
+            }),
+        }
+    }
+
+    fn connector_response_insert(attempt_id: &str) -> kv::DBOperation {
+        kv::DBOperation::Insert {
+            insertable: kv::Insertable::ConnectorResponse(ConnectorResponseNew {
+                payment_id: String::new(),
+                merchant_id: "merchant".to_string(),
+                attempt_id: attempt_id.to_string(),
+                created_at: common_utils::date_time::now(),
+                modified_at: common_utils::date_time::now(),
+                connector_name: None,
+                connector_transaction_id: None,
+                authentication_data: None,
+                encoded_data: None,
+                updated_by: String::new(),
+            }),
+        }
+    }
+
+    fn reverse_lookup_insert(lookup_id: &str) -> kv::DBOperation {
+        kv::DBOperation::Insert {
+            insertable: kv::Insertable::ReverseLookUp(ReverseLookupNew {
+                lookup_id: lookup_id.to_string(),
+                pk_id: String::new(),
+                sk_id: String::new(),
+                source: String::new(),
+                updated_by: String::new(),
+            }),
+        }
+    }
+
+    fn summary(batches: &[super::Batch]) -> Vec<(Table, Operation, usize)> {
+        batches
+            .iter()
+            .map(|batch| (batch.table, batch.operation, batch.entries.len()))
+            .collect()
+    }
+
+    #[test]
+    fn groups_operations_by_table_and_operation() {
+        let operations = vec![
+            payment_intent_insert("pay_1"),
+            payment_attempt_insert("pay_1_1"),
+            reverse_lookup_insert("lookup_1"),
+            payment_intent_insert("pay_2"),
+            payment_attempt_insert("pay_2_1"),
+            reverse_lookup_insert("lookup_2"),
+        ];
+
+        let batches = group_into_batches(operations, 10);
+
+        assert_eq!(
+            summary(&batches),
+            vec![
+                (Table::PaymentIntent, Operation::Insert, 2),
+                (Table::PaymentAttempt, Operation::Insert, 2),
+                (Table::ReverseLookup, Operation::Insert, 2),
+            ]
+        );
+    }
+
+    #[test]
+    fn respects_batch_size() {
+        let operations = (0..5)
+            .map(|index| payment_intent_insert(&format!("pay_{index}")))
+            .collect();
+
+        let batches = group_into_batches(operations, 2);
+
+        assert_eq!(
+            summary(&batches),
+            vec![
+                (Table::PaymentIntent, Operation::Insert, 2),
+                (Table::PaymentIntent, Operation::Insert, 2),
+                (Table::PaymentIntent, Operation::Insert, 1),
+            ]
+        );
+    }
+
+    #[test]
+    fn counts_operations_by_merchant() {
+        let operations = vec![
+            payment_intent_insert("pay_1"),
+            payment_attempt_insert("pay_1_1"),
+            reverse_lookup_insert("lookup_1"),
+        ];
+
+        let counts = count_operations_by_merchant(&operations);
+
+        assert_eq!(counts.len(), 1);
+        assert_eq!(counts.get("merchant"), Some(&2));
+    }
+
+    #[test]
+    fn preserves_ordering_of_operations_on_the_same_row() {
+        let operations = vec![
+            payment_attempt_insert("pay_1_1"),
+            connector_response_insert("pay_1_1"),
+            payment_attempt_insert("pay_2_1"),
+            connector_response_insert("pay_2_1"),
+            connector_response_insert("pay_3_1"),
+        ];
+
+        let batches = group_into_batches(operations, 10);
+
+        assert_eq!(
+            summary(&batches),
+            vec![
+                (Table::PaymentAttempt, Operation::Insert, 2),
+                (Table::ConnectorResponse, Operation::Insert, 1),
+                (Table::ConnectorResponse, Operation::Insert, 2),
+            ]
+        );
+    }
+}
@@ -20,9 +20,12 @@ impl Store {
         Self {
             master_pool: diesel_make_pg_pool(
                 &config.master_database,
+                None,
                 test_transaction,
-                #[cfg(feature = "kms")]
-                external_services::kms::get_kms_client(&config.kms).await,
+                external_services::secrets_management::get_secrets_manager(
+                    &config.secrets_management,
+                )
+                .await,
             )
             .await,
             redis_conn: Arc::new(crate::connection::redis_connection(config).await),
@@ -33,6 +36,34 @@ impl Store {
         }
     }
 
+    /// Creates a store for the given tenant, which writes to the tenant's schema and reads the
+    /// streams under the tenant's Redis key prefix over the same Redis connection.
+    pub async fn for_tenant(
+        &self,
+        config: &crate::settings::Settings,
+        tenant: &crate::settings::Tenant,
+    ) -> Self {
+        let mut database = config.master_database.clone();
+        if let Some(dbname) = tenant.dbname.as_ref() {
+            database.dbname = dbname.clone();
+        }
+
+        Self {
+            master_pool: diesel_make_pg_pool(
+                &database,
+                Some(tenant.schema.as_str()),
+                false,
+                external_services::secrets_management::get_secrets_manager(
+                    &config.secrets_management,
+                )
+                .await,
+            )
+            .await,
+            redis_conn: Arc::new(self.redis_conn.with_key_prefix(&tenant.redis_key_prefix)),
+            config: self.config.clone(),
+        }
+    }
+
     pub fn drainer_stream(&self, shard_key: &str) -> String {
         // Example: {shard_5}_drainer_stream
         format!("{{{}}}_{}", shard_key, self.config.drainer_stream_name,)
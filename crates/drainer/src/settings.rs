@@ -1,9 +1,8 @@
-use std::path::PathBuf;
+use std::{collections::HashMap, path::PathBuf};
 
 use common_utils::ext_traits::ConfigExt;
 use config::{Environment, File};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::{SecretValue, SecretsManagementConfig};
 use redis_interface as redis;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use router_env::{env, logger};
@@ -11,10 +10,7 @@ use serde::Deserialize;
 
 use crate::errors;
 
-#[cfg(feature = "kms")]
-pub type Password = kms::KmsValue;
-#[cfg(not(feature = "kms"))]
-pub type Password = masking::Secret<String>;
+pub type Password = SecretValue;
 
 #[derive(clap::Parser, Default)]
 #[cfg_attr(feature = "vergen", command(version = router_env::version!()))]
@@ -32,8 +28,8 @@ pub struct Settings {
     pub redis: redis::RedisSettings,
     pub log: Log,
     pub drainer: DrainerSettings,
-    #[cfg(feature = "kms")]
-    pub kms: kms::KmsConfig,
+    pub multitenancy: Multitenancy,
+    pub secrets_management: SecretsManagementConfig,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -54,10 +50,27 @@ pub struct DrainerSettings {
     pub stream_name: String,
     pub num_partitions: u8,
     pub max_read_count: u64,
+    pub batch_size: usize,
     pub shutdown_interval: u32, // in milliseconds
     pub loop_interval: u32,     // in milliseconds
 }
 
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Multitenancy {
+    pub enabled: bool,
+    pub tenants: HashMap<String, Tenant>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Tenant {
+    pub schema: String,
+    /// Overrides the name of the master database for this tenant
+    pub dbname: Option<String>,
+    pub redis_key_prefix: String,
+}
+
 impl Default for Database {
     fn default() -> Self {
         Self {
@@ -78,6 +91,7 @@ impl Default for DrainerSettings {
             stream_name: "DRAINER_STREAM".into(),
             num_partitions: 64,
             max_read_count: 100,
+            batch_size: 20,
             shutdown_interval: 1000, // in milliseconds
             loop_interval: 500,      // in milliseconds
         }
@@ -116,10 +130,48 @@ impl Database {
 
 impl DrainerSettings {
     fn validate(&self) -> Result<(), errors::DrainerError> {
-        common_utils::fp_utils::when(self.stream_name.is_default_or_empty(), || {
+        use common_utils::fp_utils::when;
+
+        when(self.stream_name.is_default_or_empty(), || {
             Err(errors::DrainerError::ConfigParsingError(
                 "drainer stream name must not be empty".into(),
             ))
+        })?;
+
+        when(self.batch_size == 0, || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "drainer batch size must be greater than zero".into(),
+            ))
+        })
+    }
+}
+
+impl Multitenancy {
+    fn validate(&self) -> Result<(), errors::DrainerError> {
+        use common_utils::fp_utils::when;
+
+        if !self.enabled {
+            return Ok(());
+        }
+
+        when(self.tenants.is_empty(), || {
+            Err(errors::DrainerError::ConfigParsingError(
+                "at least one tenant must be configured when multitenancy is enabled".into(),
+            ))
+        })?;
+
+        self.tenants.iter().try_for_each(|(tenant_id, tenant)| {
+            when(tenant.schema.is_default_or_empty(), || {
+                Err(errors::DrainerError::ConfigParsingError(format!(
+                    "schema of tenant '{tenant_id}' must not be empty"
+                )))
+            })?;
+
+            when(tenant.redis_key_prefix.is_default_or_empty(), || {
+                Err(errors::DrainerError::ConfigParsingError(format!(
+                    "redis key prefix of tenant '{tenant_id}' must not be empty"
+                )))
+            })
         })
     }
 }
@@ -171,6 +223,10 @@ impl Settings {
             errors::DrainerError::ConfigParsingError("invalid Redis configuration".into())
         })?;
         self.drainer.validate()?;
+        self.multitenancy.validate()?;
+        self.secrets_management
+            .validate()
+            .map_err(|error| errors::DrainerError::ConfigParsingError(error.into()))?;
 
         Ok(())
     }
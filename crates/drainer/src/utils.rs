@@ -1,5 +1,6 @@
 use std::{collections::HashMap, sync::Arc};
 
+use diesel_models::kv;
 use error_stack::IntoReport;
 use redis_interface as redis;
 
@@ -91,6 +92,22 @@ pub async fn trim_from_stream(
     Ok(trim_result? + 1)
 }
 
+/// Decrements the number of entries of each merchant pending in the drainer streams by the
+/// number of entries drained.
+pub async fn decrement_pending_entries(
+    redis: &redis::RedisConnectionPool,
+    drained_entries_by_merchant: HashMap<String, i64>,
+) {
+    for (merchant_id, drained_entries) in drained_entries_by_merchant {
+        if let Err(error) = redis
+            .increment_key_by(&kv::get_pending_entries_key(&merchant_id), -drained_entries)
+            .await
+        {
+            logger::error!(?error, %merchant_id, "Failed to decrement the pending drainer entries");
+        }
+    }
+}
+
 pub async fn make_stream_available(
     stream_name_flag: &str,
     redis: &redis::RedisConnectionPool,
@@ -1,4 +1,4 @@
-//! Interactions with the AWS SES SDK
+//! Sending emails through the email client selected in the configuration.
 
 use aws_config::meta::region::RegionProviderChain;
 use aws_sdk_sesv2::{
@@ -7,31 +7,103 @@ use aws_sdk_sesv2::{
     types::{Body, Content, Destination, EmailContent, Message},
     Client,
 };
-use common_utils::{errors::CustomResult, pii};
+use base64::Engine;
+use common_utils::{errors::CustomResult, ext_traits::ConfigExt, fp_utils::when, pii};
 use error_stack::{IntoReport, ResultExt};
 use masking::PeekInterface;
 use serde::Deserialize;
 
+use crate::consts;
+
+/// Capturing emails as files in a directory of the local file system.
+pub mod file_capture;
+
+/// Sending emails through an SMTP server.
+pub mod smtp;
+
 /// Custom Result type alias for Email operations.
 pub type EmailResult<T> = CustomResult<T, EmailError>;
 
 /// A trait that defines the methods that must be implemented to send email.
 #[async_trait::async_trait]
 pub trait EmailClient: Sync + Send + dyn_clone::DynClone {
-    /// Sends an email to the specified recipient with the given subject and body.
-    async fn send_email(
-        &self,
-        recipient: pii::Email,
-        subject: String,
-        body: String,
-    ) -> EmailResult<()>;
+    /// Sends the message to the specified recipient.
+    async fn send_email(&self, recipient: pii::Email, message: EmailMessage) -> EmailResult<()>;
 }
 
 dyn_clone::clone_trait_object!(EmailClient);
 
+/// The contents of an email to be sent.
+#[derive(Clone, Debug, Default)]
+pub struct EmailMessage {
+    /// The subject of the email.
+    pub subject: String,
+
+    /// The plain text body of the email, shown by clients that do not render HTML.
+    pub text_body: String,
+
+    /// The HTML body of the email, if any.
+    pub html_body: Option<String>,
+
+    /// The name shown as the sender of the email, along with the configured sender email.
+    pub sender_name: Option<String>,
+}
+
+impl EmailMessage {
+    /// Constructs a plain text message with the given subject and body.
+    pub fn plain_text(subject: String, body: String) -> Self {
+        Self {
+            subject,
+            text_body: body,
+            html_body: None,
+            sender_name: None,
+        }
+    }
+
+    /// The `From` address of the message, including the sender name if one was provided.
+    /// Non-ASCII sender names are encoded as a MIME encoded-word.
+    pub fn get_sender_address(&self, from_email: &str) -> String {
+        let sender_name = self
+            .sender_name
+            .as_deref()
+            .map(|name| {
+                name.chars()
+                    .filter(|character| !character.is_control() && !matches!(character, '"' | '\\'))
+                    .collect::<String>()
+            })
+            .filter(|name| !name.trim().is_empty());
+
+        match sender_name {
+            Some(name) if name.is_ascii() => format!("\"{}\" <{from_email}>", name.trim()),
+            Some(name) => format!(
+                "=?utf-8?B?{}?= <{from_email}>",
+                consts::BASE64_ENGINE.encode(name.trim())
+            ),
+            None => from_email.to_owned(),
+        }
+    }
+}
+
+/// The clients that emails can be sent through.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EmailClientBackend {
+    /// Emails are sent through AWS SES.
+    #[default]
+    AwsSes,
+    /// Emails are sent through an SMTP server.
+    Smtp,
+    /// Emails are written to files in a directory of the local file system, for testing.
+    FileCapture,
+}
+
 /// Struct that contains the settings required to construct an EmailClient.
 #[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
 pub struct EmailSettings {
+    /// The client that emails are sent through.
+    pub backend: EmailClientBackend,
+
     /// Sender email.
     pub from_email: String,
 
@@ -40,6 +112,41 @@ pub struct EmailSettings {
 
     /// Base-url used when adding links that should redirect to self
     pub base_url: String,
+
+    /// Configuration of the SMTP server, used when the backend is `smtp`.
+    pub smtp: smtp::SmtpConfig,
+
+    /// Configuration of the file capture client, used when the backend is `file_capture`.
+    pub file_capture: file_capture::FileCaptureConfig,
+}
+
+impl EmailSettings {
+    /// Verifies that the configuration of the selected client is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        when(self.from_email.is_default_or_empty(), || {
+            Err("Sender email must not be empty")
+        })?;
+
+        match self.backend {
+            EmailClientBackend::AwsSes => Ok(()),
+            EmailClientBackend::Smtp => self.smtp.validate(),
+            EmailClientBackend::FileCapture => self.file_capture.validate(),
+        }
+    }
+
+    /// Constructs the selected email client.
+    pub async fn get_email_client(&self) -> EmailResult<Box<dyn EmailClient>> {
+        Ok(match self.backend {
+            EmailClientBackend::AwsSes => Box::new(AwsSes::new(self).await),
+            EmailClientBackend::Smtp => {
+                Box::new(smtp::SmtpClient::new(&self.smtp, &self.from_email)?)
+            }
+            EmailClientBackend::FileCapture => Box::new(file_capture::FileCaptureClient::new(
+                &self.file_capture,
+                &self.from_email,
+            )),
+        })
+    }
 }
 
 /// Client for AWS SES operation
@@ -64,15 +171,24 @@ impl AwsSes {
 
 #[async_trait::async_trait]
 impl EmailClient for AwsSes {
-    async fn send_email(
-        &self,
-        recipient: pii::Email,
-        subject: String,
-        body: String,
-    ) -> EmailResult<()> {
+    async fn send_email(&self, recipient: pii::Email, message: EmailMessage) -> EmailResult<()> {
+        let from_email_address = message.get_sender_address(&self.from_email);
+        let body = Body::builder().text(
+            Content::builder()
+                .data(message.text_body)
+                .charset("UTF-8")
+                .build(),
+        );
+        let body = match message.html_body {
+            Some(html_body) => {
+                body.html(Content::builder().data(html_body).charset("UTF-8").build())
+            }
+            None => body,
+        };
+
         self.ses_client
             .send_email()
-            .from_email_address(self.from_email.to_owned())
+            .from_email_address(from_email_address)
             .destination(
                 Destination::builder()
                     .to_addresses(recipient.peek())
@@ -82,12 +198,13 @@ impl EmailClient for AwsSes {
                 EmailContent::builder()
                     .simple(
                         Message::builder()
-                            .subject(Content::builder().data(subject).build())
-                            .body(
-                                Body::builder()
-                                    .text(Content::builder().data(body).charset("UTF-8").build())
+                            .subject(
+                                Content::builder()
+                                    .data(message.subject)
+                                    .charset("UTF-8")
                                     .build(),
                             )
+                            .body(body.build())
                             .build(),
                     )
                     .build(),
@@ -125,3 +242,29 @@ pub enum AwsSesError {
     #[error("Failed to Send Email {0:?}")]
     SendingFailure(aws_smithy_client::SdkError<SendEmailError>),
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sender_address() {
+        let mut message = EmailMessage::plain_text("Subject".to_string(), "Body".to_string());
+        assert_eq!(
+            message.get_sender_address("notify@example.com"),
+            "notify@example.com"
+        );
+
+        message.sender_name = Some("Ekart \"Retail\"\r\n".to_string());
+        assert_eq!(
+            message.get_sender_address("notify@example.com"),
+            "\"Ekart Retail\" <notify@example.com>"
+        );
+
+        message.sender_name = Some("Café".to_string());
+        assert_eq!(
+            message.get_sender_address("notify@example.com"),
+            "=?utf-8?B?Q2Fmw6k=?= <notify@example.com>"
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use std::path::PathBuf;
+
+use common_utils::{fp_utils::when, pii};
+use error_stack::{IntoReport, ResultExt};
+use masking::PeekInterface;
+
+use super::{EmailClient, EmailError, EmailMessage, EmailResult};
+
+/// Configuration parameters required for constructing a [`FileCaptureClient`].
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct FileCaptureConfig {
+    /// The directory that captured emails are written to.
+    pub directory: PathBuf,
+}
+
+impl Default for FileCaptureConfig {
+    fn default() -> Self {
+        Self {
+            directory: router_env::env::workspace_path().join("emails"),
+        }
+    }
+}
+
+impl FileCaptureConfig {
+    /// Verifies that the [`FileCaptureClient`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        when(self.directory.as_os_str().is_empty(), || {
+            Err("Email file capture directory must not be empty")
+        })
+    }
+}
+
+/// An email captured by the [`FileCaptureClient`].
+#[derive(Debug, serde::Serialize)]
+struct CapturedEmail<'a> {
+    from: String,
+    to: &'a str,
+    subject: &'a str,
+    text_body: &'a str,
+    html_body: Option<&'a str>,
+}
+
+/// Client writing each email to a JSON file in a directory of the local file system instead of
+/// sending it, intended for tests and local development.
+#[derive(Debug, Clone)]
+pub struct FileCaptureClient {
+    directory: PathBuf,
+    from_email: String,
+}
+
+impl FileCaptureClient {
+    /// Constructs a new file capture client.
+    pub fn new(config: &FileCaptureConfig, from_email: &str) -> Self {
+        Self {
+            directory: config.directory.clone(),
+            from_email: from_email.to_owned(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailClient for FileCaptureClient {
+    async fn send_email(&self, recipient: pii::Email, message: EmailMessage) -> EmailResult<()> {
+        let captured_email = serde_json::to_vec_pretty(&CapturedEmail {
+            from: message.get_sender_address(&self.from_email),
+            to: recipient.peek(),
+            subject: &message.subject,
+            text_body: &message.text_body,
+            html_body: message.html_body.as_deref(),
+        })
+        .into_report()
+        .change_context(EmailError::EmailSendingFailure)
+        .attach_printable("Failed to serialize captured email")?;
+
+        tokio::fs::create_dir_all(&self.directory)
+            .await
+            .into_report()
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to create email capture directory")?;
+
+        let file_name = format!(
+            "{}_{}.json",
+            time::OffsetDateTime::now_utc().unix_timestamp_nanos(),
+            common_utils::generate_id(common_utils::consts::ID_LENGTH, "email")
+        );
+        tokio::fs::write(self.directory.join(file_name), captured_email)
+            .await
+            .into_report()
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Failed to write captured email")
+    }
+}
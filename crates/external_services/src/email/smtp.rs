@@ -0,0 +1,169 @@
+use std::time::Duration;
+
+use common_utils::{ext_traits::ConfigExt, fp_utils::when, pii};
+use error_stack::{IntoReport, ResultExt};
+use lettre::{
+    message::{header::ContentType, Mailbox, MultiPart},
+    transport::smtp::authentication::Credentials,
+    Address, AsyncSmtpTransport, AsyncTransport, Message, Tokio1Executor,
+};
+use masking::{PeekInterface, Secret};
+use router_env::logger;
+
+use super::{EmailClient, EmailError, EmailMessage, EmailResult};
+
+/// The security of the connection to the SMTP server.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SmtpConnection {
+    /// The connection is not encrypted, intended for local SMTP servers only.
+    Plaintext,
+    /// The connection is upgraded to TLS using the `STARTTLS` command.
+    #[default]
+    StartTls,
+    /// The connection is encrypted with TLS from the start.
+    Tls,
+}
+
+/// Configuration parameters required for constructing an [`SmtpClient`].
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct SmtpConfig {
+    /// The host name of the SMTP server.
+    pub host: String,
+
+    /// The port of the SMTP server.
+    pub port: u16,
+
+    /// The user name to authenticate with, if the server requires authentication.
+    pub username: Option<String>,
+
+    /// The password to authenticate with, if the server requires authentication.
+    pub password: Option<Secret<String>>,
+
+    /// The security of the connection to the SMTP server.
+    pub connection: SmtpConnection,
+
+    /// The number of seconds after which requests to the SMTP server time out.
+    pub timeout_in_seconds: u64,
+}
+
+impl Default for SmtpConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            port: 587,
+            username: None,
+            password: None,
+            connection: SmtpConnection::default(),
+            timeout_in_seconds: 30,
+        }
+    }
+}
+
+impl SmtpConfig {
+    /// Verifies that the [`SmtpClient`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        when(self.host.is_default_or_empty(), || {
+            Err("SMTP host must not be empty")
+        })?;
+
+        when(self.port == 0, || Err("SMTP port must not be 0"))?;
+
+        when(self.username.is_some() != self.password.is_some(), || {
+            Err("SMTP username and password must either both be set or both be unset")
+        })
+    }
+}
+
+/// Client sending emails through an SMTP server.
+#[derive(Debug, Clone)]
+pub struct SmtpClient {
+    transport: AsyncSmtpTransport<Tokio1Executor>,
+    from_email: Address,
+}
+
+impl SmtpClient {
+    /// Constructs a new SMTP client.
+    pub fn new(config: &SmtpConfig, from_email: &str) -> EmailResult<Self> {
+        let builder = match config.connection {
+            SmtpConnection::Plaintext => {
+                AsyncSmtpTransport::<Tokio1Executor>::builder_dangerous(&config.host)
+            }
+            SmtpConnection::StartTls => {
+                AsyncSmtpTransport::<Tokio1Executor>::starttls_relay(&config.host)
+                    .into_report()
+                    .change_context(EmailError::ClientBuildingFailure)
+                    .attach_printable("Failed to configure STARTTLS for the SMTP server")?
+            }
+            SmtpConnection::Tls => AsyncSmtpTransport::<Tokio1Executor>::relay(&config.host)
+                .into_report()
+                .change_context(EmailError::ClientBuildingFailure)
+                .attach_printable("Failed to configure TLS for the SMTP server")?,
+        }
+        .port(config.port)
+        .timeout(Some(Duration::from_secs(config.timeout_in_seconds)));
+
+        let builder = match (&config.username, &config.password) {
+            (Some(username), Some(password)) => {
+                builder.credentials(Credentials::new(username.clone(), password.peek().clone()))
+            }
+            _ => builder,
+        };
+
+        let from_email = from_email
+            .parse::<Address>()
+            .into_report()
+            .change_context(EmailError::ClientBuildingFailure)
+            .attach_printable("Invalid sender email")?;
+
+        Ok(Self {
+            transport: builder.build(),
+            from_email,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EmailClient for SmtpClient {
+    async fn send_email(&self, recipient: pii::Email, message: EmailMessage) -> EmailResult<()> {
+        let recipient = recipient
+            .peek()
+            .parse::<Address>()
+            .into_report()
+            .change_context(EmailError::EmailSendingFailure)
+            .attach_printable("Invalid recipient email")?;
+
+        let builder = Message::builder()
+            .from(Mailbox::new(
+                message.sender_name.clone(),
+                self.from_email.clone(),
+            ))
+            .to(Mailbox::new(None, recipient))
+            .subject(message.subject);
+        let email = match message.html_body {
+            Some(html_body) => builder.multipart(MultiPart::alternative_plain_html(
+                message.text_body,
+                html_body,
+            )),
+            None => builder
+                .header(ContentType::TEXT_PLAIN)
+                .body(message.text_body),
+        }
+        .into_report()
+        .change_context(EmailError::EmailSendingFailure)
+        .attach_printable("Failed to build email")?;
+
+        self.transport
+            .send(email)
+            .await
+            .map_err(|error| {
+                logger::error!(smtp_error=?error, "Failed to send email through SMTP server");
+                error
+            })
+            .into_report()
+            .change_context(EmailError::EmailSendingFailure)?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,140 @@
+//! Storing the files uploaded to the application in the file storage backend selected in the
+//! configuration.
+
+use std::time::Duration;
+
+use common_utils::errors::CustomResult;
+
+/// Storing files in an S3-compatible object store, such as AWS S3 or MinIO.
+pub mod aws_s3;
+
+/// Storing files in Azure Blob Storage.
+pub mod azure_blob;
+
+/// Storing files in a directory of the local file system.
+pub mod file_system;
+
+/// Storing files in Google Cloud Storage.
+pub mod gcs;
+
+/// An interface for storing, retrieving and deleting files, implemented by each of the supported
+/// file storage backends.
+///
+/// Files are identified by their key, a `/`-separated path relative to the root of the backend.
+#[async_trait::async_trait]
+pub trait FileStorageInterface: Send + Sync + std::fmt::Debug {
+    /// Stores the file under the provided key, replacing any file previously stored under it.
+    async fn upload_file(
+        &self,
+        file_key: &str,
+        file: Vec<u8>,
+        content_type: &str,
+    ) -> CustomResult<(), FileStorageError>;
+
+    /// Deletes the file stored under the provided key.
+    async fn delete_file(&self, file_key: &str) -> CustomResult<(), FileStorageError>;
+
+    /// Retrieves the contents of the file stored under the provided key.
+    async fn retrieve_file(&self, file_key: &str) -> CustomResult<Vec<u8>, FileStorageError>;
+
+    /// Generates a URL that the file stored under the provided key can be downloaded from without
+    /// further authentication, until it expires.
+    async fn get_presigned_url(
+        &self,
+        file_key: &str,
+        expires_in: Duration,
+    ) -> CustomResult<String, FileStorageError>;
+}
+
+/// The backends that files can be stored in.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum FileStorageBackend {
+    /// Files are stored in a directory of the local file system.
+    #[default]
+    FileSystem,
+    /// Files are stored in an S3-compatible object store, such as AWS S3 or MinIO.
+    AwsS3,
+    /// Files are stored in Google Cloud Storage.
+    Gcs,
+    /// Files are stored in Azure Blob Storage.
+    AzureBlob,
+}
+
+/// Configuration parameters for selecting and constructing the file storage backend.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct FileStorageConfig {
+    /// The backend that files are stored in, the local file system if unset.
+    pub backend: Option<FileStorageBackend>,
+
+    /// Configuration of the local file system, used when the backend is `file_system`.
+    pub file_system: file_system::FileSystemConfig,
+
+    /// Configuration of the S3-compatible object store, used when the backend is `aws_s3`.
+    pub aws_s3: aws_s3::AwsS3Config,
+
+    /// Configuration of Google Cloud Storage, used when the backend is `gcs`.
+    pub gcs: gcs::GcsConfig,
+
+    /// Configuration of Azure Blob Storage, used when the backend is `azure_blob`.
+    pub azure_blob: azure_blob::AzureBlobConfig,
+}
+
+impl FileStorageConfig {
+    /// Verifies that the configuration of the selected backend is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match self.backend.unwrap_or_default() {
+            FileStorageBackend::FileSystem => self.file_system.validate(),
+            FileStorageBackend::AwsS3 => self.aws_s3.validate(),
+            FileStorageBackend::Gcs => self.gcs.validate(),
+            FileStorageBackend::AzureBlob => self.azure_blob.validate(),
+        }
+    }
+
+    /// Constructs the client of the selected backend.
+    pub async fn get_file_storage_client(&self) -> Box<dyn FileStorageInterface> {
+        match self.backend.unwrap_or_default() {
+            FileStorageBackend::FileSystem => {
+                Box::new(file_system::FileSystem::new(&self.file_system))
+            }
+            FileStorageBackend::AwsS3 => {
+                Box::new(aws_s3::AwsS3FileStorage::new(&self.aws_s3).await)
+            }
+            FileStorageBackend::Gcs => {
+                Box::new(aws_s3::AwsS3FileStorage::new(&self.gcs.as_s3_config()).await)
+            }
+            FileStorageBackend::AzureBlob => {
+                Box::new(azure_blob::AzureBlobFileStorage::new(&self.azure_blob))
+            }
+        }
+    }
+}
+
+/// Errors that could occur during file storage operations.
+#[derive(Debug, thiserror::Error)]
+pub enum FileStorageError {
+    /// The file key is not a valid key for the backend.
+    #[error("Invalid file key")]
+    InvalidFileKey,
+
+    /// An error occurred when storing the file.
+    #[error("Failed to upload file")]
+    UploadFailed,
+
+    /// An error occurred when deleting the file.
+    #[error("Failed to delete file")]
+    DeleteFailed,
+
+    /// An error occurred when retrieving the file.
+    #[error("Failed to retrieve file")]
+    RetrieveFailed,
+
+    /// The backend does not support generating pre-signed URLs.
+    #[error("Pre-signed URLs are not supported by the file storage backend")]
+    PresignedUrlNotSupported,
+
+    /// An error occurred when generating the pre-signed URL.
+    #[error("Failed to generate pre-signed URL")]
+    PresignedUrlGenerationFailed,
+}
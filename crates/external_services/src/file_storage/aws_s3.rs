@@ -0,0 +1,156 @@
+use std::time::Duration;
+
+use aws_config::meta::region::RegionProviderChain;
+use aws_sdk_s3::{config::Region, presigning::PresigningConfig, Client};
+use common_utils::{errors::CustomResult, ext_traits::ConfigExt, fp_utils::when};
+use error_stack::{IntoReport, ResultExt};
+use router_env::logger;
+
+use super::{FileStorageError, FileStorageInterface};
+
+/// Configuration parameters required for constructing an [`AwsS3FileStorage`] backend.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct AwsS3Config {
+    /// The AWS region to send S3 requests to.
+    pub region: String,
+
+    /// The bucket that files are stored in.
+    pub bucket_name: String,
+
+    /// The endpoint of an S3-compatible object store to send requests to instead of AWS S3, such
+    /// as `http://localhost:9000` for a local MinIO server.
+    pub endpoint_url: Option<String>,
+
+    /// Whether the bucket is addressed in the path of the requests instead of the host, as
+    /// required by most S3-compatible object stores.
+    pub force_path_style: bool,
+}
+
+impl AwsS3Config {
+    /// Verifies that the [`AwsS3FileStorage`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        when(self.region.is_default_or_empty(), || {
+            Err("S3 region must not be empty")
+        })?;
+
+        when(self.bucket_name.is_default_or_empty(), || {
+            Err("S3 bucket name must not be empty")
+        })
+    }
+}
+
+/// A file storage backend storing files in an S3-compatible object store. We assume that the SDK
+/// has the credentials required to interact with the object store (`AWS_ACCESS_KEY_ID` and
+/// `AWS_SECRET_ACCESS_KEY`) either set in environment variables, or that the SDK is running in a
+/// machine that is able to assume an IAM role.
+#[derive(Debug)]
+pub struct AwsS3FileStorage {
+    client: Client,
+    bucket_name: String,
+}
+
+impl AwsS3FileStorage {
+    /// Constructs a new S3 backend.
+    pub async fn new(config: &AwsS3Config) -> Self {
+        let region_provider = RegionProviderChain::first_try(Region::new(config.region.clone()));
+        let sdk_config = aws_config::from_env().region(region_provider).load().await;
+        let mut s3_config = aws_sdk_s3::config::Builder::from(&sdk_config)
+            .force_path_style(config.force_path_style);
+        if let Some(endpoint_url) = &config.endpoint_url {
+            s3_config = s3_config.endpoint_url(endpoint_url);
+        }
+
+        Self {
+            client: Client::from_conf(s3_config.build()),
+            bucket_name: config.bucket_name.clone(),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl FileStorageInterface for AwsS3FileStorage {
+    async fn upload_file(
+        &self,
+        file_key: &str,
+        file: Vec<u8>,
+        content_type: &str,
+    ) -> CustomResult<(), FileStorageError> {
+        self.client
+            .put_object()
+            .bucket(&self.bucket_name)
+            .key(file_key)
+            .content_type(content_type)
+            .body(file.into())
+            .send()
+            .await
+            .map_err(|error| {
+                logger::error!(s3_sdk_error=?error, "Failed to upload file to S3");
+                error
+            })
+            .into_report()
+            .change_context(FileStorageError::UploadFailed)?;
+        Ok(())
+    }
+
+    async fn delete_file(&self, file_key: &str) -> CustomResult<(), FileStorageError> {
+        self.client
+            .delete_object()
+            .bucket(&self.bucket_name)
+            .key(file_key)
+            .send()
+            .await
+            .map_err(|error| {
+                logger::error!(s3_sdk_error=?error, "Failed to delete file from S3");
+                error
+            })
+            .into_report()
+            .change_context(FileStorageError::DeleteFailed)?;
+        Ok(())
+    }
+
+    async fn retrieve_file(&self, file_key: &str) -> CustomResult<Vec<u8>, FileStorageError> {
+        let object = self
+            .client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(file_key)
+            .send()
+            .await
+            .map_err(|error| {
+                logger::error!(s3_sdk_error=?error, "Failed to retrieve file from S3");
+                error
+            })
+            .into_report()
+            .change_context(FileStorageError::RetrieveFailed)?;
+        let file = object
+            .body
+            .collect()
+            .await
+            .into_report()
+            .change_context(FileStorageError::RetrieveFailed)
+            .attach_printable("Invalid file data received from S3")?;
+        Ok(file.into_bytes().to_vec())
+    }
+
+    async fn get_presigned_url(
+        &self,
+        file_key: &str,
+        expires_in: Duration,
+    ) -> CustomResult<String, FileStorageError> {
+        let presigning_config = PresigningConfig::expires_in(expires_in)
+            .into_report()
+            .change_context(FileStorageError::PresignedUrlGenerationFailed)
+            .attach_printable("Invalid pre-signed URL expiry")?;
+        let request = self
+            .client
+            .get_object()
+            .bucket(&self.bucket_name)
+            .key(file_key)
+            .presigned(presigning_config)
+            .await
+            .into_report()
+            .change_context(FileStorageError::PresignedUrlGenerationFailed)?;
+        Ok(request.uri().to_string())
+    }
+}
@@ -0,0 +1,303 @@
+use std::time::Duration;
+
+use base64::Engine;
+use common_utils::{
+    crypto::{HmacSha256, SignMessage},
+    errors::CustomResult,
+    ext_traits::ConfigExt,
+    fp_utils::when,
+};
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
+use router_env::logger;
+
+use super::{FileStorageError, FileStorageInterface};
+use crate::consts;
+
+/// The version of the Blob service REST API that shared access signatures are signed for.
+const SAS_VERSION: &str = "2020-12-06";
+
+/// The number of seconds for which the shared access signatures of the requests sent to Blob
+/// Storage are valid.
+const REQUEST_SAS_EXPIRY_IN_SECONDS: u64 = 300;
+
+/// The time after which requests to Blob Storage are abandoned, so that an unresponsive service
+/// does not hold up the requests storing or retrieving files indefinitely.
+const REQUEST_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Configuration parameters required for constructing an [`AzureBlobFileStorage`] backend.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct AzureBlobConfig {
+    /// The name of the storage account.
+    pub account_name: String,
+
+    /// The base64-encoded access key of the storage account.
+    pub account_key: Secret<String>,
+
+    /// The container that files are stored in.
+    pub container_name: String,
+
+    /// The endpoint of the Blob service, such as `http://127.0.0.1:10000/devstoreaccount1` for a
+    /// local Azurite emulator. Defaults to `https://<account_name>.blob.core.windows.net`.
+    pub endpoint_url: Option<String>,
+}
+
+impl AzureBlobConfig {
+    /// Verifies that the [`AzureBlobFileStorage`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        when(self.account_name.is_default_or_empty(), || {
+            Err("Azure Blob Storage account name must not be empty")
+        })?;
+
+        when(self.account_key.peek().is_default_or_empty(), || {
+            Err("Azure Blob Storage account key must not be empty")
+        })?;
+
+        when(self.container_name.is_default_or_empty(), || {
+            Err("Azure Blob Storage container name must not be empty")
+        })
+    }
+}
+
+/// The operations that a shared access signature grants access to.
+#[derive(Clone, Copy, Debug)]
+enum SasPermission {
+    Read,
+    Write,
+    Delete,
+}
+
+impl SasPermission {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Read => "r",
+            Self::Write => "w",
+            Self::Delete => "d",
+        }
+    }
+}
+
+/// A file storage backend storing files as block blobs in Azure Blob Storage. Requests are
+/// authorized with short-lived service shared access signatures, signed with the account key.
+#[derive(Debug)]
+pub struct AzureBlobFileStorage {
+    client: reqwest::Client,
+    config: AzureBlobConfig,
+}
+
+impl AzureBlobFileStorage {
+    /// Constructs a new Azure Blob Storage backend.
+    ///
+    /// # Panics
+    ///
+    /// Will panic if the HTTP client could not be constructed
+    pub fn new(config: &AzureBlobConfig) -> Self {
+        // Building the client only fails if the TLS backend cannot be initialized, in which case
+        // `reqwest::Client::new()` panics as well
+        #[allow(clippy::expect_used)]
+        let client = reqwest::Client::builder()
+            .timeout(REQUEST_TIMEOUT)
+            .build()
+            .expect("Failed to construct Azure Blob Storage client");
+
+        Self {
+            client,
+            config: config.clone(),
+        }
+    }
+
+    /// The URL of the blob stored under the provided key, authorized with a shared access
+    /// signature granting the provided permission until it expires.
+    fn get_signed_blob_url(
+        &self,
+        file_key: &str,
+        permission: SasPermission,
+        expires_in: Duration,
+    ) -> CustomResult<reqwest::Url, FileStorageError> {
+        let endpoint_url = self.config.endpoint_url.clone().unwrap_or_else(|| {
+            format!("https://{}.blob.core.windows.net", self.config.account_name)
+        });
+        let mut url = reqwest::Url::parse(&format!(
+            "{}/{}/{file_key}",
+            endpoint_url.trim_end_matches('/'),
+            self.config.container_name
+        ))
+        .into_report()
+        .change_context(FileStorageError::InvalidFileKey)?;
+
+        let expiry = (time::OffsetDateTime::now_utc() + expires_in)
+            .replace_nanosecond(0)
+            .into_report()
+            .change_context(FileStorageError::PresignedUrlGenerationFailed)?
+            .format(&time::format_description::well_known::Rfc3339)
+            .into_report()
+            .change_context(FileStorageError::PresignedUrlGenerationFailed)
+            .attach_printable("Failed to format shared access signature expiry")?;
+        let signature = self.sign(&[
+            permission.as_str(),
+            "",
+            &expiry,
+            &format!(
+                "/blob/{}/{}/{file_key}",
+                self.config.account_name, self.config.container_name
+            ),
+            "",
+            "",
+            "",
+            SAS_VERSION,
+            "b",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+            "",
+        ])?;
+
+        url.query_pairs_mut()
+            .append_pair("sv", SAS_VERSION)
+            .append_pair("sr", "b")
+            .append_pair("sp", permission.as_str())
+            .append_pair("se", &expiry)
+            .append_pair("sig", &signature);
+        Ok(url)
+    }
+
+    /// Signs the fields of a shared access signature with the account key.
+    fn sign(&self, fields: &[&str]) -> CustomResult<String, FileStorageError> {
+        let account_key = consts::BASE64_ENGINE
+            .decode(self.config.account_key.peek())
+            .into_report()
+            .change_context(FileStorageError::PresignedUrlGenerationFailed)
+            .attach_printable("Failed to base64 decode the account key")?;
+        let signature = HmacSha256
+            .sign_message(&account_key, fields.join("\n").as_bytes())
+            .change_context(FileStorageError::PresignedUrlGenerationFailed)
+            .attach_printable("Failed to sign shared access signature")?;
+        Ok(consts::BASE64_ENGINE.encode(signature))
+    }
+
+    async fn send_request(
+        &self,
+        request: reqwest::RequestBuilder,
+        operation: &str,
+    ) -> Result<reqwest::Response, reqwest::Error> {
+        request
+            .send()
+            .await
+            .and_then(reqwest::Response::error_for_status)
+            .map_err(|error| {
+                logger::error!(
+                    azure_blob_error=?error,
+                    %operation,
+                    "Azure Blob Storage request failed"
+                );
+                error
+            })
+    }
+}
+
+#[async_trait::async_trait]
+impl FileStorageInterface for AzureBlobFileStorage {
+    async fn upload_file(
+        &self,
+        file_key: &str,
+        file: Vec<u8>,
+        content_type: &str,
+    ) -> CustomResult<(), FileStorageError> {
+        let url = self.get_signed_blob_url(
+            file_key,
+            SasPermission::Write,
+            Duration::from_secs(REQUEST_SAS_EXPIRY_IN_SECONDS),
+        )?;
+        let request = self
+            .client
+            .put(url)
+            .header("x-ms-blob-type", "BlockBlob")
+            .header(reqwest::header::CONTENT_TYPE, content_type)
+            .body(file);
+        self.send_request(request, "upload")
+            .await
+            .into_report()
+            .change_context(FileStorageError::UploadFailed)?;
+        Ok(())
+    }
+
+    async fn delete_file(&self, file_key: &str) -> CustomResult<(), FileStorageError> {
+        let url = self.get_signed_blob_url(
+            file_key,
+            SasPermission::Delete,
+            Duration::from_secs(REQUEST_SAS_EXPIRY_IN_SECONDS),
+        )?;
+        self.send_request(self.client.delete(url), "delete")
+            .await
+            .into_report()
+            .change_context(FileStorageError::DeleteFailed)?;
+        Ok(())
+    }
+
+    async fn retrieve_file(&self, file_key: &str) -> CustomResult<Vec<u8>, FileStorageError> {
+        let url = self.get_signed_blob_url(
+            file_key,
+            SasPermission::Read,
+            Duration::from_secs(REQUEST_SAS_EXPIRY_IN_SECONDS),
+        )?;
+        let file = self
+            .send_request(self.client.get(url), "retrieve")
+            .await
+            .into_report()
+            .change_context(FileStorageError::RetrieveFailed)?
+            .bytes()
+            .await
+            .into_report()
+            .change_context(FileStorageError::RetrieveFailed)
+            .attach_printable("Invalid file data received from Azure Blob Storage")?;
+        Ok(file.to_vec())
+    }
+
+    async fn get_presigned_url(
+        &self,
+        file_key: &str,
+        expires_in: Duration,
+    ) -> CustomResult<String, FileStorageError> {
+        self.get_signed_blob_url(file_key, SasPermission::Read, expires_in)
+            .map(String::from)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_signed_blob_url() {
+        let azure_blob = AzureBlobFileStorage::new(&AzureBlobConfig {
+            account_name: "devstoreaccount1".to_string(),
+            account_key: Secret::new(consts::BASE64_ENGINE.encode([0u8; 32])),
+            container_name: "files".to_string(),
+            endpoint_url: Some("http://127.0.0.1:10000/devstoreaccount1/".to_string()),
+        });
+
+        let url = azure_blob
+            .get_signed_blob_url(
+                "merchant_1/file_1",
+                SasPermission::Read,
+                Duration::from_secs(60),
+            )
+            .unwrap();
+        let query = url
+            .query_pairs()
+            .collect::<std::collections::HashMap<_, _>>();
+
+        assert_eq!(url.path(), "/devstoreaccount1/files/merchant_1/file_1");
+        assert_eq!(query["sv"], SAS_VERSION);
+        assert_eq!(query["sr"], "b");
+        assert_eq!(query["sp"], "r");
+        assert!(query["se"].ends_with('Z'));
+        assert!(!query["sig"].is_empty());
+    }
+}
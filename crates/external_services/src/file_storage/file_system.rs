@@ -0,0 +1,138 @@
+use std::{
+    path::{Component, Path, PathBuf},
+    time::Duration,
+};
+
+use common_utils::errors::CustomResult;
+use error_stack::{IntoReport, ResultExt};
+
+use super::{FileStorageError, FileStorageInterface};
+
+/// Configuration parameters required for constructing a [`FileSystem`] backend.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct FileSystemConfig {
+    /// The directory that files are stored in.
+    pub path: PathBuf,
+}
+
+impl Default for FileSystemConfig {
+    fn default() -> Self {
+        Self {
+            path: router_env::env::workspace_path().join("files"),
+        }
+    }
+}
+
+impl FileSystemConfig {
+    /// Verifies that the [`FileSystem`] backend configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        common_utils::fp_utils::when(self.path.as_os_str().is_empty(), || {
+            Err("File system storage path must not be empty")
+        })
+    }
+}
+
+/// A file storage backend storing files in a directory of the local file system, intended for
+/// tests and local development.
+#[derive(Debug)]
+pub struct FileSystem {
+    path: PathBuf,
+}
+
+impl FileSystem {
+    /// Constructs a new file system backend.
+    pub fn new(config: &FileSystemConfig) -> Self {
+        Self {
+            path: config.path.clone(),
+        }
+    }
+
+    /// Resolves the path of the file stored under the provided key, rejecting keys that would
+    /// resolve outside of the storage directory.
+    fn get_file_path(&self, file_key: &str) -> CustomResult<PathBuf, FileStorageError> {
+        let relative_path = Path::new(file_key);
+        let is_relative_path = relative_path
+            .components()
+            .all(|component| matches!(component, Component::Normal(_)));
+        if file_key.is_empty() || !is_relative_path {
+            return Err(FileStorageError::InvalidFileKey)
+                .into_report()
+                .attach_printable_lazy(|| format!("Invalid file key: {file_key}"));
+        }
+        Ok(self.path.join(relative_path))
+    }
+}
+
+#[async_trait::async_trait]
+impl FileStorageInterface for FileSystem {
+    async fn upload_file(
+        &self,
+        file_key: &str,
+        file: Vec<u8>,
+        _content_type: &str,
+    ) -> CustomResult<(), FileStorageError> {
+        let file_path = self.get_file_path(file_key)?;
+        if let Some(parent) = file_path.parent() {
+            tokio::fs::create_dir_all(parent)
+                .await
+                .into_report()
+                .change_context(FileStorageError::UploadFailed)
+                .attach_printable("Failed to create file directory")?;
+        }
+        tokio::fs::write(file_path, file)
+            .await
+            .into_report()
+            .change_context(FileStorageError::UploadFailed)
+            .attach_printable("Failed while writing into file")
+    }
+
+    async fn delete_file(&self, file_key: &str) -> CustomResult<(), FileStorageError> {
+        tokio::fs::remove_file(self.get_file_path(file_key)?)
+            .await
+            .into_report()
+            .change_context(FileStorageError::DeleteFailed)
+            .attach_printable("Failed while deleting the file")
+    }
+
+    async fn retrieve_file(&self, file_key: &str) -> CustomResult<Vec<u8>, FileStorageError> {
+        tokio::fs::read(self.get_file_path(file_key)?)
+            .await
+            .into_report()
+            .change_context(FileStorageError::RetrieveFailed)
+            .attach_printable("Failed while reading the file")
+    }
+
+    async fn get_presigned_url(
+        &self,
+        _file_key: &str,
+        _expires_in: Duration,
+    ) -> CustomResult<String, FileStorageError> {
+        Err(FileStorageError::PresignedUrlNotSupported).into_report()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_file_path_rejects_keys_outside_storage_directory() {
+        let file_system = FileSystem::new(&FileSystemConfig {
+            path: PathBuf::from("/tmp/files"),
+        });
+
+        assert_eq!(
+            file_system.get_file_path("merchant_1/file_1").unwrap(),
+            PathBuf::from("/tmp/files/merchant_1/file_1")
+        );
+        assert!(file_system.get_file_path("../merchant_1/file_1").is_err());
+        assert!(file_system
+            .get_file_path("merchant_1/../../file_1")
+            .is_err());
+        assert!(file_system.get_file_path("/etc/passwd").is_err());
+        assert!(file_system.get_file_path("").is_err());
+    }
+}
@@ -0,0 +1,54 @@
+use common_utils::{ext_traits::ConfigExt, fp_utils::when};
+
+use super::aws_s3::AwsS3Config;
+
+/// The endpoint of the S3-compatible XML API of Google Cloud Storage.
+const GCS_XML_API_ENDPOINT: &str = "https://storage.googleapis.com";
+
+/// Configuration parameters for storing files in Google Cloud Storage.
+///
+/// Files are stored through the S3-compatible XML API of Google Cloud Storage, authenticated with
+/// the HMAC key of a service account set in the `AWS_ACCESS_KEY_ID` and `AWS_SECRET_ACCESS_KEY`
+/// environment variables.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct GcsConfig {
+    /// The bucket that files are stored in.
+    pub bucket_name: String,
+
+    /// The endpoint of the XML API, overridable for emulators.
+    pub endpoint_url: String,
+}
+
+impl Default for GcsConfig {
+    fn default() -> Self {
+        Self {
+            bucket_name: String::new(),
+            endpoint_url: GCS_XML_API_ENDPOINT.to_string(),
+        }
+    }
+}
+
+impl GcsConfig {
+    /// Verifies that the Google Cloud Storage configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        when(self.bucket_name.is_default_or_empty(), || {
+            Err("GCS bucket name must not be empty")
+        })?;
+
+        when(self.endpoint_url.is_default_or_empty(), || {
+            Err("GCS endpoint URL must not be empty")
+        })
+    }
+
+    /// The configuration of the S3-compatible client for the XML API.
+    pub fn as_s3_config(&self) -> AwsS3Config {
+        AwsS3Config {
+            // Google Cloud Storage ignores the region of requests signed with HMAC keys
+            region: "auto".to_string(),
+            bucket_name: self.bucket_name.clone(),
+            endpoint_url: Some(self.endpoint_url.clone()),
+            force_path_style: true,
+        }
+    }
+}
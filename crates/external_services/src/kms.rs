@@ -7,20 +7,12 @@ use aws_sdk_kms::{config::Region, primitives::Blob, Client};
 use base64::Engine;
 use common_utils::errors::CustomResult;
 use error_stack::{IntoReport, ResultExt};
-use masking::{PeekInterface, Secret};
 use router_env::logger;
-/// decrypting data using the AWS KMS SDK.
-pub mod decrypt;
 
-use crate::{consts, metrics};
-
-static KMS_CLIENT: tokio::sync::OnceCell<KmsClient> = tokio::sync::OnceCell::const_new();
-
-/// Returns a shared KMS client, or initializes a new one if not previously initialized.
-#[inline]
-pub async fn get_kms_client(config: &KmsConfig) -> &'static KmsClient {
-    KMS_CLIENT.get_or_init(|| KmsClient::new(config)).await
-}
+use crate::{
+    consts, metrics,
+    secrets_management::{SecretManagementInterface, SecretsManagementError},
+};
 
 /// Configuration parameters required for constructing a [`KmsClient`].
 #[derive(Clone, Debug, Default, serde::Deserialize)]
@@ -96,6 +88,48 @@ impl KmsClient {
 
         Ok(output)
     }
+
+    /// Encrypts the provided plaintext using the AWS KMS SDK, returning the base64-encoded
+    /// ciphertext.
+    pub async fn encrypt(&self, data: impl AsRef<[u8]>) -> CustomResult<String, KmsError> {
+        let plaintext_blob = Blob::new(data.as_ref());
+
+        let encrypt_output = self
+            .inner_client
+            .encrypt()
+            .key_id(&self.key_id)
+            .plaintext(plaintext_blob)
+            .send()
+            .await
+            .map_err(|error| {
+                logger::error!(kms_sdk_error=?error, "Failed to KMS encrypt data");
+                metrics::AWS_KMS_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+                error
+            })
+            .into_report()
+            .change_context(KmsError::EncryptionFailed)?;
+
+        encrypt_output
+            .ciphertext_blob
+            .ok_or(KmsError::MissingCiphertextEncryptionOutput)
+            .into_report()
+            .map(|blob| consts::BASE64_ENGINE.encode(blob.into_inner()))
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for KmsClient {
+    async fn encrypt(&self, plaintext: &str) -> CustomResult<String, SecretsManagementError> {
+        self.encrypt(plaintext)
+            .await
+            .change_context(SecretsManagementError::EncryptionFailed)
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> CustomResult<String, SecretsManagementError> {
+        self.decrypt(ciphertext)
+            .await
+            .change_context(SecretsManagementError::DecryptionFailed)
+    }
 }
 
 /// Errors that could occur during KMS operations.
@@ -109,17 +143,21 @@ pub enum KmsError {
     #[error("Failed to KMS decrypt input data")]
     DecryptionFailed,
 
+    /// An error occurred when KMS encrypting input data.
+    #[error("Failed to KMS encrypt input data")]
+    EncryptionFailed,
+
     /// The KMS decrypted output does not include a plaintext output.
     #[error("Missing plaintext KMS decryption output")]
     MissingPlaintextDecryptionOutput,
 
+    /// The KMS encrypted output does not include a ciphertext output.
+    #[error("Missing ciphertext KMS encryption output")]
+    MissingCiphertextEncryptionOutput,
+
     /// An error occurred UTF-8 decoding KMS decrypted output.
     #[error("Failed to UTF-8 decode decryption output")]
     Utf8DecodingFailed,
-
-    /// The KMS client has not been initialized.
-    #[error("The KMS client has not been initialized")]
-    KmsClientNotInitialized,
 }
 
 impl KmsConfig {
@@ -136,14 +174,3 @@ impl KmsConfig {
         })
     }
 }
-
-/// A wrapper around a KMS value that can be decrypted.
-#[derive(Clone, Debug, Default, serde::Deserialize, Eq, PartialEq)]
-#[serde(transparent)]
-pub struct KmsValue(Secret<String>);
-
-impl common_utils::ext_traits::ConfigExt for KmsValue {
-    fn is_empty_after_trim(&self) -> bool {
-        self.0.peek().is_empty_after_trim()
-    }
-}
@@ -6,11 +6,13 @@
 #[cfg(feature = "email")]
 pub mod email;
 
-#[cfg(feature = "kms")]
+pub mod file_storage;
+
 pub mod kms;
 
+pub mod secrets_management;
+
 /// Crate specific constants
-#[cfg(feature = "kms")]
 pub mod consts {
     /// General purpose base64 engine
     pub(crate) const BASE64_ENGINE: base64::engine::GeneralPurpose =
@@ -18,16 +20,15 @@ pub mod consts {
 }
 
 /// Metrics for interactions with external systems.
-#[cfg(feature = "kms")]
 pub mod metrics {
     use router_env::{counter_metric, global_meter, histogram_metric, metrics_context};
 
     metrics_context!(CONTEXT);
     global_meter!(GLOBAL_METER, "EXTERNAL_SERVICES");
 
-    #[cfg(feature = "kms")]
     counter_metric!(AWS_KMS_FAILURES, GLOBAL_METER); // No. of AWS KMS API failures
-
-    #[cfg(feature = "kms")]
     histogram_metric!(AWS_KMS_DECRYPT_TIME, GLOBAL_METER); // Histogram for KMS decryption time (in sec)
+
+    counter_metric!(HASHICORP_VAULT_FAILURES, GLOBAL_METER); // No. of HashiCorp Vault API failures
+    histogram_metric!(HASHICORP_VAULT_DECRYPT_TIME, GLOBAL_METER); // Histogram for Vault decryption time (in sec)
 }
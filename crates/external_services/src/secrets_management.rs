@@ -0,0 +1,167 @@
+//! Encrypting and decrypting the secrets of the application through the secrets manager selected
+//! in the configuration.
+
+use common_utils::{errors::CustomResult, ext_traits::ConfigExt};
+use error_stack::ResultExt;
+use masking::{PeekInterface, Secret};
+
+use crate::kms::{KmsClient, KmsConfig};
+
+/// Decrypting secrets with the HashiCorp Vault Transit secrets engine.
+pub mod hashicorp_vault;
+
+/// Decrypting secrets with keys read from a local keyring file.
+pub mod local;
+
+static SECRETS_MANAGER: tokio::sync::OnceCell<Box<dyn SecretManagementInterface>> =
+    tokio::sync::OnceCell::const_new();
+
+/// Returns the shared secrets manager, or initializes the one selected in the configuration if not
+/// previously initialized.
+#[inline]
+pub async fn get_secrets_manager(
+    config: &SecretsManagementConfig,
+) -> &'static dyn SecretManagementInterface {
+    SECRETS_MANAGER
+        .get_or_init(|| config.get_secret_management_client())
+        .await
+        .as_ref()
+}
+
+/// An interface for encrypting and decrypting secrets, implemented by each of the supported
+/// secrets managers.
+#[async_trait::async_trait]
+pub trait SecretManagementInterface: Send + Sync + std::fmt::Debug {
+    /// Encrypts the provided plaintext, returning a ciphertext that can be set in the
+    /// configuration in place of the plaintext.
+    async fn encrypt(&self, plaintext: &str) -> CustomResult<String, SecretsManagementError>;
+
+    /// Decrypts the provided ciphertext, as set in the configuration.
+    async fn decrypt(&self, ciphertext: &str) -> CustomResult<String, SecretsManagementError>;
+}
+
+/// The secrets managers that secrets can be encrypted with.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum SecretsManager {
+    /// Secrets are set in the configuration in plaintext.
+    #[default]
+    NoEncryption,
+    /// Secrets are encrypted with AWS KMS.
+    AwsKms,
+    /// Secrets are encrypted with the HashiCorp Vault Transit secrets engine.
+    HashicorpVault,
+    /// Secrets are encrypted with keys read from a local keyring file, intended for tests and
+    /// local development.
+    Local,
+}
+
+/// Configuration parameters for selecting and constructing the secrets manager.
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct SecretsManagementConfig {
+    /// The secrets manager that the secrets in the configuration are encrypted with.
+    pub secrets_manager: SecretsManager,
+
+    /// Configuration of AWS KMS, used when the secrets manager is `aws_kms`.
+    pub aws_kms: KmsConfig,
+
+    /// Configuration of HashiCorp Vault, used when the secrets manager is `hashicorp_vault`.
+    pub hc_vault: hashicorp_vault::HashiCorpVaultConfig,
+
+    /// Configuration of the local keyring, used when the secrets manager is `local`.
+    pub local: local::LocalKeyringConfig,
+}
+
+impl SecretsManagementConfig {
+    /// Verifies that the configuration of the selected secrets manager is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        match self.secrets_manager {
+            SecretsManager::NoEncryption => Ok(()),
+            SecretsManager::AwsKms => self.aws_kms.validate(),
+            SecretsManager::HashicorpVault => self.hc_vault.validate(),
+            SecretsManager::Local => self.local.validate(),
+        }
+    }
+
+    /// Constructs the client of the selected secrets manager.
+    pub async fn get_secret_management_client(&self) -> Box<dyn SecretManagementInterface> {
+        match self.secrets_manager {
+            SecretsManager::NoEncryption => Box::new(NoEncryption),
+            SecretsManager::AwsKms => Box::new(KmsClient::new(&self.aws_kms).await),
+            SecretsManager::HashicorpVault => {
+                Box::new(hashicorp_vault::HashiCorpVault::new(&self.hc_vault))
+            }
+            SecretsManager::Local => Box::new(local::LocalKeyring::new(&self.local)),
+        }
+    }
+}
+
+/// A secrets manager for secrets set in the configuration in plaintext.
+#[derive(Debug)]
+pub struct NoEncryption;
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for NoEncryption {
+    async fn encrypt(&self, plaintext: &str) -> CustomResult<String, SecretsManagementError> {
+        Ok(plaintext.to_owned())
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> CustomResult<String, SecretsManagementError> {
+        Ok(ciphertext.to_owned())
+    }
+}
+
+/// Errors that could occur during secrets management operations.
+#[derive(Debug, thiserror::Error)]
+pub enum SecretsManagementError {
+    /// An error occurred when encrypting input data.
+    #[error("Failed to encrypt input data")]
+    EncryptionFailed,
+
+    /// An error occurred when decrypting input data.
+    #[error("Failed to decrypt input data")]
+    DecryptionFailed,
+}
+
+/// A secret set in the configuration, encrypted with the configured secrets manager (or in
+/// plaintext when no encryption is configured).
+#[derive(Clone, Debug, Default, serde::Deserialize, Eq, PartialEq)]
+#[serde(transparent)]
+pub struct SecretValue(Secret<String>);
+
+impl ConfigExt for SecretValue {
+    fn is_empty_after_trim(&self) -> bool {
+        self.0.peek().is_empty_after_trim()
+    }
+}
+
+/// Performs in place decryption of the structure on which this is implemented.
+#[async_trait::async_trait]
+pub trait SecretsDecrypt {
+    /// The output type of the decryption
+    type Output;
+
+    /// Decrypts the structure given a secrets manager
+    async fn decrypt_inner(
+        self,
+        secrets_manager: &dyn SecretManagementInterface,
+    ) -> CustomResult<Self::Output, SecretsManagementError>
+    where
+        Self: Sized;
+}
+
+#[async_trait::async_trait]
+impl SecretsDecrypt for &SecretValue {
+    type Output = String;
+
+    async fn decrypt_inner(
+        self,
+        secrets_manager: &dyn SecretManagementInterface,
+    ) -> CustomResult<Self::Output, SecretsManagementError> {
+        secrets_manager
+            .decrypt(self.0.peek())
+            .await
+            .attach_printable("Failed to decrypt secret value")
+    }
+}
@@ -0,0 +1,245 @@
+use std::time::Instant;
+
+use base64::Engine;
+use common_utils::errors::CustomResult;
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
+use router_env::logger;
+
+use super::{SecretManagementInterface, SecretsManagementError};
+use crate::{consts, metrics};
+
+/// Configuration parameters required for constructing a [`HashiCorpVault`] client.
+#[derive(Clone, Debug, serde::Deserialize)]
+#[serde(default)]
+pub struct HashiCorpVaultConfig {
+    /// The address of the Vault server, such as `https://vault.example.com:8200`.
+    pub url: String,
+
+    /// The token authenticating the requests to Vault.
+    pub token: Secret<String>,
+
+    /// The Vault Enterprise namespace of the Transit secrets engine, if any.
+    pub namespace: Option<String>,
+
+    /// The path the Transit secrets engine is mounted at.
+    pub mount_path: String,
+
+    /// The name of the Transit key that secrets are encrypted with.
+    pub key_name: String,
+}
+
+impl Default for HashiCorpVaultConfig {
+    fn default() -> Self {
+        Self {
+            url: String::new(),
+            token: Secret::default(),
+            namespace: None,
+            mount_path: "transit".to_string(),
+            key_name: String::new(),
+        }
+    }
+}
+
+impl HashiCorpVaultConfig {
+    /// Verifies that the [`HashiCorpVault`] client configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        use common_utils::{ext_traits::ConfigExt, fp_utils::when};
+
+        when(self.url.is_default_or_empty(), || {
+            Err("HashiCorp Vault URL must not be empty")
+        })?;
+
+        when(self.token.peek().is_default_or_empty(), || {
+            Err("HashiCorp Vault token must not be empty")
+        })?;
+
+        when(self.mount_path.is_default_or_empty(), || {
+            Err("HashiCorp Vault Transit mount path must not be empty")
+        })?;
+
+        when(self.key_name.is_default_or_empty(), || {
+            Err("HashiCorp Vault Transit key name must not be empty")
+        })
+    }
+}
+
+/// Client for the HashiCorp Vault Transit secrets engine.
+#[derive(Debug)]
+pub struct HashiCorpVault {
+    client: reqwest::Client,
+    config: HashiCorpVaultConfig,
+}
+
+#[derive(serde::Serialize)]
+struct EncryptRequest {
+    plaintext: String,
+}
+
+#[derive(serde::Serialize)]
+struct DecryptRequest<'a> {
+    ciphertext: &'a str,
+}
+
+#[derive(serde::Deserialize)]
+struct TransitResponse<T> {
+    data: T,
+}
+
+#[derive(serde::Deserialize)]
+struct EncryptResponseData {
+    ciphertext: String,
+}
+
+#[derive(serde::Deserialize)]
+struct DecryptResponseData {
+    plaintext: Secret<String>,
+}
+
+impl HashiCorpVault {
+    /// Constructs a new HashiCorp Vault client.
+    pub fn new(config: &HashiCorpVaultConfig) -> Self {
+        Self {
+            client: reqwest::Client::new(),
+            config: config.clone(),
+        }
+    }
+
+    async fn send_transit_request<T, R>(
+        &self,
+        operation: &str,
+        body: &T,
+    ) -> CustomResult<R, HashiCorpVaultError>
+    where
+        T: serde::Serialize + Sync,
+        R: serde::de::DeserializeOwned,
+    {
+        let url = format!(
+            "{}/v1/{}/{operation}/{}",
+            self.config.url.trim_end_matches('/'),
+            self.config.mount_path.trim_matches('/'),
+            self.config.key_name
+        );
+        let mut request = self
+            .client
+            .post(url)
+            .header("X-Vault-Token", self.config.token.peek())
+            .json(body);
+        if let Some(namespace) = &self.config.namespace {
+            request = request.header("X-Vault-Namespace", namespace);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|error| {
+                logger::error!(
+                    vault_error=?error,
+                    %operation,
+                    "Failed to send HashiCorp Vault request"
+                );
+                metrics::HASHICORP_VAULT_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+                error
+            })
+            .into_report()
+            .change_context(HashiCorpVaultError::RequestFailed)?;
+
+        let status = response.status();
+        if !status.is_success() {
+            logger::error!(%status, %operation, "HashiCorp Vault request failed");
+            metrics::HASHICORP_VAULT_FAILURES.add(&metrics::CONTEXT, 1, &[]);
+            return Err(HashiCorpVaultError::UnexpectedStatus(status.as_u16()).into());
+        }
+
+        response
+            .json::<TransitResponse<R>>()
+            .await
+            .into_report()
+            .change_context(HashiCorpVaultError::ResponseDeserializationFailed)
+            .map(|response| response.data)
+    }
+
+    /// Encrypts the provided plaintext with the configured Transit key, returning the Vault
+    /// ciphertext (`vault:v<key version>:<ciphertext>`).
+    pub async fn encrypt(
+        &self,
+        data: impl AsRef<[u8]>,
+    ) -> CustomResult<String, HashiCorpVaultError> {
+        let request = EncryptRequest {
+            plaintext: consts::BASE64_ENGINE.encode(data),
+        };
+
+        self.send_transit_request::<_, EncryptResponseData>("encrypt", &request)
+            .await
+            .map(|data| data.ciphertext)
+    }
+
+    /// Decrypts the provided Vault ciphertext with the configured Transit key.
+    pub async fn decrypt(&self, data: &str) -> CustomResult<String, HashiCorpVaultError> {
+        let start = Instant::now();
+        let response = self
+            .send_transit_request::<_, DecryptResponseData>(
+                "decrypt",
+                &DecryptRequest { ciphertext: data },
+            )
+            .await?;
+
+        let output = consts::BASE64_ENGINE
+            .decode(response.plaintext.peek())
+            .into_report()
+            .change_context(HashiCorpVaultError::Base64DecodingFailed)
+            .and_then(|plaintext| {
+                String::from_utf8(plaintext)
+                    .into_report()
+                    .change_context(HashiCorpVaultError::Utf8DecodingFailed)
+            })?;
+
+        let time_taken = start.elapsed();
+        metrics::HASHICORP_VAULT_DECRYPT_TIME.record(
+            &metrics::CONTEXT,
+            time_taken.as_secs_f64(),
+            &[],
+        );
+
+        Ok(output)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for HashiCorpVault {
+    async fn encrypt(&self, plaintext: &str) -> CustomResult<String, SecretsManagementError> {
+        self.encrypt(plaintext)
+            .await
+            .change_context(SecretsManagementError::EncryptionFailed)
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> CustomResult<String, SecretsManagementError> {
+        self.decrypt(ciphertext)
+            .await
+            .change_context(SecretsManagementError::DecryptionFailed)
+    }
+}
+
+/// Errors that could occur during HashiCorp Vault operations.
+#[derive(Debug, thiserror::Error)]
+pub enum HashiCorpVaultError {
+    /// An error occurred when sending the request to Vault.
+    #[error("Failed to send request to HashiCorp Vault")]
+    RequestFailed,
+
+    /// Vault responded with an unsuccessful status code.
+    #[error("HashiCorp Vault responded with status code {0}")]
+    UnexpectedStatus(u16),
+
+    /// An error occurred when deserializing the response of Vault.
+    #[error("Failed to deserialize HashiCorp Vault response")]
+    ResponseDeserializationFailed,
+
+    /// An error occurred when base64 decoding the decrypted output.
+    #[error("Failed to base64 decode decryption output")]
+    Base64DecodingFailed,
+
+    /// An error occurred UTF-8 decoding the decrypted output.
+    #[error("Failed to UTF-8 decode decryption output")]
+    Utf8DecodingFailed,
+}
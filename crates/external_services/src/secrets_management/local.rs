@@ -0,0 +1,256 @@
+use std::{collections::HashMap, path::PathBuf};
+
+use base64::Engine;
+use common_utils::{
+    crypto::{DecodeMessage, EncodeMessage, GcmAes256},
+    errors::CustomResult,
+};
+use error_stack::{IntoReport, ResultExt};
+use masking::{PeekInterface, StrongSecret};
+use once_cell::sync::OnceCell;
+
+use super::{SecretManagementInterface, SecretsManagementError};
+use crate::consts;
+
+/// The length of the AES-256 keys of the keyring, in bytes.
+const KEY_LENGTH: usize = 32;
+
+/// Configuration parameters required for constructing a [`LocalKeyring`].
+#[derive(Clone, Debug, Default, serde::Deserialize)]
+#[serde(default)]
+pub struct LocalKeyringConfig {
+    /// The path of the keyring file, a JSON object holding the hex-encoded 32-byte keys of the
+    /// keyring by their identifier under `keys`, and the identifier of the key that secrets are
+    /// encrypted with under `active_key_id`.
+    pub keyring_path: PathBuf,
+}
+
+impl LocalKeyringConfig {
+    /// Verifies that the [`LocalKeyring`] configuration is usable.
+    pub fn validate(&self) -> Result<(), &'static str> {
+        common_utils::fp_utils::when(self.keyring_path.as_os_str().is_empty(), || {
+            Err("Local keyring path must not be empty")
+        })
+    }
+}
+
+/// The keys of a local keyring. Secrets are encrypted with the active key, and decrypted with the
+/// key whose identifier prefixes their ciphertext, so that the active key can be rotated without
+/// re-encrypting every secret at once.
+#[derive(Debug, serde::Deserialize)]
+pub struct Keyring {
+    /// The identifier of the key that secrets are encrypted with.
+    pub active_key_id: String,
+
+    /// The hex-encoded keys of the keyring, by their identifier.
+    pub keys: HashMap<String, StrongSecret<String>>,
+}
+
+/// A secrets manager encrypting secrets with AES-256-GCM using keys read from a local keyring
+/// file. Ciphertexts are formatted as `<key identifier>:<base64-encoded ciphertext>`.
+#[derive(Debug)]
+pub struct LocalKeyring {
+    keyring_path: PathBuf,
+    keys: OnceCell<LoadedKeyring>,
+}
+
+#[derive(Debug)]
+struct LoadedKeyring {
+    active_key_id: String,
+    keys: HashMap<String, StrongSecret<Vec<u8>>>,
+}
+
+impl LocalKeyring {
+    /// Constructs a new local keyring. The keyring file is only read on first use.
+    pub fn new(config: &LocalKeyringConfig) -> Self {
+        Self {
+            keyring_path: config.keyring_path.clone(),
+            keys: OnceCell::new(),
+        }
+    }
+
+    /// Constructs a local keyring from keys held in memory.
+    pub fn from_keyring(keyring: Keyring) -> CustomResult<Self, LocalKeyringError> {
+        Ok(Self {
+            keyring_path: PathBuf::new(),
+            keys: OnceCell::with_value(LoadedKeyring::try_from(keyring)?),
+        })
+    }
+
+    fn keys(&self) -> CustomResult<&LoadedKeyring, LocalKeyringError> {
+        self.keys.get_or_try_init(|| {
+            let contents = std::fs::read_to_string(&self.keyring_path)
+                .into_report()
+                .change_context(LocalKeyringError::KeyringReadFailed)
+                .attach_printable_lazy(|| format!("path: {}", self.keyring_path.display()))?;
+            let keyring = serde_json::from_str::<Keyring>(&contents)
+                .into_report()
+                .change_context(LocalKeyringError::KeyringReadFailed)
+                .attach_printable("Failed to deserialize keyring file")?;
+
+            LoadedKeyring::try_from(keyring)
+        })
+    }
+
+    /// Encrypts the provided plaintext with the active key of the keyring.
+    pub fn encrypt(&self, data: impl AsRef<[u8]>) -> CustomResult<String, LocalKeyringError> {
+        let keys = self.keys()?;
+        let key = keys
+            .keys
+            .get(&keys.active_key_id)
+            .ok_or(LocalKeyringError::KeyNotFound)
+            .into_report()?;
+
+        let ciphertext = GcmAes256
+            .encode_message(key.peek(), data.as_ref())
+            .change_context(LocalKeyringError::EncryptionFailed)?;
+
+        Ok(format!(
+            "{}:{}",
+            keys.active_key_id,
+            consts::BASE64_ENGINE.encode(ciphertext)
+        ))
+    }
+
+    /// Decrypts the provided ciphertext with the key of the keyring it was encrypted with.
+    pub fn decrypt(&self, data: &str) -> CustomResult<String, LocalKeyringError> {
+        let (key_id, ciphertext) = data
+            .split_once(':')
+            .ok_or(LocalKeyringError::InvalidCiphertext)
+            .into_report()?;
+        let key = self
+            .keys()?
+            .keys
+            .get(key_id)
+            .ok_or(LocalKeyringError::KeyNotFound)
+            .into_report()
+            .attach_printable_lazy(|| format!("key_id: {key_id}"))?;
+
+        let ciphertext = consts::BASE64_ENGINE
+            .decode(ciphertext)
+            .into_report()
+            .change_context(LocalKeyringError::InvalidCiphertext)?;
+        let plaintext = GcmAes256
+            .decode_message(key.peek(), ciphertext.into())
+            .change_context(LocalKeyringError::DecryptionFailed)?;
+
+        String::from_utf8(plaintext)
+            .into_report()
+            .change_context(LocalKeyringError::DecryptionFailed)
+    }
+}
+
+impl TryFrom<Keyring> for LoadedKeyring {
+    type Error = error_stack::Report<LocalKeyringError>;
+
+    fn try_from(keyring: Keyring) -> Result<Self, Self::Error> {
+        let keys = keyring
+            .keys
+            .into_iter()
+            .map(|(key_id, key)| {
+                let key = hex::decode(key.peek())
+                    .into_report()
+                    .change_context(LocalKeyringError::InvalidKey)
+                    .attach_printable_lazy(|| format!("key_id: {key_id}"))?;
+                if key.len() != KEY_LENGTH {
+                    return Err(error_stack::report!(LocalKeyringError::InvalidKey))
+                        .attach_printable_lazy(|| format!("key_id: {key_id}"));
+                }
+
+                Ok((key_id, StrongSecret::new(key)))
+            })
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        if !keys.contains_key(&keyring.active_key_id) {
+            return Err(error_stack::report!(LocalKeyringError::KeyNotFound))
+                .attach_printable("The active key is missing from the keyring");
+        }
+
+        Ok(Self {
+            active_key_id: keyring.active_key_id,
+            keys,
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretManagementInterface for LocalKeyring {
+    async fn encrypt(&self, plaintext: &str) -> CustomResult<String, SecretsManagementError> {
+        self.encrypt(plaintext)
+            .change_context(SecretsManagementError::EncryptionFailed)
+    }
+
+    async fn decrypt(&self, ciphertext: &str) -> CustomResult<String, SecretsManagementError> {
+        self.decrypt(ciphertext)
+            .change_context(SecretsManagementError::DecryptionFailed)
+    }
+}
+
+/// Errors that could occur during local keyring operations.
+#[derive(Debug, thiserror::Error)]
+pub enum LocalKeyringError {
+    /// An error occurred when reading the keyring file.
+    #[error("Failed to read the keyring file")]
+    KeyringReadFailed,
+
+    /// A key of the keyring is not a hex-encoded 32-byte key.
+    #[error("Invalid key in the keyring")]
+    InvalidKey,
+
+    /// The key that the data was encrypted with is not in the keyring.
+    #[error("Key not found in the keyring")]
+    KeyNotFound,
+
+    /// The ciphertext is not formatted as `<key identifier>:<base64-encoded ciphertext>`.
+    #[error("Invalid ciphertext")]
+    InvalidCiphertext,
+
+    /// An error occurred when encrypting input data.
+    #[error("Failed to encrypt input data")]
+    EncryptionFailed,
+
+    /// An error occurred when decrypting input data.
+    #[error("Failed to decrypt input data")]
+    DecryptionFailed,
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    fn keyring(active_key_id: &str) -> LocalKeyring {
+        LocalKeyring::from_keyring(Keyring {
+            active_key_id: active_key_id.to_string(),
+            keys: HashMap::from([
+                (
+                    "key_1".to_string(),
+                    StrongSecret::new("11".repeat(KEY_LENGTH)),
+                ),
+                (
+                    "key_2".to_string(),
+                    StrongSecret::new("22".repeat(KEY_LENGTH)),
+                ),
+            ]),
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn test_decrypt_with_rotated_keys() {
+        let ciphertext = keyring("key_1").encrypt("hunter2").unwrap();
+        assert!(ciphertext.starts_with("key_1:"));
+
+        let rotated_keyring = keyring("key_2");
+        assert_eq!(rotated_keyring.decrypt(&ciphertext).unwrap(), "hunter2");
+        assert!(rotated_keyring
+            .encrypt("hunter2")
+            .unwrap()
+            .starts_with("key_2:"));
+    }
+
+    #[test]
+    fn test_decrypt_with_unknown_key() {
+        assert!(keyring("key_1").decrypt("key_3:AAAA").is_err());
+    }
+}
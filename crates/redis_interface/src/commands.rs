@@ -6,7 +6,7 @@
 //!
 //!
 
-use std::fmt::Debug;
+use std::{collections::HashMap, fmt::Debug};
 
 use common_utils::{
     errors::CustomResult,
@@ -31,6 +31,49 @@ use crate::{
 };
 
 impl super::RedisConnectionPool {
+    /// Adds the key prefix of the connection pool to the specified key.
+    pub fn add_prefix(&self, key: &str) -> String {
+        if self.config.key_prefix.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}:{}", self.config.key_prefix, key)
+        }
+    }
+
+    fn add_prefix_to_keys<K>(&self, keys: K) -> MultipleKeys
+    where
+        K: Into<MultipleKeys>,
+    {
+        keys.into()
+            .inner()
+            .into_iter()
+            .map(|key| self.add_prefix(&key.as_str_lossy()))
+            .collect()
+    }
+
+    /// Removes the key prefix of the connection pool from the stream names in a stream read
+    /// response, so that callers can look up entries by the stream names they requested.
+    fn strip_prefix_from_stream_names<I, F, V>(
+        &self,
+        entries: XReadResponse<String, I, F, V>,
+    ) -> XReadResponse<String, I, F, V> {
+        if self.config.key_prefix.is_empty() {
+            return entries;
+        }
+
+        let prefix = format!("{}:", self.config.key_prefix);
+        entries
+            .into_iter()
+            .map(|(stream, entries)| {
+                let stream = stream
+                    .strip_prefix(prefix.as_str())
+                    .map(ToString::to_string)
+                    .unwrap_or(stream);
+                (stream, entries)
+            })
+            .collect()
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_key<V>(&self, key: &str, value: V) -> CustomResult<(), errors::RedisError>
     where
@@ -39,7 +82,7 @@ impl super::RedisConnectionPool {
     {
         self.pool
             .set(
-                key,
+                self.add_prefix(key),
                 value,
                 Some(Expiration::EX(self.config.default_ttl.into())),
                 None,
@@ -58,6 +101,17 @@ impl super::RedisConnectionPool {
         V: TryInto<RedisMap> + Debug + Send + Sync,
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
+        let value: RedisMap = value
+            .try_into()
+            .map_err(Into::into)
+            .into_report()
+            .change_context(errors::RedisError::SetFailed)?;
+        let value = value
+            .inner()
+            .into_iter()
+            .map(|(key, value)| (self.add_prefix(&key.as_str_lossy()), value))
+            .collect::<HashMap<_, _>>();
+
         self.pool
             .msetnx(value)
             .await
@@ -111,7 +165,7 @@ impl super::RedisConnectionPool {
 
         self.pool
             .set(
-                key,
+                self.add_prefix(key),
                 serialized.as_slice(),
                 Some(Expiration::EX(seconds)),
                 None,
@@ -128,7 +182,7 @@ impl super::RedisConnectionPool {
         V: FromRedis + Unpin + Send + 'static,
     {
         self.pool
-            .get(key)
+            .get(self.add_prefix(key))
             .await
             .into_report()
             .change_context(errors::RedisError::GetFailed)
@@ -140,7 +194,7 @@ impl super::RedisConnectionPool {
         V: Into<MultipleKeys> + Unpin + Send + 'static,
     {
         self.pool
-            .exists(key)
+            .exists(self.add_prefix(key))
             .await
             .into_report()
             .change_context(errors::RedisError::GetFailed)
@@ -167,7 +221,7 @@ impl super::RedisConnectionPool {
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn delete_key(&self, key: &str) -> CustomResult<DelReply, errors::RedisError> {
         self.pool
-            .del(key)
+            .del(self.add_prefix(key))
             .await
             .into_report()
             .change_context(errors::RedisError::DeleteFailed)
@@ -185,7 +239,13 @@ impl super::RedisConnectionPool {
         V::Error: Into<fred::error::RedisError> + Send + Sync,
     {
         self.pool
-            .set(key, value, Some(Expiration::EX(seconds)), None, false)
+            .set(
+                self.add_prefix(key),
+                value,
+                Some(Expiration::EX(seconds)),
+                None,
+                false,
+            )
             .await
             .into_report()
             .change_context(errors::RedisError::SetExFailed)
@@ -204,7 +264,7 @@ impl super::RedisConnectionPool {
     {
         self.pool
             .set(
-                key,
+                self.add_prefix(key),
                 value,
                 Some(Expiration::EX(
                     seconds.unwrap_or(self.config.default_ttl.into()),
@@ -217,6 +277,33 @@ impl super::RedisConnectionPool {
             .change_context(errors::RedisError::SetFailed)
     }
 
+    /// Increments the integer value of the key by one, setting the key to zero before the
+    /// increment if it does not exist, and returns the incremented value.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn increment_key(&self, key: &str) -> CustomResult<i64, errors::RedisError> {
+        self.pool
+            .incr(self.add_prefix(key))
+            .await
+            .into_report()
+            .change_context(errors::RedisError::IncrementFailed)
+    }
+
+    /// Increments the integer value of the key by the given amount, which may be negative,
+    /// setting the key to zero before the increment if it does not exist, and returns the
+    /// incremented value.
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn increment_key_by(
+        &self,
+        key: &str,
+        amount: i64,
+    ) -> CustomResult<i64, errors::RedisError> {
+        self.pool
+            .incr_by(self.add_prefix(key), amount)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::IncrementFailed)
+    }
+
     #[instrument(level = "DEBUG", skip(self))]
     pub async fn set_expiry(
         &self,
@@ -224,7 +311,7 @@ impl super::RedisConnectionPool {
         seconds: i64,
     ) -> CustomResult<(), errors::RedisError> {
         self.pool
-            .expire(key, seconds)
+            .expire(self.add_prefix(key), seconds)
             .await
             .into_report()
             .change_context(errors::RedisError::SetExpiryFailed)
@@ -237,7 +324,7 @@ impl super::RedisConnectionPool {
         timestamp: i64,
     ) -> CustomResult<(), errors::RedisError> {
         self.pool
-            .expire_at(key, timestamp)
+            .expire_at(self.add_prefix(key), timestamp)
             .await
             .into_report()
             .change_context(errors::RedisError::SetExpiryFailed)
@@ -256,7 +343,7 @@ impl super::RedisConnectionPool {
     {
         let output: Result<(), _> = self
             .pool
-            .hset(key, values)
+            .hset(self.add_prefix(key), values)
             .await
             .into_report()
             .change_context(errors::RedisError::SetHashFailed);
@@ -283,7 +370,7 @@ impl super::RedisConnectionPool {
     {
         let output: Result<HsetnxReply, _> = self
             .pool
-            .hsetnx(key, field, value)
+            .hsetnx(self.add_prefix(key), field, value)
             .await
             .into_report()
             .change_context(errors::RedisError::SetHashFieldFailed);
@@ -325,7 +412,7 @@ impl super::RedisConnectionPool {
         K: Into<MultipleKeys> + Send + Debug,
     {
         self.pool
-            .mget(keys)
+            .mget(self.add_prefix_to_keys(keys))
             .await
             .into_report()
             .change_context(errors::RedisError::GetFailed)
@@ -384,7 +471,7 @@ impl super::RedisConnectionPool {
     ) -> CustomResult<Vec<String>, errors::RedisError> {
         Ok(self
             .pool
-            .hscan::<&str, &str>(key, pattern, count)
+            .hscan::<String, &str>(self.add_prefix(key), pattern, count)
             .filter_map(|value| async move {
                 match value {
                     Ok(mut v) => {
@@ -435,7 +522,20 @@ impl super::RedisConnectionPool {
         V: FromRedis + Unpin + Send + 'static,
     {
         self.pool
-            .hget(key, field)
+            .hget(self.add_prefix(key), field)
+            .await
+            .into_report()
+            .change_context(errors::RedisError::GetHashFieldFailed)
+    }
+
+    #[instrument(level = "DEBUG", skip(self))]
+    pub async fn check_hash_field_exists(
+        &self,
+        key: &str,
+        field: &str,
+    ) -> CustomResult<bool, errors::RedisError> {
+        self.pool
+            .hexists(self.add_prefix(key), field)
             .await
             .into_report()
             .change_context(errors::RedisError::GetHashFieldFailed)
@@ -474,7 +574,7 @@ impl super::RedisConnectionPool {
         F::Error: Into<fred::error::RedisError> + Send + Sync,
     {
         self.pool
-            .xadd(stream, false, None, entry_id, fields)
+            .xadd(self.add_prefix(stream), false, None, entry_id, fields)
             .await
             .into_report()
             .change_context(errors::RedisError::StreamAppendFailed)
@@ -490,7 +590,7 @@ impl super::RedisConnectionPool {
         Ids: Into<MultipleStrings> + Debug + Send + Sync,
     {
         self.pool
-            .xdel(stream, ids)
+            .xdel(self.add_prefix(stream), ids)
             .await
             .into_report()
             .change_context(errors::RedisError::StreamDeleteFailed)
@@ -507,7 +607,7 @@ impl super::RedisConnectionPool {
         C::Error: Into<fred::error::RedisError> + Send + Sync,
     {
         self.pool
-            .xtrim(stream, xcap)
+            .xtrim(self.add_prefix(stream), xcap)
             .await
             .into_report()
             .change_context(errors::RedisError::StreamTrimFailed)
@@ -524,7 +624,7 @@ impl super::RedisConnectionPool {
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
         self.pool
-            .xack(stream, group, ids)
+            .xack(self.add_prefix(stream), group, ids)
             .await
             .into_report()
             .change_context(errors::RedisError::StreamAcknowledgeFailed)
@@ -536,7 +636,7 @@ impl super::RedisConnectionPool {
         K: Into<RedisKey> + Debug + Send + Sync,
     {
         self.pool
-            .xlen(stream)
+            .xlen(self.add_prefix(&Into::<RedisKey>::into(stream).as_str_lossy()))
             .await
             .into_report()
             .change_context(errors::RedisError::GetLengthFailed)
@@ -557,10 +657,11 @@ impl super::RedisConnectionPool {
             .xread_map(
                 Some(read_count.unwrap_or(self.config.default_stream_read_count)),
                 None,
-                streams,
+                self.add_prefix_to_keys(streams),
                 ids,
             )
             .await
+            .map(|entries| self.strip_prefix_from_stream_names(entries))
             .into_report()
             .map_err(|err| match err.current_context().kind() {
                 RedisErrorKind::NotFound => {
@@ -583,6 +684,7 @@ impl super::RedisConnectionPool {
         K: Into<MultipleKeys> + Debug + Send + Sync,
         Ids: Into<MultipleIDs> + Debug + Send + Sync,
     {
+        let streams = self.add_prefix_to_keys(streams);
         match group {
             Some((group_name, consumer_name)) => {
                 self.pool
@@ -591,6 +693,7 @@ impl super::RedisConnectionPool {
             }
             None => self.pool.xread_map(count, block, streams, ids).await,
         }
+        .map(|entries| self.strip_prefix_from_stream_names(entries))
         .into_report()
         .change_context(errors::RedisError::StreamReadFailed)
     }
@@ -613,7 +716,7 @@ impl super::RedisConnectionPool {
         }
 
         self.pool
-            .xgroup_create(stream, group, id, true)
+            .xgroup_create(self.add_prefix(stream), group, id, true)
             .await
             .into_report()
             .change_context(errors::RedisError::ConsumerGroupCreateFailed)
@@ -626,7 +729,7 @@ impl super::RedisConnectionPool {
         group: &str,
     ) -> CustomResult<usize, errors::RedisError> {
         self.pool
-            .xgroup_destroy(stream, group)
+            .xgroup_destroy(self.add_prefix(stream), group)
             .await
             .into_report()
             .change_context(errors::RedisError::ConsumerGroupDestroyFailed)
@@ -641,7 +744,7 @@ impl super::RedisConnectionPool {
         consumer: &str,
     ) -> CustomResult<usize, errors::RedisError> {
         self.pool
-            .xgroup_delconsumer(stream, group, consumer)
+            .xgroup_delconsumer(self.add_prefix(stream), group, consumer)
             .await
             .into_report()
             .change_context(errors::RedisError::ConsumerGroupRemoveConsumerFailed)
@@ -655,7 +758,7 @@ impl super::RedisConnectionPool {
         id: &RedisEntryId,
     ) -> CustomResult<String, errors::RedisError> {
         self.pool
-            .xgroup_setid(stream, group, id)
+            .xgroup_setid(self.add_prefix(stream), group, id)
             .await
             .into_report()
             .change_context(errors::RedisError::ConsumerGroupSetIdFailed)
@@ -676,7 +779,7 @@ impl super::RedisConnectionPool {
     {
         self.pool
             .xclaim(
-                stream,
+                self.add_prefix(stream),
                 group,
                 consumer,
                 min_idle_time,
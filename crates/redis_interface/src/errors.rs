@@ -14,6 +14,8 @@ pub enum RedisError {
     SetExpiryFailed,
     #[error("Failed to get key value in Redis")]
     GetFailed,
+    #[error("Failed to increment key value in Redis")]
+    IncrementFailed,
     #[error("Failed to delete key value in Redis")]
     DeleteFailed,
     #[error("Failed to append entry to Redis stream")]
@@ -38,6 +38,7 @@ pub struct RedisConnectionPool {
     pub is_redis_available: Arc<atomic::AtomicBool>,
 }
 
+#[derive(Clone)]
 pub struct RedisClient {
     inner: fred::prelude::RedisClient,
 }
@@ -65,6 +66,7 @@ impl RedisClient {
     }
 }
 
+#[derive(Clone)]
 pub struct SubscriberClient {
     inner: fred::clients::SubscriberClient,
 }
@@ -152,6 +154,21 @@ impl RedisConnectionPool {
         })
     }
 
+    /// Creates a connection pool sharing the connections of this pool, which prefixes all keys
+    /// with the specified key prefix.
+    pub fn with_key_prefix(&self, key_prefix: &str) -> Self {
+        Self {
+            pool: self.pool.clone(),
+            config: RedisConfig {
+                key_prefix: key_prefix.to_string(),
+                ..self.config.clone()
+            },
+            subscriber: self.subscriber.clone(),
+            publisher: self.publisher.clone(),
+            is_redis_available: self.is_redis_available.clone(),
+        }
+    }
+
     pub async fn on_error(&self, tx: tokio::sync::oneshot::Sender<()>) {
         while let Ok(redis_error) = self.pool.on_error().recv().await {
             logger::error!(?redis_error, "Redis protocol or connection error");
@@ -168,10 +185,12 @@ impl RedisConnectionPool {
     }
 }
 
+#[derive(Clone)]
 struct RedisConfig {
     default_ttl: u32,
     default_stream_read_count: u64,
     default_hash_ttl: u32,
+    key_prefix: String,
 }
 
 impl From<&RedisSettings> for RedisConfig {
@@ -180,6 +199,7 @@ impl From<&RedisSettings> for RedisConfig {
             default_ttl: config.default_ttl,
             default_stream_read_count: config.stream_read_count,
             default_hash_ttl: config.default_hash_ttl,
+            key_prefix: String::new(),
         }
     }
 }
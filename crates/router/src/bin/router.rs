@@ -1,5 +1,5 @@
 use router::{
-    configs::settings::{CmdLineConf, Settings},
+    configs::settings::{CmdLineConf, Settings, Subcommand},
     core::errors::{ApplicationError, ApplicationResult},
     logger,
 };
@@ -11,7 +11,6 @@ async fn main() -> ApplicationResult<()> {
 
     #[cfg(feature = "openapi")]
     {
-        use router::configs::settings::Subcommand;
         if let Some(Subcommand::GenerateOpenapiSpec) = cmd_line.subcommand {
             let file_path = "openapi/openapi_spec.json";
             #[allow(clippy::expect_used)]
@@ -40,6 +39,10 @@ async fn main() -> ApplicationResult<()> {
         [router_env::service_name!(), "actix_server"],
     );
 
+    if let Some(Subcommand::RotateMasterKey { target_version }) = cmd_line.subcommand {
+        return rotate_master_key(&conf, target_version).await;
+    }
+
     logger::info!("Application started [{:?}] [{:?}]", conf.server, conf.log);
 
     #[allow(clippy::expect_used)]
@@ -53,3 +56,54 @@ async fn main() -> ApplicationResult<()> {
         "Server shut down",
     )))
 }
+
+/// Re-encrypts the merchant key stores of the deployment and of each configured tenant with the
+/// master key of `target_version`. Succeeds only if the key stores of all of them were
+/// re-encrypted.
+async fn rotate_master_key(conf: &Settings, target_version: i32) -> ApplicationResult<()> {
+    let (redis_shutdown_signal_tx, _redis_shutdown_signal_rx) = tokio::sync::oneshot::channel();
+    #[allow(clippy::expect_used)]
+    let store = router::services::get_store(conf, redis_shutdown_signal_tx, false)
+        .await
+        .expect("Failed to create store");
+
+    let mut tenants = conf.multitenancy.tenants.iter().collect::<Vec<_>>();
+    tenants.sort_by_key(|&(tenant_id, _)| tenant_id);
+
+    let mut failed = Vec::new();
+    let mut tenant_stores = Vec::new();
+    for (tenant_id, tenant) in tenants {
+        match router::services::get_tenant_store(conf, &store, tenant, false).await {
+            Ok(tenant_store) => tenant_stores.push((format!("tenant {tenant_id}"), tenant_store)),
+            Err(error) => {
+                logger::error!(?error, %tenant_id, "Failed to create tenant store");
+                failed.push(format!("tenant {tenant_id}"));
+            }
+        }
+    }
+
+    let stores = std::iter::once(("deployment".to_string(), store)).chain(tenant_stores);
+    for (name, store) in stores {
+        match router::core::master_key_rotation::rotate_master_key(&store, target_version).await {
+            Ok(summary) => println!(
+                "Re-encrypted {} of {} merchant key stores of {name} with the master key of version {target_version}",
+                summary.rewrapped, summary.processed
+            ),
+            Err(error) => {
+                logger::error!(?error, store = %name, "Failed to rotate the master key");
+                failed.push(name);
+            }
+        }
+    }
+
+    if !failed.is_empty() {
+        return Err(ApplicationError::from(std::io::Error::new(
+            std::io::ErrorKind::Other,
+            format!(
+                "Failed to rotate the master key of the merchant key stores of {}",
+                failed.join(", ")
+            ),
+        )));
+    }
+    Ok(())
+}
@@ -41,12 +41,6 @@ async fn main() -> CustomResult<(), ProcessTrackerError> {
     // channel for listening to redis disconnect events
     let (redis_shutdown_signal_tx, redis_shutdown_signal_rx) = oneshot::channel();
     let state = routes::AppState::new(conf, redis_shutdown_signal_tx, api_client).await;
-    // channel to shutdown scheduler gracefully
-    let (tx, rx) = mpsc::channel(1);
-    tokio::spawn(router::receiver_for_error(
-        redis_shutdown_signal_rx,
-        tx.clone(),
-    ));
 
     #[allow(clippy::expect_used)]
     let scheduler_flow_str =
@@ -63,7 +57,21 @@ async fn main() -> CustomResult<(), ProcessTrackerError> {
 
     logger::debug!(startup_config=?state.conf);
 
-    start_scheduler(&state, scheduler_flow, (tx, rx)).await?;
+    // The process tracker of each tenant is run with its own channel to shutdown gracefully
+    let tenant_states = state.tenant_states();
+    let mut shutdown_senders = Vec::with_capacity(tenant_states.len());
+    let mut schedulers = Vec::with_capacity(tenant_states.len());
+    for tenant_state in tenant_states.iter() {
+        let (tx, rx) = mpsc::channel(1);
+        shutdown_senders.push(tx.clone());
+        schedulers.push(start_scheduler(tenant_state, scheduler_flow, (tx, rx)));
+    }
+    tokio::spawn(router::receiver_for_error(
+        redis_shutdown_signal_rx,
+        shutdown_senders,
+    ));
+
+    futures::future::try_join_all(schedulers).await?;
 
     eprintln!("Scheduler shut down");
     Ok(())
@@ -76,6 +84,16 @@ pub enum PTRunner {
     PaymentsSyncWorkflow,
     RefundWorkflowRouter,
     DeleteTokenizeDataWorkflow,
+    ApiKeyRevocationWorkflow,
+    MerchantKeyRotationWorkflow,
+    KvMigrationWorkflow,
+    PaymentLinkExpiryWorkflow,
+    MandateExpiryWorkflow,
+    SubscriptionBillingWorkflow,
+    #[cfg(feature = "email")]
+    TransactionalEmailWorkflow,
+    #[cfg(feature = "email")]
+    InvoiceReminderWorkflow,
 }
 
 #[derive(Debug, Copy, Clone)]
@@ -100,6 +118,32 @@ impl ProcessTrackerWorkflows<routes::AppState> for WorkflowRunner {
             Some(PTRunner::DeleteTokenizeDataWorkflow) => {
                 Box::new(workflows::tokenized_data::DeleteTokenizeDataWorkflow)
             }
+            Some(PTRunner::ApiKeyRevocationWorkflow) => {
+                Box::new(workflows::api_key_revocation::ApiKeyRevocationWorkflow)
+            }
+            Some(PTRunner::MerchantKeyRotationWorkflow) => {
+                Box::new(workflows::merchant_key_rotation::MerchantKeyRotationWorkflow)
+            }
+            Some(PTRunner::KvMigrationWorkflow) => {
+                Box::new(workflows::kv_migration::KvMigrationWorkflow)
+            }
+            Some(PTRunner::PaymentLinkExpiryWorkflow) => {
+                Box::new(workflows::payment_link_expiry::PaymentLinkExpiryWorkflow)
+            }
+            Some(PTRunner::MandateExpiryWorkflow) => {
+                Box::new(workflows::mandate_expiry::MandateExpiryWorkflow)
+            }
+            Some(PTRunner::SubscriptionBillingWorkflow) => {
+                Box::new(workflows::subscription_billing::SubscriptionBillingWorkflow)
+            }
+            #[cfg(feature = "email")]
+            Some(PTRunner::TransactionalEmailWorkflow) => {
+                Box::new(workflows::transactional_email::TransactionalEmailWorkflow)
+            }
+            #[cfg(feature = "email")]
+            Some(PTRunner::InvoiceReminderWorkflow) => {
+                Box::new(workflows::invoice_reminder::InvoiceReminderWorkflow)
+            }
             _ => Err(ProcessTrackerError::UnexpectedFlow)?,
         };
         let app_state = &state.clone();
@@ -117,6 +117,9 @@ pub enum StripeErrorCode {
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "resource_missing", message = "No such API key")]
     ApiKeyNotFound,
 
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "resource_missing", message = "No such user")]
+    UserNotFound,
+
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "resource_missing", message = "No such payout")]
     PayoutNotFound,
 
@@ -241,6 +244,14 @@ pub enum StripeErrorCode {
     LockTimeout,
     #[error(error_type = StripeErrorType::InvalidRequestError, code = "", message = "Merchant connector account is configured with invalid {config}")]
     InvalidConnectorConfiguration { config: String },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "", message = "Tenant '{tenant_id}' is not configured")]
+    InvalidTenant { tenant_id: String },
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "Idempotency key was already used with a different request")]
+    IdempotencyKeyReused,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "idempotency_key_in_use", message = "A request with the same idempotency key is still being processed")]
+    IdempotentRequestInProgress,
+    #[error(error_type = StripeErrorType::InvalidRequestError, code = "rate_limit", message = "Too many requests. Retry after {retry_after_seconds} seconds")]
+    RateLimitExceeded { retry_after_seconds: u64 },
     // [#216]: https://github.com/juspay/hyperswitch/issues/216
     // Implement the remaining stripe error codes
 
@@ -498,12 +509,16 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             | errors::ApiErrorResponse::ClientSecretExpired => Self::ClientSecretNotFound,
             errors::ApiErrorResponse::MerchantAccountNotFound => Self::MerchantAccountNotFound,
             errors::ApiErrorResponse::PaymentLinkNotFound => Self::PaymentLinkNotFound,
-            errors::ApiErrorResponse::ResourceIdNotFound => Self::ResourceIdNotFound,
+            errors::ApiErrorResponse::ResourceIdNotFound
+            | errors::ApiErrorResponse::InvoiceNotFound
+            | errors::ApiErrorResponse::SubscriptionPlanNotFound
+            | errors::ApiErrorResponse::SubscriptionNotFound => Self::ResourceIdNotFound,
             errors::ApiErrorResponse::MerchantConnectorAccountNotFound { id } => {
                 Self::MerchantConnectorAccountNotFound { id }
             }
             errors::ApiErrorResponse::MandateNotFound => Self::MandateNotFound,
             errors::ApiErrorResponse::ApiKeyNotFound => Self::ApiKeyNotFound,
+            errors::ApiErrorResponse::UserNotFound => Self::UserNotFound,
             errors::ApiErrorResponse::PayoutNotFound => Self::PayoutNotFound,
             errors::ApiErrorResponse::MandateValidationFailed { reason } => {
                 Self::PaymentIntentMandateInvalid { message: reason }
@@ -595,6 +610,18 @@ impl From<errors::ApiErrorResponse> for StripeErrorCode {
             errors::ApiErrorResponse::InvalidConnectorConfiguration { config } => {
                 Self::InvalidConnectorConfiguration { config }
             }
+            errors::ApiErrorResponse::InvalidTenant { tenant_id } => {
+                Self::InvalidTenant { tenant_id }
+            }
+            errors::ApiErrorResponse::IdempotencyKeyReused => Self::IdempotencyKeyReused,
+            errors::ApiErrorResponse::IdempotentRequestInProgress => {
+                Self::IdempotentRequestInProgress
+            }
+            errors::ApiErrorResponse::RateLimitExceeded {
+                retry_after_seconds,
+            } => Self::RateLimitExceeded {
+                retry_after_seconds,
+            },
         }
     }
 }
@@ -629,6 +656,7 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::MerchantConnectorAccountDisabled
             | Self::MandateNotFound
             | Self::ApiKeyNotFound
+            | Self::UserNotFound
             | Self::PayoutNotFound
             | Self::DuplicateMerchantAccount
             | Self::DuplicateMerchantConnectorAccount { .. }
@@ -662,7 +690,8 @@ impl actix_web::ResponseError for StripeErrorCode {
             | Self::CurrencyNotSupported { .. }
             | Self::DuplicateCustomer
             | Self::PaymentMethodUnactivated
-            | Self::InvalidConnectorConfiguration { .. } => StatusCode::BAD_REQUEST,
+            | Self::InvalidConnectorConfiguration { .. }
+            | Self::InvalidTenant { .. } => StatusCode::BAD_REQUEST,
             Self::RefundFailed
             | Self::PayoutFailed
             | Self::PaymentLinkNotFound
@@ -675,15 +704,23 @@ impl actix_web::ResponseError for StripeErrorCode {
                 StatusCode::from_u16(*status_code).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
             }
             Self::LockTimeout => StatusCode::LOCKED,
+            Self::IdempotencyKeyReused | Self::IdempotentRequestInProgress => StatusCode::CONFLICT,
+            Self::RateLimitExceeded { .. } => StatusCode::TOO_MANY_REQUESTS,
         }
     }
 
     fn error_response(&self) -> actix_web::HttpResponse {
         use actix_web::http::header;
 
-        actix_web::HttpResponseBuilder::new(self.status_code())
-            .insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON))
-            .body(self.to_string())
+        let mut response = actix_web::HttpResponseBuilder::new(self.status_code());
+        response.insert_header((header::CONTENT_TYPE, mime::APPLICATION_JSON));
+        if let Self::RateLimitExceeded {
+            retry_after_seconds,
+        } = self
+        {
+            response.insert_header((header::RETRY_AFTER, *retry_after_seconds));
+        }
+        response.body(self.to_string())
     }
 }
 
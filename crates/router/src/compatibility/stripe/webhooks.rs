@@ -1,5 +1,5 @@
 use api_models::{
-    enums::{DisputeStatus, MandateStatus},
+    enums::{DisputeStatus, MandateStatus, SubscriptionStatus},
     webhooks::{self as api},
 };
 use common_utils::{crypto::SignMessage, date_time, ext_traits};
@@ -74,6 +74,7 @@ pub enum StripeWebhookObject {
     Refund(StripeRefundResponse),
     Dispute(StripeDisputeResponse),
     Mandate(StripeMandateResponse),
+    Subscription(StripeSubscriptionResponse),
 }
 
 #[derive(Serialize, Debug)]
@@ -102,6 +103,27 @@ pub enum StripeMandateStatus {
     Pending,
 }
 
+#[derive(Serialize, Debug)]
+pub struct StripeSubscriptionResponse {
+    pub id: String,
+    pub customer: String,
+    pub plan: String,
+    pub status: StripeSubscriptionStatus,
+    pub current_period_start: i64,
+    pub current_period_end: i64,
+    pub cancel_at_period_end: bool,
+    pub latest_invoice: Option<String>,
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "snake_case")]
+pub enum StripeSubscriptionStatus {
+    Trialing,
+    Active,
+    PastDue,
+    Canceled,
+}
+
 #[derive(Serialize, Debug)]
 #[serde(rename_all = "snake_case")]
 pub enum StripeDisputeStatus {
@@ -139,11 +161,39 @@ impl From<api_models::mandates::MandateResponse> for StripeMandateResponse {
     }
 }
 
+impl From<api_models::subscriptions::SubscriptionResponse> for StripeSubscriptionResponse {
+    fn from(res: api_models::subscriptions::SubscriptionResponse) -> Self {
+        Self {
+            id: res.subscription_id,
+            customer: res.customer_id,
+            plan: res.plan_id,
+            status: StripeSubscriptionStatus::from(res.status),
+            current_period_start: res.current_period_start.assume_utc().unix_timestamp(),
+            current_period_end: res.current_period_end.assume_utc().unix_timestamp(),
+            cancel_at_period_end: res.cancel_at_period_end,
+            latest_invoice: res.latest_payment_id,
+        }
+    }
+}
+
+impl From<SubscriptionStatus> for StripeSubscriptionStatus {
+    fn from(status: SubscriptionStatus) -> Self {
+        match status {
+            SubscriptionStatus::Trialing => Self::Trialing,
+            SubscriptionStatus::Active => Self::Active,
+            SubscriptionStatus::PastDue => Self::PastDue,
+            SubscriptionStatus::Cancelled => Self::Canceled,
+        }
+    }
+}
+
 impl From<MandateStatus> for StripeMandateStatus {
     fn from(status: MandateStatus) -> Self {
         match status {
             MandateStatus::Active => Self::Active,
-            MandateStatus::Inactive | MandateStatus::Revoked => Self::Inactive,
+            MandateStatus::Inactive | MandateStatus::Revoked | MandateStatus::Expired => {
+                Self::Inactive
+            }
             MandateStatus::Pending => Self::Pending,
         }
     }
@@ -183,6 +233,12 @@ fn get_stripe_event_type(event_type: api_models::enums::EventType) -> &'static s
         api_models::enums::EventType::DisputeLost => "dispute.lost",
         api_models::enums::EventType::MandateActive => "mandate.active",
         api_models::enums::EventType::MandateRevoked => "mandate.revoked",
+        api_models::enums::EventType::MandateExpired => "mandate.expired",
+        api_models::enums::EventType::SubscriptionCreated => "customer.subscription.created",
+        api_models::enums::EventType::SubscriptionRenewed => "invoice.paid",
+        api_models::enums::EventType::SubscriptionPaymentFailed => "invoice.payment_failed",
+        api_models::enums::EventType::SubscriptionUpdated => "customer.subscription.updated",
+        api_models::enums::EventType::SubscriptionCancelled => "customer.subscription.deleted",
     }
 }
 
@@ -223,6 +279,9 @@ impl From<api::OutgoingWebhookContent> for StripeWebhookObject {
             api::OutgoingWebhookContent::MandateDetails(mandate) => {
                 Self::Mandate((*mandate).into())
             }
+            api::OutgoingWebhookContent::SubscriptionDetails(subscription) => {
+                Self::Subscription((*subscription).into())
+            }
         }
     }
 }
@@ -42,6 +42,7 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let mut idempotent_request = None;
     let res = match metrics::request::record_request_time_metric(
         api::server_wrap_util(
             &flow,
@@ -51,6 +52,7 @@ where
             func,
             api_authentication,
             lock_action,
+            &mut idempotent_request,
         ),
         &flow,
     )
@@ -146,8 +148,32 @@ where
             }
         }
 
+        Ok(api::ApplicationResponse::Html { template, context }) => {
+            match api::render_html(template, context) {
+                Ok(rendered_html) => api::http_response_html_data(rendered_html),
+                Err(_) => api::http_response_err(
+                    r#"{
+                        "error": {
+                            "message": "Error while rendering html page"
+                        }
+                    }"#,
+                ),
+            }
+        }
+
+        Ok(api::ApplicationResponse::IdempotentReplay(response)) => match response.replay() {
+            Ok(res) => res,
+            Err(error) => {
+                let switched_error = error.current_context().switch();
+                api::log_and_return_error_response(error.change_context(switched_error))
+            }
+        },
         Err(error) => api::log_and_return_error_response(error),
     };
+    let res = match idempotent_request {
+        Some(idempotent_request) => idempotent_request.record_response(res).await,
+        None => res,
+    };
 
     let response_code = res.status().as_u16();
     let end_instant = Instant::now();
@@ -1,5 +1,4 @@
 mod defaults;
-#[cfg(feature = "kms")]
-pub mod kms;
+pub mod secrets_management;
 pub mod settings;
 mod validations;
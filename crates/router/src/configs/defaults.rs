@@ -1,8 +1,6 @@
 use std::collections::{HashMap, HashSet};
 
 use api_models::{enums, payment_methods::RequiredFieldInfo};
-#[cfg(feature = "kms")]
-use external_services::kms::KmsValue;
 
 use super::settings::{ConnectorFields, Password, PaymentMethodType, RequiredFieldFinal};
 
@@ -44,6 +42,19 @@ impl Default for super::settings::Proxy {
     }
 }
 
+impl Default for super::settings::Secrets {
+    fn default() -> Self {
+        Self {
+            jwt_secret: Password::default(),
+            admin_api_key: Password::default(),
+            recon_admin_api_key: Password::default(),
+            master_enc_key: Password::default(),
+            master_enc_key_version: crate::types::domain::types::INITIAL_KEY_VERSION,
+            other_master_enc_keys: Vec::new(),
+        }
+    }
+}
+
 impl Default for super::settings::Locker {
     fn default() -> Self {
         Self {
@@ -84,6 +95,38 @@ impl Default for super::settings::Refund {
     }
 }
 
+impl Default for super::settings::PaymentLink {
+    fn default() -> Self {
+        Self {
+            sdk_url: String::new(),
+            // 1 day
+            default_expiry_in_seconds: 24 * 60 * 60,
+        }
+    }
+}
+
+impl Default for super::settings::InvoiceSettings {
+    fn default() -> Self {
+        Self {
+            // 3 days
+            reminder_interval_in_seconds: 3 * 24 * 60 * 60,
+            max_reminders: 3,
+            // 30 days
+            payment_window_after_due_in_seconds: 30 * 24 * 60 * 60,
+        }
+    }
+}
+
+impl Default for super::settings::SubscriptionSettings {
+    fn default() -> Self {
+        Self {
+            // 3 days
+            dunning_retry_interval_in_seconds: 3 * 24 * 60 * 60,
+            max_dunning_attempts: 4,
+        }
+    }
+}
+
 impl Default for super::settings::EphemeralConfig {
     fn default() -> Self {
         Self { validity: 1 }
@@ -110,6 +153,44 @@ impl Default for super::settings::KvConfig {
     }
 }
 
+impl Default for super::settings::Idempotency {
+    fn default() -> Self {
+        Self {
+            enabled: true,
+            // 24 hours
+            key_ttl_in_seconds: 86400,
+            processing_ttl_in_seconds: 300,
+        }
+    }
+}
+
+impl Default for super::settings::UserSettings {
+    fn default() -> Self {
+        Self {
+            // 1 hour
+            access_token_ttl_in_seconds: 3600,
+            // 7 days
+            refresh_token_ttl_in_seconds: 604800,
+            // 24 hours
+            email_token_ttl_in_seconds: 86400,
+        }
+    }
+}
+
+impl Default for super::settings::JwtSettings {
+    fn default() -> Self {
+        Self {
+            algorithms: vec![jsonwebtoken::Algorithm::HS256],
+            jwks: None,
+            jwks_file: None,
+            // 5 minutes
+            jwks_reload_interval_in_seconds: 300,
+            issuers: None,
+            audiences: None,
+        }
+    }
+}
+
 use super::settings::{
     Mandates, SupportedConnectorsForMandate, SupportedPaymentMethodTypesForMandate,
     SupportedPaymentMethodsForMandate,
@@ -4220,17 +4301,71 @@ impl Default for super::settings::RequiredFields {
 impl Default for super::settings::ApiKeys {
     fn default() -> Self {
         Self {
-            #[cfg(feature = "kms")]
-            kms_encrypted_hash_key: KmsValue::default(),
-
             /// Hex-encoded 32-byte long (64 characters long when hex-encoded) key used for calculating
             /// hashes of API keys
-            #[cfg(not(feature = "kms"))]
-            hash_key: String::new(),
+            hash_key: Password::default(),
 
             // Specifies the number of days before API key expiry when email reminders should be sent
             #[cfg(feature = "email")]
             expiry_reminder_days: vec![7, 3, 1],
+
+            // One day
+            rotation_grace_period_in_seconds: 24 * 60 * 60,
+
+            // 30 days
+            max_rotation_grace_period_in_seconds: 30 * 24 * 60 * 60,
+
+            trusted_proxies: Vec::new(),
+        }
+    }
+}
+
+impl Default for super::settings::FileUploadConfig {
+    fn default() -> Self {
+        Self {
+            // 10 MiB
+            max_file_size_in_bytes: 10 * 1024 * 1024,
+            allowed_content_types: vec![
+                "image/jpeg".to_string(),
+                "image/png".to_string(),
+                "application/pdf".to_string(),
+            ],
+            // 15 minutes
+            download_url_expiry_in_seconds: 15 * 60,
+            region: None,
+            bucket_name: None,
+        }
+    }
+}
+
+impl Default for super::settings::KafkaSettings {
+    fn default() -> Self {
+        Self {
+            brokers: Vec::new(),
+            topics: super::settings::EventTopics::default(),
+            message_timeout_in_milliseconds: 5000,
+        }
+    }
+}
+
+impl Default for super::settings::EventTopics {
+    fn default() -> Self {
+        Self {
+            payment_intent: "hyperswitch-payment-intent-events".into(),
+            payment_attempt: "hyperswitch-payment-attempt-events".into(),
+            refund: "hyperswitch-refund-events".into(),
+            api_logs: "hyperswitch-api-log-events".into(),
+            audit_event: "hyperswitch-audit-events".into(),
+        }
+    }
+}
+
+impl Default for super::settings::EventBufferSettings {
+    fn default() -> Self {
+        Self {
+            capacity: 10000,
+            batch_size: 100,
+            flush_interval_in_milliseconds: 1000,
         }
     }
 }
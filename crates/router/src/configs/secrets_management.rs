@@ -0,0 +1,75 @@
+use common_utils::errors::CustomResult;
+use external_services::secrets_management::{
+    SecretManagementInterface, SecretsDecrypt, SecretsManagementError,
+};
+use masking::ExposeInterface;
+
+use crate::configs::settings;
+
+#[async_trait::async_trait]
+impl SecretsDecrypt for settings::Jwekey {
+    type Output = Self;
+
+    async fn decrypt_inner(
+        mut self,
+        secrets_manager: &dyn SecretManagementInterface,
+    ) -> CustomResult<Self::Output, SecretsManagementError> {
+        (
+            self.locker_encryption_key1,
+            self.locker_encryption_key2,
+            self.locker_decryption_key1,
+            self.locker_decryption_key2,
+            self.vault_encryption_key,
+            self.vault_private_key,
+            self.tunnel_private_key,
+        ) = tokio::try_join!(
+            secrets_manager.decrypt(&self.locker_encryption_key1),
+            secrets_manager.decrypt(&self.locker_encryption_key2),
+            secrets_manager.decrypt(&self.locker_decryption_key1),
+            secrets_manager.decrypt(&self.locker_decryption_key2),
+            secrets_manager.decrypt(&self.vault_encryption_key),
+            secrets_manager.decrypt(&self.vault_private_key),
+            secrets_manager.decrypt(&self.tunnel_private_key),
+        )?;
+        Ok(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretsDecrypt for settings::ActiveSecrets {
+    type Output = Self;
+    async fn decrypt_inner(
+        mut self,
+        secrets_manager: &dyn SecretManagementInterface,
+    ) -> CustomResult<Self::Output, SecretsManagementError> {
+        self.jwekey = self
+            .jwekey
+            .expose()
+            .decrypt_inner(secrets_manager)
+            .await?
+            .into();
+        Ok(self)
+    }
+}
+
+#[async_trait::async_trait]
+impl SecretsDecrypt for settings::Database {
+    type Output = storage_impl::config::Database;
+
+    async fn decrypt_inner(
+        self,
+        secrets_manager: &dyn SecretManagementInterface,
+    ) -> CustomResult<Self::Output, SecretsManagementError> {
+        Ok(storage_impl::config::Database {
+            host: self.host,
+            port: self.port,
+            dbname: self.dbname,
+            username: self.username,
+            password: self.password.decrypt_inner(secrets_manager).await?.into(),
+            pool_size: self.pool_size,
+            connection_timeout: self.connection_timeout,
+            queue_strategy: self.queue_strategy.into(),
+            schema: None,
+        })
+    }
+}
@@ -9,8 +9,10 @@ use common_utils::ext_traits::ConfigExt;
 use config::{Environment, File};
 #[cfg(feature = "email")]
 use external_services::email::EmailSettings;
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::{
+    file_storage::FileStorageConfig,
+    secrets_management::{SecretValue, SecretsManagementConfig},
+};
 use redis_interface::RedisSettings;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use scheduler::SchedulerSettings;
@@ -20,10 +22,7 @@ use crate::{
     core::errors::{ApplicationError, ApplicationResult},
     env::{self, logger, Env},
 };
-#[cfg(feature = "kms")]
-pub type Password = kms::KmsValue;
-#[cfg(not(feature = "kms"))]
-pub type Password = masking::Secret<String>;
+pub type Password = SecretValue;
 
 #[derive(clap::Parser, Default)]
 #[cfg_attr(feature = "vergen", command(version = router_env::version!()))]
@@ -42,15 +41,22 @@ pub enum Subcommand {
     #[cfg(feature = "openapi")]
     /// Generate the OpenAPI specification file from code.
     GenerateOpenapiSpec,
+    /// Re-encrypt the keys of all merchants, of the deployment and of each configured tenant, with
+    /// the master key of the specified version.
+    RotateMasterKey {
+        /// Version of the master key to re-encrypt the keys of merchants with, which must be
+        /// configured in `[secrets]`.
+        #[arg(long)]
+        target_version: i32,
+    },
 }
 
-#[cfg(feature = "kms")]
-/// Store the decrypted kms secret values for active use in the application
+/// Store the decrypted secret values for active use in the application
 /// Currently using `StrongSecret` won't have any effect as this struct have smart pointers to heap
 /// allocations.
 /// note: we can consider adding such behaviour in the future with custom implementation
 #[derive(Clone)]
-pub struct ActiveKmsSecrets {
+pub struct ActiveSecrets {
     pub jwekey: masking::Secret<Jwekey>,
 }
 
@@ -63,6 +69,8 @@ pub struct Settings {
     pub master_database: Database,
     #[cfg(feature = "olap")]
     pub replica_database: Database,
+    #[cfg(feature = "olap")]
+    pub replica_read: ReplicaReadSettings,
     pub redis: RedisSettings,
     pub log: Log,
     pub secrets: Secrets,
@@ -78,9 +86,8 @@ pub struct Settings {
     pub pm_filters: ConnectorFilters,
     pub bank_config: BankRedirectConfig,
     pub api_keys: ApiKeys,
-    #[cfg(feature = "kms")]
-    pub kms: kms::KmsConfig,
-    #[cfg(feature = "s3")]
+    pub secrets_management: SecretsManagementConfig,
+    pub file_storage: FileStorageConfig,
     pub file_upload_config: FileUploadConfig,
     pub tokenization: TokenizationConfig,
     pub connector_customer: ConnectorCustomer,
@@ -101,8 +108,168 @@ pub struct Settings {
     pub lock_settings: LockSettings,
     pub temp_locker_enable_config: TempLockerEnableConfig,
     pub payment_link: PaymentLink,
+    pub invoice: InvoiceSettings,
+    pub subscription: SubscriptionSettings,
     #[cfg(feature = "kv_store")]
     pub kv_config: KvConfig,
+    pub multitenancy: Multitenancy,
+    pub idempotency: Idempotency,
+    pub rate_limit: RateLimitSettings,
+    pub user: UserSettings,
+    pub jwt: JwtSettings,
+    pub events: EventsConfig,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct EventsConfig {
+    /// Where the events raised by the application are published to.
+    pub source: EventSource,
+    pub kafka: KafkaSettings,
+    pub file: FileEventSinkSettings,
+    pub buffer: EventBufferSettings,
+}
+
+#[derive(Debug, Deserialize, Clone, Copy, Default, Eq, PartialEq)]
+#[serde(rename_all = "snake_case")]
+pub enum EventSource {
+    /// Events are written to the application logs.
+    #[default]
+    Logs,
+    /// Events are produced to Kafka (or a Kafka-compatible broker). Requires the `kafka_events`
+    /// feature.
+    Kafka,
+    /// Events are appended to a newline delimited JSON file, for local testing.
+    File,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct KafkaSettings {
+    pub brokers: Vec<String>,
+    pub topics: EventTopics,
+    /// The time (in milliseconds) within which an event must be delivered, before it is
+    /// considered to have failed to be delivered.
+    pub message_timeout_in_milliseconds: u64,
+}
+
+/// The topics that each type of event is produced to.
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EventTopics {
+    pub payment_intent: String,
+    pub payment_attempt: String,
+    pub refund: String,
+    pub api_logs: String,
+    pub audit_event: String,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct FileEventSinkSettings {
+    /// The path of the file that events are appended to.
+    pub path: PathBuf,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct EventBufferSettings {
+    /// The maximum number of events buffered before being published. Events raised while the
+    /// buffer is full are dropped.
+    pub capacity: usize,
+    /// The maximum number of events published together.
+    pub batch_size: usize,
+    /// The interval (in milliseconds) after which buffered events are published, even if fewer
+    /// than the batch size have been buffered.
+    pub flush_interval_in_milliseconds: u64,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct JwtSettings {
+    /// The signing algorithms that JWTs are accepted with. Tokens signed with HMAC algorithms are
+    /// issued by the router and verified with the JWT secret. Tokens signed with RSA or ECDSA
+    /// algorithms are issued by the identity provider and verified against the JWKS, the user
+    /// is identified by their `email` claim and takes the role recorded for them.
+    pub algorithms: Vec<jsonwebtoken::Algorithm>,
+    /// The JWKS document to verify asymmetrically signed JWTs against.
+    pub jwks: Option<String>,
+    /// The path of a file containing the JWKS document to verify asymmetrically signed JWTs
+    /// against, which is reloaded periodically to pick up rotated keys.
+    pub jwks_file: Option<PathBuf>,
+    /// The interval (in seconds) after which the JWKS file is reloaded.
+    pub jwks_reload_interval_in_seconds: u64,
+    /// The issuers accepted in the `iss` claim of tokens verified against the JWKS. The issuer
+    /// is not validated when unset.
+    pub issuers: Option<Vec<String>>,
+    /// The audiences accepted in the `aud` claim of tokens verified against the JWKS. The
+    /// audience is not validated when unset.
+    pub audiences: Option<Vec<String>>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct UserSettings {
+    /// The duration (in seconds) for which the access tokens issued to dashboard users are valid.
+    pub access_token_ttl_in_seconds: u32,
+    /// The duration (in seconds) for which the refresh tokens issued to dashboard users are
+    /// valid.
+    pub refresh_token_ttl_in_seconds: u32,
+    /// The duration (in seconds) for which the tokens sent in invitation and password reset
+    /// emails are valid.
+    pub email_token_ttl_in_seconds: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct RateLimitSettings {
+    /// Whether requests of merchants are rate limited.
+    pub enabled: bool,
+    /// The limit on all requests of a merchant, unless overridden for the merchant.
+    pub default_merchant_limit: Option<api_models::admin::RateLimit>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct Idempotency {
+    /// Whether the `Idempotency-Key` header is honoured on POST requests.
+    pub enabled: bool,
+    /// The duration (in seconds) for which the response of a request is replayed to retries
+    /// carrying the same idempotency key.
+    pub key_ttl_in_seconds: u32,
+    /// The duration (in seconds) after which a request that never completed no longer blocks
+    /// retries carrying the same idempotency key.
+    pub processing_ttl_in_seconds: u32,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Multitenancy {
+    /// Whether requests are served for the configured tenants. Each request must then identify
+    /// its tenant, either with the host it is received on or, for tenants without a host, with the
+    /// tenant ID header.
+    pub enabled: bool,
+    /// The tenants served by this deployment, keyed by tenant ID.
+    pub tenants: HashMap<String, Tenant>,
+}
+
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct Tenant {
+    /// The host the requests of the tenant are received on, used to resolve the tenant of its
+    /// requests. Tenants without a host are selected with the tenant ID header instead.
+    pub host: Option<String>,
+    /// The Postgres schema holding the tables of the tenant.
+    pub schema: String,
+    /// The database holding the schema of the tenant, if not the configured database.
+    pub dbname: Option<String>,
+    /// The prefix of the Redis keys of the tenant.
+    pub redis_key_prefix: String,
+    /// The admin API key of the tenant. Encrypted with the configured secrets manager.
+    pub admin_api_key: Option<Password>,
+    /// Whether admin APIs of a tenant without an admin API key of its own accept the admin API
+    /// key of the deployment. Admin requests of such tenants are rejected otherwise.
+    pub accept_deployment_admin_api_key: bool,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -110,9 +277,36 @@ pub struct KvConfig {
     pub ttl: u32,
 }
 
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
 pub struct PaymentLink {
     pub sdk_url: String,
+    /// The number of seconds after its creation that a payment link expires, when neither the
+    /// payment link nor the merchant configure its expiry
+    pub default_expiry_in_seconds: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct InvoiceSettings {
+    /// The number of seconds between the reminders sent to customers of unpaid invoices, the first
+    /// reminder being sent this long before the invoice is due
+    pub reminder_interval_in_seconds: u32,
+    /// The number of reminders sent for an invoice that remains unpaid
+    pub max_reminders: u8,
+    /// The number of seconds after its due date that an invoice can still be paid, after which
+    /// its payment link expires
+    pub payment_window_after_due_in_seconds: u32,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+#[serde(default)]
+pub struct SubscriptionSettings {
+    /// The number of seconds after which a failed subscription payment is retried
+    pub dunning_retry_interval_in_seconds: u32,
+    /// The number of failed payments for a billing period after which the subscription is
+    /// cancelled
+    pub max_dunning_attempts: u8,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -394,22 +588,24 @@ where
         .collect())
 }
 
-#[derive(Debug, Default, Deserialize, Clone)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Secrets {
-    #[cfg(not(feature = "kms"))]
-    pub jwt_secret: String,
-    #[cfg(not(feature = "kms"))]
-    pub admin_api_key: String,
-    #[cfg(not(feature = "kms"))]
-    pub recon_admin_api_key: String,
+    pub jwt_secret: Password,
+    pub admin_api_key: Password,
+    pub recon_admin_api_key: Password,
     pub master_enc_key: Password,
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_jwt_secret: kms::KmsValue,
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_admin_api_key: kms::KmsValue,
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_recon_admin_api_key: kms::KmsValue,
+    /// Version of `master_enc_key`, incremented on every rotation of the master key.
+    pub master_enc_key_version: i32,
+    /// Other versions of the master key, which merchant keys may be encrypted with while the
+    /// master key is being rotated.
+    pub other_master_enc_keys: Vec<MasterEncKey>,
+}
+
+#[derive(Debug, Deserialize, Clone)]
+pub struct MasterEncKey {
+    pub version: i32,
+    pub key: Password,
 }
 
 #[derive(Debug, Deserialize, Clone)]
@@ -421,6 +617,16 @@ pub struct Locker {
     pub locker_signing_key_id: String,
 }
 
+#[cfg(feature = "olap")]
+#[derive(Debug, Deserialize, Clone, Default)]
+#[serde(default)]
+pub struct ReplicaReadSettings {
+    /// Maximum replication lag (in seconds) tolerated for reads served by the replica database.
+    /// Reads fall back to the master database when the replica lags further behind. The replica
+    /// lag is not checked when unset.
+    pub max_staleness: Option<u64>,
+}
+
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct Refund {
@@ -497,22 +703,6 @@ impl From<QueueStrategy> for bb8::QueueStrategy {
     }
 }
 
-#[cfg(not(feature = "kms"))]
-impl From<Database> for storage_impl::config::Database {
-    fn from(val: Database) -> Self {
-        Self {
-            username: val.username,
-            password: val.password,
-            host: val.host,
-            port: val.port,
-            dbname: val.dbname,
-            pool_size: val.pool_size,
-            connection_timeout: val.connection_timeout,
-            queue_strategy: val.queue_strategy.into(),
-        }
-    }
-}
-
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct SupportedConnectors {
@@ -644,29 +834,46 @@ pub struct WebhookIgnoreErrorSettings {
 #[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct ApiKeys {
-    /// Base64-encoded (KMS encrypted) ciphertext of the key used for calculating hashes of API
-    /// keys
-    #[cfg(feature = "kms")]
-    pub kms_encrypted_hash_key: kms::KmsValue,
-
     /// Hex-encoded 32-byte long (64 characters long when hex-encoded) key used for calculating
-    /// hashes of API keys
-    #[cfg(not(feature = "kms"))]
-    pub hash_key: String,
+    /// hashes of API keys, encrypted with the configured secrets manager
+    pub hash_key: Password,
 
     // Specifies the number of days before API key expiry when email reminders should be sent
     #[cfg(feature = "email")]
     pub expiry_reminder_days: Vec<u8>,
+
+    /// The number of seconds for which a rotated API key remains valid, unless specified when
+    /// rotating it
+    pub rotation_grace_period_in_seconds: u32,
+
+    /// The maximum number of seconds for which a rotated API key may remain valid
+    pub max_rotation_grace_period_in_seconds: u32,
+
+    /// The IP addresses or CIDR blocks of the proxies in front of the router, whose
+    /// `X-Forwarded-For` header is trusted to determine the IP address requests originate from
+    pub trusted_proxies: Vec<String>,
 }
 
-#[cfg(feature = "s3")]
-#[derive(Debug, Deserialize, Clone, Default)]
+#[derive(Debug, Deserialize, Clone)]
 #[serde(default)]
 pub struct FileUploadConfig {
-    /// The AWS region to send file uploads
-    pub region: String,
-    /// The AWS s3 bucket to send file uploads
-    pub bucket_name: String,
+    /// The maximum size of the files stored by the router, in bytes
+    pub max_file_size_in_bytes: i32,
+
+    /// The content types of the files stored by the router
+    pub allowed_content_types: Vec<String>,
+
+    /// The number of seconds for which the pre-signed download URLs of files are valid
+    pub download_url_expiry_in_seconds: u64,
+
+    /// The AWS region of the S3 bucket that files were stored in before their backend was
+    /// configured under `[file_storage]`. Only checked for, so that such configurations are not
+    /// silently switched to storing files on the local file system.
+    pub region: Option<String>,
+
+    /// The S3 bucket that files were stored in before their backend was configured under
+    /// `[file_storage]`. Only checked for, like `region`.
+    pub bucket_name: Option<String>,
 }
 
 #[derive(Debug, Deserialize, Clone, Default)]
@@ -786,13 +993,24 @@ impl Settings {
         #[cfg(feature = "kv_store")]
         self.drainer.validate()?;
         self.api_keys.validate()?;
-        #[cfg(feature = "kms")]
-        self.kms
+        self.secrets_management
+            .validate()
+            .map_err(|error| ApplicationError::InvalidConfigurationValueError(error.into()))?;
+        self.file_storage
+            .validate()
+            .map_err(|error| ApplicationError::InvalidConfigurationValueError(error.into()))?;
+        #[cfg(feature = "email")]
+        self.email
             .validate()
             .map_err(|error| ApplicationError::InvalidConfigurationValueError(error.into()))?;
-        #[cfg(feature = "s3")]
-        self.file_upload_config.validate()?;
+        self.file_upload_config.validate(&self.file_storage)?;
         self.lock_settings.validate()?;
+        self.multitenancy.validate()?;
+        self.idempotency.validate()?;
+        self.rate_limit.validate()?;
+        self.user.validate()?;
+        self.jwt.validate()?;
+        self.events.validate()?;
         Ok(())
     }
 }
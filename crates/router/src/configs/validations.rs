@@ -5,43 +5,45 @@ impl super::settings::Secrets {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
 
-        #[cfg(not(feature = "kms"))]
-        {
-            when(self.jwt_secret.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "JWT secret must not be empty".into(),
-                ))
-            })?;
-
-            when(self.admin_api_key.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "admin API key must not be empty".into(),
-                ))
-            })?;
-        }
+        when(self.jwt_secret.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "JWT secret must not be empty".into(),
+            ))
+        })?;
 
-        #[cfg(feature = "kms")]
-        {
-            when(self.kms_encrypted_jwt_secret.is_default_or_empty(), || {
-                Err(ApplicationError::InvalidConfigurationValueError(
-                    "KMS encrypted JWT secret must not be empty".into(),
-                ))
-            })?;
+        when(self.admin_api_key.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "admin API key must not be empty".into(),
+            ))
+        })?;
 
-            when(
-                self.kms_encrypted_admin_api_key.is_default_or_empty(),
-                || {
-                    Err(ApplicationError::InvalidConfigurationValueError(
-                        "KMS encrypted admin API key must not be empty".into(),
-                    ))
-                },
-            )?;
-        }
         when(self.master_enc_key.is_default_or_empty(), || {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "Master encryption key must not be empty".into(),
             ))
-        })
+        })?;
+
+        let mut master_enc_key_versions =
+            std::collections::HashSet::from([self.master_enc_key_version]);
+        self.other_master_enc_keys
+            .iter()
+            .try_for_each(|master_enc_key| {
+                when(master_enc_key.key.is_default_or_empty(), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(
+                        "Other master encryption keys must not be empty".into(),
+                    ))
+                })?;
+
+                when(
+                    !master_enc_key_versions.insert(master_enc_key.version),
+                    || {
+                        Err(ApplicationError::InvalidConfigurationValueError(format!(
+                            "Master encryption key version {} is configured more than once",
+                            master_enc_key.version
+                        )))
+                    },
+                )
+            })
     }
 }
 
@@ -127,22 +129,46 @@ impl super::settings::DrainerSettings {
     }
 }
 
-#[cfg(feature = "s3")]
 impl super::settings::FileUploadConfig {
-    pub fn validate(&self) -> Result<(), ApplicationError> {
+    pub fn validate(
+        &self,
+        file_storage: &external_services::file_storage::FileStorageConfig,
+    ) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
 
-        when(self.region.is_default_or_empty(), || {
+        when(
+            (self.region.is_some() || self.bucket_name.is_some()) && file_storage.backend.is_none(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "file upload region and bucket name are no longer supported, the file storage \
+                     backend must be selected and configured under `file_storage` instead"
+                        .into(),
+                ))
+            },
+        )?;
+
+        when(self.max_file_size_in_bytes <= 0, || {
             Err(ApplicationError::InvalidConfigurationValueError(
-                "s3 region must not be empty".into(),
+                "maximum file size must be greater than zero".into(),
             ))
         })?;
 
-        when(self.bucket_name.is_default_or_empty(), || {
+        when(self.allowed_content_types.is_empty(), || {
             Err(ApplicationError::InvalidConfigurationValueError(
-                "s3 bucket name must not be empty".into(),
+                "allowed file content types must not be empty".into(),
             ))
-        })
+        })?;
+
+        // Pre-signed URLs of S3 are valid for at most 7 days
+        when(
+            self.download_url_expiry_in_seconds == 0
+                || self.download_url_expiry_in_seconds > 7 * 24 * 60 * 60,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "file download URL expiry must be between 1 second and 7 days".into(),
+                ))
+            },
+        )
     }
 }
 
@@ -150,15 +176,27 @@ impl super::settings::ApiKeys {
     pub fn validate(&self) -> Result<(), ApplicationError> {
         use common_utils::fp_utils::when;
 
-        #[cfg(feature = "kms")]
-        return when(self.kms_encrypted_hash_key.is_default_or_empty(), || {
-            Err(ApplicationError::InvalidConfigurationValueError(
-                "API key hashing key must not be empty when KMS feature is enabled".into(),
-            ))
-        });
+        when(
+            self.rotation_grace_period_in_seconds > self.max_rotation_grace_period_in_seconds,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "API key rotation grace period must not exceed the maximum grace period".into(),
+                ))
+            },
+        )?;
 
-        #[cfg(not(feature = "kms"))]
-        when(self.hash_key.is_empty(), || {
+        when(
+            self.trusted_proxies.iter().any(|proxy| {
+                <crate::core::api_keys::IpNetwork as std::str::FromStr>::from_str(proxy).is_err()
+            }),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "API key trusted proxies must be IP addresses or CIDR blocks".into(),
+                ))
+            },
+        )?;
+
+        when(self.hash_key.is_default_or_empty(), || {
             Err(ApplicationError::InvalidConfigurationValueError(
                 "API key hashing key must not be empty".into(),
             ))
@@ -193,3 +231,294 @@ impl super::settings::LockSettings {
         })
     }
 }
+
+impl super::settings::Idempotency {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.enabled && self.key_ttl_in_seconds.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "idempotency key_ttl_in_seconds must not be 0".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.enabled && self.processing_ttl_in_seconds.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "idempotency processing_ttl_in_seconds must not be 0".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::RateLimitSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.default_merchant_limit
+                .map_or(false, |limit| limit.window_in_seconds == 0),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "rate limit window_in_seconds must not be 0".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::UserSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(
+            self.access_token_ttl_in_seconds.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "user access_token_ttl_in_seconds must not be 0".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.refresh_token_ttl_in_seconds < self.access_token_ttl_in_seconds,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "user refresh_token_ttl_in_seconds must not be less than access_token_ttl_in_seconds".into(),
+                ))
+            },
+        )?;
+
+        when(
+            self.email_token_ttl_in_seconds.is_default_or_empty(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "user email_token_ttl_in_seconds must not be 0".into(),
+                ))
+            },
+        )
+    }
+}
+
+impl super::settings::JwtSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+        use jsonwebtoken::Algorithm;
+
+        when(self.algorithms.is_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "jwt algorithms must not be empty".into(),
+            ))
+        })?;
+
+        when(self.jwks.is_some() && self.jwks_file.is_some(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "only one of jwt jwks and jwks_file must be set".into(),
+            ))
+        })?;
+
+        let has_asymmetric_algorithm = self.algorithms.iter().any(|algorithm| {
+            !matches!(
+                algorithm,
+                Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+            )
+        });
+        when(
+            has_asymmetric_algorithm && self.jwks.is_none() && self.jwks_file.is_none(),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "jwt jwks or jwks_file must be set when RSA or ECDSA algorithms are accepted"
+                        .into(),
+                ))
+            },
+        )?;
+
+        if let Some(jwks) = &self.jwks {
+            serde_json::from_str::<jsonwebtoken::jwk::JwkSet>(jwks).map_err(|error| {
+                ApplicationError::InvalidConfigurationValueError(format!(
+                    "jwt jwks is not a valid JWKS document: {error}"
+                ))
+            })?;
+        }
+
+        when(self.jwks_reload_interval_in_seconds == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "jwt jwks_reload_interval_in_seconds must not be 0".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::Multitenancy {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use std::collections::HashSet;
+
+        use common_utils::fp_utils::when;
+
+        when(self.enabled && self.tenants.is_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "at least one tenant must be configured when multitenancy is enabled".into(),
+            ))
+        })?;
+
+        let mut redis_key_prefixes = HashSet::new();
+        let mut hosts = HashSet::new();
+        for (tenant_id, tenant) in &self.tenants {
+            tenant.validate(tenant_id)?;
+
+            when(
+                !redis_key_prefixes.insert(tenant.redis_key_prefix.as_str()),
+                || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "redis key prefix of tenant {tenant_id} is used by another tenant"
+                    )))
+                },
+            )?;
+
+            when(
+                tenant
+                    .host
+                    .as_deref()
+                    .map_or(false, |host| !hosts.insert(host)),
+                || {
+                    Err(ApplicationError::InvalidConfigurationValueError(format!(
+                        "host of tenant {tenant_id} is used by another tenant"
+                    )))
+                },
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+impl super::settings::Tenant {
+    pub fn validate(&self, tenant_id: &str) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        // The schema is set as the search path of the connections of the tenant, hence it is
+        // restricted to unquoted Postgres identifiers.
+        let is_valid_schema = self
+            .schema
+            .chars()
+            .next()
+            .map_or(false, |first| first.is_ascii_lowercase() || first == '_')
+            && self
+                .schema
+                .chars()
+                .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '_');
+
+        when(!is_valid_schema, || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "schema of tenant {tenant_id} must be a lowercase Postgres identifier"
+            )))
+        })?;
+
+        when(self.redis_key_prefix.is_default_or_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(format!(
+                "redis key prefix of tenant {tenant_id} must not be empty"
+            )))
+        })?;
+
+        when(
+            self.admin_api_key.is_some() && self.accept_deployment_admin_api_key,
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(format!(
+                    "tenant {tenant_id} with an admin API key must not accept the admin API key of the deployment"
+                )))
+            },
+        )
+    }
+}
+
+impl super::settings::EventsConfig {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        use super::settings::EventSource;
+
+        match self.source {
+            EventSource::Logs => return Ok(()),
+            #[cfg(feature = "kafka_events")]
+            EventSource::Kafka => self.kafka.validate()?,
+            #[cfg(not(feature = "kafka_events"))]
+            EventSource::Kafka => return Err(ApplicationError::InvalidConfigurationValueError(
+                "events source kafka requires the router to be built with the kafka_events feature"
+                    .into(),
+            )),
+            EventSource::File => {
+                when(self.file.path.as_os_str().is_empty(), || {
+                    Err(ApplicationError::InvalidConfigurationValueError(
+                        "events file path must not be empty".into(),
+                    ))
+                })?;
+            }
+        }
+
+        self.buffer.validate()
+    }
+}
+
+impl super::settings::KafkaSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.brokers.is_empty(), || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "events kafka brokers must not be empty".into(),
+            ))
+        })?;
+
+        when(
+            [
+                &self.topics.payment_intent,
+                &self.topics.payment_attempt,
+                &self.topics.refund,
+                &self.topics.api_logs,
+                &self.topics.audit_event,
+            ]
+            .iter()
+            .any(|topic| topic.is_default_or_empty()),
+            || {
+                Err(ApplicationError::InvalidConfigurationValueError(
+                    "events kafka topics must not be empty".into(),
+                ))
+            },
+        )?;
+
+        when(self.message_timeout_in_milliseconds == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "events kafka message_timeout_in_milliseconds must not be 0".into(),
+            ))
+        })
+    }
+}
+
+impl super::settings::EventBufferSettings {
+    pub fn validate(&self) -> Result<(), ApplicationError> {
+        use common_utils::fp_utils::when;
+
+        when(self.batch_size == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "events buffer batch_size must not be 0".into(),
+            ))
+        })?;
+
+        when(self.capacity < self.batch_size, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "events buffer capacity must not be less than the batch size".into(),
+            ))
+        })?;
+
+        when(self.flush_interval_in_milliseconds == 0, || {
+            Err(ApplicationError::InvalidConfigurationValueError(
+                "events buffer flush_interval_in_milliseconds must not be 0".into(),
+            ))
+        })
+    }
+}
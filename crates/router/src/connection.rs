@@ -49,6 +49,16 @@ pub async fn pg_connection_read<T: storage_impl::DatabaseStore>(
         .change_context(storage_errors::StorageError::DatabaseConnectionError)
 }
 
+pub async fn pg_connection_read_with_consistency<T: storage_impl::DatabaseStore>(
+    store: &T,
+    consistency: storage_impl::ReadConsistency,
+) -> errors::CustomResult<
+    PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>>,
+    storage_errors::StorageError,
+> {
+    storage_impl::connection::pg_connection_read_with_consistency(store, consistency).await
+}
+
 pub async fn pg_connection_write<T: storage_impl::DatabaseStore>(
     store: &T,
 ) -> errors::CustomResult<
@@ -1,6 +1,8 @@
 pub mod admin;
+pub mod analytics;
 pub mod api_keys;
 pub mod api_locking;
+pub mod audit_events;
 pub mod cache;
 pub mod cards_info;
 pub mod configs;
@@ -8,15 +10,25 @@ pub mod customers;
 pub mod disputes;
 pub mod errors;
 pub mod files;
+pub mod invoices;
 pub mod mandate;
+pub mod master_key_rotation;
+pub mod merchant_key_rotation;
 pub mod metrics;
+#[cfg(feature = "olap")]
+pub mod organization;
 pub mod payment_link;
 pub mod payment_methods;
 pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
+pub mod rate_limit;
 pub mod refunds;
+pub mod subscriptions;
+#[cfg(feature = "email")]
+pub mod transactional_emails;
+pub mod user;
 pub mod utils;
-#[cfg(all(feature = "olap", feature = "kms"))]
+#[cfg(feature = "olap")]
 pub mod verification;
 pub mod webhooks;
@@ -5,18 +5,21 @@ use api_models::{
 use common_utils::{
     crypto::{generate_cryptographically_secure_random_string, OptionalSecretValue},
     date_time,
-    ext_traits::{AsyncExt, ConfigExt, Encode, ValueExt},
+    ext_traits::{AsyncExt, ConfigExt, Encode, StringExt, ValueExt},
+    fp_utils::when,
     pii,
 };
-use error_stack::{report, FutureExt, ResultExt};
+use error_stack::{report, FutureExt, IntoReport, ResultExt};
 use futures::future::try_join_all;
-use masking::{PeekInterface, Secret};
+use masking::Secret;
 use uuid::Uuid;
 
 use crate::{
     consts,
     core::{
+        audit_events::{self, AuditRecord},
         errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payment_link,
         payments::helpers,
         utils as core_utils,
     },
@@ -49,7 +52,7 @@ pub async fn create_merchant_account(
     req: api::MerchantAccountCreate,
 ) -> RouterResponse<api::MerchantAccountResponse> {
     let db = state.store.as_ref();
-    let master_key = db.get_master_key();
+    let master_key_ring = db.get_master_key_ring();
 
     let key = services::generate_aes256_key()
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -100,12 +103,15 @@ pub async fn create_merchant_account(
 
     let key_store = domain::MerchantKeyStore {
         merchant_id: req.merchant_id.clone(),
-        key: domain_types::encrypt(key.to_vec().into(), master_key)
+        key: domain_types::encrypt(key.to_vec().into(), &master_key_ring)
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed to decrypt data from key store")?,
         created_at: date_time::now(),
+        key_version: domain_types::INITIAL_KEY_VERSION,
+        retired_keys: Vec::new(),
     };
+    let key_ring = key_store.key_ring();
 
     let enable_payment_response_hash = req.enable_payment_response_hash.unwrap_or(true);
 
@@ -113,7 +119,7 @@ pub async fn create_merchant_account(
         .payment_response_hash_key
         .or(Some(generate_cryptographically_secure_random_string(64)));
 
-    db.insert_merchant_key_store(key_store.clone(), &master_key.to_vec().into())
+    db.insert_merchant_key_store(key_store.clone(), &master_key_ring)
         .await
         .to_duplicate_response(errors::ApiErrorResponse::DuplicateMerchantAccount)?;
 
@@ -141,6 +147,7 @@ pub async fn create_merchant_account(
         .payment_link_config
         .as_ref()
         .map(|pl_metadata| {
+            payment_link::validate_payment_link_config(pl_metadata)?;
             utils::Encode::<admin_types::PaymentLinkConfig>::encode_to_value(pl_metadata)
                 .change_context(errors::ApiErrorResponse::InvalidDataValue {
                     field_name: "payment_link_config",
@@ -148,6 +155,18 @@ pub async fn create_merchant_account(
         })
         .transpose()?;
 
+    let email_config = req
+        .email_config
+        .as_ref()
+        .map(|email_config| {
+            validate_email_config(email_config)?;
+            utils::Encode::<admin_types::MerchantEmailConfig>::encode_to_value(email_config)
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "email_config",
+                })
+        })
+        .transpose()?;
+
     let organization_id = if let Some(organization_id) = req.organization_id.as_ref() {
         db.find_organization_by_org_id(organization_id)
             .await
@@ -171,10 +190,10 @@ pub async fn create_merchant_account(
             merchant_id: req.merchant_id,
             merchant_name: req
                 .merchant_name
-                .async_lift(|inner| domain_types::encrypt_optional(inner, &key))
+                .async_lift(|inner| domain_types::encrypt_optional(inner, &key_ring))
                 .await?,
             merchant_details: merchant_details
-                .async_lift(|inner| domain_types::encrypt_optional(inner, &key))
+                .async_lift(|inner| domain_types::encrypt_optional(inner, &key_ring))
                 .await?,
             return_url: req.return_url.map(|a| a.to_string()),
             webhook_details,
@@ -202,6 +221,7 @@ pub async fn create_merchant_account(
             default_profile: None,
             recon_status: diesel_models::enums::ReconStatus::NotRequested,
             payment_link_config,
+            email_config,
         })
     }
     .await
@@ -303,10 +323,7 @@ pub async fn get_merchant_account(
 ) -> RouterResponse<api::MerchantAccountResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(
-            &req.merchant_id,
-            &db.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(&req.merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -450,10 +467,7 @@ pub async fn merchant_account_update(
 ) -> RouterResponse<api::MerchantAccountResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(
-            &req.merchant_id,
-            &db.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(&req.merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -507,7 +521,7 @@ pub async fn merchant_account_update(
         })
         .await;
 
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
 
     let business_profile_id_update = if let Some(ref profile_id) = req.default_profile {
         if !profile_id.is_empty_after_trim() {
@@ -578,9 +592,40 @@ pub async fn merchant_account_update(
         intent_fulfillment_time: req.intent_fulfillment_time.map(i64::from),
         payout_routing_algorithm: req.payout_routing_algorithm,
         default_profile: business_profile_id_update,
-        payment_link_config: req.payment_link_config,
+        payment_link_config: req
+            .payment_link_config
+            .map(|pl_config| {
+                let payment_link_config = pl_config
+                    .clone()
+                    .parse_value::<admin_types::PaymentLinkConfig>("PaymentLinkConfig")
+                    .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                        field_name: "payment_link_config",
+                    })?;
+                payment_link::validate_payment_link_config(&payment_link_config)?;
+                Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(pl_config)
+            })
+            .transpose()?,
+        email_config: req
+            .email_config
+            .as_ref()
+            .map(|email_config| {
+                validate_email_config(email_config)?;
+                utils::Encode::<admin_types::MerchantEmailConfig>::encode_to_value(email_config)
+                    .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                        field_name: "email_config",
+                    })
+            })
+            .transpose()?,
     };
 
+    let merchant_account_before: api::MerchantAccountResponse = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
+
     let response = db
         .update_specific_fields_in_merchant(merchant_id, updated_merchant_account, &key_store)
         .await
@@ -588,12 +633,25 @@ pub async fn merchant_account_update(
 
     // If there are any new business labels generated, create business profile
 
-    Ok(service_api::ApplicationResponse::Json(
-        response
-            .try_into()
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("Failed while generating response")?,
-    ))
+    let response: api::MerchantAccountResponse = response
+        .try_into()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed while generating response")?;
+
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: Some(merchant_id.as_str()),
+            resource_type: api_enums::AuditResourceType::MerchantAccount,
+            resource_id: merchant_id,
+            action: api_enums::AuditAction::Update,
+            before: Some(&merchant_account_before),
+            after: Some(&response),
+        },
+    )
+    .await;
+
+    Ok(service_api::ApplicationResponse::Json(response))
 }
 
 pub async fn merchant_account_delete(
@@ -677,6 +735,33 @@ async fn validate_merchant_id<S: Into<String>>(
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
 }
 
+fn validate_email_config(email_config: &admin_types::MerchantEmailConfig) -> RouterResult<()> {
+    let is_valid_color =
+        |color: &Option<String>| color.as_deref().map_or(true, utils::is_valid_hex_color);
+    when(
+        !is_valid_color(&email_config.primary_color)
+            || !is_valid_color(&email_config.background_color),
+        || {
+            Err(errors::ApiErrorResponse::InvalidDataFormat {
+                field_name: "email_config".to_string(),
+                expected_format: "hex colours such as #0099FF".to_string(),
+            })
+            .into_report()
+        },
+    )?;
+
+    let is_valid_logo_url = email_config.logo_url.as_deref().map_or(true, |logo_url| {
+        url::Url::parse(logo_url).map_or(false, |url| url.scheme() == "https")
+    });
+    when(!is_valid_logo_url, || {
+        Err(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "email_config.logo_url".to_string(),
+            expected_format: "https URL".to_string(),
+        })
+        .into_report()
+    })
+}
+
 fn validate_certificate_in_mca_metadata(
     connector_metadata: Secret<serde_json::Value>,
 ) -> RouterResult<()> {
@@ -721,10 +806,7 @@ pub async fn create_payment_connector(
     #[cfg(feature = "dummy_connector")]
     validate_dummy_connector_enabled(&state, &req.connector_name).await?;
     let key_store = store
-        .get_merchant_key_store_by_merchant_id(
-            merchant_id,
-            &state.store.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(merchant_id, &state.store.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -839,7 +921,7 @@ pub async fn create_payment_connector(
                     field_name: "connector_account_details",
                 },
             )?,
-            key_store.key.peek(),
+            &key_store.key_ring(),
         )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
@@ -893,7 +975,21 @@ pub async fn create_payment_connector(
         ],
     );
 
-    let mca_response = mca.try_into()?;
+    let mca_response: api_models::admin::MerchantConnectorResponse = mca.try_into()?;
+
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: Some(merchant_id.as_str()),
+            resource_type: api_enums::AuditResourceType::MerchantConnectorAccount,
+            resource_id: &mca_response.merchant_connector_id,
+            action: api_enums::AuditAction::Create,
+            before: None,
+            after: Some(&mca_response),
+        },
+    )
+    .await;
+
     Ok(service_api::ApplicationResponse::Json(mca_response))
 }
 
@@ -904,10 +1000,7 @@ pub async fn retrieve_payment_connector(
 ) -> RouterResponse<api_models::admin::MerchantConnectorResponse> {
     let store = state.store.as_ref();
     let key_store = store
-        .get_merchant_key_store_by_merchant_id(
-            &merchant_id,
-            &store.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &store.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -936,10 +1029,7 @@ pub async fn list_payment_connectors(
 ) -> RouterResponse<Vec<api_models::admin::MerchantConnectorResponse>> {
     let store = state.store.as_ref();
     let key_store = store
-        .get_merchant_key_store_by_merchant_id(
-            &merchant_id,
-            &store.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &store.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -975,7 +1065,7 @@ pub async fn update_payment_connector(
 ) -> RouterResponse<api_models::admin::MerchantConnectorResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -1005,6 +1095,8 @@ pub async fn update_payment_connector(
     });
 
     let frm_configs = get_frm_config_as_secret(req.frm_configs);
+    let key_ring = key_store.key_ring();
+    let mca_before: api_models::admin::MerchantConnectorResponse = mca.clone().try_into()?;
 
     let payment_connector = storage::MerchantConnectorAccountUpdate::Update {
         merchant_id: None,
@@ -1014,9 +1106,7 @@ pub async fn update_payment_connector(
         connector_label: req.connector_label,
         connector_account_details: req
             .connector_account_details
-            .async_lift(|inner| {
-                domain_types::encrypt_optional(inner, key_store.key.get_inner().peek())
-            })
+            .async_lift(|inner| domain_types::encrypt_optional(inner, &key_ring))
             .await
             .change_context(errors::ApiErrorResponse::InternalServerError)
             .attach_printable("Failed while encrypting data")?,
@@ -1048,7 +1138,20 @@ pub async fn update_payment_connector(
             format!("Failed while updating MerchantConnectorAccount: id: {merchant_connector_id}")
         })?;
 
-    let response = updated_mca.try_into()?;
+    let response: api_models::admin::MerchantConnectorResponse = updated_mca.try_into()?;
+
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: Some(merchant_id),
+            resource_type: api_enums::AuditResourceType::MerchantConnectorAccount,
+            resource_id: merchant_connector_id,
+            action: api_enums::AuditAction::Update,
+            before: Some(&mca_before),
+            after: Some(&response),
+        },
+    )
+    .await;
 
     Ok(service_api::ApplicationResponse::Json(response))
 }
@@ -1060,7 +1163,7 @@ pub async fn delete_payment_connector(
 ) -> RouterResponse<api::MerchantConnectorDeleteResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -1069,7 +1172,7 @@ pub async fn delete_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let _mca = db
+    let mca_before: api_models::admin::MerchantConnectorResponse = db
         .find_by_merchant_connector_account_merchant_id_merchant_connector_id(
             &merchant_id,
             &merchant_connector_id,
@@ -1078,7 +1181,8 @@ pub async fn delete_payment_connector(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantConnectorAccountNotFound {
             id: merchant_connector_id.clone(),
-        })?;
+        })?
+        .try_into()?;
 
     let is_deleted = db
         .delete_merchant_connector_account_by_merchant_id_merchant_connector_id(
@@ -1090,6 +1194,21 @@ pub async fn delete_payment_connector(
             id: merchant_connector_id.clone(),
         })?;
 
+    if is_deleted {
+        audit_events::record_audit_event(
+            &state,
+            AuditRecord {
+                merchant_id: Some(&merchant_id),
+                resource_type: api_enums::AuditResourceType::MerchantConnectorAccount,
+                resource_id: &merchant_connector_id,
+                action: api_enums::AuditAction::Delete,
+                before: Some(&mca_before),
+                after: None,
+            },
+        )
+        .await;
+    }
+
     let response = api::MerchantConnectorDeleteResponse {
         merchant_id,
         merchant_connector_id,
@@ -1098,6 +1217,251 @@ pub async fn delete_payment_connector(
     Ok(service_api::ApplicationResponse::Json(response))
 }
 
+const KV_MIGRATION_TAG: &str = "KV_MIGRATION";
+const KV_MIGRATION_NAME: &str = "KV_MIGRATION";
+const KV_MIGRATION_RUNNER: &str = "KV_MIGRATION_WORKFLOW";
+
+/// The number of seconds after which the completion of a draining migration is checked again
+pub const KV_MIGRATION_CHECK_INTERVAL_IN_SECONDS: i64 = 60;
+
+/// Progress of a switch of a merchant between storage schemes, persisted in the configs table.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct KvMigration {
+    target_storage_scheme: MerchantStorageScheme,
+    status: admin_types::KvMigrationStatus,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    started_at: time::PrimitiveDateTime,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    completed_at: Option<time::PrimitiveDateTime>,
+}
+
+impl KvMigration {
+    fn new(target_storage_scheme: MerchantStorageScheme) -> Self {
+        Self {
+            target_storage_scheme,
+            status: admin_types::KvMigrationStatus::Draining,
+            started_at: date_time::now(),
+            completed_at: None,
+        }
+    }
+
+    fn finish(mut self, status: admin_types::KvMigrationStatus) -> Self {
+        self.status = status;
+        self.completed_at = Some(date_time::now());
+        self
+    }
+}
+
+fn get_kv_migration_key(merchant_id: &str) -> String {
+    format!("kv_migration_{merchant_id}")
+}
+
+async fn find_kv_migration(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<KvMigration>> {
+    match db
+        .find_config_by_key(&get_kv_migration_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("KvMigration")
+            .map(Some)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse the storage scheme migration of the merchant"),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the storage scheme migration of the merchant"),
+    }
+}
+
+async fn store_kv_migration(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    migration: &KvMigration,
+    already_present: bool,
+) -> RouterResult<()> {
+    let key = get_kv_migration_key(merchant_id);
+    let config = Encode::<KvMigration>::encode_to_string_of_json(migration)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the storage scheme migration of the merchant")?;
+
+    if already_present {
+        db.update_config_by_key(
+            &key,
+            storage::ConfigUpdate::Update {
+                config: Some(config),
+            },
+        )
+        .await
+        .map(|_| ())
+    } else {
+        db.insert_config(storage::ConfigNew { key, config })
+            .await
+            .map(|_| ())
+    }
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store the storage scheme migration of the merchant")
+}
+
+/// Returns the number of entries of the merchant which are yet to be written to the database by
+/// the drainer.
+#[cfg(feature = "kv_store")]
+async fn count_pending_drainer_entries(state: &AppState, merchant_id: &str) -> RouterResult<usize> {
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+
+    let pending_entries = redis_conn
+        .get_key::<Option<i64>>(&diesel_models::kv::get_pending_entries_key(merchant_id))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get the pending drainer entries of the merchant")?;
+
+    // The count may fall below zero for entries which were appended before they were counted
+    Ok(pending_entries
+        .and_then(|pending_entries| usize::try_from(pending_entries).ok())
+        .unwrap_or(0))
+}
+
+#[cfg(not(feature = "kv_store"))]
+async fn count_pending_drainer_entries(
+    _state: &AppState,
+    _merchant_id: &str,
+) -> RouterResult<usize> {
+    Ok(0)
+}
+
+/// Completes a migration which is draining, once no entries of the merchant are pending in the
+/// drainer streams. Returns the merchant account, the migration and the number of pending entries.
+async fn try_complete_kv_migration(
+    state: &AppState,
+    merchant_account: domain::MerchantAccount,
+    migration: KvMigration,
+    key_store: &domain::MerchantKeyStore,
+) -> RouterResult<(domain::MerchantAccount, KvMigration, usize)> {
+    if migration.status != admin_types::KvMigrationStatus::Draining {
+        return Ok((merchant_account, migration, 0));
+    }
+
+    let pending_entries =
+        count_pending_drainer_entries(state, &merchant_account.merchant_id).await?;
+    if pending_entries > 0 {
+        return Ok((merchant_account, migration, pending_entries));
+    }
+
+    let db = state.store.as_ref();
+    let merchant_account = if merchant_account.storage_scheme == migration.target_storage_scheme {
+        merchant_account
+    } else {
+        db.update_merchant(
+            merchant_account,
+            storage::MerchantAccountUpdate::StorageSchemeUpdate {
+                storage_scheme: migration.target_storage_scheme,
+            },
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("failed to switch merchant_storage_scheme")?
+    };
+
+    let migration = migration.finish(admin_types::KvMigrationStatus::Completed);
+    store_kv_migration(db, &merchant_account.merchant_id, &migration, true).await?;
+
+    Ok((merchant_account, migration, 0))
+}
+
+/// Schedules the completion of a migration which is draining.
+async fn add_kv_migration_task(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    migration: &KvMigration,
+) -> RouterResult<()> {
+    let current_time = date_time::now();
+    let tracking_data = storage::KvMigrationWorkflow {
+        merchant_id: merchant_id.to_string(),
+    };
+    let kv_migration_workflow_model = serde_json::to_value(&tracking_data)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("unable to serialize kv migration tracker: {tracking_data:?}")
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: format!(
+            "{KV_MIGRATION_RUNNER}_{merchant_id}_{}",
+            migration.started_at.assume_utc().unix_timestamp()
+        ),
+        name: Some(String::from(KV_MIGRATION_NAME)),
+        tag: vec![String::from(KV_MIGRATION_TAG)],
+        runner: Some(String::from(KV_MIGRATION_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(current_time.saturating_add(time::Duration::seconds(
+            KV_MIGRATION_CHECK_INTERVAL_IN_SECONDS,
+        ))),
+        rule: String::new(),
+        tracking_data: kv_migration_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage::enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting kv migration task to process_tracker: merchant_id: {merchant_id}"
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Completes the storage scheme migration of the merchant if all of the merchant's entries have
+/// been drained to the database. Returns whether the migration is still draining.
+pub async fn process_kv_migration(
+    state: &AppState,
+    tracking_data: &storage::KvMigrationWorkflow,
+) -> RouterResult<bool> {
+    let db = state.store.as_ref();
+    let merchant_id = &tracking_data.merchant_id;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let Some(migration) = find_kv_migration(db, merchant_id).await? else {
+        return Ok(false);
+    };
+    let (_, migration, pending_entries) =
+        try_complete_kv_migration(state, merchant_account, migration, &key_store).await?;
+    if pending_entries > 0 {
+        crate::logger::info!(%merchant_id, pending_entries, "KV migration is still draining");
+    }
+
+    Ok(migration.status == admin_types::KvMigrationStatus::Draining)
+}
+
+/// Switches the storage scheme of the merchant.
+///
+/// Enabling KV takes effect immediately, since reads under the KV scheme fall back to the
+/// database. Disabling KV starts a migration during which new entries are written to the
+/// database while reads consult both Redis and the database. The migration is completed by the
+/// scheduler once the drainer has written all of the merchant's pending entries to the database,
+/// and its progress can be tracked using [`kv_migration_status`].
 pub async fn kv_for_merchant(
     state: AppState,
     merchant_id: String,
@@ -1105,7 +1469,7 @@ pub async fn kv_for_merchant(
 ) -> RouterResponse<api_models::admin::ToggleKVResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -1115,47 +1479,91 @@ pub async fn kv_for_merchant(
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let updated_merchant_account = match (enable, merchant_account.storage_scheme) {
-        (true, MerchantStorageScheme::RedisKv) | (false, MerchantStorageScheme::PostgresOnly) => {
-            Ok(merchant_account)
-        }
-        (true, MerchantStorageScheme::PostgresOnly) => {
-            db.update_merchant(
-                merchant_account,
-                storage::MerchantAccountUpdate::StorageSchemeUpdate {
-                    storage_scheme: MerchantStorageScheme::RedisKv,
-                },
-                &key_store,
-            )
-            .await
+    let existing_migration = find_kv_migration(db, &merchant_id).await?;
+    let migration_present = existing_migration.is_some();
+    let kv_status_before = api_models::admin::ToggleKVResponse {
+        merchant_id: merchant_id.clone(),
+        kv_enabled: matches!(
+            merchant_account.storage_scheme,
+            MerchantStorageScheme::RedisKv
+        ),
+        migration_status: existing_migration
+            .as_ref()
+            .map(|migration| migration.status),
+    };
+
+    let (updated_merchant_account, migration) = match (enable, merchant_account.storage_scheme) {
+        (true, MerchantStorageScheme::RedisKv)
+        | (false, MerchantStorageScheme::PostgresOnly)
+        | (false, MerchantStorageScheme::RedisKvDraining) => (merchant_account, existing_migration),
+        (true, storage_scheme) => {
+            let updated_merchant_account = db
+                .update_merchant(
+                    merchant_account,
+                    storage::MerchantAccountUpdate::StorageSchemeUpdate {
+                        storage_scheme: MerchantStorageScheme::RedisKv,
+                    },
+                    &key_store,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("failed to switch merchant_storage_scheme")?;
+
+            // A migration away from KV which has not completed yet is cancelled
+            let migration = match existing_migration {
+                Some(migration) if storage_scheme == MerchantStorageScheme::RedisKvDraining => {
+                    migration.finish(admin_types::KvMigrationStatus::Cancelled)
+                }
+                _ => KvMigration::new(MerchantStorageScheme::RedisKv)
+                    .finish(admin_types::KvMigrationStatus::Completed),
+            };
+            store_kv_migration(db, &merchant_id, &migration, migration_present).await?;
+            (updated_merchant_account, Some(migration))
         }
         (false, MerchantStorageScheme::RedisKv) => {
-            db.update_merchant(
-                merchant_account,
-                storage::MerchantAccountUpdate::StorageSchemeUpdate {
-                    storage_scheme: MerchantStorageScheme::PostgresOnly,
-                },
-                &key_store,
-            )
-            .await
+            let updated_merchant_account = db
+                .update_merchant(
+                    merchant_account,
+                    storage::MerchantAccountUpdate::StorageSchemeUpdate {
+                        storage_scheme: MerchantStorageScheme::RedisKvDraining,
+                    },
+                    &key_store,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("failed to switch merchant_storage_scheme")?;
+
+            let migration = KvMigration::new(MerchantStorageScheme::PostgresOnly);
+            store_kv_migration(db, &merchant_id, &migration, migration_present).await?;
+            add_kv_migration_task(db, &merchant_id, &migration).await?;
+            (updated_merchant_account, Some(migration))
         }
-    }
-    .map_err(|error| {
-        error
-            .change_context(errors::ApiErrorResponse::InternalServerError)
-            .attach_printable("failed to switch merchant_storage_scheme")
-    })?;
+    };
+
     let kv_status = matches!(
         updated_merchant_account.storage_scheme,
         MerchantStorageScheme::RedisKv
     );
+    let response = api_models::admin::ToggleKVResponse {
+        merchant_id: updated_merchant_account.merchant_id,
+        kv_enabled: kv_status,
+        migration_status: migration.map(|migration| migration.status),
+    };
 
-    Ok(service_api::ApplicationResponse::Json(
-        api_models::admin::ToggleKVResponse {
-            merchant_id: updated_merchant_account.merchant_id,
-            kv_enabled: kv_status,
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: Some(&merchant_id),
+            resource_type: api_enums::AuditResourceType::MerchantStorageScheme,
+            resource_id: &merchant_id,
+            action: api_enums::AuditAction::Update,
+            before: Some(&kv_status_before),
+            after: Some(&response),
         },
-    ))
+    )
+    .await;
+
+    Ok(service_api::ApplicationResponse::Json(response))
 }
 
 pub async fn check_merchant_account_kv_status(
@@ -1164,7 +1572,7 @@ pub async fn check_merchant_account_kv_status(
 ) -> RouterResponse<api_models::admin::ToggleKVResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -1178,11 +1586,61 @@ pub async fn check_merchant_account_kv_status(
         merchant_account.storage_scheme,
         MerchantStorageScheme::RedisKv
     );
+    let migration = find_kv_migration(db, &merchant_id).await?;
 
     Ok(service_api::ApplicationResponse::Json(
         api_models::admin::ToggleKVResponse {
             merchant_id: merchant_account.merchant_id,
             kv_enabled: kv_status,
+            migration_status: migration.map(|migration| migration.status),
+        },
+    ))
+}
+
+/// Reports the progress of the latest storage scheme migration of the merchant.
+pub async fn kv_migration_status(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<api_models::admin::KvMigrationStatusResponse> {
+    let db = state.store.as_ref();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    // check if the merchant account exists
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let migration = find_kv_migration(db, &merchant_id).await?.ok_or_else(|| {
+        report!(errors::ApiErrorResponse::GenericNotFoundError {
+            message: "No storage scheme migration found for the merchant".to_string(),
+        })
+    })?;
+
+    let pending_entries = if migration.status == admin_types::KvMigrationStatus::Draining {
+        count_pending_drainer_entries(&state, &merchant_id).await?
+    } else {
+        0
+    };
+
+    Ok(service_api::ApplicationResponse::Json(
+        api_models::admin::KvMigrationStatusResponse {
+            merchant_id: merchant_account.merchant_id,
+            kv_enabled: matches!(
+                merchant_account.storage_scheme,
+                MerchantStorageScheme::RedisKv
+            ),
+            target_kv_enabled: matches!(
+                migration.target_storage_scheme,
+                MerchantStorageScheme::RedisKv
+            ),
+            status: migration.status,
+            pending_entries,
+            started_at: migration.started_at,
+            completed_at: migration.completed_at,
         },
     ))
 }
@@ -1236,7 +1694,7 @@ pub async fn create_business_profile(
 ) -> RouterResponse<api_models::admin::BusinessProfileResponse> {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key().to_vec().into())
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -1637,3 +2095,37 @@ pub async fn validate_dummy_connector_enabled(
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[test]
+    fn test_kv_migration_lifecycle() {
+        let migration = KvMigration::new(MerchantStorageScheme::PostgresOnly);
+        assert_eq!(migration.status, admin_types::KvMigrationStatus::Draining);
+        assert!(migration.completed_at.is_none());
+
+        let encoded = Encode::<KvMigration>::encode_to_string_of_json(&migration).unwrap();
+        let decoded: KvMigration = encoded.parse_struct("KvMigration").unwrap();
+        assert_eq!(
+            decoded.target_storage_scheme,
+            MerchantStorageScheme::PostgresOnly
+        );
+        assert_eq!(decoded.status, admin_types::KvMigrationStatus::Draining);
+
+        let migration = decoded.finish(admin_types::KvMigrationStatus::Completed);
+        assert_eq!(migration.status, admin_types::KvMigrationStatus::Completed);
+        assert!(migration.completed_at.is_some());
+    }
+
+    #[test]
+    fn test_draining_storage_scheme_matches_database_enum() {
+        // The value is added to the `MerchantStorageScheme` type by a migration
+        assert_eq!(
+            MerchantStorageScheme::RedisKvDraining.to_string(),
+            "redis_kv_draining"
+        );
+    }
+}
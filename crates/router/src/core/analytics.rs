@@ -0,0 +1,122 @@
+use std::str::FromStr;
+
+use api_models::analytics::{
+    GetPaymentMetricRequest, MetricsResponse, PaymentMetric, PaymentMetricsBucketResponse,
+};
+use common_utils::{date_time, fp_utils::when};
+use error_stack::{report, ResultExt};
+use router_env::{instrument, tracing};
+
+use crate::{
+    core::errors::{self, RouterResponse},
+    routes::AppState,
+    services::ApplicationResponse,
+    types::{
+        domain,
+        storage::analytics::{PaymentMetricsQuery, PaymentMetricsRow},
+    },
+};
+
+#[instrument(skip_all)]
+pub async fn get_payment_metrics(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    req: GetPaymentMetricRequest,
+) -> RouterResponse<MetricsResponse<PaymentMetricsBucketResponse>> {
+    when(req.metrics.is_empty(), || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "At least one metric must be requested".to_string(),
+        }))
+    })?;
+
+    let end_time = req.time_range.end_time.unwrap_or_else(date_time::now);
+    when(req.time_range.start_time > end_time, || {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "The start time must not be after the end time".to_string(),
+        }))
+    })?;
+
+    let query = PaymentMetricsQuery {
+        merchant_id: &merchant_account.merchant_id,
+        start_time: req.time_range.start_time,
+        end_time,
+        granularity: req.time_series.map(|time_series| time_series.granularity),
+        group_by: &req.group_by_names,
+        filters: &req.filters,
+    };
+    let amount_metrics = [
+        PaymentMetric::AvgTicketSize,
+        PaymentMetric::PaymentProcessedAmount,
+    ];
+    when(
+        req.metrics
+            .iter()
+            .any(|metric| amount_metrics.contains(metric))
+            && !query.is_single_currency(),
+        || {
+            Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: "Amount metrics require grouping by or filtering on a single currency"
+                    .to_string(),
+            }))
+        },
+    )?;
+
+    let rows = state
+        .store
+        .get_payment_metrics(&query, storage_impl::ReadConsistency::Eventual)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to compute payment metrics")?;
+
+    Ok(ApplicationResponse::Json(MetricsResponse {
+        query_data: rows
+            .into_iter()
+            .map(|row| to_bucket_response(row, &req))
+            .collect(),
+    }))
+}
+
+fn to_bucket_response(
+    row: PaymentMetricsRow,
+    req: &GetPaymentMetricRequest,
+) -> PaymentMetricsBucketResponse {
+    let requested = |metric| req.metrics.contains(&metric);
+    let success_rate = u32::try_from(row.payment_count)
+        .ok()
+        .filter(|payment_count| *payment_count > 0)
+        .zip(u32::try_from(row.success_count).ok())
+        .map(|(payment_count, success_count)| {
+            f64::from(success_count) * 100.0 / f64::from(payment_count)
+        });
+
+    PaymentMetricsBucketResponse {
+        connector: row.connector,
+        payment_method: row.payment_method,
+        payment_method_type: row.payment_method_type,
+        currency: row
+            .currency
+            .and_then(|currency| FromStr::from_str(&currency).ok()),
+        status: row
+            .status
+            .and_then(|status| FromStr::from_str(&status).ok()),
+        authentication_type: row
+            .authentication_type
+            .and_then(|authentication_type| FromStr::from_str(&authentication_type).ok()),
+        time_bucket: row.time_bucket,
+        payment_count: requested(PaymentMetric::PaymentCount)
+            .then(|| u64::try_from(row.payment_count).ok())
+            .flatten(),
+        payment_success_rate: requested(PaymentMetric::PaymentSuccessRate)
+            .then_some(success_rate)
+            .flatten(),
+        avg_ticket_size: requested(PaymentMetric::AvgTicketSize)
+            .then_some(row.avg_amount)
+            .flatten(),
+        payment_processed_amount: requested(PaymentMetric::PaymentProcessedAmount)
+            .then_some(row.processed_amount.or(Some(0)))
+            .flatten(),
+        avg_latency: requested(PaymentMetric::AvgLatency)
+            .then_some(row.avg_latency)
+            .flatten(),
+    }
+}
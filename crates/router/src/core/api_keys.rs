@@ -1,21 +1,30 @@
+use std::{collections::HashSet, net::IpAddr, str::FromStr};
+
+use api_models::enums as api_enums;
 use common_utils::date_time;
-#[cfg(feature = "email")]
 use diesel_models::{api_keys::ApiKey, enums as storage_enums};
 use error_stack::{report, IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::{SecretManagementInterface, SecretsDecrypt};
 use masking::{PeekInterface, StrongSecret};
-use router_env::{instrument, tracing};
+use router_env::{instrument, tracing, tracing::Instrument};
 
 #[cfg(feature = "email")]
 use crate::types::storage::enums;
 use crate::{
     configs::settings,
     consts,
-    core::errors::{self, RouterResponse, StorageErrorExt},
-    routes::{metrics, AppState},
+    core::{
+        api_locking,
+        audit_events::{self, AuditRecord},
+        errors::{self, RouterResponse, StorageErrorExt},
+    },
+    logger,
+    routes::{lock_utils::ApiIdentifier, metrics, AppState},
     services::ApplicationResponse,
-    types::{api, storage, transformers::ForeignInto},
+    types::{
+        api, storage,
+        transformers::{ForeignFrom, ForeignInto},
+    },
     utils,
 };
 
@@ -26,28 +35,29 @@ const API_KEY_EXPIRY_NAME: &str = "API_KEY_EXPIRY";
 #[cfg(feature = "email")]
 const API_KEY_EXPIRY_RUNNER: &str = "API_KEY_EXPIRY_WORKFLOW";
 
-#[cfg(feature = "kms")]
-use external_services::kms::decrypt::KmsDecrypt;
+const API_KEY_REVOCATION_TAG: &str = "API_KEY";
+const API_KEY_REVOCATION_NAME: &str = "API_KEY_REVOCATION";
+const API_KEY_REVOCATION_RUNNER: &str = "API_KEY_REVOCATION_WORKFLOW";
+
+/// The minimum number of seconds between updates of the last used time of an API key, so that
+/// frequently used keys are not written to the database on every request.
+const API_KEY_LAST_USED_UPDATE_INTERVAL_IN_SECONDS: i64 = 60;
 
 static HASH_KEY: tokio::sync::OnceCell<StrongSecret<[u8; PlaintextApiKey::HASH_KEY_LEN]>> =
     tokio::sync::OnceCell::const_new();
 
 pub async fn get_hash_key(
     api_key_config: &settings::ApiKeys,
-    #[cfg(feature = "kms")] kms_client: &kms::KmsClient,
+    secrets_manager: &dyn SecretManagementInterface,
 ) -> errors::RouterResult<&'static StrongSecret<[u8; PlaintextApiKey::HASH_KEY_LEN]>> {
     HASH_KEY
         .get_or_try_init(|| async {
-            #[cfg(feature = "kms")]
             let hash_key = api_key_config
-                .kms_encrypted_hash_key
-                .decrypt_inner(kms_client)
+                .hash_key
+                .decrypt_inner(secrets_manager)
                 .await
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed to KMS decrypt API key hashing key")?;
-
-            #[cfg(not(feature = "kms"))]
-            let hash_key = &api_key_config.hash_key;
+                .attach_printable("Failed to decrypt API key hashing key")?;
 
             <[u8; PlaintextApiKey::HASH_KEY_LEN]>::try_from(
                 hex::decode(hash_key)
@@ -131,7 +141,7 @@ impl PlaintextApiKey {
 #[instrument(skip_all)]
 pub async fn create_api_key(
     state: AppState,
-    #[cfg(feature = "kms")] kms_client: &kms::KmsClient,
+    secrets_manager: &dyn SecretManagementInterface,
     api_key: api::CreateApiKeyRequest,
     merchant_id: String,
 ) -> RouterResponse<api::CreateApiKeyResponse> {
@@ -142,19 +152,13 @@ pub async fn create_api_key(
     // Instead, we're only fetching merchant key store, as it is sufficient to identify
     // non-existence of a merchant account.
     store
-        .get_merchant_key_store_by_merchant_id(
-            merchant_id.as_str(),
-            &store.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(merchant_id.as_str(), &store.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
 
-    let hash_key = get_hash_key(
-        api_key_config,
-        #[cfg(feature = "kms")]
-        kms_client,
-    )
-    .await?;
+    validate_api_key_restrictions(api_key.scopes.as_deref(), api_key.allowed_ips.as_deref())?;
+
+    let hash_key = get_hash_key(api_key_config, secrets_manager).await?;
     let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
     let api_key = storage::ApiKeyNew {
         key_id: PlaintextApiKey::new_key_id(),
@@ -166,6 +170,10 @@ pub async fn create_api_key(
         created_at: date_time::now(),
         expires_at: api_key.expiration.into(),
         last_used: None,
+        scopes: api_key
+            .scopes
+            .map(|scopes| scopes.iter().map(ToString::to_string).collect()),
+        allowed_ips: api_key.allowed_ips,
     };
 
     let api_key = store
@@ -177,9 +185,25 @@ pub async fn create_api_key(
     metrics::API_KEY_CREATED.add(
         &metrics::CONTEXT,
         1,
-        &[metrics::request::add_attributes("merchant", merchant_id)],
+        &[metrics::request::add_attributes(
+            "merchant",
+            merchant_id.to_owned(),
+        )],
     );
 
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: Some(&merchant_id),
+            resource_type: api_enums::AuditResourceType::ApiKey,
+            resource_id: &api_key.key_id,
+            action: api_enums::AuditAction::Create,
+            before: None,
+            after: Some(&api::RetrieveApiKeyResponse::foreign_from(api_key.clone())),
+        },
+    )
+    .await;
+
     // Add process to process_tracker for email reminder, only if expiry is set to future date
     // If the `api_key` is set to expire in less than 7 days, the merchant is not notified about it's expiry
     #[cfg(feature = "email")]
@@ -299,6 +323,14 @@ pub async fn update_api_key(
     api_key: api::UpdateApiKeyRequest,
 ) -> RouterResponse<api::RetrieveApiKeyResponse> {
     let store = state.store.as_ref();
+    validate_api_key_restrictions(api_key.scopes.as_deref(), api_key.allowed_ips.as_deref())?;
+
+    let api_key_before = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .ok_or(report!(errors::ApiErrorResponse::ApiKeyNotFound))?;
 
     let api_key = store
         .update_api_key(
@@ -309,6 +341,19 @@ pub async fn update_api_key(
         .await
         .to_not_found_response(errors::ApiErrorResponse::ApiKeyNotFound)?;
 
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: Some(merchant_id),
+            resource_type: api_enums::AuditResourceType::ApiKey,
+            resource_id: key_id,
+            action: api_enums::AuditAction::Update,
+            before: Some(&api::RetrieveApiKeyResponse::foreign_from(api_key_before)),
+            after: Some(&api::RetrieveApiKeyResponse::foreign_from(api_key.clone())),
+        },
+    )
+    .await;
+
     #[cfg(feature = "email")]
     {
         let expiry_reminder_days = state.conf.api_keys.expiry_reminder_days.clone();
@@ -434,6 +479,12 @@ pub async fn revoke_api_key(
     key_id: &str,
 ) -> RouterResponse<api::RevokeApiKeyResponse> {
     let store = state.store.as_ref();
+    let api_key_before = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?;
+
     let revoked = store
         .revoke_api_key(merchant_id, key_id)
         .await
@@ -441,6 +492,23 @@ pub async fn revoke_api_key(
 
     metrics::API_KEY_REVOKED.add(&metrics::CONTEXT, 1, &[]);
 
+    if revoked {
+        audit_events::record_audit_event(
+            &state,
+            AuditRecord {
+                merchant_id: Some(merchant_id),
+                resource_type: api_enums::AuditResourceType::ApiKey,
+                resource_id: key_id,
+                action: api_enums::AuditAction::Revoke,
+                before: api_key_before
+                    .map(api::RetrieveApiKeyResponse::foreign_from)
+                    .as_ref(),
+                after: None,
+            },
+        )
+        .await;
+    }
+
     #[cfg(feature = "email")]
     {
         let task_id = generate_task_id_for_api_key_expiry_workflow(key_id);
@@ -522,6 +590,334 @@ fn generate_task_id_for_api_key_expiry_workflow(key_id: &str) -> String {
     format!("{API_KEY_EXPIRY_RUNNER}_{API_KEY_EXPIRY_NAME}_{key_id}")
 }
 
+#[instrument(skip_all)]
+pub async fn rotate_api_key(
+    state: AppState,
+    secrets_manager: &dyn SecretManagementInterface,
+    merchant_id: &str,
+    key_id: &str,
+    request: api::RotateApiKeyRequest,
+) -> RouterResponse<api::RotateApiKeyResponse> {
+    let api_key_config = &state.conf.api_keys;
+
+    let grace_period_in_seconds = request
+        .grace_period_in_seconds
+        .unwrap_or(api_key_config.rotation_grace_period_in_seconds);
+    if grace_period_in_seconds > api_key_config.max_rotation_grace_period_in_seconds {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "The grace period must not exceed {} seconds",
+                api_key_config.max_rotation_grace_period_in_seconds
+            ),
+        }));
+    }
+
+    // Rotations of the same API key are serialized, so that a key is never rotated twice
+    let lock_action = api_locking::LockAction::Hold {
+        input: api_locking::LockingInput {
+            unique_locking_key: key_id.to_owned(),
+            api_identifier: ApiIdentifier::ApiKeys,
+            override_lock_retries: None,
+        },
+    };
+    lock_action
+        .clone()
+        .perform_locking_action(&state, merchant_id.to_owned())
+        .await?;
+    let result = rotate_api_key_with_lock_held(
+        &state,
+        secrets_manager,
+        merchant_id,
+        key_id,
+        grace_period_in_seconds,
+    )
+    .await;
+    lock_action
+        .free_lock_action(&state, merchant_id.to_owned())
+        .await?;
+
+    result
+}
+
+async fn rotate_api_key_with_lock_held(
+    state: &AppState,
+    secrets_manager: &dyn SecretManagementInterface,
+    merchant_id: &str,
+    key_id: &str,
+    grace_period_in_seconds: u32,
+) -> RouterResponse<api::RotateApiKeyResponse> {
+    let api_key_config = &state.conf.api_keys;
+    let store = state.store.as_ref();
+
+    let rotated_api_key = store
+        .find_api_key_by_merchant_id_key_id_optional(merchant_id, key_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key")?
+        .ok_or(report!(errors::ApiErrorResponse::ApiKeyNotFound))?;
+
+    let current_time = date_time::now();
+    if rotated_api_key
+        .expires_at
+        .map(|expires_at| expires_at <= current_time)
+        .unwrap_or(false)
+    {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "Expired API keys cannot be rotated".to_string(),
+        }));
+    }
+
+    let revocation_task_id = generate_task_id_for_api_key_revocation_workflow(key_id);
+    if store
+        .find_process_by_id(&revocation_task_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve API key revocation task from process tracker")?
+        .is_some()
+    {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The API key has already been rotated".to_string(),
+        }));
+    }
+
+    let hash_key = get_hash_key(api_key_config, secrets_manager).await?;
+    let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
+    let (expires_at, revocation_time) = get_rotation_times(
+        rotated_api_key.created_at,
+        rotated_api_key.expires_at,
+        current_time,
+        grace_period_in_seconds,
+    );
+    let api_key = storage::ApiKeyNew {
+        key_id: PlaintextApiKey::new_key_id(),
+        merchant_id: merchant_id.to_owned(),
+        name: rotated_api_key.name.clone(),
+        description: rotated_api_key.description.clone(),
+        hashed_api_key: plaintext_api_key.keyed_hash(hash_key.peek()).into(),
+        prefix: plaintext_api_key.prefix(),
+        created_at: current_time,
+        expires_at,
+        last_used: None,
+        scopes: rotated_api_key.scopes.clone(),
+        allowed_ips: rotated_api_key.allowed_ips.clone(),
+    };
+
+    let api_key = store
+        .insert_api_key(api_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert new API key")?;
+
+    metrics::API_KEY_CREATED.add(
+        &metrics::CONTEXT,
+        1,
+        &[metrics::request::add_attributes(
+            "merchant",
+            merchant_id.to_owned(),
+        )],
+    );
+
+    let rotated_api_key_before = api::RetrieveApiKeyResponse::foreign_from(rotated_api_key);
+    let rotated_api_key = store
+        .update_api_key(
+            merchant_id.to_owned(),
+            key_id.to_owned(),
+            storage::ApiKeyUpdate::Update {
+                name: None,
+                description: None,
+                expires_at: Some(Some(revocation_time)),
+                last_used: None,
+                scopes: None,
+                allowed_ips: None,
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ApiKeyNotFound)?;
+
+    add_api_key_revocation_task(store, &rotated_api_key, revocation_time)
+        .await
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert API key revocation task to process tracker")?;
+
+    audit_events::record_audit_event(
+        state,
+        AuditRecord {
+            merchant_id: Some(merchant_id),
+            resource_type: api_enums::AuditResourceType::ApiKey,
+            resource_id: key_id,
+            action: api_enums::AuditAction::Rotate,
+            before: Some(&rotated_api_key_before),
+            after: Some(&api::RetrieveApiKeyResponse::foreign_from(
+                rotated_api_key.clone(),
+            )),
+        },
+    )
+    .await;
+    audit_events::record_audit_event(
+        state,
+        AuditRecord {
+            merchant_id: Some(merchant_id),
+            resource_type: api_enums::AuditResourceType::ApiKey,
+            resource_id: &api_key.key_id,
+            action: api_enums::AuditAction::Create,
+            before: None,
+            after: Some(&api::RetrieveApiKeyResponse::foreign_from(api_key.clone())),
+        },
+    )
+    .await;
+
+    #[cfg(feature = "email")]
+    {
+        if api_key.expires_at.is_some() {
+            let expiry_reminder_days = state.conf.api_keys.expiry_reminder_days.clone();
+
+            add_api_key_expiry_task(store, &api_key, expiry_reminder_days)
+                .await
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to insert API key expiry reminder to process tracker")?;
+        }
+
+        // The merchant need not be reminded about the expiry of the rotated API key
+        let expiry_task_id = generate_task_id_for_api_key_expiry_workflow(key_id);
+        let existing_process_tracker_task = store
+            .find_process_by_id(expiry_task_id.as_str())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable(
+                "Failed to retrieve API key expiry reminder task from process tracker",
+            )?;
+        if existing_process_tracker_task.is_some() {
+            revoke_api_key_expiry_task(store, key_id)
+                .await
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable(
+                    "Failed to revoke API key expiry reminder task in process tracker",
+                )?;
+        }
+    }
+
+    Ok(ApplicationResponse::Json(api::RotateApiKeyResponse {
+        api_key: (api_key, plaintext_api_key).foreign_into(),
+        rotated_api_key: rotated_api_key.foreign_into(),
+    }))
+}
+
+// Add api_key_revocation task to the process_tracker table, scheduled at the end of the grace
+// period of a rotated API key.
+#[instrument(skip_all)]
+pub async fn add_api_key_revocation_task(
+    store: &dyn crate::db::StorageInterface,
+    api_key: &ApiKey,
+    revocation_time: time::PrimitiveDateTime,
+) -> Result<(), errors::ProcessTrackerError> {
+    let current_time = date_time::now();
+
+    let api_key_revocation_tracker = &storage::ApiKeyRevocationWorkflow {
+        key_id: api_key.key_id.clone(),
+        merchant_id: api_key.merchant_id.clone(),
+    };
+    let api_key_revocation_workflow_model = serde_json::to_value(api_key_revocation_tracker)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "unable to serialize API key revocation tracker: {api_key_revocation_tracker:?}"
+            )
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: generate_task_id_for_api_key_revocation_workflow(api_key.key_id.as_str()),
+        name: Some(String::from(API_KEY_REVOCATION_NAME)),
+        tag: vec![String::from(API_KEY_REVOCATION_TAG)],
+        runner: Some(String::from(API_KEY_REVOCATION_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(revocation_time),
+        rule: String::new(),
+        tracking_data: api_key_revocation_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    store
+        .insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting API key revocation task to process_tracker: api_key_id: {}",
+                api_key_revocation_tracker.key_id
+            )
+        })?;
+
+    Ok(())
+}
+
+fn generate_task_id_for_api_key_revocation_workflow(key_id: &str) -> String {
+    format!("{API_KEY_REVOCATION_RUNNER}_{API_KEY_REVOCATION_NAME}_{key_id}")
+}
+
+/// Returns when the API key created by a rotation expires, and when the rotated API key is
+/// revoked. The new API key is valid for as long as the rotated API key was originally, and the
+/// rotated API key stops being accepted at the end of the grace period, or when it expires if it
+/// does so sooner.
+fn get_rotation_times(
+    created_at: time::PrimitiveDateTime,
+    expires_at: Option<time::PrimitiveDateTime>,
+    current_time: time::PrimitiveDateTime,
+    grace_period_in_seconds: u32,
+) -> (Option<time::PrimitiveDateTime>, time::PrimitiveDateTime) {
+    let revocation_time =
+        current_time + time::Duration::seconds(i64::from(grace_period_in_seconds));
+
+    (
+        expires_at.map(|expires_at| current_time + (expires_at - created_at)),
+        expires_at.map_or(revocation_time, |expires_at| {
+            expires_at.min(revocation_time)
+        }),
+    )
+}
+
+/// Records that an API key was used to authenticate a request. The last used time is updated at
+/// most once every [`API_KEY_LAST_USED_UPDATE_INTERVAL_IN_SECONDS`], in the background so that
+/// the request is not delayed by it, and failing to update it does not fail the request.
+#[instrument(skip_all)]
+pub fn record_api_key_usage(store: Box<dyn crate::db::StorageInterface>, api_key: &ApiKey) {
+    let current_time = date_time::now();
+    let is_recent = api_key.last_used.map_or(false, |last_used| {
+        current_time - last_used
+            < time::Duration::seconds(API_KEY_LAST_USED_UPDATE_INTERVAL_IN_SECONDS)
+    });
+    if is_recent {
+        return;
+    }
+
+    let merchant_id = api_key.merchant_id.clone();
+    let key_id = api_key.key_id.clone();
+    tokio::spawn(
+        async move {
+            if let Err(error) = store
+                .update_api_key(
+                    merchant_id,
+                    key_id,
+                    storage::ApiKeyUpdate::LastUsedUpdate {
+                        last_used: current_time,
+                    },
+                )
+                .await
+            {
+                logger::error!(?error, "Failed to update last used time of API key");
+            }
+        }
+        .in_current_span(),
+    );
+}
+
 impl From<&str> for PlaintextApiKey {
     fn from(s: &str) -> Self {
         Self(s.to_owned().into())
@@ -546,6 +942,89 @@ impl From<storage::HashedApiKey> for HashedApiKey {
     }
 }
 
+/// An IP address or a CIDR block, which API keys can be restricted to be used from.
+#[derive(Debug, PartialEq, Eq)]
+pub struct IpNetwork {
+    address: IpAddr,
+    prefix_len: u32,
+}
+
+impl IpNetwork {
+    pub fn contains(&self, ip: IpAddr) -> bool {
+        match (self.address, ip) {
+            (IpAddr::V4(network), IpAddr::V4(ip)) => {
+                let mask = u32::MAX.checked_shl(32 - self.prefix_len).unwrap_or(0);
+                u32::from(network) & mask == u32::from(ip) & mask
+            }
+            (IpAddr::V6(network), IpAddr::V6(ip)) => {
+                let mask = u128::MAX.checked_shl(128 - self.prefix_len).unwrap_or(0);
+                u128::from(network) & mask == u128::from(ip) & mask
+            }
+            _ => false,
+        }
+    }
+}
+
+impl FromStr for IpNetwork {
+    type Err = ();
+
+    fn from_str(network: &str) -> Result<Self, Self::Err> {
+        let (address, prefix_len) = match network.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (network, None),
+        };
+        let address = IpAddr::from_str(address).map_err(|_| ())?;
+        let max_prefix_len = if address.is_ipv4() { 32 } else { 128 };
+        let prefix_len = prefix_len
+            .map(|prefix_len| prefix_len.parse::<u32>().map_err(|_| ()))
+            .transpose()?
+            .unwrap_or(max_prefix_len);
+
+        if prefix_len > max_prefix_len {
+            return Err(());
+        }
+
+        Ok(Self {
+            address,
+            prefix_len,
+        })
+    }
+}
+
+fn validate_api_key_restrictions(
+    scopes: Option<&[api::ApiKeyScope]>,
+    allowed_ips: Option<&[String]>,
+) -> errors::RouterResult<()> {
+    let mut resources = HashSet::new();
+    for scope in scopes.unwrap_or_default() {
+        if ApiIdentifier::from_str(&scope.resource).is_err() {
+            return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("Unknown resource `{}` in API key scopes", scope.resource),
+            }));
+        }
+        if !resources.insert(scope.resource.as_str()) {
+            return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "Resource `{}` has more than one API key scope",
+                    scope.resource
+                ),
+            }));
+        }
+    }
+
+    if let Some(network) = allowed_ips
+        .unwrap_or_default()
+        .iter()
+        .find(|network| IpNetwork::from_str(network).is_err())
+    {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("`{network}` is neither an IP address nor a CIDR block"),
+        }));
+    }
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     #![allow(clippy::expect_used, clippy::unwrap_used)]
@@ -558,8 +1037,10 @@ mod tests {
         let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
         let hash_key = get_hash_key(
             &settings.api_keys,
-            #[cfg(feature = "kms")]
-            external_services::kms::get_kms_client(&settings.kms).await,
+            external_services::secrets_management::get_secrets_manager(
+                &settings.secrets_management,
+            )
+            .await,
         )
         .await
         .unwrap();
@@ -573,4 +1054,57 @@ mod tests {
         let new_hashed_api_key = plaintext_api_key.keyed_hash(hash_key.peek());
         assert_eq!(hashed_api_key, new_hashed_api_key)
     }
+
+    #[test]
+    fn test_ip_network() {
+        let network = IpNetwork::from_str("10.1.0.0/16").unwrap();
+        assert!(network.contains("10.1.200.3".parse().unwrap()));
+        assert!(!network.contains("10.2.0.1".parse().unwrap()));
+        assert!(!network.contains("::1".parse().unwrap()));
+
+        let address = IpNetwork::from_str("2001:db8::7").unwrap();
+        assert!(address.contains("2001:db8::7".parse().unwrap()));
+        assert!(!address.contains("2001:db8::8".parse().unwrap()));
+
+        assert!(IpNetwork::from_str("0.0.0.0/0")
+            .unwrap()
+            .contains("203.0.113.7".parse().unwrap()));
+        assert!(IpNetwork::from_str("10.0.0.0/33").is_err());
+        assert!(IpNetwork::from_str("localhost").is_err());
+    }
+
+    #[test]
+    fn test_rotation_times() {
+        let current_time = time::macros::datetime!(2024-03-01 0:00);
+        let day = time::Duration::days(1);
+        let hour = time::Duration::hours(1);
+
+        // API keys that never expire are replaced by API keys that never expire
+        assert_eq!(
+            get_rotation_times(current_time - day * 10, None, current_time, 86400),
+            (None, current_time + day)
+        );
+
+        // The new API key is valid for 90 days, as the rotated API key was
+        assert_eq!(
+            get_rotation_times(
+                current_time - day * 60,
+                Some(current_time + day * 30),
+                current_time,
+                86400
+            ),
+            (Some(current_time + day * 90), current_time + day)
+        );
+
+        // API keys expiring within the grace period are revoked when they expire
+        assert_eq!(
+            get_rotation_times(
+                current_time - day * 60,
+                Some(current_time + hour),
+                current_time,
+                86400
+            ),
+            (Some(current_time + day * 60 + hour), current_time + hour)
+        );
+    }
 }
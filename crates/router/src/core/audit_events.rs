@@ -0,0 +1,234 @@
+//! The audit log of changes made to the configuration of merchants and of the application.
+
+use std::collections::BTreeSet;
+
+use api_models::{audit_events as audit_types, enums as api_enums};
+use common_utils::date_time;
+use error_stack::{IntoReport, ResultExt};
+use router_env::{instrument, logger, tracing};
+use serde_json::{json, Map, Value};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult},
+    events::{EventType, RawEvent},
+    routes::AppState,
+    services::{self, authentication::AuthenticationType},
+    types::{storage, transformers::ForeignFrom},
+    utils,
+};
+
+/// A change made to a resource, to be recorded in the audit log.
+pub struct AuditRecord<'a, T> {
+    pub merchant_id: Option<&'a str>,
+    pub resource_type: api_enums::AuditResourceType,
+    pub resource_id: &'a str,
+    pub action: api_enums::AuditAction,
+    /// The resource before the change, unset if the change created the resource.
+    pub before: Option<&'a T>,
+    /// The resource after the change, unset if the change deleted the resource.
+    pub after: Option<&'a T>,
+}
+
+/// Records a change made by the request the state is scoped to in the audit log, and emits it
+/// through the event handler. The change has already been made when it is recorded, so failures
+/// to record it are logged rather than failing the request.
+#[instrument(skip_all)]
+pub async fn record_audit_event<T>(state: &AppState, record: AuditRecord<'_, T>)
+where
+    T: serde::Serialize,
+{
+    if let Err(error) = insert_audit_event(state, record).await {
+        logger::error!(?error, "Failed to record audit event");
+    }
+}
+
+async fn insert_audit_event<T>(state: &AppState, record: AuditRecord<'_, T>) -> RouterResult<()>
+where
+    T: serde::Serialize,
+{
+    let diff = get_masked_diff(record.before, record.after)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize audited resource")?;
+    let auth_type = state
+        .auth_type
+        .clone()
+        .unwrap_or(AuthenticationType::NoAuth);
+
+    let audit_event = storage::AuditEventNew {
+        audit_event_id: utils::generate_id(consts::ID_LENGTH, "audit"),
+        merchant_id: record.merchant_id.map(ToOwned::to_owned),
+        resource_type: record.resource_type,
+        resource_id: record.resource_id.to_owned(),
+        action: record.action,
+        auth_type: auth_type.to_string(),
+        actor_id: auth_type.get_actor_id().map(ToOwned::to_owned),
+        request_id: state.api_client.get_request_id(),
+        diff,
+        created_at: date_time::now(),
+    };
+
+    // The event is emitted before it is stored, so that it is not lost if storing it fails
+    let response = audit_types::AuditEventResponse::foreign_from(audit_event.clone());
+    match serde_json::to_value(&response) {
+        Ok(payload) => state.event_handler.log_event(RawEvent {
+            event_type: EventType::AuditEvent,
+            key: response.audit_event_id,
+            payload,
+        }),
+        Err(error) => logger::error!(?error, "Failed to serialize audit event"),
+    }
+
+    state
+        .store
+        .insert_audit_event(audit_event)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to insert audit event")?;
+
+    Ok(())
+}
+
+#[instrument(skip_all)]
+pub async fn list_audit_events(
+    state: AppState,
+    constraints: audit_types::AuditEventListConstraints,
+) -> RouterResponse<Vec<audit_types::AuditEventResponse>> {
+    let audit_events = state
+        .store
+        .list_audit_events(constraints, storage_impl::ReadConsistency::Eventual)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list audit events")?;
+
+    Ok(services::ApplicationResponse::Json(
+        audit_events
+            .into_iter()
+            .map(ForeignFrom::foreign_from)
+            .collect(),
+    ))
+}
+
+/// Returns the fields of the resource that were changed, with their values before and after the
+/// change. Fields holding secrets are masked, so changes to them are detected on their unmasked
+/// values and recorded as changed without their values.
+fn get_masked_diff<T>(before: Option<&T>, after: Option<&T>) -> Result<Value, serde_json::Error>
+where
+    T: serde::Serialize,
+{
+    let (before, masked_before) = serialize_resource(before)?;
+    let (after, masked_after) = serialize_resource(after)?;
+
+    Ok(match (into_fields(before), into_fields(after)) {
+        (Some(before), Some(after)) => diff_fields(
+            before,
+            after,
+            into_fields(masked_before).unwrap_or_default(),
+            into_fields(masked_after).unwrap_or_default(),
+        ),
+        _ => json!({ "before": masked_before, "after": masked_after }),
+    })
+}
+
+/// Serializes the resource both as is and with its secrets masked.
+fn serialize_resource<T>(resource: Option<&T>) -> Result<(Value, Value), serde_json::Error>
+where
+    T: serde::Serialize,
+{
+    Ok(match resource {
+        Some(resource) => (
+            serde_json::to_value(resource)?,
+            masking::masked_serialize(resource)?,
+        ),
+        None => (Value::Null, Value::Null),
+    })
+}
+
+/// Returns the fields of a serialized resource, a missing resource having none.
+fn into_fields(value: Value) -> Option<Map<String, Value>> {
+    match value {
+        Value::Object(fields) => Some(fields),
+        Value::Null => Some(Map::new()),
+        _ => None,
+    }
+}
+
+fn diff_fields(
+    mut before: Map<String, Value>,
+    mut after: Map<String, Value>,
+    mut masked_before: Map<String, Value>,
+    mut masked_after: Map<String, Value>,
+) -> Value {
+    let fields = before
+        .keys()
+        .chain(after.keys())
+        .cloned()
+        .collect::<BTreeSet<_>>();
+
+    Value::Object(
+        fields
+            .into_iter()
+            .filter_map(|field| {
+                let masked_before = masked_before.remove(&field).unwrap_or(Value::Null);
+                let masked_after = masked_after.remove(&field).unwrap_or(Value::Null);
+                if before.remove(&field) == after.remove(&field) {
+                    return None;
+                }
+
+                let change = if masked_before != masked_after {
+                    json!({ "before": masked_before, "after": masked_after })
+                } else {
+                    // Only secrets were changed, whose masked values are the same
+                    json!({ "changed": true })
+                };
+                Some((field, change))
+            })
+            .collect(),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use masking::Secret;
+
+    use super::*;
+
+    #[derive(serde::Serialize)]
+    struct Resource {
+        name: String,
+        disabled: bool,
+        secret: Secret<String>,
+    }
+
+    #[test]
+    fn test_masked_diff() {
+        let before = Resource {
+            name: "resource".to_string(),
+            disabled: false,
+            secret: Secret::new("old_secret".to_string()),
+        };
+        let after = Resource {
+            name: "resource".to_string(),
+            disabled: true,
+            secret: Secret::new("new_secret".to_string()),
+        };
+
+        let diff = get_masked_diff(Some(&before), Some(&after)).unwrap();
+        assert_eq!(diff["disabled"], json!({ "before": false, "after": true }));
+        assert_eq!(diff["secret"], json!({ "changed": true }));
+        assert!(diff.get("name").is_none());
+        assert!(!diff.to_string().contains("old_secret"));
+        assert!(!diff.to_string().contains("new_secret"));
+
+        let diff = get_masked_diff(Some(&before), Some(&before)).unwrap();
+        assert_eq!(diff, json!({}));
+
+        let diff = get_masked_diff(None, Some(&after)).unwrap();
+        assert_eq!(diff["name"], json!({ "before": null, "after": "resource" }));
+
+        let diff = get_masked_diff(Some(&before), None).unwrap();
+        assert_eq!(diff["disabled"], json!({ "before": false, "after": null }));
+    }
+}
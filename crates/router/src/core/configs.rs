@@ -1,7 +1,11 @@
+use api_models::enums as api_enums;
 use error_stack::ResultExt;
 
 use crate::{
-    core::errors::{self, utils::StorageErrorExt, RouterResponse},
+    core::{
+        audit_events::{self, AuditRecord},
+        errors::{self, utils::StorageErrorExt, RouterResponse},
+    },
     routes::AppState,
     services::ApplicationResponse,
     types::{api, transformers::ForeignInto},
@@ -9,16 +13,30 @@ use crate::{
 
 pub async fn set_config(state: AppState, config: api::Config) -> RouterResponse<api::Config> {
     let store = state.store.as_ref();
-    let config = store
+    let config: api::Config = store
         .insert_config(diesel_models::configs::ConfigNew {
             key: config.key,
             config: config.value,
         })
         .await
         .to_duplicate_response(errors::ApiErrorResponse::DuplicateConfig)
-        .attach_printable("Unknown error, while setting config key")?;
+        .attach_printable("Unknown error, while setting config key")?
+        .foreign_into();
 
-    Ok(ApplicationResponse::Json(config.foreign_into()))
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: None,
+            resource_type: api_enums::AuditResourceType::Config,
+            resource_id: &config.key,
+            action: api_enums::AuditAction::Create,
+            before: None,
+            after: Some(&config),
+        },
+    )
+    .await;
+
+    Ok(ApplicationResponse::Json(config))
 }
 
 pub async fn read_config(state: AppState, key: &str) -> RouterResponse<api::Config> {
@@ -35,9 +53,29 @@ pub async fn update_config(
     config_update: &api::ConfigUpdate,
 ) -> RouterResponse<api::Config> {
     let store = state.store.as_ref();
-    let config = store
+    let config_before: api::Config = store
+        .find_config_by_key(&config_update.key)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::ConfigNotFound)?
+        .foreign_into();
+    let config: api::Config = store
         .update_config_by_key(&config_update.key, config_update.foreign_into())
         .await
-        .to_not_found_response(errors::ApiErrorResponse::ConfigNotFound)?;
-    Ok(ApplicationResponse::Json(config.foreign_into()))
+        .to_not_found_response(errors::ApiErrorResponse::ConfigNotFound)?
+        .foreign_into();
+
+    audit_events::record_audit_event(
+        &state,
+        AuditRecord {
+            merchant_id: None,
+            resource_type: api_enums::AuditResourceType::Config,
+            resource_id: &config.key,
+            action: api_enums::AuditAction::Update,
+            before: Some(&config_before),
+            after: Some(&config),
+        },
+    )
+    .await;
+
+    Ok(ApplicationResponse::Json(config))
 }
@@ -11,7 +11,6 @@ use crate::{
         errors::{self, StorageErrorExt},
         payment_methods::cards,
     },
-    pii::PeekInterface,
     routes::{metrics, AppState},
     services,
     types::{
@@ -58,7 +57,7 @@ pub async fn create_customer(
         Ok(_) => Err(errors::CustomersErrorResponse::CustomerAlreadyExists).into_report(),
     }?;
 
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
     let address = if let Some(addr) = &customer_data.address {
         let customer_address: api_models::payments::AddressDetails = addr.clone();
 
@@ -242,7 +241,7 @@ pub async fn delete_customer(
         }
     };
 
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
 
     let redacted_encrypted_value: Encryptable<masking::Secret<_>> =
         Encryptable::encrypt(REDACTED.to_string().into(), key, GcmAes256)
@@ -336,7 +335,7 @@ pub async fn update_customer(
         .await
         .switch()?;
 
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
 
     let address = if let Some(addr) = &update_customer.address {
         match customer.address_id {
@@ -48,7 +48,11 @@ pub async fn retrieve_disputes_list(
 ) -> RouterResponse<Vec<api_models::disputes::DisputeResponse>> {
     let disputes = state
         .store
-        .find_disputes_by_merchant_id(&merchant_account.merchant_id, constraints)
+        .find_disputes_by_merchant_id(
+            &merchant_account.merchant_id,
+            constraints,
+            storage_impl::ReadConsistency::Eventual,
+        )
         .await
         .to_not_found_response(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Unable to retrieve disputes")?;
@@ -3,7 +3,10 @@ use common_utils::errors::CustomResult;
 use error_stack::ResultExt;
 
 use crate::{
-    core::{errors, files::helpers::retrieve_file_and_provider_file_id_from_file_id},
+    core::{
+        errors,
+        files::helpers::{get_file_download_url, retrieve_file_and_provider_file_id_from_file_id},
+    },
     routes::AppState,
     types::{
         api::{self, DisputeEvidence},
@@ -214,11 +217,15 @@ pub async fn get_dispute_evidence_block(
         .await
         .change_context(errors::ApiErrorResponse::FileNotFound)
         .attach_printable("Unable to retrieve file_metadata")?;
+    let download_url = get_file_download_url(state, &file_metadata)
+        .await?
+        .map(|(download_url, _expires_at)| download_url);
     let file_metadata_response =
         api_models::files::FileMetadataResponse::foreign_from(file_metadata);
     Ok(api_models::disputes::DisputeEvidenceBlock {
         evidence_type,
         file_metadata_response,
+        download_url,
     })
 }
 
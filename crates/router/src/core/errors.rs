@@ -280,6 +280,20 @@ pub enum ApplePayDecryptionError {
     DerivingSharedSecretKeyFailed,
 }
 
+#[derive(Debug, thiserror::Error)]
+pub enum MasterKeyRotationError {
+    #[error("Master key of the target version is not configured")]
+    MasterKeyNotConfigured,
+    #[error("Failed to fetch the merchant key stores")]
+    MerchantKeyStoreFetchFailed,
+    #[error("Failed to re-encrypt the merchant key store")]
+    MerchantKeyStoreRewrapFailed,
+    #[error("Re-encrypted merchant key store does not match the original")]
+    MerchantKeyStoreVerificationFailed,
+    #[error("Failed to update the merchant key store")]
+    MerchantKeyStoreUpdateFailed,
+}
+
 impl ConnectorError {
     pub fn is_connector_timeout(&self) -> bool {
         self == &Self::RequestTimeoutReceived
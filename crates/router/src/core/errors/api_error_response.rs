@@ -170,6 +170,8 @@ pub enum ApiErrorResponse {
     MandateUpdateFailed,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "API Key does not exist in our records")]
     ApiKeyNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "User does not exist in our records")]
+    UserNotFound,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payout does not exist in our records")]
     PayoutNotFound,
     #[error(error_type = ErrorType::ValidationError, code = "HE_03", message = "Invalid mandate id passed from connector")]
@@ -232,12 +234,26 @@ pub enum ApiErrorResponse {
     WebhookUnprocessableEntity,
     #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Payment Link does not exist in our records")]
     PaymentLinkNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Invoice does not exist in our records")]
+    InvoiceNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Subscription plan does not exist in our records")]
+    SubscriptionPlanNotFound,
+    #[error(error_type = ErrorType::ObjectNotFound, code = "HE_02", message = "Subscription does not exist in our records")]
+    SubscriptionNotFound,
     #[error(error_type = ErrorType::InvalidRequestError, code = "WE_05", message = "Merchant Secret set my merchant for webhook source verification is invalid")]
     WebhookInvalidMerchantSecret,
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_19", message = "{message}")]
     CurrencyNotSupported { message: String },
     #[error(error_type = ErrorType::InvalidRequestError, code = "IR_24", message = "Merchant connector account is configured with invalid {config}")]
     InvalidConnectorConfiguration { config: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_25", message = "Tenant '{tenant_id}' is not configured")]
+    InvalidTenant { tenant_id: String },
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_26", message = "Idempotency key was already used with a different request")]
+    IdempotencyKeyReused,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_27", message = "A request with the same idempotency key is still being processed")]
+    IdempotentRequestInProgress,
+    #[error(error_type = ErrorType::InvalidRequestError, code = "IR_28", message = "Too many requests. Retry after {retry_after_seconds} seconds")]
+    RateLimitExceeded { retry_after_seconds: u64 },
 }
 
 impl PTError for ApiErrorResponse {
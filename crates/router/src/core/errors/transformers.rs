@@ -202,6 +202,9 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::ApiKeyNotFound => {
                 AER::NotFound(ApiError::new("HE", 2, "API Key does not exist in our records", None))
             }
+            Self::UserNotFound => {
+                AER::NotFound(ApiError::new("HE", 2, "User does not exist in our records", None))
+            }
             Self::NotSupported { message } => {
                 AER::BadRequest(ApiError::new("HE", 3, "Payment method type not supported", Some(Extra {reason: Some(message.to_owned()), ..Default::default()})))
             },
@@ -267,9 +270,31 @@ impl ErrorSwitch<api_models::errors::types::ApiErrorResponse> for ApiErrorRespon
             Self::PaymentLinkNotFound => {
                 AER::NotFound(ApiError::new("HE", 2, "Payment Link does not exist in our records", None))
             }
+            Self::InvoiceNotFound => {
+                AER::NotFound(ApiError::new("HE", 2, "Invoice does not exist in our records", None))
+            }
+            Self::SubscriptionPlanNotFound => {
+                AER::NotFound(ApiError::new("HE", 2, "Subscription plan does not exist in our records", None))
+            }
+            Self::SubscriptionNotFound => {
+                AER::NotFound(ApiError::new("HE", 2, "Subscription does not exist in our records", None))
+            }
             Self::InvalidConnectorConfiguration {config} => {
                 AER::BadRequest(ApiError::new("IR", 24, format!("Merchant connector account is configured with invalid {config}"), None))
             }
+            Self::InvalidTenant { tenant_id } => {
+                AER::BadRequest(ApiError::new("IR", 25, format!("Tenant '{tenant_id}' is not configured"), None))
+            }
+            Self::IdempotencyKeyReused => {
+                AER::Conflict(ApiError::new("IR", 26, "Idempotency key was already used with a different request", None))
+            }
+            Self::IdempotentRequestInProgress => {
+                AER::Conflict(ApiError::new("IR", 27, "A request with the same idempotency key is still being processed", None))
+            }
+            Self::RateLimitExceeded { retry_after_seconds } => AER::TooManyRequests(
+                ApiError::new("IR", 28, format!("Too many requests. Retry after {retry_after_seconds} seconds"), None),
+                *retry_after_seconds,
+            ),
         }
     }
 }
@@ -1,9 +1,4 @@
 pub mod helpers;
-#[cfg(feature = "s3")]
-pub mod s3_utils;
-
-#[cfg(not(feature = "s3"))]
-pub mod fs_utils;
 
 use api_models::files;
 use error_stack::{IntoReport, ResultExt};
@@ -29,10 +24,7 @@ pub async fn files_create_core(
     )
     .await?;
     let file_id = common_utils::generate_id(consts::ID_LENGTH, "file");
-    #[cfg(feature = "s3")]
-    let file_key = format!("{}/{}", merchant_account.merchant_id, file_id);
-    #[cfg(not(feature = "s3"))]
-    let file_key = format!("{}_{}", merchant_account.merchant_id, file_id);
+    let file_key = helpers::get_file_key(&merchant_account.merchant_id, &file_id);
     let file_new = diesel_models::file::FileMetadataNew {
         file_id: file_id.clone(),
         merchant_id: merchant_account.merchant_id.clone(),
@@ -137,3 +129,28 @@ pub async fn files_retrieve_core(
         content_type,
     )))
 }
+
+pub async fn files_download_url_core(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    req: api::FileId,
+) -> RouterResponse<files::FileDownloadUrlResponse> {
+    let file_metadata_object = state
+        .store
+        .as_ref()
+        .find_file_metadata_by_merchant_id_file_id(&merchant_account.merchant_id, &req.file_id)
+        .await
+        .change_context(errors::ApiErrorResponse::FileNotFound)
+        .attach_printable("Unable to retrieve file_metadata")?;
+    let (url, expires_at) = helpers::get_file_download_url(&state, &file_metadata_object)
+        .await?
+        .ok_or(errors::ApiErrorResponse::FileProviderNotSupported {
+            message: "Download URLs are not supported for this file".to_string(),
+        })
+        .into_report()?;
+    Ok(ApplicationResponse::Json(files::FileDownloadUrlResponse {
+        file_id: req.file_id,
+        url,
+        expires_at,
+    }))
+}
@@ -1,12 +1,16 @@
+use std::time::Duration;
+
 use actix_multipart::Field;
 use common_utils::errors::CustomResult;
 use error_stack::{IntoReport, ResultExt};
+use external_services::file_storage::FileStorageError;
 use futures::TryStreamExt;
+use time::PrimitiveDateTime;
 
 use crate::{
     core::{
         errors::{self, StorageErrorExt},
-        files, payments, utils,
+        payments, utils,
     },
     routes::AppState,
     services,
@@ -30,35 +34,111 @@ pub async fn get_file_purpose(field: &mut Field) -> Option<api::FilePurpose> {
     }
 }
 
+/// The key that a file of the merchant is stored under, prefixed with the merchant ID so that the
+/// files of each merchant are stored apart.
+pub fn get_file_key(merchant_id: &str, file_id: &str) -> String {
+    format!("{merchant_id}/{file_id}")
+}
+
 pub async fn upload_file(
-    #[cfg(feature = "s3")] state: &AppState,
+    state: &AppState,
     file_key: String,
     file: Vec<u8>,
+    content_type: &str,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
-    #[cfg(feature = "s3")]
-    return files::s3_utils::upload_file_to_s3(state, file_key, file).await;
-    #[cfg(not(feature = "s3"))]
-    return files::fs_utils::save_file_to_fs(file_key, file);
+    state
+        .file_storage_client
+        .upload_file(&file_key, file, content_type)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to upload file")
 }
 
 pub async fn delete_file(
-    #[cfg(feature = "s3")] state: &AppState,
+    state: &AppState,
     file_key: String,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
-    #[cfg(feature = "s3")]
-    return files::s3_utils::delete_file_from_s3(state, file_key).await;
-    #[cfg(not(feature = "s3"))]
-    return files::fs_utils::delete_file_from_fs(file_key);
+    state
+        .file_storage_client
+        .delete_file(&file_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to delete file")
 }
 
 pub async fn retrieve_file(
-    #[cfg(feature = "s3")] state: &AppState,
+    state: &AppState,
     file_key: String,
 ) -> CustomResult<Vec<u8>, errors::ApiErrorResponse> {
-    #[cfg(feature = "s3")]
-    return files::s3_utils::retrieve_file_from_s3(state, file_key).await;
-    #[cfg(not(feature = "s3"))]
-    return files::fs_utils::retrieve_file_from_fs(file_key);
+    state
+        .file_storage_client
+        .retrieve_file(&file_key)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve file")
+}
+
+/// Generates a pre-signed URL that the file can be downloaded from, along with the time at which
+/// the URL expires. Returns `None` if the file is not stored by the router, or if the file storage
+/// backend does not support pre-signed URLs.
+pub async fn get_file_download_url(
+    state: &AppState,
+    file_metadata: &diesel_models::file::FileMetadata,
+) -> CustomResult<Option<(String, PrimitiveDateTime)>, errors::ApiErrorResponse> {
+    let provider_file_id = match (
+        file_metadata.file_upload_provider,
+        file_metadata.provider_file_id.as_ref(),
+        file_metadata.available,
+    ) {
+        (Some(diesel_models::enums::FileUploadProvider::Router), Some(provider_file_id), true) => {
+            provider_file_id
+        }
+        _ => return Ok(None),
+    };
+    let expires_in =
+        Duration::from_secs(state.conf.file_upload_config.download_url_expiry_in_seconds);
+    let expires_at = common_utils::date_time::now() + expires_in;
+
+    match state
+        .file_storage_client
+        .get_presigned_url(provider_file_id, expires_in)
+        .await
+    {
+        Ok(url) => Ok(Some((url, expires_at))),
+        Err(error) => match error.current_context() {
+            FileStorageError::PresignedUrlNotSupported => Ok(None),
+            _ => Err(error
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to generate pre-signed file download URL")),
+        },
+    }
+}
+
+/// Validates the size and the content type of the file against the files accepted by the router.
+pub fn validate_file_size_and_content_type(
+    state: &AppState,
+    create_file_request: &api::CreateFileRequest,
+) -> CustomResult<(), errors::ApiErrorResponse> {
+    let file_upload_config = &state.conf.file_upload_config;
+    if create_file_request.file_size > file_upload_config.max_file_size_in_bytes {
+        Err(errors::ApiErrorResponse::FileValidationFailed {
+            reason: format!(
+                "file size exceeds the maximum of {} bytes",
+                file_upload_config.max_file_size_in_bytes
+            ),
+        })?
+    }
+    let content_type = create_file_request.file_type.essence_str();
+    if !file_upload_config
+        .allowed_content_types
+        .iter()
+        .any(|allowed_content_type| allowed_content_type.eq_ignore_ascii_case(content_type))
+    {
+        Err(errors::ApiErrorResponse::FileValidationFailed {
+            reason: format!("file type {content_type} is not supported"),
+        })?
+    }
+    Ok(())
 }
 
 pub async fn validate_file_upload(
@@ -66,6 +146,7 @@ pub async fn validate_file_upload(
     merchant_account: domain::MerchantAccount,
     create_file_request: api::CreateFileRequest,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
+    validate_file_size_and_content_type(state, &create_file_request)?;
     //File Validation based on the purpose of file upload
     match create_file_request.purpose {
         api::FilePurpose::DisputeEvidence => {
@@ -133,12 +214,7 @@ pub async fn delete_file_using_file_id(
     };
     match provider {
         diesel_models::enums::FileUploadProvider::Router => {
-            delete_file(
-                #[cfg(feature = "s3")]
-                state,
-                provider_file_id,
-            )
-            .await
+            delete_file(state, provider_file_id).await
         }
         _ => Err(errors::ApiErrorResponse::FileProviderNotSupported {
             message: "Not Supported because provider is not Router".to_string(),
@@ -233,14 +309,7 @@ pub async fn retrieve_file_and_provider_file_id_from_file_id(
             };
             match provider {
                 diesel_models::enums::FileUploadProvider::Router => Ok((
-                    Some(
-                        retrieve_file(
-                            #[cfg(feature = "s3")]
-                            state,
-                            provider_file_id.clone(),
-                        )
-                        .await?,
-                    ),
+                    Some(retrieve_file(state, provider_file_id.clone()).await?),
                     Some(provider_file_id),
                 )),
                 _ => {
@@ -263,7 +332,7 @@ pub async fn retrieve_file_and_provider_file_id_from_file_id(
     }
 }
 
-//Upload file to connector if it supports / store it in S3 and return file_upload_provider, provider_file_id accordingly
+//Upload file to connector if it supports / store it in the file storage and return file_upload_provider, provider_file_id accordingly
 pub async fn upload_and_get_provider_provider_file_id_profile_id(
     state: &AppState,
     merchant_account: &domain::MerchantAccount,
@@ -365,10 +434,10 @@ pub async fn upload_and_get_provider_provider_file_id_profile_id(
                 ))
             } else {
                 upload_file(
-                    #[cfg(feature = "s3")]
                     state,
                     file_key.clone(),
                     create_file_request.file.clone(),
+                    create_file_request.file_type.essence_str(),
                 )
                 .await?;
                 Ok((
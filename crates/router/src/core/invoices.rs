@@ -0,0 +1,1164 @@
+pub mod pdf;
+
+use api_models::{
+    invoices::{
+        InvoiceCreateRequest, InvoiceLineItem, InvoiceLineItemRequest, InvoicePageDetails,
+        InvoicePageLineItem, InvoicePageTaxLine, InvoiceResponse, InvoiceTaxLine,
+        InvoiceTaxLineRequest,
+    },
+    payments::HeaderPayload,
+};
+use common_utils::{date_time, ext_traits::ValueExt, pii};
+use error_stack::{report, IntoReport, ResultExt};
+use masking::ExposeInterface;
+use router_env::{instrument, logger, tracing};
+#[cfg(feature = "email")]
+use time::PrimitiveDateTime;
+
+use self::pdf::{Column, Font, PdfWriter};
+#[cfg(feature = "email")]
+use super::transactional_emails;
+use super::{
+    errors::{self, RouterResult, StorageErrorExt},
+    payment_link,
+    payment_methods::PaymentMethodRetrieve,
+    payments,
+};
+#[cfg(feature = "email")]
+use crate::configs::settings;
+use crate::{
+    consts,
+    db::StorageInterface,
+    errors::RouterResponse,
+    routes::AppState,
+    services,
+    types::{
+        api, domain,
+        storage::{self, enums as storage_enums},
+    },
+    utils::{self, OptionExt},
+};
+
+#[cfg(feature = "email")]
+const INVOICE_REMINDER_TAG: &str = "INVOICE";
+#[cfg(feature = "email")]
+const INVOICE_REMINDER_NAME: &str = "INVOICE_REMINDER";
+#[cfg(feature = "email")]
+const INVOICE_REMINDER_RUNNER: &str = "INVOICE_REMINDER_WORKFLOW";
+
+const MAX_LINE_ITEMS: usize = 100;
+const MAX_TAX_LINES: usize = 10;
+const MAX_DESCRIPTION_LENGTH: usize = 255;
+const MAX_TAX_NAME_LENGTH: usize = 64;
+const MAX_TAX_RATE_BASIS_POINTS: u16 = 10_000;
+
+/// The number of characters of a line item description that fit in its column of the PDF.
+const PDF_DESCRIPTION_LENGTH: usize = 44;
+
+/// The number of times the status of an invoice is updated to follow its payment, the invoice
+/// being read again whenever it was updated concurrently.
+const MAX_INVOICE_STATUS_UPDATE_ATTEMPTS: usize = 3;
+
+#[instrument(skip(state))]
+pub async fn create_invoice(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    request: InvoiceCreateRequest,
+) -> RouterResponse<InvoiceResponse> {
+    validate_invoice_request(&request)?;
+
+    let db = &*state.store;
+    let merchant_id = merchant_account.merchant_id;
+    db.find_customer_by_customer_id_merchant_id(&request.customer_id, &merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+
+    let amounts = compute_invoice_amounts(
+        &request.line_items,
+        request.tax_lines.as_deref().unwrap_or_default(),
+    )
+    .ok_or(errors::ApiErrorResponse::InvalidRequestData {
+        message: "the amounts of the invoice are too large".to_string(),
+    })
+    .into_report()?;
+    let encode_lines = |lines: serde_json::Result<serde_json::Value>| {
+        lines
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to encode the lines of the invoice")
+    };
+    let current_time = date_time::now();
+
+    let invoice_new = storage::InvoiceNew {
+        invoice_id: utils::generate_id(consts::ID_LENGTH, "inv"),
+        merchant_id,
+        customer_id: request.customer_id,
+        status: storage_enums::InvoiceStatus::Draft,
+        currency: request.currency,
+        line_items: encode_lines(serde_json::to_value(&amounts.line_items))?,
+        tax_lines: encode_lines(serde_json::to_value(&amounts.tax_lines))?,
+        subtotal: amounts.subtotal,
+        tax_amount: amounts.tax_amount,
+        total_amount: amounts.total_amount,
+        description: request.description,
+        due_date: request.due_date,
+        metadata: request.metadata.map(ExposeInterface::expose),
+        created_at: Some(current_time),
+        last_modified_at: Some(current_time),
+    };
+
+    let invoice = db.insert_invoice(invoice_new).await.to_duplicate_response(
+        errors::ApiErrorResponse::GenericDuplicateError {
+            message: "invoice already exists!".to_string(),
+        },
+    )?;
+
+    Ok(services::ApplicationResponse::Json(get_invoice_response(
+        &state.conf.server.base_url,
+        invoice,
+    )?))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_invoice(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    invoice_id: String,
+) -> RouterResponse<InvoiceResponse> {
+    let invoice = state
+        .store
+        .find_invoice_by_merchant_id_invoice_id(&merchant_account.merchant_id, &invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(get_invoice_response(
+        &state.conf.server.base_url,
+        invoice,
+    )?))
+}
+
+/// Finalizes a draft invoice, creating the payment that collects it along with its payment link.
+/// The invoice is emailed to the customer and reminders are scheduled until it is paid.
+#[instrument(skip(state, merchant_account, key_store))]
+pub async fn finalize_invoice<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    invoice_id: String,
+) -> RouterResponse<InvoiceResponse> {
+    let db = &*state.store;
+    let invoice = db
+        .find_invoice_by_merchant_id_invoice_id(&merchant_account.merchant_id, &invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+
+    if invoice.status != storage_enums::InvoiceStatus::Draft {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!(
+                "invoice cannot be finalized as its status is {}",
+                invoice.status
+            ),
+        })
+        .into_report();
+    }
+
+    // The invoice can still be paid for a while after its due date, after which its payment
+    // link expires and the payment is cancelled
+    let link_expiry = invoice
+        .due_date
+        .saturating_add(time::Duration::seconds(i64::from(
+            state.conf.invoice.payment_window_after_due_in_seconds,
+        )));
+    if link_expiry <= date_time::now() {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "invoice cannot be finalized as it can no longer be paid".to_string(),
+        })
+        .into_report();
+    }
+
+    let (line_items, tax_lines) = parse_invoice_lines(&invoice)?;
+    let order_details =
+        line_items
+            .into_iter()
+            .map(|line_item| api_models::payments::OrderDetailsWithAmount {
+                product_name: line_item.description,
+                quantity: line_item.quantity,
+                amount: line_item.unit_amount,
+                product_img_link: None,
+            })
+            .chain(tax_lines.into_iter().map(|tax_line| {
+                api_models::payments::OrderDetailsWithAmount {
+                    product_name: tax_line.name,
+                    quantity: 1,
+                    amount: tax_line.amount,
+                    product_img_link: None,
+                }
+            }))
+            .collect();
+
+    let payment_request = api_models::payments::PaymentsRequest {
+        amount: Some(invoice.total_amount.into()),
+        currency: Some(invoice.currency),
+        customer_id: Some(invoice.customer_id.clone()),
+        confirm: Some(false),
+        description: Some(
+            invoice
+                .description
+                .clone()
+                .unwrap_or_else(|| format!("Invoice {}", invoice.invoice_id)),
+        ),
+        order_details: Some(order_details),
+        payment_link_object: Some(api_models::payments::PaymentLinkObject {
+            link_expiry: Some(link_expiry),
+            merchant_custom_domain_name: None,
+            payment_link_config: None,
+            reusable_payment_link_id: None,
+            invoice_id: Some(invoice.invoice_id.clone()),
+        }),
+        ..Default::default()
+    };
+
+    let payments_response =
+        match payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Ctx>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store,
+            payments::PaymentCreate,
+            payment_request,
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+        .await?
+        {
+            services::ApplicationResponse::Json(payments_response)
+            | services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => {
+                payments_response
+            }
+            _ => Err(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Unexpected response while creating the payment of an invoice")?,
+        };
+
+    let payment_id = payments_response
+        .payment_id
+        .get_required_value("payment_id")
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+    let payment_link = payments_response
+        .payment_link
+        .get_required_value("payment_link")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Payment link was not created for the payment of an invoice")?;
+
+    // The invoice is finalized only if it is still a draft. Invoices that were finalized or
+    // voided concurrently keep their status, and the payment created here is discarded so that
+    // its payment link cannot be paid.
+    let invoice = match db
+        .update_invoice(
+            invoice,
+            storage::InvoiceUpdate::Finalize {
+                payment_id: payment_id.clone(),
+                payment_link_id: payment_link.payment_link_id.clone(),
+            },
+        )
+        .await
+    {
+        Ok(invoice) => invoice,
+        Err(error) if error.current_context().is_db_not_found() => {
+            discard_invoice_payment(
+                &state,
+                &merchant_account,
+                &payment_id,
+                &payment_link.payment_link_id,
+            )
+            .await;
+            return Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "invoice cannot be finalized as it was updated concurrently".to_string(),
+            })
+            .into_report();
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to finalize invoice")?,
+    };
+
+    #[cfg(feature = "email")]
+    if let Some(payment_id) = &invoice.payment_id {
+        transactional_emails::trigger_invoice_issued_email(
+            &state,
+            &merchant_account,
+            payment_id,
+            &invoice.invoice_id,
+        )
+        .await;
+        // The invoice is already finalized, so that failing to schedule its reminders does not
+        // fail the request
+        if let Err(error) = add_invoice_reminder_task(db, &state.conf.invoice, &invoice).await {
+            logger::error!(?error, "Failed to schedule the reminders of the invoice");
+        }
+    }
+
+    Ok(services::ApplicationResponse::Json(get_invoice_response(
+        &state.conf.server.base_url,
+        invoice,
+    )?))
+}
+
+/// Voids an invoice so that it can no longer be paid. The payment of an open invoice is cancelled
+/// as long as the customer has not confirmed it.
+#[instrument(skip(state, merchant_account))]
+pub async fn void_invoice(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    invoice_id: String,
+) -> RouterResponse<InvoiceResponse> {
+    let db = &*state.store;
+    let invoice = db
+        .find_invoice_by_merchant_id_invoice_id(&merchant_account.merchant_id, &invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+
+    let cannot_be_voided = |reason: &str| {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: format!("invoice cannot be voided as {reason}"),
+        })
+        .into_report()
+    };
+
+    match invoice.status {
+        storage_enums::InvoiceStatus::Draft => {}
+        storage_enums::InvoiceStatus::Open => {
+            if let Some(payment_id) = &invoice.payment_id {
+                let payment_intent = payment_link::cancel_payment_of_payment_link(
+                    &state,
+                    &merchant_account.merchant_id,
+                    payment_id,
+                    merchant_account.storage_scheme,
+                    "invoice_voided",
+                )
+                .await?;
+                if payment_intent.is_none() {
+                    return cannot_be_voided("its payment has already been confirmed");
+                }
+            }
+        }
+        storage_enums::InvoiceStatus::Processing => {
+            return cannot_be_voided("its payment is being processed");
+        }
+        storage_enums::InvoiceStatus::Paid | storage_enums::InvoiceStatus::Void => {
+            return cannot_be_voided(&format!("its status is {}", invoice.status));
+        }
+    }
+
+    // The invoice is voided only if its status has not changed since it was checked
+    let invoice = match db
+        .update_invoice(
+            invoice,
+            storage::InvoiceUpdate::StatusUpdate {
+                status: storage_enums::InvoiceStatus::Void,
+            },
+        )
+        .await
+    {
+        Ok(invoice) => invoice,
+        Err(error) if error.current_context().is_db_not_found() => {
+            return cannot_be_voided("it was updated concurrently");
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to void invoice")?,
+    };
+
+    Ok(services::ApplicationResponse::Json(get_invoice_response(
+        &state.conf.server.base_url,
+        invoice,
+    )?))
+}
+
+/// Cancels the payment created for an invoice that could not be finalized and expires its payment
+/// link. Failing to do so is logged, as the payment link expires at its fulfilment time anyway.
+async fn discard_invoice_payment(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_id: &str,
+    payment_link_id: &str,
+) {
+    let result = async {
+        payment_link::cancel_payment_of_payment_link(
+            state,
+            &merchant_account.merchant_id,
+            payment_id,
+            merchant_account.storage_scheme,
+            "invoice_not_finalized",
+        )
+        .await?;
+
+        state
+            .store
+            .expire_payment_link(payment_link_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+        Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(())
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(
+            ?error,
+            payment_id,
+            "Failed to discard the payment of an invoice that could not be finalized"
+        );
+    }
+}
+
+/// Renders the PDF of an invoice, which merchants can also retrieve for draft invoices to
+/// preview them.
+#[instrument(skip(state, merchant_account, key_store))]
+pub async fn retrieve_invoice_pdf(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    invoice_id: String,
+) -> RouterResponse<()> {
+    let invoice = state
+        .store
+        .find_invoice_by_merchant_id_invoice_id(&merchant_account.merchant_id, &invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+
+    get_invoice_pdf_response(&state, &merchant_account, &key_store, invoice).await
+}
+
+/// Renders the PDF of a finalized invoice for its customer.
+#[instrument(skip(state, merchant_account, key_store))]
+pub async fn retrieve_hosted_invoice_pdf(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    invoice_id: String,
+) -> RouterResponse<()> {
+    let invoice = find_finalized_invoice(&*state.store, &merchant_account, &invoice_id).await?;
+
+    get_invoice_pdf_response(&state, &merchant_account, &key_store, invoice).await
+}
+
+/// Renders the page on which customers view a finalized invoice and pay it through its payment
+/// link.
+#[instrument(skip(state, merchant_account))]
+pub async fn retrieve_hosted_invoice(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    invoice_id: String,
+) -> RouterResponse<()> {
+    let db = &*state.store;
+    let invoice = find_finalized_invoice(db, &merchant_account, &invoice_id).await?;
+    let details =
+        get_invoice_page_details(db, &state.conf.server.base_url, &merchant_account, &invoice)
+            .await?;
+
+    Ok(services::ApplicationResponse::Html {
+        template: services::HtmlTemplate::Invoice,
+        context: serde_json::json!({ "details": details }),
+    })
+}
+
+/// Updates the status of the invoice collected by the payment, if any, to follow the status of
+/// the payment. Invoices that have been paid or voided are left as they are, including when they
+/// are paid or voided concurrently. Failing to update the invoice is logged and does not fail the
+/// payment.
+#[instrument(skip_all, fields(payment_id = %payment_intent.payment_id))]
+pub async fn update_invoice_status_with_payment(
+    state: &AppState,
+    payment_intent: &storage::PaymentIntent,
+) {
+    let db = &*state.store;
+    let status = get_invoice_status(payment_intent.status);
+    let result = async {
+        for _ in 0..MAX_INVOICE_STATUS_UPDATE_ATTEMPTS {
+            let Some(invoice) = db
+                .find_invoice_by_merchant_id_payment_id(
+                    &payment_intent.merchant_id,
+                    &payment_intent.payment_id,
+                )
+                .await
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to find the invoice of the payment")?
+            else {
+                return Ok(());
+            };
+
+            let is_updatable = matches!(
+                invoice.status,
+                storage_enums::InvoiceStatus::Open | storage_enums::InvoiceStatus::Processing
+            );
+            if !is_updatable || invoice.status == status {
+                return Ok(());
+            }
+            match db
+                .update_invoice(invoice, storage::InvoiceUpdate::StatusUpdate { status })
+                .await
+            {
+                Ok(_) => return Ok(()),
+                Err(error) if error.current_context().is_db_not_found() => {
+                    logger::info!("Invoice was updated concurrently, reading it again");
+                }
+                Err(error) => {
+                    return Err(error)
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Failed to update the status of the invoice");
+                }
+            }
+        }
+        Err(report!(errors::ApiErrorResponse::InternalServerError))
+            .attach_printable("Invoice was updated concurrently too many times")
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(
+            ?error,
+            "Failed to update the status of the invoice of the payment"
+        );
+    }
+}
+
+/// Sends a reminder to the customer of an invoice that is still open. Returns the time at which
+/// the next reminder is due, if any reminders are left to send.
+#[cfg(feature = "email")]
+#[instrument(skip_all, fields(invoice_id = %tracking_data.invoice_id))]
+pub async fn send_invoice_reminder(
+    state: &AppState,
+    tracking_data: &storage::InvoiceReminderWorkflow,
+    reminders_sent: i32,
+) -> RouterResult<Option<PrimitiveDateTime>> {
+    let invoice = state
+        .store
+        .find_invoice_by_merchant_id_invoice_id(
+            &tracking_data.merchant_id,
+            &tracking_data.invoice_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+
+    if invoice.status != storage_enums::InvoiceStatus::Open {
+        logger::info!(status = %invoice.status, "Invoice is no longer open, skipping");
+        return Ok(None);
+    }
+
+    let payment_id = invoice
+        .payment_id
+        .get_required_value("payment_id")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Open invoice has no payment")?;
+    transactional_emails::send_transactional_email(
+        state,
+        &storage::TransactionalEmailWorkflow {
+            merchant_id: invoice.merchant_id,
+            event: api_models::admin::TransactionalEmailEvent::InvoiceReminder,
+            payment_id,
+            refund_id: None,
+            payment_link_id: None,
+            invoice_id: Some(invoice.invoice_id),
+        },
+    )
+    .await?;
+
+    let invoice_conf = &state.conf.invoice;
+    Ok(
+        (reminders_sent + 1 < i32::from(invoice_conf.max_reminders)).then(|| {
+            date_time::now().saturating_add(time::Duration::seconds(i64::from(
+                invoice_conf.reminder_interval_in_seconds,
+            )))
+        }),
+    )
+}
+
+/// Schedules the reminders of an invoice that has been finalized. The first reminder is sent
+/// ahead of the due date, though never sooner than the interval between reminders.
+#[cfg(feature = "email")]
+#[instrument(skip_all)]
+async fn add_invoice_reminder_task(
+    db: &dyn StorageInterface,
+    invoice_conf: &settings::InvoiceSettings,
+    invoice: &storage::Invoice,
+) -> RouterResult<()> {
+    if invoice_conf.max_reminders == 0 {
+        return Ok(());
+    }
+
+    let current_time = date_time::now();
+    let reminder_interval =
+        time::Duration::seconds(i64::from(invoice_conf.reminder_interval_in_seconds));
+    let schedule_time = invoice
+        .due_date
+        .saturating_sub(reminder_interval)
+        .max(current_time.saturating_add(reminder_interval));
+
+    let tracking_data = storage::InvoiceReminderWorkflow {
+        merchant_id: invoice.merchant_id.clone(),
+        invoice_id: invoice.invoice_id.clone(),
+    };
+    let invoice_reminder_workflow_model = serde_json::to_value(&tracking_data)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("unable to serialize invoice reminder tracker: {tracking_data:?}")
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: format!("{INVOICE_REMINDER_RUNNER}_{}", invoice.invoice_id),
+        name: Some(String::from(INVOICE_REMINDER_NAME)),
+        tag: vec![String::from(INVOICE_REMINDER_TAG)],
+        runner: Some(String::from(INVOICE_REMINDER_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(schedule_time),
+        rule: String::new(),
+        tracking_data: invoice_reminder_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting invoice reminder task to process_tracker: invoice_id: {}",
+                invoice.invoice_id
+            )
+        })?;
+
+    Ok(())
+}
+
+pub fn get_hosted_invoice_url(base_url: &str, merchant_id: &str, invoice_id: &str) -> String {
+    format!("{base_url}/invoices/hosted/{merchant_id}/{invoice_id}")
+}
+
+pub fn get_hosted_invoice_pdf_url(base_url: &str, merchant_id: &str, invoice_id: &str) -> String {
+    format!(
+        "{}/pdf",
+        get_hosted_invoice_url(base_url, merchant_id, invoice_id)
+    )
+}
+
+/// Finds an invoice that has been finalized, draft invoices not being shown to customers.
+async fn find_finalized_invoice(
+    db: &dyn StorageInterface,
+    merchant_account: &domain::MerchantAccount,
+    invoice_id: &str,
+) -> RouterResult<storage::Invoice> {
+    let invoice = db
+        .find_invoice_by_merchant_id_invoice_id(&merchant_account.merchant_id, invoice_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+
+    if invoice.status == storage_enums::InvoiceStatus::Draft {
+        return Err(errors::ApiErrorResponse::InvoiceNotFound).into_report();
+    }
+    Ok(invoice)
+}
+
+fn get_invoice_status(payment_status: storage_enums::IntentStatus) -> storage_enums::InvoiceStatus {
+    match payment_status {
+        storage_enums::IntentStatus::Succeeded => storage_enums::InvoiceStatus::Paid,
+        storage_enums::IntentStatus::Processing
+        | storage_enums::IntentStatus::RequiresCapture
+        | storage_enums::IntentStatus::RequiresMerchantAction
+        | storage_enums::IntentStatus::PartiallyCaptured => {
+            storage_enums::InvoiceStatus::Processing
+        }
+        storage_enums::IntentStatus::Cancelled => storage_enums::InvoiceStatus::Void,
+        // The customer can still pay the invoice, possibly with another payment method
+        storage_enums::IntentStatus::RequiresPaymentMethod
+        | storage_enums::IntentStatus::RequiresConfirmation
+        | storage_enums::IntentStatus::RequiresCustomerAction
+        | storage_enums::IntentStatus::Failed => storage_enums::InvoiceStatus::Open,
+    }
+}
+
+fn validate_invoice_request(request: &InvoiceCreateRequest) -> RouterResult<()> {
+    let invalid_request = |message: String| {
+        Err(errors::ApiErrorResponse::InvalidRequestData { message }).into_report()
+    };
+    let is_valid_length =
+        |text: &str, max_length: usize| (1..=max_length).contains(&text.chars().count());
+    let tax_lines = request.tax_lines.as_deref().unwrap_or_default();
+
+    if request.line_items.is_empty() || request.line_items.len() > MAX_LINE_ITEMS {
+        invalid_request(format!(
+            "line_items must contain between 1 and {MAX_LINE_ITEMS} items"
+        ))
+    } else if request.line_items.iter().any(|line_item| {
+        line_item.quantity == 0
+            || line_item.unit_amount < 1
+            || !is_valid_length(&line_item.description, MAX_DESCRIPTION_LENGTH)
+    }) {
+        invalid_request(format!(
+            "line items must have a quantity and a unit_amount greater than 0, and a description of at most {MAX_DESCRIPTION_LENGTH} characters"
+        ))
+    } else if tax_lines.len() > MAX_TAX_LINES {
+        invalid_request(format!(
+            "tax_lines must contain at most {MAX_TAX_LINES} items"
+        ))
+    } else if tax_lines.iter().any(|tax_line| {
+        tax_line.rate_basis_points > MAX_TAX_RATE_BASIS_POINTS
+            || !is_valid_length(&tax_line.name, MAX_TAX_NAME_LENGTH)
+    }) {
+        invalid_request(format!(
+            "tax lines must have a rate_basis_points of at most {MAX_TAX_RATE_BASIS_POINTS}, and a name of at most {MAX_TAX_NAME_LENGTH} characters"
+        ))
+    } else if request.description.as_deref().map_or(false, |description| {
+        description.chars().count() > MAX_DESCRIPTION_LENGTH
+    }) {
+        invalid_request(format!(
+            "description must be at most {MAX_DESCRIPTION_LENGTH} characters long"
+        ))
+    } else if request.due_date <= date_time::now() {
+        invalid_request("due_date must be in the future".to_string())
+    } else {
+        Ok(())
+    }
+}
+
+#[derive(Debug)]
+struct InvoiceAmounts {
+    line_items: Vec<InvoiceLineItem>,
+    tax_lines: Vec<InvoiceTaxLine>,
+    subtotal: i64,
+    tax_amount: i64,
+    total_amount: i64,
+}
+
+/// Computes the amounts of the line items and tax lines of an invoice along with its totals, or
+/// `None` if any of them overflows.
+fn compute_invoice_amounts(
+    line_items: &[InvoiceLineItemRequest],
+    tax_lines: &[InvoiceTaxLineRequest],
+) -> Option<InvoiceAmounts> {
+    let line_items = line_items
+        .iter()
+        .map(|line_item| {
+            Some(InvoiceLineItem {
+                description: line_item.description.clone(),
+                quantity: line_item.quantity,
+                unit_amount: line_item.unit_amount,
+                amount: line_item
+                    .unit_amount
+                    .checked_mul(i64::from(line_item.quantity))?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let subtotal = line_items.iter().try_fold(0_i64, |subtotal, line_item| {
+        subtotal.checked_add(line_item.amount)
+    })?;
+
+    let tax_lines = tax_lines
+        .iter()
+        .map(|tax_line| {
+            Some(InvoiceTaxLine {
+                name: tax_line.name.clone(),
+                rate_basis_points: tax_line.rate_basis_points,
+                amount: compute_tax_amount(subtotal, tax_line.rate_basis_points)?,
+            })
+        })
+        .collect::<Option<Vec<_>>>()?;
+    let tax_amount = tax_lines.iter().try_fold(0_i64, |tax_amount, tax_line| {
+        tax_amount.checked_add(tax_line.amount)
+    })?;
+
+    Some(InvoiceAmounts {
+        total_amount: subtotal.checked_add(tax_amount)?,
+        line_items,
+        tax_lines,
+        subtotal,
+        tax_amount,
+    })
+}
+
+/// The tax on the subtotal at the rate, rounded half up to the lowest denomination.
+fn compute_tax_amount(subtotal: i64, rate_basis_points: u16) -> Option<i64> {
+    subtotal
+        .checked_mul(i64::from(rate_basis_points))?
+        .checked_add(5_000)
+        .map(|tax_amount| tax_amount / 10_000)
+}
+
+fn parse_invoice_lines(
+    invoice: &storage::Invoice,
+) -> RouterResult<(Vec<InvoiceLineItem>, Vec<InvoiceTaxLine>)> {
+    let line_items = invoice
+        .line_items
+        .clone()
+        .parse_value::<Vec<InvoiceLineItem>>("InvoiceLineItem")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse the line items of the invoice")?;
+    let tax_lines = invoice
+        .tax_lines
+        .clone()
+        .parse_value::<Vec<InvoiceTaxLine>>("InvoiceTaxLine")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse the tax lines of the invoice")?;
+
+    Ok((line_items, tax_lines))
+}
+
+fn get_invoice_response(
+    base_url: &str,
+    invoice: storage::Invoice,
+) -> RouterResult<InvoiceResponse> {
+    let (line_items, tax_lines) = parse_invoice_lines(&invoice)?;
+    let is_finalized = invoice.status != storage_enums::InvoiceStatus::Draft;
+
+    Ok(InvoiceResponse {
+        hosted_invoice_url: is_finalized
+            .then(|| get_hosted_invoice_url(base_url, &invoice.merchant_id, &invoice.invoice_id)),
+        invoice_pdf_url: is_finalized.then(|| {
+            get_hosted_invoice_pdf_url(base_url, &invoice.merchant_id, &invoice.invoice_id)
+        }),
+        invoice_id: invoice.invoice_id,
+        merchant_id: invoice.merchant_id,
+        customer_id: invoice.customer_id,
+        status: invoice.status,
+        currency: invoice.currency,
+        line_items,
+        tax_lines,
+        subtotal: invoice.subtotal,
+        tax_amount: invoice.tax_amount,
+        total_amount: invoice.total_amount,
+        description: invoice.description,
+        due_date: invoice.due_date,
+        payment_id: invoice.payment_id,
+        payment_link_id: invoice.payment_link_id,
+        metadata: invoice.metadata.map(pii::SecretSerdeValue::new),
+        finalized_at: invoice.finalized_at,
+        paid_at: invoice.paid_at,
+        created_at: invoice.created_at,
+    })
+}
+
+/// The details of the invoice shown to its customer, on its hosted page and in its PDF. The
+/// invoice can be paid through its payment link while both are active.
+async fn get_invoice_page_details(
+    db: &dyn StorageInterface,
+    base_url: &str,
+    merchant_account: &domain::MerchantAccount,
+    invoice: &storage::Invoice,
+) -> RouterResult<InvoicePageDetails> {
+    let (line_items, tax_lines) = parse_invoice_lines(invoice)?;
+    let format_amount = |amount: i64| {
+        invoice
+            .currency
+            .to_currency_base_unit(amount)
+            .unwrap_or_else(|_| amount.to_string())
+    };
+
+    let link_to_pay = match invoice.payment_link_id.as_deref() {
+        Some(payment_link_id) if invoice.status == storage_enums::InvoiceStatus::Open => {
+            let payment_link = db
+                .find_payment_link_by_payment_link_id(payment_link_id)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+            let is_payable = payment_link.status == storage_enums::PaymentLinkStatus::Active
+                && payment_link
+                    .fulfilment_time
+                    .map_or(true, |fulfilment_time| fulfilment_time > date_time::now());
+            is_payable.then_some(payment_link.link_to_pay)
+        }
+        _ => None,
+    };
+
+    Ok(InvoicePageDetails {
+        invoice_id: invoice.invoice_id.clone(),
+        merchant_name: merchant_account
+            .merchant_name
+            .clone()
+            .map(|merchant_name| merchant_name.into_inner().expose())
+            .unwrap_or_else(|| merchant_account.merchant_id.clone()),
+        status: invoice.status,
+        currency: invoice.currency,
+        description: invoice.description.clone(),
+        line_items: line_items
+            .into_iter()
+            .map(|line_item| InvoicePageLineItem {
+                description: line_item.description,
+                quantity: line_item.quantity,
+                unit_amount: format_amount(line_item.unit_amount),
+                amount: format_amount(line_item.amount),
+            })
+            .collect(),
+        tax_lines: tax_lines
+            .into_iter()
+            .map(|tax_line| InvoicePageTaxLine {
+                name: tax_line.name,
+                rate: format!(
+                    "{}.{:02}%",
+                    tax_line.rate_basis_points / 100,
+                    tax_line.rate_basis_points % 100
+                ),
+                amount: format_amount(tax_line.amount),
+            })
+            .collect(),
+        subtotal: format_amount(invoice.subtotal),
+        tax_amount: format_amount(invoice.tax_amount),
+        total_amount: format_amount(invoice.total_amount),
+        due_date: invoice.due_date.date().to_string(),
+        is_overdue: invoice.status == storage_enums::InvoiceStatus::Open
+            && invoice.due_date < date_time::now(),
+        link_to_pay,
+        pdf_url: get_hosted_invoice_pdf_url(base_url, &invoice.merchant_id, &invoice.invoice_id),
+    })
+}
+
+async fn get_invoice_pdf_response(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    invoice: storage::Invoice,
+) -> RouterResponse<()> {
+    let db = &*state.store;
+    let base_url = state.conf.server.base_url.as_str();
+    let details = get_invoice_page_details(db, base_url, merchant_account, &invoice).await?;
+
+    let customer = db
+        .find_customer_optional_by_customer_id_merchant_id(
+            &invoice.customer_id,
+            &invoice.merchant_id,
+            key_store,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the customer of the invoice")?;
+    let bill_to = customer
+        .map(|customer| {
+            [
+                customer.name.map(|name| name.into_inner().expose()),
+                customer.email.map(|email| email.into_inner().expose()),
+            ]
+        })
+        .into_iter()
+        .flatten()
+        .flatten()
+        .chain(std::iter::once(format!(
+            "Customer reference: {}",
+            invoice.customer_id
+        )))
+        .collect::<Vec<_>>();
+
+    let issued_on = invoice.finalized_at.unwrap_or(invoice.created_at).date();
+    let hosted_invoice_url = (invoice.status != storage_enums::InvoiceStatus::Draft)
+        .then(|| get_hosted_invoice_url(base_url, &invoice.merchant_id, &invoice.invoice_id));
+
+    Ok(services::ApplicationResponse::FileData((
+        render_invoice_pdf(
+            &details,
+            &bill_to,
+            &issued_on.to_string(),
+            hosted_invoice_url.as_deref(),
+        ),
+        mime::APPLICATION_PDF,
+    )))
+}
+
+fn render_invoice_pdf(
+    details: &InvoicePageDetails,
+    bill_to: &[String],
+    issued_on: &str,
+    hosted_invoice_url: Option<&str>,
+) -> Vec<u8> {
+    const LABEL_X: u16 = 0;
+    const VALUE_X: u16 = 110;
+    const QUANTITY_X: u16 = 270;
+    const UNIT_AMOUNT_X: u16 = 320;
+    const AMOUNT_X: u16 = 410;
+
+    fn regular(x: u16, text: &str) -> Column<'_> {
+        Column {
+            x,
+            font: Font::Regular,
+            text,
+        }
+    }
+    fn bold(x: u16, text: &str) -> Column<'_> {
+        Column {
+            x,
+            font: Font::Bold,
+            text,
+        }
+    }
+
+    let with_currency = |amount: &str| format!("{amount} {}", details.currency);
+    let status = match details.status {
+        storage_enums::InvoiceStatus::Open if details.is_overdue => "Overdue".to_string(),
+        storage_enums::InvoiceStatus::Open => "Due".to_string(),
+        status => {
+            let status = status.to_string();
+            let mut characters = status.chars();
+            characters
+                .next()
+                .map(|first| first.to_uppercase().chain(characters).collect())
+                .unwrap_or(status)
+        }
+    };
+
+    let mut writer = PdfWriter::default();
+    writer.write_line(22, &[bold(LABEL_X, "INVOICE")]);
+    writer.write_line(12, &[bold(LABEL_X, &details.merchant_name)]);
+    writer.skip(12);
+
+    for (label, value) in [
+        ("Invoice number", details.invoice_id.as_str()),
+        ("Status", status.as_str()),
+        ("Issued on", issued_on),
+        ("Due on", details.due_date.as_str()),
+    ] {
+        writer.write_line(10, &[bold(LABEL_X, label), regular(VALUE_X, value)]);
+    }
+    writer.skip(12);
+
+    writer.write_line(10, &[bold(LABEL_X, "Bill to")]);
+    for line in bill_to {
+        writer.write_line(10, &[regular(LABEL_X, line)]);
+    }
+    if let Some(description) = &details.description {
+        writer.skip(8);
+        writer.write_line(10, &[regular(LABEL_X, description)]);
+    }
+    writer.skip(16);
+
+    writer.write_line(
+        10,
+        &[
+            bold(LABEL_X, "Description"),
+            bold(QUANTITY_X, "Qty"),
+            bold(UNIT_AMOUNT_X, "Unit price"),
+            bold(AMOUNT_X, "Amount"),
+        ],
+    );
+    writer.write_rule();
+    for line_item in &details.line_items {
+        writer.write_line(
+            10,
+            &[
+                regular(
+                    LABEL_X,
+                    &truncate(&line_item.description, PDF_DESCRIPTION_LENGTH),
+                ),
+                regular(QUANTITY_X, &line_item.quantity.to_string()),
+                regular(UNIT_AMOUNT_X, &line_item.unit_amount),
+                regular(AMOUNT_X, &line_item.amount),
+            ],
+        );
+    }
+    writer.write_rule();
+
+    writer.write_line(
+        10,
+        &[
+            regular(UNIT_AMOUNT_X, "Subtotal"),
+            regular(AMOUNT_X, &with_currency(&details.subtotal)),
+        ],
+    );
+    for tax_line in &details.tax_lines {
+        writer.write_line(
+            10,
+            &[
+                regular(
+                    UNIT_AMOUNT_X,
+                    &truncate(&format!("{} ({})", tax_line.name, tax_line.rate), 18),
+                ),
+                regular(AMOUNT_X, &with_currency(&tax_line.amount)),
+            ],
+        );
+    }
+    let total_label = if details.status == storage_enums::InvoiceStatus::Paid {
+        "Amount paid"
+    } else {
+        "Amount due"
+    };
+    writer.write_line(
+        12,
+        &[
+            bold(UNIT_AMOUNT_X, total_label),
+            bold(AMOUNT_X, &with_currency(&details.total_amount)),
+        ],
+    );
+
+    if let Some(hosted_invoice_url) = hosted_invoice_url {
+        writer.skip(24);
+        writer.write_line(10, &[bold(LABEL_X, "Pay online")]);
+        writer.write_line(9, &[regular(LABEL_X, hosted_invoice_url)]);
+    }
+
+    writer.finish()
+}
+
+/// Shortens text to at most the number of characters, marking that it has been shortened.
+fn truncate(text: &str, max_length: usize) -> String {
+    if text.chars().count() <= max_length {
+        text.to_string()
+    } else {
+        let mut truncated = text
+            .chars()
+            .take(max_length.saturating_sub(3))
+            .collect::<String>();
+        truncated.push_str("...");
+        truncated
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_compute_invoice_amounts() {
+        let line_items = [
+            InvoiceLineItemRequest {
+                description: "Consulting hours".to_string(),
+                quantity: 3,
+                unit_amount: 3333,
+            },
+            InvoiceLineItemRequest {
+                description: "Travel".to_string(),
+                quantity: 1,
+                unit_amount: 1,
+            },
+        ];
+        let tax_lines = [
+            InvoiceTaxLineRequest {
+                name: "VAT".to_string(),
+                rate_basis_points: 2000,
+            },
+            InvoiceTaxLineRequest {
+                name: "City tax".to_string(),
+                rate_basis_points: 125,
+            },
+        ];
+
+        let amounts = compute_invoice_amounts(&line_items, &tax_lines).unwrap();
+        assert_eq!(amounts.line_items[0].amount, 9999);
+        assert_eq!(amounts.subtotal, 10000);
+        assert_eq!(amounts.tax_lines[0].amount, 2000);
+        // 1.25% of 100.00 is 1.25, the tax of 100.04 would be rounded half up to 1.25 as well
+        assert_eq!(amounts.tax_lines[1].amount, 125);
+        assert_eq!(amounts.tax_amount, 2125);
+        assert_eq!(amounts.total_amount, 12125);
+
+        assert_eq!(compute_tax_amount(10004, 125), Some(125));
+        assert_eq!(compute_tax_amount(10040, 125), Some(126));
+
+        let overflowing_line_items = [InvoiceLineItemRequest {
+            description: "Overflow".to_string(),
+            quantity: u16::MAX,
+            unit_amount: i64::MAX,
+        }];
+        assert!(compute_invoice_amounts(&overflowing_line_items, &[]).is_none());
+    }
+}
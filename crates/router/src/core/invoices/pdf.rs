@@ -0,0 +1,248 @@
+//! A minimal writer of PDF documents made of lines of text, which is enough to render invoices
+//! without depending on a PDF library. Text is set in the standard Helvetica fonts, which PDF
+//! readers provide, and encoded with the Windows-1252 (WinAnsi) encoding. Characters that the
+//! encoding cannot represent are replaced with `?`.
+
+use std::fmt::Write;
+
+/// A4 page size, in points.
+const PAGE_WIDTH: u16 = 595;
+const PAGE_HEIGHT: u16 = 842;
+const MARGIN: u16 = 50;
+/// The space between two lines of text, in addition to the font size of the lower line.
+const LINE_SPACING: u16 = 6;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Font {
+    Regular,
+    Bold,
+}
+
+impl Font {
+    fn resource_name(self) -> &'static str {
+        match self {
+            Self::Regular => "F1",
+            Self::Bold => "F2",
+        }
+    }
+}
+
+/// Text written at an offset from the left margin of the page, in points.
+#[derive(Clone, Copy, Debug)]
+pub struct Column<'a> {
+    pub x: u16,
+    pub font: Font,
+    pub text: &'a str,
+}
+
+#[derive(Debug)]
+pub struct PdfWriter {
+    pages: Vec<String>,
+    /// The baseline of the last line written on the current page
+    cursor: u16,
+}
+
+impl Default for PdfWriter {
+    fn default() -> Self {
+        Self {
+            pages: vec![String::new()],
+            cursor: PAGE_HEIGHT - MARGIN,
+        }
+    }
+}
+
+impl PdfWriter {
+    /// Writes a line made of the columns below the previous line, starting a new page when the
+    /// current page is full.
+    pub fn write_line(&mut self, font_size: u16, columns: &[Column<'_>]) {
+        let line_height = font_size + LINE_SPACING;
+        if self.cursor < MARGIN + line_height {
+            self.pages.push(String::new());
+            self.cursor = PAGE_HEIGHT - MARGIN;
+        }
+        self.cursor -= line_height;
+
+        let y = self.cursor;
+        let content = self.current_page();
+        for column in columns {
+            let _ = writeln!(
+                content,
+                "BT /{} {font_size} Tf {} {y} Td ({}) Tj ET",
+                column.font.resource_name(),
+                MARGIN + column.x,
+                encode_text(column.text),
+            );
+        }
+    }
+
+    /// Draws a horizontal line across the page below the previous line.
+    pub fn write_rule(&mut self) {
+        self.skip(4);
+        let y = self.cursor;
+        let content = self.current_page();
+        let _ = writeln!(
+            content,
+            "0.5 w {MARGIN} {y} m {} {y} l S",
+            PAGE_WIDTH - MARGIN
+        );
+    }
+
+    /// Leaves an empty space below the previous line.
+    pub fn skip(&mut self, height: u16) {
+        self.cursor = self.cursor.saturating_sub(height).max(MARGIN);
+    }
+
+    fn current_page(&mut self) -> &mut String {
+        if self.pages.is_empty() {
+            self.pages.push(String::new());
+        }
+        let last_page = self.pages.len() - 1;
+        &mut self.pages[last_page]
+    }
+
+    /// Serializes the document. The catalog, the page tree and the fonts are the first objects,
+    /// followed by each page and its content stream.
+    pub fn finish(self) -> Vec<u8> {
+        const FIRST_PAGE_OBJECT: usize = 5;
+
+        let kids = (0..self.pages.len())
+            .map(|index| format!("{} 0 R", FIRST_PAGE_OBJECT + 2 * index))
+            .collect::<Vec<_>>()
+            .join(" ");
+        let mut objects = vec![
+            String::from("<< /Type /Catalog /Pages 2 0 R >>"),
+            format!(
+                "<< /Type /Pages /Kids [{kids}] /Count {} >>",
+                self.pages.len()
+            ),
+            String::from(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica /Encoding /WinAnsiEncoding >>",
+            ),
+            String::from(
+                "<< /Type /Font /Subtype /Type1 /BaseFont /Helvetica-Bold /Encoding /WinAnsiEncoding >>",
+            ),
+        ];
+        for (index, content) in self.pages.iter().enumerate() {
+            objects.push(format!(
+                "<< /Type /Page /Parent 2 0 R /MediaBox [0 0 {PAGE_WIDTH} {PAGE_HEIGHT}] \
+                 /Resources << /Font << /F1 3 0 R /F2 4 0 R >> >> /Contents {} 0 R >>",
+                FIRST_PAGE_OBJECT + 2 * index + 1
+            ));
+            objects.push(format!(
+                "<< /Length {} >>\nstream\n{content}endstream",
+                content.len()
+            ));
+        }
+
+        let mut document = String::from("%PDF-1.4\n");
+        let mut offsets = Vec::with_capacity(objects.len());
+        for (index, object) in objects.iter().enumerate() {
+            offsets.push(document.len());
+            let _ = write!(document, "{} 0 obj\n{object}\nendobj\n", index + 1);
+        }
+
+        let xref_offset = document.len();
+        let _ = write!(
+            document,
+            "xref\n0 {}\n0000000000 65535 f \n",
+            objects.len() + 1
+        );
+        for offset in offsets {
+            let _ = writeln!(document, "{offset:010} 00000 n ");
+        }
+        let _ = write!(
+            document,
+            "trailer\n<< /Size {} /Root 1 0 R >>\nstartxref\n{xref_offset}\n%%EOF\n",
+            objects.len() + 1
+        );
+
+        document.into_bytes()
+    }
+}
+
+/// Encodes text as the contents of a PDF string literal, escaping the delimiters of the literal
+/// and writing the characters outside of printable ASCII as octal WinAnsi codes, so that content
+/// streams remain ASCII.
+fn encode_text(text: &str) -> String {
+    let mut encoded = String::with_capacity(text.len());
+    for character in text.chars() {
+        match character {
+            '(' | ')' | '\\' => {
+                encoded.push('\\');
+                encoded.push(character);
+            }
+            ' '..='~' => encoded.push(character),
+            _ => {
+                let _ = write!(encoded, "\\{:03o}", to_win_ansi(character));
+            }
+        }
+    }
+    encoded
+}
+
+fn to_win_ansi(character: char) -> u8 {
+    match character {
+        '€' => 0x80,
+        '…' => 0x85,
+        '‘' => 0x91,
+        '’' => 0x92,
+        '“' => 0x93,
+        '”' => 0x94,
+        '•' => 0x95,
+        '–' => 0x96,
+        '—' => 0x97,
+        // Latin-1 characters share their code points with WinAnsi
+        _ => u8::try_from(u32::from(character))
+            .ok()
+            .filter(|code| *code >= 0xA0)
+            .unwrap_or(b'?'),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_encode_text() {
+        assert_eq!(encode_text("Total (EUR)"), "Total \\(EUR\\)");
+        assert_eq!(encode_text("a\\b"), "a\\\\b");
+        assert_eq!(encode_text("Café 10 €"), "Caf\\351 10 \\200");
+        assert_eq!(encode_text("日本\n"), "\\077\\077\\077");
+    }
+
+    #[test]
+    fn test_pdf_cross_reference_offsets() {
+        let mut writer = PdfWriter::default();
+        for line in 0..100 {
+            writer.write_line(
+                12,
+                &[Column {
+                    x: 0,
+                    font: Font::Regular,
+                    text: &format!("Line {line}"),
+                }],
+            );
+        }
+        let document = String::from_utf8(writer.finish()).unwrap();
+
+        assert!(document.starts_with("%PDF-1.4\n"));
+        assert!(document.contains("/Count 3"));
+
+        let xref_offset: usize = document
+            .rsplit("startxref\n")
+            .next()
+            .and_then(|tail| tail.lines().next())
+            .and_then(|offset| offset.parse().ok())
+            .unwrap();
+        assert!(document[xref_offset..].starts_with("xref\n0 11\n"));
+
+        let entries = document[xref_offset..].lines().skip(3).take(10);
+        for (index, entry) in entries.enumerate() {
+            let offset: usize = entry[..10].parse().unwrap();
+            assert!(document[offset..].starts_with(&format!("{} 0 obj\n", index + 1)));
+        }
+    }
+}
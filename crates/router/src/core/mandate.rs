@@ -1,9 +1,10 @@
 use api_models::payments;
-use common_utils::{ext_traits::Encode, pii};
+use common_utils::{date_time, ext_traits::Encode, pii};
 use diesel_models::enums as storage_enums;
-use error_stack::{report, ResultExt};
+use error_stack::{report, IntoReport, ResultExt};
 use futures::future;
 use router_env::{instrument, logger, tracing};
+use time::PrimitiveDateTime;
 
 use super::payments::helpers;
 use crate::{
@@ -20,9 +21,13 @@ use crate::{
         domain, storage,
         transformers::ForeignTryFrom,
     },
-    utils::OptionExt,
+    utils::{self, OptionExt},
 };
 
+const MANDATE_EXPIRY_TAG: &str = "MANDATE";
+const MANDATE_EXPIRY_NAME: &str = "MANDATE_EXPIRY";
+const MANDATE_EXPIRY_RUNNER: &str = "MANDATE_EXPIRY_WORKFLOW";
+
 #[instrument(skip(state))]
 pub async fn get_mandate(
     state: AppState,
@@ -148,6 +153,13 @@ pub async fn mandate_procedure<F, FData>(
 where
     FData: MandateBehaviour,
 {
+    // Payments the connector did not make must not count towards the frequency limit
+    if resp.request.get_mandate_id().is_some()
+        && (resp.response.is_err() || is_declined_attempt_status(resp.status))
+    {
+        release_mandate_usage(state, &resp.merchant_id, &resp.attempt_id).await;
+    }
+
     match resp.response {
         Err(_) => {}
         Ok(_) => match resp.request.get_mandate_id() {
@@ -260,11 +272,16 @@ where
                                     }
                                 )))
                         }));
-                        state
+                        let mandate = state
                             .store
                             .insert_mandate(new_mandate_data)
                             .await
                             .to_duplicate_response(errors::ApiErrorResponse::DuplicateMandate)?;
+                        // The mandate is still checked against its end date when it is used, the
+                        // payment should not fail for want of expiring it
+                        if let Err(error) = add_mandate_expiry_task(&*state.store, &mandate).await {
+                            logger::error!(?error, "Failed to schedule the expiry of the mandate");
+                        }
                         metrics::MANDATE_COUNT.add(
                             &metrics::CONTEXT,
                             1,
@@ -300,6 +317,276 @@ pub async fn retrieve_mandates_list(
     Ok(services::ApplicationResponse::Json(mandates_list))
 }
 
+#[instrument(skip(state))]
+pub async fn retrieve_mandate_payments(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    req: mandates::MandateId,
+    constraints: api_models::mandates::MandatePaymentListConstraints,
+) -> RouterResponse<Vec<api_models::mandates::MandatePaymentResponse>> {
+    let db = state.store.as_ref();
+    let mandate = db
+        .find_mandate_by_merchant_id_mandate_id(&merchant_account.merchant_id, &req.mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+    let mandate_usages = db
+        .find_mandate_usages_by_merchant_id_mandate_id_created_after(
+            &mandate.merchant_id,
+            &mandate.mandate_id,
+            mandate.created_at,
+            constraints.limit,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Unable to retrieve the payments made against the mandate")?;
+
+    Ok(services::ApplicationResponse::Json(
+        mandate_usages
+            .into_iter()
+            .map(
+                |mandate_usage| api_models::mandates::MandatePaymentResponse {
+                    payment_id: mandate_usage.payment_id,
+                    attempt_id: mandate_usage.attempt_id,
+                    amount: mandate_usage.amount,
+                    currency: mandate_usage.currency,
+                    connector: mandate_usage.connector,
+                    created_at: mandate_usage.created_at,
+                },
+            )
+            .collect(),
+    ))
+}
+
+/// Records a payment made against a mandate before the connector is called, checking the
+/// frequency limit of the mandate in the same transaction so that concurrent payments cannot
+/// exceed it. The recorded payments make up the payment history of the mandate.
+pub async fn reserve_mandate_usage<F, FData>(
+    state: &AppState,
+    router_data: &types::RouterData<F, FData, types::PaymentsResponseData>,
+    mandate_id: &str,
+) -> errors::RouterResult<()>
+where
+    FData: MandateBehaviour,
+{
+    let db = &*state.store;
+    let mandate = db
+        .find_mandate_by_merchant_id_mandate_id(&router_data.merchant_id, mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+    let mandate_usage = storage::MandateUsageNew {
+        merchant_id: router_data.merchant_id.clone(),
+        mandate_id: mandate.mandate_id,
+        payment_id: router_data.payment_id.clone(),
+        attempt_id: router_data.attempt_id.clone(),
+        amount: router_data.request.get_amount(),
+        currency: router_data.request.get_currency(),
+        connector: router_data.connector.clone(),
+        created_at: Some(date_time::now()),
+    };
+
+    let Some((frequency_limit, frequency_interval)) =
+        mandate.frequency_limit.zip(mandate.frequency_interval)
+    else {
+        return match db.insert_mandate_usage(mandate_usage).await {
+            // The payment attempt was recorded when it was sent to the connector before
+            Err(error) if error.current_context().is_db_unique_violation() => Ok(()),
+            result => result
+                .map(|_| ())
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to record the payment made against the mandate"),
+        };
+    };
+
+    let interval_start = get_frequency_interval_start(date_time::now(), frequency_interval)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Failed to compute the start of the mandate frequency interval")?;
+    let mandate_usage = db
+        .insert_mandate_usage_within_frequency_limit(mandate_usage, frequency_limit, interval_start)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to record the payment made against the mandate")?;
+
+    utils::when(mandate_usage.is_none(), || {
+        Err(report!(errors::ApiErrorResponse::MandateValidationFailed {
+            reason: format!(
+                "mandate allows at most {frequency_limit} payments per {frequency_interval}"
+            )
+        }))
+    })
+}
+
+/// Removes the payment recorded against a mandate when the connector has not made it. Failing to
+/// do so is logged, as it only leaves the payment counting towards the frequency limit.
+pub async fn release_mandate_usage(state: &AppState, merchant_id: &str, attempt_id: &str) {
+    if let Err(error) = state
+        .store
+        .delete_mandate_usage_by_merchant_id_attempt_id(merchant_id, attempt_id)
+        .await
+    {
+        logger::error!(
+            ?error,
+            "Failed to remove the failed payment made against the mandate"
+        );
+    }
+}
+
+/// Whether the connector declined a payment attempt, which is then not made against the mandate
+fn is_declined_attempt_status(status: storage_enums::AttemptStatus) -> bool {
+    matches!(
+        status,
+        storage_enums::AttemptStatus::Failure
+            | storage_enums::AttemptStatus::AuthorizationFailed
+            | storage_enums::AttemptStatus::AuthenticationFailed
+            | storage_enums::AttemptStatus::RouterDeclined
+    )
+}
+
+/// Whether the end date of a mandate has passed, which the scheduler may not have expired yet
+pub fn has_mandate_ended(end_date: Option<PrimitiveDateTime>, now: PrimitiveDateTime) -> bool {
+    end_date.map_or(false, |end_date| end_date <= now)
+}
+
+/// The start of the frequency interval of a mandate that ends at the given time
+pub fn get_frequency_interval_start(
+    end: PrimitiveDateTime,
+    frequency_interval: storage_enums::MandateFrequencyInterval,
+) -> Option<PrimitiveDateTime> {
+    match frequency_interval {
+        storage_enums::MandateFrequencyInterval::Day => end.checked_sub(time::Duration::DAY),
+        storage_enums::MandateFrequencyInterval::Week => end.checked_sub(time::Duration::WEEK),
+        storage_enums::MandateFrequencyInterval::Month => date_time::add_months(end, -1),
+        storage_enums::MandateFrequencyInterval::Year => date_time::add_months(end, -12),
+    }
+}
+
+/// Schedules the expiry of the mandate at its end date.
+#[instrument(skip_all)]
+pub async fn add_mandate_expiry_task(
+    db: &dyn StorageInterface,
+    mandate: &storage::Mandate,
+) -> errors::RouterResult<()> {
+    let Some(schedule_time) = mandate.end_date else {
+        return Ok(());
+    };
+    let current_time = date_time::now();
+
+    let tracking_data = storage::MandateExpiryWorkflow {
+        merchant_id: mandate.merchant_id.clone(),
+        mandate_id: mandate.mandate_id.clone(),
+    };
+    let mandate_expiry_workflow_model = serde_json::to_value(&tracking_data)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("unable to serialize mandate expiry tracker: {tracking_data:?}")
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: format!("{MANDATE_EXPIRY_RUNNER}_{}", mandate.mandate_id),
+        name: Some(String::from(MANDATE_EXPIRY_NAME)),
+        tag: vec![String::from(MANDATE_EXPIRY_TAG)],
+        runner: Some(String::from(MANDATE_EXPIRY_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(schedule_time),
+        rule: String::new(),
+        tracking_data: mandate_expiry_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting mandate expiry task to process_tracker: mandate_id: {}",
+                mandate.mandate_id
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Expires the mandate and notifies the merchant of it. Mandates that are no longer active are
+/// left as they are.
+#[instrument(skip_all, fields(mandate_id = %tracking_data.mandate_id))]
+pub async fn expire_mandate(
+    state: &AppState,
+    tracking_data: &storage::MandateExpiryWorkflow,
+) -> errors::RouterResult<()> {
+    let db = &*state.store;
+    let merchant_id = tracking_data.merchant_id.as_str();
+    let mandate = db
+        .find_mandate_by_merchant_id_mandate_id(merchant_id, &tracking_data.mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+
+    if mandate.mandate_status != storage_enums::MandateStatus::Active {
+        logger::info!(status = %mandate.mandate_status, "Mandate is no longer active, skipping");
+        return Ok(());
+    }
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    // The merchant is notified before the mandate is expired, so that the task is retried when
+    // the webhook could not be created. Webhook events are deduplicated, so retries do not
+    // notify the merchant twice.
+    let mandate_id = mandate.mandate_id.clone();
+    let expired_mandate = storage::Mandate {
+        mandate_status: storage_enums::MandateStatus::Expired,
+        ..mandate
+    };
+    if let Some(profile_id) = merchant_account.default_profile.as_ref() {
+        let business_profile = db
+            .find_business_profile_by_profile_id(profile_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+                id: profile_id.to_string(),
+            })?;
+        let mandates_response =
+            Box::new(mandates::MandateResponse::from_db_mandate(state, expired_mandate).await?);
+        Box::pin(
+            super::webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+                state.clone(),
+                merchant_account,
+                business_profile,
+                storage_enums::EventType::MandateExpired,
+                storage_enums::EventClass::Mandates,
+                None,
+                mandate_id.clone(),
+                storage_enums::EventObjectType::MandateDetails,
+                api_models::webhooks::OutgoingWebhookContent::MandateDetails(mandates_response),
+            ),
+        )
+        .await?;
+    } else {
+        logger::info!("Merchant account has no default business profile, skipping webhook");
+    }
+
+    db.update_mandate_by_merchant_id_mandate_id(
+        merchant_id,
+        &mandate_id,
+        storage::MandateUpdate::StatusUpdate {
+            mandate_status: storage_enums::MandateStatus::Expired,
+        },
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+
+    Ok(())
+}
+
 impl ForeignTryFrom<Result<types::PaymentsResponseData, types::ErrorResponse>>
     for Option<pii::SecretSerdeValue>
 {
@@ -326,9 +613,81 @@ impl ForeignTryFrom<Result<types::PaymentsResponseData, types::ErrorResponse>>
 
 pub trait MandateBehaviour {
     fn get_amount(&self) -> i64;
+    fn get_currency(&self) -> diesel_models::enums::Currency;
     fn get_setup_future_usage(&self) -> Option<diesel_models::enums::FutureUsage>;
     fn get_mandate_id(&self) -> Option<&api_models::payments::MandateIds>;
     fn set_mandate_id(&mut self, new_mandate_id: Option<api_models::payments::MandateIds>);
     fn get_payment_method_data(&self) -> api_models::payments::PaymentMethodData;
     fn get_setup_mandate_details(&self) -> Option<&data_models::mandates::MandateData>;
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_get_frequency_interval_start() {
+        let end = datetime!(2023-03-31 10:00);
+        let cases = [
+            (
+                storage_enums::MandateFrequencyInterval::Day,
+                datetime!(2023-03-30 10:00),
+            ),
+            (
+                storage_enums::MandateFrequencyInterval::Week,
+                datetime!(2023-03-24 10:00),
+            ),
+            (
+                storage_enums::MandateFrequencyInterval::Month,
+                datetime!(2023-02-28 10:00),
+            ),
+            (
+                storage_enums::MandateFrequencyInterval::Year,
+                datetime!(2022-03-31 10:00),
+            ),
+        ];
+        for (frequency_interval, expected) in cases {
+            assert_eq!(
+                get_frequency_interval_start(end, frequency_interval).unwrap(),
+                expected
+            );
+        }
+    }
+
+    #[test]
+    fn test_frequency_limit() {
+        assert!(!storage::MandateUsage::is_frequency_limit_reached(0, 1));
+        assert!(!storage::MandateUsage::is_frequency_limit_reached(2, 3));
+        assert!(storage::MandateUsage::is_frequency_limit_reached(3, 3));
+        assert!(storage::MandateUsage::is_frequency_limit_reached(4, 3));
+    }
+
+    #[test]
+    fn test_is_declined_attempt_status() {
+        assert!(is_declined_attempt_status(
+            storage_enums::AttemptStatus::Failure
+        ));
+        assert!(is_declined_attempt_status(
+            storage_enums::AttemptStatus::AuthorizationFailed
+        ));
+        assert!(!is_declined_attempt_status(
+            storage_enums::AttemptStatus::Charged
+        ));
+        assert!(!is_declined_attempt_status(
+            storage_enums::AttemptStatus::Pending
+        ));
+    }
+
+    #[test]
+    fn test_has_mandate_ended() {
+        let now = datetime!(2023-11-17 12:00);
+        assert!(!has_mandate_ended(None, now));
+        assert!(!has_mandate_ended(Some(datetime!(2023-11-17 12:01)), now));
+        assert!(has_mandate_ended(Some(now), now));
+        assert!(has_mandate_ended(Some(datetime!(2023-11-16 12:00)), now));
+    }
+}
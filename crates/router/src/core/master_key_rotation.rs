@@ -0,0 +1,187 @@
+use common_utils::ext_traits::{Encode, ValueExt};
+use diesel_models::merchant_key_store::RetiredKey;
+use error_stack::{report, ResultExt};
+use router_env::logger;
+
+use crate::{
+    core::errors::{CustomResult, MasterKeyRotationError},
+    db::StorageInterface,
+    types::{
+        domain::types::{self as domain_types, KeyRing, KeyVersion},
+        storage,
+    },
+};
+
+/// Number of merchant key stores fetched at once.
+const MERCHANT_KEY_STORE_BATCH_SIZE: i64 = 100;
+
+#[derive(Debug, Default)]
+pub struct MasterKeyRotationSummary {
+    /// Number of merchant key stores processed.
+    pub processed: usize,
+    /// Number of merchant key stores re-encrypted with the master key of the target version.
+    pub rewrapped: usize,
+}
+
+/// Re-encrypts the keys of all merchants with the master key of `target_version`, which must be
+/// configured either as the master key or as one of the other master keys.
+///
+/// The keys of merchants can be decrypted with any of the configured master keys, so that the
+/// router keeps serving while the keys are re-encrypted. A master key is rotated by:
+/// 1. Configuring the new master key as one of the other master keys of the router.
+/// 2. Re-encrypting the keys of all merchants with the new master key using this command.
+/// 3. Configuring the new master key as the master key, and the previous master key as one of
+///    the other master keys.
+/// 4. Removing the previous master key from the configuration.
+///
+/// Each re-encrypted key store is verified to decrypt to the same keys as before. If any key
+/// store fails to be re-encrypted, the key stores re-encrypted so far are restored.
+pub async fn rotate_master_key(
+    db: &dyn StorageInterface,
+    target_version: KeyVersion,
+) -> CustomResult<MasterKeyRotationSummary, MasterKeyRotationError> {
+    let master_key_ring = db
+        .get_master_key_ring()
+        .with_active_version(target_version)
+        .ok_or(MasterKeyRotationError::MasterKeyNotConfigured)?;
+
+    let mut summary = MasterKeyRotationSummary::default();
+    let mut rewrapped_key_stores = Vec::new();
+    let mut last_merchant_id = String::new();
+
+    loop {
+        let merchant_key_stores = match db
+            .find_merchant_key_stores_after_merchant_id(
+                &last_merchant_id,
+                MERCHANT_KEY_STORE_BATCH_SIZE,
+            )
+            .await
+            .change_context(MasterKeyRotationError::MerchantKeyStoreFetchFailed)
+        {
+            Ok(merchant_key_stores) => merchant_key_stores,
+            Err(error) => {
+                restore_merchant_key_stores(db, rewrapped_key_stores).await;
+                return Err(error);
+            }
+        };
+        let fetched = merchant_key_stores.len();
+
+        for merchant_key_store in merchant_key_stores {
+            last_merchant_id = merchant_key_store.merchant_id.clone();
+            match rewrap_merchant_key_store(db, &merchant_key_store, &master_key_ring).await {
+                Ok(true) => {
+                    summary.rewrapped += 1;
+                    rewrapped_key_stores.push(merchant_key_store);
+                }
+                Ok(false) => {}
+                Err(error) => {
+                    logger::error!(
+                        merchant_id = %merchant_key_store.merchant_id,
+                        ?error,
+                        "Failed to re-encrypt merchant key store, restoring the re-encrypted key stores"
+                    );
+                    restore_merchant_key_stores(db, rewrapped_key_stores).await;
+                    return Err(error);
+                }
+            }
+            summary.processed += 1;
+        }
+
+        if i64::try_from(fetched).map_or(true, |fetched| fetched < MERCHANT_KEY_STORE_BATCH_SIZE) {
+            break;
+        }
+    }
+
+    logger::info!(
+        target_version,
+        processed = summary.processed,
+        rewrapped = summary.rewrapped,
+        "Re-encrypted merchant key stores with the master key"
+    );
+    Ok(summary)
+}
+
+/// Re-encrypts the key and the retired keys of the merchant key store with the active master key
+/// of the key ring. Returns whether the key store had to be re-encrypted.
+async fn rewrap_merchant_key_store(
+    db: &dyn StorageInterface,
+    merchant_key_store: &storage::MerchantKeyStore,
+    master_key_ring: &KeyRing,
+) -> CustomResult<bool, MasterKeyRotationError> {
+    let retired_keys = merchant_key_store
+        .retired_keys
+        .clone()
+        .map(|retired_keys| retired_keys.parse_value::<Vec<RetiredKey>>("RetiredKey"))
+        .transpose()
+        .change_context(MasterKeyRotationError::MerchantKeyStoreRewrapFailed)?
+        .unwrap_or_default();
+
+    let needs_rewrap = std::iter::once(&merchant_key_store.key)
+        .chain(retired_keys.iter().map(|retired_key| &retired_key.key))
+        .any(|key| {
+            domain_types::get_encryption_key_version(key) != master_key_ring.active_version()
+        });
+    if !needs_rewrap {
+        return Ok(false);
+    }
+
+    let rewrap = |key: &diesel_models::encryption::Encryption| {
+        let rewrapped_key = domain_types::rewrap(key.clone(), master_key_ring)
+            .change_context(MasterKeyRotationError::MerchantKeyStoreRewrapFailed)?;
+        let verified = domain_types::is_same_data(&rewrapped_key, key, master_key_ring)
+            .change_context(MasterKeyRotationError::MerchantKeyStoreVerificationFailed)?;
+        if verified {
+            Ok(rewrapped_key)
+        } else {
+            Err(report!(
+                MasterKeyRotationError::MerchantKeyStoreVerificationFailed
+            ))
+        }
+    };
+
+    let key = rewrap(&merchant_key_store.key)?;
+    let retired_keys = retired_keys
+        .iter()
+        .map(|retired_key| {
+            rewrap(&retired_key.key).map(|key| RetiredKey {
+                key_version: retired_key.key_version,
+                key,
+            })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    let retired_keys = (!retired_keys.is_empty())
+        .then(|| Encode::<Vec<RetiredKey>>::encode_to_value(&retired_keys))
+        .transpose()
+        .change_context(MasterKeyRotationError::MerchantKeyStoreRewrapFailed)?;
+
+    db.update_encrypted_merchant_key_store(storage::MerchantKeyStore {
+        key,
+        retired_keys,
+        ..merchant_key_store.clone()
+    })
+    .await
+    .change_context(MasterKeyRotationError::MerchantKeyStoreUpdateFailed)?;
+
+    Ok(true)
+}
+
+/// Restores the key stores that were re-encrypted before the rotation failed. Key stores that fail
+/// to be restored remain readable, as long as the master key of the target version is configured.
+async fn restore_merchant_key_stores(
+    db: &dyn StorageInterface,
+    merchant_key_stores: Vec<storage::MerchantKeyStore>,
+) {
+    for merchant_key_store in merchant_key_stores {
+        let merchant_id = merchant_key_store.merchant_id.clone();
+        if let Err(error) = db
+            .update_encrypted_merchant_key_store(merchant_key_store)
+            .await
+        {
+            logger::error!(
+                %merchant_id,
+                ?error,
+                "Failed to restore merchant key store"
+            );
+        }
+    }
+}
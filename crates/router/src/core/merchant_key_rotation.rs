@@ -0,0 +1,462 @@
+use api_models::admin::{
+    EncryptedEntity, EncryptedEntityProgress, MerchantKeyRotationResponse,
+    MerchantKeyRotationStatus,
+};
+use common_utils::{
+    date_time,
+    ext_traits::{Encode, StringExt},
+};
+use error_stack::{report, IntoReport, ResultExt};
+use router_env::logger;
+use scheduler::db::process_tracker::ProcessTrackerExt;
+
+use crate::{
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    db::StorageInterface,
+    routes::AppState,
+    services::{self, api as service_api},
+    types::{
+        domain::{
+            self,
+            types::{self as domain_types, KeyVersion},
+        },
+        storage::{self, enums as storage_enums},
+    },
+};
+
+const MERCHANT_KEY_ROTATION_TAG: &str = "MERCHANT_KEY";
+const MERCHANT_KEY_ROTATION_NAME: &str = "MERCHANT_KEY_ROTATION";
+const MERCHANT_KEY_ROTATION_RUNNER: &str = "MERCHANT_KEY_ROTATION_WORKFLOW";
+
+/// Number of records of an entity re-encrypted by a single execution of the rotation task.
+const MERCHANT_KEY_ROTATION_BATCH_SIZE: i64 = 100;
+
+/// The entities re-encrypted after a key rotation, in the order they are processed.
+const ENCRYPTED_ENTITIES: [EncryptedEntity; 5] = [
+    EncryptedEntity::MerchantAccount,
+    EncryptedEntity::MerchantConnectorAccount,
+    EncryptedEntity::Customer,
+    EncryptedEntity::Address,
+    EncryptedEntity::PaymentMethod,
+];
+
+/// Progress of re-encrypting the data of a merchant after its key was rotated, persisted in the
+/// configs table.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+struct MerchantKeyRotation {
+    key_version: KeyVersion,
+    status: MerchantKeyRotationStatus,
+    progress: Vec<EncryptedEntityProgress>,
+    /// The identifier of the last record processed of the entity being re-encrypted.
+    #[serde(default)]
+    cursor: String,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    started_at: time::PrimitiveDateTime,
+    #[serde(default, with = "common_utils::custom_serde::iso8601::option")]
+    completed_at: Option<time::PrimitiveDateTime>,
+}
+
+impl MerchantKeyRotation {
+    fn new(key_version: KeyVersion) -> Self {
+        Self {
+            key_version,
+            status: MerchantKeyRotationStatus::InProgress,
+            progress: ENCRYPTED_ENTITIES
+                .into_iter()
+                .map(|entity| EncryptedEntityProgress {
+                    entity,
+                    processed: 0,
+                    rewrapped: 0,
+                    completed: false,
+                })
+                .collect(),
+            cursor: String::new(),
+            started_at: date_time::now(),
+            completed_at: None,
+        }
+    }
+
+    fn finish(mut self, status: MerchantKeyRotationStatus) -> Self {
+        self.status = status;
+        self.completed_at = Some(date_time::now());
+        self
+    }
+
+    fn into_response(self, merchant_id: String) -> MerchantKeyRotationResponse {
+        MerchantKeyRotationResponse {
+            merchant_id,
+            key_version: self.key_version,
+            status: self.status,
+            progress: self.progress,
+            started_at: self.started_at,
+            completed_at: self.completed_at,
+        }
+    }
+}
+
+fn get_merchant_key_rotation_key(merchant_id: &str) -> String {
+    format!("merchant_key_rotation_{merchant_id}")
+}
+
+async fn find_merchant_key_rotation(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+) -> RouterResult<Option<MerchantKeyRotation>> {
+    match db
+        .find_config_by_key(&get_merchant_key_rotation_key(merchant_id))
+        .await
+    {
+        Ok(config) => config
+            .config
+            .parse_struct("MerchantKeyRotation")
+            .map(Some)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse the key rotation of the merchant"),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the key rotation of the merchant"),
+    }
+}
+
+async fn store_merchant_key_rotation(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    rotation: &MerchantKeyRotation,
+    already_present: bool,
+) -> RouterResult<()> {
+    let key = get_merchant_key_rotation_key(merchant_id);
+    let config = Encode::<MerchantKeyRotation>::encode_to_string_of_json(rotation)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the key rotation of the merchant")?;
+
+    if already_present {
+        db.update_config_by_key(
+            &key,
+            storage::ConfigUpdate::Update {
+                config: Some(config),
+            },
+        )
+        .await
+        .map(|_| ())
+    } else {
+        db.insert_config(storage::ConfigNew { key, config })
+            .await
+            .map(|_| ())
+    }
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store the key rotation of the merchant")
+}
+
+/// Rotates the data encryption key of the merchant and schedules re-encrypting the data of the
+/// merchant with the new key. Data encrypted with the previous keys remains readable, as the
+/// previous keys are retained as retired keys of the merchant.
+///
+/// If re-encrypting the data after the previous rotation failed, it is resumed instead of rotating
+/// the key again.
+pub async fn rotate_merchant_key(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<MerchantKeyRotationResponse> {
+    let db = state.store.as_ref();
+    let master_key_ring = db.get_master_key_ring();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &master_key_ring)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let existing_rotation = find_merchant_key_rotation(db, &merchant_id).await?;
+    let rotation_present = existing_rotation.is_some();
+
+    let rotation = match existing_rotation {
+        Some(rotation) if rotation.status == MerchantKeyRotationStatus::InProgress => {
+            return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "A key rotation is already in progress for the merchant".to_string(),
+            }));
+        }
+        Some(rotation)
+            if rotation.status == MerchantKeyRotationStatus::Failed
+                && rotation.key_version == key_store.key_version =>
+        {
+            let rotation = MerchantKeyRotation {
+                status: MerchantKeyRotationStatus::InProgress,
+                completed_at: None,
+                ..rotation
+            };
+            store_merchant_key_rotation(db, &merchant_id, &rotation, true).await?;
+            resume_merchant_key_rotation_task(db, &merchant_id, rotation.key_version).await?;
+            rotation
+        }
+        _ => {
+            let key = services::generate_aes256_key()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Unable to generate aes 256 key")?;
+            let current_key_version = key_store.key_version;
+
+            let mut retired_keys = key_store.retired_keys.clone();
+            retired_keys.push(domain::RetiredKey {
+                key_version: current_key_version,
+                key: key_store.key.clone(),
+            });
+
+            let rotated_key_store = domain::MerchantKeyStore {
+                key: domain_types::encrypt(key.to_vec().into(), &master_key_ring)
+                    .await
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to encrypt the rotated merchant key")?,
+                key_version: current_key_version + 1,
+                retired_keys,
+                ..key_store
+            };
+
+            let rotated_key_store = db
+                .update_merchant_key_store(current_key_version, rotated_key_store, &master_key_ring)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PreconditionFailed {
+                    message: "The key of the merchant was rotated concurrently".to_string(),
+                })?;
+
+            let rotation = MerchantKeyRotation::new(rotated_key_store.key_version);
+            store_merchant_key_rotation(db, &merchant_id, &rotation, rotation_present).await?;
+            add_merchant_key_rotation_task(db, &merchant_id, rotation.key_version).await?;
+            rotation
+        }
+    };
+
+    Ok(service_api::ApplicationResponse::Json(
+        rotation.into_response(merchant_id),
+    ))
+}
+
+/// Reports the progress of re-encrypting the data of the merchant after the latest rotation of its
+/// key.
+pub async fn merchant_key_rotation_status(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<MerchantKeyRotationResponse> {
+    let db = state.store.as_ref();
+    let rotation = find_merchant_key_rotation(db, &merchant_id)
+        .await?
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::GenericNotFoundError {
+                message: "No key rotation found for the merchant".to_string(),
+            })
+        })?;
+
+    Ok(service_api::ApplicationResponse::Json(
+        rotation.into_response(merchant_id),
+    ))
+}
+
+/// Re-encrypts the next batch of records of the merchant that are not yet encrypted with the
+/// rotated key. Returns whether all data of the merchant has been processed.
+pub async fn rewrap_merchant_data_batch(
+    state: &AppState,
+    tracking_data: &storage::MerchantKeyRotationWorkflow,
+) -> RouterResult<bool> {
+    let db = state.store.as_ref();
+    let merchant_id = tracking_data.merchant_id.as_str();
+    let rotation = find_merchant_key_rotation(db, merchant_id)
+        .await?
+        .filter(|rotation| rotation.key_version == tracking_data.key_version)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("No key rotation found for the rotated merchant key")?;
+
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the key store of the merchant")?;
+    if key_store.key_version != rotation.key_version {
+        // The key was rotated again since, re-encrypting the data is left to the newer rotation
+        logger::info!(
+            merchant_id,
+            key_version = rotation.key_version,
+            "Merchant key was rotated again, skipping re-encryption"
+        );
+        let rotation = rotation.finish(MerchantKeyRotationStatus::Completed);
+        store_merchant_key_rotation(db, merchant_id, &rotation, true).await?;
+        return Ok(true);
+    }
+    let key_ring = key_store.key_ring();
+
+    let mut rotation = rotation;
+    let Some(progress) = rotation
+        .progress
+        .iter_mut()
+        .find(|progress| !progress.completed)
+    else {
+        complete_merchant_key_rotation(db, merchant_id, rotation).await?;
+        return Ok(true);
+    };
+
+    let records = db
+        .find_encrypted_data_by_merchant_id(
+            merchant_id,
+            progress.entity,
+            &rotation.cursor,
+            MERCHANT_KEY_ROTATION_BATCH_SIZE,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed to fetch {} records", progress.entity))?;
+
+    let fetched = records.len();
+    let mut interrupted = false;
+    for record in records {
+        let record_id = record.id().to_owned();
+        if record.needs_rewrap(&key_ring) {
+            match db.update_encrypted_data(record, &key_ring).await {
+                Ok(_) => progress.rewrapped += 1,
+                // The record was updated since it was fetched, it is fetched again by the next batch
+                Err(error) if error.current_context().is_db_not_found() => {
+                    logger::info!(
+                        merchant_id,
+                        entity = %progress.entity,
+                        %record_id,
+                        "Encrypted record was updated concurrently, retrying re-encryption"
+                    );
+                    interrupted = true;
+                    break;
+                }
+                Err(error) => {
+                    return Err(error)
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable_lazy(|| {
+                            format!("Failed to re-encrypt {}", progress.entity)
+                        });
+                }
+            }
+        }
+        progress.processed += 1;
+        rotation.cursor = record_id;
+    }
+
+    progress.completed = !interrupted
+        && i64::try_from(fetched)
+            .map_or(true, |fetched| fetched < MERCHANT_KEY_ROTATION_BATCH_SIZE);
+    if progress.completed {
+        rotation.cursor = String::new();
+    }
+
+    if rotation.progress.iter().all(|progress| progress.completed) {
+        complete_merchant_key_rotation(db, merchant_id, rotation).await?;
+        return Ok(true);
+    }
+    store_merchant_key_rotation(db, merchant_id, &rotation, true).await?;
+
+    Ok(false)
+}
+
+/// Marks the rotation as completed. The retired keys of the merchant are kept, as records may still
+/// be written with a retired key after they were re-encrypted, such as by requests in flight, by
+/// instances with a cached key store or by data that is not yet drained from the KV store.
+async fn complete_merchant_key_rotation(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    rotation: MerchantKeyRotation,
+) -> RouterResult<()> {
+    let rotation = rotation.finish(MerchantKeyRotationStatus::Completed);
+    store_merchant_key_rotation(db, merchant_id, &rotation, true).await
+}
+
+/// Marks re-encrypting the data of the merchant after a key rotation as failed. It can be resumed
+/// by rotating the key of the merchant again.
+pub async fn fail_merchant_key_rotation(
+    state: &AppState,
+    tracking_data: &storage::MerchantKeyRotationWorkflow,
+) -> RouterResult<()> {
+    let db = state.store.as_ref();
+    match find_merchant_key_rotation(db, &tracking_data.merchant_id).await? {
+        Some(rotation)
+            if rotation.key_version == tracking_data.key_version
+                && rotation.status == MerchantKeyRotationStatus::InProgress =>
+        {
+            let rotation = rotation.finish(MerchantKeyRotationStatus::Failed);
+            store_merchant_key_rotation(db, &tracking_data.merchant_id, &rotation, true).await
+        }
+        _ => Ok(()),
+    }
+}
+
+async fn add_merchant_key_rotation_task(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    key_version: KeyVersion,
+) -> RouterResult<()> {
+    let current_time = date_time::now();
+
+    let merchant_key_rotation_tracker = &storage::MerchantKeyRotationWorkflow {
+        merchant_id: merchant_id.to_string(),
+        key_version,
+    };
+    let merchant_key_rotation_workflow_model =
+        serde_json::to_value(merchant_key_rotation_tracker)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| {
+                format!(
+                    "unable to serialize merchant key rotation tracker: {merchant_key_rotation_tracker:?}"
+                )
+            })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: generate_task_id_for_merchant_key_rotation_workflow(merchant_id, key_version),
+        name: Some(String::from(MERCHANT_KEY_ROTATION_NAME)),
+        tag: vec![String::from(MERCHANT_KEY_ROTATION_TAG)],
+        runner: Some(String::from(MERCHANT_KEY_ROTATION_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(current_time),
+        rule: String::new(),
+        tracking_data: merchant_key_rotation_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting merchant key rotation task to process_tracker: merchant_id: {merchant_id}"
+            )
+        })?;
+
+    Ok(())
+}
+
+async fn resume_merchant_key_rotation_task(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    key_version: KeyVersion,
+) -> RouterResult<()> {
+    let task_id = generate_task_id_for_merchant_key_rotation_workflow(merchant_id, key_version);
+    let process = db
+        .find_process_by_id(&task_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch the merchant key rotation task")?;
+
+    match process {
+        Some(process) => process
+            .reset(db.as_scheduler(), date_time::now())
+            .await
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to reschedule the merchant key rotation task"),
+        None => add_merchant_key_rotation_task(db, merchant_id, key_version).await,
+    }
+}
+
+fn generate_task_id_for_merchant_key_rotation_workflow(
+    merchant_id: &str,
+    key_version: KeyVersion,
+) -> String {
+    format!(
+        "{MERCHANT_KEY_ROTATION_RUNNER}_{MERCHANT_KEY_ROTATION_NAME}_{merchant_id}_{key_version}"
+    )
+}
@@ -0,0 +1,444 @@
+use api_models::organization as organization_types;
+use data_models::payments::payment_intent::PaymentIntentAggregate;
+use diesel_models::organization as storage;
+use error_stack::{IntoReport, ResultExt};
+use external_services::secrets_management;
+use masking::PeekInterface;
+
+use crate::{
+    consts,
+    core::{
+        api_keys::{self, PlaintextApiKey},
+        errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+        payments::helpers,
+        refunds::validator,
+    },
+    routes::AppState,
+    services::{self, authentication::OrganizationAuthenticationData},
+    types::{
+        api, domain,
+        storage::{enums::MerchantStorageScheme, RefundAggregate},
+        transformers::{ForeignFrom, ForeignInto},
+    },
+};
+
+pub async fn create_organization(
+    state: AppState,
+    req: organization_types::OrganizationRequest,
+) -> RouterResponse<organization_types::OrganizationResponse> {
+    let new_organization = organization_types::OrganizationNew::new(req.organization_name);
+    let organization = state
+        .store
+        .insert_organization(ForeignFrom::foreign_from(new_organization))
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::GenericDuplicateError {
+            message: "Organization with the given id already exists".to_string(),
+        })
+        .attach_printable("Error when creating organization")?;
+
+    Ok(services::ApplicationResponse::Json(
+        organization.foreign_into(),
+    ))
+}
+
+pub async fn retrieve_organization(
+    state: AppState,
+    req: organization_types::OrganizationId,
+) -> RouterResponse<organization_types::OrganizationResponse> {
+    let organization = find_organization(&state, &req.organization_id).await?;
+
+    Ok(services::ApplicationResponse::Json(
+        organization.foreign_into(),
+    ))
+}
+
+pub async fn update_organization(
+    state: AppState,
+    org_id: &str,
+    req: organization_types::OrganizationRequest,
+) -> RouterResponse<organization_types::OrganizationResponse> {
+    // An empty changeset cannot be applied, return the organization as is
+    let organization = match req.organization_name {
+        Some(org_name) => state
+            .store
+            .update_organization_by_org_id(
+                org_id,
+                storage::OrganizationUpdate::Update {
+                    org_name: Some(org_name),
+                },
+            )
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+                message: "organization with the given id does not exist".to_string(),
+            })?,
+        None => find_organization(&state, org_id).await?,
+    };
+
+    Ok(services::ApplicationResponse::Json(
+        organization.foreign_into(),
+    ))
+}
+
+/// Generates a new API key for the organization, invalidating the previous one if any.
+pub async fn create_organization_api_key(
+    state: AppState,
+    req: organization_types::OrganizationId,
+) -> RouterResponse<organization_types::OrganizationApiKeyResponse> {
+    find_organization(&state, &req.organization_id).await?;
+
+    let hash_key = api_keys::get_hash_key(
+        &state.conf.api_keys,
+        secrets_management::get_secrets_manager(&state.conf.secrets_management).await,
+    )
+    .await?;
+    let plaintext_api_key = PlaintextApiKey::new(consts::API_KEY_LENGTH);
+    let hashed_api_key: diesel_models::api_keys::HashedApiKey =
+        plaintext_api_key.keyed_hash(hash_key.peek()).into();
+
+    let organization = state
+        .store
+        .update_organization_by_org_id(
+            &req.organization_id,
+            storage::OrganizationUpdate::ApiKeyUpdate {
+                hashed_api_key: hashed_api_key.into_inner(),
+                api_key_prefix: plaintext_api_key.prefix(),
+            },
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to update the API key of the organization")?;
+
+    Ok(services::ApplicationResponse::Json(
+        organization_types::OrganizationApiKeyResponse {
+            organization_id: organization.org_id,
+            api_key: plaintext_api_key.peek().to_owned().into(),
+            prefix: plaintext_api_key.prefix(),
+        },
+    ))
+}
+
+pub async fn list_organization_merchants(
+    state: AppState,
+    auth: OrganizationAuthenticationData,
+) -> RouterResponse<organization_types::OrganizationMerchantsResponse> {
+    let merchants = get_organization_merchants(&state, &auth.organization.org_id)
+        .await?
+        .into_iter()
+        .map(|merchant_account| {
+            merchant_account
+                .try_into()
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "merchant_account",
+                })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    Ok(services::ApplicationResponse::Json(
+        organization_types::OrganizationMerchantsResponse {
+            organization_id: auth.organization.org_id,
+            merchants,
+        },
+    ))
+}
+
+/// Lists the payments of all merchants of the organization, most recent first.
+pub async fn list_organization_payments(
+    state: AppState,
+    auth: OrganizationAuthenticationData,
+    constraints: api::PaymentListFilterConstraints,
+) -> RouterResponse<api::PaymentListResponseV2> {
+    helpers::validate_payment_list_request_for_joins(constraints.limit)?;
+    let db = state.store.as_ref();
+    let merchant_ids = get_organization_merchant_ids(&state, &auth.organization.org_id).await?;
+
+    let payments = db
+        .get_filtered_payment_intents_attempt(
+            &merchant_ids,
+            &constraints.clone().into(),
+            // since OLAP doesn't have KV. Force to get the data from PSQL.
+            MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let active_attempt_ids = db
+        .get_filtered_active_attempt_ids_for_total_count(
+            &merchant_ids,
+            &constraints.clone().into(),
+            MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
+
+    let total_count = db
+        .get_total_count_of_filtered_payment_attempts(
+            &merchant_ids,
+            &active_attempt_ids,
+            constraints.connector,
+            constraints.payment_method,
+            constraints.payment_method_type,
+            constraints.authentication_type,
+            MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let data: Vec<api::PaymentsResponse> = payments
+        .into_iter()
+        .map(ForeignFrom::foreign_from)
+        .collect();
+
+    Ok(services::ApplicationResponse::Json(
+        api::PaymentListResponseV2 {
+            count: data.len(),
+            total_count,
+            data,
+        },
+    ))
+}
+
+/// Lists the refunds of all merchants of the organization, most recently modified first.
+pub async fn list_organization_refunds(
+    state: AppState,
+    auth: OrganizationAuthenticationData,
+    req: api_models::refunds::RefundListRequest,
+) -> RouterResponse<api_models::refunds::RefundListResponse> {
+    let limit = validator::validate_refund_list(req.limit)?;
+    let offset = req.offset.unwrap_or_default();
+    let db = state.store.as_ref();
+    let merchant_ids = get_organization_merchant_ids(&state, &auth.organization.org_id).await?;
+
+    let refunds = db
+        .filter_refund_by_constraints(
+            &merchant_ids,
+            &req,
+            MerchantStorageScheme::PostgresOnly,
+            limit,
+            offset,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+
+    let total_count = db
+        .get_total_count_of_refunds(
+            &merchant_ids,
+            &req,
+            MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
+
+    let data: Vec<api_models::refunds::RefundResponse> =
+        refunds.into_iter().map(ForeignInto::foreign_into).collect();
+
+    Ok(services::ApplicationResponse::Json(
+        api_models::refunds::RefundListResponse {
+            count: data.len(),
+            total_count,
+            data,
+        },
+    ))
+}
+
+/// Aggregates the payments of the organization over the time range by status and currency, in
+/// total and for each merchant.
+pub async fn get_organization_payments_aggregate(
+    state: AppState,
+    auth: OrganizationAuthenticationData,
+    time_range: api_models::payments::TimeRange,
+) -> RouterResponse<organization_types::OrganizationPaymentsAggregateResponse> {
+    let merchant_ids = get_organization_merchant_ids(&state, &auth.organization.org_id).await?;
+    let aggregates = state
+        .store
+        .get_payment_intent_aggregates(
+            &merchant_ids,
+            &time_range,
+            // since OLAP doesn't have KV. Force to get the data from PSQL.
+            MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to aggregate the payments of the organization")?;
+
+    let mut total: Vec<organization_types::PaymentsAggregate> = Vec::new();
+    let mut merchants: Vec<organization_types::MerchantPaymentsAggregate> = merchant_ids
+        .into_iter()
+        .map(
+            |merchant_id| organization_types::MerchantPaymentsAggregate {
+                merchant_id,
+                aggregates: Vec::new(),
+            },
+        )
+        .collect();
+
+    for aggregate in aggregates {
+        let PaymentIntentAggregate {
+            merchant_id,
+            status,
+            currency,
+            count,
+            total_amount,
+        } = aggregate;
+        let aggregate = organization_types::PaymentsAggregate {
+            status,
+            currency,
+            count,
+            total_amount,
+        };
+
+        match total
+            .iter_mut()
+            .find(|total| total.status == status && total.currency == currency)
+        {
+            Some(total) => add_to_total(
+                &mut total.count,
+                &mut total.total_amount,
+                count,
+                total_amount,
+            )?,
+            None => total.push(aggregate.clone()),
+        }
+
+        if let Some(merchant) = merchants
+            .iter_mut()
+            .find(|merchant| merchant.merchant_id == merchant_id)
+        {
+            merchant.aggregates.push(aggregate);
+        }
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        organization_types::OrganizationPaymentsAggregateResponse { total, merchants },
+    ))
+}
+
+/// Aggregates the refunds of the organization over the time range by status and currency, in
+/// total and for each merchant.
+pub async fn get_organization_refunds_aggregate(
+    state: AppState,
+    auth: OrganizationAuthenticationData,
+    time_range: api_models::refunds::TimeRange,
+) -> RouterResponse<organization_types::OrganizationRefundsAggregateResponse> {
+    let merchant_ids = get_organization_merchant_ids(&state, &auth.organization.org_id).await?;
+    let aggregates = state
+        .store
+        .get_refund_aggregates(
+            &merchant_ids,
+            &time_range,
+            // since OLAP doesn't have KV. Force to get the data from PSQL.
+            MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to aggregate the refunds of the organization")?;
+
+    let mut total: Vec<organization_types::RefundsAggregate> = Vec::new();
+    let mut merchants: Vec<organization_types::MerchantRefundsAggregate> = merchant_ids
+        .into_iter()
+        .map(|merchant_id| organization_types::MerchantRefundsAggregate {
+            merchant_id,
+            aggregates: Vec::new(),
+        })
+        .collect();
+
+    for aggregate in aggregates {
+        let RefundAggregate {
+            merchant_id,
+            status,
+            currency,
+            count,
+            total_amount,
+        } = aggregate;
+        let aggregate = organization_types::RefundsAggregate {
+            status,
+            currency,
+            count,
+            total_amount,
+        };
+
+        match total
+            .iter_mut()
+            .find(|total| total.status == status && total.currency == currency)
+        {
+            Some(total) => add_to_total(
+                &mut total.count,
+                &mut total.total_amount,
+                count,
+                total_amount,
+            )?,
+            None => total.push(aggregate.clone()),
+        }
+
+        if let Some(merchant) = merchants
+            .iter_mut()
+            .find(|merchant| merchant.merchant_id == merchant_id)
+        {
+            merchant.aggregates.push(aggregate);
+        }
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        organization_types::OrganizationRefundsAggregateResponse { total, merchants },
+    ))
+}
+
+/// Adds the count and total amount of an aggregate of a merchant to the organization total,
+/// failing rather than overflowing.
+fn add_to_total(
+    total_count: &mut i64,
+    total_amount: &mut i64,
+    count: i64,
+    amount: i64,
+) -> RouterResult<()> {
+    *total_count = total_count
+        .checked_add(count)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Overflow while adding up the count of the organization aggregate")?;
+    *total_amount = total_amount
+        .checked_add(amount)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Overflow while adding up the amount of the organization aggregate")?;
+    Ok(())
+}
+
+async fn find_organization(state: &AppState, org_id: &str) -> RouterResult<storage::Organization> {
+    state
+        .store
+        .find_organization_by_org_id(org_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::GenericNotFoundError {
+            message: "organization with the given id does not exist".to_string(),
+        })
+}
+
+async fn get_organization_merchants(
+    state: &AppState,
+    org_id: &str,
+) -> RouterResult<Vec<domain::MerchantAccount>> {
+    state
+        .store
+        .list_merchant_accounts_by_organization_id(org_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to list the merchant accounts of the organization")
+}
+
+async fn get_organization_merchant_ids(
+    state: &AppState,
+    org_id: &str,
+) -> RouterResult<Vec<String>> {
+    Ok(get_organization_merchants(state, org_id)
+        .await?
+        .into_iter()
+        .map(|merchant| merchant.merchant_id)
+        .collect())
+}
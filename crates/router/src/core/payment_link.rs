@@ -1,17 +1,74 @@
-use api_models::admin as admin_types;
-use common_utils::ext_traits::AsyncExt;
+use api_models::{admin as admin_types, payments::HeaderPayload};
+use common_utils::date_time;
 use error_stack::{IntoReport, ResultExt};
+use masking::ExposeInterface;
+use router_env::{instrument, logger, tracing};
+use serde::Serialize;
+use time::PrimitiveDateTime;
 
-use super::errors::{self, RouterResult, StorageErrorExt};
+use super::{
+    api_locking,
+    errors::{self, RouterResult, StorageErrorExt},
+    payment_methods::PaymentMethodRetrieve,
+    payments,
+};
 use crate::{
-    core::payments::helpers,
+    configs::settings,
+    consts,
+    db::StorageInterface,
     errors::RouterResponse,
-    routes::AppState,
+    routes::{app::AppStateInfo, lock_utils, AppState},
     services,
-    types::{domain, storage::enums as storage_enums, transformers::ForeignFrom},
-    utils::OptionExt,
+    types::{
+        api, domain,
+        storage::{self, enums as storage_enums},
+        transformers::ForeignFrom,
+    },
+    utils::{self, OptionExt},
 };
 
+const PAYMENT_LINK_EXPIRY_TAG: &str = "PAYMENT_LINK";
+const PAYMENT_LINK_EXPIRY_NAME: &str = "PAYMENT_LINK_EXPIRY";
+const PAYMENT_LINK_EXPIRY_RUNNER: &str = "PAYMENT_LINK_EXPIRY_WORKFLOW";
+
+/// The locale that payment links are shown in when their locale is not supported.
+const DEFAULT_LOCALE: &str = "en";
+
+/// The locales that the strings of payment links are translated to.
+const SUPPORTED_LOCALES: [&str; 2] = ["en", "fr"];
+
+const LOCALE_STRINGS: [(&str, &str); 2] = [
+    ("en", include_str!("payment_link/locales/en.json")),
+    ("fr", include_str!("payment_link/locales/fr.json")),
+];
+
+const MIN_EXPIRY_IN_SECONDS: u32 = 60;
+const MAX_DISPLAY_NAME_LENGTH: usize = 64;
+const MAX_CUSTOM_CSS_LENGTH: usize = 10_000;
+const PAYMENT_LINK_LIST_MAX_LIMIT: u32 = 100;
+const MAX_PRODUCT_NAME_LENGTH: usize = 255;
+
+/// The minimum amount that customers can choose to pay through a reusable payment link, unless
+/// the merchant configures a minimum amount.
+const DEFAULT_REUSABLE_MIN_AMOUNT: i64 = 1;
+
+/// The statuses of a payment that can no longer be paid through its payment link, for which the
+/// status page is shown instead.
+const NOT_PAYABLE_STATUSES: [storage_enums::IntentStatus; 5] = [
+    storage_enums::IntentStatus::Cancelled,
+    storage_enums::IntentStatus::Succeeded,
+    storage_enums::IntentStatus::Processing,
+    storage_enums::IntentStatus::RequiresCapture,
+    storage_enums::IntentStatus::RequiresMerchantAction,
+];
+
+/// The statuses of a payment that are cancelled when its payment link expires or its invoice is
+/// voided. Payments that have been confirmed are left to complete.
+pub const CANCELLABLE_STATUSES: [storage_enums::IntentStatus; 2] = [
+    storage_enums::IntentStatus::RequiresPaymentMethod,
+    storage_enums::IntentStatus::RequiresConfirmation,
+];
+
 pub async fn retrieve_payment_link(
     state: AppState,
     payment_link_id: String,
@@ -27,6 +84,193 @@ pub async fn retrieve_payment_link(
     Ok(services::ApplicationResponse::Json(response))
 }
 
+#[instrument(skip(state))]
+pub async fn list_payment_links(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    constraints: api_models::payments::PaymentLinkListConstraints,
+) -> RouterResponse<api_models::payments::PaymentLinkListResponse> {
+    utils::when(
+        !(1..=PAYMENT_LINK_LIST_MAX_LIMIT).contains(&constraints.limit),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!("limit should be in between 1 and {PAYMENT_LINK_LIST_MAX_LIMIT}"),
+            })
+            .into_report()
+        },
+    )?;
+
+    let payment_links = state
+        .store
+        .list_payment_links_by_merchant_id(&merchant_account.merchant_id, constraints)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to retrieve payment links")?;
+
+    let data: Vec<_> = payment_links
+        .into_iter()
+        .map(api_models::payments::RetrievePaymentLinkResponse::foreign_from)
+        .collect();
+
+    Ok(services::ApplicationResponse::Json(
+        api_models::payments::PaymentLinkListResponse {
+            size: data.len(),
+            data,
+        },
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn create_reusable_payment_link(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: api_models::payments::ReusablePaymentLinkCreateRequest,
+) -> RouterResponse<api_models::payments::ReusablePaymentLinkResponse> {
+    validate_reusable_payment_link_request(&request)?;
+
+    let payment_link_config = request
+        .payment_link_config
+        .as_ref()
+        .map(|pl_config| {
+            utils::Encode::<admin_types::PaymentLinkConfig>::encode_to_value(pl_config)
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "payment_link_config",
+                })
+        })
+        .transpose()?;
+
+    let domain = request
+        .merchant_custom_domain_name
+        .as_ref()
+        .map(|domain_name| format!("https://{domain_name}"))
+        .unwrap_or_else(|| state.conf.server.base_url.clone());
+    let merchant_id = merchant_account.merchant_id;
+    let reusable_payment_link_id = utils::generate_id(consts::ID_LENGTH, "rplink");
+    let current_time = date_time::now();
+
+    let reusable_payment_link_new = storage::ReusablePaymentLinkNew {
+        link_to_pay: format!(
+            "{domain}/payment_link/reusable/{merchant_id}/{reusable_payment_link_id}"
+        ),
+        reusable_payment_link_id,
+        merchant_id,
+        currency: request.currency,
+        amount: request.amount,
+        // Customers choose the amount of links without a fixed amount, at least the minimum amount
+        min_amount: request
+            .amount
+            .is_none()
+            .then(|| request.min_amount.unwrap_or(DEFAULT_REUSABLE_MIN_AMOUNT)),
+        max_amount: request.max_amount,
+        product_name: request.product_name,
+        product_img_link: request.product_img_link,
+        description: request.description,
+        return_url: request.return_url.map(String::from),
+        merchant_custom_domain_name: request.merchant_custom_domain_name,
+        payment_link_config,
+        max_uses: request.max_uses,
+        status: storage_enums::PaymentLinkStatus::Active,
+        link_expiry: request.link_expiry,
+        created_at: Some(current_time),
+        last_modified_at: Some(current_time),
+    };
+
+    let reusable_payment_link = state
+        .store
+        .insert_reusable_payment_link(reusable_payment_link_new)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::GenericDuplicateError {
+            message: "reusable payment link already exists!".to_string(),
+        })?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_reusable_payment_link_response(reusable_payment_link),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_reusable_payment_link(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    reusable_payment_link_id: String,
+) -> RouterResponse<api_models::payments::ReusablePaymentLinkResponse> {
+    let reusable_payment_link = state
+        .store
+        .find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+            &merchant_account.merchant_id,
+            &reusable_payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_reusable_payment_link_response(reusable_payment_link),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn update_reusable_payment_link(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    reusable_payment_link_id: String,
+    request: api_models::payments::ReusablePaymentLinkUpdateRequest,
+) -> RouterResponse<api_models::payments::ReusablePaymentLinkResponse> {
+    let db = &*state.store;
+    utils::when(
+        request.max_uses.map_or(false, |max_uses| max_uses < 1),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "max_uses must be at least 1".to_string(),
+            })
+            .into_report()
+        },
+    )?;
+
+    let reusable_payment_link = db
+        .find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+            &merchant_account.merchant_id,
+            &reusable_payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    let max_uses = request.max_uses.or(reusable_payment_link.max_uses);
+    let is_exhausted = max_uses.map_or(false, |max_uses| {
+        reusable_payment_link.times_used >= max_uses
+    });
+    let status = match (request.active, reusable_payment_link.status) {
+        (Some(false), _) => storage_enums::PaymentLinkStatus::Inactive,
+        // Changing the maximum number of uses completes or reopens the link accordingly
+        (Some(true), _)
+        | (
+            None,
+            storage_enums::PaymentLinkStatus::Active | storage_enums::PaymentLinkStatus::Completed,
+        ) => {
+            if is_exhausted {
+                storage_enums::PaymentLinkStatus::Completed
+            } else {
+                storage_enums::PaymentLinkStatus::Active
+            }
+        }
+        (None, status) => status,
+    };
+
+    let reusable_payment_link = db
+        .update_reusable_payment_link(
+            reusable_payment_link,
+            storage::ReusablePaymentLinkUpdate::Update {
+                status: Some(status),
+                max_uses: request.max_uses,
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_reusable_payment_link_response(reusable_payment_link),
+    ))
+}
+
 pub async fn intiate_payment_link_flow(
     state: AppState,
     merchant_account: domain::MerchantAccount,
@@ -43,44 +287,27 @@ pub async fn intiate_payment_link_flow(
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
 
-    helpers::validate_payment_status_against_not_allowed_statuses(
-        &payment_intent.status,
-        &[
-            storage_enums::IntentStatus::Cancelled,
-            storage_enums::IntentStatus::Succeeded,
-            storage_enums::IntentStatus::Processing,
-            storage_enums::IntentStatus::RequiresCapture,
-            storage_enums::IntentStatus::RequiresMerchantAction,
-        ],
-        "create payment link",
-    )?;
+    let payment_link = find_payment_link_of_payment(db, &payment_intent).await?;
+    let payment_link_config =
+        get_payment_link_config(&merchant_account, payment_link.payment_link_config.clone())?;
 
-    let fulfillment_time = payment_intent
-        .payment_link_id
-        .as_ref()
-        .async_and_then(|pli| async move {
-            db.find_payment_link_by_payment_link_id(pli)
-                .await
-                .ok()?
-                .fulfilment_time
-                .ok_or(errors::ApiErrorResponse::PaymentNotFound)
-                .ok()
-        })
-        .await
+    // Payments that can no longer be paid show their status instead of the payment form
+    if NOT_PAYABLE_STATUSES.contains(&payment_intent.status)
+        || is_payment_link_expired(&payment_link)
+    {
+        return get_payment_link_status_response(
+            &merchant_account,
+            payment_intent,
+            &payment_link,
+            &payment_link_config,
+        );
+    }
+
+    let fulfillment_time = payment_link
+        .fulfilment_time
         .get_required_value("fulfillment_time")
         .change_context(errors::ApiErrorResponse::PaymentNotFound)?;
 
-    let payment_link_config = merchant_account
-        .payment_link_config
-        .map(|pl_config| {
-            serde_json::from_value::<admin_types::PaymentLinkConfig>(pl_config)
-                .into_report()
-                .change_context(errors::ApiErrorResponse::InvalidDataValue {
-                    field_name: "payment_link_config",
-                })
-        })
-        .transpose()?;
-
     let order_details = payment_intent
         .order_details
         .get_required_value("order_details")
@@ -88,41 +315,35 @@ pub async fn intiate_payment_link_flow(
             field_name: "order_details",
         })?;
 
-    let return_url = if let Some(payment_create_return_url) = payment_intent.return_url {
-        payment_create_return_url
-    } else {
-        merchant_account
-            .return_url
-            .ok_or(errors::ApiErrorResponse::MissingRequiredField {
-                field_name: "return_url",
-            })?
-    };
-
     let (pub_key, currency, client_secret) = validate_sdk_requirements(
-        merchant_account.publishable_key,
+        merchant_account.publishable_key.clone(),
         payment_intent.currency,
         payment_intent.client_secret,
     )?;
 
+    let locale = get_locale(&payment_link_config);
     let payment_details = api_models::payments::PaymentLinkDetails {
         amount: payment_intent.amount,
         currency,
         payment_id: payment_intent.payment_id,
-        merchant_name: merchant_account.merchant_name,
+        merchant_name: get_merchant_display_name(&merchant_account, &payment_link_config),
         order_details,
-        return_url,
+        // The customer is returned to the status page of the payment link, which links back to
+        // the return URL of the merchant
+        return_url: get_payment_link_status_url(&state, &payment_link),
         expiry: fulfillment_time,
         pub_key,
         client_secret,
         merchant_logo: payment_link_config
+            .merchant_logo
             .clone()
-            .map(|pl_metadata| pl_metadata.merchant_logo.unwrap_or_default())
             .unwrap_or_default(),
         max_items_visible_after_collapse: 3,
+        locale: locale.to_owned(),
     };
 
-    let js_script = get_js_script(payment_details)?;
-    let css_script = get_color_scheme_css(payment_link_config.clone());
+    let js_script = get_js_script(payment_details, &get_payment_link_strings(locale)?)?;
+    let css_script = get_color_scheme_css(&payment_link_config);
     let payment_link_data = services::PaymentLinkFormData {
         js_script,
         sdk_url: state.conf.payment_link.sdk_url.clone(),
@@ -133,42 +354,1023 @@ pub async fn intiate_payment_link_flow(
     )))
 }
 
+pub async fn get_payment_link_status(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    merchant_id: String,
+    payment_id: String,
+) -> RouterResponse<()> {
+    let db = &*state.store;
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_link = find_payment_link_of_payment(db, &payment_intent).await?;
+    let payment_link_config =
+        get_payment_link_config(&merchant_account, payment_link.payment_link_config.clone())?;
+
+    get_payment_link_status_response(
+        &merchant_account,
+        payment_intent,
+        &payment_link,
+        &payment_link_config,
+    )
+}
+
+/// Shows the page of the reusable payment link, where customers choose the amount they pay on
+/// links without a fixed amount before paying. Links that can no longer be used show that they
+/// are unavailable. Nothing is created when the page is visited, so that visits by link previews
+/// or reloads do not use up the link.
+pub async fn initiate_reusable_payment_link_flow(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: api_models::payments::ReusablePaymentLinkInitiateRequest,
+) -> RouterResponse<()> {
+    let reusable_payment_link = state
+        .store
+        .find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+            &request.merchant_id,
+            &request.reusable_payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    let payment_link_config = get_payment_link_config(
+        &merchant_account,
+        reusable_payment_link.payment_link_config.clone(),
+    )?;
+    let is_available = is_reusable_payment_link_available(&reusable_payment_link);
+    get_reusable_payment_link_form_response(
+        &merchant_account,
+        reusable_payment_link,
+        &payment_link_config,
+        is_available,
+        None,
+    )
+}
+
+/// Creates a payment from the reusable payment link once the customer submits its page, and
+/// redirects the customer to the payment link of the payment. The page is shown again when the
+/// amount chosen by the customer is invalid or the link can no longer be used.
+pub async fn pay_reusable_payment_link_flow<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    request: api_models::payments::ReusablePaymentLinkPaymentRequest,
+) -> RouterResponse<()> {
+    let reusable_payment_link = state
+        .store
+        .find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+            &request.merchant_id,
+            &request.reusable_payment_link_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    let is_available = is_reusable_payment_link_available(&reusable_payment_link);
+    let amount = reusable_payment_link.amount.or_else(|| {
+        request
+            .amount
+            .as_deref()
+            .and_then(|amount| parse_amount_in_base_unit(reusable_payment_link.currency, amount))
+            .filter(|amount| is_amount_within_bounds(&reusable_payment_link, *amount))
+    });
+
+    let amount = match amount {
+        Some(amount) if is_available => amount,
+        _ => {
+            let payment_link_config = get_payment_link_config(
+                &merchant_account,
+                reusable_payment_link.payment_link_config.clone(),
+            )?;
+            let is_amount_invalid = request.amount.is_some() && amount.is_none();
+            return get_reusable_payment_link_form_response(
+                &merchant_account,
+                reusable_payment_link,
+                &payment_link_config,
+                is_available,
+                request.amount.filter(|_| is_amount_invalid),
+            );
+        }
+    };
+
+    // A use of the link is reserved before its payment is created, so that links paid
+    // concurrently are not paid more times than allowed
+    let reusable_payment_link = match state
+        .store
+        .reserve_reusable_payment_link_usage(&reusable_payment_link.reusable_payment_link_id)
+        .await
+    {
+        Ok(reusable_payment_link) => reusable_payment_link,
+        Err(error) if error.current_context().is_db_not_found() => {
+            let payment_link_config = get_payment_link_config(
+                &merchant_account,
+                reusable_payment_link.payment_link_config.clone(),
+            )?;
+            return get_reusable_payment_link_form_response(
+                &merchant_account,
+                reusable_payment_link,
+                &payment_link_config,
+                false,
+                None,
+            );
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to reserve a use of the reusable payment link")?,
+    };
+
+    let reusable_payment_link_id = reusable_payment_link.reusable_payment_link_id.clone();
+    let payment_link = match create_reusable_payment_link_payment::<Ctx>(
+        state.clone(),
+        merchant_account,
+        key_store,
+        reusable_payment_link,
+        amount,
+    )
+    .await
+    {
+        Ok(payment_link) => payment_link,
+        Err(error) => {
+            if let Err(release_error) = state
+                .store
+                .release_reusable_payment_link_usage(&reusable_payment_link_id)
+                .await
+            {
+                logger::error!(
+                    ?release_error,
+                    "Failed to release the use reserved for the payment of a reusable payment link"
+                );
+            }
+            return Err(error);
+        }
+    };
+
+    Ok(services::ApplicationResponse::JsonForRedirection(
+        api::RedirectionResponse {
+            return_url: String::new(),
+            params: vec![],
+            return_url_with_query_params: payment_link.link,
+            http_method: services::Method::Get.to_string(),
+            headers: vec![],
+        },
+    ))
+}
+
+/// Creates the payment of a reusable payment link, returning the payment link of the payment.
+async fn create_reusable_payment_link_payment<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    reusable_payment_link: storage::ReusablePaymentLink,
+    amount: i64,
+) -> RouterResult<api_models::payments::PaymentLinkResponse> {
+    let payment_link_config = reusable_payment_link
+        .payment_link_config
+        .clone()
+        .map(|pl_config| {
+            serde_json::from_value::<admin_types::PaymentLinkConfig>(pl_config)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "payment_link_config",
+                })
+        })
+        .transpose()?;
+
+    let payment_request = api_models::payments::PaymentsRequest {
+        amount: Some(amount.into()),
+        currency: Some(reusable_payment_link.currency),
+        confirm: Some(false),
+        description: reusable_payment_link.description,
+        return_url: reusable_payment_link
+            .return_url
+            .as_deref()
+            .and_then(|return_url| url::Url::parse(return_url).ok()),
+        order_details: Some(vec![api_models::payments::OrderDetailsWithAmount {
+            product_name: reusable_payment_link.product_name,
+            quantity: 1,
+            amount,
+            product_img_link: reusable_payment_link.product_img_link,
+        }]),
+        payment_link_object: Some(api_models::payments::PaymentLinkObject {
+            link_expiry: None,
+            merchant_custom_domain_name: reusable_payment_link.merchant_custom_domain_name,
+            payment_link_config,
+            reusable_payment_link_id: Some(reusable_payment_link.reusable_payment_link_id),
+            invoice_id: None,
+        }),
+        ..Default::default()
+    };
+
+    let payments_response =
+        match payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Ctx>(
+            state,
+            merchant_account,
+            key_store,
+            payments::PaymentCreate,
+            payment_request,
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+        .await?
+        {
+            services::ApplicationResponse::Json(payments_response)
+            | services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => {
+                payments_response
+            }
+            _ => Err(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable(
+                    "Unexpected response while creating the payment of a reusable payment link",
+                )?,
+        };
+
+    payments_response
+        .payment_link
+        .get_required_value("payment_link")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Payment link was not created for the payment of a reusable payment link")
+}
+
+async fn find_payment_link_of_payment(
+    db: &dyn StorageInterface,
+    payment_intent: &storage::PaymentIntent,
+) -> RouterResult<storage::PaymentLink> {
+    let payment_link_id = payment_intent
+        .payment_link_id
+        .as_deref()
+        .get_required_value("payment_link_id")
+        .change_context(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    db.find_payment_link_by_payment_link_id(payment_link_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)
+}
+
+fn get_payment_link_status_response<R>(
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: storage::PaymentIntent,
+    payment_link: &storage::PaymentLink,
+    payment_link_config: &admin_types::PaymentLinkConfig,
+) -> RouterResponse<R> {
+    let currency = payment_intent
+        .currency
+        .get_required_value("currency")
+        .change_context(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "currency",
+        })?;
+
+    let return_url = payment_intent
+        .return_url
+        .as_deref()
+        .or(merchant_account.return_url.as_deref())
+        .and_then(|return_url| {
+            get_merchant_return_url(
+                return_url,
+                &payment_intent.payment_id,
+                payment_intent.status,
+            )
+        });
+
+    let locale = get_locale(payment_link_config);
+    let status_details = api_models::payments::PaymentLinkStatusDetails {
+        amount: currency
+            .to_currency_base_unit(payment_intent.amount)
+            .unwrap_or_else(|_| payment_intent.amount.to_string()),
+        currency,
+        is_link_expired: payment_intent.status != storage_enums::IntentStatus::Succeeded
+            && is_payment_link_expired(payment_link),
+        payment_id: payment_intent.payment_id,
+        status: payment_intent.status,
+        created: payment_intent.created_at,
+        merchant_logo: payment_link_config
+            .merchant_logo
+            .clone()
+            .unwrap_or_default(),
+        merchant_name: get_merchant_display_name(merchant_account, payment_link_config),
+        return_url,
+        locale: locale.to_owned(),
+    };
+
+    Ok(services::ApplicationResponse::Html {
+        template: services::HtmlTemplate::PaymentLinkStatus,
+        context: serde_json::json!({
+            "css_color_scheme": get_color_scheme_css(payment_link_config),
+            "details": status_details,
+            "strings": get_payment_link_strings(locale)?,
+        }),
+    })
+}
+
+/// The return URL of the merchant, with the identifier and status of the payment appended as
+/// query parameters. URLs that are not http(s) URLs are not linked to.
+fn get_merchant_return_url(
+    return_url: &str,
+    payment_id: &str,
+    status: storage_enums::IntentStatus,
+) -> Option<String> {
+    let mut return_url = url::Url::parse(return_url)
+        .ok()
+        .filter(|url| matches!(url.scheme(), "http" | "https"))?;
+    return_url
+        .query_pairs_mut()
+        .append_pair("payment_id", payment_id)
+        .append_pair("status", &status.to_string());
+    Some(return_url.into())
+}
+
+/// The URL of the status page of the payment link, on the same domain as the payment link.
+fn get_payment_link_status_url(state: &AppState, payment_link: &storage::PaymentLink) -> String {
+    let path = format!(
+        "/payment_link/{}/{}",
+        payment_link.merchant_id, payment_link.payment_id
+    );
+    let domain = payment_link
+        .link_to_pay
+        .strip_suffix(&path)
+        .unwrap_or(state.conf.server.base_url.as_str());
+
+    format!(
+        "{domain}/payment_link/status/{}/{}",
+        payment_link.merchant_id, payment_link.payment_id
+    )
+}
+
+fn is_payment_link_expired(payment_link: &storage::PaymentLink) -> bool {
+    payment_link.status == storage_enums::PaymentLinkStatus::Expired
+        || payment_link
+            .fulfilment_time
+            .map_or(false, |fulfilment_time| fulfilment_time < date_time::now())
+}
+
+/// The configuration of the payment link, where the configuration provided while creating the
+/// payment link overrides the payment link configuration of the merchant.
+fn get_payment_link_config(
+    merchant_account: &domain::MerchantAccount,
+    payment_link_config: Option<serde_json::Value>,
+) -> RouterResult<admin_types::PaymentLinkConfig> {
+    let parse_config = |payment_link_config: Option<serde_json::Value>| {
+        payment_link_config
+            .map(|pl_config| {
+                serde_json::from_value::<admin_types::PaymentLinkConfig>(pl_config)
+                    .into_report()
+                    .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                        field_name: "payment_link_config",
+                    })
+            })
+            .transpose()
+            .map(Option::unwrap_or_default)
+    };
+
+    let merchant_config = parse_config(merchant_account.payment_link_config.clone())?;
+    let link_config = parse_config(payment_link_config)?;
+
+    Ok(merge_payment_link_config(link_config, merchant_config))
+}
+
+fn merge_payment_link_config(
+    config: admin_types::PaymentLinkConfig,
+    fallback: admin_types::PaymentLinkConfig,
+) -> admin_types::PaymentLinkConfig {
+    admin_types::PaymentLinkConfig {
+        merchant_logo: config.merchant_logo.or(fallback.merchant_logo),
+        color_scheme: config.color_scheme.or(fallback.color_scheme),
+        display_name: config.display_name.or(fallback.display_name),
+        custom_css: config.custom_css.or(fallback.custom_css),
+        locale: config.locale.or(fallback.locale),
+        expiry_in_seconds: config.expiry_in_seconds.or(fallback.expiry_in_seconds),
+    }
+}
+
+/// Verifies the payment link configuration of a merchant or of a payment link.
+pub fn validate_payment_link_config(
+    payment_link_config: &admin_types::PaymentLinkConfig,
+) -> RouterResult<()> {
+    let is_valid_color =
+        |color: &Option<String>| color.as_deref().map_or(true, utils::is_valid_hex_color);
+    let has_valid_colors = payment_link_config
+        .color_scheme
+        .as_ref()
+        .map_or(true, |color_scheme| {
+            is_valid_color(&color_scheme.primary_color)
+                && is_valid_color(&color_scheme.primary_accent_color)
+                && is_valid_color(&color_scheme.secondary_color)
+        });
+    utils::when(!has_valid_colors, || {
+        Err(errors::ApiErrorResponse::InvalidDataFormat {
+            field_name: "payment_link_config.color_scheme".to_string(),
+            expected_format: "hex colours such as #0099FF".to_string(),
+        })
+        .into_report()
+    })?;
+
+    let has_valid_display_name =
+        payment_link_config
+            .display_name
+            .as_ref()
+            .map_or(true, |display_name| {
+                !display_name.trim().is_empty()
+                    && display_name.chars().count() <= MAX_DISPLAY_NAME_LENGTH
+            });
+    utils::when(!has_valid_display_name, || {
+        Err(errors::ApiErrorResponse::InvalidDataValue {
+            field_name: "payment_link_config.display_name",
+        })
+        .into_report()
+        .attach_printable(format!(
+            "display_name must be between 1 and {MAX_DISPLAY_NAME_LENGTH} characters long"
+        ))
+    })?;
+
+    // The CSS is rendered into a style element, which it must not be able to close
+    let has_valid_custom_css = payment_link_config
+        .custom_css
+        .as_ref()
+        .map_or(true, |custom_css| {
+            custom_css.len() <= MAX_CUSTOM_CSS_LENGTH && !custom_css.contains('<')
+        });
+    utils::when(!has_valid_custom_css, || {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "payment_link_config.custom_css must be at most {MAX_CUSTOM_CSS_LENGTH} characters long and must not contain '<'"
+            ),
+        })
+        .into_report()
+    })?;
+
+    utils::when(
+        payment_link_config
+            .expiry_in_seconds
+            .map_or(false, |expiry_in_seconds| {
+                expiry_in_seconds < MIN_EXPIRY_IN_SECONDS
+            }),
+        || {
+            Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "payment_link_config.expiry_in_seconds must be at least {MIN_EXPIRY_IN_SECONDS}"
+                ),
+            })
+            .into_report()
+        },
+    )
+}
+
+/// The time at which a payment link created at the provided time expires. An explicit
+/// `link_expiry` takes precedence over the expiry configured for the payment link, then for the
+/// merchant, and then in the application configuration.
+pub fn get_payment_link_expiry(
+    payment_link_conf: &settings::PaymentLink,
+    merchant_account: &domain::MerchantAccount,
+    payment_link_object: &api_models::payments::PaymentLinkObject,
+    created_at: PrimitiveDateTime,
+) -> RouterResult<PrimitiveDateTime> {
+    if let Some(link_expiry) = payment_link_object.link_expiry {
+        return Ok(link_expiry);
+    }
+
+    let merchant_expiry_in_seconds = merchant_account
+        .payment_link_config
+        .clone()
+        .map(|pl_config| {
+            serde_json::from_value::<admin_types::PaymentLinkConfig>(pl_config)
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "payment_link_config",
+                })
+        })
+        .transpose()?
+        .and_then(|pl_config| pl_config.expiry_in_seconds);
+
+    let expiry_in_seconds = payment_link_object
+        .payment_link_config
+        .as_ref()
+        .and_then(|pl_config| pl_config.expiry_in_seconds)
+        .or(merchant_expiry_in_seconds)
+        .unwrap_or(payment_link_conf.default_expiry_in_seconds);
+
+    Ok(created_at.saturating_add(time::Duration::seconds(i64::from(expiry_in_seconds))))
+}
+
+/// Schedules the expiry of the payment link at its fulfilment time.
+#[instrument(skip_all)]
+pub async fn add_payment_link_expiry_task(
+    db: &dyn StorageInterface,
+    payment_link: &storage::PaymentLink,
+) -> RouterResult<()> {
+    let Some(schedule_time) = payment_link.fulfilment_time else {
+        return Ok(());
+    };
+    let current_time = date_time::now();
+
+    let tracking_data = storage::PaymentLinkExpiryWorkflow {
+        merchant_id: payment_link.merchant_id.clone(),
+        payment_id: payment_link.payment_id.clone(),
+        payment_link_id: payment_link.payment_link_id.clone(),
+    };
+    let payment_link_expiry_workflow_model = serde_json::to_value(&tracking_data)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("unable to serialize payment link expiry tracker: {tracking_data:?}")
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: format!(
+            "{PAYMENT_LINK_EXPIRY_RUNNER}_{}",
+            payment_link.payment_link_id
+        ),
+        name: Some(String::from(PAYMENT_LINK_EXPIRY_NAME)),
+        tag: vec![String::from(PAYMENT_LINK_EXPIRY_TAG)],
+        runner: Some(String::from(PAYMENT_LINK_EXPIRY_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(schedule_time),
+        rule: String::new(),
+        tracking_data: payment_link_expiry_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting payment link expiry task to process_tracker: payment_link_id: {}",
+                payment_link.payment_link_id
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Expires the payment link, cancelling its payment if the customer has not confirmed it yet.
+/// Payment links whose payment has succeeded are marked as completed instead.
+#[instrument(skip_all, fields(payment_link_id = %tracking_data.payment_link_id))]
+pub async fn expire_payment_link(
+    state: &AppState,
+    tracking_data: &storage::PaymentLinkExpiryWorkflow,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let payment_link = db
+        .find_payment_link_by_payment_link_id(&tracking_data.payment_link_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+
+    if payment_link.status != storage_enums::PaymentLinkStatus::Active {
+        logger::info!(status = %payment_link.status, "Payment link is no longer active, skipping");
+        return Ok(());
+    }
+
+    let merchant_id = tracking_data.merchant_id.as_str();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let storage_scheme = merchant_account.storage_scheme;
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &tracking_data.payment_id,
+            merchant_id,
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    if payment_intent.status == storage_enums::IntentStatus::Succeeded {
+        return match db
+            .complete_payment_link(&payment_link.payment_link_id)
+            .await
+        {
+            Ok(_) => Ok(()),
+            Err(error) if error.current_context().is_db_not_found() => Ok(()),
+            Err(error) => Err(error)
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to mark payment link as completed"),
+        };
+    }
+
+    if CANCELLABLE_STATUSES.contains(&payment_intent.status) {
+        if let Some(payment_intent) = cancel_payment_of_payment_link(
+            state,
+            merchant_id,
+            &payment_intent.payment_id,
+            storage_scheme,
+            "payment_link_expired",
+        )
+        .await?
+        {
+            super::invoices::update_invoice_status_with_payment(state, &payment_intent).await;
+        }
+    }
+
+    // Payment links completed concurrently are not expired, and keep the use they reserved
+    match db.expire_payment_link(&payment_link.payment_link_id).await {
+        Ok(_) => Ok(()),
+        Err(error) if error.current_context().is_db_not_found() => {
+            logger::info!("Payment link is no longer active, skipping");
+            Ok(())
+        }
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to expire payment link"),
+    }
+}
+
+/// Cancels a payment that has not been confirmed through its payment link, so that it can no
+/// longer be paid. The payment is locked while it is cancelled, the same way it is while being
+/// confirmed, and payments confirmed in the meantime are left as they are and yield `None`.
+pub async fn cancel_payment_of_payment_link(
+    state: &AppState,
+    merchant_id: &str,
+    payment_id: &str,
+    storage_scheme: storage_enums::MerchantStorageScheme,
+    cancellation_reason: &str,
+) -> RouterResult<Option<storage::PaymentIntent>> {
+    let mut state = state.clone();
+    if state.get_request_id().is_none() {
+        // Tasks that are not run for a request hold the lock under an identifier of their own
+        state.add_request_id(uuid::Uuid::new_v4());
+    }
+    let lock_action = api_locking::LockAction::Hold {
+        input: api_locking::LockingInput {
+            unique_locking_key: payment_id.to_owned(),
+            api_identifier: lock_utils::ApiIdentifier::Payments,
+            override_lock_retries: None,
+        },
+    };
+
+    lock_action
+        .clone()
+        .perform_locking_action(&state, merchant_id.to_owned())
+        .await?;
+    let result = async {
+        let payment_intent = state
+            .store
+            .find_payment_intent_by_payment_id_merchant_id(payment_id, merchant_id, storage_scheme)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+        if !CANCELLABLE_STATUSES.contains(&payment_intent.status) {
+            return Ok(None);
+        }
+        cancel_payment_intent(
+            &*state.store,
+            payment_intent,
+            storage_scheme,
+            cancellation_reason,
+        )
+        .await
+        .map(Some)
+    }
+    .await;
+    lock_action
+        .free_lock_action(&state, merchant_id.to_owned())
+        .await?;
+
+    result
+}
+
+async fn cancel_payment_intent(
+    db: &dyn StorageInterface,
+    payment_intent: storage::PaymentIntent,
+    storage_scheme: storage_enums::MerchantStorageScheme,
+    cancellation_reason: &str,
+) -> RouterResult<storage::PaymentIntent> {
+    let payment_attempt = db
+        .find_payment_attempt_by_payment_id_merchant_id_attempt_id(
+            &payment_intent.payment_id,
+            &payment_intent.merchant_id,
+            &payment_intent.active_attempt.get_id(),
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let payment_intent = db
+        .update_payment_intent(
+            payment_intent,
+            storage::PaymentIntentUpdate::PGStatusUpdate {
+                status: storage_enums::IntentStatus::Cancelled,
+                updated_by: storage_scheme.to_string(),
+            },
+            storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    db.update_payment_attempt_with_attempt_id(
+        payment_attempt,
+        storage::PaymentAttemptUpdate::VoidUpdate {
+            status: storage_enums::AttemptStatus::Voided,
+            cancellation_reason: Some(cancellation_reason.to_string()),
+            updated_by: storage_scheme.to_string(),
+        },
+        storage_scheme,
+    )
+    .await
+    .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    Ok(payment_intent)
+}
+
+/// Marks the payment link of a payment that has succeeded as completed, recording its payment on
+/// the reusable payment link that it was created from. Payment links that have already been
+/// completed are left as they are. Failing to do so is logged and does not fail the payment.
+#[instrument(skip(state))]
+pub async fn mark_payment_link_completed(state: &AppState, payment_link_id: &str) {
+    match state.store.complete_payment_link(payment_link_id).await {
+        Ok(_) => {}
+        Err(error) if error.current_context().is_db_not_found() => {
+            logger::debug!("Payment link has already been completed");
+        }
+        Err(error) => logger::error!(?error, "Failed to mark payment link as completed"),
+    }
+}
+
+fn get_reusable_payment_link_form_response<R>(
+    merchant_account: &domain::MerchantAccount,
+    reusable_payment_link: storage::ReusablePaymentLink,
+    payment_link_config: &admin_types::PaymentLinkConfig,
+    is_available: bool,
+    invalid_amount: Option<String>,
+) -> RouterResponse<R> {
+    let currency = reusable_payment_link.currency;
+    let to_base_unit = |amount: i64| {
+        currency
+            .to_currency_base_unit_with_zero_decimal_check(amount)
+            .unwrap_or_else(|_| amount.to_string())
+    };
+
+    let locale = get_locale(payment_link_config);
+    let details = api_models::payments::ReusablePaymentLinkDetails {
+        product_name: reusable_payment_link.product_name,
+        product_img_link: reusable_payment_link.product_img_link,
+        description: reusable_payment_link.description,
+        currency,
+        min_amount: to_base_unit(
+            reusable_payment_link
+                .min_amount
+                .unwrap_or(DEFAULT_REUSABLE_MIN_AMOUNT),
+        ),
+        max_amount: reusable_payment_link.max_amount.map(to_base_unit),
+        fixed_amount: reusable_payment_link.amount.map(to_base_unit),
+        is_amount_invalid: invalid_amount.is_some(),
+        amount: invalid_amount,
+        is_available,
+        merchant_logo: payment_link_config
+            .merchant_logo
+            .clone()
+            .unwrap_or_default(),
+        merchant_name: get_merchant_display_name(merchant_account, payment_link_config),
+        locale: locale.to_owned(),
+    };
+
+    Ok(services::ApplicationResponse::Html {
+        template: services::HtmlTemplate::ReusablePaymentLink,
+        context: serde_json::json!({
+            "css_color_scheme": get_color_scheme_css(payment_link_config),
+            "details": details,
+            "strings": get_payment_link_strings(locale)?,
+        }),
+    })
+}
+
+fn validate_reusable_payment_link_request(
+    request: &api_models::payments::ReusablePaymentLinkCreateRequest,
+) -> RouterResult<()> {
+    let invalid_request = |message: &str| {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: message.to_string(),
+        })
+        .into_report()
+    };
+
+    match request.amount {
+        Some(amount) if amount < 1 => invalid_request("amount must be greater than 0"),
+        Some(_) if request.min_amount.is_some() || request.max_amount.is_some() => {
+            invalid_request("min_amount and max_amount cannot be provided along with amount")
+        }
+        Some(_) => Ok(()),
+        None => {
+            let min_amount = request.min_amount.unwrap_or(DEFAULT_REUSABLE_MIN_AMOUNT);
+            if min_amount < 1 {
+                invalid_request("min_amount must be greater than 0")
+            } else if request
+                .max_amount
+                .map_or(false, |max_amount| max_amount < min_amount)
+            {
+                invalid_request("max_amount cannot be less than min_amount")
+            } else {
+                Ok(())
+            }
+        }
+    }?;
+
+    utils::when(
+        request.max_uses.map_or(false, |max_uses| max_uses < 1),
+        || invalid_request("max_uses must be at least 1"),
+    )?;
+
+    utils::when(
+        request
+            .link_expiry
+            .map_or(false, |link_expiry| link_expiry <= date_time::now()),
+        || invalid_request("link_expiry time cannot be less than current time"),
+    )?;
+
+    let product_name = request.product_name.trim();
+    utils::when(
+        product_name.is_empty() || product_name.chars().count() > MAX_PRODUCT_NAME_LENGTH,
+        || {
+            invalid_request(&format!(
+                "product_name must be between 1 and {MAX_PRODUCT_NAME_LENGTH} characters long"
+            ))
+        },
+    )?;
+
+    request
+        .payment_link_config
+        .as_ref()
+        .map_or(Ok(()), validate_payment_link_config)
+}
+
+/// The reusable payment link with the status that it is in, where active links that are past
+/// their expiry are reported as expired.
+fn get_reusable_payment_link_response(
+    reusable_payment_link: storage::ReusablePaymentLink,
+) -> api_models::payments::ReusablePaymentLinkResponse {
+    let status = get_reusable_payment_link_status(&reusable_payment_link);
+    api_models::payments::ReusablePaymentLinkResponse {
+        status,
+        ..api_models::payments::ReusablePaymentLinkResponse::foreign_from(reusable_payment_link)
+    }
+}
+
+fn get_reusable_payment_link_status(
+    reusable_payment_link: &storage::ReusablePaymentLink,
+) -> storage_enums::PaymentLinkStatus {
+    let is_expired = reusable_payment_link
+        .link_expiry
+        .map_or(false, |link_expiry| link_expiry < date_time::now());
+    if reusable_payment_link.status == storage_enums::PaymentLinkStatus::Active && is_expired {
+        storage_enums::PaymentLinkStatus::Expired
+    } else {
+        reusable_payment_link.status
+    }
+}
+
+/// Whether customers can pay through the reusable payment link. A use is reserved for each payment
+/// created from the link until its payment link expires, so links whose uses are all reserved by
+/// payments in progress are unavailable as well.
+fn is_reusable_payment_link_available(
+    reusable_payment_link: &storage::ReusablePaymentLink,
+) -> bool {
+    get_reusable_payment_link_status(reusable_payment_link)
+        == storage_enums::PaymentLinkStatus::Active
+        && reusable_payment_link.max_uses.map_or(true, |max_uses| {
+            reusable_payment_link.times_reserved < max_uses
+        })
+}
+
+fn is_amount_within_bounds(
+    reusable_payment_link: &storage::ReusablePaymentLink,
+    amount: i64,
+) -> bool {
+    amount
+        >= reusable_payment_link
+            .min_amount
+            .unwrap_or(DEFAULT_REUSABLE_MIN_AMOUNT)
+        && reusable_payment_link
+            .max_amount
+            .map_or(true, |max_amount| amount <= max_amount)
+}
+
+/// Converts an amount in the base denomination of the currency, as entered by customers, to the
+/// lowest denomination of the currency. Amounts with more decimal places than the currency has
+/// are not accepted.
+fn parse_amount_in_base_unit(currency: storage_enums::Currency, amount: &str) -> Option<i64> {
+    let decimal_places = if currency.is_zero_decimal_currency() {
+        0
+    } else if currency.is_three_decimal_currency() {
+        3
+    } else {
+        2
+    };
+
+    let amount = amount.trim().replace(',', ".");
+    let (units, fraction) = amount.split_once('.').unwrap_or((amount.as_str(), ""));
+    let is_digits = |part: &str| part.chars().all(|c| c.is_ascii_digit());
+    if (units.is_empty() && fraction.is_empty())
+        || !is_digits(units)
+        || !is_digits(fraction)
+        || fraction.len() > decimal_places
+    {
+        return None;
+    }
+
+    format!("{units}{fraction:0<decimal_places$}").parse().ok()
+}
+
+fn get_locale(payment_link_config: &admin_types::PaymentLinkConfig) -> &'static str {
+    utils::resolve_locale(
+        payment_link_config.locale.as_deref(),
+        &SUPPORTED_LOCALES,
+        DEFAULT_LOCALE,
+    )
+}
+
+/// The strings shown on payment links, translated to the provided supported locale.
+fn get_payment_link_strings(locale: &str) -> RouterResult<serde_json::Value> {
+    let strings = LOCALE_STRINGS
+        .iter()
+        .find(|(strings_locale, _)| *strings_locale == locale)
+        .map(|(_, strings)| *strings)
+        .get_required_value("payment_link_strings")
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("No payment link strings for locale {locale}"))?;
+
+    serde_json::from_str(strings)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid payment link strings for locale {locale}"))
+}
+
+fn get_merchant_display_name(
+    merchant_account: &domain::MerchantAccount,
+    payment_link_config: &admin_types::PaymentLinkConfig,
+) -> String {
+    payment_link_config.display_name.clone().unwrap_or_else(|| {
+        merchant_account
+            .merchant_name
+            .clone()
+            .map(|merchant_name| merchant_name.into_inner().expose())
+            .unwrap_or_default()
+    })
+}
+
 /*
 The get_js_script function is used to inject dynamic value to payment_link sdk, which is unique to every payment.
 */
 
 fn get_js_script(
     payment_details: api_models::payments::PaymentLinkDetails,
+    strings: &serde_json::Value,
 ) -> RouterResult<String> {
-    let payment_details_str = serde_json::to_string(&payment_details)
+    let payment_details_str = to_script_json(&payment_details)
+        .attach_printable("Failed to serialize PaymentLinkDetails")?;
+    let strings_str =
+        to_script_json(strings).attach_printable("Failed to serialize payment link strings")?;
+    Ok(format!(
+        "window.__PAYMENT_DETAILS = {payment_details_str};\nwindow.__PAYMENT_LINK_STRINGS = {strings_str};"
+    ))
+}
+
+/// Serializes the value to JSON that can be embedded in a script element, which the values
+/// provided by merchants must not be able to close.
+fn to_script_json<T: Serialize>(value: &T) -> RouterResult<String> {
+    serde_json::to_string(value)
         .into_report()
         .change_context(errors::ApiErrorResponse::InternalServerError)
-        .attach_printable("Failed to serialize PaymentLinkDetails")?;
-    Ok(format!("window.__PAYMENT_DETAILS = {payment_details_str};"))
+        .map(|json| json.replace('<', "\\u003c"))
 }
 
-fn get_color_scheme_css(
-    payment_link_config: Option<api_models::admin::PaymentLinkConfig>,
-) -> String {
+fn get_color_scheme_css(payment_link_config: &admin_types::PaymentLinkConfig) -> String {
     let (default_primary_color, default_accent_color, default_secondary_color) = (
         "#C6C7C8".to_string(),
         "#6A8EF5".to_string(),
         "#0C48F6".to_string(),
     );
 
+    // Colours are rendered into a style element, only hex colours are used
+    let color_or_default = |color: &Option<String>, default: String| {
+        color
+            .clone()
+            .filter(|color| utils::is_valid_hex_color(color))
+            .unwrap_or(default)
+    };
+
     let (primary_color, primary_accent_color, secondary_color) = payment_link_config
-        .and_then(|pl_config| {
-            pl_config.color_scheme.map(|color| {
-                (
-                    color.primary_color.unwrap_or(default_primary_color.clone()),
-                    color
-                        .primary_accent_color
-                        .unwrap_or(default_accent_color.clone()),
-                    color
-                        .secondary_color
-                        .unwrap_or(default_secondary_color.clone()),
-                )
-            })
+        .color_scheme
+        .as_ref()
+        .map(|color| {
+            (
+                color_or_default(&color.primary_color, default_primary_color.clone()),
+                color_or_default(&color.primary_accent_color, default_accent_color.clone()),
+                color_or_default(&color.secondary_color, default_secondary_color.clone()),
+            )
         })
         .unwrap_or((
             default_primary_color,
@@ -176,12 +1378,20 @@ fn get_color_scheme_css(
             default_secondary_color,
         ));
 
+    // Custom CSS is validated not to contain '<' when it is configured
+    let custom_css = payment_link_config
+        .custom_css
+        .as_deref()
+        .filter(|custom_css| !custom_css.contains('<'))
+        .unwrap_or_default();
+
     format!(
         ":root {{
       --primary-color: {primary_color};
       --primary-accent-color: {primary_accent_color};
       --secondary-color: {secondary_color};
-    }}"
+    }}
+    {custom_css}"
     )
 }
 
@@ -203,3 +1413,89 @@ fn validate_sdk_requirements(
     })?;
     Ok((pub_key, currency, client_secret))
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_payment_link_strings() {
+        let english_strings = get_payment_link_strings(DEFAULT_LOCALE).unwrap();
+        let english_keys = english_strings.as_object().unwrap();
+        for locale in SUPPORTED_LOCALES {
+            let strings = get_payment_link_strings(locale).unwrap();
+            let keys = strings.as_object().unwrap();
+            assert!(
+                keys.keys().eq(english_keys.keys()),
+                "strings of {locale} differ from the default locale"
+            );
+        }
+    }
+
+    #[test]
+    fn test_color_scheme_css() {
+        let payment_link_config = admin_types::PaymentLinkConfig {
+            color_scheme: Some(admin_types::PaymentLinkColorSchema {
+                primary_color: Some("#FF0000".to_string()),
+                primary_accent_color: Some("red; } body { display: none".to_string()),
+                secondary_color: None,
+            }),
+            custom_css: Some(".hyper-checkout { font-size: 14px; }".to_string()),
+            ..Default::default()
+        };
+
+        let css = get_color_scheme_css(&payment_link_config);
+        assert!(css.contains("--primary-color: #FF0000;"));
+        assert!(css.contains("--primary-accent-color: #6A8EF5;"));
+        assert!(css.contains(".hyper-checkout { font-size: 14px; }"));
+        assert!(!css.contains("display: none"));
+    }
+
+    #[test]
+    fn test_merchant_return_url() {
+        assert_eq!(
+            get_merchant_return_url(
+                "https://example.com/orders?id=1",
+                "pay_1",
+                storage_enums::IntentStatus::Succeeded
+            )
+            .as_deref(),
+            Some("https://example.com/orders?id=1&payment_id=pay_1&status=succeeded")
+        );
+        assert_eq!(
+            get_merchant_return_url(
+                "javascript:alert(1)",
+                "pay_1",
+                storage_enums::IntentStatus::Succeeded
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_parse_amount_in_base_unit() {
+        let usd = storage_enums::Currency::USD;
+        assert_eq!(parse_amount_in_base_unit(usd, "10"), Some(1000));
+        assert_eq!(parse_amount_in_base_unit(usd, " 10.5 "), Some(1050));
+        assert_eq!(parse_amount_in_base_unit(usd, "10,05"), Some(1005));
+        assert_eq!(parse_amount_in_base_unit(usd, ".5"), Some(50));
+        assert_eq!(parse_amount_in_base_unit(usd, "10.505"), None);
+        assert_eq!(parse_amount_in_base_unit(usd, "-10"), None);
+        assert_eq!(parse_amount_in_base_unit(usd, "1e3"), None);
+        assert_eq!(parse_amount_in_base_unit(usd, "."), None);
+        assert_eq!(
+            parse_amount_in_base_unit(storage_enums::Currency::JPY, "500"),
+            Some(500)
+        );
+        assert_eq!(
+            parse_amount_in_base_unit(storage_enums::Currency::JPY, "500.5"),
+            None
+        );
+        assert_eq!(
+            parse_amount_in_base_unit(storage_enums::Currency::KWD, "1.5"),
+            Some(1500)
+        );
+    }
+}
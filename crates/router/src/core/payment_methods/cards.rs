@@ -313,7 +313,7 @@ pub async fn decode_and_decrypt_locker_data(
     enc_card_data: String,
 ) -> errors::CustomResult<Secret<String>, errors::VaultError> {
     // Fetch key
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
     // Decode
     let decoded_bytes = hex::decode(&enc_card_data)
         .into_report()
@@ -338,10 +338,7 @@ pub async fn get_payment_method_from_hs_locker<'a>(
     payment_method_reference: &'a str,
 ) -> errors::CustomResult<Secret<String>, errors::VaultError> {
     let locker = &state.conf.locker;
-    #[cfg(not(feature = "kms"))]
-    let jwekey = &state.conf.jwekey;
-    #[cfg(feature = "kms")]
-    let jwekey = &state.kms_secrets;
+    let jwekey = &state.active_secrets;
 
     let payment_method_data = if !locker.mock_locker {
         let request = payment_methods::mk_get_card_request_hs(
@@ -393,10 +390,7 @@ pub async fn call_to_locker_hs<'a>(
     customer_id: &str,
 ) -> errors::CustomResult<payment_methods::StoreCardRespPayload, errors::VaultError> {
     let locker = &state.conf.locker;
-    #[cfg(not(feature = "kms"))]
-    let jwekey = &state.conf.jwekey;
-    #[cfg(feature = "kms")]
-    let jwekey = &state.kms_secrets;
+    let jwekey = &state.active_secrets;
     let db = &*state.store;
     let stored_card_response = if !locker.mock_locker {
         let request = payment_methods::mk_add_locker_request_hs(jwekey, locker, payload).await?;
@@ -450,10 +444,7 @@ pub async fn get_card_from_hs_locker<'a>(
     card_reference: &'a str,
 ) -> errors::CustomResult<payment_methods::Card, errors::VaultError> {
     let locker = &state.conf.locker;
-    #[cfg(not(feature = "kms"))]
-    let jwekey = &state.conf.jwekey;
-    #[cfg(feature = "kms")]
-    let jwekey = &state.kms_secrets;
+    let jwekey = &state.active_secrets;
 
     if !locker.mock_locker {
         let request = payment_methods::mk_get_card_request_hs(
@@ -503,10 +494,7 @@ pub async fn delete_card_from_hs_locker<'a>(
     card_reference: &'a str,
 ) -> errors::RouterResult<payment_methods::DeleteCardResp> {
     let locker = &state.conf.locker;
-    #[cfg(not(feature = "kms"))]
-    let jwekey = &state.conf.jwekey;
-    #[cfg(feature = "kms")]
-    let jwekey = &state.kms_secrets;
+    let jwekey = &state.active_secrets;
 
     let request = payment_methods::mk_delete_card_request_hs(
         jwekey,
@@ -1315,6 +1303,8 @@ pub async fn list_payment_methods(
                             start_date: i.start_date,
                             end_date: i.end_date,
                             metadata: i.metadata,
+                            frequency_limit: i.frequency_limit,
+                            frequency_interval: i.frequency_interval,
                         })
                     }
                     data_models::mandates::MandateDataType::MultiUse(Some(i)) => {
@@ -1324,6 +1314,8 @@ pub async fn list_payment_methods(
                             start_date: i.start_date,
                             end_date: i.end_date,
                             metadata: i.metadata,
+                            frequency_limit: i.frequency_limit,
+                            frequency_interval: i.frequency_interval,
                         }))
                     }
                     data_models::mandates::MandateDataType::MultiUse(None) => {
@@ -1850,7 +1842,7 @@ pub async fn list_customer_payment_method(
     .await
     .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
 
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
 
     let is_requires_cvv = db
         .find_config_by_key_unwrap_or(
@@ -1969,7 +1961,7 @@ pub async fn list_customer_payment_method(
 
 async fn get_card_details(
     pm: &payment_method::PaymentMethod,
-    key: &[u8],
+    key: &domain::types::KeyRing,
     state: &routes::AppState,
     hyperswitch_token: &str,
 ) -> errors::RouterResult<Option<api::CardDetailFromLocker>> {
@@ -2316,7 +2308,7 @@ pub async fn create_encrypted_payment_method_data(
     key_store: &domain::MerchantKeyStore,
     pm_data: Option<PaymentMethodsData>,
 ) -> Option<Encryption> {
-    let key = key_store.key.get_inner().peek();
+    let key = &key_store.key_ring();
 
     let pm_data_encrypted: Option<Encryption> = pm_data
         .as_ref()
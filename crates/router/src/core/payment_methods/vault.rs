@@ -920,7 +920,7 @@ pub fn get_key_id(keys: &settings::Jwekey) -> &str {
 
 #[cfg(feature = "basilisk")]
 async fn get_locker_jwe_keys(
-    keys: &settings::ActiveKmsSecrets,
+    keys: &settings::ActiveSecrets,
 ) -> CustomResult<(String, String), errors::EncryptionError> {
     let keys = keys.jwekey.peek();
     let key_id = get_key_id(keys);
@@ -954,7 +954,7 @@ pub async fn create_tokenize(
     )
     .change_context(errors::ApiErrorResponse::InternalServerError)?;
 
-    let (public_key, private_key) = get_locker_jwe_keys(&state.kms_secrets)
+    let (public_key, private_key) = get_locker_jwe_keys(&state.active_secrets)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Error getting Encryption key")?;
@@ -1031,7 +1031,7 @@ pub async fn get_tokenized_data(
     let payload = serde_json::to_string(&payload_to_be_encrypted)
         .map_err(|_x| errors::ApiErrorResponse::InternalServerError)?;
 
-    let (public_key, private_key) = get_locker_jwe_keys(&state.kms_secrets)
+    let (public_key, private_key) = get_locker_jwe_keys(&state.active_secrets)
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Error getting Encryption key")?;
@@ -1110,7 +1110,7 @@ pub async fn delete_tokenized_data(
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Error serializing api::DeleteTokenizeByTokenRequest")?;
 
-    let (public_key, _private_key) = get_locker_jwe_keys(&state.kms_secrets.clone())
+    let (public_key, _private_key) = get_locker_jwe_keys(&state.active_secrets.clone())
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)
         .attach_printable("Error getting Encryption key")?;
@@ -117,6 +117,8 @@ where
         )
         .await?;
 
+    let previous_intent_status = payment_data.payment_intent.status;
+
     let (operation, customer) = operation
         .to_domain()?
         .get_or_create_customer_details(
@@ -249,6 +251,30 @@ where
             .await?;
     }
 
+    if previous_intent_status != storage_enums::IntentStatus::Succeeded
+        && payment_data.payment_intent.status == storage_enums::IntentStatus::Succeeded
+    {
+        #[cfg(feature = "email")]
+        super::transactional_emails::trigger_payment_succeeded_email(
+            state,
+            &merchant_account,
+            &payment_data.payment_intent,
+        )
+        .await;
+
+        if let Some(payment_link_id) = &payment_data.payment_intent.payment_link_id {
+            super::payment_link::mark_payment_link_completed(state, payment_link_id).await;
+        }
+    }
+
+    // Invoices are collected through payment links, their status follows the status of the payment
+    if previous_intent_status != payment_data.payment_intent.status
+        && payment_data.payment_intent.payment_link_id.is_some()
+    {
+        super::invoices::update_invoice_status_with_payment(state, &payment_data.payment_intent)
+            .await;
+    }
+
     Ok((
         payment_data,
         req,
@@ -1692,11 +1718,13 @@ pub async fn apply_filters_on_payments(
     let limit = &constraints.limit;
     helpers::validate_payment_list_request_for_joins(*limit)?;
     let db = state.store.as_ref();
+    let merchant_ids = [merchant.merchant_id.clone()];
     let list: Vec<(storage::PaymentIntent, storage::PaymentAttempt)> = db
         .get_filtered_payment_intents_attempt(
-            &merchant.merchant_id,
+            &merchant_ids,
             &constraints.clone().into(),
             merchant.storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?
@@ -1709,22 +1737,24 @@ pub async fn apply_filters_on_payments(
 
     let active_attempt_ids = db
         .get_filtered_active_attempt_ids_for_total_count(
-            &merchant.merchant_id,
+            &merchant_ids,
             &constraints.clone().into(),
             merchant.storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
 
     let total_count = db
         .get_total_count_of_filtered_payment_attempts(
-            &merchant.merchant_id,
+            &merchant_ids,
             &active_attempt_ids,
             constraints.connector,
             constraints.payment_method,
             constraints.payment_method_type,
             constraints.authentication_type,
             merchant.storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .change_context(errors::ApiErrorResponse::InternalServerError)?;
@@ -1750,6 +1780,7 @@ pub async fn get_filters_for_payments(
             &merchant.merchant_id,
             &time_range,
             merchant.storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
@@ -1760,6 +1791,7 @@ pub async fn get_filters_for_payments(
             &merchant.merchant_id,
             // since OLAP doesn't have KV. Force to get the data from PSQL.
             storage_enums::MerchantStorageScheme::PostgresOnly,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
@@ -83,6 +83,11 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
         if self.should_proceed_with_authorize() {
             self.decide_authentication_type();
             logger::debug!(auth_type=?self.auth_type);
+            // Payments made against a mandate are recorded before the connector is called, so
+            // that concurrent payments cannot exceed the frequency limit of the mandate
+            if let Some(mandate_ids) = self.request.mandate_id.as_ref() {
+                mandate::reserve_mandate_usage(state, &self, &mandate_ids.mandate_id).await?;
+            }
             let resp = services::execute_connector_processing_step(
                 state,
                 connector_integration,
@@ -90,8 +95,11 @@ impl Feature<api::Authorize, types::PaymentsAuthorizeData> for types::PaymentsAu
                 call_connector_action,
                 connector_request,
             )
-            .await
-            .to_payment_failed_response()?;
+            .await;
+            if resp.is_err() && self.request.mandate_id.is_some() {
+                mandate::release_mandate_usage(state, &self.merchant_id, &self.attempt_id).await;
+            }
+            let resp = resp.to_payment_failed_response()?;
 
             metrics::PAYMENT_COUNT.add(&metrics::CONTEXT, 1, &[]); // Metrics
 
@@ -256,6 +264,9 @@ impl mandate::MandateBehaviour for types::PaymentsAuthorizeData {
     fn get_amount(&self) -> i64 {
         self.amount
     }
+    fn get_currency(&self) -> diesel_models::enums::Currency {
+        self.currency
+    }
     fn get_mandate_id(&self) -> Option<&api_models::payments::MandateIds> {
         self.mandate_id.as_ref()
     }
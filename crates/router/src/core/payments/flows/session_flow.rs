@@ -2,8 +2,7 @@ use api_models::payments as payment_types;
 use async_trait::async_trait;
 use common_utils::ext_traits::ByteSliceExt;
 use error_stack::{IntoReport, Report, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management;
 
 use super::{ConstructFlowSpecificData, Feature};
 use crate::{
@@ -181,28 +180,23 @@ async fn create_applepay_session_token(
                     payment_request_data,
                     session_token_data,
                 } => {
-                    #[cfg(feature = "kms")]
-                    let decrypted_apple_pay_merchant_cert = kms::get_kms_client(&state.conf.kms)
-                        .await
+                    let secrets_manager =
+                        secrets_management::get_secrets_manager(&state.conf.secrets_management)
+                            .await;
+
+                    let decrypted_apple_pay_merchant_cert = secrets_manager
                         .decrypt(&state.conf.applepay_decrypt_keys.apple_pay_merchant_cert)
                         .await
                         .change_context(errors::ApiErrorResponse::InternalServerError)
                         .attach_printable("Apple pay merchant certificate decryption failed")?;
 
-                    #[cfg(feature = "kms")]
-                    let decrypted_apple_pay_merchant_cert_key =
-                        kms::get_kms_client(&state.conf.kms)
-                            .await
-                            .decrypt(&state.conf.applepay_decrypt_keys.apple_pay_merchant_cert_key)
-                            .await
-                            .change_context(errors::ApiErrorResponse::InternalServerError)
-                            .attach_printable(
-                                "Apple pay merchant certificate key decryption failed",
-                            )?;
-
-                    #[cfg(feature = "kms")]
-                    let decrypted_merchant_identifier = kms::get_kms_client(&state.conf.kms)
+                    let decrypted_apple_pay_merchant_cert_key = secrets_manager
+                        .decrypt(&state.conf.applepay_decrypt_keys.apple_pay_merchant_cert_key)
                         .await
+                        .change_context(errors::ApiErrorResponse::InternalServerError)
+                        .attach_printable("Apple pay merchant certificate key decryption failed")?;
+
+                    let decrypted_merchant_identifier = secrets_manager
                         .decrypt(
                             &state
                                 .conf
@@ -213,30 +207,16 @@ async fn create_applepay_session_token(
                         .change_context(errors::ApiErrorResponse::InternalServerError)
                         .attach_printable("Apple pay merchant identifier decryption failed")?;
 
-                    #[cfg(not(feature = "kms"))]
-                    let decrypted_merchant_identifier = &state
-                        .conf
-                        .applepay_merchant_configs
-                        .common_merchant_identifier;
-
                     let apple_pay_session_request = get_session_request_for_simplified_apple_pay(
-                        decrypted_merchant_identifier.to_string(),
+                        decrypted_merchant_identifier,
                         session_token_data,
                     );
 
-                    #[cfg(not(feature = "kms"))]
-                    let decrypted_apple_pay_merchant_cert =
-                        &state.conf.applepay_decrypt_keys.apple_pay_merchant_cert;
-
-                    #[cfg(not(feature = "kms"))]
-                    let decrypted_apple_pay_merchant_cert_key =
-                        &state.conf.applepay_decrypt_keys.apple_pay_merchant_cert_key;
-
                     (
                         payment_request_data,
                         apple_pay_session_request,
-                        decrypted_apple_pay_merchant_cert.to_owned(),
-                        decrypted_apple_pay_merchant_cert_key.to_owned(),
+                        decrypted_apple_pay_merchant_cert,
+                        decrypted_apple_pay_merchant_cert_key,
                     )
                 }
                 payment_types::ApplePayCombinedMetadata::Manual {
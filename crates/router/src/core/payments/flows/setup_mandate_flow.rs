@@ -238,6 +238,10 @@ impl mandate::MandateBehaviour for types::SetupMandateRequestData {
         0
     }
 
+    fn get_currency(&self) -> diesel_models::enums::Currency {
+        self.currency
+    }
+
     fn get_setup_future_usage(&self) -> Option<diesel_models::enums::FutureUsage> {
         self.setup_future_usage
     }
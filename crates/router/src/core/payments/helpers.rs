@@ -12,8 +12,7 @@ use data_models::{
 use diesel_models::enums;
 // TODO : Evaluate all the helper functions ()
 use error_stack::{report, IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management;
 use josekit::jwe;
 use masking::{ExposeInterface, PeekInterface};
 use openssl::{
@@ -36,6 +35,7 @@ use crate::{
     consts::{self, BASE64_ENGINE},
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        mandate,
         payment_methods::{cards, vault, PaymentMethodRetrieve},
         payments,
     },
@@ -112,7 +112,7 @@ pub async fn create_or_update_address_for_payment_by_request(
     payment_id: &str,
     storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<Option<domain::Address>, errors::ApiErrorResponse> {
-    let key = merchant_key_store.key.get_inner().peek();
+    let key = &merchant_key_store.key_ring();
 
     Ok(match address_id {
         Some(id) => match req_address {
@@ -264,7 +264,7 @@ pub async fn create_or_find_address_for_payment_by_request(
     payment_id: &str,
     storage_scheme: storage_enums::MerchantStorageScheme,
 ) -> CustomResult<Option<domain::Address>, errors::ApiErrorResponse> {
-    let key = merchant_key_store.key.get_inner().peek();
+    let key = &merchant_key_store.key_ring();
 
     Ok(match address_id {
         Some(id) => Some(
@@ -319,7 +319,7 @@ pub async fn get_domain_address_for_payments(
     merchant_id: &str,
     customer_id: &str,
     payment_id: &str,
-    key: &[u8],
+    key: &types::KeyRing,
     storage_scheme: enums::MerchantStorageScheme,
 ) -> CustomResult<domain::Address, common_utils::errors::CryptoError> {
     async {
@@ -483,6 +483,11 @@ pub async fn get_token_for_recurring_mandate(
                 message: "mandate is not active".into()
             }))?
         };
+        if mandate::has_mandate_ended(mandate.end_date, common_utils::date_time::now()) {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "mandate has expired".into()
+            }))?
+        };
         mandate.payment_method_id.clone()
     };
     verify_mandate_details(
@@ -750,6 +755,17 @@ fn validate_new_mandate_request(
         Some(api_models::payments::MandateType::MultiUse(details)) => details,
         None => None,
     };
+    if let Some(md) = &mandate_details {
+        if md.frequency_limit.is_some() != md.frequency_interval.is_some()
+            || md.frequency_limit == Some(0)
+        {
+            Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+                message: "`mandate_data.mandate_type.multi_use.frequency_limit` should be greater than 0 \
+                          and be provided along with `mandate_data.mandate_type.multi_use.frequency_interval`"
+                    .into()
+            }))?
+        }
+    }
     mandate_details.and_then(|md| md.start_date.zip(md.end_date)).map(|(start_date, end_date)|
         utils::when (start_date >= end_date, || {
         Err(report!(errors::ApiErrorResponse::PreconditionFailed {
@@ -1211,7 +1227,7 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R, Ctx>(
                         | request_customer_details.phone.is_some()
                         | request_customer_details.phone_country_code.is_some()
                     {
-                        let key = key_store.key.get_inner().peek();
+                        let key = &key_store.key_ring();
                         let customer_update = async {
                             Ok(Update {
                                 name: request_customer_details
@@ -1259,7 +1275,7 @@ pub async fn create_customer_if_not_exist<'a, F: Clone, R, Ctx>(
                 }
                 None => {
                     let new_customer = async {
-                        let key = key_store.key.get_inner().peek();
+                        let key = &key_store.key_ring();
                         Ok(domain::Customer {
                             customer_id: customer_id.to_string(),
                             merchant_id: merchant_id.to_string(),
@@ -1848,6 +1864,7 @@ pub(super) async fn filter_by_constraints(
             merchant_id,
             &constraints.clone().into(),
             storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await?;
     Ok(result)
@@ -1873,7 +1890,7 @@ pub(super) fn validate_payment_list_request(
     Ok(())
 }
 #[cfg(feature = "olap")]
-pub(super) fn validate_payment_list_request_for_joins(
+pub(crate) fn validate_payment_list_request_for_joins(
     limit: u32,
 ) -> CustomResult<(), errors::ApiErrorResponse> {
     use common_utils::consts::PAYMENTS_LIST_MAX_LIMIT_V2;
@@ -2162,7 +2179,9 @@ pub fn generate_mandate(
                             .set_mandate_amount(Some(data.amount))
                             .set_mandate_currency(Some(data.currency))
                             .set_start_date(data.start_date)
-                            .set_end_date(data.end_date),
+                            .set_end_date(data.end_date)
+                            .set_frequency_limit(data.frequency_limit.map(i32::from))
+                            .set_frequency_interval(data.frequency_interval),
                         // .set_metadata(data.metadata),
                         // we are storing PaymentMethodData in metadata of mandate
                         None => &mut new_mandate,
@@ -2633,17 +2652,13 @@ pub async fn get_merchant_connector_account(
                     },
                 )?;
 
-            #[cfg(feature = "kms")]
             let private_key = state
-                .kms_secrets
+                .active_secrets
                 .jwekey
                 .peek()
                 .tunnel_private_key
                 .as_bytes();
 
-            #[cfg(not(feature = "kms"))]
-            let private_key = state.conf.jwekey.tunnel_private_key.as_bytes();
-
             let decrypted_mca = services::decrypt_jwe(mca_config.config.as_str(), services::KeyIdCheck::SkipKeyIdCheck, private_key, jwe::RSA_OAEP_256)
                                      .await
                                      .change_context(errors::ApiErrorResponse::UnprocessableEntity{
@@ -3313,16 +3328,12 @@ impl ApplePayData {
         &self,
         state: &AppState,
     ) -> CustomResult<String, errors::ApplePayDecryptionError> {
-        #[cfg(feature = "kms")]
-        let cert_data = kms::get_kms_client(&state.conf.kms)
+        let cert_data = secrets_management::get_secrets_manager(&state.conf.secrets_management)
             .await
             .decrypt(&state.conf.applepay_decrypt_keys.apple_pay_ppc)
             .await
             .change_context(errors::ApplePayDecryptionError::DecryptionFailed)?;
 
-        #[cfg(not(feature = "kms"))]
-        let cert_data = &state.conf.applepay_decrypt_keys.apple_pay_ppc;
-
         let base64_decode_cert_data = BASE64_ENGINE
             .decode(cert_data)
             .into_report()
@@ -3373,15 +3384,12 @@ impl ApplePayData {
             .change_context(errors::ApplePayDecryptionError::KeyDeserializationFailed)
             .attach_printable("Failed to deserialize the public key")?;
 
-        #[cfg(feature = "kms")]
-        let decrypted_apple_pay_ppc_key = kms::get_kms_client(&state.conf.kms)
-            .await
-            .decrypt(&state.conf.applepay_decrypt_keys.apple_pay_ppc_key)
-            .await
-            .change_context(errors::ApplePayDecryptionError::DecryptionFailed)?;
-
-        #[cfg(not(feature = "kms"))]
-        let decrypted_apple_pay_ppc_key = &state.conf.applepay_decrypt_keys.apple_pay_ppc_key;
+        let decrypted_apple_pay_ppc_key =
+            secrets_management::get_secrets_manager(&state.conf.secrets_management)
+                .await
+                .decrypt(&state.conf.applepay_decrypt_keys.apple_pay_ppc_key)
+                .await
+                .change_context(errors::ApplePayDecryptionError::DecryptionFailed)?;
         // Create PKey objects from EcKey
         let private_key = PKey::private_key_from_pem(decrypted_apple_pay_ppc_key.as_bytes())
             .into_report()
@@ -3460,8 +3468,11 @@ pub fn validate_payment_link_request(
 ) -> Result<(), errors::ApiErrorResponse> {
     if let Some(cnf) = confirm {
         if !cnf {
-            let current_time = Some(common_utils::date_time::now());
-            if current_time > payment_link_object.link_expiry {
+            let current_time = common_utils::date_time::now();
+            if payment_link_object
+                .link_expiry
+                .map_or(false, |link_expiry| current_time > link_expiry)
+            {
                 return Err(errors::ApiErrorResponse::InvalidRequestData {
                     message: "link_expiry time cannot be less than current time".to_string(),
                 });
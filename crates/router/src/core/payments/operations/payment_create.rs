@@ -10,10 +10,13 @@ use router_derive::PaymentOperation;
 use router_env::{instrument, tracing};
 
 use super::{BoxedOperation, Domain, GetTracker, Operation, UpdateTracker, ValidateRequest};
+#[cfg(feature = "email")]
+use crate::core::transactional_emails;
 use crate::{
     consts,
     core::{
         errors::{self, CustomResult, RouterResult, StorageErrorExt},
+        payment_link,
         payment_methods::PaymentMethodRetrieve,
         payments::{self, helpers, operations, CustomerDetails, PaymentAddress, PaymentData},
         utils::{self as core_utils},
@@ -74,7 +77,7 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
             create_payment_link(
                 request,
                 payment_link_object.clone(),
-                merchant_id.clone(),
+                merchant_account,
                 payment_id.clone(),
                 db,
                 state,
@@ -195,6 +198,25 @@ impl<F: Send + Clone, Ctx: PaymentMethodRetrieve>
                 payment_id: payment_id.clone(),
             })?;
 
+        #[cfg(feature = "email")]
+        if let Some(payment_link_data) = payment_link_data.as_ref().filter(|_| {
+            // Invoices are emailed to the customer instead of their payment link
+            request
+                .payment_link_object
+                .as_ref()
+                .map_or(true, |payment_link_object| {
+                    payment_link_object.invoice_id.is_none()
+                })
+        }) {
+            transactional_emails::trigger_payment_link_created_email(
+                state,
+                merchant_account,
+                &payment_id,
+                &payment_link_data.payment_link_id,
+            )
+            .await;
+        }
+
         connector_response = db
             .insert_connector_response(
                 Self::make_connector_response(&payment_attempt),
@@ -783,13 +805,34 @@ pub fn payments_create_request_validation(
 async fn create_payment_link(
     request: &api::PaymentsRequest,
     payment_link_object: api_models::payments::PaymentLinkObject,
-    merchant_id: String,
+    merchant_account: &domain::MerchantAccount,
     payment_id: String,
     db: &dyn StorageInterface,
     state: &AppState,
     amount: api::Amount,
 ) -> RouterResult<Option<api_models::payments::PaymentLinkResponse>> {
-    let created_at @ last_modified_at = Some(common_utils::date_time::now());
+    let merchant_id = merchant_account.merchant_id.clone();
+    let current_time = common_utils::date_time::now();
+    let created_at @ last_modified_at = Some(current_time);
+
+    let payment_link_config = payment_link_object
+        .payment_link_config
+        .as_ref()
+        .map(|pl_config| {
+            payment_link::validate_payment_link_config(pl_config)?;
+            Encode::<api_models::admin::PaymentLinkConfig>::encode_to_value(pl_config)
+                .change_context(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: "payment_link_config",
+                })
+        })
+        .transpose()?;
+    let fulfilment_time = payment_link::get_payment_link_expiry(
+        &state.conf.payment_link,
+        merchant_account,
+        &payment_link_object,
+        current_time,
+    )?;
+
     let domain = if let Some(domain_name) = payment_link_object.merchant_custom_domain_name {
         format!("https://{domain_name}")
     } else {
@@ -812,7 +855,10 @@ async fn create_payment_link(
         currency: request.currency,
         created_at,
         last_modified_at,
-        fulfilment_time: payment_link_object.link_expiry,
+        fulfilment_time: Some(fulfilment_time),
+        payment_link_config,
+        status: enums::PaymentLinkStatus::Active,
+        reusable_payment_link_id: payment_link_object.reusable_payment_link_id,
     };
     let payment_link_db = db
         .insert_payment_link(payment_link_req)
@@ -821,6 +867,8 @@ async fn create_payment_link(
             message: "payment link already exists!".to_string(),
         })?;
 
+    payment_link::add_payment_link_expiry_task(db, &payment_link_db).await?;
+
     Ok(Some(api_models::payments::PaymentLinkResponse {
         link: payment_link_db.link_to_pay,
         payment_link_id: payment_link_db.payment_link_id,
@@ -612,6 +612,8 @@ where
                                             start_date: i.start_date,
                                             end_date: i.end_date,
                                             metadata: i.metadata,
+                                            frequency_limit: i.frequency_limit,
+                                            frequency_interval: i.frequency_interval,
                                         }))
                                     }
                                     data_models::mandates::MandateDataType::SingleUse(i) => {
@@ -622,6 +624,8 @@ where
                                                 start_date: i.start_date,
                                                 end_date: i.end_date,
                                                 metadata: i.metadata,
+                                                frequency_limit: i.frequency_limit,
+                                                frequency_interval: i.frequency_interval,
                                             },
                                         )
                                     }
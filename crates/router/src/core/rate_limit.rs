@@ -0,0 +1,288 @@
+use std::str::FromStr;
+
+use api_models::admin::{MerchantRateLimits, MerchantRateLimitsResponse, RateLimit};
+use common_utils::{
+    date_time,
+    ext_traits::{Encode, StringExt},
+};
+use error_stack::{report, ResultExt};
+use redis_interface::{errors::RedisError, RedisConnectionPool};
+use router_env::{instrument, tracing, types::FlowMetric, Flow};
+
+use super::errors::{self, CustomResult, RouterResponse, RouterResult, StorageErrorExt};
+use crate::{
+    db::StorageInterface,
+    logger,
+    routes::{app::AppStateInfo, lock_utils::ApiIdentifier, metrics, AppState},
+    services::{authentication::AuthInfo, ApplicationResponse},
+    types::storage,
+};
+
+pub const RATE_LIMIT_PREFIX: &str = "RATE_LIMIT";
+
+fn get_rate_limits_config_key(merchant_id: &str) -> String {
+    format!("rate_limits_{merchant_id}")
+}
+
+/// Counts the request against the rate limits of the merchant it was authenticated for: the
+/// limit on all requests of the merchant, the limit on requests to the API of the flow, and the
+/// limit on requests made with the API key used.
+#[instrument(skip_all)]
+pub async fn check_rate_limits<A>(
+    state: &A,
+    flow: &impl FlowMetric,
+    auth: &impl AuthInfo,
+) -> RouterResult<()>
+where
+    A: AppStateInfo,
+{
+    let config = state.conf().rate_limit;
+    if !config.enabled {
+        return Ok(());
+    }
+    let Some(merchant_id) = auth.get_merchant_id() else {
+        return Ok(());
+    };
+
+    let db = state.store();
+    let rate_limits = find_merchant_rate_limits(db.as_ref(), merchant_id, true).await?;
+
+    let mut limits = Vec::new();
+    if let Some(limit) = rate_limits.merchant.or(config.default_merchant_limit) {
+        limits.push(("merchant".to_string(), limit));
+    }
+    if let Some((api, limit)) = Flow::from_str(&flow.to_string())
+        .ok()
+        .map(|flow| ApiIdentifier::from(flow).to_string())
+        .and_then(|api| Some((api.clone(), *rate_limits.apis.get(&api)?)))
+    {
+        limits.push((format!("api_{api}"), limit));
+    }
+    if let Some((key_id, limit)) = auth
+        .get_api_key()
+        .and_then(|api_key| Some((&api_key.key_id, *rate_limits.api_keys.get(&api_key.key_id)?)))
+    {
+        limits.push((format!("api_key_{key_id}"), limit));
+    }
+
+    if limits.is_empty() {
+        return Ok(());
+    }
+
+    let redis_conn = db
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    let now = date_time::now_unix_timestamp();
+
+    let mut retry_after_seconds = None;
+    for (scope, limit) in limits {
+        let redis_key = format!("{RATE_LIMIT_PREFIX}_{merchant_id}_{scope}");
+        match count_request(&redis_conn, &redis_key, limit, now).await {
+            Ok(Some(seconds)) => {
+                retry_after_seconds = retry_after_seconds.max(Some(seconds));
+            }
+            Ok(None) => {}
+            // Requests are not rejected when their rate cannot be determined
+            Err(error) => logger::error!(?error, "Failed to count request against rate limit"),
+        }
+    }
+
+    match retry_after_seconds {
+        Some(retry_after_seconds) => {
+            metrics::REQUESTS_THROTTLED.add(
+                &metrics::CONTEXT,
+                1,
+                &[
+                    metrics::request::add_attributes("merchant_id", merchant_id.to_owned()),
+                    metrics::request::add_attributes("flow", flow.to_string()),
+                ],
+            );
+            Err(report!(errors::ApiErrorResponse::RateLimitExceeded {
+                retry_after_seconds
+            }))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Counts a request in the sliding window of the limit, which is approximated from the counts of
+/// the current and the previous fixed windows. Returns the number of seconds after which the
+/// request may be retried if the limit is exceeded.
+async fn count_request(
+    redis_conn: &RedisConnectionPool,
+    redis_key: &str,
+    limit: RateLimit,
+    now: i64,
+) -> CustomResult<Option<u64>, RedisError> {
+    let window = i64::from(limit.window_in_seconds.max(1));
+    let current_window = now / window;
+    let elapsed = now % window;
+
+    let current_key = format!("{redis_key}_{window}_{current_window}");
+    // The count is created along with its expiry, so that it can't be left without one. It is
+    // needed until the end of the next window, and is only incremented during its own window.
+    redis_conn
+        .set_key_if_not_exists_with_expiry(&current_key, 0, Some(window * 2))
+        .await?;
+    let current_count = redis_conn.increment_key(&current_key).await?;
+    let previous_count = redis_conn
+        .get_key::<Option<i64>>(&format!("{redis_key}_{window}_{}", current_window - 1))
+        .await?
+        .unwrap_or_default();
+
+    // The previous window is weighed by the portion of it overlapping the sliding window
+    let estimated_count = previous_count * (window - elapsed) / window + current_count;
+
+    Ok((estimated_count > i64::from(limit.max_requests))
+        .then(|| u64::try_from(window - elapsed).unwrap_or(1)))
+}
+
+async fn find_merchant_rate_limits(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    create_if_missing: bool,
+) -> RouterResult<MerchantRateLimits> {
+    let key = get_rate_limits_config_key(merchant_id);
+    let config = if create_if_missing {
+        // An empty configuration is stored for merchants without rate limits, so that it is
+        // cached instead of being looked up in the database on every request
+        db.find_config_by_key_unwrap_or(&key, Some("{}".to_string()))
+            .await
+    } else {
+        db.find_config_by_key(&key).await
+    };
+
+    match config {
+        Ok(config) => config
+            .config
+            .parse_struct("MerchantRateLimits")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to parse the rate limits of the merchant"),
+        Err(error) if error.current_context().is_db_not_found() => Ok(Default::default()),
+        Err(error) => Err(error)
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the rate limits of the merchant"),
+    }
+}
+
+fn validate_rate_limits(rate_limits: &MerchantRateLimits) -> RouterResult<()> {
+    let limits = rate_limits
+        .merchant
+        .iter()
+        .chain(rate_limits.apis.values())
+        .chain(rate_limits.api_keys.values());
+    for limit in limits {
+        if limit.window_in_seconds == 0 {
+            return Err(report!(errors::ApiErrorResponse::InvalidDataValue {
+                field_name: "window_in_seconds",
+            }));
+        }
+    }
+
+    if let Some(api) = rate_limits
+        .apis
+        .keys()
+        .find(|api| ApiIdentifier::from_str(api).is_err())
+    {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!("Rate limits cannot be set for unknown API `{api}`"),
+        }));
+    }
+
+    Ok(())
+}
+
+async fn check_merchant_exists(db: &dyn StorageInterface, merchant_id: &str) -> RouterResult<()> {
+    db.get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)
+        .map(|_| ())
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_merchant_rate_limits(
+    state: AppState,
+    merchant_id: String,
+) -> RouterResponse<MerchantRateLimitsResponse> {
+    let db = state.store.as_ref();
+    check_merchant_exists(db, &merchant_id).await?;
+
+    let rate_limits = find_merchant_rate_limits(db, &merchant_id, false).await?;
+
+    Ok(ApplicationResponse::Json(MerchantRateLimitsResponse {
+        merchant_id,
+        rate_limits,
+    }))
+}
+
+#[instrument(skip(state))]
+pub async fn update_merchant_rate_limits(
+    state: AppState,
+    merchant_id: String,
+    rate_limits: MerchantRateLimits,
+) -> RouterResponse<MerchantRateLimitsResponse> {
+    let db = state.store.as_ref();
+    check_merchant_exists(db, &merchant_id).await?;
+    validate_rate_limits(&rate_limits)?;
+
+    let key = get_rate_limits_config_key(&merchant_id);
+    let config = Encode::<MerchantRateLimits>::encode_to_string_of_json(&rate_limits)
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to encode the rate limits of the merchant")?;
+
+    let updated = db
+        .update_config_by_key(
+            &key,
+            storage::ConfigUpdate::Update {
+                config: Some(config.clone()),
+            },
+        )
+        .await;
+    match updated {
+        Ok(_) => Ok(()),
+        Err(error) if error.current_context().is_db_not_found() => db
+            .insert_config(storage::ConfigNew { key, config })
+            .await
+            .map(|_| ()),
+        Err(error) => Err(error),
+    }
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to store the rate limits of the merchant")?;
+
+    Ok(ApplicationResponse::Json(MerchantRateLimitsResponse {
+        merchant_id,
+        rate_limits,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_validate_rate_limits() {
+        let limit = RateLimit {
+            max_requests: 10,
+            window_in_seconds: 60,
+        };
+        let mut rate_limits = MerchantRateLimits {
+            merchant: Some(limit),
+            apis: [("refunds".to_string(), limit)].into_iter().collect(),
+            api_keys: [("dev_abc".to_string(), limit)].into_iter().collect(),
+        };
+        assert!(validate_rate_limits(&rate_limits).is_ok());
+
+        rate_limits.apis.insert("unknown".to_string(), limit);
+        assert!(validate_rate_limits(&rate_limits).is_err());
+
+        rate_limits.apis.remove("unknown");
+        rate_limits.merchant = Some(RateLimit {
+            max_requests: 10,
+            window_in_seconds: 0,
+        });
+        assert!(validate_rate_limits(&rate_limits).is_err());
+    }
+}
@@ -5,6 +5,8 @@ use error_stack::{report, IntoReport, ResultExt};
 use router_env::{instrument, tracing};
 use scheduler::{consumer::types::process_data, utils as process_tracker_utils};
 
+#[cfg(feature = "email")]
+use crate::core::transactional_emails;
 use crate::{
     consts,
     core::{
@@ -247,6 +249,16 @@ pub async fn trigger_refund_to_gateway(
                 refund.refund_id
             )
         })?;
+
+    #[cfg(feature = "email")]
+    transactional_emails::trigger_refund_processed_email(
+        state,
+        merchant_account,
+        refund.refund_status,
+        &response,
+    )
+    .await;
+
     Ok(response)
 }
 
@@ -468,6 +480,16 @@ pub async fn sync_refund_with_gateway(
                 refund.refund_id
             )
         })?;
+
+    #[cfg(feature = "email")]
+    transactional_emails::trigger_refund_processed_email(
+        state,
+        merchant_account,
+        refund.refund_status,
+        &response,
+    )
+    .await;
+
     Ok(response)
 }
 
@@ -658,14 +680,16 @@ pub async fn refund_list(
     let db = state.store;
     let limit = validator::validate_refund_list(req.limit)?;
     let offset = req.offset.unwrap_or_default();
+    let merchant_ids = [merchant_account.merchant_id.clone()];
 
     let refund_list = db
         .filter_refund_by_constraints(
-            &merchant_account.merchant_id,
+            &merchant_ids,
             &req,
             merchant_account.storage_scheme,
             limit,
             offset,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
@@ -677,9 +701,10 @@ pub async fn refund_list(
 
     let total_count = db
         .get_total_count_of_refunds(
-            &merchant_account.merchant_id,
+            &merchant_ids,
             &req,
             merchant_account.storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
@@ -706,6 +731,7 @@ pub async fn refund_filter_list(
             &merchant_account.merchant_id,
             &req,
             merchant_account.storage_scheme,
+            storage_impl::ReadConsistency::Eventual,
         )
         .await
         .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
@@ -834,7 +860,7 @@ pub async fn sync_refund_with_gateway_workflow(
         .store
         .get_merchant_key_store_by_merchant_id(
             &refund_core.merchant_id,
-            &state.store.get_master_key().to_vec().into(),
+            &state.store.get_master_key_ring(),
         )
         .await?;
 
@@ -914,7 +940,7 @@ pub async fn trigger_refund_execute_workflow(
         .store
         .get_merchant_key_store_by_merchant_id(
             &refund_core.merchant_id,
-            &state.store.get_master_key().to_vec().into(),
+            &state.store.get_master_key_ring(),
         )
         .await?;
 
@@ -0,0 +1,1266 @@
+use api_models::{
+    payments::HeaderPayload,
+    subscriptions::{
+        SubscriptionCreateRequest, SubscriptionPlanCreateRequest, SubscriptionPlanResponse,
+        SubscriptionPlanUpdateRequest, SubscriptionResponse, SubscriptionUpdateRequest,
+    },
+    webhooks::OutgoingWebhookContent,
+};
+use common_utils::{date_time, pii};
+use error_stack::{IntoReport, ResultExt};
+use masking::ExposeInterface;
+use router_env::{instrument, logger, tracing};
+use time::PrimitiveDateTime;
+
+use super::{
+    errors::{self, RouterResult, StorageErrorExt},
+    payment_methods::PaymentMethodRetrieve,
+    payments, webhooks,
+};
+use crate::{
+    consts,
+    db::StorageInterface,
+    errors::RouterResponse,
+    routes::AppState,
+    services,
+    types::{
+        api, domain,
+        storage::{self, enums as storage_enums},
+    },
+    utils::{self, OptionExt},
+};
+
+const SUBSCRIPTION_BILLING_TAG: &str = "SUBSCRIPTION";
+const SUBSCRIPTION_BILLING_NAME: &str = "SUBSCRIPTION_BILLING";
+const SUBSCRIPTION_BILLING_RUNNER: &str = "SUBSCRIPTION_BILLING_WORKFLOW";
+
+const MAX_NAME_LENGTH: usize = 255;
+
+/// The statuses of payments for which the subscription is considered paid.
+const PAID_STATUSES: [storage_enums::IntentStatus; 3] = [
+    storage_enums::IntentStatus::Succeeded,
+    storage_enums::IntentStatus::RequiresCapture,
+    storage_enums::IntentStatus::PartiallyCaptured,
+];
+
+/// The statuses of payments that have not reached their final status yet. They are not retried,
+/// as that could charge the customer twice, and the subscription is past due until they do.
+const PENDING_STATUSES: [storage_enums::IntentStatus; 2] = [
+    storage_enums::IntentStatus::Processing,
+    storage_enums::IntentStatus::RequiresMerchantAction,
+];
+
+/// The statuses of payments that were created but not confirmed with the connector, such as when
+/// charging the subscription failed midway. They are confirmed when the charge is retried.
+const UNCONFIRMED_STATUSES: [storage_enums::IntentStatus; 2] = [
+    storage_enums::IntentStatus::RequiresConfirmation,
+    storage_enums::IntentStatus::RequiresPaymentMethod,
+];
+
+/// The number of times an update of a subscription that is being charged is applied again after
+/// the subscription was modified concurrently.
+const MAX_CHARGED_SUBSCRIPTION_UPDATE_ATTEMPTS: usize = 3;
+
+#[instrument(skip(state))]
+pub async fn create_subscription_plan(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    request: SubscriptionPlanCreateRequest,
+) -> RouterResponse<SubscriptionPlanResponse> {
+    let invalid_request = |message: &str| {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: message.to_string(),
+        })
+        .into_report()
+    };
+    if !is_valid_name(&request.name) || !is_valid_description(request.description.as_deref()) {
+        return invalid_request(
+            "name and description must be at most 255 characters long, and name must not be empty",
+        );
+    }
+    if request.amount < 1 {
+        return invalid_request("amount must be greater than 0");
+    }
+    if request.interval_count == Some(0) {
+        return invalid_request("interval_count must be greater than 0");
+    }
+
+    let current_time = date_time::now();
+    let subscription_plan_new = storage::SubscriptionPlanNew {
+        plan_id: utils::generate_id(consts::ID_LENGTH, "plan"),
+        merchant_id: merchant_account.merchant_id,
+        name: request.name,
+        description: request.description,
+        amount: request.amount,
+        currency: request.currency,
+        interval: request.interval,
+        interval_count: request.interval_count.map_or(1, i32::from),
+        trial_period_days: request.trial_period_days.map(i32::from),
+        active: true,
+        metadata: request.metadata.map(ExposeInterface::expose),
+        created_at: Some(current_time),
+        last_modified_at: Some(current_time),
+    };
+
+    let subscription_plan = state
+        .store
+        .insert_subscription_plan(subscription_plan_new)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::GenericDuplicateError {
+            message: "subscription plan already exists!".to_string(),
+        })?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_plan_response(subscription_plan),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_subscription_plan(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    plan_id: String,
+) -> RouterResponse<SubscriptionPlanResponse> {
+    let subscription_plan = state
+        .store
+        .find_subscription_plan_by_merchant_id_plan_id(&merchant_account.merchant_id, &plan_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_plan_response(subscription_plan),
+    ))
+}
+
+/// Updates the details of a plan. The price and billing period of a plan cannot be changed, as
+/// subscribers have agreed to them; subscriptions are moved to another plan instead.
+#[instrument(skip(state))]
+pub async fn update_subscription_plan(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    plan_id: String,
+    request: SubscriptionPlanUpdateRequest,
+) -> RouterResponse<SubscriptionPlanResponse> {
+    if !request.name.as_deref().map_or(true, is_valid_name)
+        || !is_valid_description(request.description.as_deref())
+    {
+        return Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: "name and description must be at most 255 characters long, and name must not be empty".to_string(),
+        })
+        .into_report();
+    }
+
+    let db = &*state.store;
+    let subscription_plan = db
+        .find_subscription_plan_by_merchant_id_plan_id(&merchant_account.merchant_id, &plan_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+
+    let subscription_plan = db
+        .update_subscription_plan(
+            subscription_plan,
+            storage::SubscriptionPlanUpdate::Update {
+                name: request.name,
+                description: request.description,
+                active: request.active,
+                metadata: request.metadata.map(ExposeInterface::expose),
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_plan_response(subscription_plan),
+    ))
+}
+
+/// Subscribes a customer to a plan. The customer is charged through the mandate right away,
+/// unless the plan has a trial period, in which case the first charge happens once it ends.
+#[instrument(skip(state, merchant_account, key_store))]
+pub async fn create_subscription<Ctx: PaymentMethodRetrieve>(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    key_store: domain::MerchantKeyStore,
+    request: SubscriptionCreateRequest,
+) -> RouterResponse<SubscriptionResponse> {
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let subscription_plan = db
+        .find_subscription_plan_by_merchant_id_plan_id(merchant_id, &request.plan_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+    if !subscription_plan.active {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "subscription plan is not active".to_string(),
+        })
+        .into_report();
+    }
+
+    db.find_customer_by_customer_id_merchant_id(&request.customer_id, merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::CustomerNotFound)?;
+    validate_mandate(
+        db,
+        merchant_id,
+        &request.mandate_id,
+        &request.customer_id,
+        subscription_plan.currency,
+    )
+    .await?;
+
+    // The current period ends when the subscription is next charged, right away when there is
+    // no trial period
+    let current_time = date_time::now();
+    let trial_end = subscription_plan
+        .trial_period_days
+        .filter(|trial_period_days| *trial_period_days > 0)
+        .map(|trial_period_days| {
+            current_time.saturating_add(time::Duration::days(i64::from(trial_period_days)))
+        });
+    let subscription_new = storage::SubscriptionNew {
+        subscription_id: utils::generate_id(consts::ID_LENGTH, "sub"),
+        merchant_id: merchant_id.clone(),
+        customer_id: request.customer_id,
+        plan_id: subscription_plan.plan_id.clone(),
+        mandate_id: request.mandate_id,
+        status: if trial_end.is_some() {
+            storage_enums::SubscriptionStatus::Trialing
+        } else {
+            storage_enums::SubscriptionStatus::Active
+        },
+        current_period_start: current_time,
+        current_period_end: trial_end.unwrap_or(current_time),
+        billing_anchor: trial_end.unwrap_or(current_time),
+        trial_end,
+        metadata: request.metadata.map(ExposeInterface::expose),
+        created_at: Some(current_time),
+        last_modified_at: Some(current_time),
+    };
+
+    let subscription = db
+        .insert_subscription(subscription_new)
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::GenericDuplicateError {
+            message: "subscription already exists!".to_string(),
+        })?;
+    trigger_subscription_webhook(
+        &state,
+        &merchant_account,
+        storage_enums::EventType::SubscriptionCreated,
+        &subscription,
+    )
+    .await;
+
+    let subscription = if trial_end.is_some() {
+        subscription
+    } else {
+        charge_subscription::<Ctx>(
+            &state,
+            &merchant_account,
+            &key_store,
+            subscription,
+            &subscription_plan,
+        )
+        .await?
+    };
+    if let Some(schedule_time) = get_next_billing_time(&state, &subscription) {
+        add_subscription_billing_task(db, &subscription, schedule_time).await?;
+    }
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_response(subscription),
+    ))
+}
+
+#[instrument(skip(state))]
+pub async fn retrieve_subscription(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+) -> RouterResponse<SubscriptionResponse> {
+    let subscription = state
+        .store
+        .find_subscription_by_merchant_id_subscription_id(
+            &merchant_account.merchant_id,
+            &subscription_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_response(subscription),
+    ))
+}
+
+/// Updates a subscription that has not been cancelled. Moving an active subscription to another
+/// plan prorates the difference between the prices of the plans over the rest of the current
+/// billing period, which is added to the next payment. The new plan is billed from the next
+/// billing period on.
+#[instrument(skip(state, merchant_account))]
+pub async fn update_subscription(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+    request: SubscriptionUpdateRequest,
+) -> RouterResponse<SubscriptionResponse> {
+    let db = &*state.store;
+    let merchant_id = &merchant_account.merchant_id;
+    let mut subscription = db
+        .find_subscription_by_merchant_id_subscription_id(merchant_id, &subscription_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound)?;
+    if subscription.status == storage_enums::SubscriptionStatus::Cancelled {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "subscription has been cancelled".to_string(),
+        })
+        .into_report();
+    }
+
+    let current_plan = db
+        .find_subscription_plan_by_merchant_id_plan_id(merchant_id, &subscription.plan_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+
+    if let Some(mandate_id) = &request.mandate_id {
+        validate_mandate(
+            db,
+            merchant_id,
+            mandate_id,
+            &subscription.customer_id,
+            current_plan.currency,
+        )
+        .await?;
+    }
+
+    if let Some(plan_id) = request
+        .plan_id
+        .filter(|plan_id| *plan_id != subscription.plan_id)
+    {
+        let new_plan = db
+            .find_subscription_plan_by_merchant_id_plan_id(merchant_id, &plan_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+        if !new_plan.active {
+            return Err(errors::ApiErrorResponse::PreconditionFailed {
+                message: "subscription plan is not active".to_string(),
+            })
+            .into_report();
+        }
+        if new_plan.currency != current_plan.currency {
+            return Err(errors::ApiErrorResponse::InvalidRequestData {
+                message: "subscription can only be moved to a plan of the same currency"
+                    .to_string(),
+            })
+            .into_report();
+        }
+
+        let proration_amount = match subscription.status {
+            storage_enums::SubscriptionStatus::Active => {
+                let current_time = date_time::now();
+                compute_proration_amount(
+                    current_plan.amount,
+                    new_plan.amount,
+                    (subscription.current_period_end - current_time).whole_seconds(),
+                    (subscription.current_period_end - subscription.current_period_start)
+                        .whole_seconds(),
+                )
+                .and_then(|proration_amount| {
+                    proration_amount.checked_add(subscription.proration_amount)
+                })
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Failed to compute the proration amount of the subscription")?
+            }
+            // Nothing has been paid for the current period, the new plan is charged in full
+            storage_enums::SubscriptionStatus::Trialing
+            | storage_enums::SubscriptionStatus::PastDue
+            | storage_enums::SubscriptionStatus::Cancelled => subscription.proration_amount,
+        };
+
+        subscription = db
+            .update_subscription(
+                subscription,
+                storage::SubscriptionUpdate::PlanUpdate {
+                    plan_id: new_plan.plan_id,
+                    proration_amount,
+                },
+            )
+            .await
+            .to_not_found_response(concurrent_update_error())?;
+    }
+
+    let subscription = db
+        .update_subscription(
+            subscription,
+            storage::SubscriptionUpdate::Update {
+                mandate_id: request.mandate_id,
+                cancel_at_period_end: request.cancel_at_period_end,
+                metadata: request.metadata.map(ExposeInterface::expose),
+            },
+        )
+        .await
+        .to_not_found_response(concurrent_update_error())?;
+    trigger_subscription_webhook(
+        &state,
+        &merchant_account,
+        storage_enums::EventType::SubscriptionUpdated,
+        &subscription,
+    )
+    .await;
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_response(subscription),
+    ))
+}
+
+/// Cancels a subscription right away, the customer is no longer charged. Subscriptions are
+/// cancelled at the end of their billing period by updating them instead.
+#[instrument(skip(state, merchant_account))]
+pub async fn cancel_subscription(
+    state: AppState,
+    merchant_account: domain::MerchantAccount,
+    subscription_id: String,
+) -> RouterResponse<SubscriptionResponse> {
+    let db = &*state.store;
+    let subscription = db
+        .find_subscription_by_merchant_id_subscription_id(
+            &merchant_account.merchant_id,
+            &subscription_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound)?;
+    if subscription.status == storage_enums::SubscriptionStatus::Cancelled {
+        return Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "subscription has already been cancelled".to_string(),
+        })
+        .into_report();
+    }
+
+    let subscription = cancel(&state, &merchant_account, subscription).await?;
+
+    Ok(services::ApplicationResponse::Json(
+        get_subscription_response(subscription),
+    ))
+}
+
+/// Charges a subscription whose billing period has ended, or retries charging a subscription
+/// that is past due. Returns the time at which the subscription is next charged, if it has not
+/// been cancelled.
+#[instrument(skip_all, fields(subscription_id = %tracking_data.subscription_id))]
+pub async fn process_subscription_billing<Ctx: PaymentMethodRetrieve>(
+    state: &AppState,
+    tracking_data: &storage::SubscriptionBillingWorkflow,
+) -> RouterResult<Option<PrimitiveDateTime>> {
+    let db = &*state.store;
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(
+            &tracking_data.merchant_id,
+            &db.get_master_key_ring(),
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(&tracking_data.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let subscription = db
+        .find_subscription_by_merchant_id_subscription_id(
+            &tracking_data.merchant_id,
+            &tracking_data.subscription_id,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound)?;
+
+    let subscription = match subscription.status {
+        storage_enums::SubscriptionStatus::Cancelled => return Ok(None),
+        // The billing period was extended, the subscription is not due yet
+        storage_enums::SubscriptionStatus::Trialing | storage_enums::SubscriptionStatus::Active
+            if subscription.current_period_end > date_time::now() =>
+        {
+            return Ok(Some(subscription.current_period_end));
+        }
+        _ if subscription.cancel_at_period_end => {
+            cancel(state, &merchant_account, subscription).await?
+        }
+        _ => {
+            let subscription_plan = db
+                .find_subscription_plan_by_merchant_id_plan_id(
+                    &subscription.merchant_id,
+                    &subscription.plan_id,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::SubscriptionPlanNotFound)?;
+            charge_subscription::<Ctx>(
+                state,
+                &merchant_account,
+                &key_store,
+                subscription,
+                &subscription_plan,
+            )
+            .await?
+        }
+    };
+
+    Ok(get_next_billing_time(state, &subscription))
+}
+
+/// Charges the subscription for the billing period following the current one, along with the
+/// proration of plan changes. A declined payment is retried until the maximum number of dunning
+/// attempts is reached, at which point the subscription is cancelled. Other failures are returned,
+/// so that the billing task retries the charge.
+async fn charge_subscription<Ctx: PaymentMethodRetrieve>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    subscription: storage::Subscription,
+    subscription_plan: &storage::SubscriptionPlan,
+) -> RouterResult<storage::Subscription> {
+    let db = &*state.store;
+    let amount = subscription_plan
+        .amount
+        .checked_add(subscription.proration_amount)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Failed to compute the amount to charge for the subscription")?;
+    let current_period_start = subscription.current_period_end;
+    let current_period_end = get_billing_period_end(
+        subscription.billing_anchor,
+        current_period_start,
+        subscription_plan.interval,
+        subscription_plan.interval_count,
+    )
+    .ok_or(errors::ApiErrorResponse::InternalServerError)
+    .into_report()
+    .attach_printable("Failed to compute the billing period of the subscription")?;
+
+    // The customer has been credited more than the price of the plan, nothing is charged and the
+    // rest of the credit is kept for the next period
+    if amount <= 0 {
+        return renew_subscription(
+            state,
+            merchant_account,
+            subscription,
+            None,
+            (current_period_start, current_period_end),
+            subscription_plan.amount.saturating_neg(),
+        )
+        .await;
+    }
+
+    // Each attempt to charge a billing period has a payment identifier derived from the period
+    // and the attempt, so that charges retried after a failure of the task find the payment that
+    // was already created instead of charging the customer again
+    let payment_id = get_subscription_payment_id(&subscription);
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &payment_id,
+            &merchant_account.merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound);
+    let payment_exists = match payment_intent {
+        Ok(payment_intent) if UNCONFIRMED_STATUSES.contains(&payment_intent.status) => true,
+        Ok(payment_intent) => {
+            return complete_subscription_charge(
+                state,
+                merchant_account,
+                subscription,
+                payment_intent.payment_id,
+                payment_intent.status,
+                (current_period_start, current_period_end),
+            )
+            .await;
+        }
+        Err(error)
+            if matches!(
+                error.current_context(),
+                errors::ApiErrorResponse::PaymentNotFound
+            ) =>
+        {
+            false
+        }
+        Err(error) => return Err(error),
+    };
+
+    // The payment is recorded on the subscription before the customer is charged, which fails if
+    // the subscription was modified since it was read, such as when it was cancelled
+    let subscription = db
+        .update_subscription(
+            subscription,
+            storage::SubscriptionUpdate::PaymentInitiation {
+                latest_payment_id: Some(payment_id.clone()),
+            },
+        )
+        .await
+        .to_not_found_response(concurrent_update_error())?;
+    let payment_status = make_subscription_payment::<Ctx>(
+        state,
+        merchant_account,
+        key_store,
+        &subscription,
+        subscription_plan,
+        amount,
+        payment_id.clone(),
+        payment_exists,
+    )
+    .await?;
+
+    complete_subscription_charge(
+        state,
+        merchant_account,
+        subscription,
+        payment_id,
+        payment_status,
+        (current_period_start, current_period_end),
+    )
+    .await
+}
+
+/// Updates the subscription with the outcome of the payment that charged it: renews it if the
+/// payment succeeded, keeps it past due while the payment is pending, and counts the payment as a
+/// declined one otherwise.
+async fn complete_subscription_charge(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    subscription: storage::Subscription,
+    payment_id: String,
+    payment_status: storage_enums::IntentStatus,
+    billing_period: (PrimitiveDateTime, PrimitiveDateTime),
+) -> RouterResult<storage::Subscription> {
+    if PAID_STATUSES.contains(&payment_status) {
+        // The proration included in the payment is settled
+        let charged_proration_amount = subscription.proration_amount;
+        return renew_subscription(
+            state,
+            merchant_account,
+            subscription,
+            Some(payment_id),
+            billing_period,
+            charged_proration_amount,
+        )
+        .await;
+    }
+
+    // The subscription is past due until the payment reaches its final status, when the billing
+    // task renews the subscription or charges it again
+    if PENDING_STATUSES.contains(&payment_status) {
+        let (subscription, _) = update_charged_subscription(&*state.store, subscription, |_| {
+            Ok(storage::SubscriptionUpdate::PaymentPending {
+                latest_payment_id: Some(payment_id.clone()),
+            })
+        })
+        .await?;
+        return Ok(subscription);
+    }
+
+    let max_dunning_attempts = i32::from(state.conf.subscription.max_dunning_attempts);
+    let (subscription, updated) =
+        update_charged_subscription(&*state.store, subscription, |subscription| {
+            let failed_payment_attempts = subscription.failed_payment_attempts + 1;
+            Ok(storage::SubscriptionUpdate::PaymentFailure {
+                latest_payment_id: Some(payment_id.clone()),
+                failed_payment_attempts,
+                status: if failed_payment_attempts >= max_dunning_attempts {
+                    storage_enums::SubscriptionStatus::Cancelled
+                } else {
+                    storage_enums::SubscriptionStatus::PastDue
+                },
+            })
+        })
+        .await?;
+    if !updated {
+        return Ok(subscription);
+    }
+
+    trigger_subscription_webhook(
+        state,
+        merchant_account,
+        storage_enums::EventType::SubscriptionPaymentFailed,
+        &subscription,
+    )
+    .await;
+    if subscription.status == storage_enums::SubscriptionStatus::Cancelled {
+        trigger_subscription_webhook(
+            state,
+            merchant_account,
+            storage_enums::EventType::SubscriptionCancelled,
+            &subscription,
+        )
+        .await;
+    }
+
+    Ok(subscription)
+}
+
+/// Moves the subscription to its next billing period, deducting the proration that was settled
+/// from the proration of the subscription, which plan changes made while it was being charged may
+/// have added to.
+async fn renew_subscription(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    subscription: storage::Subscription,
+    latest_payment_id: Option<String>,
+    (current_period_start, current_period_end): (PrimitiveDateTime, PrimitiveDateTime),
+    settled_proration_amount: i64,
+) -> RouterResult<storage::Subscription> {
+    let (subscription, updated) =
+        update_charged_subscription(&*state.store, subscription, |subscription| {
+            let proration_amount = subscription
+                .proration_amount
+                .checked_sub(settled_proration_amount)
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Failed to compute the proration amount of the subscription")?;
+            Ok(storage::SubscriptionUpdate::Renewal {
+                latest_payment_id: latest_payment_id.clone(),
+                current_period_start,
+                current_period_end,
+                proration_amount,
+            })
+        })
+        .await?;
+
+    if updated {
+        trigger_subscription_webhook(
+            state,
+            merchant_account,
+            storage_enums::EventType::SubscriptionRenewed,
+            &subscription,
+        )
+        .await;
+    }
+
+    Ok(subscription)
+}
+
+/// Applies an update to a subscription that has been charged. A subscription modified since it
+/// was read is read again and the update is applied to it, unless it was cancelled in the
+/// meantime, in which case it is left as it is. Returns the subscription and whether it was
+/// updated.
+async fn update_charged_subscription(
+    db: &dyn StorageInterface,
+    mut subscription: storage::Subscription,
+    get_update: impl Fn(&storage::Subscription) -> RouterResult<storage::SubscriptionUpdate>,
+) -> RouterResult<(storage::Subscription, bool)> {
+    for _ in 0..MAX_CHARGED_SUBSCRIPTION_UPDATE_ATTEMPTS {
+        let subscription_update = get_update(&subscription)?;
+        match db
+            .update_subscription(subscription.clone(), subscription_update)
+            .await
+        {
+            Ok(subscription) => return Ok((subscription, true)),
+            Err(error) if error.current_context().is_db_not_found() => {
+                subscription = db
+                    .find_subscription_by_merchant_id_subscription_id(
+                        &subscription.merchant_id,
+                        &subscription.subscription_id,
+                    )
+                    .await
+                    .to_not_found_response(errors::ApiErrorResponse::SubscriptionNotFound)?;
+                if subscription.status == storage_enums::SubscriptionStatus::Cancelled {
+                    logger::info!(
+                        subscription_id = %subscription.subscription_id,
+                        "Subscription was cancelled while it was being charged"
+                    );
+                    return Ok((subscription, false));
+                }
+            }
+            Err(error) => {
+                return Err(error)
+                    .change_context(errors::ApiErrorResponse::InternalServerError)
+                    .attach_printable("Failed to update the charged subscription");
+            }
+        }
+    }
+
+    Err(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Subscription kept being modified while it was being charged")
+}
+
+/// Charges the subscription through its mandate, creating its payment or confirming the payment
+/// that was created by a previous attempt, and returns the status of the payment. Failing to
+/// make the payment is returned as an error rather than a declined payment, as the connector may
+/// not have been reached.
+#[allow(clippy::too_many_arguments)]
+async fn make_subscription_payment<Ctx: PaymentMethodRetrieve>(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    key_store: &domain::MerchantKeyStore,
+    subscription: &storage::Subscription,
+    subscription_plan: &storage::SubscriptionPlan,
+    amount: i64,
+    payment_id: String,
+    payment_exists: bool,
+) -> RouterResult<storage_enums::IntentStatus> {
+    let payment_request = api_models::payments::PaymentsRequest {
+        payment_id: Some(api_models::payments::PaymentIdType::PaymentIntentId(
+            payment_id,
+        )),
+        amount: (!payment_exists).then_some(amount.into()),
+        currency: (!payment_exists).then_some(subscription_plan.currency),
+        customer_id: Some(subscription.customer_id.clone()),
+        mandate_id: Some(subscription.mandate_id.clone()),
+        off_session: Some(true),
+        confirm: Some(true),
+        description: Some(format!(
+            "Subscription {} to {}",
+            subscription.subscription_id, subscription_plan.name
+        )),
+        ..Default::default()
+    };
+    let payment_result = if payment_exists {
+        payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Ctx>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            payments::PaymentConfirm,
+            payment_request,
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+        .await
+    } else {
+        payments::payments_core::<api::Authorize, api::PaymentsResponse, _, _, _, Ctx>(
+            state.clone(),
+            merchant_account.clone(),
+            key_store.clone(),
+            payments::PaymentCreate,
+            payment_request,
+            services::AuthFlow::Merchant,
+            payments::CallConnectorAction::Trigger,
+            HeaderPayload::default(),
+        )
+        .await
+    };
+
+    match payment_result.attach_printable("Failed to charge the subscription")? {
+        services::ApplicationResponse::Json(payments_response)
+        | services::ApplicationResponse::JsonWithHeaders((payments_response, _)) => {
+            Ok(payments_response.status)
+        }
+        _ => Err(errors::ApiErrorResponse::InternalServerError)
+            .into_report()
+            .attach_printable("Unexpected response while charging a subscription"),
+    }
+}
+
+/// The identifier of the payment of the current attempt to charge the billing period following
+/// the current one.
+fn get_subscription_payment_id(subscription: &storage::Subscription) -> String {
+    format!(
+        "{}_{}_{}",
+        subscription.subscription_id,
+        subscription
+            .current_period_end
+            .assume_utc()
+            .unix_timestamp(),
+        subscription.failed_payment_attempts
+    )
+}
+
+async fn cancel(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    subscription: storage::Subscription,
+) -> RouterResult<storage::Subscription> {
+    let subscription = state
+        .store
+        .update_subscription(
+            subscription,
+            storage::SubscriptionUpdate::StatusUpdate {
+                status: storage_enums::SubscriptionStatus::Cancelled,
+            },
+        )
+        .await
+        .to_not_found_response(concurrent_update_error())?;
+    trigger_subscription_webhook(
+        state,
+        merchant_account,
+        storage_enums::EventType::SubscriptionCancelled,
+        &subscription,
+    )
+    .await;
+
+    Ok(subscription)
+}
+
+/// The error of updating a subscription that was modified since it was read.
+fn concurrent_update_error() -> errors::ApiErrorResponse {
+    errors::ApiErrorResponse::PreconditionFailed {
+        message: "subscription was updated concurrently, retry the request".to_string(),
+    }
+}
+
+/// The time at which the billing task next charges the subscription: at the end of its billing
+/// period, or after the dunning interval when its last payment failed.
+fn get_next_billing_time(
+    state: &AppState,
+    subscription: &storage::Subscription,
+) -> Option<PrimitiveDateTime> {
+    match subscription.status {
+        storage_enums::SubscriptionStatus::Trialing | storage_enums::SubscriptionStatus::Active => {
+            Some(subscription.current_period_end)
+        }
+        storage_enums::SubscriptionStatus::PastDue => Some(date_time::now().saturating_add(
+            time::Duration::seconds(i64::from(
+                state.conf.subscription.dunning_retry_interval_in_seconds,
+            )),
+        )),
+        storage_enums::SubscriptionStatus::Cancelled => None,
+    }
+}
+
+#[instrument(skip_all)]
+async fn add_subscription_billing_task(
+    db: &dyn StorageInterface,
+    subscription: &storage::Subscription,
+    schedule_time: PrimitiveDateTime,
+) -> RouterResult<()> {
+    let current_time = date_time::now();
+    let tracking_data = storage::SubscriptionBillingWorkflow {
+        merchant_id: subscription.merchant_id.clone(),
+        subscription_id: subscription.subscription_id.clone(),
+    };
+    let subscription_billing_workflow_model = serde_json::to_value(&tracking_data)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("unable to serialize subscription billing tracker: {tracking_data:?}")
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: format!(
+            "{SUBSCRIPTION_BILLING_RUNNER}_{}",
+            subscription.subscription_id
+        ),
+        name: Some(String::from(SUBSCRIPTION_BILLING_NAME)),
+        tag: vec![String::from(SUBSCRIPTION_BILLING_TAG)],
+        runner: Some(String::from(SUBSCRIPTION_BILLING_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(schedule_time),
+        rule: String::new(),
+        tracking_data: subscription_billing_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting subscription billing task to process_tracker: subscription_id: {}",
+                subscription.subscription_id
+            )
+        })?;
+
+    Ok(())
+}
+
+async fn validate_mandate(
+    db: &dyn StorageInterface,
+    merchant_id: &str,
+    mandate_id: &str,
+    customer_id: &str,
+    currency: storage_enums::Currency,
+) -> RouterResult<()> {
+    let mandate = db
+        .find_mandate_by_merchant_id_mandate_id(merchant_id, mandate_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MandateNotFound)?;
+
+    let invalid_request = |message: &str| {
+        Err(errors::ApiErrorResponse::InvalidRequestData {
+            message: message.to_string(),
+        })
+        .into_report()
+    };
+    if mandate.customer_id != customer_id {
+        invalid_request("mandate does not belong to the customer")
+    } else if mandate.mandate_type != storage_enums::MandateType::MultiUse {
+        invalid_request("subscriptions can only be charged through multi use mandates")
+    } else if mandate
+        .mandate_currency
+        .map_or(false, |mandate_currency| mandate_currency != currency)
+    {
+        invalid_request("mandate does not allow charging the currency of the plan")
+    } else if mandate.mandate_status != storage_enums::MandateStatus::Active {
+        Err(errors::ApiErrorResponse::PreconditionFailed {
+            message: "mandate is not active".to_string(),
+        })
+        .into_report()
+    } else {
+        Ok(())
+    }
+}
+
+/// Notifies the merchant of an event of a subscription. Failing to do so is logged and does not
+/// fail the operation on the subscription.
+async fn trigger_subscription_webhook(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    event_type: storage_enums::EventType,
+    subscription: &storage::Subscription,
+) {
+    let result = async {
+        let profile_id = merchant_account
+            .default_profile
+            .as_ref()
+            .get_required_value("default_profile")
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Merchant account has no default business profile")?;
+        let business_profile = state
+            .store
+            .find_business_profile_by_profile_id(profile_id)
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::BusinessProfileNotFound {
+                id: profile_id.to_string(),
+            })?;
+
+        // Outgoing webhooks are deduplicated by their object and type, while the events of a
+        // subscription recur over its lifetime. They are told apart by the time of the change.
+        let primary_object_id = format!(
+            "{}_{}",
+            subscription.subscription_id,
+            subscription.last_modified_at.assume_utc().unix_timestamp()
+        );
+        Box::pin(
+            webhooks::create_event_and_trigger_appropriate_outgoing_webhook(
+                state.clone(),
+                merchant_account.clone(),
+                business_profile,
+                event_type,
+                storage_enums::EventClass::Subscriptions,
+                Some(subscription.subscription_id.clone()),
+                primary_object_id,
+                storage_enums::EventObjectType::SubscriptionDetails,
+                OutgoingWebhookContent::SubscriptionDetails(Box::new(get_subscription_response(
+                    subscription.clone(),
+                ))),
+            ),
+        )
+        .await
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(
+            ?error,
+            "Failed to trigger the outgoing webhook of the subscription"
+        );
+    }
+}
+
+fn is_valid_name(name: &str) -> bool {
+    (1..=MAX_NAME_LENGTH).contains(&name.chars().count())
+}
+
+fn is_valid_description(description: Option<&str>) -> bool {
+    description.map_or(true, |description| {
+        description.chars().count() <= MAX_NAME_LENGTH
+    })
+}
+
+/// The amount to add to the next payment when moving a subscription from one plan to another,
+/// which is the difference between their prices over the rest of the billing period. The amount
+/// is negative when the customer is credited for moving to a cheaper plan.
+fn compute_proration_amount(
+    current_amount: i64,
+    new_amount: i64,
+    remaining_seconds: i64,
+    period_seconds: i64,
+) -> Option<i64> {
+    if period_seconds <= 0 {
+        return Some(0);
+    }
+    let remaining_seconds = remaining_seconds.clamp(0, period_seconds);
+    let proration_amount = (i128::from(new_amount) - i128::from(current_amount))
+        * i128::from(remaining_seconds)
+        / i128::from(period_seconds);
+
+    i64::try_from(proration_amount).ok()
+}
+
+/// Adds billing intervals to a time. Adding months keeps the day of the month, which is clamped
+/// to the last day of shorter months.
+fn add_billing_interval(
+    start: PrimitiveDateTime,
+    interval: storage_enums::SubscriptionInterval,
+    interval_count: i32,
+) -> Option<PrimitiveDateTime> {
+    let interval_count = i64::from(interval_count);
+    match interval {
+        storage_enums::SubscriptionInterval::Day => {
+            start.checked_add(time::Duration::days(interval_count))
+        }
+        storage_enums::SubscriptionInterval::Week => {
+            start.checked_add(time::Duration::weeks(interval_count))
+        }
+        storage_enums::SubscriptionInterval::Month => date_time::add_months(start, interval_count),
+        storage_enums::SubscriptionInterval::Year => {
+            date_time::add_months(start, interval_count.checked_mul(12)?)
+        }
+    }
+}
+
+/// The end of the billing period starting at `period_start`, which is the first time after it that
+/// is a whole number of billing intervals from the billing anchor. Periods are counted from the
+/// anchor rather than from the previous period, so that a period clamped to the end of a shorter
+/// month does not move the day of the month that the following periods end on.
+fn get_billing_period_end(
+    billing_anchor: PrimitiveDateTime,
+    period_start: PrimitiveDateTime,
+    interval: storage_enums::SubscriptionInterval,
+    interval_count: i32,
+) -> Option<PrimitiveDateTime> {
+    if interval_count < 1 {
+        return None;
+    }
+
+    let mut intervals = interval_count;
+    loop {
+        let period_end = add_billing_interval(billing_anchor, interval, intervals)?;
+        if period_end > period_start {
+            return Some(period_end);
+        }
+        intervals = intervals.checked_add(interval_count)?;
+    }
+}
+
+fn get_subscription_plan_response(
+    subscription_plan: storage::SubscriptionPlan,
+) -> SubscriptionPlanResponse {
+    SubscriptionPlanResponse {
+        plan_id: subscription_plan.plan_id,
+        merchant_id: subscription_plan.merchant_id,
+        name: subscription_plan.name,
+        description: subscription_plan.description,
+        amount: subscription_plan.amount,
+        currency: subscription_plan.currency,
+        interval: subscription_plan.interval,
+        interval_count: subscription_plan.interval_count,
+        trial_period_days: subscription_plan.trial_period_days,
+        active: subscription_plan.active,
+        metadata: subscription_plan.metadata.map(pii::SecretSerdeValue::new),
+        created_at: subscription_plan.created_at,
+    }
+}
+
+fn get_subscription_response(subscription: storage::Subscription) -> SubscriptionResponse {
+    SubscriptionResponse {
+        subscription_id: subscription.subscription_id,
+        merchant_id: subscription.merchant_id,
+        customer_id: subscription.customer_id,
+        plan_id: subscription.plan_id,
+        mandate_id: subscription.mandate_id,
+        status: subscription.status,
+        current_period_start: subscription.current_period_start,
+        current_period_end: subscription.current_period_end,
+        trial_end: subscription.trial_end,
+        cancel_at_period_end: subscription.cancel_at_period_end,
+        cancelled_at: subscription.cancelled_at,
+        latest_payment_id: subscription.latest_payment_id,
+        failed_payment_attempts: subscription.failed_payment_attempts,
+        proration_amount: subscription.proration_amount,
+        metadata: subscription.metadata.map(pii::SecretSerdeValue::new),
+        created_at: subscription.created_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use time::macros::datetime;
+
+    use super::*;
+
+    #[test]
+    fn test_add_billing_interval() {
+        let start = datetime!(2024-01-31 10:30);
+
+        assert_eq!(
+            add_billing_interval(start, storage_enums::SubscriptionInterval::Month, 1),
+            Some(datetime!(2024-02-29 10:30))
+        );
+        assert_eq!(
+            add_billing_interval(start, storage_enums::SubscriptionInterval::Month, 13),
+            Some(datetime!(2025-02-28 10:30))
+        );
+        assert_eq!(
+            add_billing_interval(start, storage_enums::SubscriptionInterval::Year, 1),
+            Some(datetime!(2025-01-31 10:30))
+        );
+        assert_eq!(
+            add_billing_interval(start, storage_enums::SubscriptionInterval::Week, 2),
+            Some(datetime!(2024-02-14 10:30))
+        );
+    }
+
+    #[test]
+    fn test_get_billing_period_end() {
+        let billing_anchor = datetime!(2024-01-31 10:30);
+        let monthly_period_end = |period_start| {
+            get_billing_period_end(
+                billing_anchor,
+                period_start,
+                storage_enums::SubscriptionInterval::Month,
+                1,
+            )
+        };
+
+        assert_eq!(
+            monthly_period_end(billing_anchor),
+            Some(datetime!(2024-02-29 10:30))
+        );
+        // The period clamped to the end of February does not move the following periods
+        assert_eq!(
+            monthly_period_end(datetime!(2024-02-29 10:30)),
+            Some(datetime!(2024-03-31 10:30))
+        );
+        assert_eq!(
+            monthly_period_end(datetime!(2024-03-31 10:30)),
+            Some(datetime!(2024-04-30 10:30))
+        );
+        assert_eq!(
+            monthly_period_end(datetime!(2024-04-30 10:30)),
+            Some(datetime!(2024-05-31 10:30))
+        );
+        // Periods of plans billed every few intervals are counted from the anchor as well
+        assert_eq!(
+            get_billing_period_end(
+                billing_anchor,
+                datetime!(2024-03-31 10:30),
+                storage_enums::SubscriptionInterval::Month,
+                2,
+            ),
+            Some(datetime!(2024-05-31 10:30))
+        );
+        assert_eq!(
+            get_billing_period_end(
+                billing_anchor,
+                billing_anchor,
+                storage_enums::SubscriptionInterval::Day,
+                0,
+            ),
+            None
+        );
+    }
+
+    #[test]
+    fn test_compute_proration_amount() {
+        // Upgrading halfway through the period charges half of the difference
+        assert_eq!(compute_proration_amount(1000, 3000, 15, 30), Some(1000));
+        // Downgrading credits the customer
+        assert_eq!(compute_proration_amount(3000, 1000, 10, 30), Some(-666));
+        // Changes after the end of the period are not prorated
+        assert_eq!(compute_proration_amount(1000, 3000, -5, 30), Some(0));
+        assert_eq!(compute_proration_amount(1000, 3000, 15, 0), Some(0));
+    }
+}
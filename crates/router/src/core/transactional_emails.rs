@@ -0,0 +1,438 @@
+use api_models::admin::{MerchantEmailConfig, TransactionalEmailEvent};
+use common_utils::{date_time, ext_traits::ValueExt, pii};
+use diesel_models::enums as storage_enums;
+use error_stack::{IntoReport, ResultExt};
+use masking::ExposeInterface;
+use router_env::{instrument, logger, tracing};
+use serde::Serialize;
+use time::PrimitiveDateTime;
+
+use crate::{
+    core::{
+        errors::{self, RouterResult, StorageErrorExt},
+        invoices,
+    },
+    db::StorageInterface,
+    routes::AppState,
+    services::email,
+    types::{domain, storage},
+};
+
+const TRANSACTIONAL_EMAIL_TAG: &str = "TRANSACTIONAL_EMAIL";
+const TRANSACTIONAL_EMAIL_RUNNER: &str = "TRANSACTIONAL_EMAIL_WORKFLOW";
+
+/// Queues the receipt of a payment that has succeeded.
+pub async fn trigger_payment_succeeded_email(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_intent: &storage::PaymentIntent,
+) {
+    trigger_transactional_email(
+        state,
+        merchant_account,
+        storage::TransactionalEmailWorkflow {
+            merchant_id: merchant_account.merchant_id.clone(),
+            event: TransactionalEmailEvent::PaymentSucceeded,
+            payment_id: payment_intent.payment_id.clone(),
+            refund_id: None,
+            payment_link_id: None,
+            invoice_id: None,
+        },
+    )
+    .await
+}
+
+/// Queues the refund processed email of a refund, if it has transitioned to success from the
+/// provided status.
+pub async fn trigger_refund_processed_email(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    previous_refund_status: storage_enums::RefundStatus,
+    refund: &storage::Refund,
+) {
+    if previous_refund_status == storage_enums::RefundStatus::Success
+        || refund.refund_status != storage_enums::RefundStatus::Success
+    {
+        return;
+    }
+
+    trigger_transactional_email(
+        state,
+        merchant_account,
+        storage::TransactionalEmailWorkflow {
+            merchant_id: merchant_account.merchant_id.clone(),
+            event: TransactionalEmailEvent::RefundProcessed,
+            payment_id: refund.payment_id.clone(),
+            refund_id: Some(refund.refund_id.clone()),
+            payment_link_id: None,
+            invoice_id: None,
+        },
+    )
+    .await
+}
+
+/// Queues the email containing a payment link that has been created.
+pub async fn trigger_payment_link_created_email(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_id: &str,
+    payment_link_id: &str,
+) {
+    trigger_transactional_email(
+        state,
+        merchant_account,
+        storage::TransactionalEmailWorkflow {
+            merchant_id: merchant_account.merchant_id.clone(),
+            event: TransactionalEmailEvent::PaymentLinkCreated,
+            payment_id: payment_id.to_owned(),
+            refund_id: None,
+            payment_link_id: Some(payment_link_id.to_owned()),
+            invoice_id: None,
+        },
+    )
+    .await
+}
+
+/// Queues the email containing an invoice that has been finalized.
+pub async fn trigger_invoice_issued_email(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    payment_id: &str,
+    invoice_id: &str,
+) {
+    trigger_transactional_email(
+        state,
+        merchant_account,
+        storage::TransactionalEmailWorkflow {
+            merchant_id: merchant_account.merchant_id.clone(),
+            event: TransactionalEmailEvent::InvoiceIssued,
+            payment_id: payment_id.to_owned(),
+            refund_id: None,
+            payment_link_id: None,
+            invoice_id: Some(invoice_id.to_owned()),
+        },
+    )
+    .await
+}
+
+/// Queues the email of the event if the merchant has enabled it. Emails are sent by the
+/// scheduler, failing to queue one is logged and does not fail the flow that triggered it.
+#[instrument(skip_all, fields(event = %tracking_data.event))]
+async fn trigger_transactional_email(
+    state: &AppState,
+    merchant_account: &domain::MerchantAccount,
+    tracking_data: storage::TransactionalEmailWorkflow,
+) {
+    let result = async {
+        if get_enabled_email_config(merchant_account, tracking_data.event)?.is_some() {
+            add_transactional_email_task(&*state.store, &tracking_data).await?;
+        }
+        Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(())
+    }
+    .await;
+
+    if let Err(error) = result {
+        logger::error!(?error, "Failed to queue transactional email");
+    }
+}
+
+/// The email configuration of the merchant, if the merchant has enabled emails for the event.
+fn get_enabled_email_config(
+    merchant_account: &domain::MerchantAccount,
+    event: TransactionalEmailEvent,
+) -> RouterResult<Option<MerchantEmailConfig>> {
+    let email_config = merchant_account
+        .email_config
+        .clone()
+        .map(|email_config| {
+            email_config
+                .parse_value::<MerchantEmailConfig>("MerchantEmailConfig")
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to parse the email config of the merchant")
+        })
+        .transpose()?;
+
+    Ok(email_config.filter(|email_config| email_config.enabled_events.contains(&event)))
+}
+
+#[instrument(skip_all)]
+async fn add_transactional_email_task(
+    db: &dyn StorageInterface,
+    tracking_data: &storage::TransactionalEmailWorkflow,
+) -> RouterResult<()> {
+    let current_time = date_time::now();
+
+    let transactional_email_workflow_model = serde_json::to_value(tracking_data)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!("unable to serialize transactional email tracker: {tracking_data:?}")
+        })?;
+
+    let process_tracker_entry = storage::ProcessTrackerNew {
+        id: generate_task_id_for_transactional_email_workflow(tracking_data),
+        name: Some(tracking_data.event.to_string().to_uppercase()),
+        tag: vec![String::from(TRANSACTIONAL_EMAIL_TAG)],
+        runner: Some(String::from(TRANSACTIONAL_EMAIL_RUNNER)),
+        retry_count: 0,
+        schedule_time: Some(current_time),
+        rule: String::new(),
+        tracking_data: transactional_email_workflow_model,
+        business_status: String::from("Pending"),
+        status: storage_enums::ProcessTrackerStatus::New,
+        event: vec![],
+        created_at: current_time,
+        updated_at: current_time,
+    };
+
+    db.insert_process(process_tracker_entry)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| {
+            format!(
+                "Failed while inserting transactional email task to process_tracker: payment_id: {}",
+                tracking_data.payment_id
+            )
+        })?;
+
+    Ok(())
+}
+
+/// Each event is emailed at most once for each payment, refund, payment link or invoice.
+fn generate_task_id_for_transactional_email_workflow(
+    tracking_data: &storage::TransactionalEmailWorkflow,
+) -> String {
+    let object_id = tracking_data
+        .refund_id
+        .as_ref()
+        .or(tracking_data.payment_link_id.as_ref())
+        .or(tracking_data.invoice_id.as_ref())
+        .unwrap_or(&tracking_data.payment_id);
+    format!(
+        "{TRANSACTIONAL_EMAIL_RUNNER}_{}_{}_{object_id}",
+        tracking_data.event.to_string().to_uppercase(),
+        tracking_data.merchant_id
+    )
+}
+
+#[derive(Debug, Serialize)]
+struct PaymentEmailContext {
+    payment_id: String,
+    amount: String,
+    currency: String,
+    description: Option<String>,
+    date: String,
+}
+
+#[derive(Debug, Serialize)]
+struct RefundEmailContext {
+    refund_id: String,
+    amount: String,
+    currency: String,
+    reason: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct PaymentLinkEmailContext {
+    url: String,
+    amount: String,
+    currency: String,
+    expires_at: Option<String>,
+}
+
+#[derive(Debug, Serialize)]
+struct InvoiceEmailContext {
+    invoice_id: String,
+    amount: String,
+    currency: String,
+    due_date: String,
+    is_overdue: bool,
+    url: String,
+    pdf_url: String,
+}
+
+/// Formats an amount in the lowest denomination of the currency in its base unit.
+fn format_amount(amount: i64, currency: Option<storage_enums::Currency>) -> (String, String) {
+    match currency {
+        Some(currency) => (
+            currency
+                .to_currency_base_unit(amount)
+                .unwrap_or_else(|_| amount.to_string()),
+            currency.to_string(),
+        ),
+        None => (amount.to_string(), String::new()),
+    }
+}
+
+fn format_date(date: PrimitiveDateTime) -> String {
+    format!(
+        "{} {:02}:{:02} UTC",
+        date.date(),
+        date.hour(),
+        date.minute()
+    )
+}
+
+/// Renders and sends the email of the event to the customer of the payment. Nothing is sent if
+/// the merchant has disabled the event since it was queued, or if the customer of the payment has
+/// no email.
+#[instrument(skip_all, fields(event = %tracking_data.event))]
+pub async fn send_transactional_email(
+    state: &AppState,
+    tracking_data: &storage::TransactionalEmailWorkflow,
+) -> RouterResult<()> {
+    let db = &*state.store;
+    let merchant_id = tracking_data.merchant_id.as_str();
+    let key_store = db
+        .get_merchant_key_store_by_merchant_id(merchant_id, &db.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = db
+        .find_merchant_account_by_merchant_id(merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let Some(email_config) = get_enabled_email_config(&merchant_account, tracking_data.event)?
+    else {
+        logger::info!("Transactional email disabled by the merchant, skipping");
+        return Ok(());
+    };
+
+    let payment_intent = db
+        .find_payment_intent_by_payment_id_merchant_id(
+            &tracking_data.payment_id,
+            merchant_id,
+            merchant_account.storage_scheme,
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::PaymentNotFound)?;
+
+    let customer = match payment_intent.customer_id.as_deref() {
+        Some(customer_id) => db
+            .find_customer_optional_by_customer_id_merchant_id(customer_id, merchant_id, &key_store)
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to fetch the customer of the payment")?,
+        None => None,
+    };
+    let Some(recipient) = customer
+        .and_then(|customer| customer.email)
+        .map(pii::Email::from)
+    else {
+        logger::info!("Customer of the payment has no email, skipping");
+        return Ok(());
+    };
+
+    let (amount, currency) = format_amount(payment_intent.amount, payment_intent.currency);
+    let mut context = tera::Context::new();
+    context.insert(
+        "payment",
+        &PaymentEmailContext {
+            payment_id: payment_intent.payment_id.clone(),
+            amount,
+            currency,
+            description: payment_intent.description.clone(),
+            date: format_date(payment_intent.modified_at),
+        },
+    );
+
+    match tracking_data.event {
+        TransactionalEmailEvent::PaymentSucceeded => {}
+        TransactionalEmailEvent::RefundProcessed => {
+            let refund_id = tracking_data
+                .refund_id
+                .as_deref()
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Missing refund_id in transactional email tracking data")?;
+            let refund = db
+                .find_refund_by_merchant_id_refund_id(
+                    merchant_id,
+                    refund_id,
+                    merchant_account.storage_scheme,
+                )
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::RefundNotFound)?;
+            let (amount, currency) = format_amount(refund.refund_amount, Some(refund.currency));
+            context.insert(
+                "refund",
+                &RefundEmailContext {
+                    refund_id: refund.refund_id,
+                    amount,
+                    currency,
+                    reason: refund.refund_reason,
+                },
+            );
+        }
+        TransactionalEmailEvent::PaymentLinkCreated => {
+            let payment_link_id = tracking_data
+                .payment_link_id
+                .as_deref()
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Missing payment_link_id in transactional email tracking data")?;
+            let payment_link = db
+                .find_payment_link_by_payment_link_id(payment_link_id)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::PaymentLinkNotFound)?;
+            let (amount, currency) = format_amount(payment_link.amount, payment_link.currency);
+            context.insert(
+                "payment_link",
+                &PaymentLinkEmailContext {
+                    url: payment_link.link_to_pay,
+                    amount,
+                    currency,
+                    expires_at: payment_link.fulfilment_time.map(format_date),
+                },
+            );
+        }
+        TransactionalEmailEvent::InvoiceIssued | TransactionalEmailEvent::InvoiceReminder => {
+            let invoice_id = tracking_data
+                .invoice_id
+                .as_deref()
+                .ok_or(errors::ApiErrorResponse::InternalServerError)
+                .into_report()
+                .attach_printable("Missing invoice_id in transactional email tracking data")?;
+            let invoice = db
+                .find_invoice_by_merchant_id_invoice_id(merchant_id, invoice_id)
+                .await
+                .to_not_found_response(errors::ApiErrorResponse::InvoiceNotFound)?;
+            let base_url = state.conf.server.base_url.as_str();
+            let (amount, currency) = format_amount(invoice.total_amount, Some(invoice.currency));
+            context.insert(
+                "invoice",
+                &InvoiceEmailContext {
+                    url: invoices::get_hosted_invoice_url(base_url, merchant_id, invoice_id),
+                    pdf_url: invoices::get_hosted_invoice_pdf_url(
+                        base_url,
+                        merchant_id,
+                        invoice_id,
+                    ),
+                    invoice_id: invoice.invoice_id,
+                    amount,
+                    currency,
+                    due_date: format_date(invoice.due_date),
+                    is_overdue: invoice.due_date < date_time::now(),
+                },
+            );
+        }
+    }
+
+    let merchant_name = merchant_account
+        .merchant_name
+        .map(|merchant_name| merchant_name.into_inner().expose())
+        .unwrap_or_else(|| merchant_account.merchant_id.clone());
+    let message = email::render_transactional_email(
+        tracking_data.event,
+        &merchant_name,
+        &email_config,
+        context,
+    )?;
+
+    state
+        .email_client
+        .send_email(recipient, message)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to send transactional email")
+}
@@ -0,0 +1,789 @@
+pub mod password;
+
+use api_models::{enums as api_enums, user as user_types};
+use common_utils::{date_time, pii};
+use error_stack::{report, ResultExt};
+#[cfg(feature = "email")]
+use external_services::email::EmailMessage;
+use futures::future::try_join_all;
+use masking::{PeekInterface, Secret};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResponse, RouterResult, StorageErrorExt},
+    db::StorageInterface,
+    routes::AppState,
+    services::{
+        self,
+        authentication::{self as auth, AuthToken, TokenType, UserFromToken},
+        authorization,
+    },
+    types::storage,
+    utils,
+};
+
+/// Recorded as the creator of the roles of users created with the admin API key.
+const ADMIN_API_CREATOR: &str = "admin_api";
+
+#[cfg(feature = "email")]
+const USED_EMAIL_TOKEN_PREFIX: &str = "USED_EMAIL_TOKEN";
+
+/// The purpose of a token sent to a user by email.
+#[cfg(feature = "email")]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+enum EmailTokenPurpose {
+    Invite,
+    ResetPassword,
+}
+
+/// The claims of the tokens sent to users by email.
+#[cfg(feature = "email")]
+#[derive(Debug, serde::Serialize, serde::Deserialize)]
+struct EmailToken {
+    email: pii::Email,
+    /// The merchant account the user was invited to.
+    merchant_id: Option<String>,
+    purpose: EmailTokenPurpose,
+    iat: u64,
+    exp: u64,
+}
+
+pub async fn create_user(
+    state: AppState,
+    req: user_types::CreateUserRequest,
+) -> RouterResponse<user_types::UserRoleResponse> {
+    let key_store = state
+        .store
+        .get_merchant_key_store_by_merchant_id(&req.merchant_id, &state.store.get_master_key_ring())
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+    let merchant_account = state
+        .store
+        .find_merchant_account_by_merchant_id(&req.merchant_id, &key_store)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::MerchantAccountNotFound)?;
+
+    let user = match find_user_by_email(&state, &req.email).await? {
+        Some(user) => user,
+        None => {
+            let password = req
+                .password
+                .ok_or(errors::ApiErrorResponse::MissingRequiredField {
+                    field_name: "password",
+                })?;
+            insert_user(&state, req.email, req.name, Some(password), false).await?
+        }
+    };
+
+    let user_role = insert_user_role(
+        &state,
+        &user,
+        &merchant_account.merchant_id,
+        &merchant_account.organization_id,
+        req.role,
+        api_enums::UserStatus::Active,
+        ADMIN_API_CREATOR,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::Json(get_user_role_response(
+        user, user_role,
+    )))
+}
+
+pub async fn signin(
+    state: AppState,
+    req: user_types::SignInRequest,
+) -> RouterResponse<user_types::AuthTokenResponse> {
+    let invalid_credentials = || errors::ApiErrorResponse::GenericUnauthorized {
+        message: "Incorrect email or password".to_string(),
+    };
+
+    let user = find_user_by_email(&state, &req.email).await?;
+    let is_password_valid = match user.as_ref().and_then(|user| user.password.as_ref()) {
+        Some(password_hash) => password::is_password_valid(&req.password, password_hash).await?,
+        None => {
+            // The sign in must take as long as it would for an existing user
+            password::verify_dummy_password(&req.password).await?;
+            false
+        }
+    };
+    let user = user
+        .filter(|_| is_password_valid)
+        .ok_or_else(invalid_credentials)?;
+
+    let user_role = match req.merchant_id {
+        Some(merchant_id) => find_active_user_role(&state, &user.user_id, &merchant_id).await?,
+        None => find_default_user_role(&*state.store, &user.user_id).await?,
+    }
+    .ok_or_else(|| errors::ApiErrorResponse::GenericUnauthorized {
+        message: "User is not an active member of the merchant account".to_string(),
+    })?;
+
+    Ok(services::ApplicationResponse::Json(
+        issue_tokens(&state, &user_role).await?,
+    ))
+}
+
+pub async fn refresh_token(
+    state: AppState,
+    req: user_types::RefreshTokenRequest,
+) -> RouterResponse<user_types::AuthTokenResponse> {
+    let payload = auth::decode_jwt::<AuthToken>(req.refresh_token.peek(), &state).await?;
+    if payload.token_type != TokenType::Refresh
+        || auth::is_token_revoked(&*state.store, &payload.user_id, payload.iat).await?
+    {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable("Token is not a valid refresh token");
+    }
+
+    // The role of the user may have changed since the refresh token was issued
+    let user_role = find_active_user_role(&state, &payload.user_id, &payload.merchant_id)
+        .await?
+        .ok_or(errors::ApiErrorResponse::InvalidJwtToken)?;
+
+    Ok(services::ApplicationResponse::Json(
+        issue_tokens(&state, &user_role).await?,
+    ))
+}
+
+pub async fn signout(state: AppState, user_from_token: UserFromToken) -> RouterResponse<()> {
+    auth::revoke_user_tokens(
+        &*state.store,
+        &user_from_token.user_id,
+        state.conf.user.refresh_token_ttl_in_seconds,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::StatusOk)
+}
+
+pub async fn change_password(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_types::ChangePasswordRequest,
+) -> RouterResponse<()> {
+    let user = find_user_by_id(&state, &user_from_token.user_id).await?;
+
+    let is_old_password_valid = match user.password {
+        Some(ref password_hash) => {
+            password::is_password_valid(&req.old_password, password_hash).await?
+        }
+        None => false,
+    };
+    if !is_old_password_valid {
+        return Err(report!(errors::ApiErrorResponse::GenericUnauthorized {
+            message: "Incorrect old password".to_string(),
+        }));
+    }
+
+    password::validate_password_policy(&req.new_password)?;
+    update_user(
+        &state,
+        &user.user_id,
+        storage::UserUpdate::PasswordUpdate {
+            password: password::generate_password_hash(&req.new_password).await?,
+        },
+    )
+    .await?;
+
+    // Sessions signed in with the old password must sign in again
+    auth::revoke_user_tokens(
+        &*state.store,
+        &user.user_id,
+        state.conf.user.refresh_token_ttl_in_seconds,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::StatusOk)
+}
+
+pub async fn switch_merchant(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_types::SwitchMerchantRequest,
+) -> RouterResponse<user_types::AuthTokenResponse> {
+    let user_role = find_active_user_role(&state, &user_from_token.user_id, &req.merchant_id)
+        .await?
+        .ok_or_else(|| errors::ApiErrorResponse::AccessForbidden {
+            resource: req.merchant_id.clone(),
+        })?;
+
+    Ok(services::ApplicationResponse::Json(
+        issue_tokens(&state, &user_role).await?,
+    ))
+}
+
+pub async fn get_user_details(
+    state: AppState,
+    user_from_token: UserFromToken,
+) -> RouterResponse<user_types::GetUserResponse> {
+    let user = find_user_by_id(&state, &user_from_token.user_id).await?;
+    let merchants = state
+        .store
+        .list_user_roles_by_user_id(&user.user_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .into_iter()
+        .map(|user_role| user_types::UserMerchantResponse {
+            merchant_id: user_role.merchant_id,
+            role: user_role.role,
+            status: user_role.status,
+        })
+        .collect();
+
+    Ok(services::ApplicationResponse::Json(
+        user_types::GetUserResponse {
+            user_id: user.user_id,
+            email: user.email,
+            name: user.name,
+            is_verified: user.is_verified,
+            merchant_id: user_from_token.merchant_id,
+            org_id: user_from_token.org_id,
+            role: user_from_token.role,
+            permissions: authorization::get_permissions(user_from_token.role),
+            merchants,
+        },
+    ))
+}
+
+pub async fn list_users(
+    state: AppState,
+    user_from_token: UserFromToken,
+) -> RouterResponse<Vec<user_types::UserRoleResponse>> {
+    let user_roles = state
+        .store
+        .list_user_roles_by_merchant_id(&user_from_token.merchant_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?;
+
+    let users = try_join_all(user_roles.into_iter().map(|user_role| {
+        let state = &state;
+        async move {
+            let user = find_user_by_id(state, &user_role.user_id).await?;
+            Ok::<_, error_stack::Report<errors::ApiErrorResponse>>(get_user_role_response(
+                user, user_role,
+            ))
+        }
+    }))
+    .await?;
+
+    Ok(services::ApplicationResponse::Json(users))
+}
+
+pub async fn update_user_role(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_types::UpdateUserRoleRequest,
+) -> RouterResponse<user_types::UserRoleResponse> {
+    validate_not_self(&user_from_token, &req.user_id)?;
+
+    let user = find_user_by_id(&state, &req.user_id).await?;
+    let user_role = state
+        .store
+        .update_user_role_by_user_id_merchant_id(
+            &req.user_id,
+            &user_from_token.merchant_id,
+            storage::UserRoleUpdate::RoleUpdate {
+                role: req.role,
+                modified_by: user_from_token.user_id,
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::UserNotFound)?;
+
+    // Tokens issued for the previous role must not outlive the role change
+    auth::revoke_user_tokens(
+        &*state.store,
+        &user.user_id,
+        state.conf.user.refresh_token_ttl_in_seconds,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::Json(get_user_role_response(
+        user, user_role,
+    )))
+}
+
+pub async fn delete_user_role(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_types::UserId,
+) -> RouterResponse<()> {
+    validate_not_self(&user_from_token, &req.user_id)?;
+
+    let is_deleted = state
+        .store
+        .delete_user_role_by_user_id_merchant_id(&req.user_id, &user_from_token.merchant_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::UserNotFound)?;
+    if !is_deleted {
+        return Err(report!(errors::ApiErrorResponse::UserNotFound));
+    }
+
+    auth::revoke_user_tokens(
+        &*state.store,
+        &req.user_id,
+        state.conf.user.refresh_token_ttl_in_seconds,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::StatusOk)
+}
+
+#[cfg(feature = "email")]
+pub async fn invite_user(
+    state: AppState,
+    user_from_token: UserFromToken,
+    req: user_types::InviteUserRequest,
+) -> RouterResponse<user_types::UserRoleResponse> {
+    let user = match find_user_by_email(&state, &req.email).await? {
+        Some(user) => user,
+        None => insert_user(&state, req.email, req.name, None, false).await?,
+    };
+
+    let user_role = insert_user_role(
+        &state,
+        &user,
+        &user_from_token.merchant_id,
+        &user_from_token.org_id,
+        req.role,
+        api_enums::UserStatus::InvitationSent,
+        &user_from_token.user_id,
+    )
+    .await?;
+
+    let token = generate_email_token(
+        &state,
+        user.email.clone(),
+        Some(user_from_token.merchant_id.clone()),
+        EmailTokenPurpose::Invite,
+    )
+    .await?;
+    state
+        .email_client
+        .send_email(
+            user.email.clone(),
+            EmailMessage::plain_text(
+                "You have been invited to Hyperswitch".to_string(),
+                format!(
+                    "Hello,\n\nYou have been invited to join the merchant account {} on \
+                     Hyperswitch. Accept the invitation by following the link below:\n\n\
+                     {}/user/accept_invite?token={token}\n\n\
+                     Thanks,\nTeam Hyperswitch",
+                    user_from_token.merchant_id, state.conf.email.base_url
+                ),
+            ),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to send invitation email")?;
+
+    Ok(services::ApplicationResponse::Json(get_user_role_response(
+        user, user_role,
+    )))
+}
+
+#[cfg(feature = "email")]
+pub async fn accept_invitation(
+    state: AppState,
+    req: user_types::AcceptInvitationRequest,
+) -> RouterResponse<user_types::AuthTokenResponse> {
+    let token = decode_email_token(&state, &req.token, EmailTokenPurpose::Invite).await?;
+    let merchant_id = token
+        .merchant_id
+        .ok_or(errors::ApiErrorResponse::InvalidJwtToken)?;
+    let user = find_user_by_email(&state, &token.email)
+        .await?
+        .ok_or(errors::ApiErrorResponse::UserNotFound)?;
+
+    let user_role = state
+        .store
+        .find_user_role_by_user_id_merchant_id(&user.user_id, &merchant_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::UserNotFound)?;
+    if user_role.status != api_enums::UserStatus::InvitationSent {
+        return Err(report!(errors::ApiErrorResponse::PreconditionFailed {
+            message: "The invitation was already accepted".to_string(),
+        }));
+    }
+
+    // Invited users without an account choose their password when accepting the invitation
+    let password = match (&user.password, req.password) {
+        (Some(_), _) => None,
+        (None, Some(password)) => {
+            password::validate_password_policy(&password)?;
+            Some(password::generate_password_hash(&password).await?)
+        }
+        (None, None) => Err(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: "password",
+        })?,
+    };
+    update_user(
+        &state,
+        &user.user_id,
+        storage::UserUpdate::VerifyUser { password },
+    )
+    .await?;
+
+    let user_role = state
+        .store
+        .update_user_role_by_user_id_merchant_id(
+            &user.user_id,
+            &merchant_id,
+            storage::UserRoleUpdate::StatusUpdate {
+                status: api_enums::UserStatus::Active,
+                modified_by: user.user_id.clone(),
+            },
+        )
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::UserNotFound)?;
+
+    Ok(services::ApplicationResponse::Json(
+        issue_tokens(&state, &user_role).await?,
+    ))
+}
+
+#[cfg(feature = "email")]
+pub async fn forgot_password(
+    state: AppState,
+    req: user_types::ForgotPasswordRequest,
+) -> RouterResponse<()> {
+    // Respond alike whether or not the user exists, to not disclose the users that exist
+    let Some(user) = find_user_by_email(&state, &req.email).await? else {
+        return Ok(services::ApplicationResponse::StatusOk);
+    };
+
+    let token = generate_email_token(
+        &state,
+        user.email.clone(),
+        None,
+        EmailTokenPurpose::ResetPassword,
+    )
+    .await?;
+    state
+        .email_client
+        .send_email(
+            user.email,
+            EmailMessage::plain_text(
+                "Reset your Hyperswitch password".to_string(),
+                format!(
+                    "Hello,\n\nA password reset was requested for your Hyperswitch account. \
+                     Reset your password by following the link below:\n\n\
+                     {}/user/reset_password?token={token}\n\n\
+                     If you did not request a password reset, you can ignore this email.\n\n\
+                     Thanks,\nTeam Hyperswitch",
+                    state.conf.email.base_url
+                ),
+            ),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to send password reset email")?;
+
+    Ok(services::ApplicationResponse::StatusOk)
+}
+
+#[cfg(feature = "email")]
+pub async fn reset_password(
+    state: AppState,
+    req: user_types::ResetPasswordRequest,
+) -> RouterResponse<()> {
+    let token = decode_email_token(&state, &req.token, EmailTokenPurpose::ResetPassword).await?;
+    password::validate_password_policy(&req.password)?;
+    let user = find_user_by_email(&state, &token.email)
+        .await?
+        .ok_or(errors::ApiErrorResponse::UserNotFound)?;
+
+    // Password reset tokens can only be used once
+    let redis_conn = state
+        .store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+    let token_hash = blake3::hash(req.token.peek().as_bytes()).to_hex();
+    let is_first_use = redis_conn
+        .set_key_if_not_exists_with_expiry(
+            &format!("{USED_EMAIL_TOKEN_PREFIX}_{token_hash}"),
+            true,
+            Some(i64::from(state.conf.user.email_token_ttl_in_seconds)),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to record use of password reset token")?
+        == redis_interface::SetnxReply::KeySet;
+    if !is_first_use {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable("Password reset token was already used");
+    }
+
+    update_user(
+        &state,
+        &user.user_id,
+        storage::UserUpdate::VerifyUser {
+            password: Some(password::generate_password_hash(&req.password).await?),
+        },
+    )
+    .await?;
+
+    auth::revoke_user_tokens(
+        &*state.store,
+        &user.user_id,
+        state.conf.user.refresh_token_ttl_in_seconds,
+    )
+    .await?;
+
+    Ok(services::ApplicationResponse::StatusOk)
+}
+
+async fn issue_tokens(
+    state: &AppState,
+    user_role: &storage::UserRole,
+) -> RouterResult<user_types::AuthTokenResponse> {
+    let user_config = &state.conf.user;
+    let now = date_time::now();
+
+    let token = AuthToken::new(
+        user_role,
+        TokenType::Access,
+        user_config.access_token_ttl_in_seconds,
+    )?;
+    let refresh_token = AuthToken::new(
+        user_role,
+        TokenType::Refresh,
+        user_config.refresh_token_ttl_in_seconds,
+    )?;
+
+    Ok(user_types::AuthTokenResponse {
+        user_id: user_role.user_id.clone(),
+        merchant_id: user_role.merchant_id.clone(),
+        role: user_role.role,
+        token: auth::generate_jwt(&token, state).await?.into(),
+        token_expires_at: now.saturating_add(time::Duration::seconds(i64::from(
+            user_config.access_token_ttl_in_seconds,
+        ))),
+        refresh_token: auth::generate_jwt(&refresh_token, state).await?.into(),
+        refresh_token_expires_at: now.saturating_add(time::Duration::seconds(i64::from(
+            user_config.refresh_token_ttl_in_seconds,
+        ))),
+    })
+}
+
+#[cfg(feature = "email")]
+async fn generate_email_token(
+    state: &AppState,
+    email: pii::Email,
+    merchant_id: Option<String>,
+    purpose: EmailTokenPurpose,
+) -> RouterResult<String> {
+    let iat = u64::try_from(date_time::now_unix_timestamp())
+        .map_err(|_| errors::ApiErrorResponse::InternalServerError)?;
+
+    auth::generate_jwt(
+        &EmailToken {
+            email,
+            merchant_id,
+            purpose,
+            iat,
+            exp: iat + u64::from(state.conf.user.email_token_ttl_in_seconds),
+        },
+        state,
+    )
+    .await
+}
+
+#[cfg(feature = "email")]
+async fn decode_email_token(
+    state: &AppState,
+    token: &Secret<String>,
+    purpose: EmailTokenPurpose,
+) -> RouterResult<EmailToken> {
+    let token = auth::decode_jwt::<EmailToken>(token.peek(), state).await?;
+    if token.purpose != purpose {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable_lazy(|| format!("Token was not issued for {purpose:?}"));
+    }
+
+    Ok(token)
+}
+
+async fn find_user_by_email(
+    state: &AppState,
+    email: &pii::Email,
+) -> RouterResult<Option<storage::User>> {
+    match state
+        .store
+        .find_user_by_email(&normalize_email(email))
+        .await
+    {
+        Ok(user) => Ok(Some(user)),
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error.change_context(errors::ApiErrorResponse::InternalServerError)),
+    }
+}
+
+/// Emails are stored in lowercase, so that users are found regardless of the case of the email
+/// they sign in or are invited with.
+pub fn normalize_email(email: &pii::Email) -> pii::Email {
+    pii::Email::from(Secret::<String, pii::EmailStrategy>::new(
+        email.peek().to_lowercase(),
+    ))
+}
+
+async fn find_user_by_id(state: &AppState, user_id: &str) -> RouterResult<storage::User> {
+    state
+        .store
+        .find_user_by_id(user_id)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::UserNotFound)
+}
+
+async fn find_active_user_role(
+    state: &AppState,
+    user_id: &str,
+    merchant_id: &str,
+) -> RouterResult<Option<storage::UserRole>> {
+    match state
+        .store
+        .find_user_role_by_user_id_merchant_id(user_id, merchant_id)
+        .await
+    {
+        Ok(user_role) => {
+            Ok(Some(user_role)
+                .filter(|user_role| user_role.status == api_enums::UserStatus::Active))
+        }
+        Err(error) if error.current_context().is_db_not_found() => Ok(None),
+        Err(error) => Err(error.change_context(errors::ApiErrorResponse::InternalServerError)),
+    }
+}
+
+/// Finds the active role of the user in the merchant account they joined first, which is the
+/// merchant account users sign in to unless they choose another.
+pub async fn find_default_user_role(
+    store: &dyn StorageInterface,
+    user_id: &str,
+) -> RouterResult<Option<storage::UserRole>> {
+    Ok(store
+        .list_user_roles_by_user_id(user_id)
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)?
+        .into_iter()
+        .filter(|user_role| user_role.status == api_enums::UserStatus::Active)
+        .min_by_key(|user_role| user_role.created_at))
+}
+
+async fn insert_user(
+    state: &AppState,
+    email: pii::Email,
+    name: Secret<String>,
+    password: Option<Secret<String>>,
+    is_verified: bool,
+) -> RouterResult<storage::User> {
+    let password = match password {
+        Some(password) => {
+            password::validate_password_policy(&password)?;
+            Some(password::generate_password_hash(&password).await?)
+        }
+        None => None,
+    };
+    let now = date_time::now();
+
+    state
+        .store
+        .insert_user(storage::UserNew {
+            user_id: utils::generate_id(consts::ID_LENGTH, "user"),
+            email: normalize_email(&email),
+            name,
+            password,
+            is_verified,
+            created_at: now,
+            last_modified_at: now,
+        })
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::GenericDuplicateError {
+            message: "User with the given email already exists".to_string(),
+        })
+}
+
+async fn insert_user_role(
+    state: &AppState,
+    user: &storage::User,
+    merchant_id: &str,
+    org_id: &str,
+    role: api_enums::Role,
+    status: api_enums::UserStatus,
+    created_by: &str,
+) -> RouterResult<storage::UserRole> {
+    let now = date_time::now();
+
+    state
+        .store
+        .insert_user_role(storage::UserRoleNew {
+            user_id: user.user_id.clone(),
+            merchant_id: merchant_id.to_string(),
+            org_id: org_id.to_string(),
+            role,
+            status,
+            created_by: created_by.to_string(),
+            last_modified_by: created_by.to_string(),
+            created_at: now,
+            last_modified_at: now,
+        })
+        .await
+        .to_duplicate_response(errors::ApiErrorResponse::GenericDuplicateError {
+            message: "User is already a member of the merchant account".to_string(),
+        })
+}
+
+async fn update_user(
+    state: &AppState,
+    user_id: &str,
+    update: storage::UserUpdate,
+) -> RouterResult<storage::User> {
+    state
+        .store
+        .update_user_by_user_id(user_id, update)
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::UserNotFound)
+}
+
+fn validate_not_self(user_from_token: &UserFromToken, user_id: &str) -> RouterResult<()> {
+    if user_from_token.user_id == user_id {
+        return Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: "Users cannot change or remove their own role".to_string(),
+        }));
+    }
+
+    Ok(())
+}
+
+fn get_user_role_response(
+    user: storage::User,
+    user_role: storage::UserRole,
+) -> user_types::UserRoleResponse {
+    user_types::UserRoleResponse {
+        user_id: user.user_id,
+        email: user.email,
+        name: user.name,
+        merchant_id: user_role.merchant_id,
+        role: user_role.role,
+        status: user_role.status,
+        last_modified_at: user_role.last_modified_at,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::str::FromStr;
+
+    use super::*;
+
+    #[test]
+    fn test_normalize_email() {
+        let email = pii::Email::from_str("Jane.Doe@Example.COM").unwrap();
+
+        assert_eq!(normalize_email(&email).peek(), "jane.doe@example.com");
+    }
+}
@@ -0,0 +1,202 @@
+use std::num::NonZeroU32;
+
+use base64::Engine;
+use error_stack::{report, IntoReport, ResultExt};
+use masking::{PeekInterface, Secret};
+use ring::{pbkdf2, rand::SecureRandom};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResult},
+};
+
+const HASH_SCHEME: &str = "pbkdf2_sha256";
+const HASH_ITERATIONS: u32 = 600_000;
+const SALT_LENGTH: usize = 16;
+const HASH_LENGTH: usize = 32;
+
+const MIN_PASSWORD_LENGTH: usize = 8;
+const MAX_PASSWORD_LENGTH: usize = 70;
+
+/// Hashes a password with PBKDF2-HMAC-SHA256 and a random salt, in the format
+/// `pbkdf2_sha256$<iterations>$<salt>$<hash>`.
+pub async fn generate_password_hash(password: &Secret<String>) -> RouterResult<Secret<String>> {
+    let password = password.clone();
+    spawn_blocking(move || derive_password_hash(&password)).await
+}
+
+/// Checks whether the password matches the hash generated by [`generate_password_hash`].
+pub async fn is_password_valid(
+    password: &Secret<String>,
+    password_hash: &Secret<String>,
+) -> RouterResult<bool> {
+    let password = password.clone();
+    let password_hash = password_hash.clone();
+    spawn_blocking(move || verify_password_hash(&password, &password_hash)).await
+}
+
+/// Verifies the password against a hash that no password matches, which takes as long as
+/// verifying the password of a user. Sign in attempts for users that don't exist, or don't have a
+/// password, can then not be told apart from attempts with an incorrect password by their timing.
+pub async fn verify_dummy_password(password: &Secret<String>) -> RouterResult<()> {
+    let dummy_password_hash = Secret::new(format!(
+        "{HASH_SCHEME}${HASH_ITERATIONS}${}${}",
+        consts::BASE64_ENGINE.encode([0_u8; SALT_LENGTH]),
+        consts::BASE64_ENGINE.encode([0_u8; HASH_LENGTH])
+    ));
+    is_password_valid(password, &dummy_password_hash)
+        .await
+        .map(|_| ())
+}
+
+/// Runs a password hashing operation on the blocking thread pool, as the hundreds of thousands
+/// of PBKDF2 iterations would otherwise stall the worker thread of the runtime.
+async fn spawn_blocking<T, F>(operation: F) -> RouterResult<T>
+where
+    T: Send + 'static,
+    F: FnOnce() -> RouterResult<T> + Send + 'static,
+{
+    tokio::task::spawn_blocking(operation)
+        .await
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Password hashing task failed")?
+}
+
+fn derive_password_hash(password: &Secret<String>) -> RouterResult<Secret<String>> {
+    let iterations = NonZeroU32::new(HASH_ITERATIONS)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()?;
+
+    let mut salt = [0_u8; SALT_LENGTH];
+    ring::rand::SystemRandom::new()
+        .fill(&mut salt)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to generate password salt")?;
+
+    let mut hash = [0_u8; HASH_LENGTH];
+    pbkdf2::derive(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        password.peek().as_bytes(),
+        &mut hash,
+    );
+
+    Ok(Secret::new(format!(
+        "{HASH_SCHEME}${HASH_ITERATIONS}${}${}",
+        consts::BASE64_ENGINE.encode(salt),
+        consts::BASE64_ENGINE.encode(hash)
+    )))
+}
+
+fn verify_password_hash(
+    password: &Secret<String>,
+    password_hash: &Secret<String>,
+) -> RouterResult<bool> {
+    let parts = password_hash.peek().split('$').collect::<Vec<_>>();
+    let [scheme, iterations, salt, hash] = parts.as_slice() else {
+        return Err(report!(errors::ApiErrorResponse::InternalServerError))
+            .attach_printable("Stored password hash is malformed");
+    };
+
+    if *scheme != HASH_SCHEME {
+        return Err(report!(errors::ApiErrorResponse::InternalServerError))
+            .attach_printable_lazy(|| format!("Unsupported password hash scheme {scheme}"));
+    }
+
+    let iterations = iterations
+        .parse::<u32>()
+        .ok()
+        .and_then(NonZeroU32::new)
+        .ok_or(errors::ApiErrorResponse::InternalServerError)
+        .into_report()
+        .attach_printable("Stored password hash has invalid iterations")?;
+    let salt = consts::BASE64_ENGINE
+        .decode(salt)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Stored password hash has invalid salt")?;
+    let hash = consts::BASE64_ENGINE
+        .decode(hash)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Stored password hash has invalid hash")?;
+
+    Ok(pbkdf2::verify(
+        pbkdf2::PBKDF2_HMAC_SHA256,
+        iterations,
+        &salt,
+        password.peek().as_bytes(),
+        &hash,
+    )
+    .is_ok())
+}
+
+/// Requires passwords to be 8 to 70 characters long and to contain a lowercase letter, an
+/// uppercase letter, a digit and a character that is neither.
+pub fn validate_password_policy(password: &Secret<String>) -> RouterResult<()> {
+    let password = password.peek();
+    let length = password.chars().count();
+
+    let is_valid = (MIN_PASSWORD_LENGTH..=MAX_PASSWORD_LENGTH).contains(&length)
+        && password.chars().any(|c| c.is_lowercase())
+        && password.chars().any(|c| c.is_uppercase())
+        && password.chars().any(|c| c.is_ascii_digit())
+        && password.chars().any(|c| !c.is_alphanumeric());
+
+    if is_valid {
+        Ok(())
+    } else {
+        Err(report!(errors::ApiErrorResponse::InvalidRequestData {
+            message: format!(
+                "password must be {MIN_PASSWORD_LENGTH} to {MAX_PASSWORD_LENGTH} characters long \
+                 and contain a lowercase letter, an uppercase letter, a digit and a special \
+                 character"
+            ),
+        }))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    #[tokio::test]
+    async fn test_password_hashing_and_verification() {
+        let password = Secret::new("Sup3r$ecret".to_string());
+        let password_hash = generate_password_hash(&password).await.unwrap();
+
+        assert!(password_hash.peek().starts_with("pbkdf2_sha256$600000$"));
+        assert!(is_password_valid(&password, &password_hash).await.unwrap());
+        assert!(
+            !is_password_valid(&Secret::new("Sup3r$ecreT".to_string()), &password_hash)
+                .await
+                .unwrap()
+        );
+        assert_ne!(
+            generate_password_hash(&password).await.unwrap().peek(),
+            password_hash.peek()
+        );
+    }
+
+    #[tokio::test]
+    async fn test_dummy_password_verification() {
+        assert!(
+            verify_dummy_password(&Secret::new("Sup3r$ecret".to_string()))
+                .await
+                .is_ok()
+        );
+    }
+
+    #[test]
+    fn test_password_policy() {
+        assert!(validate_password_policy(&Secret::new("Sup3r$ecret".to_string())).is_ok());
+        assert!(validate_password_policy(&Secret::new("Sh0rt$".to_string())).is_err());
+        assert!(validate_password_policy(&Secret::new("nouppercase1$".to_string())).is_err());
+        assert!(validate_password_policy(&Secret::new("NoDigitsHere$".to_string())).is_err());
+        assert!(validate_password_policy(&Secret::new("NoSpecial123".to_string())).is_err());
+    }
+}
@@ -3,8 +3,7 @@ use actix_web::web;
 use api_models::verifications::{self, ApplepayMerchantResponse};
 use common_utils::{errors::CustomResult, ext_traits::Encode};
 use error_stack::ResultExt;
-#[cfg(feature = "kms")]
-use external_services::kms;
+use external_services::secrets_management::{self, SecretsManagementConfig};
 
 use crate::{
     core::errors::{self, api_error_response},
@@ -19,7 +18,7 @@ pub async fn verify_merchant_creds_for_applepay(
     state: AppState,
     _req: &actix_web::HttpRequest,
     body: web::Json<verifications::ApplepayMerchantVerificationRequest>,
-    kms_config: &kms::KmsConfig,
+    secrets_management_config: &SecretsManagementConfig,
     merchant_id: String,
 ) -> CustomResult<
     services::ApplicationResponse<ApplepayMerchantResponse>,
@@ -33,20 +32,19 @@ pub async fn verify_merchant_creds_for_applepay(
     let encrypted_key = &state.conf.applepay_merchant_configs.merchant_cert_key;
     let applepay_endpoint = &state.conf.applepay_merchant_configs.applepay_endpoint;
 
-    let applepay_internal_merchant_identifier = kms::get_kms_client(kms_config)
-        .await
+    let secrets_manager = secrets_management::get_secrets_manager(secrets_management_config).await;
+
+    let applepay_internal_merchant_identifier = secrets_manager
         .decrypt(encrypted_merchant_identifier)
         .await
         .change_context(api_error_response::ApiErrorResponse::InternalServerError)?;
 
-    let cert_data = kms::get_kms_client(kms_config)
-        .await
+    let cert_data = secrets_manager
         .decrypt(encrypted_cert)
         .await
         .change_context(api_error_response::ApiErrorResponse::InternalServerError)?;
 
-    let key_data = kms::get_kms_client(kms_config)
-        .await
+    let key_data = secrets_manager
         .decrypt(encrypted_key)
         .await
         .change_context(api_error_response::ApiErrorResponse::InternalServerError)?;
@@ -121,7 +119,7 @@ pub async fn get_verified_apple_domains_with_mid_mca_id(
 > {
     let db = state.store.as_ref();
     let key_store = db
-        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key().to_vec().into())
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &db.get_master_key_ring())
         .await
         .change_context(api_error_response::ApiErrorResponse::MerchantAccountNotFound)?;
 
@@ -17,10 +17,7 @@ pub async fn check_existence_and_add_domain_to_db(
 ) -> CustomResult<Vec<String>, errors::ApiErrorResponse> {
     let key_store = state
         .store
-        .get_merchant_key_store_by_merchant_id(
-            &merchant_id,
-            &state.store.get_master_key().to_vec().into(),
-        )
+        .get_merchant_key_store_by_merchant_id(&merchant_id, &state.store.get_master_key_ring())
         .await
         .to_not_found_response(errors::ApiErrorResponse::InternalServerError)?;
 
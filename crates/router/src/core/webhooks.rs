@@ -232,19 +232,31 @@ pub async fn refunds_incoming_webhook_flow<W: types::OutgoingWebhookType>(
                 .attach_printable("failed refund status mapping from event type")?,
             updated_by: merchant_account.storage_scheme.to_string(),
         };
-        db.update_refund(
-            refund.to_owned(),
-            refund_update,
-            merchant_account.storage_scheme,
-        )
-        .await
-        .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)
-        .attach_printable_lazy(|| {
-            format!(
-                "Failed while updating refund: refund_id: {}",
-                refund_id.to_owned()
+        let updated_refund = db
+            .update_refund(
+                refund.to_owned(),
+                refund_update,
+                merchant_account.storage_scheme,
             )
-        })?
+            .await
+            .to_not_found_response(errors::ApiErrorResponse::WebhookResourceNotFound)
+            .attach_printable_lazy(|| {
+                format!(
+                    "Failed while updating refund: refund_id: {}",
+                    refund_id.to_owned()
+                )
+            })?;
+
+        #[cfg(feature = "email")]
+        super::transactional_emails::trigger_refund_processed_email(
+            &state,
+            &merchant_account,
+            refund.refund_status,
+            &updated_refund,
+        )
+        .await;
+
+        updated_refund
     } else {
         refunds::refund_retrieve_core(
             state.clone(),
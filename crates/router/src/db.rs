@@ -1,5 +1,7 @@
 pub mod address;
+pub mod analytics;
 pub mod api_keys;
+pub mod audit_event;
 pub mod business_profile;
 pub mod cache;
 pub mod capture;
@@ -8,12 +10,15 @@ pub mod configs;
 pub mod connector_response;
 pub mod customers;
 pub mod dispute;
+pub mod encrypted_data;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
 pub mod fraud_check;
+pub mod invoice;
 pub mod locker_mock_up;
 pub mod mandate;
+pub mod mandate_usage;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
@@ -24,6 +29,9 @@ pub mod payout_attempt;
 pub mod payouts;
 pub mod refund;
 pub mod reverse_lookup;
+pub mod subscription;
+pub mod user;
+pub mod user_role;
 
 use data_models::payments::{
     payment_attempt::PaymentAttemptInterface, payment_intent::PaymentIntentInterface,
@@ -32,7 +40,7 @@ use masking::PeekInterface;
 use redis_interface::errors::RedisError;
 use storage_impl::{redis::kv_store::RedisConnInterface, MockDb};
 
-use crate::{errors::CustomResult, services::Store};
+use crate::{errors::CustomResult, services::Store, types::domain::types::KeyRing};
 
 #[derive(PartialEq, Eq)]
 pub enum StorageImpl {
@@ -47,18 +55,22 @@ pub trait StorageInterface:
     + Sync
     + dyn_clone::DynClone
     + address::AddressInterface
+    + analytics::AnalyticsInterface
     + api_keys::ApiKeyInterface
     + configs::ConfigInterface
     + capture::CaptureInterface
     + connector_response::ConnectorResponseInterface
     + customers::CustomerInterface
     + dispute::DisputeInterface
+    + encrypted_data::EncryptedDataInterface
     + ephemeral_key::EphemeralKeyInterface
     + events::EventInterface
     + file::FileMetadataInterface
     + fraud_check::FraudCheckInterface
+    + invoice::InvoiceInterface
     + locker_mock_up::LockerMockUpInterface
     + mandate::MandateInterface
+    + mandate_usage::MandateUsageInterface
     + merchant_account::MerchantAccountInterface
     + merchant_connector_account::ConnectorAccessToken
     + merchant_connector_account::MerchantConnectorAccountInterface
@@ -70,6 +82,7 @@ pub trait StorageInterface:
     + payouts::PayoutsInterface
     + refund::RefundInterface
     + reverse_lookup::ReverseLookupInterface
+    + subscription::SubscriptionInterface
     + cards_info::CardsInfoInterface
     + merchant_key_store::MerchantKeyStoreInterface
     + MasterKeyInterface
@@ -77,6 +90,9 @@ pub trait StorageInterface:
     + RedisConnInterface
     + business_profile::BusinessProfileInterface
     + organization::OrganizationInterface
+    + user::UserInterface
+    + user_role::UserRoleInterface
+    + audit_event::AuditEventInterface
     + 'static
 {
     fn get_scheduler_db(&self) -> Box<dyn scheduler::SchedulerInterface>;
@@ -84,12 +100,29 @@ pub trait StorageInterface:
 
 pub trait MasterKeyInterface {
     fn get_master_key(&self) -> &[u8];
+
+    /// Returns the key ring that the keys of merchants are encrypted with, holding the master key
+    /// along with the other configured versions of the master key.
+    fn get_master_key_ring(&self) -> KeyRing;
 }
 
 impl MasterKeyInterface for Store {
     fn get_master_key(&self) -> &[u8] {
         self.master_key().peek()
     }
+
+    fn get_master_key_ring(&self) -> KeyRing {
+        let master_key_versions = self.master_key_versions();
+        master_key_versions.other_keys.iter().fold(
+            KeyRing::with_active_key(
+                master_key_versions.version,
+                self.get_master_key().to_vec().into(),
+            ),
+            |key_ring, (version, key)| {
+                key_ring.with_retired_key(*version, key.peek().to_vec().into())
+            },
+        )
+    }
 }
 
 /// Default dummy key for MockDb
@@ -100,6 +133,10 @@ impl MasterKeyInterface for MockDb {
             25, 26, 27, 28, 29, 30, 31, 32,
         ]
     }
+
+    fn get_master_key_ring(&self) -> KeyRing {
+        KeyRing::new(self.get_master_key().to_vec().into())
+    }
 }
 
 #[async_trait::async_trait]
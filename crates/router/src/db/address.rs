@@ -107,7 +107,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -134,7 +134,7 @@ mod storage {
             .into_report()
             .async_and_then(|address| async {
                 address
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -155,7 +155,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -181,7 +181,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -206,7 +206,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -229,7 +229,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -258,7 +258,7 @@ mod storage {
                 for address in addresses.into_iter() {
                     output.push(
                         address
-                            .convert(key_store.key.get_inner())
+                            .convert(&key_store.key_ring())
                             .await
                             .change_context(errors::StorageError::DecryptionError)?,
                     )
@@ -277,7 +277,7 @@ mod storage {
     use error_stack::{IntoReport, ResultExt};
     use redis_interface::HsetnxReply;
     use router_env::{instrument, tracing};
-    use storage_impl::redis::kv_store::{kv_wrapper, KvOperation};
+    use storage_impl::redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op};
 
     use super::AddressInterface;
     use crate::{
@@ -307,7 +307,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -336,7 +336,7 @@ mod storage {
             };
             let address = match storage_scheme {
                 MerchantStorageScheme::PostgresOnly => database_call().await,
-                MerchantStorageScheme::RedisKv => {
+                MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                     let key = format!("mid_{}_pid_{}", merchant_id, payment_id);
                     let field = format!("add_{}", address_id);
                     db_utils::try_redis_get_else_try_database_get(
@@ -355,7 +355,7 @@ mod storage {
                 }
             }?;
             address
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError)
         }
@@ -374,7 +374,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -393,6 +393,14 @@ mod storage {
             let address = Conversion::convert(this)
                 .await
                 .change_context(errors::StorageError::EncryptionError)?;
+            let key = format!("mid_{}_pid_{}", address.merchant_id.clone(), payment_id);
+            let field = format!("add_{}", address.address_id);
+            let storage_scheme = decide_storage_scheme(
+                self,
+                storage_scheme,
+                Op::Update(&key, &field, &address.updated_by),
+            )
+            .await;
             match storage_scheme {
                 MerchantStorageScheme::PostgresOnly => {
                     address
@@ -402,15 +410,13 @@ mod storage {
                         .into_report()
                         .async_and_then(|address| async {
                             address
-                                .convert(key_store.key.get_inner())
+                                .convert(&key_store.key_ring())
                                 .await
                                 .change_context(errors::StorageError::DecryptionError)
                         })
                         .await
                 }
-                MerchantStorageScheme::RedisKv => {
-                    let key = format!("mid_{}_pid_{}", address.merchant_id.clone(), payment_id);
-                    let field = format!("add_{}", address.address_id);
+                MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                     let updated_address = AddressUpdateInternal::from(address_update.clone())
                         .create_address(address.clone());
                     let redis_value = serde_json::to_string(&updated_address)
@@ -442,7 +448,7 @@ mod storage {
                     .change_context(errors::StorageError::KVError)?;
 
                     updated_address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 }
@@ -462,6 +468,9 @@ mod storage {
                 .await
                 .change_context(errors::StorageError::EncryptionError)?;
             let merchant_id = address_new.merchant_id.clone();
+            let key = format!("mid_{}_pid_{}", merchant_id, payment_id);
+            let storage_scheme =
+                decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
             match storage_scheme {
                 MerchantStorageScheme::PostgresOnly => {
                     let conn = connection::pg_connection_write(self).await?;
@@ -472,14 +481,13 @@ mod storage {
                         .into_report()
                         .async_and_then(|address| async {
                             address
-                                .convert(key_store.key.get_inner())
+                                .convert(&key_store.key_ring())
                                 .await
                                 .change_context(errors::StorageError::DecryptionError)
                         })
                         .await
                 }
-                MerchantStorageScheme::RedisKv => {
-                    let key = format!("mid_{}_pid_{}", merchant_id, payment_id);
+                MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                     let field = format!("add_{}", &address_new.address_id);
                     let created_address = diesel_models::Address {
                         id: Some(0i32),
@@ -528,7 +536,7 @@ mod storage {
                         })
                         .into_report(),
                         Ok(HsetnxReply::KeySet) => Ok(created_address
-                            .convert(key_store.key.get_inner())
+                            .convert(&key_store.key_ring())
                             .await
                             .change_context(errors::StorageError::DecryptionError)?),
                         Err(er) => Err(er).change_context(errors::StorageError::KVError),
@@ -553,7 +561,7 @@ mod storage {
                 .into_report()
                 .async_and_then(|address| async {
                     address
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -582,7 +590,7 @@ mod storage {
                 for address in addresses.into_iter() {
                     output.push(
                         address
-                            .convert(key_store.key.get_inner())
+                            .convert(&key_store.key_ring())
                             .await
                             .change_context(errors::StorageError::DecryptionError)?,
                     )
@@ -610,7 +618,7 @@ impl AddressInterface for MockDb {
         {
             Some(address) => address
                 .clone()
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError),
             None => {
@@ -638,7 +646,7 @@ impl AddressInterface for MockDb {
         {
             Some(address) => address
                 .clone()
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError),
             None => {
@@ -669,7 +677,7 @@ impl AddressInterface for MockDb {
                 address_updated
             }) {
             Some(address_updated) => address_updated
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError),
             None => Err(errors::StorageError::ValueNotFound(
@@ -700,7 +708,7 @@ impl AddressInterface for MockDb {
                 address_updated
             }) {
             Some(address_updated) => address_updated
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError),
             None => Err(errors::StorageError::ValueNotFound(
@@ -726,7 +734,7 @@ impl AddressInterface for MockDb {
         addresses.push(address.clone());
 
         address
-            .convert(key_store.key.get_inner())
+            .convert(&key_store.key_ring())
             .await
             .change_context(errors::StorageError::DecryptionError)
     }
@@ -745,7 +753,7 @@ impl AddressInterface for MockDb {
         addresses.push(address.clone());
 
         address
-            .convert(key_store.key.get_inner())
+            .convert(&key_store.key_ring())
             .await
             .change_context(errors::StorageError::DecryptionError)
     }
@@ -773,7 +781,7 @@ impl AddressInterface for MockDb {
             }) {
             Some(address) => {
                 let address: domain::Address = address
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)?;
                 Ok(vec![address])
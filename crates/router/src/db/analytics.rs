@@ -0,0 +1,47 @@
+use error_stack::IntoReport;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::analytics::{PaymentMetricsQuery, PaymentMetricsRow},
+};
+
+/// The backend payment analytics are computed by. Postgres computes them from `payment_attempt`
+/// on the replica, a columnar store can implement this to compute them from change data events.
+#[async_trait::async_trait]
+pub trait AnalyticsInterface {
+    async fn get_payment_metrics(
+        &self,
+        query: &PaymentMetricsQuery<'_>,
+        consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<PaymentMetricsRow>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl AnalyticsInterface for Store {
+    async fn get_payment_metrics(
+        &self,
+        query: &PaymentMetricsQuery<'_>,
+        consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<PaymentMetricsRow>, errors::StorageError> {
+        let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
+        query
+            .load_from_postgres(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl AnalyticsInterface for MockDb {
+    async fn get_payment_metrics(
+        &self,
+        _query: &PaymentMetricsQuery<'_>,
+        _consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<PaymentMetricsRow>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
@@ -234,6 +234,8 @@ impl ApiKeyInterface for MockDb {
             created_at: api_key.created_at,
             expires_at: api_key.expires_at,
             last_used: api_key.last_used,
+            scopes: api_key.scopes,
+            allowed_ips: api_key.allowed_ips,
         };
         locked_api_keys.push(stored_key.clone());
 
@@ -259,6 +261,8 @@ impl ApiKeyInterface for MockDb {
                 description,
                 expires_at,
                 last_used,
+                scopes,
+                allowed_ips,
             } => {
                 if let Some(name) = name {
                     key_to_update.name = name;
@@ -273,6 +277,12 @@ impl ApiKeyInterface for MockDb {
                 if last_used.is_some() {
                     key_to_update.last_used = last_used
                 }
+                if scopes.is_some() {
+                    key_to_update.scopes = scopes;
+                }
+                if let Some(allowed_ips) = allowed_ips {
+                    key_to_update.allowed_ips = allowed_ips;
+                }
             }
             storage::ApiKeyUpdate::LastUsedUpdate { last_used } => {
                 key_to_update.last_used = Some(last_used);
@@ -406,6 +416,8 @@ mod tests {
                 created_at: datetime!(2023-02-01 0:00),
                 expires_at: Some(datetime!(2023-03-01 0:00)),
                 last_used: None,
+                scopes: None,
+                allowed_ips: None,
             })
             .await
             .unwrap();
@@ -421,6 +433,8 @@ mod tests {
                 created_at: datetime!(2023-03-01 0:00),
                 expires_at: None,
                 last_used: None,
+                scopes: None,
+                allowed_ips: None,
             })
             .await
             .unwrap();
@@ -498,6 +512,8 @@ mod tests {
             created_at: datetime!(2023-06-01 0:00),
             expires_at: None,
             last_used: None,
+            scopes: None,
+            allowed_ips: None,
         };
 
         let api = db.insert_api_key(api).await.unwrap();
@@ -0,0 +1,117 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage::{self, AuditEventDbExt},
+};
+
+/// Audit events are only ever inserted and listed, never updated or deleted.
+#[async_trait::async_trait]
+pub trait AuditEventInterface {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError>;
+
+    async fn list_audit_events(
+        &self,
+        constraints: api_models::audit_events::AuditEventListConstraints,
+        consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl AuditEventInterface for Store {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        audit_event
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_audit_events(
+        &self,
+        constraints: api_models::audit_events::AuditEventListConstraints,
+        consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError> {
+        let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
+        storage::AuditEvent::filter_by_constraints(&conn, constraints)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl AuditEventInterface for MockDb {
+    async fn insert_audit_event(
+        &self,
+        audit_event: storage::AuditEventNew,
+    ) -> CustomResult<storage::AuditEvent, errors::StorageError> {
+        let mut audit_events = self.audit_events.lock().await;
+        let audit_event = storage::AuditEvent {
+            id: i32::try_from(audit_events.len())
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            audit_event_id: audit_event.audit_event_id,
+            merchant_id: audit_event.merchant_id,
+            resource_type: audit_event.resource_type,
+            resource_id: audit_event.resource_id,
+            action: audit_event.action,
+            auth_type: audit_event.auth_type,
+            actor_id: audit_event.actor_id,
+            request_id: audit_event.request_id,
+            diff: audit_event.diff,
+            created_at: audit_event.created_at,
+        };
+        audit_events.push(audit_event.clone());
+        Ok(audit_event)
+    }
+
+    async fn list_audit_events(
+        &self,
+        constraints: api_models::audit_events::AuditEventListConstraints,
+        _consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<storage::AuditEvent>, errors::StorageError> {
+        let audit_events = self.audit_events.lock().await;
+        let limit = constraints
+            .limit
+            .and_then(|limit| usize::try_from(limit).ok())
+            .unwrap_or(usize::MAX);
+
+        Ok(audit_events
+            .iter()
+            .rev()
+            .filter(|event| {
+                constraints
+                    .merchant_id
+                    .as_ref()
+                    .map_or(true, |merchant_id| {
+                        event.merchant_id.as_ref() == Some(merchant_id)
+                    })
+                    && constraints
+                        .resource_type
+                        .map_or(true, |resource_type| event.resource_type == resource_type)
+                    && constraints
+                        .resource_id
+                        .as_ref()
+                        .map_or(true, |resource_id| &event.resource_id == resource_id)
+                    && constraints
+                        .created_lt
+                        .map_or(true, |created_lt| event.created_at < created_lt)
+                    && constraints
+                        .created_gte
+                        .map_or(true, |created_gte| event.created_at >= created_gte)
+            })
+            .take(limit)
+            .cloned()
+            .collect())
+    }
+}
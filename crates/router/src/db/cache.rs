@@ -63,12 +63,21 @@ where
     F: FnOnce() -> Fut + Send,
     Fut: futures::Future<Output = CustomResult<T, errors::StorageError>> + Send,
 {
-    let cache_val = cache.get_val::<T>(key);
+    // The in-memory caches are shared by all stores in the process, hence entries are keyed by the
+    // prefixed Redis key to keep the entries of different tenants apart.
+    let cache_key = store
+        .get_redis_conn()
+        .change_context(errors::StorageError::RedisError(
+            RedisError::RedisConnectionError.into(),
+        ))
+        .attach_printable("Failed to get redis connection")?
+        .add_prefix(key);
+    let cache_val = cache.get_val::<T>(&cache_key);
     if let Some(val) = cache_val {
         Ok(val)
     } else {
         let val = get_or_populate_redis(store, key, fun).await?;
-        cache.push(key.to_string(), val.clone()).await;
+        cache.push(cache_key, val.clone()).await;
         Ok(val)
     }
 }
@@ -84,7 +93,6 @@ where
     Fut: futures::Future<Output = CustomResult<T, errors::StorageError>> + Send,
 {
     let data = fun().await?;
-    in_memory.async_map(|cache| cache.invalidate(key)).await;
 
     let redis_conn = store
         .get_redis_conn()
@@ -93,6 +101,11 @@ where
         ))
         .attach_printable("Failed to get redis connection")?;
 
+    let cache_key = redis_conn.add_prefix(key);
+    in_memory
+        .async_map(|cache| cache.invalidate(&cache_key))
+        .await;
+
     redis_conn
         .delete_key(key)
         .await
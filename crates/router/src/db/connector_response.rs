@@ -101,7 +101,7 @@ mod storage {
     use error_stack::{IntoReport, ResultExt};
     use redis_interface::HsetnxReply;
     use router_env::{instrument, tracing};
-    use storage_impl::redis::kv_store::{kv_wrapper, KvOperation};
+    use storage_impl::redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op};
 
     use super::Store;
     use crate::{
@@ -120,6 +120,11 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_type::ConnectorResponse, errors::StorageError> {
             let conn = connection::pg_connection_write(self).await?;
+            let merchant_id = &connector_response.merchant_id;
+            let payment_id = &connector_response.payment_id;
+            let key = format!("mid_{merchant_id}_pid_{payment_id}");
+            let storage_scheme =
+                decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
 
             match storage_scheme {
                 storage_enums::MerchantStorageScheme::PostgresOnly => connector_response
@@ -127,12 +132,9 @@ mod storage {
                     .await
                     .map_err(Into::into)
                     .into_report(),
-                storage_enums::MerchantStorageScheme::RedisKv => {
-                    let merchant_id = &connector_response.merchant_id;
-                    let payment_id = &connector_response.payment_id;
+                storage_enums::MerchantStorageScheme::RedisKv
+                | storage_enums::MerchantStorageScheme::RedisKvDraining => {
                     let attempt_id = &connector_response.attempt_id;
-
-                    let key = format!("mid_{merchant_id}_pid_{payment_id}");
                     let field = format!("connector_resp_{merchant_id}_{payment_id}_{attempt_id}");
 
                     let created_connector_resp = storage_type::ConnectorResponse {
@@ -202,7 +204,8 @@ mod storage {
             };
             match storage_scheme {
                 storage_enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                storage_enums::MerchantStorageScheme::RedisKv => {
+                storage_enums::MerchantStorageScheme::RedisKv
+                | storage_enums::MerchantStorageScheme::RedisKvDraining => {
                     let key = format!("mid_{merchant_id}_pid_{payment_id}");
                     let field = format!("connector_resp_{merchant_id}_{payment_id}_{attempt_id}");
 
@@ -230,14 +233,28 @@ mod storage {
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_type::ConnectorResponse, errors::StorageError> {
             let conn = connection::pg_connection_write(self).await?;
+            let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+            let storage_scheme = decide_storage_scheme(
+                self,
+                storage_scheme,
+                Op::Update(
+                    &key,
+                    &format!(
+                        "connector_resp_{}_{}_{}",
+                        this.merchant_id, this.payment_id, this.attempt_id
+                    ),
+                    &this.updated_by,
+                ),
+            )
+            .await;
             match storage_scheme {
                 storage_enums::MerchantStorageScheme::PostgresOnly => this
                     .update(&conn, connector_response_update)
                     .await
                     .map_err(Into::into)
                     .into_report(),
-                storage_enums::MerchantStorageScheme::RedisKv => {
-                    let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+                storage_enums::MerchantStorageScheme::RedisKv
+                | storage_enums::MerchantStorageScheme::RedisKvDraining => {
                     let updated_connector_response = connector_response_update
                         .clone()
                         .apply_changeset(this.clone());
@@ -85,7 +85,7 @@ impl CustomerInterface for Store {
             .map_err(Into::into)
             .into_report()?
             .async_map(|c| async {
-                c.convert(key_store.key.get_inner())
+                c.convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -122,7 +122,7 @@ impl CustomerInterface for Store {
         .map_err(Into::into)
         .into_report()
         .async_and_then(|c| async {
-            c.convert(key_store.key.get_inner())
+            c.convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError)
         })
@@ -142,7 +142,7 @@ impl CustomerInterface for Store {
                 .map_err(Into::into)
                 .into_report()
                 .async_and_then(|c| async {
-                    c.convert(key_store.key.get_inner())
+                    c.convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -170,7 +170,7 @@ impl CustomerInterface for Store {
         let customers = try_join_all(encrypted_customers.into_iter().map(
             |encrypted_customer| async {
                 encrypted_customer
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             },
@@ -195,7 +195,7 @@ impl CustomerInterface for Store {
             .map_err(Into::into)
             .into_report()
             .async_and_then(|c| async {
-                c.convert(key_store.key.get_inner())
+                c.convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -233,7 +233,7 @@ impl CustomerInterface for MockDb {
             .cloned();
         customer
             .async_map(|c| async {
-                c.convert(key_store.key.get_inner())
+                c.convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -255,7 +255,7 @@ impl CustomerInterface for MockDb {
                 .map(|customer| async {
                     customer
                         .to_owned()
-                        .convert(key_store.key.get_inner())
+                        .convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 }),
@@ -302,7 +302,7 @@ impl CustomerInterface for MockDb {
         customers.push(customer.clone());
 
         customer
-            .convert(key_store.key.get_inner())
+            .convert(&key_store.key_ring())
             .await
             .change_context(errors::StorageError::DecryptionError)
     }
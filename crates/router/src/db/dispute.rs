@@ -31,6 +31,7 @@ pub trait DisputeInterface {
         &self,
         merchant_id: &str,
         dispute_constraints: api_models::disputes::DisputeListConstraints,
+        consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError>;
 
     async fn find_disputes_by_merchant_id_payment_id(
@@ -94,8 +95,9 @@ impl DisputeInterface for Store {
         &self,
         merchant_id: &str,
         dispute_constraints: api_models::disputes::DisputeListConstraints,
+        consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError> {
-        let conn = connection::pg_connection_read(self).await?;
+        let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
         storage::Dispute::filter_by_constraints(&conn, merchant_id, dispute_constraints)
             .await
             .map_err(Into::into)
@@ -217,6 +219,7 @@ impl DisputeInterface for MockDb {
         &self,
         merchant_id: &str,
         dispute_constraints: api_models::disputes::DisputeListConstraints,
+        _consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<Vec<storage::Dispute>, errors::StorageError> {
         let locked_disputes = self.disputes.lock().await;
 
@@ -566,6 +569,7 @@ mod tests {
                         received_time_gte: None,
                         profile_id: None,
                     },
+                    storage_impl::ReadConsistency::Strong,
                 )
                 .await
                 .unwrap();
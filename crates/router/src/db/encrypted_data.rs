@@ -0,0 +1,660 @@
+use api_models::admin::EncryptedEntity;
+use diesel_models::errors::DatabaseError;
+use error_stack::{IntoReport, ResultExt};
+#[cfg(feature = "accounts_cache")]
+use storage_impl::redis::cache::CacheKind;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::{domain::types::KeyRing, storage},
+};
+
+/// Access to the raw, still encrypted records of the entities holding data encrypted with the
+/// data encryption key of a merchant, and of the merchant key stores holding the keys encrypted
+/// with the master key, for re-encrypting them after a key was rotated.
+#[async_trait::async_trait]
+pub trait EncryptedDataInterface {
+    /// Lists the records of the entity of the merchant whose identifiers follow `after_id`, in the
+    /// order of their identifiers. The records are read from the primary database, as they are
+    /// re-encrypted right after being read.
+    async fn find_encrypted_data_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        entity: EncryptedEntity,
+        after_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::EncryptedRecord>, errors::StorageError>;
+
+    /// Re-encrypts the encrypted fields of the record with the active key of the key ring,
+    /// provided that they have not been updated since the record was fetched. Fails with a not
+    /// found error otherwise, in which case the record should be fetched again.
+    async fn update_encrypted_data(
+        &self,
+        record: storage::EncryptedRecord,
+        key: &KeyRing,
+    ) -> CustomResult<storage::EncryptedRecord, errors::StorageError>;
+
+    async fn find_merchant_key_stores_after_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::MerchantKeyStore>, errors::StorageError>;
+
+    /// Replaces the encrypted keys of the merchant key store, provided that its key has not been
+    /// rotated since it was fetched.
+    async fn update_encrypted_merchant_key_store(
+        &self,
+        merchant_key_store: storage::MerchantKeyStore,
+    ) -> CustomResult<storage::MerchantKeyStore, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl EncryptedDataInterface for Store {
+    async fn find_encrypted_data_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        entity: EncryptedEntity,
+        after_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::EncryptedRecord>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        match entity {
+            // The merchant account is the only record of the entity
+            EncryptedEntity::MerchantAccount if !after_id.is_empty() => Ok(Vec::new()),
+            EncryptedEntity::MerchantAccount => {
+                storage::MerchantAccount::find_by_merchant_id(&conn, merchant_id)
+                    .await
+                    .map(|merchant_account| {
+                        vec![storage::EncryptedRecord::MerchantAccount(merchant_account)]
+                    })
+            }
+            EncryptedEntity::Customer => storage::Customer::list_by_merchant_id_after_customer_id(
+                &conn,
+                merchant_id,
+                after_id,
+                limit,
+            )
+            .await
+            .map(|records| {
+                records
+                    .into_iter()
+                    .map(storage::EncryptedRecord::Customer)
+                    .collect()
+            }),
+            EncryptedEntity::Address => storage::Address::list_by_merchant_id_after_address_id(
+                &conn,
+                merchant_id,
+                after_id,
+                limit,
+            )
+            .await
+            .map(|records| {
+                records
+                    .into_iter()
+                    .map(storage::EncryptedRecord::Address)
+                    .collect()
+            }),
+            EncryptedEntity::MerchantConnectorAccount => {
+                storage::MerchantConnectorAccount::list_by_merchant_id_after_merchant_connector_id(
+                    &conn,
+                    merchant_id,
+                    after_id,
+                    limit,
+                )
+                .await
+                .map(|records| {
+                    records
+                        .into_iter()
+                        .map(storage::EncryptedRecord::MerchantConnectorAccount)
+                        .collect()
+                })
+            }
+            EncryptedEntity::PaymentMethod => {
+                storage::PaymentMethod::list_by_merchant_id_after_payment_method_id(
+                    &conn,
+                    merchant_id,
+                    after_id,
+                    limit,
+                )
+                .await
+                .map(|records| {
+                    records
+                        .into_iter()
+                        .map(storage::EncryptedRecord::PaymentMethod)
+                        .collect()
+                })
+            }
+        }
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_encrypted_data(
+        &self,
+        record: storage::EncryptedRecord,
+        key: &KeyRing,
+    ) -> CustomResult<storage::EncryptedRecord, errors::StorageError> {
+        let rewrapped = record
+            .clone()
+            .rewrap(key)
+            .change_context(errors::StorageError::EncryptionError)?;
+        let conn = connection::pg_connection_write(self).await?;
+
+        // Each update only applies if the encrypted fields still hold the values of `record`
+        let updated = match (record, rewrapped) {
+            (
+                storage::EncryptedRecord::MerchantAccount(merchant_account),
+                storage::EncryptedRecord::MerchantAccount(rewrapped),
+            ) => merchant_account
+                .update_encrypted_fields_if_unchanged(
+                    &conn,
+                    storage::MerchantAccountUpdateInternal {
+                        merchant_name: rewrapped.merchant_name,
+                        merchant_details: rewrapped.merchant_details,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map(storage::EncryptedRecord::MerchantAccount),
+            (
+                storage::EncryptedRecord::Customer(customer),
+                storage::EncryptedRecord::Customer(rewrapped),
+            ) => customer
+                .update_encrypted_fields_if_unchanged(
+                    &conn,
+                    storage::CustomerUpdateInternal {
+                        name: rewrapped.name,
+                        email: rewrapped.email,
+                        phone: rewrapped.phone,
+                        ..Default::default()
+                    },
+                )
+                .await
+                .map(storage::EncryptedRecord::Customer),
+            (
+                storage::EncryptedRecord::Address(address),
+                storage::EncryptedRecord::Address(rewrapped),
+            ) => {
+                let address_update = storage::AddressUpdateInternal {
+                    city: None,
+                    country: None,
+                    line1: rewrapped.line1,
+                    line2: rewrapped.line2,
+                    line3: rewrapped.line3,
+                    state: rewrapped.state,
+                    zip: rewrapped.zip,
+                    first_name: rewrapped.first_name,
+                    last_name: rewrapped.last_name,
+                    phone_number: rewrapped.phone_number,
+                    country_code: None,
+                    modified_at: rewrapped.modified_at,
+                    updated_by: rewrapped.updated_by,
+                };
+                address
+                    .update_encrypted_fields_if_unchanged(&conn, address_update)
+                    .await
+                    .map(storage::EncryptedRecord::Address)
+            }
+            (
+                storage::EncryptedRecord::MerchantConnectorAccount(mca),
+                storage::EncryptedRecord::MerchantConnectorAccount(rewrapped),
+            ) => {
+                let mca_update = storage::MerchantConnectorAccountUpdateInternal {
+                    connector_account_details: Some(rewrapped.connector_account_details),
+                    ..Default::default()
+                };
+                mca.update_encrypted_fields_if_unchanged(&conn, mca_update)
+                    .await
+                    .map(storage::EncryptedRecord::MerchantConnectorAccount)
+            }
+            (
+                storage::EncryptedRecord::PaymentMethod(payment_method),
+                storage::EncryptedRecord::PaymentMethod(rewrapped),
+            ) => {
+                let payment_method_update = storage::PaymentMethodUpdate::PaymentMethodDataUpdate {
+                    payment_method_data: rewrapped.payment_method_data,
+                };
+                payment_method
+                    .update_encrypted_fields_if_unchanged(&conn, payment_method_update)
+                    .await
+                    .map(storage::EncryptedRecord::PaymentMethod)
+            }
+            (record, rewrapped) => {
+                return Err(errors::StorageError::EncryptionError)
+                    .into_report()
+                    .attach_printable_lazy(|| {
+                        format!(
+                            "{} record was re-encrypted as a {} record",
+                            record.entity(),
+                            rewrapped.entity()
+                        )
+                    })
+            }
+        }
+        .map_err(Into::into)
+        .into_report()?;
+
+        // Cached accounts are decrypted with the keys of the merchant, and must not outlive the
+        // retired keys that they may be encrypted with
+        #[cfg(feature = "accounts_cache")]
+        match &updated {
+            storage::EncryptedRecord::MerchantAccount(merchant_account) => {
+                super::merchant_account::publish_and_redact_merchant_account_cache(
+                    self,
+                    merchant_account,
+                )
+                .await?;
+            }
+            storage::EncryptedRecord::MerchantConnectorAccount(mca) => {
+                let cache_keys = [
+                    mca.connector_label
+                        .as_ref()
+                        .map(|connector_label| format!("{}_{}", mca.merchant_id, connector_label)),
+                    mca.profile_id
+                        .as_ref()
+                        .map(|profile_id| format!("{}_{}", profile_id, mca.connector_name)),
+                ];
+                for cache_key in cache_keys.into_iter().flatten() {
+                    super::cache::publish_into_redact_channel(
+                        self,
+                        CacheKind::Accounts(cache_key.into()),
+                    )
+                    .await?;
+                }
+            }
+            _ => {}
+        }
+
+        Ok(updated)
+    }
+
+    async fn find_merchant_key_stores_after_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::MerchantKeyStore>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::MerchantKeyStore::list_after_merchant_id(&conn, merchant_id, limit)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_encrypted_merchant_key_store(
+        &self,
+        merchant_key_store: storage::MerchantKeyStore,
+    ) -> CustomResult<storage::MerchantKeyStore, errors::StorageError> {
+        let update_func = || async {
+            let conn = connection::pg_connection_write(self).await?;
+            storage::MerchantKeyStore::update_by_merchant_id_key_version(
+                &conn,
+                &merchant_key_store.merchant_id,
+                merchant_key_store.key_version,
+                diesel_models::merchant_key_store::MerchantKeyStoreUpdateInternal {
+                    key: merchant_key_store.key.clone(),
+                    key_version: merchant_key_store.key_version,
+                    retired_keys: merchant_key_store.retired_keys.clone(),
+                },
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        {
+            update_func().await
+        }
+
+        #[cfg(feature = "accounts_cache")]
+        {
+            let key_store_cache_key =
+                format!("merchant_key_store_{}", merchant_key_store.merchant_id);
+            super::cache::publish_and_redact(
+                self,
+                CacheKind::Accounts(key_store_cache_key.into()),
+                update_func,
+            )
+            .await
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EncryptedDataInterface for MockDb {
+    async fn find_encrypted_data_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        entity: EncryptedEntity,
+        after_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::EncryptedRecord>, errors::StorageError> {
+        let mut records: Vec<storage::EncryptedRecord> = match entity {
+            EncryptedEntity::MerchantAccount => self
+                .merchant_accounts
+                .lock()
+                .await
+                .iter()
+                .filter(|merchant_account| merchant_account.merchant_id == merchant_id)
+                .cloned()
+                .map(storage::EncryptedRecord::MerchantAccount)
+                .collect(),
+            EncryptedEntity::Customer => self
+                .customers
+                .lock()
+                .await
+                .iter()
+                .filter(|customer| customer.merchant_id == merchant_id)
+                .cloned()
+                .map(storage::EncryptedRecord::Customer)
+                .collect(),
+            EncryptedEntity::Address => self
+                .addresses
+                .lock()
+                .await
+                .iter()
+                .filter(|address| address.merchant_id == merchant_id)
+                .cloned()
+                .map(storage::EncryptedRecord::Address)
+                .collect(),
+            EncryptedEntity::MerchantConnectorAccount => self
+                .merchant_connector_accounts
+                .lock()
+                .await
+                .iter()
+                .filter(|mca| mca.merchant_id == merchant_id)
+                .cloned()
+                .map(storage::EncryptedRecord::MerchantConnectorAccount)
+                .collect(),
+            EncryptedEntity::PaymentMethod => self
+                .payment_methods
+                .lock()
+                .await
+                .iter()
+                .filter(|payment_method| payment_method.merchant_id == merchant_id)
+                .cloned()
+                .map(storage::EncryptedRecord::PaymentMethod)
+                .collect(),
+        };
+
+        records.retain(|record| record.id() > after_id);
+        records.sort_by(|a, b| a.id().cmp(b.id()));
+        records.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+        Ok(records)
+    }
+
+    async fn update_encrypted_data(
+        &self,
+        record: storage::EncryptedRecord,
+        key: &KeyRing,
+    ) -> CustomResult<storage::EncryptedRecord, errors::StorageError> {
+        let rewrapped = record
+            .clone()
+            .rewrap(key)
+            .change_context(errors::StorageError::EncryptionError)?;
+
+        match rewrapped {
+            storage::EncryptedRecord::MerchantAccount(rewrapped) => replace_if_unchanged(
+                &mut self.merchant_accounts.lock().await,
+                storage::EncryptedRecord::MerchantAccount,
+                &record,
+                rewrapped,
+            ),
+            storage::EncryptedRecord::Customer(rewrapped) => replace_if_unchanged(
+                &mut self.customers.lock().await,
+                storage::EncryptedRecord::Customer,
+                &record,
+                rewrapped,
+            ),
+            storage::EncryptedRecord::Address(rewrapped) => replace_if_unchanged(
+                &mut self.addresses.lock().await,
+                storage::EncryptedRecord::Address,
+                &record,
+                rewrapped,
+            ),
+            storage::EncryptedRecord::MerchantConnectorAccount(rewrapped) => replace_if_unchanged(
+                &mut self.merchant_connector_accounts.lock().await,
+                storage::EncryptedRecord::MerchantConnectorAccount,
+                &record,
+                rewrapped,
+            ),
+            storage::EncryptedRecord::PaymentMethod(rewrapped) => replace_if_unchanged(
+                &mut self.payment_methods.lock().await,
+                storage::EncryptedRecord::PaymentMethod,
+                &record,
+                rewrapped,
+            ),
+        }
+    }
+
+    async fn find_merchant_key_stores_after_merchant_id(
+        &self,
+        merchant_id: &str,
+        limit: i64,
+    ) -> CustomResult<Vec<storage::MerchantKeyStore>, errors::StorageError> {
+        let mut merchant_key_stores: Vec<_> = self
+            .merchant_key_store
+            .lock()
+            .await
+            .iter()
+            .filter(|merchant_key_store| merchant_key_store.merchant_id.as_str() > merchant_id)
+            .cloned()
+            .collect();
+
+        merchant_key_stores.sort_by(|a, b| a.merchant_id.cmp(&b.merchant_id));
+        merchant_key_stores.truncate(usize::try_from(limit).unwrap_or(usize::MAX));
+        Ok(merchant_key_stores)
+    }
+
+    async fn update_encrypted_merchant_key_store(
+        &self,
+        merchant_key_store: storage::MerchantKeyStore,
+    ) -> CustomResult<storage::MerchantKeyStore, errors::StorageError> {
+        let mut merchant_key_stores = self.merchant_key_store.lock().await;
+        let stored_key_store = merchant_key_stores
+            .iter_mut()
+            .find(|stored_key_store| {
+                stored_key_store.merchant_id == merchant_key_store.merchant_id
+                    && stored_key_store.key_version == merchant_key_store.key_version
+            })
+            .ok_or(errors::StorageError::DatabaseError(
+                DatabaseError::NotFound.into(),
+            ))
+            .into_report()
+            .attach_printable_lazy(|| {
+                format!(
+                    "No merchant key store of version {} found for merchant_id = {}",
+                    merchant_key_store.key_version, merchant_key_store.merchant_id
+                )
+            })?;
+
+        *stored_key_store = merchant_key_store.clone();
+        Ok(merchant_key_store)
+    }
+}
+
+/// Replaces the stored record that `record` was fetched from by `rewrapped`, provided that its
+/// encrypted fields have not been updated since.
+fn replace_if_unchanged<T: Clone>(
+    stored_records: &mut [T],
+    to_encrypted_record: fn(T) -> storage::EncryptedRecord,
+    record: &storage::EncryptedRecord,
+    rewrapped: T,
+) -> CustomResult<storage::EncryptedRecord, errors::StorageError> {
+    let stored_record = stored_records
+        .iter_mut()
+        .find(|stored_record| {
+            let stored_record = to_encrypted_record((*stored_record).clone());
+            stored_record.id() == record.id()
+                && stored_record.encrypted_fields() == record.encrypted_fields()
+        })
+        .ok_or(errors::StorageError::DatabaseError(
+            DatabaseError::NotFound.into(),
+        ))
+        .into_report()
+        .attach_printable_lazy(|| {
+            format!(
+                "No {} with unchanged encrypted fields found for id = {}",
+                record.entity(),
+                record.id()
+            )
+        })?;
+
+    *stored_record = rewrapped.clone();
+    Ok(to_encrypted_record(rewrapped))
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use diesel_models::encryption::Encryption;
+    use masking::Secret;
+    use time::macros::datetime;
+
+    use super::*;
+    use crate::types::domain::types::{self as domain_types, INITIAL_KEY_VERSION};
+
+    fn key(byte: u8) -> Secret<Vec<u8>> {
+        vec![byte; 32].into()
+    }
+
+    async fn encrypt(data: &str, key_ring: &KeyRing) -> Encryption {
+        domain_types::encrypt(Secret::<String>::new(data.to_string()), key_ring)
+            .await
+            .unwrap()
+            .into()
+    }
+
+    fn customer(customer_id: &str, name: Option<Encryption>) -> storage::Customer {
+        storage::Customer {
+            id: 0,
+            customer_id: customer_id.to_string(),
+            merchant_id: "merchant1".to_string(),
+            name,
+            email: None,
+            phone: None,
+            phone_country_code: None,
+            description: None,
+            created_at: datetime!(2023-02-01 0:00),
+            metadata: None,
+            connector_customer: None,
+            modified_at: datetime!(2023-02-01 0:00),
+            address_id: None,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_data_is_paged_by_identifier() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        mock_db.customers.lock().await.extend([
+            customer("cus_3", None),
+            customer("cus_1", None),
+            customer("cus_2", None),
+        ]);
+
+        let first_page = mock_db
+            .find_encrypted_data_by_merchant_id("merchant1", EncryptedEntity::Customer, "", 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            first_page
+                .iter()
+                .map(|record| record.id())
+                .collect::<Vec<_>>(),
+            ["cus_1", "cus_2"]
+        );
+
+        let second_page = mock_db
+            .find_encrypted_data_by_merchant_id("merchant1", EncryptedEntity::Customer, "cus_2", 2)
+            .await
+            .unwrap();
+        assert_eq!(
+            second_page
+                .iter()
+                .map(|record| record.id())
+                .collect::<Vec<_>>(),
+            ["cus_3"]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_data_updated_concurrently_is_not_overwritten() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let initial_key_ring = KeyRing::new(key(1));
+        let rotated_key_ring =
+            KeyRing::with_active_key(2, key(2)).with_retired_key(INITIAL_KEY_VERSION, key(1));
+        mock_db.customers.lock().await.push(customer(
+            "cus_1",
+            Some(encrypt("Alice", &initial_key_ring).await),
+        ));
+
+        let record = mock_db
+            .find_encrypted_data_by_merchant_id("merchant1", EncryptedEntity::Customer, "", 10)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert!(record.needs_rewrap(&rotated_key_ring));
+
+        // The name of the customer is updated after the record was fetched
+        let updated_name = encrypt("Bob", &rotated_key_ring).await;
+        mock_db.customers.lock().await[0].name = Some(updated_name.clone());
+
+        let error = mock_db
+            .update_encrypted_data(record, &rotated_key_ring)
+            .await
+            .unwrap_err();
+        assert!(error.current_context().is_db_not_found());
+        assert_eq!(mock_db.customers.lock().await[0].name, Some(updated_name));
+
+        let record = mock_db
+            .find_encrypted_data_by_merchant_id("merchant1", EncryptedEntity::Customer, "", 10)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        assert!(!record.needs_rewrap(&rotated_key_ring));
+    }
+
+    #[tokio::test]
+    async fn test_encrypted_data_is_rewrapped_with_active_key() {
+        let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .unwrap();
+        let initial_key_ring = KeyRing::new(key(1));
+        let rotated_key_ring =
+            KeyRing::with_active_key(2, key(2)).with_retired_key(INITIAL_KEY_VERSION, key(1));
+        mock_db.customers.lock().await.push(customer(
+            "cus_1",
+            Some(encrypt("Alice", &initial_key_ring).await),
+        ));
+
+        let record = mock_db
+            .find_encrypted_data_by_merchant_id("merchant1", EncryptedEntity::Customer, "", 10)
+            .await
+            .unwrap()
+            .pop()
+            .unwrap();
+        mock_db
+            .update_encrypted_data(record, &rotated_key_ring)
+            .await
+            .unwrap();
+
+        let name = mock_db.customers.lock().await[0].name.clone().unwrap();
+        assert_eq!(domain_types::get_encryption_key_version(&name), 2);
+        let decrypted: Option<common_utils::crypto::Encryptable<Secret<String>>> =
+            domain_types::decrypt(Some(name), &rotated_key_ring)
+                .await
+                .unwrap();
+        assert_eq!(
+            decrypted.map(|name| masking::ExposeInterface::expose(name.into_inner())),
+            Some("Alice".to_string())
+        );
+    }
+}
@@ -291,6 +291,8 @@ impl MandateInterface for MockDb {
             metadata: mandate_new.metadata,
             connector_mandate_ids: mandate_new.connector_mandate_ids,
             merchant_connector_id: mandate_new.merchant_connector_id,
+            frequency_limit: mandate_new.frequency_limit,
+            frequency_interval: mandate_new.frequency_interval,
         };
         mandates.push(mandate.clone());
         Ok(mandate)
@@ -0,0 +1,140 @@
+use error_stack::IntoReport;
+use time::PrimitiveDateTime;
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait MandateUsageInterface {
+    async fn insert_mandate_usage(
+        &self,
+        mandate_usage: storage::MandateUsageNew,
+    ) -> CustomResult<storage::MandateUsage, errors::StorageError>;
+
+    async fn insert_mandate_usage_within_frequency_limit(
+        &self,
+        mandate_usage: storage::MandateUsageNew,
+        frequency_limit: i32,
+        interval_start: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::MandateUsage>, errors::StorageError>;
+
+    async fn delete_mandate_usage_by_merchant_id_attempt_id(
+        &self,
+        merchant_id: &str,
+        attempt_id: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+
+    async fn find_mandate_usages_by_merchant_id_mandate_id_created_after(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        created_after: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::MandateUsage>, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl MandateUsageInterface for Store {
+    async fn insert_mandate_usage(
+        &self,
+        mandate_usage: storage::MandateUsageNew,
+    ) -> CustomResult<storage::MandateUsage, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        mandate_usage
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn insert_mandate_usage_within_frequency_limit(
+        &self,
+        mandate_usage: storage::MandateUsageNew,
+        frequency_limit: i32,
+        interval_start: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::MandateUsage>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        mandate_usage
+            .insert_within_frequency_limit(&conn, frequency_limit, interval_start)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn delete_mandate_usage_by_merchant_id_attempt_id(
+        &self,
+        merchant_id: &str,
+        attempt_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::MandateUsage::delete_by_merchant_id_attempt_id(&conn, merchant_id, attempt_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_mandate_usages_by_merchant_id_mandate_id_created_after(
+        &self,
+        merchant_id: &str,
+        mandate_id: &str,
+        created_after: PrimitiveDateTime,
+        limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::MandateUsage>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::MandateUsage::find_by_merchant_id_mandate_id_created_after(
+            &conn,
+            merchant_id,
+            mandate_id,
+            created_after,
+            limit,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl MandateUsageInterface for MockDb {
+    async fn insert_mandate_usage(
+        &self,
+        _mandate_usage: storage::MandateUsageNew,
+    ) -> CustomResult<storage::MandateUsage, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn insert_mandate_usage_within_frequency_limit(
+        &self,
+        _mandate_usage: storage::MandateUsageNew,
+        _frequency_limit: i32,
+        _interval_start: PrimitiveDateTime,
+    ) -> CustomResult<Option<storage::MandateUsage>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn delete_mandate_usage_by_merchant_id_attempt_id(
+        &self,
+        _merchant_id: &str,
+        _attempt_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_mandate_usages_by_merchant_id_mandate_id_created_after(
+        &self,
+        _merchant_id: &str,
+        _mandate_id: &str,
+        _created_after: PrimitiveDateTime,
+        _limit: Option<i64>,
+    ) -> CustomResult<Vec<storage::MandateUsage>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+}
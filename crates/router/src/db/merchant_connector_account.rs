@@ -187,7 +187,7 @@ impl MerchantConnectorAccountInterface for Store {
         {
             find_call()
                 .await?
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DeserializationFailed)
         }
@@ -202,7 +202,7 @@ impl MerchantConnectorAccountInterface for Store {
             )
             .await
             .async_and_then(|item| async {
-                item.convert(key_store.key.get_inner())
+                item.convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -232,7 +232,7 @@ impl MerchantConnectorAccountInterface for Store {
         {
             find_call()
                 .await?
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DeserializationFailed)
         }
@@ -247,7 +247,7 @@ impl MerchantConnectorAccountInterface for Store {
             )
             .await
             .async_and_then(|item| async {
-                item.convert(key_store.key.get_inner())
+                item.convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -274,7 +274,7 @@ impl MerchantConnectorAccountInterface for Store {
             let mut output = Vec::with_capacity(items.len());
             for item in items.into_iter() {
                 output.push(
-                    item.convert(key_store.key.get_inner())
+                    item.convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)?,
                 )
@@ -300,7 +300,7 @@ impl MerchantConnectorAccountInterface for Store {
         .map_err(Into::into)
         .into_report()
         .async_and_then(|item| async {
-            item.convert(key_store.key.get_inner())
+            item.convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError)
         })
@@ -321,7 +321,7 @@ impl MerchantConnectorAccountInterface for Store {
             .map_err(Into::into)
             .into_report()
             .async_and_then(|item| async {
-                item.convert(key_store.key.get_inner())
+                item.convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -343,7 +343,7 @@ impl MerchantConnectorAccountInterface for Store {
                 let mut output = Vec::with_capacity(items.len());
                 for item in items.into_iter() {
                     output.push(
-                        item.convert(key_store.key.get_inner())
+                        item.convert(&key_store.key_ring())
                             .await
                             .change_context(errors::StorageError::DecryptionError)?,
                     )
@@ -377,7 +377,7 @@ impl MerchantConnectorAccountInterface for Store {
                 .map_err(Into::into)
                 .into_report()
                 .async_and_then(|item| async {
-                    item.convert(key_store.key.get_inner())
+                    item.convert(&key_store.key_ring())
                         .await
                         .change_context(errors::StorageError::DecryptionError)
                 })
@@ -472,7 +472,7 @@ impl MerchantConnectorAccountInterface for MockDb {
             .cloned()
             .async_map(|account| async {
                 account
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -508,7 +508,7 @@ impl MerchantConnectorAccountInterface for MockDb {
         for account in accounts.into_iter() {
             output.push(
                 account
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)?,
             )
@@ -536,7 +536,7 @@ impl MerchantConnectorAccountInterface for MockDb {
         match maybe_mca {
             Some(mca) => mca
                 .to_owned()
-                .convert(key_store.key.get_inner())
+                .convert(&key_store.key_ring())
                 .await
                 .change_context(errors::StorageError::DecryptionError),
             None => Err(errors::StorageError::ValueNotFound(
@@ -564,7 +564,7 @@ impl MerchantConnectorAccountInterface for MockDb {
             .cloned()
             .async_map(|account| async {
                 account
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -616,7 +616,7 @@ impl MerchantConnectorAccountInterface for MockDb {
         };
         accounts.push(account.clone());
         account
-            .convert(key_store.key.get_inner())
+            .convert(&key_store.key_ring())
             .await
             .change_context(errors::StorageError::DecryptionError)
     }
@@ -646,7 +646,7 @@ impl MerchantConnectorAccountInterface for MockDb {
         for account in accounts.into_iter() {
             output.push(
                 account
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)?,
             )
@@ -674,7 +674,7 @@ impl MerchantConnectorAccountInterface for MockDb {
             })
             .async_map(|account| async {
                 account
-                    .convert(key_store.key.get_inner())
+                    .convert(&key_store.key_ring())
                     .await
                     .change_context(errors::StorageError::DecryptionError)
             })
@@ -720,7 +720,6 @@ mod merchant_connector_account_cache_tests {
     use common_utils::date_time;
     use diesel_models::enums::ConnectorType;
     use error_stack::ResultExt;
-    use masking::PeekInterface;
     use storage_impl::redis::{
         cache::{CacheKind, ACCOUNTS_CACHE},
         kv_store::RedisConnInterface,
@@ -750,7 +749,7 @@ mod merchant_connector_account_cache_tests {
             .expect("Failed to create Mock store");
 
         let redis_conn = db.get_redis_conn().unwrap();
-        let master_key = db.get_master_key();
+        let master_key_ring = db.get_master_key_ring();
         redis_conn
             .subscribe("hyperswitch_invalidate")
             .await
@@ -766,19 +765,21 @@ mod merchant_connector_account_cache_tests {
                 merchant_id: merchant_id.into(),
                 key: domain::types::encrypt(
                     services::generate_aes256_key().unwrap().to_vec().into(),
-                    master_key,
+                    &master_key_ring,
                 )
                 .await
                 .unwrap(),
                 created_at: datetime!(2023-02-01 0:00),
+                key_version: domain::types::INITIAL_KEY_VERSION,
+                retired_keys: Vec::new(),
             },
-            &master_key.to_vec().into(),
+            &master_key_ring,
         )
         .await
         .unwrap();
 
         let merchant_key = db
-            .get_merchant_key_store_by_merchant_id(merchant_id, &master_key.to_vec().into())
+            .get_merchant_key_store_by_merchant_id(merchant_id, &master_key_ring)
             .await
             .unwrap();
 
@@ -788,7 +789,7 @@ mod merchant_connector_account_cache_tests {
             connector_name: "stripe".to_string(),
             connector_account_details: domain::types::encrypt(
                 serde_json::Value::default().into(),
-                merchant_key.key.get_inner().peek(),
+                &merchant_key.key_ring(),
             )
             .await
             .unwrap(),
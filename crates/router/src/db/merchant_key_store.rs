@@ -1,5 +1,4 @@
 use error_stack::{IntoReport, ResultExt};
-use masking::Secret;
 #[cfg(feature = "accounts_cache")]
 use storage_impl::redis::cache::{CacheKind, ACCOUNTS_CACHE};
 
@@ -11,6 +10,7 @@ use crate::{
     types::domain::{
         self,
         behaviour::{Conversion, ReverseConversion},
+        types::{KeyRing, KeyVersion},
     },
 };
 
@@ -19,13 +19,22 @@ pub trait MerchantKeyStoreInterface {
     async fn insert_merchant_key_store(
         &self,
         merchant_key_store: domain::MerchantKeyStore,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError>;
 
     async fn get_merchant_key_store_by_merchant_id(
         &self,
         merchant_id: &str,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
+    ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError>;
+
+    /// Replaces the key store of the merchant, provided that its key is still of
+    /// `current_key_version`.
+    async fn update_merchant_key_store(
+        &self,
+        current_key_version: KeyVersion,
+        merchant_key_store: domain::MerchantKeyStore,
+        key: &KeyRing,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError>;
 
     async fn delete_merchant_key_store_by_merchant_id(
@@ -39,7 +48,7 @@ impl MerchantKeyStoreInterface for Store {
     async fn insert_merchant_key_store(
         &self,
         merchant_key_store: domain::MerchantKeyStore,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
         let conn = connection::pg_connection_write(self).await?;
         merchant_key_store
@@ -58,7 +67,7 @@ impl MerchantKeyStoreInterface for Store {
     async fn get_merchant_key_store_by_merchant_id(
         &self,
         merchant_id: &str,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
         let fetch_func = || async {
             let conn = connection::pg_connection_read(self).await?;
@@ -97,6 +106,53 @@ impl MerchantKeyStoreInterface for Store {
         }
     }
 
+    async fn update_merchant_key_store(
+        &self,
+        current_key_version: KeyVersion,
+        merchant_key_store: domain::MerchantKeyStore,
+        key: &KeyRing,
+    ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
+        let merchant_id = merchant_key_store.merchant_id.clone();
+        let merchant_key_store = Conversion::convert(merchant_key_store)
+            .await
+            .change_context(errors::StorageError::EncryptionError)?;
+        let update_func = || async {
+            let conn = connection::pg_connection_write(self).await?;
+            diesel_models::merchant_key_store::MerchantKeyStore::update_by_merchant_id_key_version(
+                &conn,
+                &merchant_id,
+                current_key_version,
+                diesel_models::merchant_key_store::MerchantKeyStoreUpdateInternal {
+                    key: merchant_key_store.key.clone(),
+                    key_version: merchant_key_store.key_version,
+                    retired_keys: merchant_key_store.retired_keys.clone(),
+                },
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        };
+
+        #[cfg(not(feature = "accounts_cache"))]
+        let updated_merchant_key_store = update_func().await?;
+
+        #[cfg(feature = "accounts_cache")]
+        let updated_merchant_key_store = {
+            let key_store_cache_key = format!("merchant_key_store_{}", merchant_id);
+            super::cache::publish_and_redact(
+                self,
+                CacheKind::Accounts(key_store_cache_key.into()),
+                update_func,
+            )
+            .await?
+        };
+
+        updated_merchant_key_store
+            .convert(key)
+            .await
+            .change_context(errors::StorageError::DecryptionError)
+    }
+
     async fn delete_merchant_key_store_by_merchant_id(
         &self,
         merchant_id: &str,
@@ -135,7 +191,7 @@ impl MerchantKeyStoreInterface for MockDb {
     async fn insert_merchant_key_store(
         &self,
         merchant_key_store: domain::MerchantKeyStore,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
         let mut locked_merchant_key_store = self.merchant_key_store.lock().await;
 
@@ -163,7 +219,7 @@ impl MerchantKeyStoreInterface for MockDb {
     async fn get_merchant_key_store_by_merchant_id(
         &self,
         merchant_id: &str,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
     ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
         self.merchant_key_store
             .lock()
@@ -179,6 +235,33 @@ impl MerchantKeyStoreInterface for MockDb {
             .change_context(errors::StorageError::DecryptionError)
     }
 
+    async fn update_merchant_key_store(
+        &self,
+        current_key_version: KeyVersion,
+        merchant_key_store: domain::MerchantKeyStore,
+        key: &KeyRing,
+    ) -> CustomResult<domain::MerchantKeyStore, errors::StorageError> {
+        let merchant_key_store = Conversion::convert(merchant_key_store)
+            .await
+            .change_context(errors::StorageError::MockDbError)?;
+        let mut locked_merchant_key_store = self.merchant_key_store.lock().await;
+        let existing_merchant_key_store = locked_merchant_key_store
+            .iter_mut()
+            .find(|merchant_key| {
+                merchant_key.merchant_id == merchant_key_store.merchant_id
+                    && merchant_key.key_version == current_key_version
+            })
+            .ok_or(errors::StorageError::ValueNotFound(String::from(
+                "merchant_key_store",
+            )))?;
+        *existing_merchant_key_store = merchant_key_store.clone();
+
+        merchant_key_store
+            .convert(key)
+            .await
+            .change_context(errors::StorageError::DecryptionError)
+    }
+
     async fn delete_merchant_key_store_by_merchant_id(
         &self,
         merchant_id: &str,
@@ -213,7 +296,7 @@ mod tests {
         let mock_db = MockDb::new(&redis_interface::RedisSettings::default())
             .await
             .expect("Failed to create mock DB");
-        let master_key = mock_db.get_master_key();
+        let master_key_ring = mock_db.get_master_key_ring();
         let merchant_id = "merchant1";
 
         let merchant_key1 = mock_db
@@ -222,19 +305,21 @@ mod tests {
                     merchant_id: merchant_id.into(),
                     key: domain::types::encrypt(
                         services::generate_aes256_key().unwrap().to_vec().into(),
-                        master_key,
+                        &master_key_ring,
                     )
                     .await
                     .unwrap(),
                     created_at: datetime!(2023-02-01 0:00),
+                    key_version: domain::types::INITIAL_KEY_VERSION,
+                    retired_keys: Vec::new(),
                 },
-                &master_key.to_vec().into(),
+                &master_key_ring,
             )
             .await
             .unwrap();
 
         let found_merchant_key1 = mock_db
-            .get_merchant_key_store_by_merchant_id(merchant_id, &master_key.to_vec().into())
+            .get_merchant_key_store_by_merchant_id(merchant_id, &master_key_ring)
             .await
             .unwrap();
 
@@ -247,24 +332,29 @@ mod tests {
                     merchant_id: merchant_id.into(),
                     key: domain::types::encrypt(
                         services::generate_aes256_key().unwrap().to_vec().into(),
-                        master_key,
+                        &master_key_ring,
                     )
                     .await
                     .unwrap(),
                     created_at: datetime!(2023-02-01 0:00),
+                    key_version: domain::types::INITIAL_KEY_VERSION,
+                    retired_keys: Vec::new(),
                 },
-                &master_key.to_vec().into(),
+                &master_key_ring,
             )
             .await;
         assert!(insert_duplicate_merchant_key1_result.is_err());
 
         let find_non_existent_merchant_key_result = mock_db
-            .get_merchant_key_store_by_merchant_id("non_existent", &master_key.to_vec().into())
+            .get_merchant_key_store_by_merchant_id("non_existent", &master_key_ring)
             .await;
         assert!(find_non_existent_merchant_key_result.is_err());
 
         let find_merchant_key_with_incorrect_master_key_result = mock_db
-            .get_merchant_key_store_by_merchant_id(merchant_id, &vec![0; 32].into())
+            .get_merchant_key_store_by_merchant_id(
+                merchant_id,
+                &domain::types::KeyRing::new(vec![0; 32].into()),
+            )
             .await;
         assert!(find_merchant_key_with_incorrect_master_key_result.is_err());
     }
@@ -16,9 +16,14 @@ pub trait OrganizationInterface {
         org_id: &str,
     ) -> CustomResult<storage::Organization, errors::StorageError>;
 
+    async fn find_organization_by_hashed_api_key_optional(
+        &self,
+        hashed_api_key: diesel_models::api_keys::HashedApiKey,
+    ) -> CustomResult<Option<storage::Organization>, errors::StorageError>;
+
     async fn update_organization_by_org_id(
         &self,
-        user_id: &str,
+        org_id: &str,
         update: storage::OrganizationUpdate,
     ) -> CustomResult<storage::Organization, errors::StorageError>;
 }
@@ -48,6 +53,17 @@ impl OrganizationInterface for Store {
             .into_report()
     }
 
+    async fn find_organization_by_hashed_api_key_optional(
+        &self,
+        hashed_api_key: diesel_models::api_keys::HashedApiKey,
+    ) -> CustomResult<Option<storage::Organization>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::Organization::find_optional_by_hashed_api_key(&conn, hashed_api_key.into_inner())
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
     async fn update_organization_by_org_id(
         &self,
         org_id: &str,
@@ -82,6 +98,8 @@ impl OrganizationInterface for super::MockDb {
         let org = storage::Organization {
             org_id: organization.org_id.clone(),
             org_name: organization.org_name,
+            hashed_api_key: None,
+            api_key_prefix: None,
         };
         organizations.push(org.clone());
         Ok(org)
@@ -105,6 +123,21 @@ impl OrganizationInterface for super::MockDb {
             )
     }
 
+    async fn find_organization_by_hashed_api_key_optional(
+        &self,
+        hashed_api_key: diesel_models::api_keys::HashedApiKey,
+    ) -> CustomResult<Option<storage::Organization>, errors::StorageError> {
+        let hashed_api_key = hashed_api_key.into_inner();
+
+        Ok(self
+            .organizations
+            .lock()
+            .await
+            .iter()
+            .find(|org| org.hashed_api_key.as_ref() == Some(&hashed_api_key))
+            .cloned())
+    }
+
     async fn update_organization_by_org_id(
         &self,
         org_id: &str,
@@ -115,11 +148,9 @@ impl OrganizationInterface for super::MockDb {
         organizations
             .iter_mut()
             .find(|org| org.org_id == org_id)
-            .map(|org| match &update {
-                storage::OrganizationUpdate::Update { org_name } => storage::Organization {
-                    org_name: org_name.clone(),
-                    ..org.to_owned()
-                },
+            .map(|org| {
+                *org = update.apply_changeset(org.clone());
+                org.clone()
             })
             .ok_or(
                 errors::StorageError::ValueNotFound(format!(
@@ -129,3 +160,64 @@ impl OrganizationInterface for super::MockDb {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use diesel_models::organization as storage;
+
+    use crate::db::{organization::OrganizationInterface, MockDb};
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mockdb_organization_interface() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        mockdb
+            .insert_organization(storage::OrganizationNew {
+                org_id: "org1".into(),
+                org_name: Some("Organization 1".into()),
+            })
+            .await
+            .unwrap();
+
+        let organization = mockdb
+            .update_organization_by_org_id(
+                "org1",
+                storage::OrganizationUpdate::ApiKeyUpdate {
+                    hashed_api_key: "hashed_key1".into(),
+                    api_key_prefix: "abc".into(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(organization.api_key_prefix, Some("abc".into()));
+
+        let organization = mockdb
+            .update_organization_by_org_id(
+                "org1",
+                storage::OrganizationUpdate::Update {
+                    org_name: Some("Renamed organization".into()),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(organization.org_name, Some("Renamed organization".into()));
+        assert_eq!(organization.hashed_api_key, Some("hashed_key1".into()));
+
+        let found_organization = mockdb
+            .find_organization_by_hashed_api_key_optional("hashed_key1".to_string().into())
+            .await
+            .unwrap()
+            .unwrap();
+        assert_eq!(found_organization.org_id, "org1");
+
+        assert!(mockdb
+            .find_organization_by_hashed_api_key_optional("hashed_key2".to_string().into())
+            .await
+            .unwrap()
+            .is_none());
+    }
+}
@@ -4,7 +4,7 @@ use super::{MockDb, Store};
 use crate::{
     connection,
     core::errors::{self, CustomResult},
-    types::storage,
+    types::storage::{self, PaymentLinkDbExt},
 };
 
 #[async_trait::async_trait]
@@ -18,6 +18,55 @@ pub trait PaymentLinkInterface {
         &self,
         _payment_link: storage::PaymentLinkNew,
     ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+
+    async fn update_payment_link(
+        &self,
+        payment_link: storage::PaymentLink,
+        payment_link_update: storage::PaymentLinkUpdate,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+
+    async fn list_payment_links_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        payment_link_constraints: api_models::payments::PaymentLinkListConstraints,
+    ) -> CustomResult<Vec<storage::PaymentLink>, errors::StorageError>;
+
+    async fn insert_reusable_payment_link(
+        &self,
+        reusable_payment_link: storage::ReusablePaymentLinkNew,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError>;
+
+    async fn find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+        &self,
+        merchant_id: &str,
+        reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError>;
+
+    async fn update_reusable_payment_link(
+        &self,
+        reusable_payment_link: storage::ReusablePaymentLink,
+        reusable_payment_link_update: storage::ReusablePaymentLinkUpdate,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError>;
+
+    async fn complete_payment_link(
+        &self,
+        payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+
+    async fn expire_payment_link(
+        &self,
+        payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError>;
+
+    async fn reserve_reusable_payment_link_usage(
+        &self,
+        reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError>;
+
+    async fn release_reusable_payment_link_usage(
+        &self,
+        reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError>;
 }
 
 #[async_trait::async_trait]
@@ -44,6 +93,116 @@ impl PaymentLinkInterface for Store {
             .map_err(Into::into)
             .into_report()
     }
+
+    async fn update_payment_link(
+        &self,
+        payment_link: storage::PaymentLink,
+        payment_link_update: storage::PaymentLinkUpdate,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        payment_link
+            .update_by_payment_link_id(&conn, payment_link_update)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_payment_links_by_merchant_id(
+        &self,
+        merchant_id: &str,
+        payment_link_constraints: api_models::payments::PaymentLinkListConstraints,
+    ) -> CustomResult<Vec<storage::PaymentLink>, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::PaymentLink::filter_by_constraints(&conn, merchant_id, payment_link_constraints)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn insert_reusable_payment_link(
+        &self,
+        reusable_payment_link: storage::ReusablePaymentLinkNew,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        reusable_payment_link
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+        &self,
+        merchant_id: &str,
+        reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_read(self).await?;
+        storage::ReusablePaymentLink::find_by_merchant_id_reusable_payment_link_id(
+            &conn,
+            merchant_id,
+            reusable_payment_link_id,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn update_reusable_payment_link(
+        &self,
+        reusable_payment_link: storage::ReusablePaymentLink,
+        reusable_payment_link_update: storage::ReusablePaymentLinkUpdate,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        reusable_payment_link
+            .update_by_reusable_payment_link_id(&conn, reusable_payment_link_update)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn complete_payment_link(
+        &self,
+        payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::PaymentLink::complete(&conn, payment_link_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn expire_payment_link(
+        &self,
+        payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::PaymentLink::expire(&conn, payment_link_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn reserve_reusable_payment_link_usage(
+        &self,
+        reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ReusablePaymentLink::reserve_usage(&conn, reusable_payment_link_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn release_reusable_payment_link_usage(
+        &self,
+        reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::ReusablePaymentLink::release_usage(&conn, reusable_payment_link_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
 }
 
 #[async_trait::async_trait]
@@ -63,4 +222,80 @@ impl PaymentLinkInterface for MockDb {
         // TODO: Implement function for `MockDb`x
         Err(errors::StorageError::MockDbError)?
     }
+
+    async fn update_payment_link(
+        &self,
+        _payment_link: storage::PaymentLink,
+        _payment_link_update: storage::PaymentLinkUpdate,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn list_payment_links_by_merchant_id(
+        &self,
+        _merchant_id: &str,
+        _payment_link_constraints: api_models::payments::PaymentLinkListConstraints,
+    ) -> CustomResult<Vec<storage::PaymentLink>, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn insert_reusable_payment_link(
+        &self,
+        _reusable_payment_link: storage::ReusablePaymentLinkNew,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn find_reusable_payment_link_by_merchant_id_reusable_payment_link_id(
+        &self,
+        _merchant_id: &str,
+        _reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn update_reusable_payment_link(
+        &self,
+        _reusable_payment_link: storage::ReusablePaymentLink,
+        _reusable_payment_link_update: storage::ReusablePaymentLinkUpdate,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn complete_payment_link(
+        &self,
+        _payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn expire_payment_link(
+        &self,
+        _payment_link_id: &str,
+    ) -> CustomResult<storage::PaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn reserve_reusable_payment_link_usage(
+        &self,
+        _reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
+
+    async fn release_reusable_payment_link_usage(
+        &self,
+        _reusable_payment_link_id: &str,
+    ) -> CustomResult<storage::ReusablePaymentLink, errors::StorageError> {
+        // TODO: Implement function for `MockDb`
+        Err(errors::StorageError::MockDbError)?
+    }
 }
@@ -67,11 +67,12 @@ pub trait RefundInterface {
     #[cfg(feature = "olap")]
     async fn filter_refund_by_constraints(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_details: &api_models::refunds::RefundListRequest,
         storage_scheme: enums::MerchantStorageScheme,
         limit: i64,
         offset: i64,
+        consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError>;
 
     #[cfg(feature = "olap")]
@@ -80,22 +81,49 @@ pub trait RefundInterface {
         merchant_id: &str,
         refund_details: &api_models::refunds::TimeRange,
         storage_scheme: enums::MerchantStorageScheme,
+        consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError>;
 
     #[cfg(feature = "olap")]
     async fn get_total_count_of_refunds(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_details: &api_models::refunds::RefundListRequest,
         storage_scheme: enums::MerchantStorageScheme,
+        consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<i64, errors::StorageError>;
+
+    #[cfg(feature = "olap")]
+    async fn get_refund_aggregates(
+        &self,
+        merchant_ids: &[String],
+        time_range: &api_models::refunds::TimeRange,
+        storage_scheme: enums::MerchantStorageScheme,
+        consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<storage_types::RefundAggregate>, errors::StorageError>;
+}
+
+fn publish_refund_change_event(
+    store: &crate::services::Store,
+    operation: storage_impl::events::ChangeOperation,
+    storage_scheme: enums::MerchantStorageScheme,
+    refund: &storage_types::Refund,
+) {
+    store.publish_change_event(
+        storage_impl::events::ChangeEventRecordType::Refund,
+        format!("{}_{}", refund.merchant_id, refund.refund_id),
+        operation,
+        storage_scheme,
+        refund,
+    );
 }
 
 #[cfg(not(feature = "kv_store"))]
 mod storage {
     use error_stack::IntoReport;
+    use storage_impl::events::ChangeOperation;
 
-    use super::RefundInterface;
+    use super::{publish_refund_change_event, RefundInterface};
     use crate::{
         connection,
         core::errors::{self, CustomResult},
@@ -125,10 +153,13 @@ mod storage {
         async fn insert_refund(
             &self,
             new: storage_types::RefundNew,
-            _storage_scheme: enums::MerchantStorageScheme,
+            storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             let conn = connection::pg_connection_write(self).await?;
-            new.insert(&conn).await.map_err(Into::into).into_report()
+            let refund = new.insert(&conn).await.map_err(Into::into).into_report()?;
+
+            publish_refund_change_event(self, ChangeOperation::Insert, storage_scheme, &refund);
+            Ok(refund)
         }
 
         async fn find_refund_by_merchant_id_connector_transaction_id(
@@ -152,13 +183,17 @@ mod storage {
             &self,
             this: storage_types::Refund,
             refund: storage_types::RefundUpdate,
-            _storage_scheme: enums::MerchantStorageScheme,
+            storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
             let conn = connection::pg_connection_write(self).await?;
-            this.update(&conn, refund)
+            let refund = this
+                .update(&conn, refund)
                 .await
                 .map_err(Into::into)
-                .into_report()
+                .into_report()?;
+
+            publish_refund_change_event(self, ChangeOperation::Update, storage_scheme, &refund);
+            Ok(refund)
         }
 
         async fn find_refund_by_merchant_id_refund_id(
@@ -209,16 +244,17 @@ mod storage {
         #[cfg(feature = "olap")]
         async fn filter_refund_by_constraints(
             &self,
-            merchant_id: &str,
+            merchant_ids: &[String],
             refund_details: &api_models::refunds::RefundListRequest,
             _storage_scheme: enums::MerchantStorageScheme,
             limit: i64,
             offset: i64,
+            consistency: storage_impl::ReadConsistency,
         ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(
                 &conn,
-                merchant_id,
+                merchant_ids,
                 refund_details,
                 limit,
                 offset,
@@ -234,8 +270,9 @@ mod storage {
             merchant_id: &str,
             refund_details: &api_models::refunds::TimeRange,
             _storage_scheme: enums::MerchantStorageScheme,
+            consistency: storage_impl::ReadConsistency,
         ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_meta_constraints(
                 &conn,
                 merchant_id,
@@ -248,20 +285,40 @@ mod storage {
         #[cfg(feature = "olap")]
         async fn get_total_count_of_refunds(
             &self,
-            merchant_id: &str,
+            merchant_ids: &[String],
             refund_details: &api_models::refunds::RefundListRequest,
             _storage_scheme: enums::MerchantStorageScheme,
+            consistency: storage_impl::ReadConsistency,
         ) -> CustomResult<i64, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::get_refunds_count(
                 &conn,
-                merchant_id,
+                merchant_ids,
                 refund_details,
             )
             .await
             .map_err(Into::into)
             .into_report()
         }
+
+        #[cfg(feature = "olap")]
+        async fn get_refund_aggregates(
+            &self,
+            merchant_ids: &[String],
+            time_range: &api_models::refunds::TimeRange,
+            _storage_scheme: enums::MerchantStorageScheme,
+            consistency: storage_impl::ReadConsistency,
+        ) -> CustomResult<Vec<storage_types::RefundAggregate>, errors::StorageError> {
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
+            <diesel_models::refund::Refund as storage_types::RefundDbExt>::get_refund_aggregates(
+                &conn,
+                merchant_ids,
+                time_range,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
     }
 }
 
@@ -270,9 +327,12 @@ mod storage {
     use common_utils::date_time;
     use error_stack::{IntoReport, ResultExt};
     use redis_interface::HsetnxReply;
-    use storage_impl::redis::kv_store::{kv_wrapper, KvOperation};
+    use storage_impl::{
+        events::ChangeOperation,
+        redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op},
+    };
 
-    use super::RefundInterface;
+    use super::{publish_refund_change_event, RefundInterface};
     use crate::{
         connection,
         core::errors::{self, CustomResult},
@@ -303,7 +363,8 @@ mod storage {
             };
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let lookup_id = format!("{merchant_id}_{internal_reference_id}");
                     let lookup = self
                         .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -332,13 +393,24 @@ mod storage {
             new: storage_types::RefundNew,
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let key = format!("mid_{}_pid_{}", new.merchant_id, new.payment_id);
+            let storage_scheme =
+                decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
                     let conn = connection::pg_connection_write(self).await?;
-                    new.insert(&conn).await.map_err(Into::into).into_report()
+                    let refund = new.insert(&conn).await.map_err(Into::into).into_report()?;
+
+                    publish_refund_change_event(
+                        self,
+                        ChangeOperation::Insert,
+                        storage_scheme,
+                        &refund,
+                    );
+                    Ok(refund)
                 }
-                enums::MerchantStorageScheme::RedisKv => {
-                    let key = format!("mid_{}_pid_{}", new.merchant_id, new.payment_id);
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     // TODO: need to add an application generated payment attempt id to distinguish between multiple attempts for the same payment id
                     // Check for database presence as well Maybe use a read replica here ?
                     let created_refund = storage_types::Refund {
@@ -447,6 +519,12 @@ mod storage {
 
                             futures::future::try_join_all(rev_look).await?;
 
+                            publish_refund_change_event(
+                                self,
+                                ChangeOperation::Insert,
+                                storage_scheme,
+                                &created_refund,
+                            );
                             Ok(created_refund)
                         }
                         Err(er) => Err(er).change_context(errors::StorageError::KVError),
@@ -474,7 +552,8 @@ mod storage {
             };
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let lookup_id = format!("{merchant_id}_{connector_transaction_id}");
                     let lookup = match self
                         .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -513,17 +592,33 @@ mod storage {
             refund: storage_types::RefundUpdate,
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<storage_types::Refund, errors::StorageError> {
+            let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+            let field = format!("pa_{}_ref_{}", &this.attempt_id, &this.refund_id);
+            let storage_scheme = decide_storage_scheme(
+                self,
+                storage_scheme,
+                Op::Update(&key, &field, &this.updated_by),
+            )
+            .await;
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
                     let conn = connection::pg_connection_write(self).await?;
-                    this.update(&conn, refund)
+                    let refund = this
+                        .update(&conn, refund)
                         .await
                         .map_err(Into::into)
-                        .into_report()
+                        .into_report()?;
+
+                    publish_refund_change_event(
+                        self,
+                        ChangeOperation::Update,
+                        storage_scheme,
+                        &refund,
+                    );
+                    Ok(refund)
                 }
-                enums::MerchantStorageScheme::RedisKv => {
-                    let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
-                    let field = format!("pa_{}_ref_{}", &this.attempt_id, &this.refund_id);
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let updated_refund = refund.clone().apply_changeset(this.clone());
 
                     let redis_value =
@@ -554,6 +649,12 @@ mod storage {
                     .try_into_hset()
                     .change_context(errors::StorageError::KVError)?;
 
+                    publish_refund_change_event(
+                        self,
+                        ChangeOperation::Update,
+                        storage_scheme,
+                        &updated_refund,
+                    );
                     Ok(updated_refund)
                 }
             }
@@ -574,7 +675,8 @@ mod storage {
             };
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let lookup_id = format!("{merchant_id}_{refund_id}");
                     let lookup = self
                         .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -619,7 +721,8 @@ mod storage {
             };
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let lookup_id = format!("{merchant_id}_{connector_refund_id}_{connector}");
                     let lookup = self
                         .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -662,7 +765,8 @@ mod storage {
             };
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let key = format!("mid_{merchant_id}_pid_{payment_id}");
                     db_utils::try_redis_get_else_try_database_get(
                         async {
@@ -684,16 +788,17 @@ mod storage {
         #[cfg(feature = "olap")]
         async fn filter_refund_by_constraints(
             &self,
-            merchant_id: &str,
+            merchant_ids: &[String],
             refund_details: &api_models::refunds::RefundListRequest,
             _storage_scheme: enums::MerchantStorageScheme,
             limit: i64,
             offset: i64,
+            consistency: storage_impl::ReadConsistency,
         ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_constraints(
                 &conn,
-                merchant_id,
+                merchant_ids,
                 refund_details,
                 limit,
                 offset,
@@ -709,8 +814,9 @@ mod storage {
             merchant_id: &str,
             refund_details: &api_models::refunds::TimeRange,
             _storage_scheme: enums::MerchantStorageScheme,
+            consistency: storage_impl::ReadConsistency,
         ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::filter_by_meta_constraints(&conn, merchant_id, refund_details)
                         .await
                         .map_err(Into::into)
@@ -720,20 +826,40 @@ mod storage {
         #[cfg(feature = "olap")]
         async fn get_total_count_of_refunds(
             &self,
-            merchant_id: &str,
+            merchant_ids: &[String],
             refund_details: &api_models::refunds::RefundListRequest,
             _storage_scheme: enums::MerchantStorageScheme,
+            consistency: storage_impl::ReadConsistency,
         ) -> CustomResult<i64, errors::StorageError> {
-            let conn = connection::pg_connection_read(self).await?;
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
             <diesel_models::refund::Refund as storage_types::RefundDbExt>::get_refunds_count(
                 &conn,
-                merchant_id,
+                merchant_ids,
                 refund_details,
             )
             .await
             .map_err(Into::into)
             .into_report()
         }
+
+        #[cfg(feature = "olap")]
+        async fn get_refund_aggregates(
+            &self,
+            merchant_ids: &[String],
+            time_range: &api_models::refunds::TimeRange,
+            _storage_scheme: enums::MerchantStorageScheme,
+            consistency: storage_impl::ReadConsistency,
+        ) -> CustomResult<Vec<storage_types::RefundAggregate>, errors::StorageError> {
+            let conn = connection::pg_connection_read_with_consistency(self, consistency).await?;
+            <diesel_models::refund::Refund as storage_types::RefundDbExt>::get_refund_aggregates(
+                &conn,
+                merchant_ids,
+                time_range,
+            )
+            .await
+            .map_err(Into::into)
+            .into_report()
+        }
     }
 }
 
@@ -899,11 +1025,12 @@ impl RefundInterface for MockDb {
     #[cfg(feature = "olap")]
     async fn filter_refund_by_constraints(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_details: &api_models::refunds::RefundListRequest,
         _storage_scheme: enums::MerchantStorageScheme,
         limit: i64,
         offset: i64,
+        _consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<Vec<diesel_models::refund::Refund>, errors::StorageError> {
         let mut unique_connectors = HashSet::new();
         let mut unique_currencies = HashSet::new();
@@ -931,7 +1058,7 @@ impl RefundInterface for MockDb {
         let refunds = self.refunds.lock().await;
         let filtered_refunds = refunds
             .iter()
-            .filter(|refund| refund.merchant_id == merchant_id)
+            .filter(|refund| merchant_ids.contains(&refund.merchant_id))
             .filter(|refund| {
                 refund_details
                     .payment_id
@@ -981,6 +1108,7 @@ impl RefundInterface for MockDb {
         _merchant_id: &str,
         refund_details: &api_models::refunds::TimeRange,
         _storage_scheme: enums::MerchantStorageScheme,
+        _consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<api_models::refunds::RefundListMetaData, errors::StorageError> {
         let refunds = self.refunds.lock().await;
 
@@ -1025,9 +1153,10 @@ impl RefundInterface for MockDb {
     #[cfg(feature = "olap")]
     async fn get_total_count_of_refunds(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_details: &api_models::refunds::RefundListRequest,
         _storage_scheme: enums::MerchantStorageScheme,
+        _consistency: storage_impl::ReadConsistency,
     ) -> CustomResult<i64, errors::StorageError> {
         let mut unique_connectors = HashSet::new();
         let mut unique_currencies = HashSet::new();
@@ -1055,7 +1184,7 @@ impl RefundInterface for MockDb {
         let refunds = self.refunds.lock().await;
         let filtered_refunds = refunds
             .iter()
-            .filter(|refund| refund.merchant_id == merchant_id)
+            .filter(|refund| merchant_ids.contains(&refund.merchant_id))
             .filter(|refund| {
                 refund_details
                     .payment_id
@@ -1098,4 +1227,44 @@ impl RefundInterface for MockDb {
 
         Ok(filtered_refunds_count)
     }
+
+    #[cfg(feature = "olap")]
+    async fn get_refund_aggregates(
+        &self,
+        merchant_ids: &[String],
+        time_range: &api_models::refunds::TimeRange,
+        _storage_scheme: enums::MerchantStorageScheme,
+        _consistency: storage_impl::ReadConsistency,
+    ) -> CustomResult<Vec<storage_types::RefundAggregate>, errors::StorageError> {
+        let refunds = self.refunds.lock().await;
+        let mut aggregates: Vec<storage_types::RefundAggregate> = Vec::new();
+
+        for refund in refunds.iter().filter(|refund| {
+            merchant_ids.contains(&refund.merchant_id)
+                && refund.created_at >= time_range.start_time
+                && time_range
+                    .end_time
+                    .map_or(true, |end_time| refund.created_at <= end_time)
+        }) {
+            match aggregates.iter_mut().find(|aggregate| {
+                aggregate.merchant_id == refund.merchant_id
+                    && aggregate.status == refund.refund_status
+                    && aggregate.currency == refund.currency
+            }) {
+                Some(aggregate) => {
+                    aggregate.count += 1;
+                    aggregate.total_amount += refund.refund_amount;
+                }
+                None => aggregates.push(storage_types::RefundAggregate {
+                    merchant_id: refund.merchant_id.clone(),
+                    status: refund.refund_status,
+                    currency: refund.currency,
+                    count: 1,
+                    total_amount: refund.refund_amount,
+                }),
+            }
+        }
+
+        Ok(aggregates)
+    }
 }
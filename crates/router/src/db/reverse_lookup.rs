@@ -64,7 +64,7 @@ mod storage {
 mod storage {
     use error_stack::{IntoReport, ResultExt};
     use redis_interface::SetnxReply;
-    use storage_impl::redis::kv_store::{kv_wrapper, KvOperation};
+    use storage_impl::redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op};
 
     use super::{ReverseLookupInterface, Store};
     use crate::{
@@ -84,12 +84,16 @@ mod storage {
             new: ReverseLookupNew,
             storage_scheme: enums::MerchantStorageScheme,
         ) -> CustomResult<ReverseLookup, errors::StorageError> {
+            let key = format!("reverse_lookup_{}", &new.lookup_id);
+            let storage_scheme =
+                decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => {
                     let conn = connection::pg_connection_write(self).await?;
                     new.insert(&conn).await.map_err(Into::into).into_report()
                 }
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let created_rev_lookup = ReverseLookup {
                         lookup_id: new.lookup_id.clone(),
                         sk_id: new.sk_id.clone(),
@@ -106,7 +110,7 @@ mod storage {
                     match kv_wrapper::<ReverseLookup, _, _>(
                         self,
                         KvOperation::SetNx(&created_rev_lookup, redis_entry),
-                        format!("reverse_lookup_{}", &created_rev_lookup.lookup_id),
+                        key,
                     )
                     .await
                     .change_context(errors::StorageError::KVError)?
@@ -139,7 +143,8 @@ mod storage {
 
             match storage_scheme {
                 enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-                enums::MerchantStorageScheme::RedisKv => {
+                enums::MerchantStorageScheme::RedisKv
+                | enums::MerchantStorageScheme::RedisKvDraining => {
                     let redis_fut = async {
                         kv_wrapper(
                             self,
@@ -0,0 +1,245 @@
+use common_utils::pii;
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait UserInterface {
+    async fn insert_user(
+        &self,
+        user_data: storage::UserNew,
+    ) -> CustomResult<storage::User, errors::StorageError>;
+
+    async fn find_user_by_email(
+        &self,
+        user_email: &pii::Email,
+    ) -> CustomResult<storage::User, errors::StorageError>;
+
+    async fn find_user_by_id(
+        &self,
+        user_id: &str,
+    ) -> CustomResult<storage::User, errors::StorageError>;
+
+    async fn update_user_by_user_id(
+        &self,
+        user_id: &str,
+        user: storage::UserUpdate,
+    ) -> CustomResult<storage::User, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl UserInterface for Store {
+    async fn insert_user(
+        &self,
+        user_data: storage::UserNew,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        user_data
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_user_by_email(
+        &self,
+        user_email: &pii::Email,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::User::find_by_user_email(&conn, user_email)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_user_by_id(
+        &self,
+        user_id: &str,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::User::find_by_user_id(&conn, user_id)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_user_by_user_id(
+        &self,
+        user_id: &str,
+        user: storage::UserUpdate,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::User::update_by_user_id(&conn, user_id, user)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl UserInterface for MockDb {
+    async fn insert_user(
+        &self,
+        user_data: storage::UserNew,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let mut users = self.users.lock().await;
+        if users
+            .iter()
+            .any(|user| user.email == user_data.email || user.user_id == user_data.user_id)
+        {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "email or user_id",
+                key: None,
+            })?
+        }
+        let user = storage::User {
+            id: i32::try_from(users.len())
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            user_id: user_data.user_id,
+            email: user_data.email,
+            name: user_data.name,
+            password: user_data.password,
+            is_verified: user_data.is_verified,
+            created_at: user_data.created_at,
+            last_modified_at: user_data.last_modified_at,
+        };
+        users.push(user.clone());
+        Ok(user)
+    }
+
+    async fn find_user_by_email(
+        &self,
+        user_email: &pii::Email,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let users = self.users.lock().await;
+        users
+            .iter()
+            .find(|user| user.email == *user_email)
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound("No user available for the email".to_string())
+                    .into(),
+            )
+    }
+
+    async fn find_user_by_id(
+        &self,
+        user_id: &str,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let users = self.users.lock().await;
+        users
+            .iter()
+            .find(|user| user.user_id == user_id)
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No user available for user_id = {user_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn update_user_by_user_id(
+        &self,
+        user_id: &str,
+        update_user: storage::UserUpdate,
+    ) -> CustomResult<storage::User, errors::StorageError> {
+        let mut users = self.users.lock().await;
+        users
+            .iter_mut()
+            .find(|user| user.user_id == user_id)
+            .map(|user| {
+                *user = update_user.apply_changeset(user.clone());
+                user.to_owned()
+            })
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No user available for user_id = {user_id}"
+                ))
+                .into(),
+            )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use common_utils::{date_time, pii};
+    use diesel_models::user as storage;
+    use masking::Secret;
+
+    use crate::{
+        core::errors,
+        db::{user::UserInterface, MockDb},
+    };
+
+    fn get_user_new(user_id: &str, email: &str) -> storage::UserNew {
+        let now = date_time::now();
+        storage::UserNew {
+            user_id: user_id.into(),
+            #[allow(clippy::unwrap_used)]
+            email: pii::Email::from_str(email).unwrap(),
+            name: Secret::new("Jane".into()),
+            password: None,
+            is_verified: false,
+            created_at: now,
+            last_modified_at: now,
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mockdb_user_interface() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        mockdb
+            .insert_user(get_user_new("user1", "jane@example.com"))
+            .await
+            .unwrap();
+
+        let error = mockdb
+            .insert_user(get_user_new("user2", "jane@example.com"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::StorageError::DuplicateValue { .. }
+        ));
+
+        let found_user = mockdb
+            .find_user_by_email(&pii::Email::from_str("jane@example.com").unwrap())
+            .await
+            .unwrap();
+        assert_eq!(found_user.user_id, "user1");
+        assert!(mockdb
+            .find_user_by_email(&pii::Email::from_str("john@example.com").unwrap())
+            .await
+            .is_err());
+
+        let user = mockdb
+            .update_user_by_user_id(
+                "user1",
+                storage::UserUpdate::VerifyUser {
+                    password: Some(Secret::new("password_hash".into())),
+                },
+            )
+            .await
+            .unwrap();
+        assert!(user.is_verified);
+        assert_eq!(user.password, Some(Secret::new("password_hash".into())));
+
+        let found_user = mockdb.find_user_by_id("user1").await.unwrap();
+        assert!(found_user.is_verified);
+        assert_eq!(found_user.name, Secret::new("Jane".into()));
+    }
+}
@@ -0,0 +1,352 @@
+use error_stack::{IntoReport, ResultExt};
+
+use super::{MockDb, Store};
+use crate::{
+    connection,
+    core::errors::{self, CustomResult},
+    types::storage,
+};
+
+#[async_trait::async_trait]
+pub trait UserRoleInterface {
+    async fn insert_user_role(
+        &self,
+        user_role: storage::UserRoleNew,
+    ) -> CustomResult<storage::UserRole, errors::StorageError>;
+
+    async fn find_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<storage::UserRole, errors::StorageError>;
+
+    async fn list_user_roles_by_user_id(
+        &self,
+        user_id: &str,
+    ) -> CustomResult<Vec<storage::UserRole>, errors::StorageError>;
+
+    async fn list_user_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::UserRole>, errors::StorageError>;
+
+    async fn update_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+        update: storage::UserRoleUpdate,
+    ) -> CustomResult<storage::UserRole, errors::StorageError>;
+
+    async fn delete_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError>;
+}
+
+#[async_trait::async_trait]
+impl UserRoleInterface for Store {
+    async fn insert_user_role(
+        &self,
+        user_role: storage::UserRoleNew,
+    ) -> CustomResult<storage::UserRole, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        user_role
+            .insert(&conn)
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn find_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<storage::UserRole, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::UserRole::find_by_user_id_merchant_id(
+            &conn,
+            user_id.to_owned(),
+            merchant_id.to_owned(),
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn list_user_roles_by_user_id(
+        &self,
+        user_id: &str,
+    ) -> CustomResult<Vec<storage::UserRole>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::UserRole::list_by_user_id(&conn, user_id.to_owned())
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn list_user_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::UserRole>, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::UserRole::list_by_merchant_id(&conn, merchant_id.to_owned())
+            .await
+            .map_err(Into::into)
+            .into_report()
+    }
+
+    async fn update_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+        update: storage::UserRoleUpdate,
+    ) -> CustomResult<storage::UserRole, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::UserRole::update_by_user_id_merchant_id(
+            &conn,
+            user_id.to_owned(),
+            merchant_id.to_owned(),
+            update,
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+
+    async fn delete_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let conn = connection::pg_connection_write(self).await?;
+        storage::UserRole::delete_by_user_id_merchant_id(
+            &conn,
+            user_id.to_owned(),
+            merchant_id.to_owned(),
+        )
+        .await
+        .map_err(Into::into)
+        .into_report()
+    }
+}
+
+#[async_trait::async_trait]
+impl UserRoleInterface for MockDb {
+    async fn insert_user_role(
+        &self,
+        user_role: storage::UserRoleNew,
+    ) -> CustomResult<storage::UserRole, errors::StorageError> {
+        let mut user_roles = self.user_roles.lock().await;
+        if user_roles.iter().any(|role| {
+            role.user_id == user_role.user_id && role.merchant_id == user_role.merchant_id
+        }) {
+            Err(errors::StorageError::DuplicateValue {
+                entity: "user_id and merchant_id",
+                key: None,
+            })?
+        }
+        let user_role = storage::UserRole {
+            id: i32::try_from(user_roles.len())
+                .into_report()
+                .change_context(errors::StorageError::MockDbError)?,
+            user_id: user_role.user_id,
+            merchant_id: user_role.merchant_id,
+            org_id: user_role.org_id,
+            role: user_role.role,
+            status: user_role.status,
+            created_by: user_role.created_by,
+            last_modified_by: user_role.last_modified_by,
+            created_at: user_role.created_at,
+            last_modified_at: user_role.last_modified_at,
+        };
+        user_roles.push(user_role.clone());
+        Ok(user_role)
+    }
+
+    async fn find_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<storage::UserRole, errors::StorageError> {
+        let user_roles = self.user_roles.lock().await;
+        user_roles
+            .iter()
+            .find(|role| role.user_id == user_id && role.merchant_id == merchant_id)
+            .cloned()
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No user role available for user_id = {user_id} and merchant_id = {merchant_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn list_user_roles_by_user_id(
+        &self,
+        user_id: &str,
+    ) -> CustomResult<Vec<storage::UserRole>, errors::StorageError> {
+        let user_roles = self.user_roles.lock().await;
+        Ok(user_roles
+            .iter()
+            .filter(|role| role.user_id == user_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn list_user_roles_by_merchant_id(
+        &self,
+        merchant_id: &str,
+    ) -> CustomResult<Vec<storage::UserRole>, errors::StorageError> {
+        let user_roles = self.user_roles.lock().await;
+        Ok(user_roles
+            .iter()
+            .filter(|role| role.merchant_id == merchant_id)
+            .cloned()
+            .collect())
+    }
+
+    async fn update_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+        update: storage::UserRoleUpdate,
+    ) -> CustomResult<storage::UserRole, errors::StorageError> {
+        let mut user_roles = self.user_roles.lock().await;
+        user_roles
+            .iter_mut()
+            .find(|role| role.user_id == user_id && role.merchant_id == merchant_id)
+            .map(|role| {
+                *role = update.apply_changeset(role.clone());
+                role.to_owned()
+            })
+            .ok_or(
+                errors::StorageError::ValueNotFound(format!(
+                    "No user role available for user_id = {user_id} and merchant_id = {merchant_id}"
+                ))
+                .into(),
+            )
+    }
+
+    async fn delete_user_role_by_user_id_merchant_id(
+        &self,
+        user_id: &str,
+        merchant_id: &str,
+    ) -> CustomResult<bool, errors::StorageError> {
+        let mut user_roles = self.user_roles.lock().await;
+        let initial_len = user_roles.len();
+        user_roles.retain(|role| !(role.user_id == user_id && role.merchant_id == merchant_id));
+        Ok(user_roles.len() < initial_len)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use common_utils::date_time;
+    use diesel_models::{
+        enums::{Role, UserStatus},
+        user_role as storage,
+    };
+
+    use crate::{
+        core::errors,
+        db::{user_role::UserRoleInterface, MockDb},
+    };
+
+    fn get_user_role_new(user_id: &str, merchant_id: &str) -> storage::UserRoleNew {
+        let now = date_time::now();
+        storage::UserRoleNew {
+            user_id: user_id.into(),
+            merchant_id: merchant_id.into(),
+            org_id: "org1".into(),
+            role: Role::Viewer,
+            status: UserStatus::InvitationSent,
+            created_by: "admin".into(),
+            last_modified_by: "admin".into(),
+            created_at: now,
+            last_modified_at: now,
+        }
+    }
+
+    #[allow(clippy::unwrap_used)]
+    #[tokio::test]
+    async fn test_mockdb_user_role_interface() {
+        #[allow(clippy::expect_used)]
+        let mockdb = MockDb::new(&redis_interface::RedisSettings::default())
+            .await
+            .expect("Failed to create Mock store");
+
+        mockdb
+            .insert_user_role(get_user_role_new("user1", "merchant1"))
+            .await
+            .unwrap();
+        mockdb
+            .insert_user_role(get_user_role_new("user1", "merchant2"))
+            .await
+            .unwrap();
+        mockdb
+            .insert_user_role(get_user_role_new("user2", "merchant1"))
+            .await
+            .unwrap();
+
+        let error = mockdb
+            .insert_user_role(get_user_role_new("user1", "merchant1"))
+            .await
+            .unwrap_err();
+        assert!(matches!(
+            error.current_context(),
+            errors::StorageError::DuplicateValue { .. }
+        ));
+
+        assert_eq!(
+            mockdb
+                .list_user_roles_by_user_id("user1")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+        assert_eq!(
+            mockdb
+                .list_user_roles_by_merchant_id("merchant1")
+                .await
+                .unwrap()
+                .len(),
+            2
+        );
+
+        let user_role = mockdb
+            .update_user_role_by_user_id_merchant_id(
+                "user1",
+                "merchant1",
+                storage::UserRoleUpdate::RoleUpdate {
+                    role: Role::Admin,
+                    modified_by: "user2".into(),
+                },
+            )
+            .await
+            .unwrap();
+        assert_eq!(user_role.role, Role::Admin);
+        assert_eq!(user_role.last_modified_by, "user2");
+
+        let found_user_role = mockdb
+            .find_user_role_by_user_id_merchant_id("user1", "merchant1")
+            .await
+            .unwrap();
+        assert_eq!(found_user_role.role, Role::Admin);
+        assert_eq!(found_user_role.status, UserStatus::InvitationSent);
+
+        assert!(mockdb
+            .delete_user_role_by_user_id_merchant_id("user1", "merchant1")
+            .await
+            .unwrap());
+        assert!(!mockdb
+            .delete_user_role_by_user_id_merchant_id("user1", "merchant1")
+            .await
+            .unwrap());
+        assert!(mockdb
+            .find_user_role_by_user_id_merchant_id("user1", "merchant1")
+            .await
+            .is_err());
+    }
+}
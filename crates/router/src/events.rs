@@ -1,15 +1,31 @@
+#[cfg(not(feature = "kafka_events"))]
+use error_stack::IntoReport;
 use serde::Serialize;
+use storage_impl::{
+    errors::ApplicationError,
+    events::{ChangeEvent, ChangeEventHandler, ChangeEventRecordType},
+};
+
+use crate::{configs::settings, core::errors::CustomResult};
 
 pub mod api_logs;
 pub mod event_logger;
+pub mod file;
+#[cfg(feature = "kafka_events")]
+pub mod kafka;
+pub mod sink;
 
+#[async_trait::async_trait]
 pub trait EventHandler: Sync + Send + dyn_clone::DynClone {
     fn log_event(&self, event: RawEvent);
+
+    /// Publishes the events raised so far, before the application exits.
+    async fn shutdown(&self) {}
 }
 
 dyn_clone::clone_trait_object!(EventHandler);
 
-#[derive(Debug)]
+#[derive(Debug, Serialize)]
 pub struct RawEvent {
     pub event_type: EventType,
     pub key: String,
@@ -23,4 +39,55 @@ pub enum EventType {
     PaymentAttempt,
     Refund,
     ApiLogs,
+    AuditEvent,
+}
+
+/// Creates the event handler publishing events to the configured source.
+pub fn get_event_handler(
+    conf: &settings::EventsConfig,
+) -> CustomResult<Box<dyn EventHandler>, ApplicationError> {
+    Ok(match conf.source {
+        settings::EventSource::Logs => Box::<event_logger::EventLogger>::default(),
+        #[cfg(feature = "kafka_events")]
+        settings::EventSource::Kafka => Box::new(sink::BufferedEventHandler::new(
+            kafka::KafkaProducer::create(&conf.kafka)?,
+            &conf.buffer,
+        )),
+        #[cfg(not(feature = "kafka_events"))]
+        settings::EventSource::Kafka => Err(ApplicationError::InvalidConfigurationValueError(
+            "events source kafka requires the router to be built with the kafka_events feature"
+                .into(),
+        ))
+        .into_report()?,
+        settings::EventSource::File => Box::new(sink::BufferedEventHandler::new(
+            file::FileEventSink::create(&conf.file)?,
+            &conf.buffer,
+        )),
+    })
+}
+
+/// Publishes the change data events of the store through the event handler.
+#[derive(Clone)]
+pub struct ChangeEventForwarder(pub Box<dyn EventHandler>);
+
+impl std::fmt::Debug for ChangeEventForwarder {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ChangeEventForwarder").finish()
+    }
+}
+
+impl ChangeEventHandler for ChangeEventForwarder {
+    fn publish_change_event(&self, event: ChangeEvent) {
+        let event_type = match event.record_type {
+            ChangeEventRecordType::PaymentIntent => EventType::PaymentIntent,
+            ChangeEventRecordType::PaymentAttempt => EventType::PaymentAttempt,
+            ChangeEventRecordType::Refund => EventType::Refund,
+        };
+
+        self.0.log_event(RawEvent {
+            event_type,
+            key: event.key,
+            payload: event.payload,
+        });
+    }
 }
@@ -3,6 +3,7 @@ use serde::{Deserialize, Serialize};
 use time::OffsetDateTime;
 
 use super::{EventType, RawEvent};
+use crate::services::authentication::AuthenticationType;
 
 #[derive(Clone, Debug, Eq, PartialEq, Serialize, Deserialize)]
 pub struct ApiEvent {
@@ -13,6 +14,8 @@ pub struct ApiEvent {
     status_code: i64,
     request: serde_json::Value,
     response: Option<serde_json::Value>,
+    #[serde(flatten)]
+    auth_type: AuthenticationType,
 }
 
 impl ApiEvent {
@@ -23,6 +26,7 @@ impl ApiEvent {
         status_code: i64,
         request: serde_json::Value,
         response: Option<serde_json::Value>,
+        auth_type: AuthenticationType,
     ) -> Self {
         Self {
             api_flow: api_flow.to_string(),
@@ -32,6 +36,7 @@ impl ApiEvent {
             status_code,
             request,
             response,
+            auth_type,
         }
     }
 }
@@ -0,0 +1,73 @@
+//! Appending events to a newline delimited JSON file, to inspect the events raised while testing
+//! locally.
+
+use std::{
+    fs::{File, OpenOptions},
+    io::{BufWriter, Write},
+    sync::{Arc, Mutex},
+};
+
+use error_stack::{IntoReport, ResultExt};
+use storage_impl::errors::ApplicationError;
+
+use super::{sink::EventSink, RawEvent};
+use crate::{configs::settings, core::errors::CustomResult, services::logger};
+
+pub struct FileEventSink {
+    file: Arc<Mutex<File>>,
+}
+
+impl FileEventSink {
+    pub fn create(conf: &settings::FileEventSinkSettings) -> CustomResult<Self, ApplicationError> {
+        let file = OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(&conf.path)
+            .into_report()
+            .change_context(ApplicationError::InvalidConfigurationValueError(
+                "Failed to open events file".into(),
+            ))
+            .attach_printable_lazy(|| format!("path: {}", conf.path.display()))?;
+
+        Ok(Self {
+            file: Arc::new(Mutex::new(file)),
+        })
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for FileEventSink {
+    fn name(&self) -> &'static str {
+        "file"
+    }
+
+    async fn publish_batch(&self, events: Vec<RawEvent>) -> usize {
+        let file = self.file.clone();
+        let event_count = events.len();
+
+        let written = tokio::task::spawn_blocking(move || {
+            let mut file = file
+                .lock()
+                .map_err(|_| std::io::Error::new(std::io::ErrorKind::Other, "lock poisoned"))?;
+            let mut writer = BufWriter::new(&mut *file);
+            for event in &events {
+                serde_json::to_writer(&mut writer, event)?;
+                writer.write_all(b"\n")?;
+            }
+            writer.flush()
+        })
+        .await;
+
+        match written {
+            Ok(Ok(())) => 0,
+            Ok(Err(error)) => {
+                logger::error!(?error, "Failed to write events to file");
+                event_count
+            }
+            Err(error) => {
+                logger::error!(?error, "Failed to write events to file");
+                event_count
+            }
+        }
+    }
+}
@@ -0,0 +1,134 @@
+//! Producing events to Kafka, or a Kafka-compatible broker.
+
+use std::time::Duration;
+
+use error_stack::{IntoReport, ResultExt};
+use rdkafka::{
+    config::ClientConfig,
+    producer::{FutureProducer, FutureRecord, Producer},
+};
+use storage_impl::errors::ApplicationError;
+
+use super::{sink::EventSink, EventType, RawEvent};
+use crate::{configs::settings, core::errors::CustomResult, services::logger};
+
+/// The maximum time spent delivering the queued events when the application exits.
+const FLUSH_TIMEOUT: Duration = Duration::from_secs(5);
+
+pub struct KafkaProducer {
+    producer: FutureProducer,
+    topics: settings::EventTopics,
+}
+
+impl KafkaProducer {
+    pub fn create(conf: &settings::KafkaSettings) -> CustomResult<Self, ApplicationError> {
+        let producer = ClientConfig::new()
+            .set("bootstrap.servers", conf.brokers.join(","))
+            .set(
+                "message.timeout.ms",
+                conf.message_timeout_in_milliseconds.to_string(),
+            )
+            .create()
+            .into_report()
+            .change_context(ApplicationError::InvalidConfigurationValueError(
+                "Failed to create Kafka producer".into(),
+            ))?;
+
+        Ok(Self {
+            producer,
+            topics: conf.topics.clone(),
+        })
+    }
+
+    fn get_topic(&self, event_type: EventType) -> &str {
+        match event_type {
+            EventType::PaymentIntent => &self.topics.payment_intent,
+            EventType::PaymentAttempt => &self.topics.payment_attempt,
+            EventType::Refund => &self.topics.refund,
+            EventType::ApiLogs => &self.topics.api_logs,
+            EventType::AuditEvent => &self.topics.audit_event,
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventSink for KafkaProducer {
+    fn name(&self) -> &'static str {
+        "kafka"
+    }
+
+    async fn publish_batch(&self, events: Vec<RawEvent>) -> usize {
+        let mut failed = 0;
+        let mut deliveries = Vec::with_capacity(events.len());
+
+        // All events of the batch are enqueued before waiting for any of them to be delivered
+        for event in &events {
+            let payload = match serde_json::to_vec(&event.payload) {
+                Ok(payload) => payload,
+                Err(error) => {
+                    logger::error!(
+                        ?error,
+                        event_type = ?event.event_type,
+                        event_id = ?event.key,
+                        "Failed to serialize event"
+                    );
+                    failed += 1;
+                    continue;
+                }
+            };
+            let record = FutureRecord::to(self.get_topic(event.event_type))
+                .key(&event.key)
+                .payload(&payload);
+
+            match self.producer.send_result(record) {
+                Ok(delivery) => deliveries.push((event, delivery)),
+                Err((error, _)) => {
+                    logger::error!(
+                        ?error,
+                        event_type = ?event.event_type,
+                        event_id = ?event.key,
+                        "Failed to enqueue event to Kafka"
+                    );
+                    failed += 1;
+                }
+            }
+        }
+
+        for (event, delivery) in deliveries {
+            match delivery.await {
+                Ok(Ok(_)) => {}
+                Ok(Err((error, _))) => {
+                    logger::error!(
+                        ?error,
+                        event_type = ?event.event_type,
+                        event_id = ?event.key,
+                        "Failed to deliver event to Kafka"
+                    );
+                    failed += 1;
+                }
+                Err(_) => {
+                    logger::error!(
+                        event_type = ?event.event_type,
+                        event_id = ?event.key,
+                        "Kafka producer dropped event before delivering it"
+                    );
+                    failed += 1;
+                }
+            }
+        }
+
+        failed
+    }
+
+    async fn flush(&self) {
+        let producer = self.producer.clone();
+        // Flushing blocks the calling thread until the queued events are delivered
+        let flushed = tokio::task::spawn_blocking(move || producer.flush(FLUSH_TIMEOUT)).await;
+
+        match flushed {
+            Ok(Ok(())) => {}
+            Ok(Err(error)) => logger::error!(?error, "Failed to flush Kafka producer"),
+            Err(error) => logger::error!(?error, "Failed to flush Kafka producer"),
+        }
+    }
+}
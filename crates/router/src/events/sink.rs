@@ -0,0 +1,277 @@
+//! Publishing events to external sinks in batches, off the request path.
+
+use std::{sync::Arc, time::Duration};
+
+use tokio::{
+    sync::{mpsc, Mutex, Notify},
+    task::JoinHandle,
+};
+
+use super::{EventHandler, RawEvent};
+use crate::{configs::settings, routes::metrics, services::logger};
+
+/// A destination that events are published to, such as an event stream.
+#[async_trait::async_trait]
+pub trait EventSink: Send + Sync {
+    /// The name of the sink, that the metrics of the events published to it are recorded with.
+    fn name(&self) -> &'static str;
+
+    /// Publishes a batch of events, returning the number of events that could not be delivered.
+    /// The reason the events could not be delivered is logged by the sink.
+    async fn publish_batch(&self, events: Vec<RawEvent>) -> usize;
+
+    /// Delivers the events still queued by the sink, once no more events will be published to it.
+    async fn flush(&self) {}
+}
+
+/// Buffers the events raised by the application in a bounded buffer, and publishes them to an
+/// event sink in batches from a background task, so that raising an event never blocks a request.
+#[derive(Clone, Debug)]
+pub struct BufferedEventHandler {
+    sender: mpsc::Sender<RawEvent>,
+    sink_name: &'static str,
+    shutdown_signal: Arc<Notify>,
+    publisher: Arc<Mutex<Option<JoinHandle<()>>>>,
+}
+
+impl BufferedEventHandler {
+    /// Spawns the task publishing buffered events to the sink, which runs until the handler is shut
+    /// down or all handlers are dropped.
+    pub fn new<S>(sink: S, settings: &settings::EventBufferSettings) -> Self
+    where
+        S: EventSink + 'static,
+    {
+        let (sender, receiver) = mpsc::channel(settings.capacity);
+        let sink_name = sink.name();
+        let shutdown_signal = Arc::new(Notify::new());
+
+        let publisher = tokio::spawn(publish_buffered_events(
+            sink,
+            receiver,
+            shutdown_signal.clone(),
+            settings.batch_size,
+            Duration::from_millis(settings.flush_interval_in_milliseconds),
+        ));
+
+        Self {
+            sender,
+            sink_name,
+            shutdown_signal,
+            publisher: Arc::new(Mutex::new(Some(publisher))),
+        }
+    }
+}
+
+#[async_trait::async_trait]
+impl EventHandler for BufferedEventHandler {
+    fn log_event(&self, event: RawEvent) {
+        match self.sender.try_send(event) {
+            Ok(()) => {}
+            Err(mpsc::error::TrySendError::Full(event)) => {
+                logger::warn!(
+                    event_type = ?event.event_type,
+                    event_id = ?event.key,
+                    "Event buffer is full, dropping event"
+                );
+                metrics::EVENTS_DROPPED.add(
+                    &metrics::CONTEXT,
+                    1,
+                    &[metrics::request::add_attributes("sink", self.sink_name)],
+                );
+            }
+            Err(mpsc::error::TrySendError::Closed(event)) => {
+                logger::error!(
+                    event_type = ?event.event_type,
+                    event_id = ?event.key,
+                    "Event publisher has stopped, dropping event"
+                );
+                metrics::EVENTS_DROPPED.add(
+                    &metrics::CONTEXT,
+                    1,
+                    &[metrics::request::add_attributes("sink", self.sink_name)],
+                );
+            }
+        }
+    }
+
+    async fn shutdown(&self) {
+        self.shutdown_signal.notify_one();
+
+        if let Some(publisher) = self.publisher.lock().await.take() {
+            if let Err(error) = publisher.await {
+                logger::error!(?error, "Event publisher failed while shutting down");
+            }
+        }
+    }
+}
+
+async fn publish_buffered_events<S: EventSink>(
+    sink: S,
+    mut receiver: mpsc::Receiver<RawEvent>,
+    shutdown_signal: Arc<Notify>,
+    batch_size: usize,
+    flush_interval: Duration,
+) {
+    let mut batch = Vec::with_capacity(batch_size);
+    let mut flush_interval = tokio::time::interval(flush_interval);
+    // The first tick of an interval completes immediately
+    flush_interval.tick().await;
+
+    loop {
+        tokio::select! {
+            event = receiver.recv() => match event {
+                Some(event) => {
+                    batch.push(event);
+                    if batch.len() >= batch_size {
+                        publish_batch(&sink, &mut batch).await;
+                    }
+                }
+                // All handlers have been dropped, publish the remaining events and stop
+                None => {
+                    publish_batch(&sink, &mut batch).await;
+                    break;
+                }
+            },
+            _ = flush_interval.tick() => publish_batch(&sink, &mut batch).await,
+            // The application is exiting, stop accepting events and publish the buffered ones
+            _ = shutdown_signal.notified() => {
+                receiver.close();
+                while let Some(event) = receiver.recv().await {
+                    batch.push(event);
+                    if batch.len() >= batch_size {
+                        publish_batch(&sink, &mut batch).await;
+                    }
+                }
+                publish_batch(&sink, &mut batch).await;
+                break;
+            }
+        }
+    }
+
+    sink.flush().await;
+}
+
+async fn publish_batch<S: EventSink>(sink: &S, batch: &mut Vec<RawEvent>) {
+    if batch.is_empty() {
+        return;
+    }
+
+    let events = std::mem::take(batch);
+    let published = u64::try_from(events.len()).unwrap_or(u64::MAX);
+    let failed = u64::try_from(sink.publish_batch(events).await).unwrap_or(u64::MAX);
+
+    let attributes = [metrics::request::add_attributes("sink", sink.name())];
+    metrics::EVENTS_PUBLISHED.add(
+        &metrics::CONTEXT,
+        published.saturating_sub(failed),
+        &attributes,
+    );
+    if failed > 0 {
+        metrics::EVENT_DELIVERY_FAILURES.add(&metrics::CONTEXT, failed, &attributes);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+    use crate::events::EventType;
+
+    #[derive(Clone, Default)]
+    struct RecordingSink {
+        batches: Arc<std::sync::Mutex<Vec<Vec<String>>>>,
+        flushed: Arc<std::sync::Mutex<bool>>,
+        flush_signal: Arc<Notify>,
+    }
+
+    impl RecordingSink {
+        /// Waits until the publisher has flushed the sink, which it does once it has stopped.
+        async fn wait_for_flush(&self) {
+            tokio::time::timeout(Duration::from_secs(5), self.flush_signal.notified())
+                .await
+                .unwrap();
+        }
+    }
+
+    #[async_trait::async_trait]
+    impl EventSink for RecordingSink {
+        fn name(&self) -> &'static str {
+            "recording"
+        }
+
+        async fn publish_batch(&self, events: Vec<RawEvent>) -> usize {
+            self.batches
+                .lock()
+                .unwrap()
+                .push(events.into_iter().map(|event| event.key).collect());
+            0
+        }
+
+        async fn flush(&self) {
+            *self.flushed.lock().unwrap() = true;
+            self.flush_signal.notify_one();
+        }
+    }
+
+    fn event(key: &str) -> RawEvent {
+        RawEvent {
+            event_type: EventType::ApiLogs,
+            key: key.to_string(),
+            payload: serde_json::Value::Null,
+        }
+    }
+
+    #[tokio::test]
+    async fn test_events_are_published_in_batches() {
+        let sink = RecordingSink::default();
+        let handler = BufferedEventHandler::new(
+            sink.clone(),
+            &settings::EventBufferSettings {
+                capacity: 10,
+                batch_size: 2,
+                flush_interval_in_milliseconds: 60_000,
+            },
+        );
+
+        for key in ["1", "2", "3"] {
+            handler.log_event(event(key));
+        }
+        // The last event is published when the handler is dropped, before the flush interval
+        drop(handler);
+        sink.wait_for_flush().await;
+
+        assert_eq!(
+            *sink.batches.lock().unwrap(),
+            vec![
+                vec!["1".to_string(), "2".to_string()],
+                vec!["3".to_string()]
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn test_buffered_events_are_published_on_shutdown() {
+        let sink = RecordingSink::default();
+        let handler = BufferedEventHandler::new(
+            sink.clone(),
+            &settings::EventBufferSettings {
+                capacity: 10,
+                batch_size: 10,
+                flush_interval_in_milliseconds: 60_000,
+            },
+        );
+
+        for key in ["1", "2"] {
+            handler.log_event(event(key));
+        }
+        // A clone of the handler is still alive, so the publisher only stops because of the shutdown
+        let _other_handler = handler.clone();
+        handler.shutdown().await;
+
+        assert_eq!(
+            *sink.batches.lock().unwrap(),
+            vec![vec!["1".to_string(), "2".to_string()]]
+        );
+        assert!(*sink.flushed.lock().unwrap());
+    }
+}
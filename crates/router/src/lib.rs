@@ -24,7 +24,7 @@ pub mod utils;
 
 use actix_web::{
     body::MessageBody,
-    dev::{Server, ServerHandle, ServiceFactory, ServiceRequest},
+    dev::{ServerHandle, ServiceFactory, ServiceRequest},
     middleware::ErrorHandlers,
 };
 use http::StatusCode;
@@ -53,6 +53,7 @@ pub mod headers {
     pub const CONTENT_TYPE: &str = "Content-Type";
     pub const DATE: &str = "Date";
     pub const IDEMPOTENCY_KEY: &str = "Idempotency-Key";
+    pub const IDEMPOTENT_REPLAYED: &str = "Idempotent-Replayed";
     pub const NONCE: &str = "nonce";
     pub const TIMESTAMP: &str = "Timestamp";
     pub const TOKEN: &str = "token";
@@ -60,6 +61,7 @@ pub mod headers {
     pub const X_API_VERSION: &str = "X-ApiVersion";
     pub const X_FORWARDED_FOR: &str = "X-Forwarded-For";
     pub const X_MERCHANT_ID: &str = "X-Merchant-Id";
+    pub const X_TENANT_ID: &str = "X-Tenant-Id";
     pub const X_LOGIN: &str = "X-Login";
     pub const X_TRANS_KEY: &str = "X-Trans-Key";
     pub const X_VERSION: &str = "X-Version";
@@ -131,19 +133,25 @@ pub fn mk_app(
             .service(routes::PaymentMethods::server(state.clone()))
             .service(routes::EphemeralKey::server(state.clone()))
             .service(routes::Webhooks::server(state.clone()))
-            .service(routes::PaymentLink::server(state.clone()));
+            .service(routes::PaymentLink::server(state.clone()))
+            .service(routes::Invoices::server(state.clone()))
+            .service(routes::Subscriptions::server(state.clone()));
     }
 
     #[cfg(feature = "olap")]
     {
         server_app = server_app
             .service(routes::MerchantAccount::server(state.clone()))
+            .service(routes::Organization::server(state.clone()))
+            .service(routes::User::server(state.clone()))
             .service(routes::ApiKeys::server(state.clone()))
+            .service(routes::AuditEvents::server(state.clone()))
             .service(routes::Files::server(state.clone()))
             .service(routes::Disputes::server(state.clone()))
+            .service(routes::Analytics::server(state.clone()))
     }
 
-    #[cfg(all(feature = "olap", feature = "kms"))]
+    #[cfg(feature = "olap")]
     {
         server_app = server_app.service(routes::Verify::server(state.clone()));
     }
@@ -164,13 +172,16 @@ pub fn mk_app(
     server_app
 }
 
-/// Starts the server
+/// Starts the server, returning a future that completes once the server has stopped and the
+/// events raised by the application have been published
 ///
 /// # Panics
 ///
 ///  Unwrap used because without the value we can't start the server
 #[allow(clippy::expect_used, clippy::unwrap_used)]
-pub async fn start_server(conf: settings::Settings) -> ApplicationResult<Server> {
+pub async fn start_server(
+    conf: settings::Settings,
+) -> ApplicationResult<impl std::future::Future<Output = std::io::Result<()>>> {
     logger::debug!(startup_config=?conf);
     let server = conf.server.clone();
     let (tx, rx) = oneshot::channel();
@@ -185,13 +196,18 @@ pub async fn start_server(conf: settings::Settings) -> ApplicationResult<Server>
     );
     let state = routes::AppState::new(conf, tx, api_client).await;
     let request_body_limit = server.request_body_limit;
+    let event_handler = state.event_handler.clone();
     let server = actix_web::HttpServer::new(move || mk_app(state.clone(), request_body_limit))
         .bind((server.host.as_str(), server.port))?
         .workers(server.workers)
         .shutdown_timeout(server.shutdown_timeout)
         .run();
     tokio::spawn(receiver_for_error(rx, server.handle()));
-    Ok(server)
+    Ok(async move {
+        let result = server.await;
+        event_handler.shutdown().await;
+        result
+    })
 }
 
 pub async fn receiver_for_error(rx: oneshot::Receiver<()>, mut server: impl Stop) {
@@ -224,6 +240,15 @@ impl Stop for mpsc::Sender<()> {
     }
 }
 
+#[async_trait::async_trait]
+impl Stop for Vec<mpsc::Sender<()>> {
+    async fn stop_server(&mut self) {
+        for sender in self.iter_mut() {
+            sender.stop_server().await;
+        }
+    }
+}
+
 pub fn get_application_builder(
     request_body_limit: usize,
 ) -> actix_web::App<
@@ -62,6 +62,8 @@ Never share your secret api keys. Keep them guarded and secure.
         // (name = "API Key", description = "Create and manage API Keys"),
         (name = "Payouts", description = "Create and manage payouts"),
         (name = "payment link", description = "Create payment link"),
+        (name = "Invoices", description = "Create, finalize and void invoices billed to customers"),
+        (name = "Subscriptions", description = "Create subscription plans and subscribe customers to them"),
     ),
     paths(
         crate::routes::refunds::refunds_create,
@@ -80,6 +82,7 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::admin::payment_connector_delete,
         crate::routes::mandates::get_mandate,
         crate::routes::mandates::revoke_mandate,
+        crate::routes::mandates::retrieve_mandate_payments,
         crate::routes::payments::payments_create,
     // crate::routes::payments::payments_start,
         crate::routes::payments::payments_retrieve,
@@ -106,6 +109,7 @@ Never share your secret api keys. Keep them guarded and secure.
         // crate::routes::api_keys::api_key_retrieve,
         // crate::routes::api_keys::api_key_update,
         // crate::routes::api_keys::api_key_revoke,
+        // crate::routes::api_keys::api_key_rotate,
         // crate::routes::api_keys::api_key_list,
         crate::routes::disputes::retrieve_disputes_list,
         crate::routes::disputes::retrieve_dispute,
@@ -114,7 +118,23 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::routes::payouts::payouts_fulfill,
         crate::routes::payouts::payouts_retrieve,
         crate::routes::payouts::payouts_update,
-        crate::routes::payment_link::payment_link_retrieve
+        crate::routes::payment_link::payment_link_retrieve,
+        crate::routes::payment_link::payment_links_list,
+        crate::routes::payment_link::reusable_payment_link_create,
+        crate::routes::payment_link::reusable_payment_link_retrieve,
+        crate::routes::payment_link::reusable_payment_link_update,
+        crate::routes::invoices::invoice_create,
+        crate::routes::invoices::invoice_retrieve,
+        crate::routes::invoices::invoice_finalize,
+        crate::routes::invoices::invoice_void,
+        crate::routes::invoices::invoice_pdf,
+        crate::routes::subscriptions::subscription_plan_create,
+        crate::routes::subscriptions::subscription_plan_retrieve,
+        crate::routes::subscriptions::subscription_plan_update,
+        crate::routes::subscriptions::subscription_create,
+        crate::routes::subscriptions::subscription_retrieve,
+        crate::routes::subscriptions::subscription_update,
+        crate::routes::subscriptions::subscription_cancel
     ),
     components(schemas(
         crate::types::api::refunds::RefundRequest,
@@ -157,6 +177,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::PaymentMethod,
         api_models::enums::PaymentMethodIssuerCode,
         api_models::enums::MandateStatus,
+        api_models::enums::MandateFrequencyInterval,
         api_models::enums::PaymentExperience,
         api_models::enums::BankNames,
         api_models::enums::CardNetwork,
@@ -170,6 +191,10 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::enums::AttemptStatus,
         api_models::enums::CaptureStatus,
         api_models::enums::ReconStatus,
+        api_models::enums::PaymentLinkStatus,
+        api_models::enums::InvoiceStatus,
+        api_models::enums::SubscriptionInterval,
+        api_models::enums::SubscriptionStatus,
         api_models::admin::MerchantConnectorCreate,
         api_models::admin::MerchantConnectorUpdate,
         api_models::admin::PrimaryBusinessDetails,
@@ -182,6 +207,8 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::admin::MerchantConnectorWebhookDetails,
         api_models::admin::PaymentLinkConfig,
         api_models::admin::PaymentLinkColorSchema,
+        api_models::admin::MerchantEmailConfig,
+        api_models::admin::TransactionalEmailEvent,
         api_models::disputes::DisputeResponse,
         api_models::disputes::DisputeResponsePaymentsRetrieve,
         api_models::payments::AddressDetails,
@@ -309,6 +336,7 @@ Never share your secret api keys. Keep them guarded and secure.
         api_models::mandates::MandateRevokedResponse,
         api_models::mandates::MandateResponse,
         api_models::mandates::MandateCardDetails,
+        api_models::mandates::MandatePaymentResponse,
         api_models::ephemeral_key::EphemeralKeyCreateResponse,
         api_models::payments::CustomerDetails,
         api_models::payments::GiftCardData,
@@ -342,12 +370,30 @@ Never share your secret api keys. Keep them guarded and secure.
         crate::types::api::api_keys::CreateApiKeyResponse,
         crate::types::api::api_keys::RetrieveApiKeyResponse,
         crate::types::api::api_keys::RevokeApiKeyResponse,
+        crate::types::api::api_keys::RotateApiKeyRequest,
+        crate::types::api::api_keys::RotateApiKeyResponse,
         crate::types::api::api_keys::UpdateApiKeyRequest,
         api_models::payments::RetrievePaymentLinkRequest,
         api_models::payments::PaymentLinkResponse,
         api_models::payments::RetrievePaymentLinkResponse,
         api_models::payments::PaymentLinkInitiateRequest,
-        api_models::payments::PaymentLinkObject
+        api_models::payments::PaymentLinkObject,
+        api_models::payments::PaymentLinkListResponse,
+        api_models::payments::ReusablePaymentLinkCreateRequest,
+        api_models::payments::ReusablePaymentLinkUpdateRequest,
+        api_models::payments::ReusablePaymentLinkResponse,
+        api_models::invoices::InvoiceCreateRequest,
+        api_models::invoices::InvoiceLineItemRequest,
+        api_models::invoices::InvoiceTaxLineRequest,
+        api_models::invoices::InvoiceLineItem,
+        api_models::invoices::InvoiceTaxLine,
+        api_models::invoices::InvoiceResponse,
+        api_models::subscriptions::SubscriptionPlanCreateRequest,
+        api_models::subscriptions::SubscriptionPlanUpdateRequest,
+        api_models::subscriptions::SubscriptionPlanResponse,
+        api_models::subscriptions::SubscriptionCreateRequest,
+        api_models::subscriptions::SubscriptionUpdateRequest,
+        api_models::subscriptions::SubscriptionResponse
     )),
     modifiers(&SecurityAddon)
 )]
@@ -1,6 +1,10 @@
 pub mod admin;
+#[cfg(feature = "olap")]
+pub mod analytics;
 pub mod api_keys;
 pub mod app;
+#[cfg(feature = "olap")]
+pub mod audit_events;
 pub mod cache;
 pub mod cards_info;
 pub mod configs;
@@ -11,16 +15,22 @@ pub mod dummy_connector;
 pub mod ephemeral_key;
 pub mod files;
 pub mod health;
+pub mod invoices;
 pub mod lock_utils;
 pub mod mandates;
 pub mod metrics;
+#[cfg(feature = "olap")]
+pub mod organization;
 pub mod payment_link;
 pub mod payment_methods;
 pub mod payments;
 #[cfg(feature = "payouts")]
 pub mod payouts;
 pub mod refunds;
-#[cfg(all(feature = "olap", feature = "kms"))]
+pub mod subscriptions;
+#[cfg(feature = "olap")]
+pub mod user;
+#[cfg(feature = "olap")]
 pub mod verification;
 pub mod webhooks;
 
@@ -28,12 +38,13 @@ pub mod webhooks;
 pub use self::app::DummyConnector;
 #[cfg(feature = "payouts")]
 pub use self::app::Payouts;
-#[cfg(all(feature = "olap", feature = "kms"))]
+#[cfg(feature = "olap")]
 pub use self::app::Verify;
 pub use self::app::{
-    ApiKeys, AppState, BusinessProfile, Cache, Cards, Configs, Customers, Disputes, EphemeralKey,
-    Files, Health, Mandates, MerchantAccount, MerchantConnectorAccount, PaymentLink,
-    PaymentMethods, Payments, Refunds, Webhooks,
+    Analytics, ApiKeys, AppState, AuditEvents, BusinessProfile, Cache, Cards, Configs, Customers,
+    Disputes, EphemeralKey, Files, Health, Invoices, Mandates, MerchantAccount,
+    MerchantConnectorAccount, Organization, PaymentLink, PaymentMethods, Payments, Refunds,
+    Subscriptions, User, Webhooks,
 };
 #[cfg(feature = "stripe")]
 pub use super::compatibility::stripe::StripeApis;
@@ -1,9 +1,10 @@
 use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::user::Permission;
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
 use crate::{
-    core::{admin::*, api_locking},
+    core::{admin::*, api_locking, merchant_key_rotation, rate_limit},
     services::{api, authentication as auth},
     types::api::admin,
 };
@@ -64,7 +65,10 @@ pub async fn retrieve_merchant_account(
 ) -> HttpResponse {
     let flow = Flow::MerchantsAccountRetrieve;
     let merchant_id = mid.into_inner();
-    let payload = web::Json(admin::MerchantId { merchant_id }).into_inner();
+    let payload = web::Json(admin::MerchantId {
+        merchant_id: merchant_id.clone(),
+    })
+    .into_inner();
 
     api::server_wrap(
         flow,
@@ -72,7 +76,14 @@ pub async fn retrieve_merchant_account(
         &req,
         payload,
         |state, _, req| get_merchant_account(state, req),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantAccountRead,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -130,7 +141,14 @@ pub async fn update_merchant_account(
         &req,
         json_payload.into_inner(),
         |state, _, req| merchant_account_update(state, &merchant_id, req),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantAccountWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -203,7 +221,14 @@ pub async fn payment_connector_create(
         &req,
         json_payload.into_inner(),
         |state, _, req| create_payment_connector(state, req, &merchant_id),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantConnectorAccountWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -236,7 +261,7 @@ pub async fn payment_connector_retrieve(
     let flow = Flow::MerchantConnectorsRetrieve;
     let (merchant_id, merchant_connector_id) = path.into_inner();
     let payload = web::Json(admin::MerchantConnectorId {
-        merchant_id,
+        merchant_id: merchant_id.clone(),
         merchant_connector_id,
     })
     .into_inner();
@@ -249,7 +274,14 @@ pub async fn payment_connector_retrieve(
         |state, _, req| {
             retrieve_payment_connector(state, req.merchant_id, req.merchant_connector_id)
         },
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantConnectorAccountRead,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -285,9 +317,16 @@ pub async fn payment_connector_list(
         flow,
         state,
         &req,
-        merchant_id,
+        merchant_id.clone(),
         |state, _, merchant_id| list_payment_connectors(state, merchant_id),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantConnectorAccountRead,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -328,7 +367,14 @@ pub async fn payment_connector_update(
         &req,
         json_payload.into_inner(),
         |state, _, req| update_payment_connector(state, &merchant_id, &merchant_connector_id, req),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantConnectorAccountWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -362,7 +408,7 @@ pub async fn payment_connector_delete(
     let (merchant_id, merchant_connector_id) = path.into_inner();
 
     let payload = web::Json(admin::MerchantConnectorId {
-        merchant_id,
+        merchant_id: merchant_id.clone(),
         merchant_connector_id,
     })
     .into_inner();
@@ -372,7 +418,14 @@ pub async fn payment_connector_delete(
         &req,
         payload,
         |state, _, req| delete_payment_connector(state, req.merchant_id, req.merchant_connector_id),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::MerchantConnectorAccountWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -528,3 +581,129 @@ pub async fn merchant_account_kv_status(
     )
     .await
 }
+/// Merchant Account - KV Migration Status
+///
+/// Retrieve the progress of the latest storage scheme migration of the Merchant Account
+#[instrument(skip_all, fields(flow = ?Flow::MerchantKvMigrationStatus))]
+pub async fn merchant_account_kv_migration_status(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantKvMigrationStatus;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, req| kv_migration_status(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Update Rate Limits
+///
+/// Set the limits on the rate of requests of the merchant, overall, per API and per API key
+#[instrument(skip_all, fields(flow = ?Flow::MerchantRateLimitsUpdate))]
+pub async fn merchant_rate_limits_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<admin::MerchantRateLimits>,
+) -> HttpResponse {
+    let flow = Flow::MerchantRateLimitsUpdate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (merchant_id, json_payload.into_inner()),
+        |state, _, (merchant_id, payload)| {
+            rate_limit::update_merchant_rate_limits(state, merchant_id, payload)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Retrieve Rate Limits
+///
+/// Retrieve the limits on the rate of requests of the merchant
+#[instrument(skip_all, fields(flow = ?Flow::MerchantRateLimitsRetrieve))]
+pub async fn merchant_rate_limits_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantRateLimitsRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| rate_limit::retrieve_merchant_rate_limits(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Rotate Key
+///
+/// Rotate the key that the data of the Merchant Account is encrypted with, and re-encrypt the data
+/// with the new key in the background
+#[instrument(skip_all, fields(flow = ?Flow::MerchantKeyRotate))]
+pub async fn merchant_key_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantKeyRotate;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| merchant_key_rotation::rotate_merchant_key(state, merchant_id),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Merchant Account - Retrieve Key Rotation
+///
+/// Retrieve the progress of re-encrypting the data of the Merchant Account after the latest
+/// rotation of its key
+#[instrument(skip_all, fields(flow = ?Flow::MerchantKeyRotationRetrieve))]
+pub async fn merchant_key_rotation_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::MerchantKeyRotationRetrieve;
+    let merchant_id = path.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        merchant_id,
+        |state, _, merchant_id| {
+            merchant_key_rotation::merchant_key_rotation_status(state, merchant_id)
+        },
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
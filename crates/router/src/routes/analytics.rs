@@ -0,0 +1,36 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::{analytics as analytics_types, user::Permission};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{analytics, api_locking},
+    services::{api, authentication as auth},
+};
+
+/// Analytics - Payment Metrics
+///
+/// Compute metrics of the payment attempts of the merchant over a time range, optionally split
+/// into time buckets and grouped by dimensions such as the connector or the payment method.
+#[instrument(skip_all, fields(flow = ?Flow::GetPaymentMetrics))]
+pub async fn get_payment_metrics(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<analytics_types::GetPaymentMetricRequest>,
+) -> HttpResponse {
+    let flow = Flow::GetPaymentMetrics;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| analytics::get_payment_metrics(state, auth.merchant_account, req),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::AnalyticsRead),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -1,4 +1,5 @@
 use actix_web::{web, HttpRequest, Responder};
+use api_models::user::Permission;
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
@@ -42,18 +43,20 @@ pub async fn api_key_create(
         &req,
         payload,
         |state, _, payload| async {
-            #[cfg(feature = "kms")]
-            let kms_client = external_services::kms::get_kms_client(&state.clone().conf.kms).await;
-            api_keys::create_api_key(
-                state,
-                #[cfg(feature = "kms")]
-                kms_client,
-                payload,
-                merchant_id.clone(),
+            let secrets_manager = external_services::secrets_management::get_secrets_manager(
+                &state.clone().conf.secrets_management,
             )
-            .await
+            .await;
+            api_keys::create_api_key(state, secrets_manager, payload, merchant_id.clone()).await
         },
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::ApiKeyWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -91,7 +94,14 @@ pub async fn api_key_retrieve(
         &req,
         (&merchant_id, &key_id),
         |state, _, (merchant_id, key_id)| api_keys::retrieve_api_key(state, merchant_id, key_id),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::ApiKeyRead,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -134,7 +144,14 @@ pub async fn api_key_update(
         |state, _, (merchant_id, key_id, payload)| {
             api_keys::update_api_key(state, merchant_id, key_id, payload)
         },
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::ApiKeyWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -173,11 +190,76 @@ pub async fn api_key_revoke(
         &req,
         (&merchant_id, &key_id),
         |state, _, (merchant_id, key_id)| api_keys::revoke_api_key(state, merchant_id, key_id),
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::ApiKeyWrite,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
 }
+/// API Key - Rotate
+///
+/// Issue a new API Key to replace the specified API Key, which remains valid for a grace period
+/// so that the new API Key can be deployed before the rotated API Key is revoked. The plaintext
+/// API Key will be displayed only once, so ensure you store it securely.
+#[utoipa::path(
+    post,
+    path = "/api_keys/{merchant_id}/{key_id}/rotate",
+    request_body = RotateApiKeyRequest,
+    params (
+        ("merchant_id" = String, Path, description = "The unique identifier for the merchant account"),
+        ("key_id" = String, Path, description = "The unique identifier for the API Key")
+    ),
+    responses(
+        (status = 200, description = "API Key rotated", body = RotateApiKeyResponse),
+        (status = 404, description = "API Key not found"),
+        (status = 412, description = "API Key has expired or was already rotated")
+    ),
+    tag = "API Key",
+    operation_id = "Rotate an API Key",
+    security(("admin_api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ApiKeyRotate))]
+pub async fn api_key_rotate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<(String, String)>,
+    json_payload: web::Json<api_types::RotateApiKeyRequest>,
+) -> impl Responder {
+    let flow = Flow::ApiKeyRotate;
+    let (merchant_id, key_id) = path.into_inner();
+    let payload = json_payload.into_inner();
+
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (&merchant_id, &key_id, payload),
+        |state, _, (merchant_id, key_id, payload)| async move {
+            let secrets_manager = external_services::secrets_management::get_secrets_manager(
+                &state.clone().conf.secrets_management,
+            )
+            .await;
+            api_keys::rotate_api_key(state, secrets_manager, merchant_id, key_id, payload).await
+        },
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::ApiKeyWrite,
+            },
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
 /// API Key - List
 ///
 /// List all API Keys associated with your merchant account.
@@ -213,11 +295,18 @@ pub async fn api_key_list(
         flow,
         state,
         &req,
-        (limit, offset, merchant_id),
+        (limit, offset, merchant_id.clone()),
         |state, _, (limit, offset, merchant_id)| async move {
             api_keys::list_api_keys(state, merchant_id, limit, offset).await
         },
-        &auth::AdminApiAuth,
+        auth::auth_type(
+            &auth::AdminApiAuth,
+            &auth::JWTAuthMerchantFromRoute {
+                merchant_id: merchant_id.clone(),
+                required_permission: Permission::ApiKeyRead,
+            },
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
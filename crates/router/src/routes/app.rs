@@ -1,11 +1,15 @@
-use std::sync::Arc;
+use std::{collections::HashMap, sync::Arc};
 
-use actix_web::{web, Scope};
+use actix_web::{http::header::HeaderMap, web, Scope};
+use common_utils::ext_traits::AsyncExt;
+use error_stack::report;
 #[cfg(feature = "email")]
-use external_services::email::{AwsSes, EmailClient};
-#[cfg(feature = "kms")]
-use external_services::kms::{self, decrypt::KmsDecrypt};
-use router_env::tracing_actix_web::RequestId;
+use external_services::email::EmailClient;
+use external_services::{
+    file_storage::FileStorageInterface,
+    secrets_management::{self, SecretsDecrypt},
+};
+use masking::StrongSecret;
 use scheduler::SchedulerInterface;
 use storage_impl::MockDb;
 use tokio::sync::oneshot;
@@ -14,21 +18,26 @@ use tokio::sync::oneshot;
 use super::dummy_connector::*;
 #[cfg(feature = "payouts")]
 use super::payouts::*;
-#[cfg(all(feature = "olap", feature = "kms"))]
+#[cfg(feature = "olap")]
 use super::verification::{apple_pay_merchant_registration, retrieve_apple_pay_verified_domains};
 #[cfg(feature = "olap")]
-use super::{admin::*, api_keys::*, disputes::*, files::*};
-use super::{cache::*, health::*, payment_link::*};
+use super::{
+    admin::*, analytics::*, api_keys::*, audit_events::*, disputes::*, files::*, organization::*,
+    user::*,
+};
+use super::{cache::*, health::*, invoices::*, payment_link::*, subscriptions::*};
 #[cfg(any(feature = "olap", feature = "oltp"))]
 use super::{configs::*, customers::*, mandates::*, payments::*, refunds::*};
 #[cfg(feature = "oltp")]
 use super::{ephemeral_key::*, payment_methods::*, webhooks::*};
 use crate::{
     configs::settings,
+    core::errors::{self, RouterResult},
     db::{StorageImpl, StorageInterface},
-    events::{event_logger::EventLogger, EventHandler},
+    events::{self, EventHandler},
+    headers,
     routes::cards_info::card_iin_info,
-    services::get_store,
+    services::{authentication::AuthenticationType, get_store, get_tenant_store},
 };
 
 #[derive(Clone)]
@@ -39,9 +48,23 @@ pub struct AppState {
     pub event_handler: Box<dyn EventHandler>,
     #[cfg(feature = "email")]
     pub email_client: Arc<dyn EmailClient>,
-    #[cfg(feature = "kms")]
-    pub kms_secrets: Arc<settings::ActiveKmsSecrets>,
+    pub active_secrets: Arc<settings::ActiveSecrets>,
+    pub file_storage_client: Arc<dyn FileStorageInterface>,
     pub api_client: Box<dyn crate::services::ApiClient>,
+    /// The tenants served by this deployment when multitenancy is enabled, keyed by tenant ID.
+    pub tenants: Arc<HashMap<String, TenantState>>,
+    /// The tenant the state is scoped to. `store` is the store of this tenant when set.
+    pub tenant: Option<TenantState>,
+    /// How the request the state is scoped to was authenticated.
+    pub auth_type: Option<AuthenticationType>,
+}
+
+#[derive(Clone)]
+pub struct TenantState {
+    pub tenant_id: String,
+    pub store: Box<dyn StorageInterface>,
+    pub admin_api_key: Option<StrongSecret<String>>,
+    pub accept_deployment_admin_api_key: bool,
 }
 
 impl scheduler::SchedulerAppState for AppState {
@@ -56,10 +79,16 @@ pub trait AppStateInfo {
     fn event_handler(&self) -> Box<dyn EventHandler>;
     #[cfg(feature = "email")]
     fn email_client(&self) -> Arc<dyn EmailClient>;
-    fn add_request_id(&mut self, request_id: RequestId);
+    fn add_request_id(&mut self, request_id: uuid::Uuid);
     fn add_merchant_id(&mut self, merchant_id: Option<String>);
     fn add_flow_name(&mut self, flow_name: String);
     fn get_request_id(&self) -> Option<String>;
+    fn select_tenant(&mut self, request_headers: &HeaderMap) -> RouterResult<()>;
+    fn tenant_admin_api_key(&self) -> Option<StrongSecret<String>>;
+    /// Whether admin APIs accept the admin API key of the deployment, which is the case when no
+    /// tenant is selected or the selected tenant explicitly allows it.
+    fn accepts_deployment_admin_api_key(&self) -> bool;
+    fn add_auth_type(&mut self, auth_type: AuthenticationType);
 }
 
 impl AppStateInfo for AppState {
@@ -76,7 +105,7 @@ impl AppStateInfo for AppState {
     fn event_handler(&self) -> Box<dyn EventHandler> {
         self.event_handler.to_owned()
     }
-    fn add_request_id(&mut self, request_id: RequestId) {
+    fn add_request_id(&mut self, request_id: uuid::Uuid) {
         self.api_client.add_request_id(request_id);
     }
     fn add_merchant_id(&mut self, merchant_id: Option<String>) {
@@ -88,6 +117,87 @@ impl AppStateInfo for AppState {
     fn get_request_id(&self) -> Option<String> {
         self.api_client.get_request_id()
     }
+    fn select_tenant(&mut self, request_headers: &HeaderMap) -> RouterResult<()> {
+        if !self.conf.multitenancy.enabled {
+            return Ok(());
+        }
+
+        let tenant_id = get_tenant_id(request_headers, &self.conf.multitenancy)?;
+        let tenant = self
+            .tenants
+            .get(&tenant_id)
+            .ok_or(errors::ApiErrorResponse::InvalidTenant { tenant_id })?
+            .clone();
+        self.store = tenant.store.clone();
+        self.tenant = Some(tenant);
+        Ok(())
+    }
+    fn tenant_admin_api_key(&self) -> Option<StrongSecret<String>> {
+        self.tenant
+            .as_ref()
+            .and_then(|tenant| tenant.admin_api_key.clone())
+    }
+    fn accepts_deployment_admin_api_key(&self) -> bool {
+        self.tenant
+            .as_ref()
+            .map_or(true, |tenant| tenant.accept_deployment_admin_api_key)
+    }
+    fn add_auth_type(&mut self, auth_type: AuthenticationType) {
+        self.auth_type = Some(auth_type);
+    }
+}
+
+/// Resolves the tenant of a request from the host the request was received on. The tenant ID
+/// header may only confirm the tenant of the host; it selects the tenant of requests received on
+/// any other host only if that tenant is not served on a host of its own.
+fn get_tenant_id(
+    request_headers: &HeaderMap,
+    multitenancy: &settings::Multitenancy,
+) -> RouterResult<String> {
+    let header_tenant_id = request_headers
+        .get(headers::X_TENANT_ID)
+        .map(|tenant_id| {
+            tenant_id.to_str().map_err(|_| {
+                report!(errors::ApiErrorResponse::InvalidDataValue {
+                    field_name: headers::X_TENANT_ID,
+                })
+            })
+        })
+        .transpose()?;
+
+    let host = request_headers
+        .get(actix_web::http::header::HOST)
+        .and_then(|host| host.to_str().ok())
+        .map(|host| host.split(':').next().unwrap_or(host));
+
+    let host_tenant_id = host.and_then(|host| {
+        multitenancy
+            .tenants
+            .iter()
+            .find(|(_, tenant)| tenant.host.as_deref() == Some(host))
+            .map(|(tenant_id, _)| tenant_id.as_str())
+    });
+
+    match (host_tenant_id, header_tenant_id) {
+        (Some(host_tenant_id), None) => Ok(host_tenant_id.to_string()),
+        (Some(host_tenant_id), Some(header_tenant_id)) if host_tenant_id == header_tenant_id => {
+            Ok(host_tenant_id.to_string())
+        }
+        (None, Some(header_tenant_id))
+            if multitenancy
+                .tenants
+                .get(header_tenant_id)
+                .map_or(false, |tenant| tenant.host.is_none()) =>
+        {
+            Ok(header_tenant_id.to_string())
+        }
+        (_, Some(header_tenant_id)) => Err(report!(errors::ApiErrorResponse::InvalidTenant {
+            tenant_id: header_tenant_id.to_string(),
+        })),
+        (None, None) => Err(report!(errors::ApiErrorResponse::MissingRequiredField {
+            field_name: headers::X_TENANT_ID,
+        })),
+    }
 }
 
 impl AsRef<Self> for AppState {
@@ -106,46 +216,131 @@ impl AppState {
         shut_down_signal: oneshot::Sender<()>,
         api_client: Box<dyn crate::services::ApiClient>,
     ) -> Self {
-        #[cfg(feature = "kms")]
-        let kms_client = kms::get_kms_client(&conf.kms).await;
+        let secrets_manager =
+            secrets_management::get_secrets_manager(&conf.secrets_management).await;
         let testable = storage_impl == StorageImpl::PostgresqlTest;
+        let tenants = conf
+            .multitenancy
+            .tenants
+            .iter()
+            .filter(|_| conf.multitenancy.enabled);
+        #[allow(clippy::expect_used)]
+        let event_handler =
+            events::get_event_handler(&conf.events).expect("Failed to create event handler");
+        let mut tenant_stores: HashMap<String, Box<dyn StorageInterface>> = HashMap::new();
         let store: Box<dyn StorageInterface> = match storage_impl {
-            StorageImpl::Postgresql | StorageImpl::PostgresqlTest => Box::new(
+            StorageImpl::Postgresql | StorageImpl::PostgresqlTest => {
                 #[allow(clippy::expect_used)]
-                get_store(&conf, shut_down_signal, testable)
+                let mut store = get_store(&conf, shut_down_signal, testable)
                     .await
-                    .expect("Failed to create store"),
-            ),
+                    .expect("Failed to create store");
+                // Tenant stores are created from this store, and publish change data events
+                // through the same handler
+                store.set_change_event_handler(Arc::new(events::ChangeEventForwarder(
+                    event_handler.clone(),
+                )));
+                for (tenant_id, tenant) in tenants {
+                    #[allow(clippy::expect_used)]
+                    let tenant_store = get_tenant_store(&conf, &store, tenant, testable)
+                        .await
+                        .expect("Failed to create tenant store");
+                    tenant_stores.insert(tenant_id.clone(), Box::new(tenant_store));
+                }
+                Box::new(store)
+            }
             #[allow(clippy::expect_used)]
-            StorageImpl::Mock => Box::new(
-                MockDb::new(&conf.redis)
-                    .await
-                    .expect("Failed to create mock store"),
-            ),
+            StorageImpl::Mock => {
+                for (tenant_id, _) in tenants {
+                    let tenant_store = MockDb::new(&conf.redis)
+                        .await
+                        .expect("Failed to create mock store");
+                    tenant_stores.insert(tenant_id.clone(), Box::new(tenant_store));
+                }
+                Box::new(
+                    MockDb::new(&conf.redis)
+                        .await
+                        .expect("Failed to create mock store"),
+                )
+            }
         };
 
-        #[cfg(feature = "kms")]
+        let mut tenants = HashMap::new();
+        for (tenant_id, store) in tenant_stores {
+            let tenant_conf = conf.multitenancy.tenants.get(&tenant_id);
+            let admin_api_key = tenant_conf.and_then(|tenant| tenant.admin_api_key.clone());
+            let accept_deployment_admin_api_key =
+                tenant_conf.map_or(false, |tenant| tenant.accept_deployment_admin_api_key);
+
+            #[allow(clippy::expect_used)]
+            let admin_api_key = admin_api_key
+                .async_map(|admin_api_key| async move {
+                    admin_api_key.decrypt_inner(secrets_manager).await
+                })
+                .await
+                .transpose()
+                .expect("Failed while decrypting tenant admin API key");
+
+            tenants.insert(
+                tenant_id.clone(),
+                TenantState {
+                    tenant_id,
+                    store,
+                    admin_api_key: admin_api_key.map(StrongSecret::new),
+                    accept_deployment_admin_api_key,
+                },
+            );
+        }
+
         #[allow(clippy::expect_used)]
-        let kms_secrets = settings::ActiveKmsSecrets {
+        let active_secrets = settings::ActiveSecrets {
             jwekey: conf.jwekey.clone().into(),
         }
-        .decrypt_inner(kms_client)
+        .decrypt_inner(secrets_manager)
         .await
-        .expect("Failed while performing KMS decryption");
+        .expect("Failed while decrypting secrets");
 
         #[cfg(feature = "email")]
-        let email_client = Arc::new(AwsSes::new(&conf.email).await);
+        #[allow(clippy::expect_used)]
+        let email_client = conf
+            .email
+            .get_email_client()
+            .await
+            .expect("Failed to create email client")
+            .into();
+
+        let file_storage_client = conf.file_storage.get_file_storage_client().await.into();
+
         Self {
             flow_name: String::from("default"),
             store,
             conf: Arc::new(conf),
             #[cfg(feature = "email")]
             email_client,
-            #[cfg(feature = "kms")]
-            kms_secrets: Arc::new(kms_secrets),
+            active_secrets: Arc::new(active_secrets),
+            file_storage_client,
             api_client,
-            event_handler: Box::<EventLogger>::default(),
+            event_handler,
+            tenants: Arc::new(tenants),
+            tenant: None,
+            auth_type: None,
+        }
+    }
+
+    /// The states to run the background jobs of each tenant with, or the state itself when
+    /// multitenancy is disabled.
+    pub fn tenant_states(&self) -> Vec<Self> {
+        if !self.conf.multitenancy.enabled {
+            return vec![self.clone()];
         }
+
+        self.tenants
+            .values()
+            .map(|tenant| Self {
+                store: tenant.store.clone(),
+                tenant: Some(tenant.clone()),
+                ..self.clone()
+            })
+            .collect()
     }
 
     pub async fn new(
@@ -397,6 +592,20 @@ impl MerchantAccount {
                     .route(web::post().to(merchant_account_toggle_kv))
                     .route(web::get().to(merchant_account_kv_status)),
             )
+            .service(
+                web::resource("/{id}/kv/migration")
+                    .route(web::get().to(merchant_account_kv_migration_status)),
+            )
+            .service(web::resource("/{id}/keys/rotate").route(web::post().to(merchant_key_rotate)))
+            .service(
+                web::resource("/{id}/keys/rotation")
+                    .route(web::get().to(merchant_key_rotation_retrieve)),
+            )
+            .service(
+                web::resource("/{id}/rate_limits")
+                    .route(web::post().to(merchant_rate_limits_update))
+                    .route(web::get().to(merchant_rate_limits_retrieve)),
+            )
             .service(
                 web::resource("/{id}")
                     .route(web::get().to(retrieve_merchant_account))
@@ -406,6 +615,78 @@ impl MerchantAccount {
     }
 }
 
+pub struct Organization;
+
+#[cfg(feature = "olap")]
+impl Organization {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/organization")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(organization_create)))
+            .service(web::resource("/merchants").route(web::get().to(organization_merchants_list)))
+            .service(
+                web::resource("/payments/list").route(web::post().to(organization_payments_list)),
+            )
+            .service(
+                web::resource("/payments/aggregate")
+                    .route(web::post().to(organization_payments_aggregate)),
+            )
+            .service(
+                web::resource("/refunds/list").route(web::post().to(organization_refunds_list)),
+            )
+            .service(
+                web::resource("/refunds/aggregate")
+                    .route(web::post().to(organization_refunds_aggregate)),
+            )
+            .service(
+                web::resource("/{id}/api_key").route(web::post().to(organization_api_key_create)),
+            )
+            .service(
+                web::resource("/{id}")
+                    .route(web::get().to(organization_retrieve))
+                    .route(web::post().to(organization_update)),
+            )
+    }
+}
+
+pub struct User;
+
+#[cfg(feature = "olap")]
+impl User {
+    pub fn server(state: AppState) -> Scope {
+        #[cfg_attr(not(feature = "email"), allow(unused_mut))]
+        let mut route = web::scope("/user")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::get().to(user_retrieve)))
+            .service(web::resource("/create").route(web::post().to(user_create)))
+            .service(web::resource("/signin").route(web::post().to(user_signin)))
+            .service(web::resource("/signout").route(web::post().to(user_signout)))
+            .service(web::resource("/refresh_token").route(web::post().to(user_refresh_token)))
+            .service(web::resource("/change_password").route(web::post().to(user_change_password)))
+            .service(web::resource("/switch_merchant").route(web::post().to(user_switch_merchant)))
+            .service(web::resource("/list").route(web::get().to(users_list)))
+            .service(web::resource("/role").route(web::post().to(user_role_update)))
+            .service(web::resource("/role/{user_id}").route(web::delete().to(user_role_delete)));
+
+        #[cfg(feature = "email")]
+        {
+            route = route
+                .service(web::resource("/invite").route(web::post().to(user_invite)))
+                .service(
+                    web::resource("/accept_invite").route(web::post().to(user_accept_invitation)),
+                )
+                .service(
+                    web::resource("/forgot_password").route(web::post().to(user_forgot_password)),
+                )
+                .service(
+                    web::resource("/reset_password").route(web::post().to(user_reset_password)),
+                );
+        }
+
+        route
+    }
+}
+
 pub struct MerchantConnectorAccount;
 
 #[cfg(any(feature = "olap", feature = "oltp"))]
@@ -464,6 +745,9 @@ impl Mandates {
             route =
                 route.service(web::resource("/list").route(web::get().to(retrieve_mandates_list)));
             route = route.service(web::resource("/{id}").route(web::get().to(get_mandate)));
+            route = route.service(
+                web::resource("/{id}/payments").route(web::get().to(retrieve_mandate_payments)),
+            );
         }
         #[cfg(feature = "oltp")]
         {
@@ -512,6 +796,17 @@ impl Configs {
     }
 }
 
+pub struct AuditEvents;
+
+#[cfg(feature = "olap")]
+impl AuditEvents {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/audit_events")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::get().to(audit_events_list)))
+    }
+}
+
 pub struct ApiKeys;
 
 #[cfg(feature = "olap")]
@@ -527,6 +822,18 @@ impl ApiKeys {
                     .route(web::post().to(api_key_update))
                     .route(web::delete().to(api_key_revoke)),
             )
+            .service(web::resource("/{key_id}/rotate").route(web::post().to(api_key_rotate)))
+    }
+}
+
+pub struct Analytics;
+
+#[cfg(feature = "olap")]
+impl Analytics {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/analytics/v1")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/metrics/payments").route(web::post().to(get_payment_metrics)))
     }
 }
 
@@ -575,6 +882,10 @@ impl Files {
                     .route(web::delete().to(files_delete))
                     .route(web::get().to(files_retrieve)),
             )
+            .service(
+                web::resource("/{file_id}/download_url")
+                    .route(web::get().to(files_retrieve_download_url)),
+            )
     }
 }
 
@@ -592,11 +903,32 @@ pub struct PaymentLink;
 
 impl PaymentLink {
     pub fn server(state: AppState) -> Scope {
-        web::scope("/payment_link")
-            .app_data(web::Data::new(state))
+        let mut route = web::scope("/payment_link").app_data(web::Data::new(state));
+
+        #[cfg(feature = "olap")]
+        {
+            route = route.service(web::resource("/list").route(web::get().to(payment_links_list)));
+        }
+
+        route
+            .service(web::resource("/reusable").route(web::post().to(reusable_payment_link_create)))
+            .service(
+                web::resource("/reusable/{reusable_payment_link_id}")
+                    .route(web::get().to(reusable_payment_link_retrieve))
+                    .route(web::post().to(reusable_payment_link_update)),
+            )
+            .service(
+                web::resource("/reusable/{merchant_id}/{reusable_payment_link_id}")
+                    .route(web::get().to(initiate_reusable_payment_link))
+                    .route(web::post().to(pay_reusable_payment_link)),
+            )
             .service(
                 web::resource("/{payment_link_id}").route(web::get().to(payment_link_retrieve)),
             )
+            .service(
+                web::resource("/status/{merchant_id}/{payment_id}")
+                    .route(web::get().to(payment_link_status)),
+            )
             .service(
                 web::resource("{merchant_id}/{payment_id}")
                     .route(web::get().to(initiate_payment_link)),
@@ -604,6 +936,55 @@ impl PaymentLink {
     }
 }
 
+pub struct Invoices;
+
+impl Invoices {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/invoices")
+            .app_data(web::Data::new(state))
+            .service(web::resource("").route(web::post().to(invoice_create)))
+            .service(
+                web::resource("/hosted/{merchant_id}/{invoice_id}")
+                    .route(web::get().to(hosted_invoice_retrieve)),
+            )
+            .service(
+                web::resource("/hosted/{merchant_id}/{invoice_id}/pdf")
+                    .route(web::get().to(hosted_invoice_pdf)),
+            )
+            .service(web::resource("/{invoice_id}").route(web::get().to(invoice_retrieve)))
+            .service(
+                web::resource("/{invoice_id}/finalize").route(web::post().to(invoice_finalize)),
+            )
+            .service(web::resource("/{invoice_id}/void").route(web::post().to(invoice_void)))
+            .service(web::resource("/{invoice_id}/pdf").route(web::get().to(invoice_pdf)))
+    }
+}
+
+pub struct Subscriptions;
+
+impl Subscriptions {
+    pub fn server(state: AppState) -> Scope {
+        web::scope("/subscriptions")
+            .app_data(web::Data::new(state))
+            .service(web::resource("/plans").route(web::post().to(subscription_plan_create)))
+            .service(
+                web::resource("/plans/{plan_id}")
+                    .route(web::get().to(subscription_plan_retrieve))
+                    .route(web::post().to(subscription_plan_update)),
+            )
+            .service(web::resource("").route(web::post().to(subscription_create)))
+            .service(
+                web::resource("/{subscription_id}")
+                    .route(web::get().to(subscription_retrieve))
+                    .route(web::post().to(subscription_update)),
+            )
+            .service(
+                web::resource("/{subscription_id}/cancel")
+                    .route(web::post().to(subscription_cancel)),
+            )
+    }
+}
+
 pub struct BusinessProfile;
 
 #[cfg(feature = "olap")]
@@ -625,10 +1006,10 @@ impl BusinessProfile {
     }
 }
 
-#[cfg(all(feature = "olap", feature = "kms"))]
+#[cfg(feature = "olap")]
 pub struct Verify;
 
-#[cfg(all(feature = "olap", feature = "kms"))]
+#[cfg(feature = "olap")]
 impl Verify {
     pub fn server(state: AppState) -> Scope {
         web::scope("/verify")
@@ -643,3 +1024,82 @@ impl Verify {
             )
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use actix_web::http::header::{HeaderName, HeaderValue, HOST};
+
+    use super::*;
+
+    fn multitenancy() -> settings::Multitenancy {
+        settings::Multitenancy {
+            enabled: true,
+            tenants: HashMap::from([
+                (
+                    "acme".to_string(),
+                    settings::Tenant {
+                        host: Some("acme.example.com".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                (
+                    "globex".to_string(),
+                    settings::Tenant {
+                        host: Some("globex.example.com".to_string()),
+                        ..Default::default()
+                    },
+                ),
+                ("internal".to_string(), settings::Tenant::default()),
+            ]),
+        }
+    }
+
+    fn request_headers(host: &'static str, tenant_id: Option<&'static str>) -> HeaderMap {
+        let mut request_headers = HeaderMap::new();
+        request_headers.insert(HOST, HeaderValue::from_static(host));
+        if let Some(tenant_id) = tenant_id {
+            request_headers.insert(
+                HeaderName::from_static("x-tenant-id"),
+                HeaderValue::from_static(tenant_id),
+            );
+        }
+        request_headers
+    }
+
+    #[test]
+    fn tenant_is_resolved_from_host() {
+        let multitenancy = multitenancy();
+
+        let headers = request_headers("acme.example.com:8080", None);
+        assert_eq!(get_tenant_id(&headers, &multitenancy).unwrap(), "acme");
+
+        let headers = request_headers("acme.example.com", Some("acme"));
+        assert_eq!(get_tenant_id(&headers, &multitenancy).unwrap(), "acme");
+    }
+
+    #[test]
+    fn tenant_header_does_not_override_host() {
+        let multitenancy = multitenancy();
+
+        let headers = request_headers("acme.example.com", Some("globex"));
+        assert!(get_tenant_id(&headers, &multitenancy).is_err());
+
+        let headers = request_headers("acme.example.com", Some("internal"));
+        assert!(get_tenant_id(&headers, &multitenancy).is_err());
+    }
+
+    #[test]
+    fn tenant_header_selects_only_tenants_without_host() {
+        let multitenancy = multitenancy();
+
+        let headers = request_headers("router.internal", Some("internal"));
+        assert_eq!(get_tenant_id(&headers, &multitenancy).unwrap(), "internal");
+
+        let headers = request_headers("router.internal", Some("globex"));
+        assert!(get_tenant_id(&headers, &multitenancy).is_err());
+
+        let headers = request_headers("router.internal", None);
+        assert!(get_tenant_id(&headers, &multitenancy).is_err());
+    }
+}
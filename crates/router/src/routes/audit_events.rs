@@ -0,0 +1,31 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::audit_events as audit_types;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, audit_events},
+    services::{api, authentication as auth},
+};
+
+/// Audit Events - List
+///
+/// List the audit events recorded for configuration changes, most recent first.
+#[instrument(skip_all, fields(flow = ?Flow::AuditEventsList))]
+pub async fn audit_events_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    payload: web::Query<audit_types::AuditEventListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::AuditEventsList;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload.into_inner(),
+        |state, _, req| audit_events::list_audit_events(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -1,5 +1,6 @@
 use actix_multipart::Multipart;
 use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::user::Permission;
 use router_env::{instrument, tracing, Flow};
 
 use crate::core::api_locking;
@@ -45,7 +46,11 @@ pub async fn files_create(
         &req,
         create_file_request,
         |state, auth, req| files_create_core(state, auth.merchant_account, auth.key_store, req),
-        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::FileWrite),
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -83,7 +88,11 @@ pub async fn files_delete(
         &req,
         file_id,
         |state, auth, req| files_delete_core(state, auth.merchant_account, req),
-        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::FileWrite),
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -121,7 +130,54 @@ pub async fn files_retrieve(
         &req,
         file_id,
         |state, auth, req| files_retrieve_core(state, auth.merchant_account, auth.key_store, req),
-        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::FileRead),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Files - Retrieve Download URL
+///
+/// To retrieve a pre-signed URL a file can be downloaded from until it expires
+#[utoipa::path(
+    get,
+    path = "/files/{file_id}/download_url",
+    params(
+        ("file_id" = String, Path, description = "The identifier for file")
+    ),
+    responses(
+        (status = 200, description = "File download URL", body = FileDownloadUrlResponse),
+        (status = 400, description = "Bad Request")
+    ),
+    tag = "Files",
+    operation_id = "Retrieve a File Download URL",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::RetrieveFileDownloadUrl))]
+pub async fn files_retrieve_download_url(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::RetrieveFileDownloadUrl;
+    let file_id = files::FileId {
+        file_id: path.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        file_id,
+        |state, auth, req| files_download_url_core(state, auth.merchant_account, req),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::FileRead),
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
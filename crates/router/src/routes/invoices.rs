@@ -0,0 +1,234 @@
+use actix_web::{web, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    core::{api_locking, invoices::*, payment_methods::Oss},
+    services::{api, authentication as auth},
+    AppState,
+};
+
+/// Invoices - Create
+///
+/// To create a draft invoice billed to a customer. The invoice can be edited until it is finalized
+#[utoipa::path(
+    post,
+    path = "/invoices",
+    request_body = InvoiceCreateRequest,
+    responses(
+        (status = 200, description = "Invoice created", body = InvoiceResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Invoices",
+    operation_id = "Create an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoiceCreate))]
+pub async fn invoice_create(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<api_models::invoices::InvoiceCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::InvoiceCreate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| create_invoice(state, auth.merchant_account, auth.key_store, req),
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Invoices - Retrieve
+///
+/// To retrieve an invoice along with its status and the URLs of its hosted page and PDF
+#[utoipa::path(
+    get,
+    path = "/invoices/{invoice_id}",
+    params(
+        ("invoice_id" = String, Path, description = "The identifier for invoice")
+    ),
+    responses(
+        (status = 200, description = "Gets details regarding invoice", body = InvoiceResponse),
+        (status = 404, description = "No invoice found")
+    ),
+    tag = "Invoices",
+    operation_id = "Retrieve an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoiceRetrieve))]
+pub async fn invoice_retrieve(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::InvoiceRetrieve;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, invoice_id| retrieve_invoice(state, auth.merchant_account, invoice_id),
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Invoices - Finalize
+///
+/// To finalize a draft invoice, creating the payment link through which the customer pays it. The
+/// invoice is emailed to the customer, who is reminded of it until it is paid
+#[utoipa::path(
+    post,
+    path = "/invoices/{invoice_id}/finalize",
+    params(
+        ("invoice_id" = String, Path, description = "The identifier for invoice")
+    ),
+    responses(
+        (status = 200, description = "Invoice finalized", body = InvoiceResponse),
+        (status = 400, description = "Invoice is not a draft"),
+        (status = 404, description = "No invoice found")
+    ),
+    tag = "Invoices",
+    operation_id = "Finalize an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoiceFinalize))]
+pub async fn invoice_finalize(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::InvoiceFinalize;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, invoice_id| {
+            finalize_invoice::<Oss>(state, auth.merchant_account, auth.key_store, invoice_id)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Invoices - Void
+///
+/// To void an invoice that has not been paid, cancelling its payment if the customer has not
+/// started paying it
+#[utoipa::path(
+    post,
+    path = "/invoices/{invoice_id}/void",
+    params(
+        ("invoice_id" = String, Path, description = "The identifier for invoice")
+    ),
+    responses(
+        (status = 200, description = "Invoice voided", body = InvoiceResponse),
+        (status = 400, description = "Invoice cannot be voided"),
+        (status = 404, description = "No invoice found")
+    ),
+    tag = "Invoices",
+    operation_id = "Void an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoiceVoid))]
+pub async fn invoice_void(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::InvoiceVoid;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, invoice_id| void_invoice(state, auth.merchant_account, invoice_id),
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Invoices - Retrieve PDF
+///
+/// To retrieve the PDF of an invoice, which can be used to preview draft invoices
+#[utoipa::path(
+    get,
+    path = "/invoices/{invoice_id}/pdf",
+    params(
+        ("invoice_id" = String, Path, description = "The identifier for invoice")
+    ),
+    responses(
+        (status = 200, description = "PDF of the invoice", content_type = "application/pdf"),
+        (status = 404, description = "No invoice found")
+    ),
+    tag = "Invoices",
+    operation_id = "Retrieve the PDF of an Invoice",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::InvoicePdf))]
+pub async fn invoice_pdf(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::InvoicePdf;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, invoice_id| {
+            retrieve_invoice_pdf(state, auth.merchant_account, auth.key_store, invoice_id)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+pub async fn hosted_invoice_retrieve(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::HostedInvoiceRetrieve;
+    let (merchant_id, invoice_id) = path.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        invoice_id,
+        |state, auth, invoice_id| retrieve_hosted_invoice(state, auth.merchant_account, invoice_id),
+        &auth::MerchantIdAuth(merchant_id),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+pub async fn hosted_invoice_pdf(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::HostedInvoicePdf;
+    let (merchant_id, invoice_id) = path.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        invoice_id,
+        |state, auth, invoice_id| {
+            retrieve_hosted_invoice_pdf(state, auth.merchant_account, auth.key_store, invoice_id)
+        },
+        &auth::MerchantIdAuth(merchant_id),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
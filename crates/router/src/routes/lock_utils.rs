@@ -1,6 +1,6 @@
 use router_env::Flow;
 
-#[derive(Clone, Debug, strum::Display)]
+#[derive(Clone, Debug, strum::Display, strum::EnumString)]
 #[strum(serialize_all = "snake_case")]
 pub enum ApiIdentifier {
     Payments,
@@ -22,6 +22,12 @@ pub enum ApiIdentifier {
     Verification,
     ApiKeys,
     PaymentLink,
+    Invoices,
+    Subscriptions,
+    Organization,
+    User,
+    AuditEvents,
+    Analytics,
 }
 
 impl From<Flow> for ApiIdentifier {
@@ -31,7 +37,12 @@ impl From<Flow> for ApiIdentifier {
             | Flow::MerchantsAccountRetrieve
             | Flow::MerchantsAccountUpdate
             | Flow::MerchantsAccountDelete
-            | Flow::MerchantAccountList => Self::MerchantAccount,
+            | Flow::MerchantAccountList
+            | Flow::MerchantRateLimitsUpdate
+            | Flow::MerchantRateLimitsRetrieve
+            | Flow::MerchantKeyRotate
+            | Flow::MerchantKeyRotationRetrieve
+            | Flow::MerchantKvMigrationStatus => Self::MerchantAccount,
 
             Flow::MerchantConnectorsCreate
             | Flow::MerchantConnectorsRetrieve
@@ -53,7 +64,10 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::EphemeralKeyCreate | Flow::EphemeralKeyDelete => Self::Ephemeral,
 
-            Flow::MandatesRetrieve | Flow::MandatesRevoke | Flow::MandatesList => Self::Mandates,
+            Flow::MandatesRetrieve
+            | Flow::MandatesRevoke
+            | Flow::MandatesList
+            | Flow::MandatePaymentsList => Self::Mandates,
 
             Flow::PaymentMethodsCreate
             | Flow::PaymentMethodsList
@@ -94,6 +108,7 @@ impl From<Flow> for ApiIdentifier {
             | Flow::ApiKeyRetrieve
             | Flow::ApiKeyUpdate
             | Flow::ApiKeyRevoke
+            | Flow::ApiKeyRotate
             | Flow::ApiKeyList => Self::ApiKeys,
 
             Flow::DisputesRetrieve
@@ -104,7 +119,10 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::CardsInfo => Self::CardsInfo,
 
-            Flow::CreateFile | Flow::DeleteFile | Flow::RetrieveFile => Self::Files,
+            Flow::CreateFile
+            | Flow::DeleteFile
+            | Flow::RetrieveFile
+            | Flow::RetrieveFileDownloadUrl => Self::Files,
 
             Flow::CacheInvalidate => Self::Cache,
 
@@ -116,7 +134,60 @@ impl From<Flow> for ApiIdentifier {
 
             Flow::Verification => Self::Verification,
 
-            Flow::PaymentLinkInitiate | Flow::PaymentLinkRetrieve => Self::PaymentLink,
+            Flow::PaymentLinkInitiate
+            | Flow::PaymentLinkRetrieve
+            | Flow::PaymentLinkStatus
+            | Flow::PaymentLinkList
+            | Flow::ReusablePaymentLinkCreate
+            | Flow::ReusablePaymentLinkRetrieve
+            | Flow::ReusablePaymentLinkUpdate
+            | Flow::ReusablePaymentLinkInitiate
+            | Flow::ReusablePaymentLinkPayment => Self::PaymentLink,
+
+            Flow::InvoiceCreate
+            | Flow::InvoiceRetrieve
+            | Flow::InvoiceFinalize
+            | Flow::InvoiceVoid
+            | Flow::InvoicePdf
+            | Flow::HostedInvoiceRetrieve
+            | Flow::HostedInvoicePdf => Self::Invoices,
+
+            Flow::SubscriptionPlanCreate
+            | Flow::SubscriptionPlanRetrieve
+            | Flow::SubscriptionPlanUpdate
+            | Flow::SubscriptionCreate
+            | Flow::SubscriptionRetrieve
+            | Flow::SubscriptionUpdate
+            | Flow::SubscriptionCancel => Self::Subscriptions,
+
+            Flow::OrganizationCreate
+            | Flow::OrganizationRetrieve
+            | Flow::OrganizationUpdate
+            | Flow::OrganizationApiKeyCreate
+            | Flow::OrganizationMerchantsList
+            | Flow::OrganizationPaymentsList
+            | Flow::OrganizationRefundsList
+            | Flow::OrganizationPaymentsAggregate
+            | Flow::OrganizationRefundsAggregate => Self::Organization,
+
+            Flow::UserCreate
+            | Flow::UserSignIn
+            | Flow::UserSignOut
+            | Flow::UserRefreshToken
+            | Flow::UserChangePassword
+            | Flow::UserSwitchMerchant
+            | Flow::UserRetrieve
+            | Flow::UsersList
+            | Flow::UserInvite
+            | Flow::UserAcceptInvitation
+            | Flow::UserForgotPassword
+            | Flow::UserResetPassword
+            | Flow::UserRoleUpdate
+            | Flow::UserRoleDelete => Self::User,
+
+            Flow::AuditEventsList => Self::AuditEvents,
+
+            Flow::GetPaymentMetrics => Self::Analytics,
         }
     }
 }
@@ -1,4 +1,5 @@
 use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::user::Permission;
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
@@ -122,7 +123,58 @@ pub async fn retrieve_mandates_list(
         &req,
         payload,
         |state, auth, req| mandate::retrieve_mandates_list(state, auth.merchant_account, req),
-        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::MandateRead),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+/// Mandates - List Mandate Payments
+///
+/// List the payments made against a mandate, most recent first
+#[utoipa::path(
+    get,
+    path = "/mandates/{mandate_id}/payments",
+    params(
+        ("mandate_id" = String, Path, description = "The identifier for mandate"),
+        ("limit" = Option<i64>, Query, description = "The maximum number of payments to include in the response"),
+    ),
+    responses(
+        (status = 200, description = "The payments made against the mandate were retrieved successfully", body = Vec<MandatePaymentResponse>),
+        (status = 404, description = "Mandate does not exist in our records")
+    ),
+    tag = "Mandates",
+    operation_id = "List Mandate Payments",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::MandatePaymentsList))]
+pub async fn retrieve_mandate_payments(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+    payload: web::Query<api_models::mandates::MandatePaymentListConstraints>,
+) -> HttpResponse {
+    let flow = Flow::MandatePaymentsList;
+    let mandate_id = mandates::MandateId {
+        mandate_id: path.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (mandate_id, payload.into_inner()),
+        |state, auth, (mandate_id, constraints)| {
+            mandate::retrieve_mandate_payments(
+                state,
+                auth.merchant_account,
+                mandate_id,
+                constraints,
+            )
+        },
+        &auth::ApiKeyAuth,
         api_locking::LockAction::NotApplicable,
     )
     .await
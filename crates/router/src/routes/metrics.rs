@@ -5,14 +5,18 @@ global_meter!(GLOBAL_METER, "ROUTER_API");
 
 counter_metric!(HEALTH_METRIC, GLOBAL_METER); // No. of health API hits
 counter_metric!(KV_MISS, GLOBAL_METER); // No. of KV misses
-#[cfg(feature = "kms")]
-counter_metric!(AWS_KMS_FAILURES, GLOBAL_METER); // No. of AWS KMS API failures
 
 // API Level Metrics
 counter_metric!(REQUESTS_RECEIVED, GLOBAL_METER);
 counter_metric!(REQUEST_STATUS, GLOBAL_METER);
 histogram_metric!(REQUEST_TIME, GLOBAL_METER);
 histogram_metric!(EXTERNAL_REQUEST_TIME, GLOBAL_METER);
+counter_metric!(REQUESTS_THROTTLED, GLOBAL_METER); // No. of requests rejected by rate limits
+
+// Event Sink Metrics
+counter_metric!(EVENTS_PUBLISHED, GLOBAL_METER); // No. of events delivered to the event sink
+counter_metric!(EVENT_DELIVERY_FAILURES, GLOBAL_METER); // No. of events that failed to be delivered
+counter_metric!(EVENTS_DROPPED, GLOBAL_METER); // No. of events dropped as the event buffer was full
 
 // Operation Level Metrics
 counter_metric!(PAYMENT_OPS_COUNT, GLOBAL_METER);
@@ -61,8 +61,10 @@ pub fn track_response_status_code<Q>(response: &ApplicationResponse<Q>) -> i64 {
         | ApplicationResponse::TextPlain(_)
         | ApplicationResponse::Form(_)
         | ApplicationResponse::PaymenkLinkForm(_)
+        | ApplicationResponse::Html { .. }
         | ApplicationResponse::FileData(_)
         | ApplicationResponse::JsonWithHeaders(_) => 200,
         ApplicationResponse::JsonForRedirection(_) => 302,
+        ApplicationResponse::IdempotentReplay(response) => response.status_code.into(),
     }
 }
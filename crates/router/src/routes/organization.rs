@@ -0,0 +1,214 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::organization as organization_types;
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, organization::*},
+    services::{api, authentication as auth},
+    types::api as api_types,
+};
+
+/// Organization - Create
+///
+/// Create a new organization, which merchant accounts can be created under.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationCreate))]
+pub async fn organization_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<organization_types::OrganizationRequest>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationCreate;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| create_organization(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+/// Organization - Retrieve
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationRetrieve))]
+pub async fn organization_retrieve(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    org_id: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationRetrieve;
+    let payload = organization_types::OrganizationId {
+        organization_id: org_id.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req| retrieve_organization(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Organization - Update
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationUpdate))]
+pub async fn organization_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    org_id: web::Path<String>,
+    json_payload: web::Json<organization_types::OrganizationRequest>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationUpdate;
+    let org_id = org_id.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| update_organization(state, &org_id, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Organization - Create API Key
+///
+/// Generate the API key of an organization, replacing the existing one if any. The plaintext API
+/// key will be displayed only once, so ensure you store it securely.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationApiKeyCreate))]
+pub async fn organization_api_key_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    org_id: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationApiKeyCreate;
+    let payload = organization_types::OrganizationId {
+        organization_id: org_id.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, _, req| create_organization_api_key(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Organization - List Merchants
+///
+/// List the merchant accounts of the organization the API key belongs to.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationMerchantsList))]
+pub async fn organization_merchants_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+) -> HttpResponse {
+    let flow = Flow::OrganizationMerchantsList;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, auth, _| list_organization_merchants(state, auth),
+        &auth::OrganizationApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Organization - List Payments
+///
+/// List the payments of all merchants of the organization the API key belongs to.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationPaymentsList))]
+pub async fn organization_payments_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_types::PaymentListFilterConstraints>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationPaymentsList;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        list_organization_payments,
+        &auth::OrganizationApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+/// Organization - List Refunds
+///
+/// List the refunds of all merchants of the organization the API key belongs to.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationRefundsList))]
+pub async fn organization_refunds_list(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_models::refunds::RefundListRequest>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationRefundsList;
+    Box::pin(api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        list_organization_refunds,
+        &auth::OrganizationApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    ))
+    .await
+}
+
+/// Organization - Aggregate Payments
+///
+/// Aggregate the payments of the organization the API key belongs to over a time range, by
+/// status and currency.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationPaymentsAggregate))]
+pub async fn organization_payments_aggregate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_models::payments::TimeRange>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationPaymentsAggregate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        get_organization_payments_aggregate,
+        &auth::OrganizationApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Organization - Aggregate Refunds
+///
+/// Aggregate the refunds of the organization the API key belongs to over a time range, by status
+/// and currency.
+#[instrument(skip_all, fields(flow = ?Flow::OrganizationRefundsAggregate))]
+pub async fn organization_refunds_aggregate(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<api_models::refunds::TimeRange>,
+) -> HttpResponse {
+    let flow = Flow::OrganizationRefundsAggregate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        get_organization_refunds_aggregate,
+        &auth::OrganizationApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
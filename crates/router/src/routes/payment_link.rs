@@ -1,8 +1,10 @@
 use actix_web::{web, Responder};
+#[cfg(feature = "olap")]
+use api_models::user::Permission;
 use router_env::{instrument, tracing, Flow};
 
 use crate::{
-    core::{api_locking, payment_link::*},
+    core::{api_locking, payment_link::*, payment_methods::Oss},
     services::{api, authentication as auth},
     AppState,
 };
@@ -80,3 +82,249 @@ pub async fn initiate_payment_link(
     )
     .await
 }
+
+pub async fn payment_link_status(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinkStatus;
+    let (merchant_id, payment_id) = path.into_inner();
+    let payload = api_models::payments::PaymentLinkInitiateRequest {
+        payment_id,
+        merchant_id: merchant_id.clone(),
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload.clone(),
+        |state, auth, _| {
+            get_payment_link_status(
+                state,
+                auth.merchant_account,
+                payload.merchant_id.clone(),
+                payload.payment_id.clone(),
+            )
+        },
+        &crate::services::authentication::MerchantIdAuth(merchant_id),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Payment Link - List
+///
+/// To list the payment links of the merchant, optionally filtered by their status
+#[utoipa::path(
+    get,
+    path = "/payment_link/list",
+    params(
+        ("status" = Option<PaymentLinkStatus>, Query, description = "Only list the payment links of this status"),
+        ("limit" = Option<u32>, Query, description = "Limit on the number of objects to return"),
+        ("offset" = Option<u32>, Query, description = "The number of objects to skip"),
+        ("created.gte" = Option<PrimitiveDateTime>, Query, description = "Time greater than or equals to the payment link created time"),
+        ("created.lte" = Option<PrimitiveDateTime>, Query, description = "Time less than or equals to the payment link created time")
+    ),
+    responses(
+        (status = 200, description = "Received the list of payment links", body = PaymentLinkListResponse),
+        (status = 400, description = "Invalid list constraints")
+    ),
+    tag = "Payments",
+    operation_id = "List all Payment Links",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::PaymentLinkList))]
+#[cfg(feature = "olap")]
+pub async fn payment_links_list(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    payload: web::Query<api_models::payments::PaymentLinkListConstraints>,
+) -> impl Responder {
+    let flow = Flow::PaymentLinkList;
+    let payload = payload.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| list_payment_links(state, auth.merchant_account, req),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::PaymentRead),
+            req.headers(),
+        ),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Reusable Payment Link - Create
+///
+/// To create a payment link that can be paid many times, each visit creating a new payment
+#[utoipa::path(
+    post,
+    path = "/payment_link/reusable",
+    request_body = ReusablePaymentLinkCreateRequest,
+    responses(
+        (status = 200, description = "Reusable payment link created", body = ReusablePaymentLinkResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Payments",
+    operation_id = "Create a Reusable Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReusablePaymentLinkCreate))]
+pub async fn reusable_payment_link_create(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<api_models::payments::ReusablePaymentLinkCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::ReusablePaymentLinkCreate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| create_reusable_payment_link(state, auth.merchant_account, req),
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Reusable Payment Link - Retrieve
+///
+/// To retrieve a reusable payment link along with the payments collected through it
+#[utoipa::path(
+    get,
+    path = "/payment_link/reusable/{reusable_payment_link_id}",
+    params(
+        ("reusable_payment_link_id" = String, Path, description = "The identifier for reusable payment link")
+    ),
+    responses(
+        (status = 200, description = "Gets details regarding reusable payment link", body = ReusablePaymentLinkResponse),
+        (status = 404, description = "No reusable payment link found")
+    ),
+    tag = "Payments",
+    operation_id = "Retrieve a Reusable Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReusablePaymentLinkRetrieve))]
+pub async fn reusable_payment_link_retrieve(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::ReusablePaymentLinkRetrieve;
+    let reusable_payment_link_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        reusable_payment_link_id,
+        |state, auth, reusable_payment_link_id| {
+            retrieve_reusable_payment_link(state, auth.merchant_account, reusable_payment_link_id)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Reusable Payment Link - Update
+///
+/// To deactivate or activate a reusable payment link, or to change the number of times it can be
+/// paid
+#[utoipa::path(
+    post,
+    path = "/payment_link/reusable/{reusable_payment_link_id}",
+    params(
+        ("reusable_payment_link_id" = String, Path, description = "The identifier for reusable payment link")
+    ),
+    request_body = ReusablePaymentLinkUpdateRequest,
+    responses(
+        (status = 200, description = "Reusable payment link updated", body = ReusablePaymentLinkResponse),
+        (status = 404, description = "No reusable payment link found")
+    ),
+    tag = "Payments",
+    operation_id = "Update a Reusable Payment Link",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::ReusablePaymentLinkUpdate))]
+pub async fn reusable_payment_link_update(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_models::payments::ReusablePaymentLinkUpdateRequest>,
+) -> impl Responder {
+    let flow = Flow::ReusablePaymentLinkUpdate;
+    let reusable_payment_link_id = path.into_inner();
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            update_reusable_payment_link(
+                state,
+                auth.merchant_account,
+                reusable_payment_link_id.clone(),
+                req,
+            )
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+pub async fn initiate_reusable_payment_link(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<(String, String)>,
+) -> impl Responder {
+    let flow = Flow::ReusablePaymentLinkInitiate;
+    let (merchant_id, reusable_payment_link_id) = path.into_inner();
+    let payload = api_models::payments::ReusablePaymentLinkInitiateRequest {
+        merchant_id: merchant_id.clone(),
+        reusable_payment_link_id,
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| initiate_reusable_payment_link_flow(state, auth.merchant_account, req),
+        &crate::services::authentication::MerchantIdAuth(merchant_id),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+pub async fn pay_reusable_payment_link(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<(String, String)>,
+    form: web::Form<api_models::payments::ReusablePaymentLinkPaymentForm>,
+) -> impl Responder {
+    let flow = Flow::ReusablePaymentLinkPayment;
+    let (merchant_id, reusable_payment_link_id) = path.into_inner();
+    let payload = api_models::payments::ReusablePaymentLinkPaymentRequest {
+        merchant_id: merchant_id.clone(),
+        reusable_payment_link_id,
+        amount: form.into_inner().amount,
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        |state, auth, req| {
+            pay_reusable_payment_link_flow::<Oss>(state, auth.merchant_account, auth.key_store, req)
+        },
+        &crate::services::authentication::MerchantIdAuth(merchant_id),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
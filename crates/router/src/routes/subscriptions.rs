@@ -0,0 +1,276 @@
+use actix_web::{web, Responder};
+use router_env::{instrument, tracing, Flow};
+
+use crate::{
+    core::{api_locking, payment_methods::Oss, subscriptions::*},
+    services::{api, authentication as auth},
+    AppState,
+};
+
+/// Subscription Plans - Create
+///
+/// To create a plan, which sets the price and billing period of the subscriptions to it
+#[utoipa::path(
+    post,
+    path = "/subscriptions/plans",
+    request_body = SubscriptionPlanCreateRequest,
+    responses(
+        (status = 200, description = "Subscription plan created", body = SubscriptionPlanResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Create a Subscription Plan",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionPlanCreate))]
+pub async fn subscription_plan_create(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<api_models::subscriptions::SubscriptionPlanCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionPlanCreate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| create_subscription_plan(state, auth.merchant_account, req),
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Subscription Plans - Retrieve
+///
+/// To retrieve a subscription plan
+#[utoipa::path(
+    get,
+    path = "/subscriptions/plans/{plan_id}",
+    params(
+        ("plan_id" = String, Path, description = "The identifier for subscription plan")
+    ),
+    responses(
+        (status = 200, description = "Gets details regarding subscription plan", body = SubscriptionPlanResponse),
+        (status = 404, description = "No subscription plan found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Retrieve a Subscription Plan",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionPlanRetrieve))]
+pub async fn subscription_plan_retrieve(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionPlanRetrieve;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, plan_id| retrieve_subscription_plan(state, auth.merchant_account, plan_id),
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Subscription Plans - Update
+///
+/// To update the name, description or metadata of a subscription plan, or to deactivate it so
+/// that no new subscriptions can be created to it
+#[utoipa::path(
+    post,
+    path = "/subscriptions/plans/{plan_id}",
+    params(
+        ("plan_id" = String, Path, description = "The identifier for subscription plan")
+    ),
+    request_body = SubscriptionPlanUpdateRequest,
+    responses(
+        (status = 200, description = "Subscription plan updated", body = SubscriptionPlanResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "No subscription plan found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Update a Subscription Plan",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionPlanUpdate))]
+pub async fn subscription_plan_update(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_models::subscriptions::SubscriptionPlanUpdateRequest>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionPlanUpdate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (path.into_inner(), json_payload.into_inner()),
+        |state, auth, (plan_id, req)| {
+            update_subscription_plan(state, auth.merchant_account, plan_id, req)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Subscriptions - Create
+///
+/// To subscribe a customer to a plan, charging them through a multi use mandate at the start of
+/// every billing period
+#[utoipa::path(
+    post,
+    path = "/subscriptions",
+    request_body = SubscriptionCreateRequest,
+    responses(
+        (status = 200, description = "Subscription created", body = SubscriptionResponse),
+        (status = 400, description = "Invalid data")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Create a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionCreate))]
+pub async fn subscription_create(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    json_payload: web::Json<api_models::subscriptions::SubscriptionCreateRequest>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionCreate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, auth, req| {
+            create_subscription::<Oss>(state, auth.merchant_account, auth.key_store, req)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Subscriptions - Retrieve
+///
+/// To retrieve a subscription along with its status and current billing period
+#[utoipa::path(
+    get,
+    path = "/subscriptions/{subscription_id}",
+    params(
+        ("subscription_id" = String, Path, description = "The identifier for subscription")
+    ),
+    responses(
+        (status = 200, description = "Gets details regarding subscription", body = SubscriptionResponse),
+        (status = 404, description = "No subscription found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Retrieve a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionRetrieve))]
+pub async fn subscription_retrieve(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionRetrieve;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, subscription_id| {
+            retrieve_subscription(state, auth.merchant_account, subscription_id)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Subscriptions - Update
+///
+/// To move a subscription to another plan, prorating the change over the current billing period,
+/// or to change its mandate, metadata or whether it is cancelled at the end of the period
+#[utoipa::path(
+    post,
+    path = "/subscriptions/{subscription_id}",
+    params(
+        ("subscription_id" = String, Path, description = "The identifier for subscription")
+    ),
+    request_body = SubscriptionUpdateRequest,
+    responses(
+        (status = 200, description = "Subscription updated", body = SubscriptionResponse),
+        (status = 400, description = "Invalid data"),
+        (status = 404, description = "No subscription found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Update a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionUpdate))]
+pub async fn subscription_update(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+    json_payload: web::Json<api_models::subscriptions::SubscriptionUpdateRequest>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionUpdate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (path.into_inner(), json_payload.into_inner()),
+        |state, auth, (subscription_id, req)| {
+            update_subscription(state, auth.merchant_account, subscription_id, req)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// Subscriptions - Cancel
+///
+/// To cancel a subscription right away, after which the customer is no longer charged
+#[utoipa::path(
+    post,
+    path = "/subscriptions/{subscription_id}/cancel",
+    params(
+        ("subscription_id" = String, Path, description = "The identifier for subscription")
+    ),
+    responses(
+        (status = 200, description = "Subscription cancelled", body = SubscriptionResponse),
+        (status = 400, description = "Subscription has already been cancelled"),
+        (status = 404, description = "No subscription found")
+    ),
+    tag = "Subscriptions",
+    operation_id = "Cancel a Subscription",
+    security(("api_key" = []))
+)]
+#[instrument(skip_all, fields(flow = ?Flow::SubscriptionCancel))]
+pub async fn subscription_cancel(
+    state: web::Data<AppState>,
+    req: actix_web::HttpRequest,
+    path: web::Path<String>,
+) -> impl Responder {
+    let flow = Flow::SubscriptionCancel;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        path.into_inner(),
+        |state, auth, subscription_id| {
+            cancel_subscription(state, auth.merchant_account, subscription_id)
+        },
+        &auth::ApiKeyAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
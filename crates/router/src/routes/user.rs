@@ -0,0 +1,279 @@
+use actix_web::{web, HttpRequest, HttpResponse};
+use api_models::user::{self as user_types, Permission};
+use router_env::{instrument, tracing, Flow};
+
+use super::app::AppState;
+use crate::{
+    core::{api_locking, user as user_core},
+    services::{api, authentication as auth},
+};
+
+/// User - Create
+///
+/// Create a user in a merchant account, or add an existing user to it.
+#[instrument(skip_all, fields(flow = ?Flow::UserCreate))]
+pub async fn user_create(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::CreateUserRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserCreate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| user_core::create_user(state, req),
+        &auth::AdminApiAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserSignIn))]
+pub async fn user_signin(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::SignInRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserSignIn;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| user_core::signin(state, req),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserRefreshToken))]
+pub async fn user_refresh_token(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::RefreshTokenRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserRefreshToken;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| user_core::refresh_token(state, req),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserSignOut))]
+pub async fn user_signout(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::UserSignOut;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, user, _| user_core::signout(state, user),
+        &auth::UserJWTAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserChangePassword))]
+pub async fn user_change_password(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::ChangePasswordRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserChangePassword;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        user_core::change_password,
+        &auth::UserJWTAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserSwitchMerchant))]
+pub async fn user_switch_merchant(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::SwitchMerchantRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserSwitchMerchant;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        user_core::switch_merchant,
+        &auth::UserJWTAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserRetrieve))]
+pub async fn user_retrieve(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::UserRetrieve;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, user, _| user_core::get_user_details(state, user),
+        &auth::UserJWTAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// User - List
+///
+/// List the users of the merchant account the user is signed in to.
+#[instrument(skip_all, fields(flow = ?Flow::UsersList))]
+pub async fn users_list(state: web::Data<AppState>, req: HttpRequest) -> HttpResponse {
+    let flow = Flow::UsersList;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        (),
+        |state, user, _| user_core::list_users(state, user),
+        &auth::JWTAuth(Permission::UsersRead),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[instrument(skip_all, fields(flow = ?Flow::UserRoleUpdate))]
+pub async fn user_role_update(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::UpdateUserRoleRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserRoleUpdate;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        user_core::update_user_role,
+        &auth::JWTAuth(Permission::UsersWrite),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+/// User - Delete Role
+///
+/// Remove a user from the merchant account the requesting user is signed in to.
+#[instrument(skip_all, fields(flow = ?Flow::UserRoleDelete))]
+pub async fn user_role_delete(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    path: web::Path<String>,
+) -> HttpResponse {
+    let flow = Flow::UserRoleDelete;
+    let payload = user_types::UserId {
+        user_id: path.into_inner(),
+    };
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        payload,
+        user_core::delete_user_role,
+        &auth::JWTAuth(Permission::UsersWrite),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[cfg(feature = "email")]
+#[instrument(skip_all, fields(flow = ?Flow::UserInvite))]
+pub async fn user_invite(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::InviteUserRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserInvite;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        user_core::invite_user,
+        &auth::JWTAuth(Permission::UsersWrite),
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[cfg(feature = "email")]
+#[instrument(skip_all, fields(flow = ?Flow::UserAcceptInvitation))]
+pub async fn user_accept_invitation(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::AcceptInvitationRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserAcceptInvitation;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| user_core::accept_invitation(state, req),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[cfg(feature = "email")]
+#[instrument(skip_all, fields(flow = ?Flow::UserForgotPassword))]
+pub async fn user_forgot_password(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::ForgotPasswordRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserForgotPassword;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| user_core::forgot_password(state, req),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
+
+#[cfg(feature = "email")]
+#[instrument(skip_all, fields(flow = ?Flow::UserResetPassword))]
+pub async fn user_reset_password(
+    state: web::Data<AppState>,
+    req: HttpRequest,
+    json_payload: web::Json<user_types::ResetPasswordRequest>,
+) -> HttpResponse {
+    let flow = Flow::UserResetPassword;
+    api::server_wrap(
+        flow,
+        state,
+        &req,
+        json_payload.into_inner(),
+        |state, _, req| user_core::reset_password(state, req),
+        &auth::NoAuth,
+        api_locking::LockAction::NotApplicable,
+    )
+    .await
+}
@@ -1,5 +1,5 @@
 use actix_web::{web, HttpRequest, Responder};
-use api_models::verifications;
+use api_models::{user::Permission, verifications};
 use router_env::{instrument, tracing, Flow};
 
 use super::app::AppState;
@@ -17,7 +17,7 @@ pub async fn apple_pay_merchant_registration(
 ) -> impl Responder {
     let flow = Flow::Verification;
     let merchant_id = path.into_inner();
-    let kms_conf = &state.clone().conf.kms;
+    let secrets_management_conf = &state.clone().conf.secrets_management;
     api::server_wrap(
         flow,
         state,
@@ -28,11 +28,15 @@ pub async fn apple_pay_merchant_registration(
                 state.clone(),
                 &req,
                 body,
-                kms_conf,
+                secrets_management_conf,
                 merchant_id.clone(),
             )
         },
-        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::MerchantConnectorAccountWrite),
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
@@ -60,7 +64,11 @@ pub async fn retrieve_apple_pay_verified_domains(
                 mca_id.to_string(),
             )
         },
-        auth::auth_type(&auth::ApiKeyAuth, &auth::JWTAuth, req.headers()),
+        auth::auth_type(
+            &auth::ApiKeyAuth,
+            &auth::JWTAuth(Permission::MerchantConnectorAccountRead),
+            req.headers(),
+        ),
         api_locking::LockAction::NotApplicable,
     )
     .await
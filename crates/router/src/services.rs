@@ -1,20 +1,18 @@
 pub mod api;
 pub mod authentication;
+pub mod authorization;
+#[cfg(feature = "email")]
+pub mod email;
 pub mod encryption;
 pub mod logger;
 
-#[cfg(feature = "kms")]
-use data_models::errors::StorageError;
-use data_models::errors::StorageResult;
+use data_models::errors::{StorageError, StorageResult};
 use error_stack::{IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms::{self, decrypt::KmsDecrypt};
-#[cfg(not(feature = "kms"))]
-use masking::PeekInterface;
+use external_services::secrets_management::{self, SecretManagementInterface, SecretsDecrypt};
 use masking::StrongSecret;
 #[cfg(feature = "kv_store")]
 use storage_impl::KVRouterStore;
-use storage_impl::RouterStore;
+use storage_impl::{MasterKeyVersions, RouterStore};
 use tokio::sync::oneshot;
 
 pub use self::{api::*, encryption::*};
@@ -35,44 +33,13 @@ pub async fn get_store(
     shut_down_signal: oneshot::Sender<()>,
     test_transaction: bool,
 ) -> StorageResult<Store> {
-    #[cfg(feature = "kms")]
-    let kms_client = kms::get_kms_client(&config.kms).await;
+    let secrets_manager = secrets_management::get_secrets_manager(&config.secrets_management).await;
 
-    #[cfg(feature = "kms")]
-    let master_config = config
-        .master_database
-        .clone()
-        .decrypt_inner(kms_client)
-        .await
-        .change_context(StorageError::InitializationError)
-        .attach_printable("Failed to decrypt master database config")?;
-    #[cfg(not(feature = "kms"))]
-    let master_config = config.master_database.clone().into();
+    let conf = get_database_config(config, None, secrets_manager).await?;
 
-    #[cfg(all(feature = "olap", feature = "kms"))]
-    let replica_config = config
-        .replica_database
-        .clone()
-        .decrypt_inner(kms_client)
-        .await
-        .change_context(StorageError::InitializationError)
-        .attach_printable("Failed to decrypt replica database config")?;
-
-    #[cfg(all(feature = "olap", not(feature = "kms")))]
-    let replica_config = config.replica_database.clone().into();
+    let master_enc_key = get_master_enc_key(config, secrets_manager).await;
 
-    let master_enc_key = get_master_enc_key(
-        config,
-        #[cfg(feature = "kms")]
-        kms_client,
-    )
-    .await;
-    #[cfg(not(feature = "olap"))]
-    let conf = master_config;
-    #[cfg(feature = "olap")]
-    let conf = (master_config, replica_config);
-
-    let store: RouterStore<StoreType> = if test_transaction {
+    let mut store: RouterStore<StoreType> = if test_transaction {
         RouterStore::test_store(conf, &config.redis, master_enc_key).await?
     } else {
         RouterStore::from_config(
@@ -84,6 +51,7 @@ pub async fn get_store(
         )
         .await?
     };
+    store.set_master_key_versions(get_master_key_versions(config, secrets_manager).await);
 
     #[cfg(feature = "kv_store")]
     let store = KVRouterStore::from_store(
@@ -96,26 +64,119 @@ pub async fn get_store(
     Ok(store)
 }
 
-#[allow(clippy::expect_used)]
+/// Creates the store of a tenant, which shares the Redis connections of the `store` of the
+/// deployment.
+pub async fn get_tenant_store(
+    config: &settings::Settings,
+    store: &Store,
+    tenant: &settings::Tenant,
+    test_transaction: bool,
+) -> StorageResult<Store> {
+    let conf = get_database_config(
+        config,
+        Some(tenant),
+        secrets_management::get_secrets_manager(&config.secrets_management).await,
+    )
+    .await?;
+
+    store
+        .for_tenant(conf, &tenant.redis_key_prefix, test_transaction)
+        .await
+}
+
+async fn get_database_config(
+    config: &settings::Settings,
+    tenant: Option<&settings::Tenant>,
+    secrets_manager: &dyn SecretManagementInterface,
+) -> StorageResult<<StoreType as storage_impl::DatabaseStore>::Config> {
+    let master_config = config
+        .master_database
+        .clone()
+        .decrypt_inner(secrets_manager)
+        .await
+        .change_context(StorageError::InitializationError)
+        .attach_printable("Failed to decrypt master database config")?;
+    let master_config = with_tenant_database(master_config, tenant);
+
+    #[cfg(feature = "olap")]
+    let replica_config = config
+        .replica_database
+        .clone()
+        .decrypt_inner(secrets_manager)
+        .await
+        .change_context(StorageError::InitializationError)
+        .attach_printable("Failed to decrypt replica database config")?;
+    #[cfg(feature = "olap")]
+    let replica_config = with_tenant_database(replica_config, tenant);
+
+    #[cfg(not(feature = "olap"))]
+    let conf = master_config;
+    #[cfg(feature = "olap")]
+    let conf = (
+        master_config,
+        replica_config,
+        config
+            .replica_read
+            .max_staleness
+            .map(std::time::Duration::from_secs),
+    );
+
+    Ok(conf)
+}
+
+fn with_tenant_database(
+    mut database: storage_impl::config::Database,
+    tenant: Option<&settings::Tenant>,
+) -> storage_impl::config::Database {
+    if let Some(tenant) = tenant {
+        database.schema = Some(tenant.schema.clone());
+        if let Some(dbname) = &tenant.dbname {
+            database.dbname = dbname.clone();
+        }
+    }
+    database
+}
+
 async fn get_master_enc_key(
     conf: &crate::configs::settings::Settings,
-    #[cfg(feature = "kms")] kms_client: &kms::KmsClient,
+    secrets_manager: &dyn SecretManagementInterface,
+) -> StrongSecret<Vec<u8>> {
+    decrypt_master_enc_key(&conf.secrets.master_enc_key, secrets_manager).await
+}
+
+async fn get_master_key_versions(
+    conf: &crate::configs::settings::Settings,
+    secrets_manager: &dyn SecretManagementInterface,
+) -> MasterKeyVersions {
+    let other_keys = futures::future::join_all(conf.secrets.other_master_enc_keys.iter().map(
+        |master_enc_key| async move {
+            (
+                master_enc_key.version,
+                decrypt_master_enc_key(&master_enc_key.key, secrets_manager).await,
+            )
+        },
+    ))
+    .await;
+
+    MasterKeyVersions {
+        version: conf.secrets.master_enc_key_version,
+        other_keys,
+    }
+}
+
+#[allow(clippy::expect_used)]
+async fn decrypt_master_enc_key(
+    master_enc_key: &settings::Password,
+    secrets_manager: &dyn SecretManagementInterface,
 ) -> StrongSecret<Vec<u8>> {
-    #[cfg(feature = "kms")]
     let master_enc_key = hex::decode(
-        conf.secrets
-            .master_enc_key
-            .clone()
-            .decrypt_inner(kms_client)
+        master_enc_key
+            .decrypt_inner(secrets_manager)
             .await
             .expect("Failed to decrypt master enc key"),
     )
     .expect("Failed to decode from hex");
 
-    #[cfg(not(feature = "kms"))]
-    let master_enc_key =
-        hex::decode(conf.secrets.master_enc_key.peek()).expect("Failed to decode from hex");
-
     StrongSecret::new(master_enc_key)
 }
 
@@ -1,4 +1,5 @@
 pub mod client;
+pub mod idempotency;
 pub mod request;
 use std::{
     collections::HashMap,
@@ -25,14 +26,14 @@ use serde_json::json;
 use tera::{Context, Tera};
 
 use self::request::{HeaderExt, RequestBuilderExt};
-use super::authentication::{AuthInfo, AuthenticateAndFetch};
+use super::authentication::{authorize_api_key_access, AuthInfo, AuthenticateAndFetch};
 use crate::{
     configs::settings::{Connectors, Settings},
     consts,
     core::{
         api_locking,
         errors::{self, CustomResult},
-        payments,
+        payments, rate_limit,
     },
     events::api_logs::ApiEvent,
     logger,
@@ -668,8 +669,15 @@ pub enum ApplicationResponse<R> {
     JsonForRedirection(api::RedirectionResponse),
     Form(Box<RedirectionFormData>),
     PaymenkLinkForm(Box<PaymentLinkFormData>),
+    /// A page rendered from an HTML template, with the values of the context inserted into it.
+    Html {
+        template: HtmlTemplate,
+        context: serde_json::Value,
+    },
     FileData((Vec<u8>, mime::Mime)),
     JsonWithHeaders((R, Vec<(String, String)>)),
+    /// The recorded response of an already processed request, replayed to a retry of it.
+    IdempotentReplay(idempotency::RecordedResponse),
 }
 
 #[derive(Debug, Eq, PartialEq, Clone, serde::Serialize, serde::Deserialize)]
@@ -679,6 +687,36 @@ pub struct PaymentLinkFormData {
     pub sdk_url: String,
 }
 
+/// The HTML templates that pages are rendered from.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub enum HtmlTemplate {
+    PaymentLinkStatus,
+    ReusablePaymentLink,
+    Invoice,
+}
+
+impl HtmlTemplate {
+    fn name(self) -> &'static str {
+        match self {
+            Self::PaymentLinkStatus => "payment_link_status.html",
+            Self::ReusablePaymentLink => "reusable_payment_link.html",
+            Self::Invoice => "invoice.html",
+        }
+    }
+
+    fn source(self) -> &'static str {
+        match self {
+            Self::PaymentLinkStatus => {
+                include_str!("../core/payment_link/payment_link_status.html")
+            }
+            Self::ReusablePaymentLink => {
+                include_str!("../core/payment_link/reusable_payment_link.html")
+            }
+            Self::Invoice => include_str!("../core/invoices/invoice.html"),
+        }
+    }
+}
+
 #[derive(Debug, Eq, PartialEq)]
 pub struct RedirectionFormData {
     pub redirect_form: RedirectForm,
@@ -744,7 +782,13 @@ pub enum AuthFlow {
     Merchant,
 }
 
-#[instrument(skip(request, payload, state, func, api_auth), fields(merchant_id))]
+/// Authenticates and processes a request. Requests carrying an idempotency key are set in
+/// `idempotent_request` once they are authenticated, so that the caller records their response.
+#[instrument(
+    skip(request, payload, state, func, api_auth, idempotent_request),
+    fields(merchant_id)
+)]
+#[allow(clippy::too_many_arguments)]
 pub async fn server_wrap_util<'a, 'b, A, U, T, Q, F, Fut, E, OErr>(
     flow: &'a impl router_env::types::FlowMetric,
     state: web::Data<A>,
@@ -753,6 +797,7 @@ pub async fn server_wrap_util<'a, 'b, A, U, T, Q, F, Fut, E, OErr>(
     func: F,
     api_auth: &dyn AuthenticateAndFetch<U, A>,
     lock_action: api_locking::LockAction,
+    idempotent_request: &mut Option<idempotency::IdempotentRequest>,
 ) -> CustomResult<ApplicationResponse<Q>, OErr>
 where
     F: Fn(A, U, T) -> Fut,
@@ -774,7 +819,8 @@ where
 
     let mut request_state = state.get_ref().clone();
 
-    request_state.add_request_id(request_id);
+    request_state.add_request_id(*request_id);
+    request_state.select_tenant(request.headers()).switch()?;
     let start_instant = Instant::now();
     let serialized_request = masking::masked_serialize(&payload)
         .into_report()
@@ -782,11 +828,13 @@ where
         .change_context(errors::ApiErrorResponse::InternalServerError.switch())?;
 
     // Currently auth failures are not recorded as API events
-    let auth_out = api_auth
+    let (auth_out, auth_type) = api_auth
         .authenticate_and_fetch(request.headers(), &request_state)
         .await
         .switch()?;
 
+    request_state.add_auth_type(auth_type.clone());
+
     let merchant_id = auth_out
         .get_merchant_id()
         .unwrap_or("MERCHANT_ID_NOT_FOUND")
@@ -798,20 +846,39 @@ where
 
     tracing::Span::current().record("merchant_id", &merchant_id);
 
-    let output = {
-        lock_action
-            .clone()
-            .perform_locking_action(&request_state, merchant_id.to_owned())
-            .await
-            .switch()?;
-        let res = func(request_state.clone(), auth_out, payload)
-            .await
-            .switch();
-        lock_action
-            .free_lock_action(&request_state, merchant_id.to_owned())
-            .await
-            .switch()?;
-        res
+    authorize_api_key_access(&auth_out, flow, request, &request_state).switch()?;
+    rate_limit::check_rate_limits(&request_state, flow, &auth_out)
+        .await
+        .switch()?;
+
+    let idempotency_check = idempotency::check_request(
+        &request_state,
+        request,
+        auth_out.get_merchant_id(),
+        &payload,
+    )
+    .await
+    .switch()?;
+    let output = match idempotency_check {
+        idempotency::IdempotencyCheck::Replay(response) => {
+            Ok(ApplicationResponse::IdempotentReplay(response))
+        }
+        idempotency::IdempotencyCheck::Proceed(request) => {
+            *idempotent_request = request;
+            lock_action
+                .clone()
+                .perform_locking_action(&request_state, merchant_id.to_owned())
+                .await
+                .switch()?;
+            let res = func(request_state.clone(), auth_out, payload)
+                .await
+                .switch();
+            lock_action
+                .free_lock_action(&request_state, merchant_id.to_owned())
+                .await
+                .switch()?;
+            res
+        }
     };
     let request_duration = Instant::now()
         .saturating_duration_since(start_instant)
@@ -841,6 +908,7 @@ where
         status_code,
         serialized_request,
         serialized_response,
+        auth_type,
     );
     match api_event.clone().try_into() {
         Ok(event) => {
@@ -887,6 +955,7 @@ where
     let start_instant = Instant::now();
     logger::info!(tag = ?Tag::BeginRequest, payload = ?payload);
 
+    let mut idempotent_request = None;
     let res = match metrics::request::record_request_time_metric(
         server_wrap_util(
             &flow,
@@ -896,6 +965,7 @@ where
             func,
             api_auth,
             lock_action,
+            &mut idempotent_request,
         ),
         &flow,
     )
@@ -957,6 +1027,19 @@ where
             }
         }
 
+        Ok(ApplicationResponse::Html { template, context }) => {
+            match render_html(template, context) {
+                Ok(rendered_html) => http_response_html_data(rendered_html),
+                Err(_) => http_response_err(
+                    r#"{
+                            "error": {
+                                "message": "Error while rendering html page"
+                            }
+                        }"#,
+                ),
+            }
+        }
+
         Ok(ApplicationResponse::JsonWithHeaders((response, headers))) => {
             let request_elapsed_time = request.headers().get(X_HS_LATENCY).and_then(|value| {
                 if value == "true" {
@@ -976,8 +1059,16 @@ where
                 ),
             }
         }
+        Ok(ApplicationResponse::IdempotentReplay(response)) => match response.replay() {
+            Ok(res) => res,
+            Err(error) => log_and_return_error_response(error),
+        },
         Err(error) => log_and_return_error_response(error),
     };
+    let res = match idempotent_request {
+        Some(idempotent_request) => idempotent_request.record_response(res).await,
+        None => res,
+    };
 
     let response_code = res.status().as_u16();
     let end_instant = Instant::now();
@@ -1440,6 +1531,29 @@ pub fn build_payment_link_html(
     }
 }
 
+/// Renders the page of the HTML template with the values of the context.
+pub fn render_html(
+    template: HtmlTemplate,
+    context: serde_json::Value,
+) -> CustomResult<String, errors::ApiErrorResponse> {
+    // Values rendered into templates with an `.html` extension are escaped by tera
+    let mut tera = Tera::default();
+    tera.add_raw_template(template.name(), template.source())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid template {}", template.name()))?;
+
+    let context = Context::from_value(context)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Invalid context of template {}", template.name()))?;
+
+    tera.render(template.name(), &context)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable_lazy(|| format!("Failed to render template {}", template.name()))
+}
+
 fn get_hyper_loader_sdk(sdk_url: &str) -> String {
     format!("<script src=\"{sdk_url}\"></script>")
 }
@@ -5,7 +5,6 @@ use http::{HeaderValue, Method};
 use masking::PeekInterface;
 use once_cell::sync::OnceCell;
 use reqwest::multipart::Form;
-use router_env::tracing_actix_web::RequestId;
 
 use super::{request::Maskable, Request};
 use crate::{
@@ -168,7 +167,7 @@ where
         forward_to_kafka: bool,
     ) -> CustomResult<reqwest::Response, ApiClientError>;
 
-    fn add_request_id(&mut self, request_id: RequestId);
+    fn add_request_id(&mut self, request_id: uuid::Uuid);
     fn get_request_id(&self) -> Option<String>;
     fn add_merchant_id(&mut self, _merchant_id: Option<String>);
     fn add_flow_name(&mut self, flow_name: String);
@@ -351,7 +350,7 @@ impl ApiClient for ProxyClient {
         crate::services::send_request(state, request, option_timeout_secs).await
     }
 
-    fn add_request_id(&mut self, request_id: RequestId) {
+    fn add_request_id(&mut self, request_id: uuid::Uuid) {
         self.request_id
             .replace(request_id.as_hyphenated().to_string());
     }
@@ -404,7 +403,7 @@ impl ApiClient for MockApiClient {
         Err(ApiClientError::UnexpectedState.into())
     }
 
-    fn add_request_id(&mut self, _request_id: RequestId) {
+    fn add_request_id(&mut self, _request_id: uuid::Uuid) {
         // [#2066]: Add Mock implementation for ApiClient
     }
 
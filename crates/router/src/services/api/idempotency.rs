@@ -0,0 +1,322 @@
+//! Replaying of responses to retried requests carrying an `Idempotency-Key` header.
+//!
+//! The first POST request with a given key records a fingerprint of the request in Redis and,
+//! once processed, the response it received. Retries with the same key and an identical request
+//! receive the recorded response without being processed again, while reusing the key for a
+//! different request is rejected. Requests are checked once they are authenticated, authorized and
+//! within their rate limits, and keys are scoped to the authenticated merchant, so that responses
+//! are only ever replayed to the merchant they were recorded for.
+
+use actix_web::{body, http, HttpRequest, HttpResponse};
+use base64::Engine;
+use error_stack::{report, IntoReport, ResultExt};
+use redis_interface::{errors::RedisError, RedisConnectionPool, SetnxReply};
+use router_env::{instrument, tracing};
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    consts,
+    core::errors::{self, RouterResult},
+    headers, logger,
+    routes::app::AppStateInfo,
+};
+
+const IDEMPOTENCY_KEY_PREFIX: &str = "IDEMPOTENCY";
+const IDEMPOTENCY_KEY_MAX_LENGTH: usize = 255;
+
+#[derive(Debug, Serialize, Deserialize)]
+struct IdempotencyRecord {
+    fingerprint: String,
+    /// The response to the request, absent while the request is being processed.
+    response: Option<RecordedResponse>,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize)]
+pub struct RecordedResponse {
+    pub status_code: u16,
+    headers: Vec<(String, String)>,
+    /// The base64 encoded response body.
+    body: String,
+}
+
+impl RecordedResponse {
+    pub fn replay(&self) -> RouterResult<HttpResponse> {
+        let status_code = http::StatusCode::from_u16(self.status_code)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Invalid status code in recorded response")?;
+        let body = consts::BASE64_ENGINE
+            .decode(&self.body)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable("Failed to decode recorded response body")?;
+
+        let mut response = HttpResponse::build(status_code);
+        for header in &self.headers {
+            response.append_header(header.clone());
+        }
+        response.insert_header((headers::IDEMPOTENT_REPLAYED, "true"));
+
+        Ok(response.body(body))
+    }
+}
+
+pub enum IdempotencyCheck {
+    /// The request is to be processed, and its response recorded if it is idempotent.
+    Proceed(Option<IdempotentRequest>),
+    /// The request is a retry of an already processed request, and receives its response.
+    Replay(RecordedResponse),
+}
+
+/// A request carrying an idempotency key, whose response is yet to be recorded.
+pub struct IdempotentRequest {
+    redis_conn: std::sync::Arc<RedisConnectionPool>,
+    redis_key: String,
+    fingerprint: String,
+    key_ttl_in_seconds: u32,
+}
+
+/// Records the start of processing of a POST request carrying an idempotency key, or returns the
+/// recorded response if the request was already processed. Requests not authenticated as a
+/// merchant are always processed.
+#[instrument(skip_all)]
+pub async fn check_request<A, T>(
+    state: &A,
+    request: &HttpRequest,
+    merchant_id: Option<&str>,
+    payload: &T,
+) -> RouterResult<IdempotencyCheck>
+where
+    A: AppStateInfo,
+    T: Serialize,
+{
+    let config = state.conf().idempotency;
+    let Some(idempotency_key) = request.headers().get(headers::IDEMPOTENCY_KEY) else {
+        return Ok(IdempotencyCheck::Proceed(None));
+    };
+    let Some(merchant_id) = merchant_id else {
+        return Ok(IdempotencyCheck::Proceed(None));
+    };
+    if !config.enabled || request.method() != http::Method::POST {
+        return Ok(IdempotencyCheck::Proceed(None));
+    }
+
+    let idempotency_key = idempotency_key
+        .to_str()
+        .ok()
+        .filter(|key| !key.is_empty() && key.len() <= IDEMPOTENCY_KEY_MAX_LENGTH)
+        .ok_or_else(|| {
+            report!(errors::ApiErrorResponse::InvalidRequestData {
+                message: format!(
+                    "`{}` header must be a non-empty string of at most {IDEMPOTENCY_KEY_MAX_LENGTH} characters",
+                    headers::IDEMPOTENCY_KEY
+                ),
+            })
+        })?;
+
+    let redis_conn = state
+        .store()
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+
+    let redis_key = get_redis_key(merchant_id, idempotency_key);
+    let fingerprint = get_request_fingerprint(request, payload)?;
+
+    let inserted = redis_conn
+        .serialize_and_set_key_if_not_exist(
+            &redis_key,
+            IdempotencyRecord {
+                fingerprint: fingerprint.clone(),
+                response: None,
+            },
+            Some(i64::from(config.processing_ttl_in_seconds)),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to record idempotent request in redis")?;
+
+    if inserted == SetnxReply::KeySet {
+        return Ok(IdempotencyCheck::Proceed(Some(IdempotentRequest {
+            redis_conn,
+            redis_key,
+            fingerprint,
+            key_ttl_in_seconds: config.key_ttl_in_seconds,
+        })));
+    }
+
+    let record = redis_conn
+        .get_and_deserialize_key::<IdempotencyRecord>(&redis_key, "IdempotencyRecord")
+        .await
+        .map_err(|error| match error.current_context() {
+            // The record expired since it was found to exist, so the request it was recorded for
+            // never completed
+            RedisError::NotFound => {
+                error.change_context(errors::ApiErrorResponse::IdempotentRequestInProgress)
+            }
+            _ => error
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to fetch idempotent request from redis"),
+        })?;
+
+    if record.fingerprint != fingerprint {
+        return Err(report!(errors::ApiErrorResponse::IdempotencyKeyReused));
+    }
+
+    let response = record
+        .response
+        .ok_or(errors::ApiErrorResponse::IdempotentRequestInProgress)?;
+    logger::info!("Replaying the recorded response of an idempotent request");
+    Ok(IdempotencyCheck::Replay(response))
+}
+
+impl IdempotentRequest {
+    /// Records the response to the request so that it is replayed to retries. Responses that may
+    /// differ on a retry, like server errors, are not recorded and the key is released instead.
+    #[instrument(skip_all)]
+    pub async fn record_response(self, response: HttpResponse) -> HttpResponse {
+        let status_code = response.status();
+        if !is_replayable(status_code) {
+            if let Err(error) = self.redis_conn.delete_key(&self.redis_key).await {
+                logger::error!(?error, "Failed to release idempotency key");
+            }
+            return response;
+        }
+
+        let (response, response_body) = response.into_parts();
+        let response_body = match body::to_bytes(response_body).await {
+            Ok(response_body) => response_body,
+            Err(error) => {
+                logger::error!(%error, "Failed to read response body of idempotent request");
+                return HttpResponse::InternalServerError().finish();
+            }
+        };
+
+        let record = IdempotencyRecord {
+            fingerprint: self.fingerprint,
+            response: Some(RecordedResponse {
+                status_code: status_code.as_u16(),
+                headers: response
+                    .headers()
+                    .iter()
+                    .filter_map(|(name, value)| {
+                        value
+                            .to_str()
+                            .ok()
+                            .map(|value| (name.to_string(), value.to_string()))
+                    })
+                    .collect(),
+                body: consts::BASE64_ENGINE.encode(&response_body),
+            }),
+        };
+        if let Err(error) = self
+            .redis_conn
+            .serialize_and_set_key_with_expiry(
+                &self.redis_key,
+                record,
+                i64::from(self.key_ttl_in_seconds),
+            )
+            .await
+        {
+            logger::error!(?error, "Failed to record response of idempotent request");
+        }
+
+        response.set_body(body::BoxBody::new(response_body))
+    }
+}
+
+/// Responses which a retry of the request could change are not replayed: server errors, as well
+/// as authentication, locking and rate limiting failures.
+fn is_replayable(status_code: http::StatusCode) -> bool {
+    !(status_code.is_server_error()
+        || matches!(
+            status_code,
+            http::StatusCode::UNAUTHORIZED
+                | http::StatusCode::LOCKED
+                | http::StatusCode::TOO_MANY_REQUESTS
+        ))
+}
+
+fn get_redis_key(merchant_id: &str, idempotency_key: &str) -> String {
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(merchant_id.as_bytes());
+    hasher.update(&[0]);
+    hasher.update(idempotency_key.as_bytes());
+
+    format!("{IDEMPOTENCY_KEY_PREFIX}_{}", hasher.finalize().to_hex())
+}
+
+fn get_request_fingerprint<T: Serialize>(
+    request: &HttpRequest,
+    payload: &T,
+) -> RouterResult<String> {
+    let payload = serde_json::to_vec(payload)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to serialize request payload")?;
+
+    let mut hasher = blake3::Hasher::new();
+    hasher.update(request.path().as_bytes());
+    hasher.update(&[0]);
+    hasher.update(request.query_string().as_bytes());
+    hasher.update(&[0]);
+    hasher.update(&payload);
+
+    Ok(hasher.finalize().to_hex().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    #[test]
+    fn test_request_fingerprint() {
+        let request = TestRequest::post().uri("/refunds").to_http_request();
+        let payload = serde_json::json!({ "payment_id": "pay_1", "amount": 100 });
+        let fingerprint = get_request_fingerprint(&request, &payload).unwrap();
+
+        assert_eq!(
+            fingerprint,
+            get_request_fingerprint(&request, &payload).unwrap()
+        );
+
+        let other_payload = serde_json::json!({ "payment_id": "pay_1", "amount": 200 });
+        assert_ne!(
+            fingerprint,
+            get_request_fingerprint(&request, &other_payload).unwrap()
+        );
+
+        let other_request = TestRequest::post()
+            .uri("/payments/pay_1/confirm")
+            .to_http_request();
+        assert_ne!(
+            fingerprint,
+            get_request_fingerprint(&other_request, &payload).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_redis_key_is_scoped_to_merchant() {
+        assert_eq!(
+            get_redis_key("merchant_1", "retry_1"),
+            get_redis_key("merchant_1", "retry_1")
+        );
+        assert_ne!(
+            get_redis_key("merchant_1", "retry_1"),
+            get_redis_key("merchant_1", "retry_2")
+        );
+        assert_ne!(
+            get_redis_key("merchant_1", "retry_1"),
+            get_redis_key("merchant_2", "retry_1")
+        );
+        // The separator keeps the merchant and the key from running into each other
+        assert_ne!(
+            get_redis_key("merchant_1", "retry_1"),
+            get_redis_key("merchant_1retry_1", "")
+        );
+    }
+}
@@ -1,33 +1,54 @@
-use actix_web::http::header::HeaderMap;
-use api_models::{payment_methods::PaymentMethodListRequest, payments};
+mod jwks;
+
+use std::{net::IpAddr, str::FromStr};
+
+use actix_web::{
+    http::{
+        header::{HeaderMap, X_FORWARDED_FOR},
+        Method,
+    },
+    HttpRequest,
+};
+use api_models::{
+    enums::Role, payment_methods::PaymentMethodListRequest, payments, user::Permission,
+};
 use async_trait::async_trait;
-use common_utils::date_time;
+use common_utils::{date_time, pii};
 use error_stack::{report, IntoReport, ResultExt};
-#[cfg(feature = "kms")]
-use external_services::kms::{self, decrypt::KmsDecrypt};
-use jsonwebtoken::{decode, Algorithm, DecodingKey, Validation};
+use external_services::secrets_management::{self, SecretManagementInterface, SecretsDecrypt};
+use jsonwebtoken::{
+    decode, decode_header, encode, Algorithm, DecodingKey, EncodingKey, Header, Validation,
+};
 use masking::{PeekInterface, StrongSecret};
+use router_env::{types::FlowMetric, Flow};
 
 use crate::{
     configs::settings,
     core::{
         api_keys,
         errors::{self, utils::StorageErrorExt, RouterResult},
+        user,
     },
     db::StorageInterface,
-    routes::app::AppStateInfo,
-    services::api,
-    types::domain,
+    routes::{app::AppStateInfo, lock_utils::ApiIdentifier},
+    services::{api, authorization},
+    types::{domain, storage, transformers::parse_api_key_scopes},
     utils::OptionExt,
 };
 
 pub struct AuthenticationData {
     pub merchant_account: domain::MerchantAccount,
     pub key_store: domain::MerchantKeyStore,
+    /// The API key the request was authenticated with, if any.
+    pub api_key: Option<storage::ApiKey>,
 }
 
 pub trait AuthInfo {
     fn get_merchant_id(&self) -> Option<&str>;
+
+    fn get_api_key(&self) -> Option<&storage::ApiKey> {
+        None
+    }
 }
 
 impl AuthInfo for () {
@@ -40,6 +61,51 @@ impl AuthInfo for AuthenticationData {
     fn get_merchant_id(&self) -> Option<&str> {
         Some(&self.merchant_account.merchant_id)
     }
+
+    fn get_api_key(&self) -> Option<&storage::ApiKey> {
+        self.api_key.as_ref()
+    }
+}
+
+/// How a request was authenticated, which identifies the actor that made the request.
+#[derive(Clone, Debug, Eq, PartialEq, serde::Serialize, serde::Deserialize, strum::Display)]
+#[serde(tag = "auth_type", rename_all = "snake_case")]
+#[strum(serialize_all = "snake_case")]
+pub enum AuthenticationType {
+    ApiKey {
+        merchant_id: String,
+        key_id: String,
+    },
+    AdminApiKey,
+    OrganizationApiKey {
+        org_id: String,
+    },
+    MerchantJwt {
+        merchant_id: String,
+        user_id: String,
+    },
+    MerchantId {
+        merchant_id: String,
+    },
+    PublishableKey {
+        merchant_id: String,
+    },
+    NoAuth,
+}
+
+impl AuthenticationType {
+    /// Returns the ID of the API key, organization or user that made the request, if any.
+    pub fn get_actor_id(&self) -> Option<&str> {
+        match self {
+            Self::ApiKey { key_id, .. } => Some(key_id),
+            Self::OrganizationApiKey { org_id } => Some(org_id),
+            Self::MerchantJwt { user_id, .. } => Some(user_id),
+            Self::MerchantId { merchant_id } | Self::PublishableKey { merchant_id } => {
+                Some(merchant_id)
+            }
+            Self::AdminApiKey | Self::NoAuth => None,
+        }
+    }
 }
 
 #[async_trait]
@@ -52,7 +118,7 @@ where
         &self,
         request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<T>;
+    ) -> RouterResult<(T, AuthenticationType)>;
 }
 
 #[derive(Debug)]
@@ -69,8 +135,8 @@ where
         &self,
         _request_headers: &HeaderMap,
         _state: &A,
-    ) -> RouterResult<()> {
-        Ok(())
+    ) -> RouterResult<((), AuthenticationType)> {
+        Ok(((), AuthenticationType::NoAuth))
     }
 }
 
@@ -83,7 +149,7 @@ where
         &self,
         request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<AuthenticationData> {
+    ) -> RouterResult<(AuthenticationData, AuthenticationType)> {
         let api_key = get_api_key(request_headers)
             .change_context(errors::ApiErrorResponse::Unauthorized)?
             .trim();
@@ -98,8 +164,7 @@ where
             let config = state.conf();
             api_keys::get_hash_key(
                 &config.api_keys,
-                #[cfg(feature = "kms")]
-                kms::get_kms_client(&config.kms).await,
+                secrets_management::get_secrets_manager(&config.secrets_management).await,
             )
             .await?
         };
@@ -127,7 +192,7 @@ where
             .store()
             .get_merchant_key_store_by_merchant_id(
                 &stored_api_key.merchant_id,
-                &state.store().get_master_key().to_vec().into(),
+                &state.store().get_master_key_ring(),
             )
             .await
             .change_context(errors::ApiErrorResponse::Unauthorized)
@@ -139,10 +204,207 @@ where
             .await
             .to_not_found_response(errors::ApiErrorResponse::Unauthorized)?;
 
-        Ok(AuthenticationData {
-            merchant_account: merchant,
-            key_store,
-        })
+        api_keys::record_api_key_usage(state.store(), &stored_api_key);
+
+        let auth_type = AuthenticationType::ApiKey {
+            merchant_id: stored_api_key.merchant_id.clone(),
+            key_id: stored_api_key.key_id.clone(),
+        };
+        Ok((
+            AuthenticationData {
+                merchant_account: merchant,
+                key_store,
+                api_key: Some(stored_api_key),
+            },
+            auth_type,
+        ))
+    }
+}
+
+/// Checks that the API key a request was authenticated with, if any, grants the access needed by
+/// the flow to its API, and that the request originates from an IP address the key is allowed to
+/// be used from.
+pub fn authorize_api_key_access(
+    auth: &impl AuthInfo,
+    flow: &impl FlowMetric,
+    request: &HttpRequest,
+    state: &impl AppStateInfo,
+) -> RouterResult<()> {
+    let Some(api_key) = auth.get_api_key() else {
+        return Ok(());
+    };
+
+    if let Some(allowed_ips) = &api_key.allowed_ips {
+        let trusted_proxies = state
+            .conf()
+            .api_keys
+            .trusted_proxies
+            .iter()
+            .filter_map(|proxy| api_keys::IpNetwork::from_str(proxy).ok())
+            .collect::<Vec<_>>();
+        let client_ip = get_client_ip(request, &trusted_proxies);
+        let is_allowed = client_ip.map_or(false, |client_ip| {
+            allowed_ips
+                .iter()
+                .filter_map(|network| api_keys::IpNetwork::from_str(network).ok())
+                .any(|network| network.contains(client_ip))
+        });
+        if !is_allowed {
+            return Err(report!(errors::ApiErrorResponse::AccessForbidden {
+                resource: "this API key from the IP address of the request".to_string(),
+            }))
+            .attach_printable_lazy(|| format!("Request received from {client_ip:?}"));
+        }
+    }
+
+    if let Some(scopes) = &api_key.scopes {
+        let flow = Flow::from_str(&flow.to_string()).ok();
+        let api = flow
+            .clone()
+            .map(ApiIdentifier::from)
+            .map(|api| api.to_string());
+        let granted_access = api
+            .as_ref()
+            .and_then(|api| {
+                parse_api_key_scopes(scopes.clone())
+                    .into_iter()
+                    .find(|scope| &scope.resource == api)
+            })
+            .map_or(api_models::api_keys::ApiKeyAccess::None, |scope| {
+                scope.access
+            });
+
+        if granted_access < get_required_access(flow, request.method()) {
+            return Err(report!(errors::ApiErrorResponse::AccessForbidden {
+                resource: api.unwrap_or_else(|| request.path().to_string()),
+            }))
+            .attach_printable("API key scopes do not grant access to the API");
+        }
+    }
+
+    Ok(())
+}
+
+/// Requests only read data if they are made with the GET method, or belong to one of the flows
+/// which only read data although they are served over POST.
+fn get_required_access(flow: Option<Flow>, method: &Method) -> api_models::api_keys::ApiKeyAccess {
+    let is_read = method == Method::GET
+        || method == Method::HEAD
+        || matches!(
+            flow,
+            Some(
+                Flow::PaymentsList
+                    | Flow::PaymentsRetrieve
+                    | Flow::RefundsList
+                    | Flow::RefundsRetrieve
+                    | Flow::PayoutsRetrieve
+                    | Flow::DisputesList
+            )
+        );
+
+    if is_read {
+        api_models::api_keys::ApiKeyAccess::Read
+    } else {
+        api_models::api_keys::ApiKeyAccess::Write
+    }
+}
+
+/// Determines the IP address a request originates from. This is the address of the peer, unless
+/// the peer is a trusted proxy, in which case the addresses in the `X-Forwarded-For` header are
+/// walked from the last one added, skipping those of trusted proxies, since only the addresses
+/// appended by trusted proxies can't be spoofed by the client.
+fn get_client_ip(request: &HttpRequest, trusted_proxies: &[api_keys::IpNetwork]) -> Option<IpAddr> {
+    let is_trusted = |ip: IpAddr| trusted_proxies.iter().any(|proxy| proxy.contains(ip));
+
+    let mut client_ip = request.peer_addr()?.ip();
+    if !is_trusted(client_ip) {
+        return Some(client_ip);
+    }
+
+    let forwarded_ips = request
+        .headers()
+        .get_all(X_FORWARDED_FOR)
+        .filter_map(|header| header.to_str().ok())
+        .flat_map(|header| header.split(','))
+        .map(|address| parse_ip_address(address.trim()))
+        .collect::<Vec<_>>();
+    for forwarded_ip in forwarded_ips.into_iter().rev() {
+        // An address that can't be parsed can't be attributed to a trusted proxy
+        client_ip = forwarded_ip?;
+        if !is_trusted(client_ip) {
+            break;
+        }
+    }
+    Some(client_ip)
+}
+
+/// Parses the IP address of a client, which may include the port.
+fn parse_ip_address(address: &str) -> Option<IpAddr> {
+    address
+        .parse::<std::net::SocketAddr>()
+        .map(|address| address.ip())
+        .or_else(|_| address.parse::<IpAddr>())
+        .ok()
+}
+
+pub struct OrganizationAuthenticationData {
+    pub organization: diesel_models::organization::Organization,
+}
+
+impl AuthInfo for OrganizationAuthenticationData {
+    fn get_merchant_id(&self) -> Option<&str> {
+        None
+    }
+}
+
+/// Authenticates requests made with the API key of an organization, which grants read access to
+/// the data of all merchants of the organization.
+#[derive(Debug)]
+pub struct OrganizationApiKeyAuth;
+
+#[async_trait]
+impl<A> AuthenticateAndFetch<OrganizationAuthenticationData, A> for OrganizationApiKeyAuth
+where
+    A: AppStateInfo + Sync,
+{
+    async fn authenticate_and_fetch(
+        &self,
+        request_headers: &HeaderMap,
+        state: &A,
+    ) -> RouterResult<(OrganizationAuthenticationData, AuthenticationType)> {
+        let api_key = get_api_key(request_headers)
+            .change_context(errors::ApiErrorResponse::Unauthorized)?
+            .trim();
+        if api_key.is_empty() {
+            return Err(errors::ApiErrorResponse::Unauthorized)
+                .into_report()
+                .attach_printable("API key is empty");
+        }
+
+        let api_key = api_keys::PlaintextApiKey::from(api_key);
+        let hash_key = {
+            let config = state.conf();
+            api_keys::get_hash_key(
+                &config.api_keys,
+                secrets_management::get_secrets_manager(&config.secrets_management).await,
+            )
+            .await?
+        };
+        let hashed_api_key = api_key.keyed_hash(hash_key.peek());
+
+        let organization = state
+            .store()
+            .find_organization_by_hashed_api_key_optional(hashed_api_key.into())
+            .await
+            .change_context(errors::ApiErrorResponse::InternalServerError) // If retrieve failed
+            .attach_printable("Failed to retrieve organization")?
+            .ok_or(report!(errors::ApiErrorResponse::Unauthorized)) // If retrieve returned `None`
+            .attach_printable("Organization not authenticated")?;
+
+        let auth_type = AuthenticationType::OrganizationApiKey {
+            org_id: organization.org_id.clone(),
+        };
+        Ok((OrganizationAuthenticationData { organization }, auth_type))
     }
 }
 
@@ -151,20 +413,16 @@ static ADMIN_API_KEY: tokio::sync::OnceCell<StrongSecret<String>> =
 
 pub async fn get_admin_api_key(
     secrets: &settings::Secrets,
-    #[cfg(feature = "kms")] kms_client: &kms::KmsClient,
+    secrets_manager: &dyn SecretManagementInterface,
 ) -> RouterResult<&'static StrongSecret<String>> {
     ADMIN_API_KEY
         .get_or_try_init(|| async {
-            #[cfg(feature = "kms")]
             let admin_api_key = secrets
-                .kms_encrypted_admin_api_key
-                .decrypt_inner(kms_client)
+                .admin_api_key
+                .decrypt_inner(secrets_manager)
                 .await
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed to KMS decrypt admin API key")?;
-
-            #[cfg(not(feature = "kms"))]
-            let admin_api_key = secrets.admin_api_key.clone();
+                .attach_printable("Failed to decrypt admin API key")?;
 
             Ok(StrongSecret::new(admin_api_key))
         })
@@ -183,24 +441,30 @@ where
         &self,
         request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<()> {
+    ) -> RouterResult<((), AuthenticationType)> {
         let request_admin_api_key =
             get_api_key(request_headers).change_context(errors::ApiErrorResponse::Unauthorized)?;
-        let conf = state.conf();
-
-        let admin_api_key = get_admin_api_key(
-            &conf.secrets,
-            #[cfg(feature = "kms")]
-            kms::get_kms_client(&conf.kms).await,
-        )
-        .await?;
+        let admin_api_key = match state.tenant_admin_api_key() {
+            Some(tenant_admin_api_key) => tenant_admin_api_key,
+            None if state.accepts_deployment_admin_api_key() => {
+                let conf = state.conf();
+                get_admin_api_key(
+                    &conf.secrets,
+                    secrets_management::get_secrets_manager(&conf.secrets_management).await,
+                )
+                .await?
+                .clone()
+            }
+            None => Err(report!(errors::ApiErrorResponse::Unauthorized)
+                .attach_printable("Tenant does not accept the admin API key of the deployment"))?,
+        };
 
         if request_admin_api_key != admin_api_key.peek() {
             Err(report!(errors::ApiErrorResponse::Unauthorized)
                 .attach_printable("Admin Authentication Failure"))?;
         }
 
-        Ok(())
+        Ok(((), AuthenticationType::AdminApiKey))
     }
 }
 
@@ -216,12 +480,12 @@ where
         &self,
         _request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<AuthenticationData> {
+    ) -> RouterResult<(AuthenticationData, AuthenticationType)> {
         let key_store = state
             .store()
             .get_merchant_key_store_by_merchant_id(
                 self.0.as_ref(),
-                &state.store().get_master_key().to_vec().into(),
+                &state.store().get_master_key_ring(),
             )
             .await
             .map_err(|e| {
@@ -245,10 +509,16 @@ where
                 }
             })?;
 
-        Ok(AuthenticationData {
-            merchant_account: merchant,
-            key_store,
-        })
+        Ok((
+            AuthenticationData {
+                merchant_account: merchant,
+                key_store,
+                api_key: None,
+            },
+            AuthenticationType::MerchantId {
+                merchant_id: self.0.clone(),
+            },
+        ))
     }
 }
 
@@ -264,7 +534,7 @@ where
         &self,
         request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<AuthenticationData> {
+    ) -> RouterResult<(AuthenticationData, AuthenticationType)> {
         let publishable_key =
             get_api_key(request_headers).change_context(errors::ApiErrorResponse::Unauthorized)?;
 
@@ -279,18 +549,92 @@ where
                     e.change_context(errors::ApiErrorResponse::InternalServerError)
                 }
             })
+            .map(|auth| {
+                let auth_type = AuthenticationType::PublishableKey {
+                    merchant_id: auth.merchant_account.merchant_id.clone(),
+                };
+                (auth, auth_type)
+            })
     }
 }
 
-#[derive(Debug)]
-pub(crate) struct JWTAuth;
+/// The type of a JWT issued to a dashboard user, which restricts what the token can be used for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TokenType {
+    Access,
+    Refresh,
+}
+
+/// The claims of the access and refresh tokens issued to dashboard users.
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct AuthToken {
+    pub user_id: String,
+    pub merchant_id: String,
+    pub org_id: String,
+    pub role: Role,
+    pub token_type: TokenType,
+    pub iat: u64,
+    pub exp: u64,
+}
+
+impl AuthToken {
+    pub fn new(
+        user_role: &storage::UserRole,
+        token_type: TokenType,
+        ttl_in_seconds: u32,
+    ) -> RouterResult<Self> {
+        let iat = get_current_unix_timestamp()?;
+        Ok(Self {
+            user_id: user_role.user_id.clone(),
+            merchant_id: user_role.merchant_id.clone(),
+            org_id: user_role.org_id.clone(),
+            role: user_role.role,
+            token_type,
+            iat,
+            exp: iat + u64::from(ttl_in_seconds),
+        })
+    }
+
+    fn get_auth_type(&self) -> AuthenticationType {
+        AuthenticationType::MerchantJwt {
+            merchant_id: self.merchant_id.clone(),
+            user_id: self.user_id.clone(),
+        }
+    }
+}
+
+/// The user and merchant account a dashboard user's access token was issued for.
+#[derive(Debug, Clone)]
+pub struct UserFromToken {
+    pub user_id: String,
+    pub merchant_id: String,
+    pub org_id: String,
+    pub role: Role,
+}
 
-#[derive(serde::Deserialize)]
-struct JwtAuthPayloadFetchUnit {
-    #[serde(rename(deserialize = "exp"))]
-    _exp: u64,
+impl AuthInfo for UserFromToken {
+    fn get_merchant_id(&self) -> Option<&str> {
+        Some(&self.merchant_id)
+    }
 }
 
+impl From<AuthToken> for UserFromToken {
+    fn from(token: AuthToken) -> Self {
+        Self {
+            user_id: token.user_id,
+            merchant_id: token.merchant_id,
+            org_id: token.org_id,
+            role: token.role,
+        }
+    }
+}
+
+/// Authenticates a dashboard user with their access token, requiring their role to grant the
+/// permission.
+#[derive(Debug)]
+pub(crate) struct JWTAuth(pub Permission);
+
 #[async_trait]
 impl<A> AuthenticateAndFetch<(), A> for JWTAuth
 where
@@ -300,18 +644,28 @@ where
         &self,
         request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<()> {
-        let mut token = get_jwt(request_headers)?;
-        token = strip_jwt_token(token)?;
-        decode_jwt::<JwtAuthPayloadFetchUnit>(token, state)
-            .await
-            .map(|_| ())
+    ) -> RouterResult<((), AuthenticationType)> {
+        let payload = parse_access_token(request_headers, state).await?;
+        authorization::check_authorization(payload.role, self.0)?;
+        Ok(((), payload.get_auth_type()))
     }
 }
 
-#[derive(serde::Deserialize)]
-struct JwtAuthPayloadFetchMerchantAccount {
-    merchant_id: String,
+#[async_trait]
+impl<A> AuthenticateAndFetch<UserFromToken, A> for JWTAuth
+where
+    A: AppStateInfo + Sync,
+{
+    async fn authenticate_and_fetch(
+        &self,
+        request_headers: &HeaderMap,
+        state: &A,
+    ) -> RouterResult<(UserFromToken, AuthenticationType)> {
+        let payload = parse_access_token(request_headers, state).await?;
+        authorization::check_authorization(payload.role, self.0)?;
+        let auth_type = payload.get_auth_type();
+        Ok((payload.into(), auth_type))
+    }
 }
 
 #[async_trait]
@@ -323,15 +677,15 @@ where
         &self,
         request_headers: &HeaderMap,
         state: &A,
-    ) -> RouterResult<AuthenticationData> {
-        let mut token = get_jwt(request_headers)?;
-        token = strip_jwt_token(token)?;
-        let payload = decode_jwt::<JwtAuthPayloadFetchMerchantAccount>(token, state).await?;
+    ) -> RouterResult<(AuthenticationData, AuthenticationType)> {
+        let payload = parse_access_token(request_headers, state).await?;
+        authorization::check_authorization(payload.role, self.0)?;
+
         let key_store = state
             .store()
             .get_merchant_key_store_by_merchant_id(
                 &payload.merchant_id,
-                &state.store().get_master_key().to_vec().into(),
+                &state.store().get_master_key_ring(),
             )
             .await
             .change_context(errors::ApiErrorResponse::InvalidJwtToken)
@@ -343,13 +697,193 @@ where
             .await
             .change_context(errors::ApiErrorResponse::InvalidJwtToken)?;
 
-        Ok(AuthenticationData {
-            merchant_account: merchant,
-            key_store,
-        })
+        Ok((
+            AuthenticationData {
+                merchant_account: merchant,
+                key_store,
+                api_key: None,
+            },
+            payload.get_auth_type(),
+        ))
+    }
+}
+
+/// Authenticates a dashboard user with their access token, for routes that act on the user
+/// themselves and so require no permission.
+#[derive(Debug)]
+pub(crate) struct UserJWTAuth;
+
+#[async_trait]
+impl<A> AuthenticateAndFetch<UserFromToken, A> for UserJWTAuth
+where
+    A: AppStateInfo + Sync,
+{
+    async fn authenticate_and_fetch(
+        &self,
+        request_headers: &HeaderMap,
+        state: &A,
+    ) -> RouterResult<(UserFromToken, AuthenticationType)> {
+        let payload = parse_access_token(request_headers, state).await?;
+        let auth_type = payload.get_auth_type();
+        Ok((payload.into(), auth_type))
+    }
+}
+
+/// Authenticates a dashboard user for routes that carry the merchant ID in their path, requiring
+/// the user's access token to have been issued for that merchant account.
+#[derive(Debug)]
+pub(crate) struct JWTAuthMerchantFromRoute {
+    pub merchant_id: String,
+    pub required_permission: Permission,
+}
+
+#[async_trait]
+impl<A> AuthenticateAndFetch<(), A> for JWTAuthMerchantFromRoute
+where
+    A: AppStateInfo + Sync,
+{
+    async fn authenticate_and_fetch(
+        &self,
+        request_headers: &HeaderMap,
+        state: &A,
+    ) -> RouterResult<((), AuthenticationType)> {
+        let payload = parse_access_token(request_headers, state).await?;
+        authorization::check_authorization(payload.role, self.required_permission)?;
+
+        if payload.merchant_id != self.merchant_id {
+            return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+                .attach_printable("Merchant ID in the route does not match that of the token");
+        }
+
+        Ok(((), payload.get_auth_type()))
     }
 }
 
+/// Parses and validates the access token of the request, rejecting tokens that were revoked.
+async fn parse_access_token<A>(request_headers: &HeaderMap, state: &A) -> RouterResult<AuthToken>
+where
+    A: AppStateInfo + Sync,
+{
+    let token = strip_jwt_token(get_jwt(request_headers)?)?;
+    let header = decode_accepted_header(token, &state.conf().jwt)?;
+    let payload = if is_hmac_algorithm(header.alg) {
+        decode_jwt::<AuthToken>(token, state).await?
+    } else {
+        let identity_provider_token =
+            decode_identity_provider_jwt::<IdentityProviderToken>(token, state).await?;
+        get_identity_provider_user_token(state, identity_provider_token).await?
+    };
+
+    if payload.token_type != TokenType::Access {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable("Token is not an access token");
+    }
+
+    if is_token_revoked(&*state.store(), &payload.user_id, payload.iat).await? {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable("Token was revoked");
+    }
+
+    Ok(payload)
+}
+
+/// The claims of the access tokens issued to dashboard users by the identity provider. Only the
+/// user is taken from the token, their role and merchant account are those recorded for them.
+#[derive(Debug, serde::Deserialize)]
+struct IdentityProviderToken {
+    email: pii::Email,
+    iat: u64,
+    exp: u64,
+}
+
+/// Resolves the user an identity provider token was issued to, authenticating them for the
+/// merchant account they sign in to by default with their recorded role.
+async fn get_identity_provider_user_token<A>(
+    state: &A,
+    token: IdentityProviderToken,
+) -> RouterResult<AuthToken>
+where
+    A: AppStateInfo + Sync,
+{
+    let store = state.store();
+    let user = store
+        .find_user_by_email(&user::normalize_email(&token.email))
+        .await
+        .to_not_found_response(errors::ApiErrorResponse::InvalidJwtToken)
+        .attach_printable("No user exists with the email of the identity provider token")?;
+    let user_role = user::find_default_user_role(&*store, &user.user_id)
+        .await?
+        .ok_or(errors::ApiErrorResponse::InvalidJwtToken)
+        .into_report()
+        .attach_printable("User is not an active member of any merchant account")?;
+
+    Ok(AuthToken {
+        user_id: user_role.user_id,
+        merchant_id: user_role.merchant_id,
+        org_id: user_role.org_id,
+        role: user_role.role,
+        token_type: TokenType::Access,
+        iat: token.iat,
+        exp: token.exp,
+    })
+}
+
+const USER_TOKEN_BLACKLIST_PREFIX: &str = "BLACKLIST_USER";
+
+/// Revokes the tokens issued to a user until now. The revocation is retained for the lifetime of
+/// the longest-lived token.
+pub async fn revoke_user_tokens(
+    store: &dyn StorageInterface,
+    user_id: &str,
+    refresh_token_ttl_in_seconds: u32,
+) -> RouterResult<()> {
+    let redis_conn = store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+
+    let now = date_time::now_unix_timestamp();
+    redis_conn
+        .set_key_with_expiry(
+            &format!("{USER_TOKEN_BLACKLIST_PREFIX}_{user_id}"),
+            now,
+            i64::from(refresh_token_ttl_in_seconds),
+        )
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to revoke user tokens")
+}
+
+/// Checks whether a token issued to the user at `issued_at` was revoked. Tokens are issued with
+/// second precision, hence tokens issued in the second of the revocation are revoked as well.
+pub async fn is_token_revoked(
+    store: &dyn StorageInterface,
+    user_id: &str,
+    issued_at: u64,
+) -> RouterResult<bool> {
+    let redis_conn = store
+        .get_redis_conn()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to get redis connection")?;
+
+    let revoked_at = redis_conn
+        .get_key::<Option<i64>>(&format!("{USER_TOKEN_BLACKLIST_PREFIX}_{user_id}"))
+        .await
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to fetch user token revocation")?;
+
+    Ok(revoked_at.map_or(false, |revoked_at| {
+        i64::try_from(issued_at).map_or(true, |issued_at| issued_at <= revoked_at)
+    }))
+}
+
+fn get_current_unix_timestamp() -> RouterResult<u64> {
+    u64::try_from(date_time::now_unix_timestamp())
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Current time precedes the Unix epoch")
+}
+
 pub trait ClientSecretFetch {
     fn get_client_secret(&self) -> Option<&String>;
 }
@@ -462,47 +996,127 @@ static JWT_SECRET: tokio::sync::OnceCell<StrongSecret<String>> = tokio::sync::On
 
 pub async fn get_jwt_secret(
     secrets: &settings::Secrets,
-    #[cfg(feature = "kms")] kms_client: &kms::KmsClient,
+    secrets_manager: &dyn SecretManagementInterface,
 ) -> RouterResult<&'static StrongSecret<String>> {
     JWT_SECRET
         .get_or_try_init(|| async {
-            #[cfg(feature = "kms")]
             let jwt_secret = secrets
-                .kms_encrypted_jwt_secret
-                .decrypt_inner(kms_client)
+                .jwt_secret
+                .decrypt_inner(secrets_manager)
                 .await
                 .change_context(errors::ApiErrorResponse::InternalServerError)
-                .attach_printable("Failed to KMS decrypt JWT secret")?;
-
-            #[cfg(not(feature = "kms"))]
-            let jwt_secret = secrets.jwt_secret.clone();
+                .attach_printable("Failed to decrypt JWT secret")?;
 
             Ok(StrongSecret::new(jwt_secret))
         })
         .await
 }
 
+/// Decodes a JWT issued by the router, which is signed with the JWT secret using one of the
+/// configured HMAC algorithms.
 pub async fn decode_jwt<T>(token: &str, state: &impl AppStateInfo) -> RouterResult<T>
 where
     T: serde::de::DeserializeOwned,
 {
     let conf = state.conf();
+    let header = decode_accepted_header(token, &conf.jwt)?;
+    if !is_hmac_algorithm(header.alg) {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable("Token was not issued by the router");
+    }
+
     let secret = get_jwt_secret(
         &conf.secrets,
-        #[cfg(feature = "kms")]
-        kms::get_kms_client(&conf.kms).await,
+        secrets_management::get_secrets_manager(&conf.secrets_management).await,
     )
-    .await?
-    .peek()
-    .as_bytes();
+    .await?;
+
+    decode::<T>(
+        token,
+        &DecodingKey::from_secret(secret.peek().as_bytes()),
+        &Validation::new(header.alg),
+    )
+    .map(|decoded| decoded.claims)
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InvalidJwtToken)
+}
 
-    let key = DecodingKey::from_secret(secret);
-    decode::<T>(token, &key, &Validation::new(Algorithm::HS256))
+/// Decodes a JWT issued by the identity provider, which is signed using one of the configured RSA
+/// or ECDSA algorithms and verified against the JWKS. Its issuer and audience must also be among
+/// those configured.
+async fn decode_identity_provider_jwt<T>(token: &str, state: &impl AppStateInfo) -> RouterResult<T>
+where
+    T: serde::de::DeserializeOwned,
+{
+    let conf = state.conf();
+    let header = decode_accepted_header(token, &conf.jwt)?;
+    if is_hmac_algorithm(header.alg) {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable("Token was not issued by the identity provider");
+    }
+
+    // Tokens missing the claims must be rejected rather than let through unvalidated
+    let mut validation = Validation::new(header.alg);
+    if let Some(issuers) = &conf.jwt.issuers {
+        validation.set_issuer(issuers);
+        validation.required_spec_claims.insert("iss".to_string());
+    }
+    if let Some(audiences) = &conf.jwt.audiences {
+        validation.set_audience(audiences);
+        validation.required_spec_claims.insert("aud".to_string());
+    }
+
+    let key = jwks::get_decoding_key(&conf.jwt, header.alg, header.kid.as_deref()).await?;
+    decode::<T>(token, &key, &validation)
         .map(|decoded| decoded.claims)
         .into_report()
         .change_context(errors::ApiErrorResponse::InvalidJwtToken)
 }
 
+/// Decodes the header of a JWT, requiring it to be signed with one of the configured algorithms.
+fn decode_accepted_header(token: &str, conf: &settings::JwtSettings) -> RouterResult<Header> {
+    let header = decode_header(token)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InvalidJwtToken)?;
+
+    if !conf.algorithms.contains(&header.alg) {
+        return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+            .attach_printable_lazy(|| format!("Token algorithm {:?} is not accepted", header.alg));
+    }
+
+    Ok(header)
+}
+
+fn is_hmac_algorithm(algorithm: Algorithm) -> bool {
+    matches!(
+        algorithm,
+        Algorithm::HS256 | Algorithm::HS384 | Algorithm::HS512
+    )
+}
+
+pub async fn generate_jwt<T>(claims: &T, state: &impl AppStateInfo) -> RouterResult<String>
+where
+    T: serde::Serialize,
+{
+    let conf = state.conf();
+    let secret = get_jwt_secret(
+        &conf.secrets,
+        secrets_management::get_secrets_manager(&conf.secrets_management).await,
+    )
+    .await?
+    .peek()
+    .as_bytes();
+
+    encode(
+        &Header::new(Algorithm::HS256),
+        claims,
+        &EncodingKey::from_secret(secret),
+    )
+    .into_report()
+    .change_context(errors::ApiErrorResponse::InternalServerError)
+    .attach_printable("Failed to encode JWT")
+}
+
 pub fn get_api_key(headers: &HeaderMap) -> RouterResult<&str> {
     get_header_value_by_key("api-key".into(), headers)?.get_required_value("api_key")
 }
@@ -551,3 +1165,43 @@ where
     }
     default_auth
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use actix_web::test::TestRequest;
+
+    use super::*;
+
+    fn trusted_proxies() -> Vec<api_keys::IpNetwork> {
+        vec![api_keys::IpNetwork::from_str("10.0.0.0/8").unwrap()]
+    }
+
+    #[test]
+    fn spoofed_forwarded_for_header_is_ignored() {
+        let allowed_network = api_keys::IpNetwork::from_str("203.0.113.7").unwrap();
+        let request = TestRequest::default()
+            .peer_addr("198.51.100.1:443".parse().unwrap())
+            .insert_header((X_FORWARDED_FOR, "203.0.113.7"))
+            .to_http_request();
+
+        let client_ip = get_client_ip(&request, &trusted_proxies()).unwrap();
+        assert_eq!(client_ip, "198.51.100.1".parse::<IpAddr>().unwrap());
+        assert!(!allowed_network.contains(client_ip));
+    }
+
+    #[test]
+    fn forwarded_for_header_of_trusted_proxy_is_used() {
+        let request = TestRequest::default()
+            .peer_addr("10.0.0.2:443".parse().unwrap())
+            .insert_header((X_FORWARDED_FOR, "203.0.113.7, 198.51.100.1, 10.0.0.1"))
+            .to_http_request();
+
+        // The first address was supplied by the client, only the ones appended by the trusted
+        // proxies can be relied upon
+        assert_eq!(
+            get_client_ip(&request, &trusted_proxies()),
+            Some("198.51.100.1".parse().unwrap())
+        );
+    }
+}
@@ -0,0 +1,202 @@
+//! The keys that asymmetrically signed JWTs are verified with, loaded from a JWKS document.
+
+use std::time::{Duration, Instant};
+
+use error_stack::{report, IntoReport, ResultExt};
+use jsonwebtoken::{
+    jwk::{Jwk, JwkSet},
+    Algorithm, DecodingKey,
+};
+use once_cell::sync::Lazy;
+use router_env::logger;
+use tokio::sync::RwLock;
+
+use crate::{
+    configs::settings,
+    core::errors::{self, RouterResult},
+};
+
+/// The JWKS is reloaded at most this often to look up key IDs missing from it, so that tokens
+/// with unknown key IDs cannot cause the JWKS to be reloaded on every request.
+const MIN_RELOAD_INTERVAL: Duration = Duration::from_secs(30);
+
+struct LoadedJwks {
+    jwks: JwkSet,
+    loaded_at: Instant,
+}
+
+static JWKS: Lazy<RwLock<Option<LoadedJwks>>> = Lazy::new(|| RwLock::new(None));
+
+/// Returns the key to verify a token signed with the algorithm and key ID with. The JWKS is
+/// reloaded once it is older than the configured reload interval, and early when the key ID is
+/// missing from it, to pick up rotated keys.
+pub async fn get_decoding_key(
+    conf: &settings::JwtSettings,
+    algorithm: Algorithm,
+    key_id: Option<&str>,
+) -> RouterResult<DecodingKey> {
+    let reload_interval = Duration::from_secs(conf.jwks_reload_interval_in_seconds);
+
+    if let Some(key) = find_cached_key(reload_interval, algorithm, key_id).await? {
+        return Ok(key);
+    }
+
+    let mut cached_jwks = JWKS.write().await;
+
+    // Another request may have reloaded the JWKS while waiting for the lock
+    if let Some(loaded) = cached_jwks
+        .as_ref()
+        .filter(|loaded| loaded.loaded_at.elapsed() < MIN_RELOAD_INTERVAL)
+    {
+        return find_key(&loaded.jwks, algorithm, key_id)?.ok_or_else(key_not_found);
+    }
+
+    match load_jwks(conf).await {
+        Ok(jwks) => {
+            let key = find_key(&jwks, algorithm, key_id)?;
+            *cached_jwks = Some(LoadedJwks {
+                jwks,
+                loaded_at: Instant::now(),
+            });
+            key.ok_or_else(key_not_found)
+        }
+        // Keep verifying tokens with the previously loaded keys rather than rejecting all tokens
+        Err(error) => match cached_jwks.as_ref() {
+            Some(loaded) => {
+                logger::error!(
+                    ?error,
+                    "Failed to reload JWKS, using previously loaded keys"
+                );
+                find_key(&loaded.jwks, algorithm, key_id)?.ok_or_else(key_not_found)
+            }
+            None => Err(error),
+        },
+    }
+}
+
+async fn find_cached_key(
+    reload_interval: Duration,
+    algorithm: Algorithm,
+    key_id: Option<&str>,
+) -> RouterResult<Option<DecodingKey>> {
+    let cached_jwks = JWKS.read().await;
+    let Some(loaded) = cached_jwks
+        .as_ref()
+        .filter(|loaded| loaded.loaded_at.elapsed() < reload_interval)
+    else {
+        return Ok(None);
+    };
+
+    match find_key(&loaded.jwks, algorithm, key_id)? {
+        Some(key) => Ok(Some(key)),
+        None if loaded.loaded_at.elapsed() < MIN_RELOAD_INTERVAL => Err(key_not_found()),
+        None => Ok(None),
+    }
+}
+
+async fn load_jwks(conf: &settings::JwtSettings) -> RouterResult<JwkSet> {
+    let jwks = match (&conf.jwks, &conf.jwks_file) {
+        (Some(jwks), _) => jwks.clone(),
+        (None, Some(jwks_file)) => {
+            let jwks_file = jwks_file.clone();
+            tokio::task::spawn_blocking(move || std::fs::read_to_string(jwks_file))
+                .await
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)?
+                .into_report()
+                .change_context(errors::ApiErrorResponse::InternalServerError)
+                .attach_printable("Failed to read JWKS file")?
+        }
+        (None, None) => {
+            return Err(report!(errors::ApiErrorResponse::InvalidJwtToken))
+                .attach_printable("No JWKS is configured to verify asymmetrically signed tokens")
+        }
+    };
+
+    serde_json::from_str(&jwks)
+        .into_report()
+        .change_context(errors::ApiErrorResponse::InternalServerError)
+        .attach_printable("Failed to parse JWKS")
+}
+
+/// Finds the key with the key ID, or the only key usable with the algorithm for tokens without a
+/// key ID.
+fn find_key(
+    jwks: &JwkSet,
+    algorithm: Algorithm,
+    key_id: Option<&str>,
+) -> RouterResult<Option<DecodingKey>> {
+    let is_usable = |jwk: &&Jwk| {
+        jwk.common
+            .algorithm
+            .map_or(true, |key_algorithm| key_algorithm == algorithm)
+    };
+
+    let jwk = match key_id {
+        Some(key_id) => jwks.find(key_id).filter(is_usable),
+        None => {
+            let mut usable_keys = jwks.keys.iter().filter(is_usable);
+            match (usable_keys.next(), usable_keys.next()) {
+                (Some(jwk), None) => Some(jwk),
+                _ => None,
+            }
+        }
+    };
+
+    jwk.map(|jwk| {
+        DecodingKey::from_jwk(jwk)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InvalidJwtToken)
+            .attach_printable("Failed to construct decoding key from JWK")
+    })
+    .transpose()
+}
+
+fn key_not_found() -> error_stack::Report<errors::ApiErrorResponse> {
+    report!(errors::ApiErrorResponse::InvalidJwtToken)
+        .attach_printable("No key in the JWKS matches the key ID and algorithm of the token")
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use super::*;
+
+    const JWKS: &str = r#"{
+        "keys": [
+            {
+                "kty": "RSA",
+                "kid": "key_1",
+                "alg": "RS256",
+                "n": "u1SU1LfVLPHCozMxH2Mo4lgOEePzNm0tRgeLezV6ffAt0gunVTLw7onLRnrq0_IzW7yWR7QkrmBL7jTKEn5u-qKhbwKfBstIs-bMY2Zkp18gnTxKLxoS2tFczGkPLPgizskuemMghRniWaoLcyehkd3qqGElvW_VDL5AaWTg0nLVkjRo9z-40RQzuVaE8AkAFmxZzow3x-VJYKdjykkJ0iT9wCS0DRTXu269V264Vf_3jvredZiKRkgwlL9xNAwxXFg0x_XFw005UWVRIkdgcKWTjpBP2dPwVZ4WWC-9aGVd-Gyn1o0CLelf4rEjGoXbAAEgAqeGUxrcIlbjXfbcmw",
+                "e": "AQAB"
+            },
+            {
+                "kty": "RSA",
+                "kid": "key_2",
+                "alg": "RS384",
+                "n": "u1SU1LfVLPHCozMxH2Mo4lgOEePzNm0tRgeLezV6ffAt0gunVTLw7onLRnrq0_IzW7yWR7QkrmBL7jTKEn5u-qKhbwKfBstIs-bMY2Zkp18gnTxKLxoS2tFczGkPLPgizskuemMghRniWaoLcyehkd3qqGElvW_VDL5AaWTg0nLVkjRo9z-40RQzuVaE8AkAFmxZzow3x-VJYKdjykkJ0iT9wCS0DRTXu269V264Vf_3jvredZiKRkgwlL9xNAwxXFg0x_XFw005UWVRIkdgcKWTjpBP2dPwVZ4WWC-9aGVd-Gyn1o0CLelf4rEjGoXbAAEgAqeGUxrcIlbjXfbcmw",
+                "e": "AQAB"
+            }
+        ]
+    }"#;
+
+    #[test]
+    fn test_find_key() {
+        let jwks = serde_json::from_str::<JwkSet>(JWKS).unwrap();
+
+        assert!(find_key(&jwks, Algorithm::RS256, Some("key_1"))
+            .unwrap()
+            .is_some());
+        // The key ID must match a key usable with the algorithm of the token
+        assert!(find_key(&jwks, Algorithm::RS384, Some("key_1"))
+            .unwrap()
+            .is_none());
+        assert!(find_key(&jwks, Algorithm::RS256, Some("key_3"))
+            .unwrap()
+            .is_none());
+        // Tokens without a key ID are verified with the only key usable with their algorithm
+        assert!(find_key(&jwks, Algorithm::RS384, None).unwrap().is_some());
+        assert!(find_key(&jwks, Algorithm::ES256, None).unwrap().is_none());
+    }
+}
@@ -0,0 +1,93 @@
+//! The permissions granted to dashboard users by their roles in a merchant account.
+
+use api_models::{enums::Role, user::Permission};
+use error_stack::{report, ResultExt};
+
+use crate::core::errors::{self, RouterResult};
+
+const VIEWER_PERMISSIONS: &[Permission] = &[
+    Permission::PaymentRead,
+    Permission::RefundRead,
+    Permission::DisputeRead,
+    Permission::MandateRead,
+    Permission::CustomerRead,
+    Permission::FileRead,
+    Permission::MerchantAccountRead,
+    Permission::MerchantConnectorAccountRead,
+    Permission::UsersRead,
+    Permission::AnalyticsRead,
+];
+
+const OPERATOR_PERMISSIONS: &[Permission] = &[
+    Permission::PaymentRead,
+    Permission::PaymentWrite,
+    Permission::RefundRead,
+    Permission::RefundWrite,
+    Permission::DisputeRead,
+    Permission::DisputeWrite,
+    Permission::MandateRead,
+    Permission::MandateWrite,
+    Permission::CustomerRead,
+    Permission::CustomerWrite,
+    Permission::FileRead,
+    Permission::FileWrite,
+    Permission::MerchantAccountRead,
+    Permission::MerchantConnectorAccountRead,
+    Permission::UsersRead,
+    Permission::AnalyticsRead,
+];
+
+const DEVELOPER_PERMISSIONS: &[Permission] = &[
+    Permission::PaymentRead,
+    Permission::RefundRead,
+    Permission::DisputeRead,
+    Permission::MandateRead,
+    Permission::CustomerRead,
+    Permission::FileRead,
+    Permission::ApiKeyRead,
+    Permission::ApiKeyWrite,
+    Permission::MerchantAccountRead,
+    Permission::MerchantAccountWrite,
+    Permission::MerchantConnectorAccountRead,
+    Permission::MerchantConnectorAccountWrite,
+    Permission::UsersRead,
+    Permission::AnalyticsRead,
+];
+
+/// Returns the permissions granted by a role. Admins are granted every permission.
+pub fn get_permissions(role: Role) -> Vec<Permission> {
+    match role {
+        Role::Admin => <Permission as strum::IntoEnumIterator>::iter().collect(),
+        Role::Developer => DEVELOPER_PERMISSIONS.to_vec(),
+        Role::Operator => OPERATOR_PERMISSIONS.to_vec(),
+        Role::Viewer => VIEWER_PERMISSIONS.to_vec(),
+    }
+}
+
+pub fn check_authorization(role: Role, required_permission: Permission) -> RouterResult<()> {
+    if role == Role::Admin || get_permissions(role).contains(&required_permission) {
+        return Ok(());
+    }
+
+    Err(report!(errors::ApiErrorResponse::AccessForbidden {
+        resource: required_permission.to_string(),
+    }))
+    .attach_printable_lazy(|| format!("Role {role} lacks permission {required_permission}"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_role_permissions() {
+        assert!(check_authorization(Role::Admin, Permission::UsersWrite).is_ok());
+        assert!(check_authorization(Role::Developer, Permission::ApiKeyWrite).is_ok());
+        assert!(check_authorization(Role::Developer, Permission::RefundWrite).is_err());
+        assert!(check_authorization(Role::Operator, Permission::RefundWrite).is_ok());
+        assert!(check_authorization(Role::Operator, Permission::ApiKeyRead).is_err());
+        assert!(check_authorization(Role::Viewer, Permission::PaymentRead).is_ok());
+        assert!(check_authorization(Role::Viewer, Permission::PaymentWrite).is_err());
+        assert!(check_authorization(Role::Viewer, Permission::UsersWrite).is_err());
+    }
+}
@@ -0,0 +1,192 @@
+//! Rendering the transactional emails sent to the customers of merchants from the templates of
+//! each event and locale, branded with the email configuration of the merchant.
+
+use api_models::admin::{MerchantEmailConfig, TransactionalEmailEvent};
+use error_stack::{IntoReport, ResultExt};
+use external_services::email::EmailMessage;
+use once_cell::sync::Lazy;
+use serde::Serialize;
+use tera::{Context, Tera};
+
+use crate::{
+    core::errors::{self, CustomResult},
+    utils,
+};
+
+/// The locale that templates are rendered in when no template exists for the locale of the
+/// merchant.
+const DEFAULT_LOCALE: &str = "en";
+
+/// The locales that templates exist for.
+const SUPPORTED_LOCALES: [&str; 2] = ["en", "fr"];
+
+const DEFAULT_PRIMARY_COLOR: &str = "#006DF9";
+const DEFAULT_BACKGROUND_COLOR: &str = "#F5F5F5";
+
+macro_rules! email_template {
+    ($name:literal) => {
+        ($name, include_str!(concat!("email/templates/", $name)))
+    };
+}
+
+/// The templates are named `<locale>/<event>.<part>`, where the subject and the text body are
+/// plain text templates and the HTML body extends the shared `layout.html`. Tera escapes the
+/// values rendered into templates with an `.html` extension.
+const TEMPLATES: [(&str, &str); 31] = [
+    email_template!("layout.html"),
+    email_template!("en/payment_succeeded.subject.txt"),
+    email_template!("en/payment_succeeded.html"),
+    email_template!("en/payment_succeeded.txt"),
+    email_template!("en/refund_processed.subject.txt"),
+    email_template!("en/refund_processed.html"),
+    email_template!("en/refund_processed.txt"),
+    email_template!("en/payment_link_created.subject.txt"),
+    email_template!("en/payment_link_created.html"),
+    email_template!("en/payment_link_created.txt"),
+    email_template!("en/invoice_issued.subject.txt"),
+    email_template!("en/invoice_issued.html"),
+    email_template!("en/invoice_issued.txt"),
+    email_template!("en/invoice_reminder.subject.txt"),
+    email_template!("en/invoice_reminder.html"),
+    email_template!("en/invoice_reminder.txt"),
+    email_template!("fr/payment_succeeded.subject.txt"),
+    email_template!("fr/payment_succeeded.html"),
+    email_template!("fr/payment_succeeded.txt"),
+    email_template!("fr/refund_processed.subject.txt"),
+    email_template!("fr/refund_processed.html"),
+    email_template!("fr/refund_processed.txt"),
+    email_template!("fr/payment_link_created.subject.txt"),
+    email_template!("fr/payment_link_created.html"),
+    email_template!("fr/payment_link_created.txt"),
+    email_template!("fr/invoice_issued.subject.txt"),
+    email_template!("fr/invoice_issued.html"),
+    email_template!("fr/invoice_issued.txt"),
+    email_template!("fr/invoice_reminder.subject.txt"),
+    email_template!("fr/invoice_reminder.html"),
+    email_template!("fr/invoice_reminder.txt"),
+];
+
+static EMAIL_TEMPLATES: Lazy<Result<Tera, tera::Error>> = Lazy::new(|| {
+    let mut tera = Tera::default();
+    tera.add_raw_templates(TEMPLATES)?;
+    Ok(tera)
+});
+
+/// The branding of the emails of a merchant, with defaults applied for the values that the
+/// merchant has not configured.
+#[derive(Debug, Serialize)]
+struct EmailBranding {
+    logo_url: Option<String>,
+    primary_color: String,
+    background_color: String,
+}
+
+impl From<&MerchantEmailConfig> for EmailBranding {
+    fn from(email_config: &MerchantEmailConfig) -> Self {
+        // Colours are rendered into style attributes, which are not escaped by the templates
+        let color_or_default = |color: &Option<String>, default: &str| {
+            color
+                .as_deref()
+                .filter(|color| utils::is_valid_hex_color(color))
+                .unwrap_or(default)
+                .to_owned()
+        };
+
+        Self {
+            logo_url: email_config.logo_url.clone(),
+            primary_color: color_or_default(&email_config.primary_color, DEFAULT_PRIMARY_COLOR),
+            background_color: color_or_default(
+                &email_config.background_color,
+                DEFAULT_BACKGROUND_COLOR,
+            ),
+        }
+    }
+}
+
+/// Renders the email of the event in the locale of the merchant. The context of the event is
+/// extended with the name and branding of the merchant.
+pub fn render_transactional_email(
+    event: TransactionalEmailEvent,
+    merchant_name: &str,
+    email_config: &MerchantEmailConfig,
+    mut context: Context,
+) -> CustomResult<EmailMessage, errors::ApiErrorResponse> {
+    let tera = EMAIL_TEMPLATES
+        .as_ref()
+        .map_err(|error| {
+            crate::logger::error!(?error, "Invalid email templates");
+            errors::ApiErrorResponse::InternalServerError
+        })
+        .into_report()?;
+
+    let locale = utils::resolve_locale(
+        email_config.locale.as_deref(),
+        &SUPPORTED_LOCALES,
+        DEFAULT_LOCALE,
+    );
+    context.insert("merchant_name", merchant_name);
+    context.insert("branding", &EmailBranding::from(email_config));
+
+    let render = |part: &str| {
+        tera.render(&format!("{locale}/{event}.{part}"), &context)
+            .into_report()
+            .change_context(errors::ApiErrorResponse::InternalServerError)
+            .attach_printable_lazy(|| format!("Failed to render {part} of {event} email"))
+    };
+
+    Ok(EmailMessage {
+        subject: render("subject.txt")?.trim().to_owned(),
+        text_body: render("txt")?,
+        html_body: Some(render("html")?),
+        sender_name: Some(
+            email_config
+                .sender_name
+                .clone()
+                .unwrap_or_else(|| merchant_name.to_owned()),
+        ),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+
+    use super::*;
+
+    #[test]
+    fn test_render_transactional_email() {
+        let email_config = MerchantEmailConfig {
+            logo_url: Some("https://example.com/logo.png".to_string()),
+            primary_color: Some("red; background: url(x)".to_string()),
+            locale: Some("fr-FR".to_string()),
+            ..Default::default()
+        };
+        let mut context = Context::new();
+        context.insert(
+            "payment",
+            &serde_json::json!({
+                "payment_id": "pay_1",
+                "amount": "10.00",
+                "currency": "EUR",
+                "date": "2023-11-06",
+                "description": "<script>alert(1)</script>",
+            }),
+        );
+
+        let message = render_transactional_email(
+            TransactionalEmailEvent::PaymentSucceeded,
+            "Ekart & Co",
+            &email_config,
+            context,
+        )
+        .unwrap();
+        let html_body = message.html_body.unwrap();
+
+        assert_eq!(message.subject, "Votre paiement à Ekart & Co a été accepté");
+        assert_eq!(message.sender_name.as_deref(), Some("Ekart & Co"));
+        assert!(message.text_body.contains("Montant payé : 10.00 EUR"));
+        assert!(html_body.contains("Ekart &amp; Co"));
+        assert!(html_body.contains(DEFAULT_PRIMARY_COLOR));
+        assert!(!html_body.contains("<script>"));
+    }
+}
@@ -69,6 +69,10 @@ impl MandateResponseExt for MandateResponse {
             status: mandate.mandate_status,
             payment_method: payment_method.payment_method.to_string(),
             payment_method_id: mandate.payment_method_id,
+            frequency_limit: mandate
+                .frequency_limit
+                .and_then(|frequency_limit| u16::try_from(frequency_limit).ok()),
+            frequency_interval: mandate.frequency_interval,
         })
     }
 }
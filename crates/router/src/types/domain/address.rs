@@ -5,7 +5,6 @@ use common_utils::{
 };
 use diesel_models::{address::AddressUpdateInternal, encryption::Encryption, enums};
 use error_stack::ResultExt;
-use masking::{PeekInterface, Secret};
 use time::{OffsetDateTime, PrimitiveDateTime};
 
 use super::{
@@ -72,10 +71,10 @@ impl behaviour::Conversion for Address {
 
     async fn convert_back(
         other: Self::DstType,
-        key: &Secret<Vec<u8>>,
+        key: &types::KeyRing,
     ) -> CustomResult<Self, ValidationError> {
         async {
-            let inner_decrypt = |inner| types::decrypt(inner, key.peek());
+            let inner_decrypt = |inner| types::decrypt(inner, key);
             Ok(Self {
                 id: other.id,
                 address_id: other.address_id,
@@ -1,7 +1,6 @@
 use common_utils::{crypto, date_time, pii};
 use diesel_models::{customers::CustomerUpdateInternal, encryption::Encryption};
 use error_stack::ResultExt;
-use masking::{PeekInterface, Secret};
 use time::PrimitiveDateTime;
 
 use super::types::{self, AsyncLift};
@@ -50,14 +49,14 @@ impl super::behaviour::Conversion for Customer {
 
     async fn convert_back(
         item: Self::DstType,
-        key: &Secret<Vec<u8>>,
+        key: &types::KeyRing,
     ) -> CustomResult<Self, ValidationError>
     where
         Self: Sized,
     {
         async {
-            let inner_decrypt = |inner| types::decrypt(inner, key.peek());
-            let inner_decrypt_email = |inner| types::decrypt(inner, key.peek());
+            let inner_decrypt = |inner| types::decrypt(inner, key);
+            let inner_decrypt_email = |inner| types::decrypt(inner, key);
             Ok(Self {
                 id: Some(item.id),
                 customer_id: item.customer_id,
@@ -9,7 +9,6 @@ use diesel_models::{
     merchant_account::MerchantAccountUpdateInternal,
 };
 use error_stack::ResultExt;
-use masking::{PeekInterface, Secret};
 use router_env::logger;
 
 use crate::{
@@ -46,6 +45,7 @@ pub struct MerchantAccount {
     pub default_profile: Option<String>,
     pub recon_status: diesel_models::enums::ReconStatus,
     pub payment_link_config: Option<serde_json::Value>,
+    pub email_config: Option<serde_json::Value>,
 }
 
 #[allow(clippy::large_enum_variant)]
@@ -71,6 +71,7 @@ pub enum MerchantAccountUpdate {
         payout_routing_algorithm: Option<serde_json::Value>,
         default_profile: Option<Option<String>>,
         payment_link_config: Option<serde_json::Value>,
+        email_config: Option<serde_json::Value>,
     },
     StorageSchemeUpdate {
         storage_scheme: MerchantStorageScheme,
@@ -104,6 +105,7 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 payout_routing_algorithm,
                 default_profile,
                 payment_link_config,
+                email_config,
             } => Self {
                 merchant_name: merchant_name.map(Encryption::from),
                 merchant_details: merchant_details.map(Encryption::from),
@@ -125,6 +127,7 @@ impl From<MerchantAccountUpdate> for MerchantAccountUpdateInternal {
                 payout_routing_algorithm,
                 default_profile,
                 payment_link_config,
+                email_config,
                 ..Default::default()
             },
             MerchantAccountUpdate::StorageSchemeUpdate { storage_scheme } => Self {
@@ -179,12 +182,13 @@ impl super::behaviour::Conversion for MerchantAccount {
             default_profile: self.default_profile,
             recon_status: self.recon_status,
             payment_link_config: self.payment_link_config,
+            email_config: self.email_config,
         })
     }
 
     async fn convert_back(
         item: Self::DstType,
-        key: &Secret<Vec<u8>>,
+        key: &types::KeyRing,
     ) -> CustomResult<Self, ValidationError>
     where
         Self: Sized,
@@ -199,11 +203,11 @@ impl super::behaviour::Conversion for MerchantAccount {
                 redirect_to_merchant_with_http_post: item.redirect_to_merchant_with_http_post,
                 merchant_name: item
                     .merchant_name
-                    .async_lift(|inner| types::decrypt(inner, key.peek()))
+                    .async_lift(|inner| types::decrypt(inner, key))
                     .await?,
                 merchant_details: item
                     .merchant_details
-                    .async_lift(|inner| types::decrypt(inner, key.peek()))
+                    .async_lift(|inner| types::decrypt(inner, key))
                     .await?,
                 webhook_details: item.webhook_details,
                 sub_merchants_enabled: item.sub_merchants_enabled,
@@ -224,6 +228,7 @@ impl super::behaviour::Conversion for MerchantAccount {
                 default_profile: item.default_profile,
                 recon_status: item.recon_status,
                 payment_link_config: item.payment_link_config,
+                email_config: item.email_config,
             })
         }
         .await
@@ -260,6 +265,7 @@ impl super::behaviour::Conversion for MerchantAccount {
             default_profile: self.default_profile,
             recon_status: self.recon_status,
             payment_link_config: self.payment_link_config,
+            email_config: self.email_config,
         })
     }
 }
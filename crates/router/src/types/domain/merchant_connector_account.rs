@@ -9,9 +9,12 @@ use diesel_models::{
     merchant_connector_account::MerchantConnectorAccountUpdateInternal,
 };
 use error_stack::ResultExt;
-use masking::{PeekInterface, Secret};
+use masking::Secret;
 
-use super::{behaviour, types::TypeEncryption};
+use super::{
+    behaviour,
+    types::{KeyRing, TypeEncryption},
+};
 #[derive(Clone, Debug)]
 pub struct MerchantConnectorAccount {
     pub id: Option<i32>,
@@ -95,7 +98,7 @@ impl behaviour::Conversion for MerchantConnectorAccount {
 
     async fn convert_back(
         other: Self::DstType,
-        key: &Secret<Vec<u8>>,
+        key: &KeyRing,
     ) -> CustomResult<Self, ValidationError> {
         Ok(Self {
             id: Some(other.id),
@@ -103,7 +106,7 @@ impl behaviour::Conversion for MerchantConnectorAccount {
             connector_name: other.connector_name,
             connector_account_details: Encryptable::decrypt(
                 other.connector_account_details,
-                key.peek(),
+                key,
                 GcmAes256,
             )
             .await
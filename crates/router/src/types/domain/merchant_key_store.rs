@@ -1,14 +1,15 @@
 use common_utils::{
     crypto::{Encryptable, GcmAes256},
     custom_serde, date_time,
+    ext_traits::ValueExt,
 };
-use error_stack::ResultExt;
-use masking::{PeekInterface, Secret};
+use error_stack::{IntoReport, ResultExt};
+use masking::Secret;
 use time::PrimitiveDateTime;
 
 use crate::{
     errors::{CustomResult, ValidationError},
-    types::domain::types::TypeEncryption,
+    types::domain::types::{KeyRing, KeyVersion, TypeEncryption},
 };
 
 #[derive(Clone, Debug, serde::Serialize)]
@@ -17,6 +18,54 @@ pub struct MerchantKeyStore {
     pub key: Encryptable<Secret<Vec<u8>>>,
     #[serde(with = "custom_serde::iso8601")]
     pub created_at: PrimitiveDateTime,
+    pub key_version: KeyVersion,
+    pub retired_keys: Vec<RetiredKey>,
+}
+
+/// A key of the merchant which data is no longer encrypted with, but which data encrypted before
+/// the key was rotated may still be encrypted with.
+#[derive(Clone, Debug, serde::Serialize)]
+pub struct RetiredKey {
+    pub key_version: KeyVersion,
+    pub key: Encryptable<Secret<Vec<u8>>>,
+}
+
+impl MerchantKeyStore {
+    /// Returns the key ring that the data of the merchant is encrypted and decrypted with.
+    pub fn key_ring(&self) -> KeyRing {
+        self.retired_keys.iter().fold(
+            KeyRing::with_active_key(self.key_version, self.key.get_inner().clone()),
+            |key_ring, retired_key| {
+                key_ring
+                    .with_retired_key(retired_key.key_version, retired_key.key.get_inner().clone())
+            },
+        )
+    }
+}
+
+fn encode_retired_keys(
+    retired_keys: Vec<RetiredKey>,
+) -> CustomResult<Option<serde_json::Value>, ValidationError> {
+    if retired_keys.is_empty() {
+        return Ok(None);
+    }
+
+    let retired_keys = retired_keys
+        .into_iter()
+        .map(
+            |retired_key| diesel_models::merchant_key_store::RetiredKey {
+                key_version: retired_key.key_version,
+                key: retired_key.key.into(),
+            },
+        )
+        .collect::<Vec<_>>();
+
+    serde_json::to_value(retired_keys)
+        .into_report()
+        .change_context(ValidationError::InvalidValue {
+            message: "Failed while encoding retired merchant keys".to_string(),
+        })
+        .map(Some)
 }
 
 #[async_trait::async_trait]
@@ -28,24 +77,51 @@ impl super::behaviour::Conversion for MerchantKeyStore {
             key: self.key.into(),
             merchant_id: self.merchant_id,
             created_at: self.created_at,
+            key_version: self.key_version,
+            retired_keys: encode_retired_keys(self.retired_keys)?,
         })
     }
 
-    async fn convert_back(
-        item: Self::DstType,
-        key: &Secret<Vec<u8>>,
-    ) -> CustomResult<Self, ValidationError>
+    async fn convert_back(item: Self::DstType, key: &KeyRing) -> CustomResult<Self, ValidationError>
     where
         Self: Sized,
     {
+        let decryption_error = || ValidationError::InvalidValue {
+            message: "Failed while decrypting merchant key store".to_string(),
+        };
+
+        let retired_keys = item
+            .retired_keys
+            .map(|retired_keys| {
+                retired_keys
+                    .parse_value::<Vec<diesel_models::merchant_key_store::RetiredKey>>("RetiredKey")
+            })
+            .transpose()
+            .change_context(ValidationError::InvalidValue {
+                message: "Failed while parsing retired merchant keys".to_string(),
+            })?
+            .unwrap_or_default();
+
+        let retired_keys =
+            futures::future::try_join_all(retired_keys.into_iter().map(|retired_key| async move {
+                Encryptable::decrypt(retired_key.key, key, GcmAes256)
+                    .await
+                    .map(|decrypted_key| RetiredKey {
+                        key_version: retired_key.key_version,
+                        key: decrypted_key,
+                    })
+            }))
+            .await
+            .change_context_lazy(decryption_error)?;
+
         Ok(Self {
-            key: Encryptable::decrypt(item.key, key.peek(), GcmAes256)
+            key: Encryptable::decrypt(item.key, key, GcmAes256)
                 .await
-                .change_context(ValidationError::InvalidValue {
-                    message: "Failed while decrypting customer data".to_string(),
-                })?,
+                .change_context_lazy(decryption_error)?,
             merchant_id: item.merchant_id,
             created_at: item.created_at,
+            key_version: item.key_version,
+            retired_keys,
         })
     }
 
@@ -54,6 +130,8 @@ impl super::behaviour::Conversion for MerchantKeyStore {
             merchant_id: self.merchant_id,
             key: self.key.into(),
             created_at: date_time::now(),
+            key_version: self.key_version,
+            retired_keys: encode_retired_keys(self.retired_keys)?,
         })
     }
 }
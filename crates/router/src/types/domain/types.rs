@@ -1,8 +1,11 @@
+use std::collections::BTreeMap;
+
 use async_trait::async_trait;
 use common_utils::{
     crypto,
     errors::{self, CustomResult},
     ext_traits::AsyncExt,
+    pii::EncryptionStratergy,
 };
 use diesel_models::encryption::Encryption;
 use error_stack::{IntoReport, ResultExt};
@@ -11,6 +14,160 @@ use router_env::{instrument, tracing};
 
 use crate::routes::metrics::{request, DECRYPTION_TIME, ENCRYPTION_TIME};
 
+/// The version of a data encryption key.
+pub type KeyVersion = i32;
+
+/// The version of the key a merchant is created with. Data encrypted with this version is stored
+/// untagged, as it was before keys were versioned.
+pub const INITIAL_KEY_VERSION: KeyVersion = 1;
+
+/// Prefix of encrypted data tagged with the version of the key it was encrypted with, followed by
+/// the big-endian key version and the ciphertext.
+const KEY_VERSION_TAG: &[u8] = b"hsk\x00";
+
+/// The versioned keys that data is encrypted with. Data is encrypted with the active key and
+/// decrypted with the key of the version it is tagged with, so that data encrypted before a key
+/// rotation can still be decrypted.
+#[derive(Clone, Debug)]
+pub struct KeyRing {
+    active_version: KeyVersion,
+    keys: BTreeMap<KeyVersion, Secret<Vec<u8>>>,
+}
+
+impl KeyRing {
+    /// Constructs a key ring holding a single unversioned key.
+    pub fn new(key: Secret<Vec<u8>>) -> Self {
+        Self::with_active_key(INITIAL_KEY_VERSION, key)
+    }
+
+    /// Constructs a key ring whose active key is of the provided version.
+    pub fn with_active_key(version: KeyVersion, key: Secret<Vec<u8>>) -> Self {
+        Self {
+            active_version: version,
+            keys: BTreeMap::from([(version, key)]),
+        }
+    }
+
+    /// Adds a key which is not used for encryption, but that data may be encrypted with.
+    pub fn with_retired_key(mut self, version: KeyVersion, key: Secret<Vec<u8>>) -> Self {
+        self.keys.entry(version).or_insert(key);
+        self
+    }
+
+    pub fn active_version(&self) -> KeyVersion {
+        self.active_version
+    }
+
+    /// Makes the key of the provided version the active key, if the key ring holds it.
+    pub fn with_active_version(mut self, version: KeyVersion) -> Option<Self> {
+        self.keys.contains_key(&version).then(|| {
+            self.active_version = version;
+            self
+        })
+    }
+
+    fn active_key(&self) -> &[u8] {
+        self.keys
+            .get(&self.active_version)
+            .map(|key| key.peek().as_slice())
+            .unwrap_or_default()
+    }
+
+    fn get_key(&self, version: KeyVersion) -> Option<&[u8]> {
+        self.keys.get(&version).map(|key| key.peek().as_slice())
+    }
+
+    fn encode<V: crypto::EncodeMessage>(
+        &self,
+        crypt_algo: &V,
+        data: &[u8],
+    ) -> CustomResult<Secret<Vec<u8>, EncryptionStratergy>, errors::CryptoError> {
+        let encrypted = crypt_algo.encode_message(self.active_key(), data)?;
+        if self.active_version == INITIAL_KEY_VERSION {
+            return Ok(encrypted.into());
+        }
+
+        let mut tagged = Vec::with_capacity(KEY_VERSION_TAG.len() + 4 + encrypted.len());
+        tagged.extend_from_slice(KEY_VERSION_TAG);
+        tagged.extend_from_slice(&self.active_version.to_be_bytes());
+        tagged.extend(encrypted);
+        Ok(tagged.into())
+    }
+
+    fn decode<V: crypto::DecodeMessage>(
+        &self,
+        crypt_algo: &V,
+        encrypted: Secret<Vec<u8>, EncryptionStratergy>,
+    ) -> CustomResult<Vec<u8>, errors::CryptoError> {
+        let initial_key = self.get_key(INITIAL_KEY_VERSION);
+
+        if let Some((version, ciphertext)) = get_key_version(encrypted.peek()) {
+            let decoded = self
+                .get_key(version)
+                .ok_or(errors::CryptoError::DecodingFailed)
+                .into_report()
+                .attach_printable_lazy(|| format!("Key of version {version} not found"))
+                .and_then(|key| crypt_algo.decode_message(key, ciphertext.to_vec().into()));
+
+            // Untagged data could begin with the tag by chance, in which case it is decrypted with
+            // the initial key below
+            match decoded {
+                Ok(data) => return Ok(data),
+                Err(error) if initial_key.is_none() => return Err(error),
+                Err(_) => {}
+            }
+        }
+
+        let key = initial_key
+            .ok_or(errors::CryptoError::DecodingFailed)
+            .into_report()
+            .attach_printable("Key of the initial version not found")?;
+        crypt_algo.decode_message(key, encrypted)
+    }
+}
+
+/// Returns the key version that the encrypted data is tagged with, along with the ciphertext.
+fn get_key_version(encrypted: &[u8]) -> Option<(KeyVersion, &[u8])> {
+    let tagged = encrypted.strip_prefix(KEY_VERSION_TAG)?;
+    let version = tagged.get(..4)?.try_into().ok()?;
+    Some((KeyVersion::from_be_bytes(version), tagged.get(4..)?))
+}
+
+/// Returns the version of the key that the encrypted data was encrypted with.
+pub fn get_encryption_key_version(encrypted: &Encryption) -> KeyVersion {
+    get_key_version(encrypted.get_inner().peek())
+        .map(|(version, _)| version)
+        .unwrap_or(INITIAL_KEY_VERSION)
+}
+
+/// Re-encrypts the encrypted data with the active key of the key ring, if it was encrypted with a
+/// different key.
+pub fn rewrap(
+    encrypted: Encryption,
+    key_ring: &KeyRing,
+) -> CustomResult<Encryption, errors::CryptoError> {
+    if get_encryption_key_version(&encrypted) == key_ring.active_version() {
+        return Ok(encrypted);
+    }
+
+    let data = key_ring.decode(&crypto::GcmAes256, encrypted.into_inner())?;
+    key_ring
+        .encode(&crypto::GcmAes256, &data)
+        .map(Encryption::new)
+}
+
+/// Whether the encrypted data decrypts to the same data as the other encrypted data, with the
+/// key ring.
+pub fn is_same_data(
+    encrypted: &Encryption,
+    other: &Encryption,
+    key_ring: &KeyRing,
+) -> CustomResult<bool, errors::CryptoError> {
+    let data = key_ring.decode(&crypto::GcmAes256, encrypted.get_inner().clone())?;
+    let other_data = key_ring.decode(&crypto::GcmAes256, other.get_inner().clone())?;
+    Ok(data == other_data)
+}
+
 #[async_trait]
 pub trait TypeEncryption<
     T,
@@ -20,13 +177,13 @@ pub trait TypeEncryption<
 {
     async fn encrypt(
         masked_data: Secret<T, S>,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError>;
 
     async fn decrypt(
         encrypted_data: Encryption,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError>;
 }
@@ -40,22 +197,22 @@ impl<
     #[instrument(skip_all)]
     async fn encrypt(
         masked_data: Secret<String, S>,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError> {
-        let encrypted_data = crypt_algo.encode_message(key, masked_data.peek().as_bytes())?;
+        let encrypted_data = key.encode(&crypt_algo, masked_data.peek().as_bytes())?;
 
-        Ok(Self::new(masked_data, encrypted_data.into()))
+        Ok(Self::new(masked_data, encrypted_data))
     }
 
     #[instrument(skip_all)]
     async fn decrypt(
         encrypted_data: Encryption,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError> {
         let encrypted = encrypted_data.into_inner();
-        let data = crypt_algo.decode_message(key, encrypted.clone())?;
+        let data = key.decode(&crypt_algo, encrypted.clone())?;
 
         let value: String = std::str::from_utf8(&data)
             .into_report()
@@ -76,25 +233,25 @@ impl<
     #[instrument(skip_all)]
     async fn encrypt(
         masked_data: Secret<serde_json::Value, S>,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError> {
         let data = serde_json::to_vec(&masked_data.peek())
             .into_report()
             .change_context(errors::CryptoError::DecodingFailed)?;
-        let encrypted_data = crypt_algo.encode_message(key, &data)?;
+        let encrypted_data = key.encode(&crypt_algo, &data)?;
 
-        Ok(Self::new(masked_data, encrypted_data.into()))
+        Ok(Self::new(masked_data, encrypted_data))
     }
 
     #[instrument(skip_all)]
     async fn decrypt(
         encrypted_data: Encryption,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError> {
         let encrypted = encrypted_data.into_inner();
-        let data = crypt_algo.decode_message(key, encrypted.clone())?;
+        let data = key.decode(&crypt_algo, encrypted.clone())?;
 
         let value: serde_json::Value = serde_json::from_slice(&data)
             .into_report()
@@ -113,22 +270,22 @@ impl<
     #[instrument(skip_all)]
     async fn encrypt(
         masked_data: Secret<Vec<u8>, S>,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError> {
-        let encrypted_data = crypt_algo.encode_message(key, masked_data.peek())?;
+        let encrypted_data = key.encode(&crypt_algo, masked_data.peek())?;
 
-        Ok(Self::new(masked_data, encrypted_data.into()))
+        Ok(Self::new(masked_data, encrypted_data))
     }
 
     #[instrument(skip_all)]
     async fn decrypt(
         encrypted_data: Encryption,
-        key: &[u8],
+        key: &KeyRing,
         crypt_algo: V,
     ) -> CustomResult<Self, errors::CryptoError> {
         let encrypted = encrypted_data.into_inner();
-        let data = crypt_algo.decode_message(key, encrypted.clone())?;
+        let data = key.decode(&crypt_algo, encrypted.clone())?;
 
         Ok(Self::new(data.into(), encrypted))
     }
@@ -183,7 +340,7 @@ impl<U, V: Lift<U> + Lift<U, SelfWrapper<U> = V> + Send> AsyncLift<U> for V {
 #[inline]
 pub async fn encrypt<E: Clone, S>(
     inner: Secret<E, S>,
-    key: &[u8],
+    key: &KeyRing,
 ) -> CustomResult<crypto::Encryptable<Secret<E, S>>, errors::CryptoError>
 where
     S: masking::Strategy<E>,
@@ -200,7 +357,7 @@ where
 #[inline]
 pub async fn encrypt_optional<E: Clone, S>(
     inner: Option<Secret<E, S>>,
-    key: &[u8],
+    key: &KeyRing,
 ) -> CustomResult<Option<crypto::Encryptable<Secret<E, S>>>, errors::CryptoError>
 where
     Secret<E, S>: Send,
@@ -213,7 +370,7 @@ where
 #[inline]
 pub async fn decrypt<T: Clone, S: masking::Strategy<T>>(
     inner: Option<Encryption>,
-    key: &[u8],
+    key: &KeyRing,
 ) -> CustomResult<Option<crypto::Encryptable<Secret<T, S>>>, errors::CryptoError>
 where
     crypto::Encryptable<Secret<T, S>>: TypeEncryption<T, crypto::GcmAes256, S>,
@@ -226,3 +383,48 @@ where
     .await
     .transpose()
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::expect_used)]
+    use super::*;
+
+    fn key(byte: u8) -> Secret<Vec<u8>> {
+        vec![byte; 32].into()
+    }
+
+    #[test]
+    fn test_decrypting_data_encrypted_before_rotation() {
+        let initial_key_ring = KeyRing::new(key(1));
+        let encrypted = Encryption::new(
+            initial_key_ring
+                .encode(&crypto::GcmAes256, b"4242")
+                .expect("Failed to encrypt"),
+        );
+        assert_eq!(get_encryption_key_version(&encrypted), INITIAL_KEY_VERSION);
+
+        let rotated_key_ring =
+            KeyRing::with_active_key(2, key(2)).with_retired_key(INITIAL_KEY_VERSION, key(1));
+        let rewrapped = rewrap(encrypted, &rotated_key_ring).expect("Failed to rewrap");
+        assert_eq!(get_encryption_key_version(&rewrapped), 2);
+
+        let decrypted = rotated_key_ring
+            .decode(&crypto::GcmAes256, rewrapped.into_inner())
+            .expect("Failed to decrypt");
+        assert_eq!(decrypted, b"4242");
+    }
+
+    #[test]
+    fn test_decrypting_data_encrypted_with_unknown_key_version() {
+        let encrypted = Encryption::new(
+            KeyRing::with_active_key(3, key(3))
+                .encode(&crypto::GcmAes256, b"4242")
+                .expect("Failed to encrypt"),
+        );
+
+        let key_ring = KeyRing::with_active_key(2, key(2));
+        assert!(key_ring
+            .decode(&crypto::GcmAes256, encrypted.into_inner())
+            .is_err());
+    }
+}
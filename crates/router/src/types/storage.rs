@@ -1,5 +1,7 @@
 pub mod address;
+pub mod analytics;
 pub mod api_keys;
+pub mod audit_event;
 pub mod business_profile;
 pub mod capture;
 pub mod cards_info;
@@ -7,14 +9,17 @@ pub mod configs;
 pub mod connector_response;
 pub mod customers;
 pub mod dispute;
+pub mod encrypted_data;
 pub mod enums;
 pub mod ephemeral_key;
 pub mod events;
 pub mod file;
+pub mod invoice;
 #[cfg(feature = "kv_store")]
 pub mod kv;
 pub mod locker_mock_up;
 pub mod mandate;
+pub mod mandate_usage;
 pub mod merchant_account;
 pub mod merchant_connector_account;
 pub mod merchant_key_store;
@@ -29,6 +34,11 @@ pub mod payout_attempt;
 pub mod payouts;
 mod query;
 pub mod refund;
+pub mod reusable_payment_link;
+pub mod subscription;
+pub mod subscription_plan;
+pub mod user;
+pub mod user_role;
 
 pub use data_models::payments::{
     payment_attempt::{PaymentAttempt, PaymentAttemptNew, PaymentAttemptUpdate},
@@ -37,13 +47,62 @@ pub use data_models::payments::{
 };
 
 pub use self::{
-    address::*, api_keys::*, capture::*, cards_info::*, configs::*, connector_response::*,
-    customers::*, dispute::*, ephemeral_key::*, events::*, file::*, locker_mock_up::*, mandate::*,
+    address::*, api_keys::*, audit_event::*, capture::*, cards_info::*, configs::*,
+    connector_response::*, customers::*, dispute::*, encrypted_data::*, ephemeral_key::*,
+    events::*, file::*, invoice::*, locker_mock_up::*, mandate::*, mandate_usage::*,
     merchant_account::*, merchant_connector_account::*, merchant_key_store::*, payment_link::*,
     payment_method::*, payout_attempt::*, payouts::*, process_tracker::*, refund::*,
-    reverse_lookup::*,
+    reusable_payment_link::*, reverse_lookup::*, subscription::*, subscription_plan::*, user::*,
+    user_role::*,
 };
 
+// Tracking data by process_tracker for sending a transactional email to the customer of a payment
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct TransactionalEmailWorkflow {
+    pub merchant_id: String,
+    pub event: api_models::admin::TransactionalEmailEvent,
+    pub payment_id: String,
+    pub refund_id: Option<String>,
+    pub payment_link_id: Option<String>,
+    #[serde(default)]
+    pub invoice_id: Option<String>,
+}
+
+// Tracking data by process_tracker for expiring a payment link
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct PaymentLinkExpiryWorkflow {
+    pub merchant_id: String,
+    pub payment_id: String,
+    pub payment_link_id: String,
+}
+
+// Tracking data by process_tracker for completing the migration of a merchant away from KV
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct KvMigrationWorkflow {
+    pub merchant_id: String,
+}
+
+// Tracking data by process_tracker for expiring a mandate at its end date
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct MandateExpiryWorkflow {
+    pub merchant_id: String,
+    pub mandate_id: String,
+}
+
+// Tracking data by process_tracker for reminding the customer of an unpaid invoice
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct InvoiceReminderWorkflow {
+    pub merchant_id: String,
+    pub invoice_id: String,
+}
+
+// Tracking data by process_tracker for charging a subscription at the end of each billing period
+#[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
+pub struct SubscriptionBillingWorkflow {
+    pub merchant_id: String,
+    pub subscription_id: String,
+}
+
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
 pub struct RoutingData {
     pub routed_through: Option<String>,
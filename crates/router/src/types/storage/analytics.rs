@@ -0,0 +1,269 @@
+use api_models::analytics::{Granularity, PaymentDimension, PaymentFilters};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use common_utils::errors::CustomResult;
+use diesel::{
+    pg::Pg,
+    sql_types::{Array, BigInt, Double, Nullable, Text, Timestamp},
+    QueryableByName,
+};
+use diesel_models::errors;
+use error_stack::{IntoReport, ResultExt};
+use time::PrimitiveDateTime;
+
+use crate::{connection::PgPooledConn, logger};
+
+/// The payment metrics of the payment attempts of a merchant created in a time range, independent
+/// of the backend computing them.
+#[derive(Clone, Debug)]
+pub struct PaymentMetricsQuery<'a> {
+    pub merchant_id: &'a str,
+    pub start_time: PrimitiveDateTime,
+    pub end_time: PrimitiveDateTime,
+    pub granularity: Option<Granularity>,
+    pub group_by: &'a [PaymentDimension],
+    pub filters: &'a PaymentFilters,
+}
+
+/// The payment metrics of a time bucket and a combination of the values of the grouped dimensions.
+/// Dimensions that are not grouped by are unset.
+#[derive(Debug, QueryableByName)]
+pub struct PaymentMetricsRow {
+    #[diesel(sql_type = Nullable<Text>)]
+    pub connector: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub payment_method: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub payment_method_type: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub currency: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub status: Option<String>,
+    #[diesel(sql_type = Nullable<Text>)]
+    pub authentication_type: Option<String>,
+    #[diesel(sql_type = Nullable<Timestamp>)]
+    pub time_bucket: Option<PrimitiveDateTime>,
+    #[diesel(sql_type = BigInt)]
+    pub payment_count: i64,
+    #[diesel(sql_type = BigInt)]
+    pub success_count: i64,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub avg_amount: Option<f64>,
+    #[diesel(sql_type = Nullable<BigInt>)]
+    pub processed_amount: Option<i64>,
+    #[diesel(sql_type = Nullable<Double>)]
+    pub avg_latency: Option<f64>,
+}
+
+#[derive(Debug, PartialEq)]
+enum Bind {
+    Text(String),
+    Timestamp(PrimitiveDateTime),
+    TextArray(Vec<String>),
+}
+
+/// The column of `payment_attempt` holding a dimension, as text.
+fn dimension_column(dimension: PaymentDimension) -> &'static str {
+    match dimension {
+        PaymentDimension::Connector => "connector::text",
+        PaymentDimension::PaymentMethod => "payment_method::text",
+        PaymentDimension::PaymentMethodType => "payment_method_type::text",
+        PaymentDimension::Currency => "currency::text",
+        PaymentDimension::Status => "status::text",
+        PaymentDimension::AuthenticationType => "authentication_type::text",
+    }
+}
+
+impl PaymentMetricsQuery<'_> {
+    /// Whether every row of the metrics is computed from payments in a single currency, which
+    /// metrics summing or averaging amounts require to be meaningful.
+    pub fn is_single_currency(&self) -> bool {
+        self.group_by.contains(&PaymentDimension::Currency) || self.filters.currency.len() == 1
+    }
+
+    /// Builds the Postgres query computing the metrics from `payment_attempt`. Values are only
+    /// ever passed as binds, the query text is built from constants alone.
+    fn build_postgres_query(&self) -> (String, Vec<Bind>) {
+        let mut binds = vec![
+            Bind::Text(self.merchant_id.to_owned()),
+            Bind::Timestamp(self.start_time),
+            Bind::Timestamp(self.end_time),
+        ];
+
+        let mut select = <PaymentDimension as strum::IntoEnumIterator>::iter()
+            .map(|dimension| {
+                if self.group_by.contains(&dimension) {
+                    format!("{} AS {dimension}", dimension_column(dimension))
+                } else {
+                    format!("NULL::text AS {dimension}")
+                }
+            })
+            .collect::<Vec<_>>();
+        let mut group_by = self
+            .group_by
+            .iter()
+            .map(|dimension| dimension_column(*dimension).to_owned())
+            .collect::<Vec<_>>();
+
+        match self.granularity {
+            Some(granularity) => {
+                let time_bucket = format!("date_trunc('{granularity}', created_at)");
+                select.push(format!("{time_bucket} AS time_bucket"));
+                group_by.push(time_bucket);
+            }
+            None => select.push("NULL::timestamp AS time_bucket".to_owned()),
+        }
+
+        let mut filters = vec![
+            "merchant_id = $1".to_owned(),
+            "created_at >= $2".to_owned(),
+            "created_at <= $3".to_owned(),
+        ];
+        let filter_values = [
+            (
+                PaymentDimension::Connector,
+                to_strings(&self.filters.connector),
+            ),
+            (
+                PaymentDimension::PaymentMethod,
+                to_strings(&self.filters.payment_method),
+            ),
+            (
+                PaymentDimension::PaymentMethodType,
+                to_strings(&self.filters.payment_method_type),
+            ),
+            (
+                PaymentDimension::Currency,
+                to_strings(&self.filters.currency),
+            ),
+            (PaymentDimension::Status, to_strings(&self.filters.status)),
+            (
+                PaymentDimension::AuthenticationType,
+                to_strings(&self.filters.authentication_type),
+            ),
+        ];
+        for (dimension, values) in filter_values {
+            if !values.is_empty() {
+                binds.push(Bind::TextArray(values));
+                filters.push(format!(
+                    "{} = ANY(${})",
+                    dimension_column(dimension),
+                    binds.len()
+                ));
+            }
+        }
+
+        let mut query = format!(
+            "SELECT {}, \
+             COUNT(*) AS payment_count, \
+             COUNT(*) FILTER (WHERE status = 'charged') AS success_count, \
+             AVG(amount)::double precision AS avg_amount, \
+             (SUM(amount) FILTER (WHERE status = 'charged'))::bigint AS processed_amount, \
+             AVG(EXTRACT(EPOCH FROM (modified_at - created_at)))::double precision AS avg_latency \
+             FROM payment_attempt WHERE {}",
+            select.join(", "),
+            filters.join(" AND "),
+        );
+        if !group_by.is_empty() {
+            query.push_str(&format!(" GROUP BY {}", group_by.join(", ")));
+        }
+        if self.granularity.is_some() {
+            query.push_str(" ORDER BY time_bucket");
+        }
+
+        (query, binds)
+    }
+
+    pub async fn load_from_postgres(
+        &self,
+        conn: &PgPooledConn,
+    ) -> CustomResult<Vec<PaymentMetricsRow>, errors::DatabaseError> {
+        let (query, binds) = self.build_postgres_query();
+        logger::debug!(%query, "Computing payment metrics");
+
+        let query = binds.into_iter().fold(
+            diesel::sql_query(query).into_boxed::<Pg>(),
+            |query, bind| match bind {
+                Bind::Text(value) => query.bind::<Text, _>(value),
+                Bind::Timestamp(value) => query.bind::<Timestamp, _>(value),
+                Bind::TextArray(values) => query.bind::<Array<Text>, _>(values),
+            },
+        );
+
+        query
+            .get_results_async(conn)
+            .await
+            .into_report()
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error computing payment metrics")
+    }
+}
+
+fn to_strings<T: ToString>(values: &[T]) -> Vec<String> {
+    values.iter().map(ToString::to_string).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use api_models::enums;
+
+    use super::*;
+
+    #[test]
+    fn test_build_postgres_query() {
+        let filters = PaymentFilters {
+            currency: vec![enums::Currency::USD],
+            status: vec![enums::AttemptStatus::Charged, enums::AttemptStatus::Failure],
+            ..Default::default()
+        };
+        let query = PaymentMetricsQuery {
+            merchant_id: "merchant_1",
+            start_time: common_utils::date_time::now(),
+            end_time: common_utils::date_time::now(),
+            granularity: Some(Granularity::Day),
+            group_by: &[PaymentDimension::Connector],
+            filters: &filters,
+        };
+
+        let (query, binds) = query.build_postgres_query();
+
+        assert!(query.contains("connector::text AS connector"));
+        assert!(query.contains("NULL::text AS currency"));
+        assert!(query.contains("date_trunc('day', created_at) AS time_bucket"));
+        assert!(query.contains("currency::text = ANY($4) AND status::text = ANY($5)"));
+        assert!(query.contains("GROUP BY connector::text, date_trunc('day', created_at)"));
+        assert!(!query.contains("merchant_1"));
+        assert_eq!(binds.len(), 5);
+        assert_eq!(
+            binds[4],
+            Bind::TextArray(vec!["charged".to_string(), "failure".to_string()])
+        );
+    }
+
+    #[test]
+    fn test_is_single_currency() {
+        let query = |group_by: &[PaymentDimension], currency| {
+            let filters = PaymentFilters {
+                currency,
+                ..Default::default()
+            };
+            PaymentMetricsQuery {
+                merchant_id: "merchant_1",
+                start_time: common_utils::date_time::now(),
+                end_time: common_utils::date_time::now(),
+                granularity: None,
+                group_by,
+                filters: &filters,
+            }
+            .is_single_currency()
+        };
+
+        assert!(!query(&[], vec![]));
+        assert!(!query(&[PaymentDimension::Connector], vec![]));
+        assert!(!query(
+            &[],
+            vec![enums::Currency::USD, enums::Currency::EUR]
+        ));
+        assert!(query(&[PaymentDimension::Currency], vec![]));
+        assert!(query(&[], vec![enums::Currency::USD]));
+    }
+}
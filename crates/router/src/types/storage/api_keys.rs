@@ -1,3 +1,5 @@
 #[cfg(feature = "email")]
 pub use diesel_models::api_keys::ApiKeyExpiryWorkflow;
-pub use diesel_models::api_keys::{ApiKey, ApiKeyNew, ApiKeyUpdate, HashedApiKey};
+pub use diesel_models::api_keys::{
+    ApiKey, ApiKeyNew, ApiKeyRevocationWorkflow, ApiKeyUpdate, HashedApiKey,
+};
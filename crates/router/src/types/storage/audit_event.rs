@@ -0,0 +1,58 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
+use common_utils::errors::CustomResult;
+use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+pub use diesel_models::audit_event::{AuditEvent, AuditEventNew};
+use diesel_models::{errors, query::generics::db_metrics, schema::audit_events::dsl};
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{connection::PgPooledConn, logger};
+
+#[async_trait::async_trait]
+pub trait AuditEventDbExt: Sized {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        constraints: api_models::audit_events::AuditEventListConstraints,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
+}
+
+#[async_trait::async_trait]
+impl AuditEventDbExt for AuditEvent {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        constraints: api_models::audit_events::AuditEventListConstraints,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
+        let mut filter = <Self as HasTable>::table()
+            .order(dsl::created_at.desc())
+            .into_boxed();
+
+        if let Some(merchant_id) = constraints.merchant_id {
+            filter = filter.filter(dsl::merchant_id.eq(merchant_id));
+        }
+        if let Some(resource_type) = constraints.resource_type {
+            filter = filter.filter(dsl::resource_type.eq(resource_type));
+        }
+        if let Some(resource_id) = constraints.resource_id {
+            filter = filter.filter(dsl::resource_id.eq(resource_id));
+        }
+        if let Some(created_lt) = constraints.created_lt {
+            filter = filter.filter(dsl::created_at.lt(created_lt));
+        }
+        if let Some(created_gte) = constraints.created_gte {
+            filter = filter.filter(dsl::created_at.ge(created_gte));
+        }
+        if let Some(limit) = constraints.limit {
+            filter = filter.limit(limit);
+        }
+
+        logger::debug!(query = %diesel::debug_query::<diesel::pg::Pg, _>(&filter).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            filter.get_results_async(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::NotFound)
+        .attach_printable_lazy(|| "Error filtering records by predicate")
+    }
+}
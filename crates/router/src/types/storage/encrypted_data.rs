@@ -0,0 +1,130 @@
+use api_models::admin::EncryptedEntity;
+use common_utils::errors::{CryptoError, CustomResult};
+use diesel_models::encryption::Encryption;
+
+use super::{Address, Customer, MerchantAccount, MerchantConnectorAccount, PaymentMethod};
+use crate::types::domain::types::{self as domain_types, KeyRing};
+
+/// A record holding data encrypted with the data encryption key of a merchant.
+#[allow(clippy::large_enum_variant)]
+#[derive(Clone, Debug)]
+pub enum EncryptedRecord {
+    MerchantAccount(MerchantAccount),
+    Customer(Customer),
+    Address(Address),
+    MerchantConnectorAccount(MerchantConnectorAccount),
+    PaymentMethod(PaymentMethod),
+}
+
+impl EncryptedRecord {
+    pub fn entity(&self) -> EncryptedEntity {
+        match self {
+            Self::MerchantAccount(_) => EncryptedEntity::MerchantAccount,
+            Self::Customer(_) => EncryptedEntity::Customer,
+            Self::Address(_) => EncryptedEntity::Address,
+            Self::MerchantConnectorAccount(_) => EncryptedEntity::MerchantConnectorAccount,
+            Self::PaymentMethod(_) => EncryptedEntity::PaymentMethod,
+        }
+    }
+
+    /// The identifier of the record, that the records of an entity are ordered by.
+    pub fn id(&self) -> &str {
+        match self {
+            Self::MerchantAccount(merchant_account) => &merchant_account.merchant_id,
+            Self::Customer(customer) => &customer.customer_id,
+            Self::Address(address) => &address.address_id,
+            Self::MerchantConnectorAccount(mca) => &mca.merchant_connector_id,
+            Self::PaymentMethod(payment_method) => &payment_method.payment_method_id,
+        }
+    }
+
+    /// The encrypted fields of the record, including the fields that are not set.
+    pub fn encrypted_fields(&self) -> Vec<Option<&Encryption>> {
+        match self {
+            Self::MerchantAccount(merchant_account) => vec![
+                merchant_account.merchant_name.as_ref(),
+                merchant_account.merchant_details.as_ref(),
+            ],
+            Self::Customer(customer) => vec![
+                customer.name.as_ref(),
+                customer.email.as_ref(),
+                customer.phone.as_ref(),
+            ],
+            Self::Address(address) => [
+                &address.line1,
+                &address.line2,
+                &address.line3,
+                &address.state,
+                &address.zip,
+                &address.first_name,
+                &address.last_name,
+                &address.phone_number,
+            ]
+            .into_iter()
+            .map(Option::as_ref)
+            .collect(),
+            Self::MerchantConnectorAccount(mca) => vec![Some(&mca.connector_account_details)],
+            Self::PaymentMethod(payment_method) => {
+                vec![payment_method.payment_method_data.as_ref()]
+            }
+        }
+    }
+
+    /// Whether any of the encrypted fields of the record is encrypted with a key other than the
+    /// active key of the key ring.
+    pub fn needs_rewrap(&self, key: &KeyRing) -> bool {
+        self.encrypted_fields()
+            .into_iter()
+            .flatten()
+            .any(|encrypted| {
+                domain_types::get_encryption_key_version(encrypted) != key.active_version()
+            })
+    }
+
+    /// Re-encrypts the encrypted fields of the record with the active key of the key ring.
+    pub fn rewrap(self, key: &KeyRing) -> CustomResult<Self, CryptoError> {
+        let rewrap_optional = |encrypted: Option<Encryption>| {
+            encrypted
+                .map(|encrypted| domain_types::rewrap(encrypted, key))
+                .transpose()
+        };
+
+        Ok(match self {
+            Self::MerchantAccount(merchant_account) => Self::MerchantAccount(MerchantAccount {
+                merchant_name: rewrap_optional(merchant_account.merchant_name)?,
+                merchant_details: rewrap_optional(merchant_account.merchant_details)?,
+                ..merchant_account
+            }),
+            Self::Customer(customer) => Self::Customer(Customer {
+                name: rewrap_optional(customer.name)?,
+                email: rewrap_optional(customer.email)?,
+                phone: rewrap_optional(customer.phone)?,
+                ..customer
+            }),
+            Self::Address(address) => Self::Address(Address {
+                line1: rewrap_optional(address.line1)?,
+                line2: rewrap_optional(address.line2)?,
+                line3: rewrap_optional(address.line3)?,
+                state: rewrap_optional(address.state)?,
+                zip: rewrap_optional(address.zip)?,
+                first_name: rewrap_optional(address.first_name)?,
+                last_name: rewrap_optional(address.last_name)?,
+                phone_number: rewrap_optional(address.phone_number)?,
+                ..address
+            }),
+            Self::MerchantConnectorAccount(mca) => {
+                Self::MerchantConnectorAccount(MerchantConnectorAccount {
+                    connector_account_details: domain_types::rewrap(
+                        mca.connector_account_details,
+                        key,
+                    )?,
+                    ..mca
+                })
+            }
+            Self::PaymentMethod(payment_method) => Self::PaymentMethod(PaymentMethod {
+                payment_method_data: rewrap_optional(payment_method.payment_method_data)?,
+                ..payment_method
+            }),
+        })
+    }
+}
@@ -1 +1 @@
-pub use diesel_models::merchant_key_store::MerchantKeyStore;
+pub use diesel_models::merchant_key_store::{MerchantKeyRotationWorkflow, MerchantKeyStore};
@@ -1 +1,58 @@
-pub use diesel_models::payment_link::{PaymentLink, PaymentLinkNew};
+use async_bb8_diesel::AsyncRunQueryDsl;
+use common_utils::errors::CustomResult;
+use diesel::{associations::HasTable, ExpressionMethods, QueryDsl};
+pub use diesel_models::payment_link::{
+    PaymentLink, PaymentLinkNew, PaymentLinkUpdate, PaymentLinkUpdateInternal,
+};
+use diesel_models::{errors, schema::payment_link::dsl};
+use error_stack::{IntoReport, ResultExt};
+
+use crate::{connection::PgPooledConn, logger};
+
+#[async_trait::async_trait]
+pub trait PaymentLinkDbExt: Sized {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_link_list_constraints: api_models::payments::PaymentLinkListConstraints,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError>;
+}
+
+#[async_trait::async_trait]
+impl PaymentLinkDbExt for PaymentLink {
+    async fn filter_by_constraints(
+        conn: &PgPooledConn,
+        merchant_id: &str,
+        payment_link_list_constraints: api_models::payments::PaymentLinkListConstraints,
+    ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
+        let mut filter = <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .order(dsl::created_at.desc())
+            .into_boxed();
+
+        if let Some(status) = payment_link_list_constraints.status {
+            filter = filter.filter(dsl::status.eq(status));
+        }
+        if let Some(created_gte) = payment_link_list_constraints.created_gte {
+            filter = filter.filter(dsl::created_at.ge(created_gte));
+        }
+        if let Some(created_lte) = payment_link_list_constraints.created_lte {
+            filter = filter.filter(dsl::created_at.le(created_lte));
+        }
+
+        filter = filter
+            .limit(payment_link_list_constraints.limit.into())
+            .offset(payment_link_list_constraints.offset.into());
+
+        logger::debug!(query = %diesel::debug_query::<diesel::pg::Pg, _>(&filter).to_string());
+
+        filter
+            .get_results_async(conn)
+            .await
+            .into_report()
+            // The query built here returns an empty Vec when no records are found, and if any error does occur,
+            // it would be an internal database error, due to which we are raising a DatabaseError::Unknown error
+            .change_context(errors::DatabaseError::Others)
+            .attach_printable("Error filtering payment links by specified constraints")
+    }
+}
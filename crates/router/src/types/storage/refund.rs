@@ -18,7 +18,7 @@ use crate::{connection::PgPooledConn, logger};
 pub trait RefundDbExt: Sized {
     async fn filter_by_constraints(
         conn: &PgPooledConn,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_list_details: &api_models::refunds::RefundListRequest,
         limit: i64,
         offset: i64,
@@ -32,22 +32,38 @@ pub trait RefundDbExt: Sized {
 
     async fn get_refunds_count(
         conn: &PgPooledConn,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_list_details: &api_models::refunds::RefundListRequest,
     ) -> CustomResult<i64, errors::DatabaseError>;
+
+    async fn get_refund_aggregates(
+        conn: &PgPooledConn,
+        merchant_ids: &[String],
+        time_range: &api_models::refunds::TimeRange,
+    ) -> CustomResult<Vec<RefundAggregate>, errors::DatabaseError>;
+}
+
+/// The number and total amount of the refunds of a merchant with the same status and currency.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RefundAggregate {
+    pub merchant_id: String,
+    pub status: RefundStatus,
+    pub currency: Currency,
+    pub count: i64,
+    pub total_amount: i64,
 }
 
 #[async_trait::async_trait]
 impl RefundDbExt for Refund {
     async fn filter_by_constraints(
         conn: &PgPooledConn,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_list_details: &api_models::refunds::RefundListRequest,
         limit: i64,
         offset: i64,
     ) -> CustomResult<Vec<Self>, errors::DatabaseError> {
         let mut filter = <Self as HasTable>::table()
-            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::merchant_id.eq_any(merchant_ids.to_owned()))
             .order(dsl::modified_at.desc())
             .into_boxed();
 
@@ -171,12 +187,12 @@ impl RefundDbExt for Refund {
 
     async fn get_refunds_count(
         conn: &PgPooledConn,
-        merchant_id: &str,
+        merchant_ids: &[String],
         refund_list_details: &api_models::refunds::RefundListRequest,
     ) -> CustomResult<i64, errors::DatabaseError> {
         let mut filter = <Self as HasTable>::table()
             .count()
-            .filter(dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(dsl::merchant_id.eq_any(merchant_ids.to_owned()))
             .into_boxed();
 
         if let Some(pay_id) = &refund_list_details.payment_id {
@@ -219,4 +235,54 @@ impl RefundDbExt for Refund {
             .change_context(errors::DatabaseError::NotFound)
             .attach_printable_lazy(|| "Error filtering count of refunds")
     }
+
+    async fn get_refund_aggregates(
+        conn: &PgPooledConn,
+        merchant_ids: &[String],
+        time_range: &api_models::refunds::TimeRange,
+    ) -> CustomResult<Vec<RefundAggregate>, errors::DatabaseError> {
+        use diesel::dsl::{count_star, sql};
+
+        let mut query = <Self as HasTable>::table()
+            .filter(dsl::merchant_id.eq_any(merchant_ids.to_owned()))
+            .filter(dsl::created_at.ge(time_range.start_time))
+            .group_by((dsl::merchant_id, dsl::refund_status, dsl::currency))
+            .select((
+                dsl::merchant_id,
+                dsl::refund_status,
+                dsl::currency,
+                count_star(),
+                sql::<diesel::sql_types::BigInt>("CAST(COALESCE(SUM(refund_amount), 0) AS BIGINT)"),
+            ))
+            .into_boxed();
+
+        if let Some(end_time) = time_range.end_time {
+            query = query.filter(dsl::created_at.le(end_time));
+        }
+
+        logger::debug!(query = %diesel::debug_query::<diesel::pg::Pg, _>(&query).to_string());
+
+        db_metrics::track_database_call::<<Self as HasTable>::Table, _, _>(
+            query.get_results_async::<(String, RefundStatus, Currency, i64, i64)>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .change_context(errors::DatabaseError::Others)
+        .attach_printable("Error aggregating refunds")
+        .map(|aggregates| {
+            aggregates
+                .into_iter()
+                .map(
+                    |(merchant_id, status, currency, count, total_amount)| RefundAggregate {
+                        merchant_id,
+                        status,
+                        currency,
+                        count,
+                        total_amount,
+                    },
+                )
+                .collect()
+        })
+    }
 }
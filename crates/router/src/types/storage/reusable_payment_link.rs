@@ -0,0 +1,4 @@
+pub use diesel_models::reusable_payment_link::{
+    ReusablePaymentLink, ReusablePaymentLinkNew, ReusablePaymentLinkUpdate,
+    ReusablePaymentLinkUpdateInternal,
+};
@@ -0,0 +1 @@
+pub use diesel_models::user_role::{UserRole, UserRoleNew, UserRoleUpdate};
@@ -177,6 +177,8 @@ impl ForeignFrom<storage_enums::MandateAmountData> for api_models::payments::Man
             start_date: from.start_date,
             end_date: from.end_date,
             metadata: from.metadata,
+            frequency_limit: from.frequency_limit,
+            frequency_interval: from.frequency_interval,
         }
     }
 }
@@ -211,6 +213,8 @@ impl ForeignFrom<api_models::payments::MandateData> for data_models::mandates::M
                             start_date: i.start_date,
                             end_date: i.end_date,
                             metadata: i.metadata,
+                            frequency_limit: i.frequency_limit,
+                            frequency_interval: i.frequency_interval,
                         },
                     ))
                 }
@@ -222,6 +226,8 @@ impl ForeignFrom<api_models::payments::MandateData> for data_models::mandates::M
                             start_date: i.start_date,
                             end_date: i.end_date,
                             metadata: i.metadata,
+                            frequency_limit: i.frequency_limit,
+                            frequency_interval: i.frequency_interval,
                         },
                     )
                 }
@@ -241,6 +247,8 @@ impl ForeignFrom<api_models::payments::MandateAmountData> for storage_enums::Man
             start_date: from.start_date,
             end_date: from.end_date,
             metadata: from.metadata,
+            frequency_limit: from.frequency_limit,
+            frequency_interval: from.frequency_interval,
         }
     }
 }
@@ -415,6 +423,7 @@ impl ForeignFrom<storage_enums::MandateStatus> for Option<storage_enums::EventTy
         match value {
             storage_enums::MandateStatus::Active => Some(storage_enums::EventType::MandateActive),
             storage_enums::MandateStatus::Revoked => Some(storage_enums::EventType::MandateRevoked),
+            storage_enums::MandateStatus::Expired => Some(storage_enums::EventType::MandateExpired),
             storage_enums::MandateStatus::Inactive | storage_enums::MandateStatus::Pending => None,
         }
     }
@@ -514,6 +523,8 @@ impl
             api_key: StrongSecret::from(plaintext_api_key.peek().to_owned()),
             created: api_key.created_at,
             expiration: api_key.expires_at.into(),
+            scopes: api_key.scopes.map(parse_api_key_scopes),
+            allowed_ips: api_key.allowed_ips,
         }
     }
 }
@@ -528,10 +539,21 @@ impl ForeignFrom<diesel_models::api_keys::ApiKey> for api_models::api_keys::Retr
             prefix: api_key.prefix.into(),
             created: api_key.created_at,
             expiration: api_key.expires_at.into(),
+            scopes: api_key.scopes.map(parse_api_key_scopes),
+            allowed_ips: api_key.allowed_ips,
+            last_used: api_key.last_used,
         }
     }
 }
 
+/// Parses the stored scopes of an API key, which are validated when they are set.
+pub fn parse_api_key_scopes(scopes: Vec<String>) -> Vec<api_models::api_keys::ApiKeyScope> {
+    scopes
+        .iter()
+        .filter_map(|scope| scope.parse().ok())
+        .collect()
+}
+
 impl ForeignFrom<api_models::api_keys::UpdateApiKeyRequest>
     for diesel_models::api_keys::ApiKeyUpdate
 {
@@ -541,6 +563,13 @@ impl ForeignFrom<api_models::api_keys::UpdateApiKeyRequest>
             description: api_key.description,
             expires_at: api_key.expiration.map(Into::into),
             last_used: None,
+            scopes: api_key
+                .scopes
+                .map(|scopes| scopes.iter().map(ToString::to_string).collect()),
+            // An empty list of allowed IP addresses lifts the restriction
+            allowed_ips: api_key
+                .allowed_ips
+                .map(|allowed_ips| Some(allowed_ips).filter(|ips| !ips.is_empty())),
         }
     }
 }
@@ -842,6 +871,42 @@ impl ForeignFrom<storage::PaymentLink> for api_models::payments::RetrievePayment
             created_at: payment_link_object.created_at,
             last_modified_at: payment_link_object.last_modified_at,
             link_expiry: payment_link_object.fulfilment_time,
+            status: payment_link_object.status,
+            // The configuration is validated before the payment link is stored
+            payment_link_config: payment_link_object
+                .payment_link_config
+                .and_then(|payment_link_config| serde_json::from_value(payment_link_config).ok()),
+        }
+    }
+}
+
+impl ForeignFrom<storage::ReusablePaymentLink>
+    for api_models::payments::ReusablePaymentLinkResponse
+{
+    fn foreign_from(reusable_payment_link: storage::ReusablePaymentLink) -> Self {
+        Self {
+            reusable_payment_link_id: reusable_payment_link.reusable_payment_link_id,
+            merchant_id: reusable_payment_link.merchant_id,
+            link_to_pay: reusable_payment_link.link_to_pay,
+            currency: reusable_payment_link.currency,
+            amount: reusable_payment_link.amount,
+            min_amount: reusable_payment_link.min_amount,
+            max_amount: reusable_payment_link.max_amount,
+            product_name: reusable_payment_link.product_name,
+            product_img_link: reusable_payment_link.product_img_link,
+            description: reusable_payment_link.description,
+            return_url: reusable_payment_link.return_url,
+            max_uses: reusable_payment_link.max_uses,
+            times_used: reusable_payment_link.times_used,
+            amount_collected: reusable_payment_link.amount_collected,
+            status: reusable_payment_link.status,
+            link_expiry: reusable_payment_link.link_expiry,
+            // The configuration is validated before the reusable payment link is stored
+            payment_link_config: reusable_payment_link
+                .payment_link_config
+                .and_then(|payment_link_config| serde_json::from_value(payment_link_config).ok()),
+            created_at: reusable_payment_link.created_at,
+            last_modified_at: reusable_payment_link.last_modified_at,
         }
     }
 }
@@ -872,3 +937,49 @@ impl ForeignFrom<api_models::organization::OrganizationNew>
         }
     }
 }
+
+impl ForeignFrom<diesel_models::organization::Organization>
+    for api_models::organization::OrganizationResponse
+{
+    fn foreign_from(item: diesel_models::organization::Organization) -> Self {
+        Self {
+            organization_id: item.org_id,
+            organization_name: item.org_name,
+            api_key_prefix: item.api_key_prefix,
+        }
+    }
+}
+
+impl ForeignFrom<storage::AuditEvent> for api_models::audit_events::AuditEventResponse {
+    fn foreign_from(item: storage::AuditEvent) -> Self {
+        Self {
+            audit_event_id: item.audit_event_id,
+            merchant_id: item.merchant_id,
+            resource_type: item.resource_type,
+            resource_id: item.resource_id,
+            action: item.action,
+            auth_type: item.auth_type,
+            actor_id: item.actor_id,
+            request_id: item.request_id,
+            diff: item.diff,
+            created_at: item.created_at,
+        }
+    }
+}
+
+impl ForeignFrom<storage::AuditEventNew> for api_models::audit_events::AuditEventResponse {
+    fn foreign_from(item: storage::AuditEventNew) -> Self {
+        Self {
+            audit_event_id: item.audit_event_id,
+            merchant_id: item.merchant_id,
+            resource_type: item.resource_type,
+            resource_id: item.resource_id,
+            action: item.action,
+            auth_type: item.auth_type,
+            actor_id: item.actor_id,
+            request_id: item.request_id,
+            diff: item.diff,
+            created_at: item.created_at,
+        }
+    }
+}
@@ -102,6 +102,40 @@ pub fn generate_uuid() -> String {
     Uuid::new_v4().to_string()
 }
 
+/// Whether the colour is a hex colour such as `#0099FF` or `#09F`.
+pub fn is_valid_hex_color(color: &str) -> bool {
+    color
+        .strip_prefix('#')
+        .map(|hex| {
+            matches!(hex.len(), 3 | 6) && hex.chars().all(|character| character.is_ascii_hexdigit())
+        })
+        .unwrap_or(false)
+}
+
+/// The supported locale that content of the provided locale is shown in. Falls back from the
+/// exact locale to its language, and then to the default locale.
+pub fn resolve_locale(
+    locale: Option<&str>,
+    supported_locales: &[&'static str],
+    default_locale: &'static str,
+) -> &'static str {
+    let Some(locale) = locale.map(|locale| locale.trim().to_lowercase().replace('_', "-")) else {
+        return default_locale;
+    };
+    let language = locale.split('-').next().unwrap_or_default();
+
+    supported_locales
+        .iter()
+        .find(|supported_locale| **supported_locale == locale)
+        .or_else(|| {
+            supported_locales
+                .iter()
+                .find(|supported_locale| **supported_locale == language)
+        })
+        .copied()
+        .unwrap_or(default_locale)
+}
+
 pub trait ConnectorResponseExt: Sized {
     fn get_response(self) -> RouterResult<types::Response>;
     fn get_error_response(self) -> RouterResult<types::Response>;
@@ -195,6 +229,17 @@ mod tests {
         let qr_image_data_source_url = utils::QrImage::new_from_data("Hyperswitch".to_string());
         assert!(qr_image_data_source_url.is_ok());
     }
+
+    #[test]
+    fn test_resolve_locale() {
+        let supported_locales = ["en", "fr"];
+        let resolve_locale = |locale| utils::resolve_locale(locale, &supported_locales, "en");
+        assert_eq!(resolve_locale(None), "en");
+        assert_eq!(resolve_locale(Some("fr")), "fr");
+        assert_eq!(resolve_locale(Some("fr_CA")), "fr");
+        assert_eq!(resolve_locale(Some("FR-fr")), "fr");
+        assert_eq!(resolve_locale(Some("de-DE")), "en");
+    }
 }
 
 pub async fn find_payment_intent_from_payment_id_type(
@@ -453,7 +498,7 @@ pub trait CustomerAddress {
     async fn get_address_update(
         &self,
         address_details: api_models::payments::AddressDetails,
-        key: &[u8],
+        key: &domain::types::KeyRing,
         storage_scheme: storage::enums::MerchantStorageScheme,
     ) -> CustomResult<storage::AddressUpdate, common_utils::errors::CryptoError>;
 
@@ -462,7 +507,7 @@ pub trait CustomerAddress {
         address_details: api_models::payments::AddressDetails,
         merchant_id: &str,
         customer_id: &str,
-        key: &[u8],
+        key: &domain::types::KeyRing,
         storage_scheme: storage::enums::MerchantStorageScheme,
     ) -> CustomResult<domain::Address, common_utils::errors::CryptoError>;
 }
@@ -472,7 +517,7 @@ impl CustomerAddress for api_models::customers::CustomerRequest {
     async fn get_address_update(
         &self,
         address_details: api_models::payments::AddressDetails,
-        key: &[u8],
+        key: &domain::types::KeyRing,
         storage_scheme: storage::enums::MerchantStorageScheme,
     ) -> CustomResult<storage::AddressUpdate, common_utils::errors::CryptoError> {
         async {
@@ -524,7 +569,7 @@ impl CustomerAddress for api_models::customers::CustomerRequest {
         address_details: api_models::payments::AddressDetails,
         merchant_id: &str,
         customer_id: &str,
-        key: &[u8],
+        key: &domain::types::KeyRing,
         storage_scheme: storage::enums::MerchantStorageScheme,
     ) -> CustomResult<domain::Address, common_utils::errors::CryptoError> {
         async {
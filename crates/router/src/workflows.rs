@@ -1,3 +1,13 @@
+pub mod api_key_revocation;
+#[cfg(feature = "email")]
+pub mod invoice_reminder;
+pub mod kv_migration;
+pub mod mandate_expiry;
+pub mod merchant_key_rotation;
+pub mod payment_link_expiry;
 pub mod payment_sync;
 pub mod refund_router;
+pub mod subscription_billing;
 pub mod tokenized_data;
+#[cfg(feature = "email")]
+pub mod transactional_email;
@@ -1,5 +1,6 @@
 use common_utils::ext_traits::ValueExt;
 use diesel_models::enums::{self as storage_enums};
+use external_services::email::EmailMessage;
 
 use super::{ApiKeyExpiryWorkflow, ProcessTrackerWorkflow};
 use crate::{
@@ -30,7 +31,7 @@ impl ProcessTrackerWorkflow for ApiKeyExpiryWorkflow {
             .store
             .get_merchant_key_store_by_merchant_id(
                 tracking_data.merchant_id.as_str(),
-                &state.store.get_master_key().to_vec().into(),
+                &state.store.get_master_key_ring(),
             )
             .await?;
 
@@ -65,11 +66,13 @@ impl ProcessTrackerWorkflow for ApiKeyExpiryWorkflow {
             .clone()
             .send_email(
                 email_id.ok_or_else(|| errors::ProcessTrackerError::MissingRequiredField)?,
-                "API Key Expiry Notice".to_string(),
-                format!("Dear Merchant,\n
+                EmailMessage::plain_text(
+                    "API Key Expiry Notice".to_string(),
+                    format!("Dear Merchant,\n
 It has come to our attention that your API key will expire in {expires_in} days. To ensure uninterrupted access to our platform and continued smooth operation of your services, we kindly request that you take the necessary actions as soon as possible.\n\n
 Thanks,\n
 Team Hyperswitch"),
+                ),
             )
             .await
             .map_err(|_| errors::ProcessTrackerError::FlowExecutionError {
@@ -0,0 +1,51 @@
+use common_utils::ext_traits::ValueExt;
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    errors,
+    logger::{error, info},
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+};
+
+pub struct ApiKeyRevocationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for ApiKeyRevocationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: storage::ApiKeyRevocationWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("ApiKeyRevocationWorkflow")?;
+
+        // The API key may have already been revoked by the merchant during the grace period
+        let revoked = db
+            .revoke_api_key(&tracking_data.merchant_id, &tracking_data.key_id)
+            .await?;
+        if !revoked {
+            info!(key_id = %tracking_data.key_id, "Rotated API key was already revoked");
+        }
+
+        let task_id = process.id.clone();
+        process
+            .finish_with_status(db.as_scheduler(), format!("COMPLETED_BY_PT_{task_id}"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        _state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing workflow");
+        Ok(())
+    }
+}
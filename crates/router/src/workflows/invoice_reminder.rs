@@ -0,0 +1,67 @@
+use common_utils::{date_time, ext_traits::ValueExt};
+use error_stack::IntoReport;
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    core::invoices,
+    errors,
+    logger::error,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+};
+
+/// Sends the reminders of an invoice, the task being retried once for each reminder. The number
+/// of times the task has been retried is the number of reminders that have been sent.
+pub struct InvoiceReminderWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for InvoiceReminderWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: storage::InvoiceReminderWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("InvoiceReminderWorkflow")?;
+
+        match invoices::send_invoice_reminder(state, &tracking_data, process.retry_count).await? {
+            Some(schedule_time) => process.retry(db.as_scheduler(), schedule_time).await?,
+            None => {
+                let task_id = process.id.clone();
+                process
+                    .finish_with_status(db.as_scheduler(), format!("COMPLETED_BY_PT_{task_id}"))
+                    .await?
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, ?error, "Failed while executing workflow");
+
+        // A reminder that could not be sent counts towards the reminders of the invoice, so that
+        // failures cannot make the task run forever
+        let invoice_conf = &state.conf.invoice;
+        let db = state.store.as_scheduler();
+        if process.retry_count + 1 < i32::from(invoice_conf.max_reminders) {
+            let schedule_time = date_time::now().saturating_add(time::Duration::seconds(
+                i64::from(invoice_conf.reminder_interval_in_seconds),
+            ));
+            process.retry(db, schedule_time).await.into_report()
+        } else {
+            process
+                .finish_with_status(db, "FAILURE".to_string())
+                .await
+                .into_report()
+        }
+    }
+}
@@ -0,0 +1,63 @@
+use common_utils::{date_time, ext_traits::ValueExt};
+use error_stack::IntoReport;
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    core::admin,
+    errors,
+    logger::error,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+};
+
+/// Completes the migration of a merchant away from KV, checking periodically until all of the
+/// merchant's entries have been drained to the database.
+pub struct KvMigrationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for KvMigrationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: storage::KvMigrationWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("KvMigrationWorkflow")?;
+
+        if admin::process_kv_migration(state, &tracking_data).await? {
+            let schedule_time = date_time::now().saturating_add(time::Duration::seconds(
+                admin::KV_MIGRATION_CHECK_INTERVAL_IN_SECONDS,
+            ));
+            process.retry(db.as_scheduler(), schedule_time).await?;
+        } else {
+            let task_id = process.id.clone();
+            process
+                .finish_with_status(db.as_scheduler(), format!("COMPLETED_BY_PT_{task_id}"))
+                .await?;
+        }
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, ?error, "Failed while executing workflow");
+
+        // The merchant stays on the draining scheme until the migration completes, so the check
+        // is retried rather than abandoned
+        let schedule_time = date_time::now().saturating_add(time::Duration::seconds(
+            admin::KV_MIGRATION_CHECK_INTERVAL_IN_SECONDS,
+        ));
+        process
+            .retry(state.store.as_scheduler(), schedule_time)
+            .await
+            .into_report()
+    }
+}
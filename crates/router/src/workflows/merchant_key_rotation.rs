@@ -0,0 +1,69 @@
+use common_utils::{date_time, ext_traits::ValueExt};
+use error_stack::{IntoReport, ResultExt};
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    core::merchant_key_rotation,
+    errors,
+    logger::error,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+};
+
+pub struct MerchantKeyRotationWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for MerchantKeyRotationWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: storage::MerchantKeyRotationWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("MerchantKeyRotationWorkflow")?;
+
+        let completed =
+            merchant_key_rotation::rewrap_merchant_data_batch(state, &tracking_data).await?;
+
+        // Each execution re-encrypts a single batch, the task is rescheduled until all data of
+        // the merchant has been processed
+        if completed {
+            let task_id = process.id.clone();
+            process
+                .finish_with_status(db.as_scheduler(), format!("COMPLETED_BY_PT_{task_id}"))
+                .await?;
+        } else {
+            process.reset(db.as_scheduler(), date_time::now()).await?;
+        }
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        _error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, "Failed while executing workflow");
+
+        let tracking_data: storage::MerchantKeyRotationWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("MerchantKeyRotationWorkflow")
+            .change_context(errors::ProcessTrackerError::DeserializationFailed)?;
+        merchant_key_rotation::fail_merchant_key_rotation(state, &tracking_data)
+            .await
+            .change_context(errors::ProcessTrackerError::EApiErrorResponse)?;
+
+        process
+            .finish_with_status(state.store.as_scheduler(), "FAILURE".to_string())
+            .await
+            .into_report()?;
+
+        Ok(())
+    }
+}
@@ -0,0 +1,66 @@
+use common_utils::{date_time, ext_traits::ValueExt};
+use error_stack::IntoReport;
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    core::payment_link,
+    errors,
+    logger::error,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+};
+
+/// The number of times expiring a payment link is retried before the task is marked as failed.
+const MAX_RETRIES: i32 = 3;
+
+/// The number of seconds after which expiring a payment link is retried, multiplied by the
+/// number of times it has been retried.
+const RETRY_INTERVAL_IN_SECONDS: i64 = 60;
+
+pub struct PaymentLinkExpiryWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for PaymentLinkExpiryWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: storage::PaymentLinkExpiryWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("PaymentLinkExpiryWorkflow")?;
+
+        payment_link::expire_payment_link(state, &tracking_data).await?;
+
+        let task_id = process.id.clone();
+        process
+            .finish_with_status(db.as_scheduler(), format!("COMPLETED_BY_PT_{task_id}"))
+            .await?;
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, ?error, "Failed while executing workflow");
+
+        let db = state.store.as_scheduler();
+        if process.retry_count < MAX_RETRIES {
+            let schedule_time = date_time::now().saturating_add(time::Duration::seconds(
+                RETRY_INTERVAL_IN_SECONDS * i64::from(process.retry_count + 1),
+            ));
+            process.retry(db, schedule_time).await.into_report()
+        } else {
+            process
+                .finish_with_status(db, "FAILURE".to_string())
+                .await
+                .into_report()
+        }
+    }
+}
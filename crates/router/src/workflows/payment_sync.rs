@@ -46,7 +46,7 @@ impl ProcessTrackerWorkflow<AppState> for PaymentsSyncWorkflow {
                     .merchant_id
                     .as_ref()
                     .get_required_value("merchant_id")?,
-                &db.get_master_key().to_vec().into(),
+                &db.get_master_key_ring(),
             )
             .await?;
 
@@ -0,0 +1,66 @@
+use common_utils::{date_time, ext_traits::ValueExt};
+use error_stack::IntoReport;
+use scheduler::consumer::workflows::ProcessTrackerWorkflow;
+
+use crate::{
+    core::{payment_methods::Oss, subscriptions},
+    errors,
+    logger::error,
+    routes::AppState,
+    types::storage::{self, ProcessTrackerExt},
+};
+
+/// The number of seconds after which the billing of a subscription is retried when the task
+/// failed for reasons other than a declined payment
+const BILLING_RETRY_INTERVAL_IN_SECONDS: i64 = 60 * 60;
+
+/// Charges a subscription at the end of each of its billing periods, the task being retried for
+/// every period until the subscription is cancelled.
+pub struct SubscriptionBillingWorkflow;
+
+#[async_trait::async_trait]
+impl ProcessTrackerWorkflow<AppState> for SubscriptionBillingWorkflow {
+    async fn execute_workflow<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+    ) -> Result<(), errors::ProcessTrackerError> {
+        let db = &*state.store;
+        let tracking_data: storage::SubscriptionBillingWorkflow = process
+            .tracking_data
+            .clone()
+            .parse_value("SubscriptionBillingWorkflow")?;
+
+        match subscriptions::process_subscription_billing::<Oss>(state, &tracking_data).await? {
+            Some(schedule_time) => process.retry(db.as_scheduler(), schedule_time).await?,
+            None => {
+                let task_id = process.id.clone();
+                process
+                    .finish_with_status(db.as_scheduler(), format!("COMPLETED_BY_PT_{task_id}"))
+                    .await?
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn error_handler<'a>(
+        &'a self,
+        state: &'a AppState,
+        process: storage::ProcessTracker,
+        error: errors::ProcessTrackerError,
+    ) -> errors::CustomResult<(), errors::ProcessTrackerError> {
+        error!(%process.id, ?error, "Failed while executing workflow");
+
+        // The retry count of the task grows with every billing period, so it cannot bound the
+        // retries of a failure. Declined payments are bounded by the dunning attempts instead,
+        // other failures are retried until the subscription is cancelled. Retried charges find the
+        // payment created before the failure, as its identifier is derived from the billing period.
+        let schedule_time = date_time::now()
+            .saturating_add(time::Duration::seconds(BILLING_RETRY_INTERVAL_IN_SECONDS));
+        process
+            .retry(state.store.as_scheduler(), schedule_time)
+            .await
+            .into_report()
+    }
+}
@@ -287,7 +287,7 @@ async fn payments_create_core() {
         .store
         .get_merchant_key_store_by_merchant_id(
             "juspay_merchant",
-            &state.store.get_master_key().to_vec().into(),
+            &state.store.get_master_key_ring(),
         )
         .await
         .unwrap();
@@ -460,7 +460,7 @@ async fn payments_create_core_adyen_no_redirect() {
         .store
         .get_merchant_key_store_by_merchant_id(
             "juspay_merchant",
-            &state.store.get_master_key().to_vec().into(),
+            &state.store.get_master_key_ring(),
         )
         .await
         .unwrap();
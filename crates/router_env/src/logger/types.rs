@@ -52,7 +52,7 @@ pub enum Tag {
 }
 
 /// API Flow
-#[derive(Debug, Display, Clone, PartialEq, Eq)]
+#[derive(Debug, Display, EnumString, Clone, PartialEq, Eq)]
 pub enum Flow {
     /// Merchants account create flow.
     MerchantsAccountCreate,
@@ -100,6 +100,8 @@ pub enum Flow {
     MandatesRevoke,
     /// Mandates list flow.
     MandatesList,
+    /// Mandate payments list flow.
+    MandatePaymentsList,
     /// Payment methods create flow.
     PaymentMethodsCreate,
     /// Payment methods list flow.
@@ -175,6 +177,8 @@ pub enum Flow {
     ApiKeyUpdate,
     /// API Key revoke flow
     ApiKeyRevoke,
+    /// API Key rotate flow
+    ApiKeyRotate,
     /// API Key list flow
     ApiKeyList,
     /// Dispute Retrieve flow
@@ -189,6 +193,8 @@ pub enum Flow {
     DeleteFile,
     /// Retrieve File flow
     RetrieveFile,
+    /// Retrieve File download URL flow
+    RetrieveFileDownloadUrl,
     /// Dispute Evidence submission flow
     DisputesEvidenceSubmit,
     /// Create Config Key flow
@@ -203,6 +209,48 @@ pub enum Flow {
     PaymentLinkRetrieve,
     /// payment Link Initiate flow
     PaymentLinkInitiate,
+    /// Payment Link Status flow
+    PaymentLinkStatus,
+    /// Payment Link List flow
+    PaymentLinkList,
+    /// Reusable Payment Link Create flow
+    ReusablePaymentLinkCreate,
+    /// Reusable Payment Link Retrieve flow
+    ReusablePaymentLinkRetrieve,
+    /// Reusable Payment Link Update flow
+    ReusablePaymentLinkUpdate,
+    /// Reusable Payment Link Initiate flow
+    ReusablePaymentLinkInitiate,
+    /// Reusable Payment Link Payment flow
+    ReusablePaymentLinkPayment,
+    /// Invoice Create flow
+    InvoiceCreate,
+    /// Invoice Retrieve flow
+    InvoiceRetrieve,
+    /// Invoice Finalize flow
+    InvoiceFinalize,
+    /// Invoice Void flow
+    InvoiceVoid,
+    /// Invoice PDF flow
+    InvoicePdf,
+    /// Hosted Invoice Retrieve flow
+    HostedInvoiceRetrieve,
+    /// Hosted Invoice PDF flow
+    HostedInvoicePdf,
+    /// Subscription Plan Create flow
+    SubscriptionPlanCreate,
+    /// Subscription Plan Retrieve flow
+    SubscriptionPlanRetrieve,
+    /// Subscription Plan Update flow
+    SubscriptionPlanUpdate,
+    /// Subscription Create flow
+    SubscriptionCreate,
+    /// Subscription Retrieve flow
+    SubscriptionRetrieve,
+    /// Subscription Update flow
+    SubscriptionUpdate,
+    /// Subscription Cancel flow
+    SubscriptionCancel,
     /// Create a business profile
     BusinessProfileCreate,
     /// Update a business profile
@@ -213,8 +261,68 @@ pub enum Flow {
     BusinessProfileDelete,
     /// List all the business profiles for a merchant
     BusinessProfileList,
+    /// Organization create flow.
+    OrganizationCreate,
+    /// Organization retrieve flow.
+    OrganizationRetrieve,
+    /// Organization update flow.
+    OrganizationUpdate,
+    /// Organization API key create flow.
+    OrganizationApiKeyCreate,
+    /// List the merchants of an organization
+    OrganizationMerchantsList,
+    /// List the payments of all merchants of an organization
+    OrganizationPaymentsList,
+    /// List the refunds of all merchants of an organization
+    OrganizationRefundsList,
+    /// Aggregate the payments of all merchants of an organization
+    OrganizationPaymentsAggregate,
+    /// Aggregate the refunds of all merchants of an organization
+    OrganizationRefundsAggregate,
+    /// Merchant rate limits update flow.
+    MerchantRateLimitsUpdate,
+    /// Merchant rate limits retrieve flow.
+    MerchantRateLimitsRetrieve,
+    /// Merchant key rotate flow.
+    MerchantKeyRotate,
+    /// Merchant key rotation retrieve flow.
+    MerchantKeyRotationRetrieve,
+    /// Merchant KV migration status flow.
+    MerchantKvMigrationStatus,
     /// Different verification flows
     Verification,
+    /// User create flow.
+    UserCreate,
+    /// User sign in flow.
+    UserSignIn,
+    /// User sign out flow.
+    UserSignOut,
+    /// User token refresh flow.
+    UserRefreshToken,
+    /// User password change flow.
+    UserChangePassword,
+    /// User merchant account switch flow.
+    UserSwitchMerchant,
+    /// User details retrieve flow.
+    UserRetrieve,
+    /// List the users of a merchant account.
+    UsersList,
+    /// User invite flow.
+    UserInvite,
+    /// User invitation accept flow.
+    UserAcceptInvitation,
+    /// User forgot password flow.
+    UserForgotPassword,
+    /// User password reset flow.
+    UserResetPassword,
+    /// User role update flow.
+    UserRoleUpdate,
+    /// User role delete flow.
+    UserRoleDelete,
+    /// List audit events flow.
+    AuditEventsList,
+    /// Payment metrics flow.
+    GetPaymentMetrics,
 }
 
 ///
@@ -1,11 +1,6 @@
-#[cfg(feature = "kms")]
-use external_services::kms;
 pub use router_env::config::{Log, LogConsole, LogFile, LogTelemetry};
 use serde::Deserialize;
-#[cfg(feature = "kms")]
-pub type Password = kms::KmsValue;
-#[cfg(not(feature = "kms"))]
-pub type Password = masking::Secret<String>;
+pub type Password = external_services::secrets_management::SecretValue;
 
 #[derive(Debug, Clone, Deserialize)]
 #[serde(default)]
@@ -10,4 +10,6 @@ pub struct Database {
     pub pool_size: u32,
     pub connection_timeout: u64,
     pub queue_strategy: bb8::QueueStrategy,
+    /// The schema the tables are looked up in, if not the default schema of the database.
+    pub schema: Option<String>,
 }
@@ -1,7 +1,11 @@
+use async_bb8_diesel::AsyncRunQueryDsl;
 use bb8::PooledConnection;
 use common_utils::errors;
-use diesel::PgConnection;
+use diesel::{sql_types::Double, PgConnection, QueryableByName};
 use error_stack::{IntoReport, ResultExt};
+use router_env::logger;
+
+use crate::{database::store::REPLICA_LAG_SAMPLE_TTL, metrics, ReadConsistency};
 
 pub type PgPool = bb8::Pool<async_bb8_diesel::ConnectionManager<PgConnection>>;
 
@@ -47,6 +51,102 @@ pub async fn pg_connection_read<T: crate::DatabaseStore>(
         .change_context(crate::errors::StorageError::DatabaseConnectionError)
 }
 
+/// Obtains a connection for a read with the specified consistency.
+///
+/// Eventually consistent reads are served by the replica database, unless its replication lag
+/// exceeds the staleness tolerance configured for the store (or cannot be determined), in which
+/// case they fall back to the master database.
+pub async fn pg_connection_read_with_consistency<T: crate::DatabaseStore>(
+    store: &T,
+    consistency: ReadConsistency,
+) -> errors::CustomResult<
+    PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>>,
+    crate::errors::StorageError,
+> {
+    match consistency {
+        ReadConsistency::Strong => pg_connection_write(store).await,
+        ReadConsistency::Eventual => {
+            let conn = store
+                .get_replica_pool()
+                .get()
+                .await
+                .into_report()
+                .change_context(crate::errors::StorageError::DatabaseConnectionError)?;
+
+            if let Some(max_staleness) = store.get_replica_max_staleness() {
+                let is_stale = sampled_replication_lag(store, &conn).await.map_or_else(
+                    |error| {
+                        logger::error!(replica_lag_error=?error);
+                        true
+                    },
+                    |lag| lag > max_staleness,
+                );
+
+                if is_stale {
+                    metrics::STALE_REPLICA_READS_SERVED_BY_MASTER.add(&metrics::CONTEXT, 1, &[]);
+                    return pg_connection_write(store).await;
+                }
+            }
+
+            metrics::REPLICA_READS.add(&metrics::CONTEXT, 1, &[]);
+            Ok(conn)
+        }
+    }
+}
+
+/// Determines the replication lag of the replica database of the store, reusing the lag sampled by
+/// an earlier read for up to [`REPLICA_LAG_SAMPLE_TTL`] so that a burst of reads does not query the
+/// replica for its lag on every read.
+async fn sampled_replication_lag<T: crate::DatabaseStore>(
+    store: &T,
+    conn: &PgPooledConn,
+) -> errors::CustomResult<std::time::Duration, crate::errors::StorageError> {
+    let Some(lag_sample) = store.get_replica_lag_sample() else {
+        return replication_lag(conn).await;
+    };
+
+    if let Some(lag) = lag_sample.get(std::time::Instant::now(), REPLICA_LAG_SAMPLE_TTL) {
+        return Ok(lag);
+    }
+
+    let lag = replication_lag(conn).await?;
+    lag_sample.set(std::time::Instant::now(), lag);
+    Ok(lag)
+}
+
+#[derive(QueryableByName)]
+struct ReplicationLag {
+    #[diesel(sql_type = Double)]
+    lag_seconds: f64,
+}
+
+/// Determines how far the database behind the connection lags behind its primary. A database that
+/// is not replaying WAL (such as the master database itself) has no lag.
+async fn replication_lag(
+    conn: &PgPooledConn,
+) -> errors::CustomResult<std::time::Duration, crate::errors::StorageError> {
+    let replication_lag = diesel::sql_query(
+        "SELECT CASE \
+            WHEN pg_last_wal_receive_lsn() = pg_last_wal_replay_lsn() THEN 0 \
+            ELSE COALESCE(EXTRACT(EPOCH FROM now() - pg_last_xact_replay_timestamp()), 0) \
+        END::DOUBLE PRECISION AS lag_seconds",
+    )
+    .get_result_async::<ReplicationLag>(conn)
+    .await
+    .into_report()
+    .change_context(crate::errors::StorageError::DatabaseError(
+        diesel_models::errors::DatabaseError::Others.into(),
+    ))
+    .attach_printable("Failed to determine the replication lag of the replica database")?;
+
+    std::time::Duration::try_from_secs_f64(replication_lag.lag_seconds.max(0.0))
+        .into_report()
+        .change_context(crate::errors::StorageError::DatabaseError(
+            diesel_models::errors::DatabaseError::Others.into(),
+        ))
+        .attach_printable("Invalid replication lag reported by the replica database")
+}
+
 pub async fn pg_connection_write<T: crate::DatabaseStore>(
     store: &T,
 ) -> errors::CustomResult<
@@ -5,6 +5,8 @@ use diesel::PgConnection;
 use error_stack::{IntoReport, ResultExt};
 use masking::PeekInterface;
 
+pub use data_models::ReadConsistency;
+
 use crate::config::Database;
 
 pub type PgPool = bb8::Pool<async_bb8_diesel::ConnectionManager<PgConnection>>;
@@ -16,6 +18,45 @@ pub trait DatabaseStore: Clone + Send + Sync {
     async fn new(config: Self::Config, test_transaction: bool) -> StorageResult<Self>;
     fn get_master_pool(&self) -> &PgPool;
     fn get_replica_pool(&self) -> &PgPool;
+
+    /// The maximum replication lag tolerated for reads served by the replica database, if any.
+    fn get_replica_max_staleness(&self) -> Option<std::time::Duration> {
+        None
+    }
+
+    /// The replication lag last sampled from the replica database, shared by all clones of the
+    /// store, if the store samples it.
+    fn get_replica_lag_sample(&self) -> Option<&ReplicaLagSample> {
+        None
+    }
+}
+
+/// How long a replication lag sampled from the replica database is reused before it is sampled
+/// again.
+pub const REPLICA_LAG_SAMPLE_TTL: std::time::Duration = std::time::Duration::from_secs(1);
+
+/// The most recent replication lag sampled from a replica database, along with when it was sampled.
+#[derive(Debug, Default)]
+pub struct ReplicaLagSample(std::sync::RwLock<Option<(std::time::Instant, std::time::Duration)>>);
+
+impl ReplicaLagSample {
+    /// Returns the sampled replication lag, if it was sampled less than `ttl` before `now`.
+    pub fn get(
+        &self,
+        now: std::time::Instant,
+        ttl: std::time::Duration,
+    ) -> Option<std::time::Duration> {
+        let sample = *self.0.read().ok()?;
+        sample.and_then(|(sampled_at, lag)| {
+            (now.saturating_duration_since(sampled_at) < ttl).then_some(lag)
+        })
+    }
+
+    pub fn set(&self, sampled_at: std::time::Instant, lag: std::time::Duration) {
+        if let Ok(mut sample) = self.0.write() {
+            *sample = Some((sampled_at, lag));
+        }
+    }
 }
 
 #[derive(Debug, Clone)]
@@ -45,13 +86,15 @@ impl DatabaseStore for Store {
 pub struct ReplicaStore {
     pub master_pool: PgPool,
     pub replica_pool: PgPool,
+    pub replica_max_staleness: Option<std::time::Duration>,
+    pub replica_lag_sample: std::sync::Arc<ReplicaLagSample>,
 }
 
 #[async_trait::async_trait]
 impl DatabaseStore for ReplicaStore {
-    type Config = (Database, Database);
-    async fn new(config: (Database, Database), test_transaction: bool) -> StorageResult<Self> {
-        let (master_config, replica_config) = config;
+    type Config = (Database, Database, Option<std::time::Duration>);
+    async fn new(config: Self::Config, test_transaction: bool) -> StorageResult<Self> {
+        let (master_config, replica_config, replica_max_staleness) = config;
         let master_pool = diesel_make_pg_pool(&master_config, test_transaction)
             .await
             .attach_printable("failed to create master pool")?;
@@ -61,6 +104,8 @@ impl DatabaseStore for ReplicaStore {
         Ok(Self {
             master_pool,
             replica_pool,
+            replica_max_staleness,
+            replica_lag_sample: std::sync::Arc::default(),
         })
     }
 
@@ -71,13 +116,21 @@ impl DatabaseStore for ReplicaStore {
     fn get_replica_pool(&self) -> &PgPool {
         &self.replica_pool
     }
+
+    fn get_replica_max_staleness(&self) -> Option<std::time::Duration> {
+        self.replica_max_staleness
+    }
+
+    fn get_replica_lag_sample(&self) -> Option<&ReplicaLagSample> {
+        Some(&self.replica_lag_sample)
+    }
 }
 
 pub async fn diesel_make_pg_pool(
     database: &Database,
     test_transaction: bool,
 ) -> StorageResult<PgPool> {
-    let database_url = format!(
+    let mut database_url = format!(
         "postgres://{}:{}@{}:{}/{}",
         database.username,
         database.password.peek(),
@@ -85,6 +138,9 @@ pub async fn diesel_make_pg_pool(
         database.port,
         database.dbname
     );
+    if let Some(schema) = &database.schema {
+        database_url.push_str(&format!("?options=-c%20search_path%3D{schema}"));
+    }
     let manager = async_bb8_diesel::ConnectionManager::<PgConnection>::new(database_url);
     let mut pool = bb8::Pool::builder()
         .max_size(database.pool_size)
@@ -118,3 +174,30 @@ impl CustomizeConnection<PgPooledConn, ConnectionError> for TestTransaction {
         .await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use std::time::{Duration, Instant};
+
+    use super::ReplicaLagSample;
+
+    #[test]
+    fn replica_lag_sample_expires_after_ttl() {
+        let sample = ReplicaLagSample::default();
+        let sampled_at = Instant::now();
+        let ttl = Duration::from_secs(1);
+
+        assert_eq!(sample.get(sampled_at, ttl), None);
+
+        sample.set(sampled_at, Duration::from_millis(250));
+        assert_eq!(
+            sample.get(sampled_at + Duration::from_millis(999), ttl),
+            Some(Duration::from_millis(250))
+        );
+        assert_eq!(sample.get(sampled_at + ttl, ttl), None);
+
+        sample.set(sampled_at + ttl, Duration::ZERO);
+        assert_eq!(sample.get(sampled_at + ttl, ttl), Some(Duration::ZERO));
+    }
+}
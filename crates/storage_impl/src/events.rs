@@ -0,0 +1,164 @@
+//! Change data events, published with a snapshot of payment intents, payment attempts and refunds
+//! every time they are inserted or updated, so that their state can be rebuilt outside the
+//! database.
+
+use diesel_models::enums::MerchantStorageScheme;
+use router_env::logger;
+use serde::Serialize;
+
+/// The version of the change data event format, incremented on changes incompatible with
+/// consumers of the events.
+pub const CHANGE_EVENT_VERSION: u16 = 1;
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeEventRecordType {
+    PaymentIntent,
+    PaymentAttempt,
+    Refund,
+}
+
+impl ChangeEventRecordType {
+    /// The fields of the record that are left out of its change data events, as they hold secrets
+    /// that are stored without being masked.
+    fn omitted_fields(self) -> &'static [&'static str] {
+        match self {
+            Self::PaymentIntent => &["client_secret"],
+            Self::PaymentAttempt | Self::Refund => &[],
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ChangeOperation {
+    Insert,
+    Update,
+}
+
+#[derive(Debug)]
+pub struct ChangeEvent {
+    pub record_type: ChangeEventRecordType,
+    /// The identifier of the record, that change data events of the same record share.
+    pub key: String,
+    pub payload: serde_json::Value,
+}
+
+/// Publishes the change data events of the store, such as through the event handler of the
+/// application.
+pub trait ChangeEventHandler: Send + Sync + std::fmt::Debug {
+    fn publish_change_event(&self, event: ChangeEvent);
+}
+
+#[derive(Serialize)]
+struct ChangeEventPayload {
+    version: u16,
+    operation: ChangeOperation,
+    storage_scheme: MerchantStorageScheme,
+    #[serde(with = "common_utils::custom_serde::iso8601")]
+    published_at: time::PrimitiveDateTime,
+    /// The record after the change, with secrets masked or omitted.
+    record: serde_json::Value,
+}
+
+/// Builds the change data event of a record, or `None` if the record cannot be serialized.
+pub(crate) fn build_change_event<R: Serialize>(
+    record_type: ChangeEventRecordType,
+    key: String,
+    operation: ChangeOperation,
+    storage_scheme: MerchantStorageScheme,
+    record: &R,
+) -> Option<ChangeEvent> {
+    let payload = masking::masked_serialize(record).and_then(|mut record| {
+        if let serde_json::Value::Object(fields) = &mut record {
+            for field in record_type.omitted_fields() {
+                fields.remove(*field);
+            }
+        }
+
+        serde_json::to_value(ChangeEventPayload {
+            version: CHANGE_EVENT_VERSION,
+            operation,
+            storage_scheme,
+            published_at: common_utils::date_time::now(),
+            record,
+        })
+    });
+
+    match payload {
+        Ok(payload) => Some(ChangeEvent {
+            record_type,
+            key,
+            payload,
+        }),
+        Err(error) => {
+            logger::error!(?error, ?record_type, %key, "Failed to serialize change data event");
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    #![allow(clippy::unwrap_used)]
+    use masking::Secret;
+    use serde_json::json;
+
+    use super::*;
+
+    #[derive(Serialize)]
+    struct Record {
+        id: String,
+        secret: Secret<serde_json::Value>,
+    }
+
+    #[test]
+    fn test_build_change_event() {
+        let record = Record {
+            id: "record_1".to_string(),
+            secret: Secret::new(json!("hunter2")),
+        };
+
+        let event = build_change_event(
+            ChangeEventRecordType::Refund,
+            "record_1".to_string(),
+            ChangeOperation::Update,
+            MerchantStorageScheme::RedisKv,
+            &record,
+        )
+        .unwrap();
+
+        assert_eq!(event.payload["version"], json!(CHANGE_EVENT_VERSION));
+        assert_eq!(event.payload["operation"], json!("update"));
+        assert_eq!(event.payload["storage_scheme"], json!("redis_kv"));
+        assert_eq!(event.payload["record"]["id"], json!("record_1"));
+        assert!(!event.payload.to_string().contains("hunter2"));
+    }
+
+    #[test]
+    fn test_build_change_event_omits_client_secret() {
+        #[derive(Serialize)]
+        struct PaymentIntent {
+            payment_id: String,
+            client_secret: Option<String>,
+        }
+
+        let payment_intent = PaymentIntent {
+            payment_id: "pay_1".to_string(),
+            client_secret: Some("pay_1_secret_abc".to_string()),
+        };
+
+        let event = build_change_event(
+            ChangeEventRecordType::PaymentIntent,
+            "merchant_1_pay_1".to_string(),
+            ChangeOperation::Insert,
+            MerchantStorageScheme::PostgresOnly,
+            &payment_intent,
+        )
+        .unwrap();
+
+        assert_eq!(event.payload["record"]["payment_id"], json!("pay_1"));
+        assert!(event.payload["record"].get("client_secret").is_none());
+        assert!(!event.payload.to_string().contains("pay_1_secret_abc"));
+    }
+}
@@ -11,6 +11,7 @@ pub mod connection;
 mod connector_response;
 pub mod database;
 pub mod errors;
+pub mod events;
 mod lookup;
 pub mod metrics;
 pub mod mock_db;
@@ -23,14 +24,36 @@ mod utils;
 use database::store::PgPool;
 pub use mock_db::MockDb;
 use redis_interface::errors::RedisError;
+use router_env::logger;
 
-pub use crate::database::store::DatabaseStore;
+pub use crate::database::store::{DatabaseStore, ReadConsistency};
 
 #[derive(Debug, Clone)]
 pub struct RouterStore<T: DatabaseStore> {
     db_store: T,
     cache_store: RedisStore,
     master_encryption_key: StrongSecret<Vec<u8>>,
+    master_key_versions: MasterKeyVersions,
+    change_event_handler: Option<Arc<dyn events::ChangeEventHandler>>,
+}
+
+/// The version of the master encryption key, along with the other versions of the master key
+/// which merchant keys may be encrypted with while the master key is being rotated.
+#[derive(Debug, Clone)]
+pub struct MasterKeyVersions {
+    pub version: i32,
+    pub other_keys: Vec<(i32, StrongSecret<Vec<u8>>)>,
+}
+
+impl Default for MasterKeyVersions {
+    fn default() -> Self {
+        // Merchant keys encrypted with the first version of the master key are not tagged with a
+        // version, see `KeyRing` in the router
+        Self {
+            version: 1,
+            other_keys: Vec::new(),
+        }
+    }
 }
 
 #[async_trait::async_trait]
@@ -70,6 +93,13 @@ where
     fn get_replica_pool(&self) -> &PgPool {
         self.db_store.get_replica_pool()
     }
+    fn get_replica_max_staleness(&self) -> Option<std::time::Duration> {
+        self.db_store.get_replica_max_staleness()
+    }
+
+    fn get_replica_lag_sample(&self) -> Option<&database::store::ReplicaLagSample> {
+        self.db_store.get_replica_lag_sample()
+    }
 }
 
 impl<T: DatabaseStore> RedisConnInterface for RouterStore<T> {
@@ -103,6 +133,8 @@ impl<T: DatabaseStore> RouterStore<T> {
             db_store,
             cache_store,
             master_encryption_key: encryption_key,
+            master_key_versions: MasterKeyVersions::default(),
+            change_event_handler: None,
         })
     }
 
@@ -110,6 +142,57 @@ impl<T: DatabaseStore> RouterStore<T> {
         &self.master_encryption_key
     }
 
+    pub fn master_key_versions(&self) -> &MasterKeyVersions {
+        &self.master_key_versions
+    }
+
+    /// Sets the version of the master encryption key and the other versions of the master key.
+    pub fn set_master_key_versions(&mut self, master_key_versions: MasterKeyVersions) {
+        self.master_key_versions = master_key_versions;
+    }
+
+    /// Creates the store of a tenant, with its own database pools created from `db_conf`, sharing
+    /// the Redis connections of this store with all keys prefixed by `redis_key_prefix`.
+    pub async fn for_tenant(
+        &self,
+        db_conf: T::Config,
+        redis_key_prefix: &str,
+        test_transaction: bool,
+    ) -> StorageResult<Self> {
+        Ok(Self {
+            db_store: T::new(db_conf, test_transaction).await?,
+            cache_store: self.cache_store.with_key_prefix(redis_key_prefix),
+            master_encryption_key: self.master_encryption_key.clone(),
+            master_key_versions: self.master_key_versions.clone(),
+            change_event_handler: self.change_event_handler.clone(),
+        })
+    }
+
+    /// Sets the handler that change data events of payment intents, payment attempts and refunds
+    /// are published to.
+    pub fn set_change_event_handler(&mut self, handler: Arc<dyn events::ChangeEventHandler>) {
+        self.change_event_handler = Some(handler);
+    }
+
+    /// Publishes a change data event with a masked snapshot of the record, if a change event
+    /// handler is set.
+    pub fn publish_change_event<R: serde::Serialize>(
+        &self,
+        record_type: events::ChangeEventRecordType,
+        key: String,
+        operation: events::ChangeOperation,
+        storage_scheme: diesel_models::enums::MerchantStorageScheme,
+        record: &R,
+    ) {
+        if let Some(handler) = &self.change_event_handler {
+            if let Some(event) =
+                events::build_change_event(record_type, key, operation, storage_scheme, record)
+            {
+                handler.publish_change_event(event);
+            }
+        }
+    }
+
     /// # Panics
     ///
     /// Will panic if `CONNECTOR_AUTH_FILE_PATH` is not set
@@ -128,6 +211,8 @@ impl<T: DatabaseStore> RouterStore<T> {
             db_store,
             cache_store,
             master_encryption_key: encryption_key,
+            master_key_versions: MasterKeyVersions::default(),
+            change_event_handler: None,
         })
     }
 }
@@ -162,6 +247,13 @@ where
     fn get_replica_pool(&self) -> &PgPool {
         self.router_store.get_replica_pool()
     }
+    fn get_replica_max_staleness(&self) -> Option<std::time::Duration> {
+        self.router_store.get_replica_max_staleness()
+    }
+
+    fn get_replica_lag_sample(&self) -> Option<&database::store::ReplicaLagSample> {
+        self.router_store.get_replica_lag_sample()
+    }
 }
 
 impl<T: DatabaseStore> RedisConnInterface for KVRouterStore<T> {
@@ -191,6 +283,46 @@ impl<T: DatabaseStore> KVRouterStore<T> {
         self.router_store.master_key()
     }
 
+    pub fn master_key_versions(&self) -> &MasterKeyVersions {
+        self.router_store.master_key_versions()
+    }
+
+    /// See [`RouterStore::set_change_event_handler`].
+    pub fn set_change_event_handler(&mut self, handler: Arc<dyn events::ChangeEventHandler>) {
+        self.router_store.set_change_event_handler(handler);
+    }
+
+    /// See [`RouterStore::publish_change_event`].
+    pub fn publish_change_event<R: serde::Serialize>(
+        &self,
+        record_type: events::ChangeEventRecordType,
+        key: String,
+        operation: events::ChangeOperation,
+        storage_scheme: diesel_models::enums::MerchantStorageScheme,
+        record: &R,
+    ) {
+        self.router_store
+            .publish_change_event(record_type, key, operation, storage_scheme, record);
+    }
+
+    /// Creates the store of a tenant, see [`RouterStore::for_tenant`].
+    pub async fn for_tenant(
+        &self,
+        db_conf: T::Config,
+        redis_key_prefix: &str,
+        test_transaction: bool,
+    ) -> StorageResult<Self> {
+        Ok(Self {
+            router_store: self
+                .router_store
+                .for_tenant(db_conf, redis_key_prefix, test_transaction)
+                .await?,
+            drainer_stream_name: self.drainer_stream_name.clone(),
+            drainer_num_partitions: self.drainer_num_partitions,
+            ttl_for_kv: self.ttl_for_kv,
+        })
+    }
+
     pub fn get_drainer_stream_name(&self, shard_key: &str) -> String {
         format!("{{{}}}_{}", shard_key, self.drainer_stream_name)
     }
@@ -205,18 +337,41 @@ impl<T: DatabaseStore> KVRouterStore<T> {
     {
         let shard_key = R::shard_key(partition_key, self.drainer_num_partitions);
         let stream_name = self.get_drainer_stream_name(&shard_key);
-        self.router_store
-            .cache_store
-            .redis_conn
+        let redis_conn = &self.router_store.cache_store.redis_conn;
+        let field_value_pairs = redis_entry
+            .to_field_value_pairs()
+            .change_context(RedisError::JsonSerializationFailed)?;
+
+        // The pending entries of the merchant are counted before the entry is appended, so that
+        // the count never falls behind the entries in the stream. It is decremented by the drainer
+        // once the entry has been written to the database.
+        let pending_entries_key = redis_entry
+            .op
+            .merchant_id()
+            .map(store::kv::get_pending_entries_key);
+        if let Some(pending_entries_key) = pending_entries_key.as_deref() {
+            redis_conn.increment_key(pending_entries_key).await?;
+        }
+
+        let result = redis_conn
             .stream_append_entry(
                 &stream_name,
                 &redis_interface::RedisEntryId::AutoGeneratedID,
-                redis_entry
-                    .to_field_value_pairs()
-                    .change_context(RedisError::JsonSerializationFailed)?,
+                field_value_pairs,
             )
             .await
-            .change_context(RedisError::StreamAppendFailed)
+            .change_context(RedisError::StreamAppendFailed);
+
+        if let (Err(_), Some(pending_entries_key)) = (&result, pending_entries_key.as_deref()) {
+            if let Err(error) = redis_conn.increment_key_by(pending_entries_key, -1).await {
+                logger::error!(
+                    ?error,
+                    "Failed to decrement the pending drainer entries of the merchant"
+                );
+            }
+        }
+
+        result
     }
 }
 
@@ -11,7 +11,7 @@ use redis_interface::SetnxReply;
 
 use crate::{
     diesel_error_to_data_error,
-    redis::kv_store::{kv_wrapper, KvOperation},
+    redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op},
     utils::{self, try_redis_get_else_try_database_get},
     DatabaseStore, KVRouterStore, RouterStore,
 };
@@ -71,13 +71,16 @@ impl<T: DatabaseStore> ReverseLookupInterface for KVRouterStore<T> {
         new: DieselReverseLookupNew,
         storage_scheme: storage_enums::MerchantStorageScheme,
     ) -> CustomResult<DieselReverseLookup, errors::StorageError> {
+        let key = format!("reverse_lookup_{}", &new.lookup_id);
+        let storage_scheme = decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
         match storage_scheme {
             storage_enums::MerchantStorageScheme::PostgresOnly => {
                 self.router_store
                     .insert_reverse_lookup(new, storage_scheme)
                     .await
             }
-            storage_enums::MerchantStorageScheme::RedisKv => {
+            storage_enums::MerchantStorageScheme::RedisKv
+            | storage_enums::MerchantStorageScheme::RedisKvDraining => {
                 let created_rev_lookup = DieselReverseLookup {
                     lookup_id: new.lookup_id.clone(),
                     sk_id: new.sk_id.clone(),
@@ -94,7 +97,7 @@ impl<T: DatabaseStore> ReverseLookupInterface for KVRouterStore<T> {
                 match kv_wrapper::<DieselReverseLookup, _, _>(
                     self,
                     KvOperation::SetNx(&created_rev_lookup, redis_entry),
-                    format!("reverse_lookup_{}", &created_rev_lookup.lookup_id),
+                    key,
                 )
                 .await
                 .change_context(errors::StorageError::KVError)?
@@ -124,7 +127,8 @@ impl<T: DatabaseStore> ReverseLookupInterface for KVRouterStore<T> {
         };
         match storage_scheme {
             storage_enums::MerchantStorageScheme::PostgresOnly => database_call().await,
-            storage_enums::MerchantStorageScheme::RedisKv => {
+            storage_enums::MerchantStorageScheme::RedisKv
+            | storage_enums::MerchantStorageScheme::RedisKvDraining => {
                 let redis_fut = async {
                     kv_wrapper(
                         self,
@@ -8,3 +8,7 @@ counter_metric!(KV_MISS, GLOBAL_METER); // No. of KV misses
 // Metrics for KV
 counter_metric!(KV_OPERATION_SUCCESSFUL, GLOBAL_METER);
 counter_metric!(KV_OPERATION_FAILED, GLOBAL_METER);
+
+// Metrics for reads served by the replica database
+counter_metric!(REPLICA_READS, GLOBAL_METER);
+counter_metric!(STALE_REPLICA_READS_SERVED_BY_MASTER, GLOBAL_METER);
@@ -42,6 +42,9 @@ pub struct MockDb {
     pub reverse_lookups: Arc<Mutex<Vec<store::ReverseLookup>>>,
     pub payment_link: Arc<Mutex<Vec<store::payment_link::PaymentLink>>>,
     pub organizations: Arc<Mutex<Vec<store::organization::Organization>>>,
+    pub users: Arc<Mutex<Vec<store::user::User>>>,
+    pub user_roles: Arc<Mutex<Vec<store::user_role::UserRole>>>,
+    pub audit_events: Arc<Mutex<Vec<store::audit_event::AuditEvent>>>,
 }
 
 impl MockDb {
@@ -76,6 +79,9 @@ impl MockDb {
             reverse_lookups: Default::default(),
             payment_link: Default::default(),
             organizations: Default::default(),
+            users: Default::default(),
+            user_roles: Default::default(),
+            audit_events: Default::default(),
         })
     }
 }
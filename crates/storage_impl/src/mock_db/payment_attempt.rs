@@ -9,7 +9,7 @@ use data_models::{
 use diesel_models::enums as storage_enums;
 
 use super::MockDb;
-use crate::DataModelExt;
+use crate::{DataModelExt, ReadConsistency};
 
 #[async_trait::async_trait]
 impl PaymentAttemptInterface for MockDb {
@@ -29,6 +29,7 @@ impl PaymentAttemptInterface for MockDb {
         _pi: &[data_models::payments::PaymentIntent],
         _merchant_id: &str,
         _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
     ) -> CustomResult<data_models::payments::payment_attempt::PaymentListFilters, StorageError>
     {
         Err(StorageError::MockDbError)?
@@ -36,13 +37,14 @@ impl PaymentAttemptInterface for MockDb {
 
     async fn get_total_count_of_filtered_payment_attempts(
         &self,
-        _merchant_id: &str,
+        _merchant_ids: &[String],
         _active_attempt_ids: &[String],
         _connector: Option<Vec<Connector>>,
         _payment_method: Option<Vec<PaymentMethod>>,
         _payment_method_type: Option<Vec<PaymentMethodType>>,
         _authentication_type: Option<Vec<AuthenticationType>>,
         _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
     ) -> CustomResult<i64, StorageError> {
         Err(StorageError::MockDbError)?
     }
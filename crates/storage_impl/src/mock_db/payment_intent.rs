@@ -11,7 +11,7 @@ use diesel_models::enums as storage_enums;
 use error_stack::{IntoReport, ResultExt};
 
 use super::MockDb;
-use crate::DataModelExt;
+use crate::{DataModelExt, ReadConsistency};
 
 #[async_trait::async_trait]
 impl PaymentIntentInterface for MockDb {
@@ -21,6 +21,7 @@ impl PaymentIntentInterface for MockDb {
         _merchant_id: &str,
         _filters: &data_models::payments::payment_intent::PaymentIntentFetchConstraints,
         _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
     ) -> CustomResult<Vec<PaymentIntent>, StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(StorageError::MockDbError)?
@@ -31,6 +32,7 @@ impl PaymentIntentInterface for MockDb {
         _merchant_id: &str,
         _time_range: &api_models::payments::TimeRange,
         _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
     ) -> CustomResult<Vec<PaymentIntent>, StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(StorageError::MockDbError)?
@@ -38,19 +40,66 @@ impl PaymentIntentInterface for MockDb {
     #[cfg(feature = "olap")]
     async fn get_filtered_active_attempt_ids_for_total_count(
         &self,
-        _merchant_id: &str,
+        _merchant_ids: &[String],
         _constraints: &data_models::payments::payment_intent::PaymentIntentFetchConstraints,
         _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<String>, StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(StorageError::MockDbError)?
     }
     #[cfg(feature = "olap")]
+    async fn get_payment_intent_aggregates(
+        &self,
+        merchant_ids: &[String],
+        time_range: &api_models::payments::TimeRange,
+        _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
+    ) -> error_stack::Result<
+        Vec<data_models::payments::payment_intent::PaymentIntentAggregate>,
+        StorageError,
+    > {
+        let payment_intents = self.payment_intents.lock().await;
+        let mut aggregates: Vec<data_models::payments::payment_intent::PaymentIntentAggregate> =
+            Vec::new();
+
+        for payment_intent in payment_intents.iter().filter(|payment_intent| {
+            merchant_ids.contains(&payment_intent.merchant_id)
+                && payment_intent.created_at >= time_range.start_time
+                && time_range
+                    .end_time
+                    .map_or(true, |end_time| payment_intent.created_at <= end_time)
+        }) {
+            match aggregates.iter_mut().find(|aggregate| {
+                aggregate.merchant_id == payment_intent.merchant_id
+                    && aggregate.status == payment_intent.status
+                    && aggregate.currency == payment_intent.currency
+            }) {
+                Some(aggregate) => {
+                    aggregate.count += 1;
+                    aggregate.total_amount += payment_intent.amount;
+                }
+                None => aggregates.push(
+                    data_models::payments::payment_intent::PaymentIntentAggregate {
+                        merchant_id: payment_intent.merchant_id.clone(),
+                        status: payment_intent.status,
+                        currency: payment_intent.currency,
+                        count: 1,
+                        total_amount: payment_intent.amount,
+                    },
+                ),
+            }
+        }
+
+        Ok(aggregates)
+    }
+    #[cfg(feature = "olap")]
     async fn get_filtered_payment_intents_attempt(
         &self,
-        _merchant_id: &str,
+        _merchant_ids: &[String],
         _constraints: &data_models::payments::payment_intent::PaymentIntentFetchConstraints,
         _storage_scheme: storage_enums::MerchantStorageScheme,
+        _consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<(PaymentIntent, PaymentAttempt)>, StorageError> {
         // [#172]: Implement function for `MockDb`
         Err(StorageError::MockDbError)?
@@ -29,10 +29,14 @@ use router_env::{instrument, tracing};
 
 use crate::{
     diesel_error_to_data_error,
+    events::{ChangeEventRecordType, ChangeOperation},
     lookup::ReverseLookupInterface,
-    redis::kv_store::{kv_wrapper, KvOperation},
-    utils::{pg_connection_read, pg_connection_write, try_redis_get_else_try_database_get},
-    DataModelExt, DatabaseStore, KVRouterStore, RouterStore,
+    redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op},
+    utils::{
+        pg_connection_read, pg_connection_read_with_consistency, pg_connection_write,
+        try_redis_get_else_try_database_get,
+    },
+    DataModelExt, DatabaseStore, KVRouterStore, ReadConsistency, RouterStore,
 };
 
 #[async_trait::async_trait]
@@ -41,18 +45,25 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
     async fn insert_payment_attempt(
         &self,
         payment_attempt: PaymentAttemptNew,
-        _storage_scheme: MerchantStorageScheme,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, errors::StorageError> {
         let conn = pg_connection_write(self).await?;
-        payment_attempt
+        let payment_attempt = payment_attempt
             .to_storage_model()
             .insert(&conn)
             .await
             .map_err(|er| {
                 let new_err = diesel_error_to_data_error(er.current_context());
                 er.change_context(new_err)
-            })
-            .map(PaymentAttempt::from_storage_model)
+            })?;
+
+        publish_payment_attempt_change_event(
+            self,
+            ChangeOperation::Insert,
+            storage_scheme,
+            &payment_attempt,
+        );
+        Ok(PaymentAttempt::from_storage_model(payment_attempt))
     }
 
     #[instrument(skip_all)]
@@ -60,17 +71,25 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
         &self,
         this: PaymentAttempt,
         payment_attempt: PaymentAttemptUpdate,
-        _storage_scheme: MerchantStorageScheme,
+        storage_scheme: MerchantStorageScheme,
     ) -> CustomResult<PaymentAttempt, errors::StorageError> {
         let conn = pg_connection_write(self).await?;
-        this.to_storage_model()
+        let payment_attempt = this
+            .to_storage_model()
             .update_with_attempt_id(&conn, payment_attempt.to_storage_model())
             .await
             .map_err(|er| {
                 let new_err = diesel_error_to_data_error(er.current_context());
                 er.change_context(new_err)
-            })
-            .map(PaymentAttempt::from_storage_model)
+            })?;
+
+        publish_payment_attempt_change_event(
+            self,
+            ChangeOperation::Update,
+            storage_scheme,
+            &payment_attempt,
+        );
+        Ok(PaymentAttempt::from_storage_model(payment_attempt))
     }
 
     async fn find_payment_attempt_by_connector_transaction_id_payment_id_merchant_id(
@@ -164,8 +183,9 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
         pi: &[PaymentIntent],
         merchant_id: &str,
         _storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> CustomResult<PaymentListFilters, errors::StorageError> {
-        let conn = pg_connection_read(self).await?;
+        let conn = pg_connection_read_with_consistency(self, consistency).await?;
         let intents = pi
             .iter()
             .cloned()
@@ -256,21 +276,16 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
 
     async fn get_total_count_of_filtered_payment_attempts(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         active_attempt_ids: &[String],
         connector: Option<Vec<Connector>>,
         payment_method: Option<Vec<PaymentMethod>>,
         payment_method_type: Option<Vec<PaymentMethodType>>,
         authentication_type: Option<Vec<AuthenticationType>>,
         _storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> CustomResult<i64, errors::StorageError> {
-        let conn = self
-            .db_store
-            .get_replica_pool()
-            .get()
-            .await
-            .into_report()
-            .change_context(errors::StorageError::DatabaseConnectionError)?;
+        let conn = pg_connection_read_with_consistency(self, consistency).await?;
         let connector_strings = connector.as_ref().map(|connector| {
             connector
                 .iter()
@@ -279,7 +294,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for RouterStore<T> {
         });
         DieselPaymentAttempt::get_total_count_of_attempts(
             &conn,
-            merchant_id,
+            merchant_ids,
             active_attempt_ids,
             connector_strings,
             payment_method,
@@ -302,18 +317,18 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         payment_attempt: PaymentAttemptNew,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
+        let key = format!(
+            "mid_{}_pid_{}",
+            payment_attempt.merchant_id, payment_attempt.payment_id
+        );
+        let storage_scheme = decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
         match storage_scheme {
             MerchantStorageScheme::PostgresOnly => {
                 self.router_store
                     .insert_payment_attempt(payment_attempt, storage_scheme)
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
-                let key = format!(
-                    "mid_{}_pid_{}",
-                    payment_attempt.merchant_id, payment_attempt.payment_id
-                );
-
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let created_attempt = PaymentAttempt {
                     id: Default::default(),
                     payment_id: payment_attempt.payment_id.clone(),
@@ -376,13 +391,11 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     },
                 };
 
+                let diesel_attempt = created_attempt.clone().to_storage_model();
+
                 match kv_wrapper::<PaymentAttempt, _, _>(
                     self,
-                    KvOperation::HSetNx(
-                        &field,
-                        &created_attempt.clone().to_storage_model(),
-                        redis_entry,
-                    ),
+                    KvOperation::HSetNx(&field, &diesel_attempt, redis_entry),
                     &key,
                 )
                 .await
@@ -409,6 +422,12 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                         self.insert_reverse_lookup(reverse_lookup, storage_scheme)
                             .await?;
 
+                        publish_payment_attempt_change_event(
+                            &self.router_store,
+                            ChangeOperation::Insert,
+                            storage_scheme,
+                            &diesel_attempt,
+                        );
                         Ok(created_attempt)
                     }
                     Err(error) => Err(error.change_context(errors::StorageError::KVError)),
@@ -424,22 +443,27 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         payment_attempt: PaymentAttemptUpdate,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentAttempt, errors::StorageError> {
+        let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+        let storage_scheme = decide_storage_scheme(
+            self,
+            storage_scheme,
+            Op::Update(&key, &format!("pa_{}", this.attempt_id), &this.updated_by),
+        )
+        .await;
         match storage_scheme {
             MerchantStorageScheme::PostgresOnly => {
                 self.router_store
                     .update_payment_attempt_with_attempt_id(this, payment_attempt, storage_scheme)
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
-                let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let old_connector_transaction_id = &this.connector_transaction_id;
                 let old_preprocessing_id = &this.preprocessing_step_id;
-                let updated_attempt = PaymentAttempt::from_storage_model(
-                    payment_attempt
-                        .clone()
-                        .to_storage_model()
-                        .apply_changeset(this.clone().to_storage_model()),
-                );
+                let diesel_attempt = payment_attempt
+                    .clone()
+                    .to_storage_model()
+                    .apply_changeset(this.clone().to_storage_model());
+                let updated_attempt = PaymentAttempt::from_storage_model(diesel_attempt.clone());
                 // Check for database presence as well Maybe use a read replica here ?
                 let redis_value = serde_json::to_string(&updated_attempt)
                     .into_report()
@@ -526,6 +550,12 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     (_, _) => {}
                 }
 
+                publish_payment_attempt_change_event(
+                    &self.router_store,
+                    ChangeOperation::Update,
+                    storage_scheme,
+                    &diesel_attempt,
+                );
                 Ok(updated_attempt)
             }
         }
@@ -549,7 +579,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     )
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 // We assume that PaymentAttempt <=> PaymentIntent is a one-to-one relation for now
                 let lookup_id = format!("{merchant_id}_{connector_transaction_id}");
                 let lookup = self
@@ -584,7 +614,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         };
         match storage_scheme {
             MerchantStorageScheme::PostgresOnly => database_call().await,
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
                 let pattern = "pa_*";
 
@@ -628,7 +658,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     )
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let lookup_id = format!("{merchant_id}_{connector_txn_id}");
                 let lookup = self
                     .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -679,7 +709,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     )
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
                 let field = format!("pa_{attempt_id}");
                 try_redis_get_else_try_database_get(
@@ -720,7 +750,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     )
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let lookup_id = format!("{merchant_id}_{attempt_id}");
                 let lookup = self
                     .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -767,7 +797,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     )
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let lookup_id = format!("{merchant_id}_{preprocessing_id}");
                 let lookup = self
                     .get_lookup_by_lookup_id(&lookup_id, storage_scheme)
@@ -815,7 +845,7 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
                     )
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
 
                 kv_wrapper(self, KvOperation::<DieselPaymentAttempt>::Scan("pa_*"), key)
@@ -832,36 +862,57 @@ impl<T: DatabaseStore> PaymentAttemptInterface for KVRouterStore<T> {
         pi: &[PaymentIntent],
         merchant_id: &str,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<PaymentListFilters, errors::StorageError> {
         self.router_store
-            .get_filters_for_payments(pi, merchant_id, storage_scheme)
+            .get_filters_for_payments(pi, merchant_id, storage_scheme, consistency)
             .await
     }
 
     async fn get_total_count_of_filtered_payment_attempts(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         active_attempt_ids: &[String],
         connector: Option<Vec<Connector>>,
         payment_method: Option<Vec<PaymentMethod>>,
         payment_method_type: Option<Vec<PaymentMethodType>>,
         authentication_type: Option<Vec<AuthenticationType>>,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> CustomResult<i64, errors::StorageError> {
         self.router_store
             .get_total_count_of_filtered_payment_attempts(
-                merchant_id,
+                merchant_ids,
                 active_attempt_ids,
                 connector,
                 payment_method,
                 payment_method_type,
                 authentication_type,
                 storage_scheme,
+                consistency,
             )
             .await
     }
 }
 
+fn publish_payment_attempt_change_event<T: DatabaseStore>(
+    store: &RouterStore<T>,
+    operation: ChangeOperation,
+    storage_scheme: MerchantStorageScheme,
+    payment_attempt: &DieselPaymentAttempt,
+) {
+    store.publish_change_event(
+        ChangeEventRecordType::PaymentAttempt,
+        format!(
+            "{}_{}_{}",
+            payment_attempt.merchant_id, payment_attempt.payment_id, payment_attempt.attempt_id
+        ),
+        operation,
+        storage_scheme,
+        payment_attempt,
+    );
+}
+
 impl DataModelExt for MandateAmountData {
     type StorageModel = DieselMandateAmountData;
 
@@ -872,6 +923,8 @@ impl DataModelExt for MandateAmountData {
             start_date: self.start_date,
             end_date: self.end_date,
             metadata: self.metadata,
+            frequency_limit: self.frequency_limit,
+            frequency_interval: self.frequency_interval,
         }
     }
 
@@ -882,6 +935,8 @@ impl DataModelExt for MandateAmountData {
             start_date: storage_model.start_date,
             end_date: storage_model.end_date,
             metadata: storage_model.metadata,
+            frequency_limit: storage_model.frequency_limit,
+            frequency_interval: storage_model.frequency_interval,
         }
     }
 }
@@ -2,7 +2,9 @@
 use async_bb8_diesel::{AsyncConnection, AsyncRunQueryDsl};
 use common_utils::{date_time, ext_traits::Encode};
 #[cfg(feature = "olap")]
-use data_models::payments::payment_intent::PaymentIntentFetchConstraints;
+use data_models::payments::payment_intent::{
+    PaymentIntentAggregate, PaymentIntentFetchConstraints,
+};
 use data_models::{
     errors::StorageError,
     payments::{
@@ -35,10 +37,11 @@ use router_env::logger;
 use router_env::{instrument, tracing};
 
 #[cfg(feature = "olap")]
-use crate::connection;
+use crate::{connection, ReadConsistency};
 use crate::{
     diesel_error_to_data_error,
-    redis::kv_store::{kv_wrapper, KvOperation},
+    events::{ChangeEventRecordType, ChangeOperation},
+    redis::kv_store::{decide_storage_scheme, kv_wrapper, KvOperation, Op},
     utils::{pg_connection_read, pg_connection_write},
     DataModelExt, DatabaseStore, KVRouterStore,
 };
@@ -50,6 +53,8 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         new: PaymentIntentNew,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentIntent, StorageError> {
+        let key = format!("mid_{}_pid_{}", new.merchant_id, new.payment_id);
+        let storage_scheme = decide_storage_scheme(self, storage_scheme, Op::Insert(&key)).await;
         match storage_scheme {
             MerchantStorageScheme::PostgresOnly => {
                 self.router_store
@@ -57,8 +62,7 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                     .await
             }
 
-            MerchantStorageScheme::RedisKv => {
-                let key = format!("mid_{}_pid_{}", new.merchant_id, new.payment_id);
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let field = format!("pi_{}", new.payment_id);
                 let created_intent = PaymentIntent {
                     id: 0i32,
@@ -104,13 +108,11 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                     },
                 };
 
+                let diesel_intent = created_intent.clone().to_storage_model();
+
                 match kv_wrapper::<DieselPaymentIntent, _, _>(
                     self,
-                    KvOperation::<DieselPaymentIntent>::HSetNx(
-                        &field,
-                        &created_intent.clone().to_storage_model(),
-                        redis_entry,
-                    ),
+                    KvOperation::<DieselPaymentIntent>::HSetNx(&field, &diesel_intent, redis_entry),
                     &key,
                 )
                 .await
@@ -122,7 +124,15 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                         key: Some(key),
                     })
                     .into_report(),
-                    Ok(HsetnxReply::KeySet) => Ok(created_intent),
+                    Ok(HsetnxReply::KeySet) => {
+                        publish_payment_intent_change_event(
+                            &self.router_store,
+                            ChangeOperation::Insert,
+                            storage_scheme,
+                            &diesel_intent,
+                        );
+                        Ok(created_intent)
+                    }
                     Err(error) => Err(error.change_context(StorageError::KVError)),
                 }
             }
@@ -136,16 +146,21 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         payment_intent_update: PaymentIntentUpdate,
         storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentIntent, StorageError> {
+        let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
+        let field = format!("pi_{}", this.payment_id);
+        let storage_scheme = decide_storage_scheme(
+            self,
+            storage_scheme,
+            Op::Update(&key, &field, &this.updated_by),
+        )
+        .await;
         match storage_scheme {
             MerchantStorageScheme::PostgresOnly => {
                 self.router_store
                     .update_payment_intent(this, payment_intent_update, storage_scheme)
                     .await
             }
-            MerchantStorageScheme::RedisKv => {
-                let key = format!("mid_{}_pid_{}", this.merchant_id, this.payment_id);
-                let field = format!("pi_{}", this.payment_id);
-
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let diesel_intent_update = payment_intent_update.to_storage_model();
                 let origin_diesel_intent = this.to_storage_model();
 
@@ -179,6 +194,12 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
                 .try_into_hset()
                 .change_context(StorageError::KVError)?;
 
+                publish_payment_intent_change_event(
+                    &self.router_store,
+                    ChangeOperation::Update,
+                    storage_scheme,
+                    &diesel_intent,
+                );
                 Ok(PaymentIntent::from_storage_model(diesel_intent))
             }
         }
@@ -203,7 +224,7 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         match storage_scheme {
             MerchantStorageScheme::PostgresOnly => database_call().await,
 
-            MerchantStorageScheme::RedisKv => {
+            MerchantStorageScheme::RedisKv | MerchantStorageScheme::RedisKvDraining => {
                 let key = format!("mid_{merchant_id}_pid_{payment_id}");
                 let field = format!("pi_{payment_id}");
                 crate::utils::try_redis_get_else_try_database_get(
@@ -257,9 +278,10 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         merchant_id: &str,
         filters: &PaymentIntentFetchConstraints,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
         self.router_store
-            .filter_payment_intent_by_constraints(merchant_id, filters, storage_scheme)
+            .filter_payment_intent_by_constraints(merchant_id, filters, storage_scheme, consistency)
             .await
     }
 
@@ -269,12 +291,14 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
         merchant_id: &str,
         time_range: &api_models::payments::TimeRange,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
         self.router_store
             .filter_payment_intents_by_time_range_constraints(
                 merchant_id,
                 time_range,
                 storage_scheme,
+                consistency,
             )
             .await
     }
@@ -282,30 +306,51 @@ impl<T: DatabaseStore> PaymentIntentInterface for KVRouterStore<T> {
     #[cfg(feature = "olap")]
     async fn get_filtered_payment_intents_attempt(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         filters: &PaymentIntentFetchConstraints,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<(PaymentIntent, PaymentAttempt)>, StorageError> {
         self.router_store
-            .get_filtered_payment_intents_attempt(merchant_id, filters, storage_scheme)
+            .get_filtered_payment_intents_attempt(
+                merchant_ids,
+                filters,
+                storage_scheme,
+                consistency,
+            )
             .await
     }
 
     #[cfg(feature = "olap")]
     async fn get_filtered_active_attempt_ids_for_total_count(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         constraints: &PaymentIntentFetchConstraints,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<String>, StorageError> {
         self.router_store
             .get_filtered_active_attempt_ids_for_total_count(
-                merchant_id,
+                merchant_ids,
                 constraints,
                 storage_scheme,
+                consistency,
             )
             .await
     }
+
+    #[cfg(feature = "olap")]
+    async fn get_payment_intent_aggregates(
+        &self,
+        merchant_ids: &[String],
+        time_range: &api_models::payments::TimeRange,
+        storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
+    ) -> error_stack::Result<Vec<PaymentIntentAggregate>, StorageError> {
+        self.router_store
+            .get_payment_intent_aggregates(merchant_ids, time_range, storage_scheme, consistency)
+            .await
+    }
 }
 
 #[async_trait::async_trait]
@@ -313,34 +358,46 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
     async fn insert_payment_intent(
         &self,
         new: PaymentIntentNew,
-        _storage_scheme: MerchantStorageScheme,
+        storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentIntent, StorageError> {
         let conn = pg_connection_write(self).await?;
-        new.to_storage_model()
-            .insert(&conn)
-            .await
-            .map_err(|er| {
-                let new_err = diesel_error_to_data_error(er.current_context());
-                er.change_context(new_err)
-            })
-            .map(PaymentIntent::from_storage_model)
+        let payment_intent = new.to_storage_model().insert(&conn).await.map_err(|er| {
+            let new_err = diesel_error_to_data_error(er.current_context());
+            er.change_context(new_err)
+        })?;
+
+        publish_payment_intent_change_event(
+            self,
+            ChangeOperation::Insert,
+            storage_scheme,
+            &payment_intent,
+        );
+        Ok(PaymentIntent::from_storage_model(payment_intent))
     }
 
     async fn update_payment_intent(
         &self,
         this: PaymentIntent,
         payment_intent: PaymentIntentUpdate,
-        _storage_scheme: MerchantStorageScheme,
+        storage_scheme: MerchantStorageScheme,
     ) -> error_stack::Result<PaymentIntent, StorageError> {
         let conn = pg_connection_write(self).await?;
-        this.to_storage_model()
+        let payment_intent = this
+            .to_storage_model()
             .update(&conn, payment_intent.to_storage_model())
             .await
             .map_err(|er| {
                 let new_err = diesel_error_to_data_error(er.current_context());
                 er.change_context(new_err)
-            })
-            .map(PaymentIntent::from_storage_model)
+            })?;
+
+        publish_payment_intent_change_event(
+            self,
+            ChangeOperation::Update,
+            storage_scheme,
+            &payment_intent,
+        );
+        Ok(PaymentIntent::from_storage_model(payment_intent))
     }
 
     #[instrument(skip_all)]
@@ -393,10 +450,13 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
         merchant_id: &str,
         filters: &PaymentIntentFetchConstraints,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
         use common_utils::errors::ReportSwitchExt;
 
-        let conn = connection::pg_connection_read(self).await.switch()?;
+        let conn = connection::pg_connection_read_with_consistency(self, consistency)
+            .await
+            .switch()?;
         let conn = async_bb8_diesel::Connection::as_async_conn(&conn);
 
         //[#350]: Replace this with Boxable Expression and pass it into generic filter
@@ -505,30 +565,39 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
         merchant_id: &str,
         time_range: &api_models::payments::TimeRange,
         storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<PaymentIntent>, StorageError> {
         // TODO: Remove this redundant function
         let payment_filters = (*time_range).into();
-        self.filter_payment_intent_by_constraints(merchant_id, &payment_filters, storage_scheme)
-            .await
+        self.filter_payment_intent_by_constraints(
+            merchant_id,
+            &payment_filters,
+            storage_scheme,
+            consistency,
+        )
+        .await
     }
 
     #[cfg(feature = "olap")]
     async fn get_filtered_payment_intents_attempt(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         constraints: &PaymentIntentFetchConstraints,
-        storage_scheme: MerchantStorageScheme,
+        _storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<(PaymentIntent, PaymentAttempt)>, StorageError> {
         use common_utils::errors::ReportSwitchExt;
 
-        let conn = connection::pg_connection_read(self).await.switch()?;
+        let conn = connection::pg_connection_read_with_consistency(self, consistency)
+            .await
+            .switch()?;
         let conn = async_bb8_diesel::Connection::as_async_conn(&conn);
         let mut query = DieselPaymentIntent::table()
             .inner_join(
                 diesel_models::schema::payment_attempt::table
                     .on(pa_dsl::attempt_id.eq(pi_dsl::active_attempt_id)),
             )
-            .filter(pi_dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(pi_dsl::merchant_id.eq_any(merchant_ids.to_owned()))
             .order(pi_dsl::created_at.desc())
             .into_boxed();
 
@@ -552,15 +621,13 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                 query = match (params.starting_at, &params.starting_after_id) {
                     (Some(starting_at), _) => query.filter(pi_dsl::created_at.ge(starting_at)),
                     (None, Some(starting_after_id)) => {
-                        // TODO: Fetch partial columns for this query since we only need some columns
-                        let starting_at = self
-                            .find_payment_intent_by_payment_id_merchant_id(
-                                starting_after_id,
-                                merchant_id,
-                                storage_scheme,
-                            )
-                            .await?
-                            .created_at;
+                        let starting_at = find_payment_created_at(
+                            self,
+                            starting_after_id,
+                            merchant_ids,
+                            consistency,
+                        )
+                        .await?;
                         query.filter(pi_dsl::created_at.ge(starting_at))
                     }
                     (None, None) => query,
@@ -569,15 +636,13 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
                 query = match (params.ending_at, &params.ending_before_id) {
                     (Some(ending_at), _) => query.filter(pi_dsl::created_at.le(ending_at)),
                     (None, Some(ending_before_id)) => {
-                        // TODO: Fetch partial columns for this query since we only need some columns
-                        let ending_at = self
-                            .find_payment_intent_by_payment_id_merchant_id(
-                                ending_before_id,
-                                merchant_id,
-                                storage_scheme,
-                            )
-                            .await?
-                            .created_at;
+                        let ending_at = find_payment_created_at(
+                            self,
+                            ending_before_id,
+                            merchant_ids,
+                            consistency,
+                        )
+                        .await?;
                         query.filter(pi_dsl::created_at.le(ending_at))
                     }
                     (None, None) => query,
@@ -654,17 +719,20 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
     #[cfg(feature = "olap")]
     async fn get_filtered_active_attempt_ids_for_total_count(
         &self,
-        merchant_id: &str,
+        merchant_ids: &[String],
         constraints: &PaymentIntentFetchConstraints,
         _storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
     ) -> error_stack::Result<Vec<String>, StorageError> {
         use common_utils::errors::ReportSwitchExt;
 
-        let conn = connection::pg_connection_read(self).await.switch()?;
+        let conn = connection::pg_connection_read_with_consistency(self, consistency)
+            .await
+            .switch()?;
         let conn = async_bb8_diesel::Connection::as_async_conn(&conn);
         let mut query = DieselPaymentIntent::table()
             .select(pi_dsl::active_attempt_id)
-            .filter(pi_dsl::merchant_id.eq(merchant_id.to_owned()))
+            .filter(pi_dsl::merchant_id.eq_any(merchant_ids.to_owned()))
             .order(pi_dsl::created_at.desc())
             .into_boxed();
 
@@ -716,6 +784,89 @@ impl<T: DatabaseStore> PaymentIntentInterface for crate::RouterStore<T> {
         })
         .attach_printable_lazy(|| "Error filtering records by predicate")
     }
+
+    #[cfg(feature = "olap")]
+    async fn get_payment_intent_aggregates(
+        &self,
+        merchant_ids: &[String],
+        time_range: &api_models::payments::TimeRange,
+        _storage_scheme: MerchantStorageScheme,
+        consistency: ReadConsistency,
+    ) -> error_stack::Result<Vec<PaymentIntentAggregate>, StorageError> {
+        use common_utils::errors::ReportSwitchExt;
+        use diesel::dsl::{count_star, sql};
+
+        let conn = connection::pg_connection_read_with_consistency(self, consistency)
+            .await
+            .switch()?;
+        let conn = async_bb8_diesel::Connection::as_async_conn(&conn);
+        let mut query = DieselPaymentIntent::table()
+            .filter(pi_dsl::merchant_id.eq_any(merchant_ids.to_owned()))
+            .filter(pi_dsl::created_at.ge(time_range.start_time))
+            .group_by((pi_dsl::merchant_id, pi_dsl::status, pi_dsl::currency))
+            .select((
+                pi_dsl::merchant_id,
+                pi_dsl::status,
+                pi_dsl::currency,
+                count_star(),
+                sql::<diesel::sql_types::BigInt>("CAST(COALESCE(SUM(amount), 0) AS BIGINT)"),
+            ))
+            .into_boxed();
+
+        if let Some(end_time) = time_range.end_time {
+            query = query.filter(pi_dsl::created_at.le(end_time));
+        }
+
+        db_metrics::track_database_call::<<DieselPaymentIntent as HasTable>::Table, _, _>(
+            query.get_results_async::<(
+                String,
+                diesel_models::enums::IntentStatus,
+                Option<diesel_models::enums::Currency>,
+                i64,
+                i64,
+            )>(conn),
+            db_metrics::DatabaseOperation::Filter,
+        )
+        .await
+        .into_report()
+        .map_err(|er| {
+            let new_err = StorageError::DatabaseError(format!("{er:?}"));
+            er.change_context(new_err)
+        })
+        .attach_printable_lazy(|| "Error aggregating payment intents")
+        .map(|aggregates| {
+            aggregates
+                .into_iter()
+                .map(|(merchant_id, status, currency, count, total_amount)| {
+                    PaymentIntentAggregate {
+                        merchant_id,
+                        status,
+                        currency,
+                        count,
+                        total_amount,
+                    }
+                })
+                .collect()
+        })
+    }
+}
+
+fn publish_payment_intent_change_event<T: DatabaseStore>(
+    store: &crate::RouterStore<T>,
+    operation: ChangeOperation,
+    storage_scheme: MerchantStorageScheme,
+    payment_intent: &DieselPaymentIntent,
+) {
+    store.publish_change_event(
+        ChangeEventRecordType::PaymentIntent,
+        format!(
+            "{}_{}",
+            payment_intent.merchant_id, payment_intent.payment_id
+        ),
+        operation,
+        storage_scheme,
+        payment_intent,
+    );
 }
 
 impl DataModelExt for PaymentIntentNew {
@@ -1028,3 +1179,37 @@ impl DataModelExt for PaymentIntentUpdate {
         todo!("Reverse map should no longer be needed")
     }
 }
+
+/// Finds when the payment a list of payments starts after or ends before was created. The payment
+/// may belong to any of the merchants the payments are listed for.
+#[cfg(feature = "olap")]
+async fn find_payment_created_at<T: DatabaseStore>(
+    store: &crate::RouterStore<T>,
+    payment_id: &str,
+    merchant_ids: &[String],
+    consistency: ReadConsistency,
+) -> error_stack::Result<time::PrimitiveDateTime, StorageError> {
+    use common_utils::errors::ReportSwitchExt;
+
+    let conn = connection::pg_connection_read_with_consistency(store, consistency)
+        .await
+        .switch()?;
+    let conn = async_bb8_diesel::Connection::as_async_conn(&conn);
+    DieselPaymentIntent::table()
+        .select(pi_dsl::created_at)
+        .filter(pi_dsl::payment_id.eq(payment_id.to_owned()))
+        .filter(pi_dsl::merchant_id.eq_any(merchant_ids.to_owned()))
+        .get_results_async::<time::PrimitiveDateTime>(conn)
+        .await
+        .into_report()
+        .map_err(|er| {
+            let new_er = StorageError::DatabaseError(format!("{er:?}"));
+            er.change_context(new_er)
+        })?
+        .into_iter()
+        .next()
+        .ok_or(StorageError::ValueNotFound(format!(
+            "Payment with id {payment_id} does not exist"
+        )))
+        .into_report()
+}
@@ -33,6 +33,14 @@ impl RedisStore {
         })
     }
 
+    /// Creates a store sharing the Redis connections of this store, which prefixes all keys with
+    /// the specified key prefix.
+    pub fn with_key_prefix(&self, key_prefix: &str) -> Self {
+        Self {
+            redis_conn: Arc::new(self.redis_conn.with_key_prefix(key_prefix)),
+        }
+    }
+
     pub fn set_error_callback(&self, callback: tokio::sync::oneshot::Sender<()>) {
         let redis_clone = self.redis_conn.clone();
         tokio::spawn(async move {
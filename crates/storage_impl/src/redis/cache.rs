@@ -188,12 +188,22 @@ where
     F: FnOnce() -> Fut + Send,
     Fut: futures::Future<Output = CustomResult<T, StorageError>> + Send,
 {
-    let cache_val = cache.get_val::<T>(key);
+    // The in-memory caches are shared by all stores in the process, hence entries are keyed by the
+    // prefixed Redis key to keep the entries of different tenants apart.
+    let cache_key = store
+        .get_redis_conn()
+        .map_err(|er| {
+            let error = format!("{}", er);
+            er.change_context(StorageError::RedisError(error))
+        })
+        .attach_printable("Failed to get redis connection")?
+        .add_prefix(key);
+    let cache_val = cache.get_val::<T>(&cache_key);
     if let Some(val) = cache_val {
         Ok(val)
     } else {
         let val = get_or_populate_redis(store, key, fun).await?;
-        cache.push(key.to_string(), val.clone()).await;
+        cache.push(cache_key, val.clone()).await;
         Ok(val)
     }
 }
@@ -209,7 +219,6 @@ where
     Fut: futures::Future<Output = CustomResult<T, StorageError>> + Send,
 {
     let data = fun().await?;
-    in_memory.async_map(|cache| cache.invalidate(key)).await;
 
     let redis_conn = store
         .get_redis_conn()
@@ -219,6 +228,11 @@ where
         })
         .attach_printable("Failed to get redis connection")?;
 
+    let cache_key = redis_conn.add_prefix(key);
+    in_memory
+        .async_map(|cache| cache.invalidate(&cache_key))
+        .await;
+
     redis_conn
         .delete_key(key)
         .await
@@ -1,12 +1,13 @@
 use std::{fmt::Debug, sync::Arc};
 
 use common_utils::errors::CustomResult;
+use diesel_models::enums::MerchantStorageScheme;
 use redis_interface::errors::RedisError;
 use router_derive::TryGetEnumVariant;
 use router_env::logger;
 use serde::de;
 
-use crate::{metrics, store::kv::TypedSql, KVRouterStore};
+use crate::{metrics, store::kv::TypedSql, DatabaseStore, KVRouterStore};
 
 pub trait KvStorePartition {
     fn partition_number(key: PartitionKey<'_>, num_partitions: u8) -> u32 {
@@ -86,6 +87,59 @@ where
     }
 }
 
+/// An operation on an entry, for which the storage scheme is decided using
+/// [`decide_storage_scheme`]
+pub enum Op<'a> {
+    /// Inserting an entry under the key
+    Insert(&'a str),
+    /// Updating the entry in the field of the hash under the key, the entry having last been
+    /// written using the given storage scheme
+    Update(&'a str, &'a str, &'a str),
+}
+
+/// Decides the storage scheme an operation is performed with.
+///
+/// While KV is being disabled for a merchant, new entries are written to the database, while
+/// reads keep consulting both Redis and the database. Entries which still live in Redis, and
+/// entries added to them, keep being written through Redis, so that the drainer does not
+/// overwrite their later changes made to the database.
+pub async fn decide_storage_scheme<T: DatabaseStore>(
+    store: &KVRouterStore<T>,
+    storage_scheme: MerchantStorageScheme,
+    operation: Op<'_>,
+) -> MerchantStorageScheme {
+    if storage_scheme != MerchantStorageScheme::RedisKvDraining {
+        return storage_scheme;
+    }
+
+    let redis_conn = match store.get_redis_conn() {
+        Ok(redis_conn) => redis_conn,
+        Err(error) => {
+            logger::error!(?error, "Failed to get redis connection");
+            return MerchantStorageScheme::RedisKv;
+        }
+    };
+    let exists_in_redis = match operation {
+        Op::Update(_, _, updated_by)
+            if updated_by == MerchantStorageScheme::PostgresOnly.to_string() =>
+        {
+            return MerchantStorageScheme::PostgresOnly
+        }
+        Op::Insert(key) => redis_conn.exists::<String>(key).await,
+        Op::Update(key, field, _) => redis_conn.check_hash_field_exists(key, field).await,
+    };
+
+    match exists_in_redis {
+        Ok(false) => MerchantStorageScheme::PostgresOnly,
+        Ok(true) => MerchantStorageScheme::RedisKv,
+        // Writing through Redis is consistent with reads either way, as reads consult Redis first
+        Err(error) => {
+            logger::error!(?error, "Failed to check the presence of the entry in redis");
+            MerchantStorageScheme::RedisKv
+        }
+    }
+}
+
 pub async fn kv_wrapper<'a, T, D, S>(
     store: &KVRouterStore<D>,
     op: KvOperation<'a, S>,
@@ -37,6 +37,14 @@ impl PubSubInterface for redis_interface::RedisConnectionPool {
         channel: &str,
         key: CacheKind<'a>,
     ) -> error_stack::Result<usize, redis_errors::RedisError> {
+        // Subscribers invalidate the cached entries and delete the Redis keys as received, hence
+        // the keys are published with the key prefix of the connection pool.
+        let key = match key {
+            CacheKind::Config(key) => CacheKind::Config(self.add_prefix(&key).into()),
+            CacheKind::Accounts(key) => CacheKind::Accounts(self.add_prefix(&key).into()),
+            CacheKind::All(key) => CacheKind::All(self.add_prefix(&key).into()),
+        };
+
         self.publisher
             .publish(channel, RedisValue::from(key).into_inner())
             .await
@@ -1,9 +1,10 @@
 use bb8::PooledConnection;
+use common_utils::errors::ReportSwitchExt;
 use data_models::errors::StorageError;
 use diesel::PgConnection;
 use error_stack::{IntoReport, ResultExt};
 
-use crate::{metrics, DatabaseStore};
+use crate::{metrics, DatabaseStore, ReadConsistency};
 
 pub async fn pg_connection_read<T: DatabaseStore>(
     store: &T,
@@ -32,6 +33,18 @@ pub async fn pg_connection_read<T: DatabaseStore>(
         .change_context(StorageError::DatabaseConnectionError)
 }
 
+pub async fn pg_connection_read_with_consistency<T: DatabaseStore>(
+    store: &T,
+    consistency: ReadConsistency,
+) -> error_stack::Result<
+    PooledConnection<'_, async_bb8_diesel::ConnectionManager<PgConnection>>,
+    StorageError,
+> {
+    crate::connection::pg_connection_read_with_consistency(store, consistency)
+        .await
+        .switch()
+}
+
 pub async fn pg_connection_write<T: DatabaseStore>(
     store: &T,
 ) -> error_stack::Result<
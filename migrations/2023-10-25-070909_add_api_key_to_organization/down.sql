@@ -0,0 +1,8 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS merchant_account_organization_id_index;
+
+DROP INDEX IF EXISTS organization_hashed_api_key_index;
+
+ALTER TABLE ORGANIZATION
+DROP COLUMN IF EXISTS hashed_api_key,
+DROP COLUMN IF EXISTS api_key_prefix;
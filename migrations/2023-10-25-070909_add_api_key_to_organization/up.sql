@@ -0,0 +1,8 @@
+-- Your SQL goes here
+ALTER TABLE ORGANIZATION
+ADD COLUMN IF NOT EXISTS hashed_api_key VARCHAR(128),
+ADD COLUMN IF NOT EXISTS api_key_prefix VARCHAR(16);
+
+CREATE UNIQUE INDEX IF NOT EXISTS organization_hashed_api_key_index ON ORGANIZATION (hashed_api_key);
+
+CREATE INDEX IF NOT EXISTS merchant_account_organization_id_index ON merchant_account (organization_id);
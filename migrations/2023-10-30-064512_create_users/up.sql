@@ -0,0 +1,30 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS users (
+    id SERIAL PRIMARY KEY,
+    user_id VARCHAR(64) NOT NULL UNIQUE,
+    email VARCHAR(255) NOT NULL UNIQUE,
+    name VARCHAR(255) NOT NULL,
+    password VARCHAR(255),
+    is_verified BOOLEAN NOT NULL DEFAULT FALSE,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+-- Emails are stored in lowercase, the index also rejects emails differing in case only
+CREATE UNIQUE INDEX IF NOT EXISTS users_email_lower_unique_index ON users (LOWER(email));
+
+CREATE TABLE IF NOT EXISTS user_roles (
+    id SERIAL PRIMARY KEY,
+    user_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    org_id VARCHAR(64) NOT NULL,
+    role VARCHAR(64) NOT NULL,
+    status VARCHAR(64) NOT NULL,
+    created_by VARCHAR(64) NOT NULL,
+    last_modified_by VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    CONSTRAINT user_merchant_unique UNIQUE (user_id, merchant_id)
+);
+
+CREATE INDEX IF NOT EXISTS user_roles_merchant_id_index ON user_roles (merchant_id);
@@ -0,0 +1,29 @@
+-- Your SQL goes here
+CREATE TABLE IF NOT EXISTS audit_events (
+    id SERIAL PRIMARY KEY,
+    audit_event_id VARCHAR(64) NOT NULL UNIQUE,
+    merchant_id VARCHAR(64),
+    resource_type VARCHAR(64) NOT NULL,
+    resource_id VARCHAR(255) NOT NULL,
+    action VARCHAR(64) NOT NULL,
+    auth_type VARCHAR(64) NOT NULL,
+    actor_id VARCHAR(255),
+    request_id VARCHAR(64),
+    diff JSONB NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS audit_events_merchant_id_created_at_index ON audit_events (merchant_id, created_at);
+
+CREATE INDEX IF NOT EXISTS audit_events_resource_index ON audit_events (resource_type, resource_id);
+
+-- Audit events are immutable once recorded
+CREATE OR REPLACE FUNCTION reject_audit_event_modification() RETURNS TRIGGER AS $$
+BEGIN
+    RAISE EXCEPTION 'audit events cannot be modified or deleted';
+END;
+$$ LANGUAGE plpgsql;
+
+CREATE TRIGGER audit_events_immutable
+    BEFORE UPDATE OR DELETE ON audit_events
+    FOR EACH ROW EXECUTE FUNCTION reject_audit_event_modification();
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+CREATE INDEX IF NOT EXISTS payment_attempt_merchant_id_created_at_index ON payment_attempt (merchant_id, created_at);
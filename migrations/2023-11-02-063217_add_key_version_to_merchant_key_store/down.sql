@@ -0,0 +1,4 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE merchant_key_store
+DROP COLUMN IF EXISTS key_version,
+DROP COLUMN IF EXISTS retired_keys;
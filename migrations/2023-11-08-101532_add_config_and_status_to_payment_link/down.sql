@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP INDEX IF EXISTS payment_link_merchant_id_created_at_index;
+
+ALTER TABLE payment_link
+DROP COLUMN IF EXISTS payment_link_config,
+DROP COLUMN IF EXISTS status;
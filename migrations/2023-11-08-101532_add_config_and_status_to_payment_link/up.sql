@@ -0,0 +1,6 @@
+-- Your SQL goes here
+ALTER TABLE payment_link
+ADD COLUMN IF NOT EXISTS payment_link_config JSONB,
+ADD COLUMN IF NOT EXISTS status VARCHAR(64) NOT NULL DEFAULT 'active';
+
+CREATE INDEX IF NOT EXISTS payment_link_merchant_id_created_at_index ON payment_link (merchant_id, created_at);
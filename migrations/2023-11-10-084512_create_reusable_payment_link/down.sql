@@ -0,0 +1,5 @@
+-- This file should undo anything in `up.sql`
+ALTER TABLE payment_link
+DROP COLUMN IF EXISTS reusable_payment_link_id;
+
+DROP TABLE IF EXISTS reusable_payment_link;
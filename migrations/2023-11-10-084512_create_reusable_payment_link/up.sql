@@ -0,0 +1,30 @@
+-- Your SQL goes here
+CREATE TABLE reusable_payment_link (
+    reusable_payment_link_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    link_to_pay VARCHAR(255) NOT NULL,
+    currency "Currency" NOT NULL,
+    amount INT8,
+    min_amount INT8,
+    max_amount INT8,
+    product_name VARCHAR(255) NOT NULL,
+    product_img_link TEXT,
+    description VARCHAR(255),
+    return_url TEXT,
+    merchant_custom_domain_name VARCHAR(255),
+    payment_link_config JSONB,
+    max_uses INT4,
+    times_used INT4 NOT NULL DEFAULT 0,
+    times_reserved INT4 NOT NULL DEFAULT 0,
+    amount_collected INT8 NOT NULL DEFAULT 0,
+    status VARCHAR(64) NOT NULL DEFAULT 'active',
+    link_expiry TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (reusable_payment_link_id)
+);
+
+CREATE INDEX IF NOT EXISTS reusable_payment_link_merchant_id_index ON reusable_payment_link (merchant_id);
+
+ALTER TABLE payment_link
+ADD COLUMN IF NOT EXISTS reusable_payment_link_id VARCHAR(64);
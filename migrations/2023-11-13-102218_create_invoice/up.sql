@@ -0,0 +1,25 @@
+-- Your SQL goes here
+CREATE TABLE invoice (
+    invoice_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    customer_id VARCHAR(64) NOT NULL,
+    status VARCHAR(64) NOT NULL DEFAULT 'draft',
+    currency "Currency" NOT NULL,
+    line_items JSONB NOT NULL,
+    tax_lines JSONB NOT NULL,
+    subtotal INT8 NOT NULL,
+    tax_amount INT8 NOT NULL,
+    total_amount INT8 NOT NULL,
+    description VARCHAR(255),
+    due_date TIMESTAMP NOT NULL,
+    payment_id VARCHAR(64),
+    payment_link_id VARCHAR(255),
+    metadata JSONB,
+    finalized_at TIMESTAMP,
+    paid_at TIMESTAMP,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (invoice_id)
+);
+
+CREATE INDEX IF NOT EXISTS invoice_merchant_id_payment_id_index ON invoice (merchant_id, payment_id);
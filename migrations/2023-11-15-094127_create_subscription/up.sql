@@ -0,0 +1,43 @@
+-- Your SQL goes here
+CREATE TABLE subscription_plan (
+    plan_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    name VARCHAR(255) NOT NULL,
+    description VARCHAR(255),
+    amount INT8 NOT NULL,
+    currency "Currency" NOT NULL,
+    interval VARCHAR(64) NOT NULL,
+    interval_count INT4 NOT NULL DEFAULT 1,
+    trial_period_days INT4,
+    active BOOLEAN NOT NULL DEFAULT TRUE,
+    metadata JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (plan_id)
+);
+
+CREATE INDEX IF NOT EXISTS subscription_plan_merchant_id_index ON subscription_plan (merchant_id);
+
+CREATE TABLE subscription (
+    subscription_id VARCHAR(64) NOT NULL,
+    merchant_id VARCHAR(64) NOT NULL,
+    customer_id VARCHAR(64) NOT NULL,
+    plan_id VARCHAR(64) NOT NULL,
+    mandate_id VARCHAR(64) NOT NULL,
+    status VARCHAR(64) NOT NULL DEFAULT 'active',
+    current_period_start TIMESTAMP NOT NULL,
+    current_period_end TIMESTAMP NOT NULL,
+    billing_anchor TIMESTAMP NOT NULL,
+    trial_end TIMESTAMP,
+    cancel_at_period_end BOOLEAN NOT NULL DEFAULT FALSE,
+    cancelled_at TIMESTAMP,
+    latest_payment_id VARCHAR(64),
+    failed_payment_attempts INT4 NOT NULL DEFAULT 0,
+    proration_amount INT8 NOT NULL DEFAULT 0,
+    metadata JSONB,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    last_modified_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP,
+    PRIMARY KEY (subscription_id)
+);
+
+CREATE INDEX IF NOT EXISTS subscription_merchant_id_customer_id_index ON subscription (merchant_id, customer_id);
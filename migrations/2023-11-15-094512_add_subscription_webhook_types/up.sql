@@ -0,0 +1,14 @@
+-- Your SQL goes here
+ALTER TYPE "EventClass" ADD VALUE 'subscriptions';
+
+ALTER TYPE "EventObjectType" ADD VALUE 'subscription_details';
+
+ALTER TYPE "EventType" ADD VALUE 'subscription_created';
+
+ALTER TYPE "EventType" ADD VALUE 'subscription_renewed';
+
+ALTER TYPE "EventType" ADD VALUE 'subscription_payment_failed';
+
+ALTER TYPE "EventType" ADD VALUE 'subscription_updated';
+
+ALTER TYPE "EventType" ADD VALUE 'subscription_cancelled';
@@ -0,0 +1,6 @@
+-- This file should undo anything in `up.sql`
+DROP TABLE mandate_usage;
+
+ALTER TABLE mandate
+DROP COLUMN IF EXISTS frequency_limit,
+DROP COLUMN IF EXISTS frequency_interval;
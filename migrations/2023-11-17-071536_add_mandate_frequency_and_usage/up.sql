@@ -0,0 +1,21 @@
+-- Your SQL goes here
+ALTER TABLE mandate
+ADD COLUMN IF NOT EXISTS frequency_limit INT4,
+ADD COLUMN IF NOT EXISTS frequency_interval VARCHAR(64);
+
+CREATE TABLE mandate_usage (
+    id SERIAL PRIMARY KEY,
+    merchant_id VARCHAR(64) NOT NULL,
+    mandate_id VARCHAR(64) NOT NULL,
+    payment_id VARCHAR(64) NOT NULL,
+    attempt_id VARCHAR(64) NOT NULL,
+    amount INT8 NOT NULL,
+    currency "Currency" NOT NULL,
+    connector VARCHAR(64) NOT NULL,
+    created_at TIMESTAMP NOT NULL DEFAULT now()::TIMESTAMP
+);
+
+CREATE INDEX IF NOT EXISTS mandate_usage_merchant_id_mandate_id_created_at_index ON mandate_usage (merchant_id, mandate_id, created_at);
+
+-- A payment attempt is recorded at most once, however often it is sent to the connector
+CREATE UNIQUE INDEX IF NOT EXISTS mandate_usage_merchant_id_attempt_id_index ON mandate_usage (merchant_id, attempt_id);
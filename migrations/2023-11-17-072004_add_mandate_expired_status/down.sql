@@ -0,0 +1,2 @@
+-- This file should undo anything in `up.sql`
+Select 1;
\ No newline at end of file
@@ -0,0 +1,4 @@
+-- Your SQL goes here
+ALTER TYPE "MandateStatus" ADD VALUE 'expired';
+
+ALTER TYPE "EventType" ADD VALUE 'mandate_expired';
@@ -0,0 +1,2 @@
+-- Your SQL goes here
+ALTER TYPE "MerchantStorageScheme" ADD VALUE 'redis_kv_draining';